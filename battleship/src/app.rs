@@ -0,0 +1,534 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{Core, Game, Player, Point, TerminalGuard};
+
+use crate::battleship::{Battleship, ShotResult, Side};
+use crate::battleshipui::{self, BattleshipUI, BattleshipUIAction};
+use crate::cli::Args;
+use crate::config::BattleshipConfig;
+use crate::fleet::{Fleet, Orientation, Ship, ShipKind, Shot, SIZE};
+use crate::hunter::Hunter;
+
+// how long the computer takes over its shot (it knows straight away, but
+// answering instantly makes it hard to see where it fired)
+const COMPUTER_DELAY: Duration = Duration::from_millis(600);
+
+// terminal columns each square takes up, including the space before it
+const SQUARE_COLS: usize = 2;
+
+// columns of the row numbers left of each board
+const LABEL_COLS: usize = 2;
+
+// space between the two boards
+const BOARD_GAP: &str = "    ";
+
+// lines of text around the boards (titles, column letters, gap, ships
+// afloat, games won, the computer's last shot, message)
+const STATUS_ROWS: usize = 7;
+
+// what's going on
+enum Stage {
+    /// you putting your ships down, one at a time
+    Placing {
+        fleet: Fleet,
+        orientation: Orientation,
+    },
+    Battle(Battleship),
+}
+
+/// Battleship app (your fleet while you place it, then the battle, the
+/// computer player, the score over every game so far, and UI state)
+pub struct BattleshipApp {
+    stage: Stage,
+    ui: BattleshipUI,
+    computer: Hunter,
+    rng: StdRng,        // for the seeds of the computer's fleets
+    first: Side,        // who fires first (swapping every game)
+    random_fleet: bool, // skip placing your ships
+    wins: (u32, u32),   // you and the computer's
+    report: String,     // where the computer last fired, and what it hit
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last game decided
+    over: bool,
+}
+
+impl BattleshipApp {
+    /// Game against the computer, with its fleets and shots from [seed],
+    /// and yours placed at random if [random_fleet]
+    pub fn new(seed: u64, random_fleet: bool) -> Self {
+        let mut app = Self {
+            stage: Stage::Placing {
+                fleet: Fleet::empty(),
+                orientation: Orientation::Across,
+            },
+            ui: BattleshipUI::new(),
+            computer: Hunter::new(seed),
+            rng: StdRng::seed_from_u64(seed),
+            first: Side::You,
+            random_fleet,
+            wins: (0, 0),
+            report: String::new(),
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        };
+        if random_fleet {
+            let seed = app.rng.gen();
+            app.start_battle(Fleet::random(seed));
+        }
+        app
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn is_computers_turn(&self) -> bool {
+        match &self.stage {
+            Stage::Battle(game) => game.turn() == Side::Computer && !game.is_over(),
+            Stage::Placing { .. } => false,
+        }
+    }
+
+    fn new_game(&mut self) {
+        self.first = self.first.other();
+        self.report.clear();
+        self.message = "".to_string().reset();
+        self.stage = Stage::Placing {
+            fleet: Fleet::empty(),
+            orientation: Orientation::Across,
+        };
+        if self.random_fleet {
+            let seed = self.rng.gen();
+            self.start_battle(Fleet::random(seed));
+        }
+    }
+
+    // line [yours] up against a fleet placed at random for the computer
+    fn start_battle(&mut self, yours: Fleet) {
+        let seed = self.rng.gen();
+        debug!("computer's fleet seed {seed}");
+        self.stage = Stage::Battle(Battleship::new(yours, Fleet::random(seed), self.first));
+    }
+
+    // put the next ship down at the cursor, and start the battle once
+    // they're all placed
+    fn place(&mut self) {
+        let Stage::Placing { fleet, orientation } = &mut self.stage else {
+            return;
+        };
+        if !fleet.place(self.ui.get_cursor(), *orientation) {
+            bell::ring("invalid move");
+            self.message = locale::text("battleship.blocked")
+                .to_string()
+                .with(theme::color(Role::Danger));
+            return;
+        }
+        self.message = "".to_string().reset();
+        if fleet.is_complete() {
+            let fleet = fleet.clone();
+            self.start_battle(fleet);
+        }
+    }
+
+    // place whatever's left of your fleet at random
+    fn place_rest(&mut self) {
+        let seed = self.rng.gen();
+        if let Stage::Placing { fleet, .. } = &mut self.stage {
+            fleet.place_rest(seed);
+            let fleet = fleet.clone();
+            self.message = "".to_string().reset();
+            self.start_battle(fleet);
+        }
+    }
+
+    fn fire(&mut self, p: Point) {
+        let Stage::Battle(game) = &mut self.stage else {
+            return;
+        };
+        let side = game.turn();
+        let res = game.fire(p);
+        match side {
+            Side::You => self.handle_res(p, res),
+            Side::Computer => self.report_res(p, res),
+        }
+    }
+
+    // what your shot at [p] led to
+    fn handle_res(&mut self, p: Point, res: ShotResult) {
+        let at = coordinates(p);
+        match res {
+            ShotResult::Miss => {
+                self.message = format!("{at}: {}", locale::text("battleship.miss")).reset()
+            }
+            ShotResult::Hit => {
+                self.message = format!("{at}: {}", locale::text("battleship.hit")).bold()
+            }
+            ShotResult::Sunk(kind) => {
+                bell::ring("sunk a ship");
+                let sank = locale::text("battleship.sank_theirs");
+                self.message = format!("{at}: {sank} {}!", ship_name(kind))
+                    .bold()
+                    .with(theme::color(Role::Success));
+            }
+            ShotResult::Won(_) => {
+                bell::ring("win");
+                self.wins.0 += 1;
+                self.outcome = Outcome::Won;
+                self.message = format!(
+                    "{} {}",
+                    locale::text("battleship.win"),
+                    locale::text("battleship.again")
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success));
+            }
+            ShotResult::AlreadyShot => {
+                bell::ring("invalid move");
+                self.message = locale::text("battleship.already")
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            ShotResult::GameOver => bell::ring("invalid move"),
+        }
+    }
+
+    // what the computer's shot at [p] led to
+    fn report_res(&mut self, p: Point, res: ShotResult) {
+        let what = match res {
+            ShotResult::Miss => locale::text("battleship.miss").to_string(),
+            ShotResult::Hit => locale::text("battleship.hit").to_string(),
+            ShotResult::Sunk(kind) | ShotResult::Won(kind) => {
+                let sank = locale::text("battleship.sank_yours");
+                format!("{sank} {}!", ship_name(kind))
+            }
+            ShotResult::AlreadyShot | ShotResult::GameOver => return,
+        };
+        self.report = format!(
+            "{} {}: {what}",
+            locale::text("battleship.fires"),
+            coordinates(p)
+        );
+        if let ShotResult::Won(_) = res {
+            bell::ring("lose");
+            self.wins.1 += 1;
+            self.outcome = Outcome::Lost;
+            self.message = format!(
+                "{} {}",
+                locale::text("battleship.lose"),
+                locale::text("battleship.again")
+            )
+            .bold()
+            .with(theme::color(Role::Text))
+            .on(theme::color(Role::Danger));
+        }
+    }
+
+    // (styled) string for square [p] of your own board: your ships, and
+    // the computer's shots at them, or while placing, the ship about to
+    // go down at the cursor
+    fn styled_own_square(&self, p: Point) -> StyledContent<String> {
+        let (fleet, view) = match &self.stage {
+            Stage::Placing { fleet, orientation } => {
+                if let Some(kind) = fleet.next_kind() {
+                    let ship = Ship {
+                        kind,
+                        origin: self.ui.get_cursor(),
+                        orientation: *orientation,
+                    };
+                    if ship.squares().any(|q| q == p) {
+                        let role = match fleet.fits(&ship) {
+                            true => Role::Accent,
+                            false => Role::Danger,
+                        };
+                        return "■".to_string().bold().with(theme::color(role));
+                    }
+                }
+                (fleet, None)
+            }
+            Stage::Battle(game) => {
+                let fleet = game.fleet(Side::You);
+                (fleet, fleet.view(p))
+            }
+        };
+        let styled = match view {
+            Some(shot) => styled_shot(shot),
+            None if fleet.ship_at(p).is_some() => "■".to_string().with(theme::color(Role::Text)),
+            None => "·".to_string().with(theme::color(Role::Muted)),
+        };
+        match &self.stage {
+            Stage::Battle(game) if game.last_shot(Side::Computer) == Some(p) => styled.underlined(),
+            _ => styled,
+        }
+    }
+
+    // (styled) string for square [p] of the computer's board: your shots
+    // at it (and once it's won, where the rest of its ships were). The
+    // cursor is reversed
+    fn styled_target_square(&self, p: Point) -> StyledContent<String> {
+        let Stage::Battle(game) = &self.stage else {
+            return "·".to_string().with(theme::color(Role::Muted));
+        };
+        let fleet = game.fleet(Side::Computer);
+        let styled = match fleet.view(p) {
+            Some(shot) => styled_shot(shot),
+            None if game.winner() == Some(Side::Computer) && fleet.ship_at(p).is_some() => {
+                "■".to_string().with(theme::color(Role::Muted))
+            }
+            None => "·".to_string().with(theme::color(Role::Muted)),
+        };
+        if p == self.ui.get_cursor() && game.turn() == Side::You && !game.is_over() {
+            return styled.reverse();
+        }
+        styled
+    }
+
+    /// Lines of both boards side by side (yours, then your shots at the
+    /// computer's), each a list of (styled) pieces
+    fn board_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let muted = |s: String| s.with(theme::color(Role::Muted));
+        let width = LABEL_COLS + SQUARE_COLS * SIZE;
+        let titles = [
+            locale::text("battleship.your_fleet"),
+            locale::text("battleship.your_shots"),
+        ];
+        let title = |s: &str| {
+            let pad = " ".repeat((width as u16).saturating_sub(text_width(s)) as usize);
+            format!("{s}{pad}").bold()
+        };
+        let letters: String = ('A'..).take(SIZE).map(|c| format!(" {c}")).collect();
+        let letters = format!("{}{letters}", " ".repeat(LABEL_COLS));
+
+        let mut lines = vec![
+            vec![
+                title(titles[0]),
+                BOARD_GAP.to_string().reset(),
+                title(titles[1]),
+            ],
+            vec![
+                muted(letters.clone()),
+                BOARD_GAP.to_string().reset(),
+                muted(letters),
+            ],
+        ];
+        for i in 0..SIZE {
+            let label = || muted(format!("{:>LABEL_COLS$}", i + 1));
+            let mut line = vec![label()];
+            for j in 0..SIZE {
+                line.push(" ".to_string().reset());
+                line.push(self.styled_own_square(Point(i, j)));
+            }
+            line.push(BOARD_GAP.to_string().reset());
+            line.push(label());
+            for j in 0..SIZE {
+                line.push(" ".to_string().reset());
+                line.push(self.styled_target_square(Point(i, j)));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    // the ship to place next, or the ships each side has left
+    fn status_line(&self) -> String {
+        match &self.stage {
+            Stage::Placing { fleet, .. } => match fleet.next_kind() {
+                Some(kind) => format!(
+                    "{} {} ({} {})",
+                    locale::text("battleship.place"),
+                    ship_name(kind),
+                    kind.length(),
+                    locale::text("battleship.squares")
+                ),
+                None => String::new(),
+            },
+            Stage::Battle(game) => format!(
+                "{} {} {}   {} {}",
+                locale::text("battleship.afloat"),
+                locale::text("battleship.you"),
+                game.fleet(Side::You).afloat(),
+                locale::text("battleship.computer"),
+                game.fleet(Side::Computer).afloat()
+            ),
+        }
+    }
+
+    // games won by each side
+    fn score_line(&self) -> String {
+        format!(
+            "{} {} {}   {} {}",
+            locale::text("battleship.won"),
+            locale::text("battleship.you"),
+            self.wins.0,
+            locale::text("battleship.computer"),
+            self.wins.1
+        )
+    }
+}
+
+fn styled_shot(shot: Shot) -> StyledContent<String> {
+    match shot {
+        Shot::Miss => "o".to_string().with(theme::color(Role::Text)),
+        Shot::Hit => "X".to_string().bold().with(theme::color(Role::Danger)),
+        Shot::Sunk => "#".to_string().bold().with(theme::color(Role::Danger)),
+    }
+}
+
+fn ship_name(kind: ShipKind) -> &'static str {
+    match kind {
+        ShipKind::Carrier => locale::text("battleship.carrier"),
+        ShipKind::Battleship => locale::text("battleship.battleship"),
+        ShipKind::Cruiser => locale::text("battleship.cruiser"),
+        ShipKind::Submarine => locale::text("battleship.submarine"),
+        ShipKind::Destroyer => locale::text("battleship.destroyer"),
+    }
+}
+
+// a square as it's named on the board, e.g. C5
+fn coordinates(Point(i, j): Point) -> String {
+    format!("{}{}", (b'A' + j as u8) as char, i + 1)
+}
+
+// Pretty-print
+impl fmt::Display for BattleshipApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.board_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.status_line())?;
+        write!(f, "{}\r\n", self.score_line())?;
+        write!(f, "{}\r\n", self.report)?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", battleshipui::help_text())
+    }
+}
+
+impl Game for BattleshipApp {
+    fn name(&self) -> &'static str {
+        "battleship"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != BattleshipUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            BattleshipUIAction::Quit => self.over = true,
+            BattleshipUIAction::NewGame => self.new_game(),
+            BattleshipUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the board");
+                }
+            }
+            BattleshipUIAction::Rotate => {
+                if let Stage::Placing { orientation, .. } = &mut self.stage {
+                    *orientation = orientation.turned();
+                }
+            }
+            BattleshipUIAction::RandomFleet => self.place_rest(),
+            // (the computer's turn is its own)
+            BattleshipUIAction::Select if self.is_computers_turn() => {}
+            BattleshipUIAction::Select => match self.stage {
+                Stage::Placing { .. } => self.place(),
+                Stage::Battle(_) => self.fire(self.ui.get_cursor()),
+            },
+            BattleshipUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // let the computer fire, when it's its turn
+    fn tick(&mut self) {
+        if !self.is_computers_turn() {
+            return;
+        }
+        let Stage::Battle(game) = &self.stage else {
+            return;
+        };
+        if let Some(p) = self.computer.choose_action(game) {
+            self.fire(p);
+            self.redraw.set(true);
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(COMPUTER_DELAY)
+    }
+
+    // both boards, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = battleshipui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        let boards = 2 * (LABEL_COLS + SQUARE_COLS * SIZE) + BOARD_GAP.len();
+        (
+            cols.max(boards as u16),
+            (SIZE + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Battleship against the computer
+pub fn run(args: Args) -> Result<(), String> {
+    let config: BattleshipConfig = common::cli::setup(&args.common, "battleship")?;
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("battleship seed {seed}");
+    let mut app = BattleshipApp::new(seed, args.random_fleet || config.random_fleet);
+    app.ui
+        .bindings
+        .remap(&config.keys, &battleshipui::ACTIONS)
+        .map_err(|e| format!("bad [battleship.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
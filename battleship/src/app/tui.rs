@@ -0,0 +1,27 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::BattleshipApp;
+use crate::battleshipui;
+
+impl TuiGame for BattleshipApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .board_lines()
+            .iter()
+            .map(|line| line.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows);
+
+        let status = StatusBar::new(battleshipui::help_text())
+            .line(self.status_line())
+            .line(self.score_line())
+            .line(self.report.clone())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
@@ -0,0 +1,144 @@
+use common::{Core, Point};
+use log::debug;
+
+use crate::fleet::{Fleet, ShipKind, Shot, SIZE};
+
+/// The two sides
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    You,
+    Computer,
+}
+
+impl Side {
+    pub fn other(self) -> Self {
+        match self {
+            Side::You => Side::Computer,
+            Side::Computer => Side::You,
+        }
+    }
+}
+
+/// What a shot led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShotResult {
+    Miss,
+    Hit,
+    /// the shot sank this ship
+    Sunk(ShipKind),
+    /// the shot sank this ship, the last one afloat
+    Won(ShipKind),
+    /// the square's been fired at before
+    AlreadyShot,
+    /// the game has already ended
+    GameOver,
+}
+
+/// A game of Battleship: both sides' fleets, and whose turn it is to fire
+#[derive(Debug, Clone, PartialEq)]
+pub struct Battleship {
+    fleets: [Fleet; 2], // yours, then the computer's
+    turn: Side,
+    last_shot: [Option<Point>; 2], // each side's
+}
+
+impl Battleship {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Battle between two placed fleets, with [first] firing first
+    pub fn new(yours: Fleet, theirs: Fleet, first: Side) -> Self {
+        assert!(
+            yours.is_complete() && theirs.is_complete(),
+            "fleets need every ship placed"
+        );
+        Self {
+            fleets: [yours, theirs],
+            turn: first,
+            last_shot: [None; 2],
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Fire at [p] on the other side's board, for whoever's turn it is
+    /// (the turn passes on unless it's a square fired at before)
+    pub fn fire(&mut self, p: Point) -> ShotResult {
+        if self.is_over() {
+            return ShotResult::GameOver;
+        }
+        let side = self.turn;
+        let target = &mut self.fleets[side.other() as usize];
+        let Some(shot) = target.fire(p) else {
+            return ShotResult::AlreadyShot;
+        };
+        self.last_shot[side as usize] = Some(p);
+        let res = match shot {
+            Shot::Miss => ShotResult::Miss,
+            Shot::Hit => ShotResult::Hit,
+            Shot::Sunk => {
+                let kind = target
+                    .ship_at(p)
+                    .map(|ship| ship.kind)
+                    .expect("sunk a ship");
+                match target.afloat() {
+                    0 => ShotResult::Won(kind),
+                    _ => ShotResult::Sunk(kind),
+                }
+            }
+        };
+        debug!("{side:?} fires at ({}, {}): {res:?}", p.0, p.1);
+        if !self.is_over() {
+            self.turn = side.other();
+        }
+        res
+    }
+
+    /// Whose turn it is to fire (once the game's over, the winner's)
+    pub fn turn(&self) -> Side {
+        self.turn
+    }
+
+    /// [side]'s own fleet
+    pub fn fleet(&self, side: Side) -> &Fleet {
+        &self.fleets[side as usize]
+    }
+
+    /// Where [side] last fired
+    pub fn last_shot(&self, side: Side) -> Option<Point> {
+        self.last_shot[side as usize]
+    }
+
+    /// Whoever sank the other's whole fleet
+    pub fn winner(&self) -> Option<Side> {
+        [Side::You, Side::Computer]
+            .into_iter()
+            .find(|&side| self.fleet(side.other()).afloat() == 0)
+    }
+}
+
+impl Core for Battleship {
+    type Action = Point;
+    type Outcome = ShotResult;
+    /// What whoever's turn it is knows of the board they're firing at
+    type Cell = Option<Shot>;
+
+    fn size(&self) -> (usize, usize) {
+        (SIZE, SIZE)
+    }
+
+    fn cell(&self, p: Point) -> Option<Shot> {
+        self.fleet(self.turn.other()).view(p)
+    }
+
+    fn act(&mut self, p: Point) -> ShotResult {
+        self.fire(p)
+    }
+
+    fn is_over(&self) -> bool {
+        self.winner().is_some()
+    }
+}
@@ -0,0 +1,129 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+use crate::fleet::SIZE;
+
+/// Help lines under the boards, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("battleship.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BattleshipUIAction {
+    Wait,
+    Move(MoveDirection),
+    /// place a ship, or fire
+    Select,
+    /// turn the ship being placed
+    Rotate,
+    /// place the rest of the fleet at random
+    RandomFleet,
+    NewGame,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, BattleshipUIAction); 11] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        BattleshipUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        BattleshipUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        BattleshipUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        BattleshipUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), BattleshipUIAction::Select),
+    (KeyBinding::key(KeyCode::Enter), BattleshipUIAction::Select),
+    (KeyBinding::char('r'), BattleshipUIAction::Rotate),
+    (KeyBinding::char('a'), BattleshipUIAction::RandomFleet),
+    (KeyBinding::char('n'), BattleshipUIAction::NewGame),
+    (KeyBinding::char('q'), BattleshipUIAction::Quit),
+    (KeyBinding::ctrl('c'), BattleshipUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, BattleshipUIAction); 9] = [
+    ("up", BattleshipUIAction::Move(MoveDirection::Up)),
+    ("down", BattleshipUIAction::Move(MoveDirection::Down)),
+    ("left", BattleshipUIAction::Move(MoveDirection::Left)),
+    ("right", BattleshipUIAction::Move(MoveDirection::Right)),
+    ("select", BattleshipUIAction::Select),
+    ("rotate", BattleshipUIAction::Rotate),
+    ("random_fleet", BattleshipUIAction::RandomFleet),
+    ("new_game", BattleshipUIAction::NewGame),
+    ("quit", BattleshipUIAction::Quit),
+];
+
+pub struct BattleshipUI {
+    cursor: Point,
+    pub bindings: Bindings<BattleshipUIAction>,
+}
+
+impl BattleshipUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cursor starting in the top left corner
+    pub fn new() -> Self {
+        Self {
+            cursor: Point(0, 0),
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> BattleshipUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(BattleshipUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> BattleshipUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => BattleshipUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a square, unless that's off the board
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let Point(i, j) = self.cursor;
+        let (i, j) = match dir {
+            MoveDirection::Up => (i.checked_sub(1), Some(j)),
+            MoveDirection::Down => (Some(i + 1).filter(|&i| i < SIZE), Some(j)),
+            MoveDirection::Left => (Some(i), j.checked_sub(1)),
+            MoveDirection::Right => (Some(i), Some(j + 1).filter(|&j| j < SIZE)),
+        };
+        let (Some(i), Some(j)) = (i, j) else {
+            return Err("already at the edge".into());
+        };
+        self.cursor = Point(i, j);
+        Ok(())
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+}
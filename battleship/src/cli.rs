@@ -0,0 +1,31 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Battleship
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on sinkings, wins and achievements")
+}))]
+pub struct Args {
+    /// Place your fleet at random, instead of ship by ship
+    #[arg(long)]
+    pub random_fleet: bool,
+
+    /// Random seed for the computer's fleet and its shots (picked at
+    /// random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
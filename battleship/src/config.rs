@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [battleship] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BattleshipConfig {
+    /// place your fleet at random, instead of ship by ship
+    pub random_fleet: bool,
+    /// keys for each action, e.g. rotate = "r"
+    pub keys: KeyMap,
+}
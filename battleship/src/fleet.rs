@@ -0,0 +1,198 @@
+use common::Point;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Board side, in squares
+pub const SIZE: usize = 10;
+
+/// The ships in a fleet, biggest first (and the order they're placed in)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShipKind {
+    Carrier,
+    Battleship,
+    Cruiser,
+    Submarine,
+    Destroyer,
+}
+
+impl ShipKind {
+    pub const ALL: [ShipKind; 5] = [
+        ShipKind::Carrier,
+        ShipKind::Battleship,
+        ShipKind::Cruiser,
+        ShipKind::Submarine,
+        ShipKind::Destroyer,
+    ];
+
+    /// Squares the ship takes up
+    pub fn length(self) -> usize {
+        match self {
+            ShipKind::Carrier => 5,
+            ShipKind::Battleship => 4,
+            ShipKind::Cruiser | ShipKind::Submarine => 3,
+            ShipKind::Destroyer => 2,
+        }
+    }
+}
+
+/// Which way a ship lies from its first square
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Orientation {
+    Across,
+    Down,
+}
+
+impl Orientation {
+    pub fn turned(self) -> Self {
+        match self {
+            Orientation::Across => Orientation::Down,
+            Orientation::Down => Orientation::Across,
+        }
+    }
+}
+
+/// A ship on the board, lying from [origin] (its top or left square)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ship {
+    pub kind: ShipKind,
+    pub origin: Point,
+    pub orientation: Orientation,
+}
+
+impl Ship {
+    /// Squares the ship covers (some may be off the board, for one that
+    /// doesn't fit)
+    pub fn squares(&self) -> impl Iterator<Item = Point> {
+        let Point(i, j) = self.origin;
+        let orientation = self.orientation;
+        (0..self.kind.length()).map(move |k| match orientation {
+            Orientation::Across => Point(i, j + k),
+            Orientation::Down => Point(i + k, j),
+        })
+    }
+}
+
+/// What's known about a square that's been fired at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shot {
+    Miss,
+    /// hit a ship that's still afloat
+    Hit,
+    /// hit a ship that's been sunk
+    Sunk,
+}
+
+/// One side's board: the ships on it, and the squares the other side has
+/// fired at
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fleet {
+    ships: Vec<Ship>,
+    shot: [[bool; SIZE]; SIZE],
+}
+
+impl Fleet {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Board with no ships on it yet
+    pub fn empty() -> Self {
+        Self {
+            ships: Vec::new(),
+            shot: [[false; SIZE]; SIZE],
+        }
+    }
+
+    /// Whole fleet placed at random (the same seed always places it the
+    /// same way)
+    pub fn random(seed: u64) -> Self {
+        let mut fleet = Self::empty();
+        fleet.place_rest(seed);
+        fleet
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Next ship to place (None once they all are)
+    pub fn next_kind(&self) -> Option<ShipKind> {
+        ShipKind::ALL.get(self.ships.len()).copied()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.next_kind().is_none()
+    }
+
+    /// Whether [ship] is on the board and clear of every other ship
+    pub fn fits(&self, ship: &Ship) -> bool {
+        ship.squares()
+            .all(|p @ Point(i, j)| i < SIZE && j < SIZE && self.ship_at(p).is_none())
+    }
+
+    /// Put the next ship down at [origin] facing [orientation], if it fits
+    pub fn place(&mut self, origin: Point, orientation: Orientation) -> bool {
+        let Some(kind) = self.next_kind() else {
+            return false;
+        };
+        let ship = Ship {
+            kind,
+            origin,
+            orientation,
+        };
+        if !self.fits(&ship) {
+            return false;
+        }
+        self.ships.push(ship);
+        true
+    }
+
+    /// Place every ship still to go at random
+    pub fn place_rest(&mut self, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        while !self.is_complete() {
+            let origin = Point(rng.gen_range(0..SIZE), rng.gen_range(0..SIZE));
+            let orientation = match rng.gen() {
+                true => Orientation::Across,
+                false => Orientation::Down,
+            };
+            self.place(origin, orientation);
+        }
+    }
+
+    /// Fire at [p], and hear what was there (None if it's been fired at
+    /// before)
+    pub fn fire(&mut self, p @ Point(i, j): Point) -> Option<Shot> {
+        if self.shot[i][j] {
+            return None;
+        }
+        self.shot[i][j] = true;
+        Some(self.view(p).unwrap_or(Shot::Miss))
+    }
+
+    /// What the other side knows about [p] (None if they haven't fired there)
+    pub fn view(&self, p @ Point(i, j): Point) -> Option<Shot> {
+        if !self.shot[i][j] {
+            return None;
+        }
+        match self.ship_at(p) {
+            Some(ship) if self.is_sunk(ship) => Some(Shot::Sunk),
+            Some(_) => Some(Shot::Hit),
+            None => Some(Shot::Miss),
+        }
+    }
+
+    pub fn ship_at(&self, p: Point) -> Option<&Ship> {
+        self.ships
+            .iter()
+            .find(|ship| ship.squares().any(|q| q == p))
+    }
+
+    pub fn is_sunk(&self, ship: &Ship) -> bool {
+        ship.squares().all(|Point(i, j)| self.shot[i][j])
+    }
+
+    /// Ships not sunk yet
+    pub fn afloat(&self) -> usize {
+        self.ships.iter().filter(|ship| !self.is_sunk(ship)).count()
+    }
+}
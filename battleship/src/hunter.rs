@@ -0,0 +1,87 @@
+use common::{Core, Player, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::battleship::Battleship;
+use crate::fleet::{Shot, SIZE};
+
+// directions to look for the rest of a ship in, as (rows, cols)
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Computer player that hunts and then targets: it fires at random on
+/// every other square (every ship covers at least one of them) until it
+/// hits something, then round the hit, then along the line of hits until
+/// the ship sinks
+pub struct Hunter {
+    rng: StdRng, // the same seed always fires the same way
+}
+
+impl Hunter {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Player<Battleship> for Hunter {
+    fn choose_action(&mut self, game: &Battleship) -> Option<Point> {
+        if game.is_over() {
+            return None;
+        }
+        let squares = || (0..SIZE).flat_map(|i| (0..SIZE).map(move |j| Point(i, j)));
+        let unknown = |p: &Point| game.cell(*p).is_none();
+        let hits: Vec<_> = squares()
+            .filter(|&p| game.cell(p) == Some(Shot::Hit))
+            .collect();
+
+        // past the end of a line of two or more hits
+        let mut targets: Vec<_> = hits
+            .iter()
+            .flat_map(|&p| DIRECTIONS.map(|dir| (p, dir)))
+            .filter(|&(p, (di, dj))| {
+                offset(p, (-di, -dj)).is_some_and(|q| game.cell(q) == Some(Shot::Hit))
+            })
+            .filter_map(|(p, dir)| {
+                let mut q = p;
+                while let Some(next) = offset(q, dir).filter(|&r| game.cell(r) == Some(Shot::Hit)) {
+                    q = next;
+                }
+                offset(q, dir).filter(unknown)
+            })
+            .collect();
+        // next to any hit
+        if targets.is_empty() {
+            targets = hits
+                .iter()
+                .flat_map(|&p| DIRECTIONS.map(|dir| offset(p, dir)))
+                .flatten()
+                .filter(unknown)
+                .collect();
+        }
+        // anywhere on the checkerboard, or anywhere left at all
+        if targets.is_empty() {
+            targets = squares()
+                .filter(|&Point(i, j)| (i + j) % 2 == 0)
+                .filter(unknown)
+                .collect();
+        }
+        if targets.is_empty() {
+            targets = squares().filter(unknown).collect();
+        }
+        let &p = targets.choose(&mut self.rng)?;
+        debug!("hunter fires at ({}, {})", p.0, p.1);
+        Some(p)
+    }
+}
+
+// square next to [p] in direction [dir], if that's on the board
+fn offset(Point(i, j): Point, (di, dj): (isize, isize)) -> Option<Point> {
+    let i = i.checked_add_signed(di).filter(|&i| i < SIZE)?;
+    let j = j.checked_add_signed(dj).filter(|&j| j < SIZE)?;
+    Some(Point(i, j))
+}
@@ -0,0 +1,24 @@
+//! Battleship: the game core (each side's fleet, the shots fired at it, and
+//! whose turn it is), a computer player that hunts for ships and then
+//! targets them, plus the crossterm frontend for playing it in a terminal
+//! against the computer
+
+mod battleship;
+mod fleet;
+mod hunter;
+pub use battleship::{Battleship, ShotResult, Side};
+pub use fleet::{Fleet, Orientation, Ship, ShipKind, Shot, SIZE};
+pub use hunter::Hunter;
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod battleshipui;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+pub use app::{run, BattleshipApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,519 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Write};
+
+use crossterm::event::Event;
+use crossterm::style::{ContentStyle, StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info, warn};
+
+use common::bell;
+use common::locale;
+use common::replay;
+use common::stats::{Outcome, Stats};
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Game, TerminalGuard};
+
+use crate::blackjack::{self, Action, Blackjack, Card, HandResult, Rules, Suit, TurnResult};
+use crate::blackjackui::{self, BlackjackUI, BlackjackUIAction};
+use crate::cli::Args;
+use crate::config::BlackjackConfig;
+
+// decks in the shoe when nothing else sets it
+const DEFAULT_DECKS: u8 = 6;
+
+// fewest and most decks the shoe can hold
+const MIN_DECKS: u8 = 1;
+const MAX_DECKS: u8 = 8;
+
+// chips to start with (the first time, and after going broke)
+const STARTING_BANKROLL: u64 = 1000;
+
+// bet on the first round, and how much raising or lowering it changes it by
+const STARTING_BET: u64 = 10;
+const BET_STEP: u64 = 10;
+
+// terminal columns each card takes up, with the gap after it
+const CARD_COLS: usize = 4;
+
+// lines of text under the hands (gap, bankroll and bet, rules, message)
+const STATUS_ROWS: usize = 4;
+
+/// Blackjack app (the table, the bet for the next round, the bankroll the
+/// session started with, and UI state)
+pub struct BlackjackApp {
+    game: Blackjack,
+    ui: BlackjackUI,
+    bet: u64,
+    ascii: bool,              // suits as letters
+    results: Vec<HandResult>, // how the last round's hands came out
+    start: u64,               // chips at the start of the session (and bought back in with)
+    played: bool,             // whether a round's been dealt
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    over: bool,
+}
+
+impl BlackjackApp {
+    /// Table playing by [rules], for a player with [bankroll] chips, shuffling
+    /// with [seed], and suits drawn as letters if [ascii]
+    pub fn new(rules: Rules, bankroll: u64, ascii: bool, seed: u64) -> Self {
+        let message = match bankroll {
+            0 => broke_message(""),
+            _ => "".to_string().reset(),
+        };
+        Self {
+            game: Blackjack::new(rules, bankroll, seed),
+            ui: BlackjackUI::new(),
+            bet: STARTING_BET.min(bankroll).max(1),
+            ascii,
+            results: Vec::new(),
+            start: bankroll,
+            played: false,
+            message,
+            redraw: Cell::new(true),
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn change_bet(&mut self, raise: bool) {
+        if self.game.in_round() {
+            bell::ring("invalid move");
+            return;
+        }
+        let bet = match raise {
+            true => (self.bet - self.bet % BET_STEP + BET_STEP).min(self.game.bankroll()),
+            false => self.bet.saturating_sub(BET_STEP).max(1),
+        };
+        if bet == self.bet || bet == 0 {
+            bell::ring("invalid move");
+            return;
+        }
+        self.bet = bet;
+    }
+
+    fn rebuy(&mut self) {
+        if !self.game.rebuy(STARTING_BANKROLL) {
+            bell::ring("invalid move");
+            return;
+        }
+        self.start += STARTING_BANKROLL;
+        self.bet = STARTING_BET;
+        self.message = "".to_string().reset();
+        self.save_bankroll();
+    }
+
+    fn act(&mut self, action: Action) {
+        debug!("{action:?}");
+        let res = self.game.act(action);
+        debug!("{res:?}");
+        self.handle_res(action, res);
+    }
+
+    fn handle_res(&mut self, action: Action, res: TurnResult) {
+        match res {
+            TurnResult::Dealt => {
+                self.played = true;
+                self.results.clear();
+                self.message = match self.game.was_shuffled() {
+                    true => locale::text("blackjack.shuffled")
+                        .to_string()
+                        .with(theme::color(Role::Accent)),
+                    false => "".to_string().reset(),
+                };
+            }
+            TurnResult::Played => self.message = "".to_string().reset(),
+            TurnResult::Settled(results) => {
+                self.played = true;
+                self.settled(action, &results);
+            }
+            TurnResult::Invalid => {
+                bell::ring("invalid move");
+                return;
+            }
+        }
+        self.save_bankroll();
+    }
+
+    // the round's over: say how it went, and get the bet ready for the next
+    fn settled(&mut self, action: Action, results: &[(HandResult, u64)]) {
+        self.results = results.iter().map(|&(result, _)| result).collect();
+        let bets: u64 = self.game.hands().iter().map(|hand| hand.bet()).sum();
+        let paid: u64 = results.iter().map(|&(_, payout)| payout).sum();
+        let shuffled = match matches!(action, Action::Deal(_)) && self.game.was_shuffled() {
+            true => format!("{} ", locale::text("blackjack.shuffled")),
+            false => "".to_string(),
+        };
+        let bankroll = self.game.bankroll();
+        if bankroll == 0 {
+            bell::ring("lose");
+            self.message = broke_message(&shuffled);
+            return;
+        }
+        self.bet = self.bet.min(bankroll);
+        let again = locale::text("blackjack.again");
+        self.message = match paid.cmp(&bets) {
+            Ordering::Greater => {
+                if results
+                    .iter()
+                    .any(|&(result, _)| result == HandResult::Blackjack)
+                {
+                    bell::ring("win");
+                }
+                format!(
+                    "{shuffled}{} {} {}. {again}",
+                    locale::text("blackjack.won_chips"),
+                    paid - bets,
+                    locale::text("blackjack.chips")
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success))
+            }
+            Ordering::Less => format!(
+                "{shuffled}{} {} {}. {again}",
+                locale::text("blackjack.lost_chips"),
+                bets - paid,
+                locale::text("blackjack.chips")
+            )
+            .with(theme::color(Role::Danger)),
+            Ordering::Equal => format!("{shuffled}{} {again}", locale::text("blackjack.even"))
+                .with(theme::color(Role::Accent)),
+        };
+    }
+
+    // keep the bankroll for next time (replays have been played already)
+    fn save_bankroll(&self) {
+        if replay::is_replaying() {
+            return;
+        }
+        // (like the rest of the stats, it isn't worth failing a game over)
+        if let Err(e) = Stats::save_bankroll("blackjack", self.game.bankroll()) {
+            warn!("failed to save the bankroll: {e}");
+        }
+    }
+
+    /// Lines of the table: the dealer's hand, a gap, and the player's hands,
+    /// each line a list of (styled) pieces
+    fn table_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let dealer = locale::text("blackjack.dealer");
+        let hands = self.game.hands();
+        let labels: Vec<String> = (1..=hands.len())
+            .map(|k| match hands.len() {
+                1 => locale::text("blackjack.you").to_string(),
+                _ => format!("{} {k}", locale::text("blackjack.hand")),
+            })
+            .collect();
+        let label_cols = labels
+            .iter()
+            .map(|label| text_width(label))
+            .chain([text_width(dealer)])
+            .max()
+            .unwrap_or_default() as usize;
+        let card_cols = hands
+            .iter()
+            .map(|hand| hand.cards().len())
+            .chain([self.game.dealer().len(), 2])
+            .max()
+            .unwrap_or_default()
+            * CARD_COLS;
+        let pad = |text: &str, cols: usize| {
+            format!(
+                "{text}{}",
+                " ".repeat(cols.saturating_sub(text_width(text) as usize))
+            )
+        };
+
+        // (the face down card, and what the dealer has, hidden during a round)
+        let in_round = self.game.in_round();
+        let mut line = vec![format!("{} ", pad(dealer, label_cols)).bold()];
+        let mut cols = 0;
+        for (j, &card) in self.game.dealer().iter().enumerate() {
+            line.push(match in_round && j == 1 {
+                true => self.face_down(),
+                false => self.card(card),
+            });
+            line.push(" ".to_string().reset());
+            cols += CARD_COLS;
+        }
+        line.push(" ".repeat(card_cols - cols).reset());
+        if !self.game.dealer().is_empty() {
+            let shown = match in_round {
+                true => &self.game.dealer()[..1],
+                false => self.game.dealer(),
+            };
+            line.push(total_text(shown).bold());
+        }
+        let mut lines = vec![line, vec![]];
+
+        for (k, hand) in hands.iter().enumerate() {
+            let label = format!("{} ", pad(&labels[k], label_cols));
+            let mut line = vec![match self.game.active() == Some(k) {
+                true => label.bold().with(theme::color(Role::Accent)).reverse(),
+                false => label.bold(),
+            }];
+            for &card in hand.cards() {
+                line.push(self.card(card));
+                line.push(" ".to_string().reset());
+            }
+            line.push(
+                " ".repeat(card_cols - hand.cards().len() * CARD_COLS)
+                    .reset(),
+            );
+            line.push(format!("{:<8}", total_text(hand.cards())).bold());
+            line.push(
+                format!("{} {:<6}", locale::text("blackjack.bet"), hand.bet())
+                    .with(theme::color(Role::Muted)),
+            );
+            if let Some(&result) = self.results.get(k) {
+                let (text, role) = match result {
+                    HandResult::Blackjack => (locale::text("blackjack.blackjack"), Role::Success),
+                    HandResult::Won => (locale::text("blackjack.won"), Role::Success),
+                    HandResult::Pushed => (locale::text("blackjack.pushed"), Role::Accent),
+                    HandResult::Lost => (locale::text("blackjack.lost"), Role::Danger),
+                    HandResult::Bust => (locale::text("blackjack.bust"), Role::Danger),
+                };
+                line.push(text.to_string().bold().with(theme::color(role)));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    fn card(&self, card: Card) -> StyledContent<String> {
+        let role = match card.suit.is_red() {
+            true => Role::Danger,
+            false => Role::Text,
+        };
+        let rank = match card.rank {
+            1 => "A".to_string(),
+            11 => "J".to_string(),
+            12 => "Q".to_string(),
+            13 => "K".to_string(),
+            rank => rank.to_string(),
+        };
+        let suit = match (card.suit, self.ascii) {
+            (Suit::Spades, false) => "♠",
+            (Suit::Hearts, false) => "♥",
+            (Suit::Diamonds, false) => "♦",
+            (Suit::Clubs, false) => "♣",
+            (Suit::Spades, true) => "S",
+            (Suit::Hearts, true) => "H",
+            (Suit::Diamonds, true) => "D",
+            (Suit::Clubs, true) => "C",
+        };
+        ContentStyle::new()
+            .bold()
+            .with(theme::color(role))
+            .apply(format!("{rank:>2}{suit}"))
+    }
+
+    fn face_down(&self) -> StyledContent<String> {
+        let back = match self.ascii {
+            true => "#",
+            false => "▒",
+        };
+        back.repeat(CARD_COLS - 1).with(theme::color(Role::Hidden))
+    }
+
+    // chips left, the next bet, and cards left in the shoe
+    fn counters(&self) -> String {
+        let mut counters = format!(
+            "{} {}   {} {}",
+            locale::text("blackjack.bankroll"),
+            self.game.bankroll(),
+            locale::text("blackjack.next_bet"),
+            self.bet
+        );
+        if self.played {
+            counters += &format!(
+                "   {} {}",
+                locale::text("blackjack.shoe"),
+                self.game.shoe_len()
+            );
+        }
+        counters
+    }
+
+    // the table's rules
+    fn rules_line(&self) -> String {
+        let rules = self.game.rules();
+        let dealer = match rules.hit_soft_17 {
+            true => locale::text("blackjack.h17"),
+            false => locale::text("blackjack.s17"),
+        };
+        let doubling = match rules.double_after_split {
+            true => locale::text("blackjack.das"),
+            false => locale::text("blackjack.no_das"),
+        };
+        format!(
+            "{} {}, {dealer}, {doubling}",
+            rules.decks,
+            locale::text("blackjack.decks")
+        )
+    }
+}
+
+// out of chips, and how to buy back in
+fn broke_message(before: &str) -> StyledContent<String> {
+    format!(
+        "{before}{} {STARTING_BANKROLL}.",
+        locale::text("blackjack.broke")
+    )
+    .bold()
+    .with(theme::color(Role::Text))
+    .on(theme::color(Role::Danger))
+}
+
+// e.g. "17", or "soft 17" with an Ace counted as 11
+fn total_text(cards: &[Card]) -> String {
+    match blackjack::total(cards) {
+        (total, true) => format!("{} {total}", locale::text("blackjack.soft")),
+        (total, false) => total.to_string(),
+    }
+}
+
+// Pretty-print
+impl fmt::Display for BlackjackApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.table_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.counters())?;
+        write!(
+            f,
+            "{}\r\n",
+            self.rules_line().with(theme::color(Role::Muted))
+        )?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", blackjackui::help_text())
+    }
+}
+
+impl Game for BlackjackApp {
+    fn name(&self) -> &'static str {
+        "blackjack"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != BlackjackUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            BlackjackUIAction::Quit => self.over = true,
+            BlackjackUIAction::RaiseBet => self.change_bet(true),
+            BlackjackUIAction::LowerBet => self.change_bet(false),
+            BlackjackUIAction::Deal => self.act(Action::Deal(self.bet)),
+            BlackjackUIAction::Hit => self.act(Action::Hit),
+            BlackjackUIAction::Stand => self.act(Action::Stand),
+            BlackjackUIAction::Double => self.act(Action::Double),
+            BlackjackUIAction::Split => self.act(Action::Split),
+            BlackjackUIAction::Rebuy => self.rebuy(),
+            BlackjackUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    // the hands as they are now, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = blackjackui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        let table = self.table_lines();
+        let table_cols = table
+            .iter()
+            .map(|line| line.iter().map(|piece| text_width(piece.content())).sum())
+            .max()
+            .unwrap_or_default();
+        (
+            cols.max(table_cols),
+            (table.len() + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    // up or down on the session, counting chips bought back in with (and
+    // any still riding on a hand as lost)
+    fn outcome(&self) -> Outcome {
+        if !self.played {
+            return Outcome::Quit;
+        }
+        match self.game.bankroll().cmp(&self.start) {
+            Ordering::Greater => Outcome::Won,
+            Ordering::Less => Outcome::Lost,
+            Ordering::Equal => Outcome::Quit,
+        }
+    }
+}
+
+/// Play blackjack, with the bankroll carried over from last time
+pub fn run(args: Args) -> Result<(), String> {
+    let config: BlackjackConfig = common::cli::setup(&args.common, "blackjack")?;
+
+    let decks = args.decks.or(config.decks).unwrap_or(DEFAULT_DECKS);
+    if !(MIN_DECKS..=MAX_DECKS).contains(&decks) {
+        return Err(format!(
+            "bad [blackjack] config: decks {decks} (goes from {MIN_DECKS} to {MAX_DECKS})"
+        ));
+    }
+    let rules = Rules {
+        decks,
+        hit_soft_17: args.hit_soft_17 || config.hit_soft_17,
+        double_after_split: !(args.no_double_after_split || config.no_double_after_split),
+    };
+    let ascii = args.ascii || config.ascii || !style::utf8_locale();
+    // (a stats file that can't be read just means starting afresh)
+    let bankroll = match Stats::bankroll("blackjack") {
+        Ok(bankroll) => bankroll.unwrap_or(STARTING_BANKROLL),
+        Err(e) => {
+            warn!("failed to read the bankroll: {e}");
+            STARTING_BANKROLL
+        }
+    };
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("blackjack seed {seed}, {rules:?}, bankroll {bankroll}");
+
+    let mut app = BlackjackApp::new(rules, bankroll, ascii, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &blackjackui::ACTIONS)
+        .map_err(|e| format!("bad [blackjack.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
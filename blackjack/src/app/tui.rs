@@ -0,0 +1,25 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::BlackjackApp;
+use crate::blackjackui;
+
+impl TuiGame for BlackjackApp {
+    fn draw(&self, frame: &mut Frame) {
+        let board = Board::new(
+            self.table_lines()
+                .iter()
+                .map(|line| line.iter().map(tui::span).collect::<Line>())
+                .collect(),
+        );
+        let status = StatusBar::new(blackjackui::help_text())
+            .line(self.counters())
+            .line(self.rules_line())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
@@ -0,0 +1,477 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// Most hands splitting can make out of one
+pub const MAX_HANDS: usize = 4;
+
+/// Total the hands try to get to without going over
+pub const TWENTY_ONE: u8 = 21;
+
+// the dealer draws to this, and then stands (unless it's soft, and the rules
+// say otherwise)
+const DEALER_STANDS: u8 = 17;
+
+// share of the shoe dealt before it's shuffled again (where the cut card goes)
+const PENETRATION: f64 = 0.75;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suit {
+    Spades,
+    Hearts,
+    Diamonds,
+    Clubs,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+    pub fn is_red(self) -> bool {
+        matches!(self, Suit::Hearts | Suit::Diamonds)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Card {
+    /// from 1 (Ace) to 13 (King)
+    pub rank: u8,
+    pub suit: Suit,
+}
+
+impl Card {
+    /// What the card counts for: its rank, 10 for a picture card, and 1 for
+    /// an Ace (which can count as 11 instead)
+    pub fn value(self) -> u8 {
+        self.rank.min(10)
+    }
+}
+
+/// Total of [cards], counting an Ace as 11 if that doesn't take it over
+/// [TWENTY_ONE], and whether one is (making it soft)
+pub fn total(cards: &[Card]) -> (u8, bool) {
+    let hard: u8 = cards.iter().map(|card| card.value()).sum();
+    let has_ace = cards.iter().any(|card| card.rank == 1);
+    match has_ace && hard + 10 <= TWENTY_ONE {
+        true => (hard + 10, true),
+        false => (hard, false),
+    }
+}
+
+/// How the table plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rules {
+    /// decks shuffled together into the shoe
+    pub decks: u8,
+    /// the dealer draws to a soft 17, instead of standing on it
+    pub hit_soft_17: bool,
+    /// hands split off can be doubled
+    pub double_after_split: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            decks: 6,
+            hit_soft_17: false,
+            double_after_split: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// start a round, betting this many chips
+    Deal(u64),
+    /// take another card on the hand being played
+    Hit,
+    /// play the next hand (or let the dealer play)
+    Stand,
+    /// double the bet on the hand being played, and take one card only
+    Double,
+    /// split the hand being played (two cards of the same value) into two,
+    /// betting as much again on the second
+    Split,
+}
+
+/// How a hand came out against the dealer's
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandResult {
+    /// 21 with the first two cards, paying 3 to 2
+    Blackjack,
+    Won,
+    Pushed,
+    Lost,
+    /// went over 21
+    Bust,
+}
+
+/// What an action led to
+#[derive(Debug, Clone, PartialEq)]
+pub enum TurnResult {
+    /// the round's been dealt, and there are hands to play
+    Dealt,
+    /// the round goes on
+    Played,
+    /// the round's over: how each hand came out, and the chips it paid back
+    /// (the bet included)
+    Settled(Vec<(HandResult, u64)>),
+    /// not allowed now, or not with the chips left
+    Invalid,
+}
+
+/// One of the player's hands, and the chips riding on it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hand {
+    cards: Vec<Card>,
+    bet: u64,
+    split: bool, // split off another hand (so 21 isn't a blackjack)
+    done: bool,
+}
+
+impl Hand {
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn bet(&self) -> u64 {
+        self.bet
+    }
+
+    pub fn total(&self) -> (u8, bool) {
+        total(&self.cards)
+    }
+
+    /// 21 with the first two cards, not split
+    pub fn is_blackjack(&self) -> bool {
+        !self.split && is_natural(&self.cards)
+    }
+
+    pub fn is_bust(&self) -> bool {
+        self.total().0 > TWENTY_ONE
+    }
+
+    /// Whether the hand's stood, doubled or gone bust
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+fn is_natural(cards: &[Card]) -> bool {
+    cards.len() == 2 && total(cards).0 == TWENTY_ONE
+}
+
+/// A blackjack table: a shoe of shuffled decks, the dealer's hand and the
+/// player's (one, or more after splitting), and the player's bankroll
+#[derive(Debug, Clone)]
+pub struct Blackjack {
+    rules: Rules,
+    shoe: Vec<Card>, // the next card last
+    shuffled: bool,  // for the round being played
+    bankroll: u64,   // chips not riding on a hand
+    hands: Vec<Hand>,
+    active: usize, // the hand being played
+    dealer: Vec<Card>,
+    in_round: bool,
+    rng: StdRng,
+}
+
+impl Blackjack {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Table playing by [rules], for a player with [bankroll] chips (the
+    /// same seed always deals the same cards, if played the same way)
+    pub fn new(rules: Rules, bankroll: u64, seed: u64) -> Self {
+        Self {
+            rules,
+            shoe: Vec::new(),
+            shuffled: false,
+            bankroll,
+            hands: Vec::new(),
+            active: 0,
+            dealer: Vec::new(),
+            in_round: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn act(&mut self, action: Action) -> TurnResult {
+        if !self.is_legal(action) {
+            return TurnResult::Invalid;
+        }
+        match action {
+            Action::Deal(bet) => return self.deal(bet),
+            Action::Hit => {
+                let card = self.draw();
+                let hand = &mut self.hands[self.active];
+                hand.cards.push(card);
+                hand.done = hand.total().0 >= TWENTY_ONE;
+            }
+            Action::Stand => self.hands[self.active].done = true,
+            Action::Double => {
+                let card = self.draw();
+                let hand = &mut self.hands[self.active];
+                self.bankroll -= hand.bet;
+                hand.bet *= 2;
+                hand.cards.push(card);
+                hand.done = true;
+            }
+            Action::Split => {
+                let hand = &mut self.hands[self.active];
+                let card = hand.cards.pop().expect("a pair to split");
+                let bet = hand.bet;
+                hand.split = true;
+                self.bankroll -= bet;
+                let split = Hand {
+                    cards: vec![card],
+                    bet,
+                    split: true,
+                    done: false,
+                };
+                self.hands.insert(self.active + 1, split);
+                // (each gets a second card, and split Aces get no more)
+                let aces = card.rank == 1;
+                for k in [self.active, self.active + 1] {
+                    let card = self.draw();
+                    let hand = &mut self.hands[k];
+                    hand.cards.push(card);
+                    hand.done = aces || hand.total().0 == TWENTY_ONE;
+                }
+            }
+        }
+        self.next_hand()
+    }
+
+    /// Whether [action] is allowed now: dealing between rounds, for chips
+    /// the player has, and the rest during one, with doubling and splitting
+    /// on a hand's first two cards, if there are chips to match the bet
+    pub fn is_legal(&self, action: Action) -> bool {
+        if let Action::Deal(bet) = action {
+            return !self.in_round && bet > 0 && bet <= self.bankroll;
+        }
+        let Some(hand) = self.hands.get(self.active).filter(|_| self.in_round) else {
+            return false;
+        };
+        let first_two = hand.cards.len() == 2;
+        let covered = hand.bet <= self.bankroll;
+        match action {
+            Action::Deal(_) => unreachable!(),
+            Action::Hit | Action::Stand => true,
+            Action::Double => {
+                first_two && covered && (!hand.split || self.rules.double_after_split)
+            }
+            Action::Split => {
+                first_two
+                    && covered
+                    && self.hands.len() < MAX_HANDS
+                    && hand.cards[0].value() == hand.cards[1].value()
+            }
+        }
+    }
+
+    /// Chips not riding on a hand
+    pub fn bankroll(&self) -> u64 {
+        self.bankroll
+    }
+
+    /// Start over with [chips], after losing every one. Returns whether the
+    /// player was broke
+    pub fn rebuy(&mut self, chips: u64) -> bool {
+        if self.in_round || self.bankroll > 0 {
+            return false;
+        }
+        self.bankroll = chips;
+        true
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Player's hands this round (or last round, between them)
+    pub fn hands(&self) -> &[Hand] {
+        &self.hands
+    }
+
+    /// Hand being played, during a round
+    pub fn active(&self) -> Option<usize> {
+        self.in_round.then_some(self.active)
+    }
+
+    /// Dealer's cards, the second one face down during a round
+    pub fn dealer(&self) -> &[Card] {
+        &self.dealer
+    }
+
+    /// Dealer's face up card, during a round
+    pub fn up_card(&self) -> Option<Card> {
+        self.dealer.first().copied()
+    }
+
+    pub fn in_round(&self) -> bool {
+        self.in_round
+    }
+
+    /// Whether the shoe was shuffled for the last round dealt
+    pub fn was_shuffled(&self) -> bool {
+        self.shuffled
+    }
+
+    /// Cards left in the shoe
+    pub fn shoe_len(&self) -> usize {
+        self.shoe.len()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn deal(&mut self, bet: u64) -> TurnResult {
+        let decks = self.rules.decks.max(1) as usize;
+        let cut = (52.0 * decks as f64 * (1.0 - PENETRATION)) as usize;
+        self.shuffled = self.shoe.len() <= cut;
+        if self.shuffled {
+            self.shoe = Suit::ALL
+                .iter()
+                .flat_map(|&suit| (1..=13).map(move |rank| Card { rank, suit }))
+                .cycle()
+                .take(52 * decks)
+                .collect();
+            self.shoe.shuffle(&mut self.rng);
+            debug!("shuffled {decks} decks");
+        }
+        self.bankroll -= bet;
+        self.in_round = true;
+        self.active = 0;
+        let cards = [self.draw(), self.draw(), self.draw(), self.draw()];
+        let hand = Hand {
+            cards: vec![cards[0], cards[2]],
+            bet,
+            split: false,
+            done: false,
+        };
+        self.dealer = vec![cards[1], cards[3]];
+        debug!("dealt {:?} against {:?}", hand.cards, self.dealer);
+        let naturals = hand.is_blackjack() || is_natural(&self.dealer);
+        self.hands = vec![hand];
+        // (the dealer checks the face down card for a blackjack straight away)
+        match naturals {
+            true => self.settle(),
+            false => TurnResult::Dealt,
+        }
+    }
+
+    fn draw(&mut self) -> Card {
+        self.shoe
+            .pop()
+            .expect("the shoe is shuffled long before it runs out")
+    }
+
+    // move on to the next hand not done yet, or once they all are, let the
+    // dealer play, and settle up
+    fn next_hand(&mut self) -> TurnResult {
+        match (self.active..self.hands.len()).find(|&k| !self.hands[k].done) {
+            Some(k) => {
+                self.active = k;
+                TurnResult::Played
+            }
+            None => {
+                if self.hands.iter().any(|hand| !hand.is_bust()) {
+                    self.dealer_plays();
+                }
+                self.settle()
+            }
+        }
+    }
+
+    fn dealer_plays(&mut self) {
+        loop {
+            let (total, soft) = total(&self.dealer);
+            let hits =
+                total < DEALER_STANDS || (total == DEALER_STANDS && soft && self.rules.hit_soft_17);
+            if !hits {
+                break;
+            }
+            let card = self.draw();
+            self.dealer.push(card);
+        }
+    }
+
+    fn settle(&mut self) -> TurnResult {
+        let (dealer, _) = total(&self.dealer);
+        let dealer_natural = is_natural(&self.dealer);
+        let results: Vec<(HandResult, u64)> = self
+            .hands
+            .iter()
+            .map(|hand| {
+                let (player, _) = hand.total();
+                let result = if player > TWENTY_ONE {
+                    HandResult::Bust
+                } else if hand.is_blackjack() && !dealer_natural {
+                    HandResult::Blackjack
+                } else if dealer_natural {
+                    match hand.is_blackjack() {
+                        true => HandResult::Pushed,
+                        false => HandResult::Lost,
+                    }
+                } else if dealer > TWENTY_ONE || player > dealer {
+                    HandResult::Won
+                } else if player == dealer {
+                    HandResult::Pushed
+                } else {
+                    HandResult::Lost
+                };
+                let payout = match result {
+                    HandResult::Blackjack => hand.bet + hand.bet * 3 / 2,
+                    HandResult::Won => 2 * hand.bet,
+                    HandResult::Pushed => hand.bet,
+                    HandResult::Lost | HandResult::Bust => 0,
+                };
+                (result, payout)
+            })
+            .collect();
+        self.bankroll += results.iter().map(|&(_, payout)| payout).sum::<u64>();
+        self.in_round = false;
+        for hand in &mut self.hands {
+            hand.done = true;
+        }
+        debug!("dealer {:?}: {results:?}", self.dealer);
+        TurnResult::Settled(results)
+    }
+}
+
+impl Core for Blackjack {
+    type Action = Action;
+    type Outcome = TurnResult;
+    /// The card (the dealer's hand on the first row, and the player's under
+    /// it), if it's there and face up
+    type Cell = Option<Card>;
+
+    fn size(&self) -> (usize, usize) {
+        let cards = self.hands.iter().map(|hand| hand.cards.len());
+        let widest = cards.chain([self.dealer.len()]).max();
+        (1 + self.hands.len(), widest.unwrap_or_default())
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Option<Card> {
+        match i {
+            0 if j == 1 && self.in_round => None,
+            0 => self.dealer.get(j).copied(),
+            _ => self.hands.get(i - 1)?.cards.get(j).copied(),
+        }
+    }
+
+    fn act(&mut self, action: Action) -> TurnResult {
+        Blackjack::act(self, action)
+    }
+
+    /// Out of chips
+    fn is_over(&self) -> bool {
+        !self.in_round && self.bankroll == 0
+    }
+}
@@ -0,0 +1,88 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+/// Help lines under the table, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("blackjack.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlackjackUIAction {
+    Wait,
+    RaiseBet,
+    LowerBet,
+    Deal,
+    Hit,
+    Stand,
+    Double,
+    Split,
+    /// start over with a fresh bankroll, once it's all gone
+    Rebuy,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, BlackjackUIAction); 14] = [
+    (KeyBinding::key(KeyCode::Up), BlackjackUIAction::RaiseBet),
+    (KeyBinding::char('+'), BlackjackUIAction::RaiseBet),
+    (KeyBinding::key(KeyCode::Down), BlackjackUIAction::LowerBet),
+    (KeyBinding::char('-'), BlackjackUIAction::LowerBet),
+    (KeyBinding::key(KeyCode::Enter), BlackjackUIAction::Deal),
+    (KeyBinding::char(' '), BlackjackUIAction::Deal),
+    (KeyBinding::char('n'), BlackjackUIAction::Deal),
+    (KeyBinding::char('h'), BlackjackUIAction::Hit),
+    (KeyBinding::char('s'), BlackjackUIAction::Stand),
+    (KeyBinding::char('d'), BlackjackUIAction::Double),
+    (KeyBinding::char('p'), BlackjackUIAction::Split),
+    (KeyBinding::char('r'), BlackjackUIAction::Rebuy),
+    (KeyBinding::char('q'), BlackjackUIAction::Quit),
+    (KeyBinding::ctrl('c'), BlackjackUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, BlackjackUIAction); 9] = [
+    ("raise_bet", BlackjackUIAction::RaiseBet),
+    ("lower_bet", BlackjackUIAction::LowerBet),
+    ("deal", BlackjackUIAction::Deal),
+    ("hit", BlackjackUIAction::Hit),
+    ("stand", BlackjackUIAction::Stand),
+    ("double", BlackjackUIAction::Double),
+    ("split", BlackjackUIAction::Split),
+    ("rebuy", BlackjackUIAction::Rebuy),
+    ("quit", BlackjackUIAction::Quit),
+];
+
+pub struct BlackjackUI {
+    pub bindings: Bindings<BlackjackUIAction>,
+}
+
+impl BlackjackUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> BlackjackUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(BlackjackUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> BlackjackUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => BlackjackUIAction::Wait,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Blackjack
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on invalid moves, blackjacks, going broke and achievements")
+}))]
+pub struct Args {
+    /// Decks shuffled together into the shoe, from 1 to 8
+    /// [default: 6, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=8))]
+    pub decks: Option<u8>,
+
+    /// Have the dealer draw to a soft 17 (one with an Ace counted as 11),
+    /// instead of standing on it
+    #[arg(long)]
+    pub hit_soft_17: bool,
+
+    /// Don't allow doubling on hands split off another
+    #[arg(long)]
+    pub no_double_after_split: bool,
+
+    /// Draw suits as letters (S, H, D, C) instead of symbols (also done
+    /// when the locale isn't UTF-8)
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Random seed for shuffling the shoe (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
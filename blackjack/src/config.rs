@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [blackjack] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BlackjackConfig {
+    /// decks shuffled together into the shoe, from 1 to 8
+    pub decks: Option<u8>,
+    /// the dealer draws to a soft 17
+    pub hit_soft_17: bool,
+    /// hands split off another can't be doubled
+    pub no_double_after_split: bool,
+    /// draw suits as letters instead of symbols
+    pub ascii: bool,
+    /// keys for each action, e.g. split = "x"
+    pub keys: KeyMap,
+}
@@ -0,0 +1,23 @@
+//! Blackjack: the game core (a shoe of shuffled decks, the dealer playing by
+//! the table's rules, doubling and splitting, and the bankroll), plus the
+//! crossterm frontend for playing it in a terminal, with the bankroll kept
+//! from one session to the next
+
+mod blackjack;
+pub use blackjack::{
+    total, Action, Blackjack, Card, Hand, HandResult, Rules, Suit, TurnResult, MAX_HANDS,
+    TWENTY_ONE,
+};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod blackjackui;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+pub use app::{run, BlackjackApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
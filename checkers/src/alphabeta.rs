@@ -0,0 +1,189 @@
+use common::{Core, Player, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::checkers::{Checkers, Side, SIZE};
+
+/// Deepest search allowed (each extra move looked ahead takes several
+/// times as long)
+pub const MAX_DEPTH: u32 = 8;
+
+// score of a won game, less the moves it takes to get there
+const WIN: i32 = 1_000_000;
+
+// what each piece is worth: a king goes both ways, so it's worth more
+const MAN: i32 = 100;
+const KING: i32 = 160;
+
+// worth of each row a man has come forward, on its way to being crowned
+const ADVANCE: i32 = 3;
+
+// cost of each square between the kings of the side ahead and the other
+// side's nearest piece
+const CHASE: i32 = 2;
+
+// worth of a man still on its own back row, keeping the other side from
+// being crowned there
+const BACK_ROW: i32 = 8;
+
+/// Computer player looking [depth] moves ahead with alpha-beta pruned
+/// minimax, and weighing up the positions it gets to by the pieces each
+/// side has (kings more), how far their men have come, and the back row
+/// they're keeping. A run of jumps counts as one move. Picks at random
+/// between equally good moves
+pub struct AlphaBeta {
+    depth: u32,
+    rng: StdRng, // the same seed always picks the same way
+}
+
+impl AlphaBeta {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Player looking [depth] moves ahead (1 to [MAX_DEPTH])
+    pub fn new(depth: u32, seed: u64) -> Self {
+        Self {
+            depth: depth.clamp(1, MAX_DEPTH),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Player<Checkers> for AlphaBeta {
+    fn choose_action(&mut self, game: &Checkers) -> Option<(Point, Point)> {
+        if game.is_over() {
+            return None;
+        }
+        let me = game.turn();
+        let steps = game.legal_steps();
+        // (nothing to think about when there's only the one way to go)
+        if let [step] = steps[..] {
+            return Some(step);
+        }
+        let scored: Vec<_> = steps
+            .into_iter()
+            .map(|(from, to)| {
+                let mut next = game.clone();
+                next.step(from, to);
+                let depth = remaining(game, &next, self.depth);
+                ((from, to), search(&next, me, depth, 1, -WIN - 1, WIN + 1))
+            })
+            .collect();
+        let best = scored.iter().map(|&(_, s)| s).max()?;
+        let best_steps: Vec<_> = scored
+            .into_iter()
+            .filter(|&(_, s)| s == best)
+            .map(|(step, _)| step)
+            .collect();
+        let &(from, to) = best_steps.choose(&mut self.rng)?;
+        debug!(
+            "alpha-beta plays ({}, {}) to ({}, {}) for {best}",
+            from.0, from.1, to.0, to.1
+        );
+        Some((from, to))
+    }
+}
+
+// moves still to look ahead after going from [game] to [next], with
+// [depth] left before: one fewer, unless the same piece is jumping on
+fn remaining(game: &Checkers, next: &Checkers, depth: u32) -> u32 {
+    match next.jumping().is_some() && next.turn() == game.turn() {
+        true => depth,
+        false => depth - 1,
+    }
+}
+
+// how good [game] is for [me], looking [depth] more moves ahead, with
+// [ply] steps made since the search started. Scores outside of
+// (alpha, beta) are only bounds, since they can't change the choice
+fn search(game: &Checkers, me: Side, depth: u32, ply: i32, mut alpha: i32, mut beta: i32) -> i32 {
+    match game.winner() {
+        Some(side) if side == me => return WIN - ply,
+        Some(_) => return ply - WIN,
+        None if game.is_over() => return 0,
+        None if depth == 0 => return evaluate(game, me),
+        None => {}
+    }
+    let maximizing = game.turn() == me;
+    let mut best = if maximizing { -WIN - 1 } else { WIN + 1 };
+    for (from, to) in game.legal_steps() {
+        let mut next = game.clone();
+        next.step(from, to);
+        let score = search(
+            &next,
+            me,
+            remaining(game, &next, depth),
+            ply + 1,
+            alpha,
+            beta,
+        );
+        if maximizing {
+            best = best.max(score);
+            alpha = alpha.max(score);
+        } else {
+            best = best.min(score);
+            beta = beta.min(score);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+// rough worth to [me] of a position that isn't over: each side's pieces,
+// plus how far their men have come and whether they're holding their back
+// row, for [me] and against the other side. The side ahead on pieces does
+// better with its kings close to the other side's pieces, so it goes after
+// them instead of wandering about until the game's drawn
+fn evaluate(game: &Checkers, me: Side) -> i32 {
+    let mut score = 0;
+    let mut pieces = Vec::new();
+    for i in 0..SIZE {
+        for j in 0..SIZE {
+            let Some(piece) = game.cell(Point(i, j)) else {
+                continue;
+            };
+            pieces.push((Point(i, j), piece));
+            let worth = match piece.king {
+                true => KING,
+                false => {
+                    let back = piece.side.other().crown_row();
+                    let advanced = i.abs_diff(back) as i32;
+                    let guard = if i == back { BACK_ROW } else { 0 };
+                    MAN + ADVANCE * advanced + guard
+                }
+            };
+            match piece.side == me {
+                true => score += worth,
+                false => score -= worth,
+            }
+        }
+    }
+    let ahead = match score {
+        0 => return 0,
+        s if s > 0 => me,
+        _ => me.other(),
+    };
+    let chase: i32 = pieces
+        .iter()
+        .filter(|(_, piece)| piece.side == ahead && piece.king)
+        .filter_map(|&(p, _)| {
+            pieces
+                .iter()
+                .filter(|(_, other)| other.side != ahead)
+                .map(|&(q, _)| distance(p, q) as i32)
+                .min()
+        })
+        .sum();
+    match ahead == me {
+        true => score - CHASE * chase,
+        false => score + CHASE * chase,
+    }
+}
+
+// diagonal steps a king would take between two squares
+fn distance(a: Point, b: Point) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
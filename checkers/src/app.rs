@@ -0,0 +1,503 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Player, Point, TerminalGuard};
+
+use crate::alphabeta::{AlphaBeta, MAX_DEPTH};
+use crate::checkers::{is_dark, Checkers, MoveResult, Piece, Side, SIZE};
+use crate::checkersui::{self, CheckersUI, CheckersUIAction, START_CURSOR};
+use crate::cli::Args;
+use crate::config::CheckersConfig;
+
+// how far ahead the computer looks when nothing else sets it
+const DEFAULT_DEPTH: u32 = 6;
+
+// time between each of the computer's steps, so a run of jumps can be
+// followed a jump at a time
+const COMPUTER_TICK: Duration = Duration::from_millis(400);
+
+// terminal columns each square takes up, including the space before it
+const SQUARE_COLS: usize = 2;
+
+// lines of text around the board (column letters, gap, turn, pieces,
+// score, message)
+const STATUS_ROWS: usize = 6;
+
+/// Checkers app (the game being played, the computer player if there is
+/// one, the score over every game so far, and UI state)
+pub struct CheckersApp {
+    game: Checkers,
+    ui: CheckersUI,
+    computer: Option<AlphaBeta>, // none for two players at one keyboard
+    computer_side: Side,         // (swapping every game)
+    selected: Option<Point>,     // the piece picked to move
+    wins: (u32, u32),            // you and the computer's, or black and white's
+    draws: u32,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last game decided against the computer
+    over: bool,
+}
+
+impl CheckersApp {
+    /// Game against [computer] playing [computer_side], or between two
+    /// people without one
+    pub fn new(computer: Option<AlphaBeta>, computer_side: Side) -> Self {
+        let mut app = Self {
+            game: Checkers::new(),
+            ui: CheckersUI::new(),
+            computer,
+            computer_side,
+            selected: None,
+            wins: (0, 0),
+            draws: 0,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        };
+        app.ui.set_cursor(app.start_cursor());
+        app
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn is_computers_turn(&self) -> bool {
+        self.computer.is_some() && self.game.turn() == self.computer_side && !self.game.is_over()
+    }
+
+    fn new_game(&mut self) {
+        self.game = Checkers::new();
+        self.computer_side = self.computer_side.other();
+        self.selected = None;
+        self.ui.set_cursor(self.start_cursor());
+        self.message = "".to_string().reset();
+    }
+
+    // on one of the player's men in their front row (black's, with two
+    // players), turned round when they're white at the top
+    fn start_cursor(&self) -> Point {
+        match self.computer.is_some() && self.computer_side == Side::Black {
+            true => Point(SIZE - 1 - START_CURSOR.0, SIZE - 1 - START_CURSOR.1),
+            false => START_CURSOR,
+        }
+    }
+
+    // pick up the piece under the cursor (or put it back), or move the
+    // piece picked up there
+    fn select(&mut self) {
+        let p = self.ui.get_cursor();
+        if let Some(from) = self.game.jumping() {
+            // (a piece partway through its jumps can't be swapped for another)
+            self.step(from, p);
+            return;
+        }
+        let turn = self.game.turn();
+        if self.game.cell(p).is_some_and(|piece| piece.side == turn) {
+            if self.selected == Some(p) {
+                self.selected = None;
+                return;
+            }
+            self.selected = Some(p);
+            if self.game.legal_targets(p).is_empty() {
+                let why = match self.game.must_jump() {
+                    true => "checkers.must_jump",
+                    false => "checkers.stuck",
+                };
+                self.message = locale::text(why)
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            return;
+        }
+        match self.selected {
+            Some(from) => self.step(from, p),
+            None => {
+                bell::ring("invalid move");
+                self.message = locale::text("checkers.pick")
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+        }
+    }
+
+    // move the piece on [from] to [to]
+    fn step(&mut self, from: Point, to: Point) {
+        let side = self.game.turn();
+        let res = self.game.step(from, to);
+        debug!(
+            "{side:?} ({}, {}) to ({}, {}): {res:?}",
+            from.0, from.1, to.0, to.1
+        );
+        self.handle_res(side, res);
+    }
+
+    // what [side]'s move led to
+    fn handle_res(&mut self, side: Side, res: MoveResult) {
+        let again = locale::text("checkers.again");
+        match res {
+            MoveResult::Moved => {
+                self.selected = None;
+                self.message = "".to_string().reset();
+            }
+            MoveResult::JumpAgain => {
+                self.selected = self.game.jumping();
+                self.message = match self.is_computers_turn() {
+                    true => "".to_string().reset(),
+                    false => locale::text("checkers.jump_again").to_string().bold(),
+                };
+            }
+            MoveResult::Crowned => {
+                bell::ring("crowned");
+                self.selected = None;
+                let crowned = locale::text("checkers.crowned");
+                self.message = format!("{} {crowned}", side_name(side)).bold();
+            }
+            MoveResult::Won(winner) if self.computer.is_none() => {
+                bell::ring("win");
+                self.selected = None;
+                match winner {
+                    Side::Black => self.wins.0 += 1,
+                    Side::White => self.wins.1 += 1,
+                }
+                let wins = locale::text("checkers.wins");
+                self.message = format!("{} {wins} {again}", side_name(winner))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Won(winner) if winner == self.computer_side => {
+                bell::ring("lose");
+                self.selected = None;
+                self.wins.1 += 1;
+                self.outcome = Outcome::Lost;
+                self.message = format!("{} {again}", locale::text("checkers.lose"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+            }
+            MoveResult::Won(_) => {
+                bell::ring("win");
+                self.selected = None;
+                self.wins.0 += 1;
+                self.outcome = Outcome::Won;
+                self.message = format!("{} {again}", locale::text("checkers.win"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Draw => {
+                self.selected = None;
+                self.draws += 1;
+                self.message = format!("{} {again}", locale::text("checkers.draw")).bold();
+            }
+            MoveResult::Illegal | MoveResult::MustJump => {
+                bell::ring("invalid move");
+                let why = match res {
+                    MoveResult::MustJump => "checkers.must_jump",
+                    _ => "checkers.illegal",
+                };
+                self.message = locale::text(why)
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            MoveResult::GameOver => bell::ring("invalid move"),
+        }
+    }
+
+    // (styled) string for a square: a piece, or on a dark square a dot
+    // (a + where the picked up piece could go). The picked up piece stands
+    // out, the squares the last move left are marked, and the cursor is
+    // reversed
+    fn styled_square(&self, p: Point) -> StyledContent<String> {
+        let players_turn = !self.game.is_over() && !self.is_computers_turn();
+        let targets = match self.selected.filter(|_| players_turn) {
+            Some(from) => self.game.legal_targets(from),
+            None => Vec::new(),
+        };
+        let last_move = self.game.last_move();
+        let styled = match self.game.cell(p) {
+            Some(piece) if self.selected == Some(p) && players_turn => {
+                match style::color_enabled() {
+                    true => piece_glyph(piece)
+                        .to_string()
+                        .bold()
+                        .with(theme::color(Role::Accent)),
+                    false => piece_glyph(piece).to_string().bold().underlined(),
+                }
+            }
+            Some(piece) => piece_glyph(piece).to_string().bold(),
+            None if targets.contains(&p) => "+".to_string().with(theme::color(Role::Marker)),
+            None if last_move[..last_move.len().saturating_sub(1)].contains(&p) => {
+                "·".to_string().bold().with(theme::color(Role::Accent))
+            }
+            None if is_dark(p) => "·".to_string().with(theme::color(Role::Muted)),
+            None => " ".to_string().reset(),
+        };
+        if p == self.ui.get_cursor() && players_turn {
+            return styled.reverse();
+        }
+        styled
+    }
+
+    /// Lines of the board, with letters over the columns and numbers by
+    /// the rows, each a list of (styled) pieces
+    fn board_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let muted = |s: String| s.with(theme::color(Role::Muted));
+        let letters: String = ('a'..).take(SIZE).map(|c| format!(" {c}")).collect();
+        let mut lines = vec![vec![muted(format!("  {letters}"))]];
+        for i in 0..SIZE {
+            let mut line = vec![muted(format!("{:>2}", i + 1))];
+            for j in 0..SIZE {
+                line.push(" ".to_string().reset());
+                line.push(self.styled_square(Point(i, j)));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    // which side the player is on, whose turn it is, and the last move
+    fn turn_line(&self) -> String {
+        let mut parts = Vec::new();
+        if self.computer.is_some() {
+            let you = side_name(self.computer_side.other());
+            parts.push(format!("{} {you}", locale::text("checkers.playing")));
+        }
+        if !self.game.is_over() {
+            let turn = side_name(self.game.turn());
+            parts.push(format!("{turn} {}", locale::text("checkers.to_move")));
+        }
+        if let Some(last) = notation(self.game.last_move()) {
+            parts.push(format!("{} {last}", locale::text("checkers.last_move")));
+        }
+        parts.join("   ")
+    }
+
+    // pieces each side has on the board, and how many are kings
+    fn count_line(&self) -> String {
+        [Side::Black, Side::White]
+            .map(|side| {
+                let (men, kings) = self.game.count(side);
+                let mut count = format!("{} {}", side_name(side), men + kings);
+                if kings > 0 {
+                    count += &format!(" ({} {kings})", locale::text("checkers.kings"));
+                }
+                count
+            })
+            .join("   ")
+    }
+
+    // games won by each side, and drawn
+    fn score_line(&self) -> String {
+        let (first, second) = match self.computer {
+            Some(_) => (
+                locale::text("checkers.you").to_string(),
+                locale::text("checkers.computer").to_string(),
+            ),
+            None => (side_name(Side::Black), side_name(Side::White)),
+        };
+        format!(
+            "{first} {}   {second} {}   {} {}",
+            self.wins.0,
+            self.wins.1,
+            locale::text("checkers.draws"),
+            self.draws
+        )
+    }
+}
+
+// a side's name, with its men
+fn side_name(side: Side) -> String {
+    let name = match side {
+        Side::Black => locale::text("checkers.black"),
+        Side::White => locale::text("checkers.white"),
+    };
+    let man = piece_glyph(Piece { side, king: false });
+    format!("{name} ({man})")
+}
+
+// a piece: a filled or hollow circle (ringed for a king), or a letter
+// without color (a capital for a king)
+fn piece_glyph(piece: Piece) -> &'static str {
+    match (piece.side, piece.king, style::color_enabled()) {
+        (Side::Black, false, true) => "●",
+        (Side::Black, true, true) => "◉",
+        (Side::White, false, true) => "○",
+        (Side::White, true, true) => "◎",
+        (Side::Black, false, false) => "x",
+        (Side::Black, true, false) => "X",
+        (Side::White, false, false) => "o",
+        (Side::White, true, false) => "O",
+    }
+}
+
+// a move the way it's written down, by the squares it went through
+// ("c6-d5" for a step, "e3xc5xa7" for a run of jumps)
+fn notation(path: &[Point]) -> Option<String> {
+    let [from, to, ..] = path else {
+        return None;
+    };
+    let sep = match from.0.abs_diff(to.0) {
+        1 => "-",
+        _ => "x",
+    };
+    let squares: Vec<String> = path
+        .iter()
+        .map(|&Point(i, j)| format!("{}{}", (b'a' + j as u8) as char, i + 1))
+        .collect();
+    Some(squares.join(sep))
+}
+
+// Pretty-print
+impl fmt::Display for CheckersApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.board_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.turn_line())?;
+        write!(f, "{}\r\n", self.count_line())?;
+        write!(f, "{}\r\n", self.score_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", checkersui::help_text())
+    }
+}
+
+impl Game for CheckersApp {
+    fn name(&self) -> &'static str {
+        "checkers"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != CheckersUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            CheckersUIAction::Quit => self.over = true,
+            CheckersUIAction::NewGame => self.new_game(),
+            CheckersUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the board");
+                }
+            }
+            // (nothing moves on the computer's turn)
+            CheckersUIAction::Select if self.is_computers_turn() => {}
+            CheckersUIAction::Select => {
+                self.message = "".to_string().reset();
+                self.select();
+            }
+            // (a piece partway through its jumps has to finish them)
+            CheckersUIAction::Cancel if self.game.jumping().is_none() => self.selected = None,
+            CheckersUIAction::Cancel | CheckersUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // let the computer take a step, when it's their turn
+    fn tick(&mut self) {
+        if !self.is_computers_turn() {
+            return;
+        }
+        if let Some((from, to)) = self
+            .computer
+            .as_mut()
+            .and_then(|computer| computer.choose_action(&self.game))
+        {
+            self.step(from, to);
+            self.redraw.set(true);
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(COMPUTER_TICK)
+    }
+
+    // the board, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = checkersui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max((SQUARE_COLS * (SIZE + 1)) as u16),
+            (SIZE + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play checkers against the computer, or two people at one keyboard
+pub fn run(args: Args) -> Result<(), String> {
+    let config: CheckersConfig = common::cli::setup(&args.common, "checkers")?;
+
+    let depth = args.depth.or(config.depth).unwrap_or(DEFAULT_DEPTH);
+    if !(1..=MAX_DEPTH).contains(&depth) {
+        return Err(format!(
+            "bad [checkers] config: depth {depth} (goes from 1 to {MAX_DEPTH})"
+        ));
+    }
+    let computer = match args.two_player || config.two_player {
+        true => None,
+        false => {
+            let seed = args.seed.unwrap_or_else(rand::random);
+            info!("checkers seed {seed}, depth {depth}");
+            Some(AlphaBeta::new(depth, seed))
+        }
+    };
+    let computer_side = match args.second {
+        true => Side::Black,
+        false => Side::White,
+    };
+    let mut app = CheckersApp::new(computer, computer_side);
+    app.ui
+        .bindings
+        .remap(&config.keys, &checkersui::ACTIONS)
+        .map_err(|e| format!("bad [checkers.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::CheckersApp;
+use crate::checkersui;
+
+impl TuiGame for CheckersApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .board_lines()
+            .iter()
+            .map(|line| line.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows);
+
+        let status = StatusBar::new(checkersui::help_text())
+            .line(self.turn_line())
+            .line(self.count_line())
+            .line(self.score_line())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
@@ -0,0 +1,354 @@
+use common::{Core, Point};
+use log::debug;
+
+/// Board side, in squares
+pub const SIZE: usize = 8;
+
+// rows of men each side starts with, at its own end
+const START_ROWS: usize = 3;
+
+// moves in a row (counting both sides') without a capture or a man moving
+// before the game's drawn: forty each
+const QUIET_LIMIT: u32 = 80;
+
+/// A player's pieces (black starts at the bottom of the board, and always
+/// goes first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Black,
+    White,
+}
+
+impl Side {
+    /// The other player's pieces
+    pub fn other(self) -> Self {
+        match self {
+            Side::Black => Side::White,
+            Side::White => Side::Black,
+        }
+    }
+
+    /// Rows a man steps forward by
+    pub fn forward(self) -> isize {
+        match self {
+            Side::Black => -1,
+            Side::White => 1,
+        }
+    }
+
+    /// Row a man is crowned on reaching: the far end of the board
+    pub fn crown_row(self) -> usize {
+        match self {
+            Side::Black => 0,
+            Side::White => SIZE - 1,
+        }
+    }
+}
+
+/// A piece on the board: a man, which only goes forward, or a king, which
+/// goes either way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece {
+    pub side: Side,
+    pub king: bool,
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveResult {
+    /// the piece moved (or jumped), and it's the other side's turn
+    Moved,
+    /// the piece jumped, and has to jump again from where it landed
+    JumpAgain,
+    /// as Moved, and the piece reached the far end and was crowned (which
+    /// ends the turn, even with a jump still to make)
+    Crowned,
+    /// the other side has no piece left that can move, so this side wins
+    Won(Side),
+    /// forty moves each without a capture or a man moving
+    Draw,
+    /// the piece can't go there (or there's no piece of the side to move)
+    Illegal,
+    /// the piece could step there, but a jump has to be made instead
+    MustJump,
+    /// the game has already ended
+    GameOver,
+}
+
+/// A game of checkers (English draughts): the pieces on the dark squares,
+/// whose turn it is, and the piece partway through a run of jumps
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkers {
+    board: [[Option<Piece>; SIZE]; SIZE],
+    turn: Side,
+    jumping: Option<Point>, // the piece that has to jump again this turn
+    last_move: Vec<Point>,  // squares the last piece to move went through
+    quiet: u32,             // moves since a capture or a man moving
+    winner: Option<Side>,
+    drawn: bool,
+}
+
+impl Checkers {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// The usual start (twelve men each, on the dark squares of the three
+    /// rows at their end), with black to move
+    pub fn new() -> Self {
+        let mut board = [[None; SIZE]; SIZE];
+        for (i, row) in board.iter_mut().enumerate() {
+            let side = match i {
+                _ if i < START_ROWS => Side::White,
+                _ if i >= SIZE - START_ROWS => Side::Black,
+                _ => continue,
+            };
+            for (j, square) in row.iter_mut().enumerate() {
+                if is_dark(Point(i, j)) {
+                    *square = Some(Piece { side, king: false });
+                }
+            }
+        }
+        Self {
+            board,
+            turn: Side::Black,
+            jumping: None,
+            last_move: Vec::new(),
+            quiet: 0,
+            winner: None,
+            drawn: false,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Move the piece on [from] to [to]: a step forward diagonally (either
+    /// way for a king) onto an empty square, or a jump over one of the
+    /// other side's pieces next to it, which is taken off. Jumps have to
+    /// be made when there are any, and once a piece starts jumping it
+    /// carries on as long as it can
+    pub fn step(&mut self, from: Point, to: Point) -> MoveResult {
+        if self.is_over() {
+            return MoveResult::GameOver;
+        }
+        if !self.legal_steps().contains(&(from, to)) {
+            let is_step = self.cell(from).is_some_and(|piece| piece.side == self.turn)
+                && self.moves_from(from).0.contains(&to);
+            return match is_step && self.must_jump() {
+                true => MoveResult::MustJump,
+                false => MoveResult::Illegal,
+            };
+        }
+        let Point(i, j) = from;
+        let mut piece = self.board[i][j].take().expect("a piece moves");
+        let jumped = i.abs_diff(to.0) == 2;
+        if jumped {
+            let Point(mi, mj) = Point((i + to.0) / 2, (j + to.1) / 2);
+            self.board[mi][mj] = None;
+        }
+        let crowned = !piece.king && to.0 == piece.side.crown_row();
+        piece.king |= crowned;
+        self.board[to.0][to.1] = Some(piece);
+
+        match self.jumping {
+            Some(_) => self.last_move.push(to),
+            None => self.last_move = vec![from, to],
+        }
+        if jumped || !piece.king || crowned {
+            self.quiet = 0;
+        } else {
+            self.quiet += 1;
+        }
+
+        if jumped && !crowned && !self.jumps_from(to).is_empty() {
+            self.jumping = Some(to);
+            return MoveResult::JumpAgain;
+        }
+        self.jumping = None;
+        self.turn = piece.side.other();
+        if self.legal_steps().is_empty() {
+            debug!("{:?} has no move left", self.turn);
+            self.winner = Some(piece.side);
+            return MoveResult::Won(piece.side);
+        }
+        if self.quiet >= QUIET_LIMIT {
+            debug!("drawn after {} quiet moves", self.quiet);
+            self.drawn = true;
+            return MoveResult::Draw;
+        }
+        match crowned {
+            true => MoveResult::Crowned,
+            false => MoveResult::Moved,
+        }
+    }
+
+    /// Every (from, to) the side to move could make next: only jumps if
+    /// there are any, and only the jumping piece's partway through a turn
+    pub fn legal_steps(&self) -> Vec<(Point, Point)> {
+        if self.is_over() {
+            return Vec::new();
+        }
+        if let Some(from) = self.jumping {
+            return self
+                .jumps_from(from)
+                .into_iter()
+                .map(|to| (from, to))
+                .collect();
+        }
+        let pieces: Vec<Point> = squares()
+            .filter(|&p| self.cell(p).is_some_and(|piece| piece.side == self.turn))
+            .collect();
+        let jumps: Vec<_> = pieces
+            .iter()
+            .flat_map(|&from| self.jumps_from(from).into_iter().map(move |to| (from, to)))
+            .collect();
+        if !jumps.is_empty() {
+            return jumps;
+        }
+        pieces
+            .iter()
+            .flat_map(|&from| {
+                self.moves_from(from)
+                    .0
+                    .into_iter()
+                    .map(move |to| (from, to))
+            })
+            .collect()
+    }
+
+    /// Squares the piece on [from] could go to next
+    pub fn legal_targets(&self, from: Point) -> Vec<Point> {
+        self.legal_steps()
+            .into_iter()
+            .filter(|&(p, _)| p == from)
+            .map(|(_, to)| to)
+            .collect()
+    }
+
+    /// Whether the side to move has a jump to make (which they have to)
+    pub fn must_jump(&self) -> bool {
+        self.legal_steps()
+            .first()
+            .is_some_and(|&(from, to)| from.0.abs_diff(to.0) == 2)
+    }
+
+    /// Whose turn it is (once the game's over, the side that couldn't move)
+    pub fn turn(&self) -> Side {
+        self.turn
+    }
+
+    /// The piece partway through a run of jumps, which has to jump again
+    pub fn jumping(&self) -> Option<Point> {
+        self.jumping
+    }
+
+    /// Pieces of [side] on the board, as (men, kings)
+    pub fn count(&self, side: Side) -> (usize, usize) {
+        squares()
+            .filter_map(|p| self.cell(p).filter(|piece| piece.side == side))
+            .fold((0, 0), |(men, kings), piece| match piece.king {
+                true => (men, kings + 1),
+                false => (men + 1, kings),
+            })
+    }
+
+    /// The side that left the other with no move (None while the game's
+    /// on, or if it was drawn)
+    pub fn winner(&self) -> Option<Side> {
+        self.winner
+    }
+
+    /// Squares the last piece to move went through, from where it started
+    /// (more than two for a run of jumps)
+    pub fn last_move(&self) -> &[Point] {
+        &self.last_move
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // (empty squares the piece on [from] could step to, the ones it could
+    // jump to), whatever the rest of the board has to do
+    fn moves_from(&self, from: Point) -> (Vec<Point>, Vec<Point>) {
+        let mut steps = Vec::new();
+        let mut jumps = Vec::new();
+        let Some(piece) = self.cell(from) else {
+            return (steps, jumps);
+        };
+        let forward = piece.side.forward();
+        let rows: &[isize] = match piece.king {
+            true => &[-1, 1],
+            false => &[forward],
+        };
+        for &di in rows {
+            for dj in [-1, 1] {
+                let Some(next) = offset(from, di, dj) else {
+                    continue;
+                };
+                match self.cell(next) {
+                    None => steps.push(next),
+                    Some(other) if other.side != piece.side => {
+                        if let Some(land) = offset(next, di, dj).filter(|&q| self.cell(q).is_none())
+                        {
+                            jumps.push(land);
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        (steps, jumps)
+    }
+
+    fn jumps_from(&self, from: Point) -> Vec<Point> {
+        self.moves_from(from).1
+    }
+}
+
+impl Default for Checkers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Core for Checkers {
+    type Action = (Point, Point);
+    type Outcome = MoveResult;
+    type Cell = Option<Piece>;
+
+    fn size(&self) -> (usize, usize) {
+        (SIZE, SIZE)
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Option<Piece> {
+        self.board[i][j]
+    }
+
+    fn act(&mut self, (from, to): (Point, Point)) -> MoveResult {
+        self.step(from, to)
+    }
+
+    fn is_over(&self) -> bool {
+        self.winner.is_some() || self.drawn
+    }
+}
+
+/// Whether [p] is one of the dark squares the pieces are played on
+pub fn is_dark(Point(i, j): Point) -> bool {
+    (i + j) % 2 == 1
+}
+
+// every square on the board, a row at a time
+fn squares() -> impl Iterator<Item = Point> {
+    (0..SIZE).flat_map(|i| (0..SIZE).map(move |j| Point(i, j)))
+}
+
+// square [di] rows and [dj] columns from [p], if that's on the board
+fn offset(Point(i, j): Point, di: isize, dj: isize) -> Option<Point> {
+    let i = i.checked_add_signed(di).filter(|&i| i < SIZE)?;
+    let j = j.checked_add_signed(dj).filter(|&j| j < SIZE)?;
+    Some(Point(i, j))
+}
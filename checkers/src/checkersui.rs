@@ -0,0 +1,131 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+use crate::checkers::SIZE;
+
+/// Help lines under the board, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("checkers.help")
+}
+
+/// Where the cursor starts each game: on one of black's men, in the front
+/// row at the bottom
+pub const START_CURSOR: Point = Point(SIZE - 3, 0);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckersUIAction {
+    Wait,
+    Move(MoveDirection),
+    Select,
+    Cancel,
+    NewGame,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, CheckersUIAction); 10] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        CheckersUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        CheckersUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        CheckersUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        CheckersUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), CheckersUIAction::Select),
+    (KeyBinding::key(KeyCode::Enter), CheckersUIAction::Select),
+    (KeyBinding::key(KeyCode::Esc), CheckersUIAction::Cancel),
+    (KeyBinding::char('n'), CheckersUIAction::NewGame),
+    (KeyBinding::char('q'), CheckersUIAction::Quit),
+    (KeyBinding::ctrl('c'), CheckersUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, CheckersUIAction); 8] = [
+    ("up", CheckersUIAction::Move(MoveDirection::Up)),
+    ("down", CheckersUIAction::Move(MoveDirection::Down)),
+    ("left", CheckersUIAction::Move(MoveDirection::Left)),
+    ("right", CheckersUIAction::Move(MoveDirection::Right)),
+    ("select", CheckersUIAction::Select),
+    ("cancel", CheckersUIAction::Cancel),
+    ("new_game", CheckersUIAction::NewGame),
+    ("quit", CheckersUIAction::Quit),
+];
+
+pub struct CheckersUI {
+    cursor: Point,
+    pub bindings: Bindings<CheckersUIAction>,
+}
+
+impl CheckersUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cursor starting on the front row of black's men
+    pub fn new() -> Self {
+        Self {
+            cursor: START_CURSOR,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> CheckersUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(CheckersUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> CheckersUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => CheckersUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a square, unless that's off the board
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let Point(i, j) = self.cursor;
+        let (i, j) = match dir {
+            MoveDirection::Up => (i.checked_sub(1), Some(j)),
+            MoveDirection::Down => (Some(i + 1).filter(|&i| i < SIZE), Some(j)),
+            MoveDirection::Left => (Some(i), j.checked_sub(1)),
+            MoveDirection::Right => (Some(i), Some(j + 1).filter(|&j| j < SIZE)),
+        };
+        let (Some(i), Some(j)) = (i, j) else {
+            return Err("already at the edge".into());
+        };
+        self.cursor = Point(i, j);
+        Ok(())
+    }
+
+    pub fn set_cursor(&mut self, p: Point) {
+        self.cursor = p;
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+}
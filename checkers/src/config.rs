@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [checkers] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CheckersConfig {
+    /// two people taking turns at the same keyboard, instead of playing the computer
+    pub two_player: bool,
+    /// moves the computer looks ahead, from 1 to 8
+    pub depth: Option<u32>,
+    /// keys for each action, e.g. select = ["space", "enter"]
+    pub keys: KeyMap,
+}
@@ -0,0 +1,22 @@
+//! Checkers (English draughts): the game core (men and kings on the dark
+//! squares of an 8x8 board, with jumps that have to be taken), an
+//! alpha-beta computer player, plus the crossterm frontend for playing it
+//! in a terminal, against the computer or another person
+
+mod alphabeta;
+mod checkers;
+pub use alphabeta::{AlphaBeta, MAX_DEPTH};
+pub use checkers::{is_dark, Checkers, MoveResult, Piece, Side, SIZE};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod checkersui;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+pub use app::{run, CheckersApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use log::{info, warn};
+
+use crate::replay;
+use crate::stats::Stats;
+
+/// Something worth celebrating, unlocked once and kept in the stats file
+#[derive(Debug, PartialEq)]
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// Every achievement there is, grouped by game
+pub static ACHIEVEMENTS: [Achievement; 7] = [
+    Achievement {
+        id: "mines-win",
+        name: "Clean sweep",
+        description: "win a game of Minesweeper",
+    },
+    Achievement {
+        id: "mines-expert",
+        name: "Expert",
+        description: "win an expert Minesweeper board",
+    },
+    Achievement {
+        id: "mines-expert-100s",
+        name: "Speed sweeper",
+        description: "win an expert Minesweeper board in under 100s",
+    },
+    Achievement {
+        id: "life-cycle",
+        name: "Groundhog day",
+        description: "watch Life settle into a cycle",
+    },
+    Achievement {
+        id: "life-10k",
+        name: "Long life",
+        description: "run Life for 10,000 generations",
+    },
+    Achievement {
+        id: "langton-highway",
+        name: "Highway builder",
+        description: "watch an ant start building a highway",
+    },
+    Achievement {
+        id: "langton-100k",
+        name: "Marathon ant",
+        description: "run Langton's Ant for 100,000 steps",
+    },
+];
+
+// achievements unlocked since the game loop last asked, waiting to be shown
+static UNLOCKED: Mutex<Vec<&'static Achievement>> = Mutex::new(Vec::new());
+
+impl fmt::Display for Achievement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.description)
+    }
+}
+
+/// Achievement with the given id (which has to be one of [ACHIEVEMENTS])
+pub fn get(id: &str) -> &'static Achievement {
+    ACHIEVEMENTS
+        .iter()
+        .find(|achievement| achievement.id == id)
+        .unwrap_or_else(|| panic!("no achievement called '{id}'"))
+}
+
+/// Unlock an achievement, saving it in the stats file and queueing a toast for
+/// it if it's new. Returns whether it was (replays never unlock anything)
+pub fn unlock(id: &str) -> bool {
+    let achievement = get(id);
+    if replay::is_replaying() {
+        return false;
+    }
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    let unlocked = Stats::update(|stats| {
+        if stats.achievements.contains_key(id) {
+            return false;
+        }
+        stats.achievements.insert(id.to_string(), now.as_secs());
+        true
+    });
+    match unlocked {
+        Ok(true) => {}
+        Ok(false) => return false,
+        // (like the rest of the stats, achievements aren't worth failing a game over)
+        Err(e) => {
+            warn!("failed to unlock {id}: {e}");
+            return false;
+        }
+    }
+    info!("unlocked achievement {id}");
+    UNLOCKED.lock().unwrap().push(achievement);
+    true
+}
+
+/// Achievements unlocked since the last call, oldest first
+pub fn take_unlocked() -> Vec<&'static Achievement> {
+    std::mem::take(&mut *UNLOCKED.lock().unwrap())
+}
@@ -0,0 +1,28 @@
+use std::io::{stdout, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::debug;
+
+// set by --bell (or `bell = true` in the config file)
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn on the terminal bell for things worth hearing about (losing, winning,
+/// invalid moves, achievements), which are easy to miss on a silent screen
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Ring the terminal bell, if it's been turned on. [why] is only for the log
+pub fn ring(why: &str) {
+    if !enabled() {
+        return;
+    }
+    debug!("bell: {why}");
+    let mut stdout = stdout();
+    // (a bell that doesn't ring isn't worth stopping the game over)
+    write!(stdout, "\x07").and_then(|()| stdout.flush()).ok();
+}
@@ -0,0 +1,42 @@
+use crossterm::style::{Color, StyledContent, Stylize};
+
+use crate::point::Point;
+
+const HALF_BLOCK: char = '▀';
+
+/// Terminal rows taken up by a grid [height] cells tall, drawn as half-blocks
+pub fn half_block_rows(height: usize) -> usize {
+    height.div_ceil(2)
+}
+
+/// Draw a (height x width) grid of cells colored by [color] as half-blocks,
+/// two cells (one above the other) to each character, as one list of styled
+/// characters per terminal row. The upper cell is drawn in the foreground,
+/// and the lower in the background (left blank below the last row of an
+/// odd-height grid)
+pub fn half_blocks(
+    height: usize,
+    width: usize,
+    color: impl Fn(Point) -> Color,
+) -> Vec<Vec<StyledContent<String>>> {
+    let lower_color = |i: usize, j: usize| {
+        if i < height {
+            color(Point(i, j))
+        } else {
+            Color::Reset
+        }
+    };
+    (0..height)
+        .step_by(2)
+        .map(|i| {
+            (0..width)
+                .map(|j| {
+                    HALF_BLOCK
+                        .to_string()
+                        .with(color(Point(i, j)))
+                        .on(lower_color(i + 1, j))
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,162 @@
+use std::io::{self, stdout, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use log::{debug, info, warn};
+
+use crate::terminal::TerminalGuard;
+
+// frames a spectator can fall behind by before it's cut off, so a slow
+// connection never holds up the game
+const BACKLOG: usize = 256;
+
+// how often the watcher checks for keys, between frames coming in
+const KEY_POLL: Duration = Duration::from_millis(50);
+
+// a spectator's copy of the screen starts out cleared
+const CLEAR_SCREEN: &[u8] = b"\x1b[H\x1b[2J";
+
+// where frames go to be sent on to a spectator
+type Spectator = SyncSender<Arc<[u8]>>;
+
+// None when not broadcasting
+static SPECTATORS: Mutex<Option<Vec<Spectator>>> = Mutex::new(None);
+
+// set when someone starts watching, so the whole screen gets drawn again for
+// them (games otherwise only draw what changed)
+static JOINED: AtomicBool = AtomicBool::new(false);
+
+/// Stream everything games draw from now on to anyone who connects to
+/// [addr] (with the watch subcommand, or anything else that shows raw
+/// terminal output, like netcat), until [stop]
+pub fn start(addr: &str) -> Result<(), String> {
+    let listener =
+        TcpListener::bind(addr).map_err(|e| format!("failed to listen on {addr}: {e}"))?;
+    info!("broadcasting on {addr}");
+    *SPECTATORS.lock().unwrap() = Some(Vec::new());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => join(stream),
+                Err(e) => warn!("spectator failed to connect: {e}"),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Stop broadcasting, disconnecting every spectator
+pub fn stop() {
+    *SPECTATORS.lock().unwrap() = None;
+}
+
+/// Whether what's drawn is being broadcast
+pub fn is_broadcasting() -> bool {
+    SPECTATORS.lock().unwrap().is_some()
+}
+
+/// Whether anyone started watching since the last call (when the screen
+/// needs drawing in full again)
+pub fn take_joined() -> bool {
+    JOINED.swap(false, Ordering::Relaxed)
+}
+
+/// Send a frame to every spectator, dropping any that have gone away or
+/// fallen too far behind
+pub fn send(frame: &[u8]) {
+    let mut spectators = SPECTATORS.lock().unwrap();
+    let Some(spectators) = spectators.as_mut().filter(|s| !s.is_empty()) else {
+        return;
+    };
+    let frame: Arc<[u8]> = frame.into();
+    let n_spectators = spectators.len();
+    spectators.retain(|spectator| spectator.try_send(frame.clone()).is_ok());
+    if spectators.len() < n_spectators {
+        debug!("{} spectators left", n_spectators - spectators.len());
+    }
+}
+
+// start sending frames to a new spectator, from a thread of its own
+fn join(mut stream: TcpStream) {
+    let peer = stream
+        .peer_addr()
+        .map_or_else(|_| "?".to_string(), |addr| addr.to_string());
+    let (sender, frames) = mpsc::sync_channel::<Arc<[u8]>>(BACKLOG);
+    match &mut *SPECTATORS.lock().unwrap() {
+        Some(spectators) => spectators.push(sender),
+        None => return,
+    }
+    info!("spectator {peer} joined");
+    JOINED.store(true, Ordering::Relaxed);
+    thread::spawn(move || {
+        stream.set_nodelay(true).ok();
+        let sent = stream
+            .write_all(CLEAR_SCREEN)
+            .and_then(|()| frames.iter().try_for_each(|frame| stream.write_all(&frame)));
+        match sent {
+            Ok(()) => info!("stopped sending to spectator {peer}"),
+            Err(e) => info!("spectator {peer} left: {e}"),
+        }
+    });
+}
+
+/// Watch a game broadcast from [addr] (see [start]), until it ends or a
+/// <q>, <esc> or <ctrl-c> stops watching
+pub fn watch(addr: &str) -> Result<(), String> {
+    let mut stream =
+        TcpStream::connect(addr).map_err(|e| format!("failed to connect to {addr}: {e}"))?;
+    let ended = Arc::new(AtomicBool::new(false));
+    let guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+
+    // frames are drawn as they come in, while this thread waits for keys
+    thread::spawn({
+        let ended = ended.clone();
+        move || {
+            let mut buf = [0; 4096];
+            let mut stdout = stdout();
+            loop {
+                let n = match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+                if stdout
+                    .write_all(&buf[..n])
+                    .and_then(|()| stdout.flush())
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            ended.store(true, Ordering::Relaxed);
+        }
+    });
+    let result = wait_for_quit(&ended);
+    drop(guard);
+    result.map_err(|e| format!("failed to read keys: {e}"))?;
+    if ended.load(Ordering::Relaxed) {
+        println!("the broadcast from {addr} has ended");
+    }
+    Ok(())
+}
+
+// wait until [ended] is set or a key to quit is pressed
+fn wait_for_quit(ended: &AtomicBool) -> io::Result<()> {
+    while !ended.load(Ordering::Relaxed) {
+        if !event::poll(KEY_POLL)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            let ctrl_c =
+                key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+            if ctrl_c || matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::io::{self, Stdout, Write};
+use std::mem;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::json;
+
+use crate::broadcast;
+
+/// Every frame drawn during a game, with when it was drawn, to be saved as
+/// an asciinema (v2) cast
+pub struct Cast {
+    width: u16,
+    height: u16,
+    timestamp: u64, // unix time the recording started at
+    started: Instant,
+    frames: Vec<(Duration, String)>,
+}
+
+static CAST: Mutex<Option<Cast>> = Mutex::new(None);
+
+/// Terminal output, copied into the cast being recorded (if any) and out to
+/// spectators (when broadcasting) one frame (everything written between
+/// flushes) at a time
+pub struct Output {
+    stdout: Stdout,
+    frame: Option<Vec<u8>>, // None when neither recording nor broadcasting
+}
+
+impl Cast {
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Write the cast out: a header line, then a line per frame
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut text = json!({
+            "version": 2,
+            "width": self.width,
+            "height": self.height,
+            "timestamp": self.timestamp,
+        })
+        .to_string();
+        for (time, frame) in &self.frames {
+            text += "\n";
+            text += &json!([time.as_secs_f64(), "o", frame]).to_string();
+        }
+        text += "\n";
+        fs::write(path, text).map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+}
+
+impl Output {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        let recording = CAST.lock().unwrap().is_some();
+        Self {
+            stdout: io::stdout(),
+            frame: (recording || broadcast::is_broadcasting()).then(Vec::new),
+        }
+    }
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.stdout.write(buf)?;
+        if let Some(frame) = &mut self.frame {
+            frame.extend_from_slice(&buf[..n]);
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stdout.flush()?;
+        let Some(frame) = self.frame.as_mut().filter(|frame| !frame.is_empty()) else {
+            return Ok(());
+        };
+        let frame = mem::take(frame);
+        broadcast::send(&frame);
+        if let Some(cast) = &mut *CAST.lock().unwrap() {
+            let text = String::from_utf8_lossy(&frame).into_owned();
+            cast.frames.push((cast.started.elapsed(), text));
+        }
+        Ok(())
+    }
+}
+
+/// Record everything games draw from now on, on a [width]x[height] terminal
+pub fn start(width: u16, height: u16) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    *CAST.lock().unwrap() = Some(Cast {
+        width,
+        height,
+        timestamp,
+        started: Instant::now(),
+        frames: Vec::new(),
+    });
+}
+
+/// Stop recording, returning what was recorded
+pub fn stop() -> Option<Cast> {
+    CAST.lock().unwrap().take()
+}
@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+
+use crate::config::Config;
+use crate::theme::{self, Theme};
+use crate::{bell, style};
+
+/// Flags every game takes, for its `Args` to `#[command(flatten)]`
+#[derive(clap::Args, Debug, Default)]
+pub struct CommonArgs {
+    /// Draw without colors (also turned off by setting NO_COLOR)
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Ring the terminal bell on losing, winning and achievements
+    #[arg(long)]
+    pub bell: bool,
+
+    /// Color theme: default, solarized, gruvbox or mono
+    #[arg(long)]
+    pub theme: Option<&'static Theme>,
+
+    /// Config file to read settings from, instead of
+    /// ~/.config/crossterm-games/config.toml (flags override it)
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+/// A game's command-line arguments, as far as a launcher needs to know them
+pub trait GameArgs {
+    fn common(&mut self) -> &mut CommonArgs;
+
+    /// The game's --seed, for games that use random numbers
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        None
+    }
+}
+
+/// Set up everything [args] (and the game's [section] of the config file)
+/// cover, before the game starts, and return the rest of that section
+pub fn setup<T: DeserializeOwned + Default>(args: &CommonArgs, section: &str) -> Result<T, String> {
+    if args.no_color {
+        style::disable_color();
+    }
+    let (common, config) = Config::load(args.config.as_deref())?.game_section(section)?;
+    if let Some(theme) = args.theme.or(common.theme) {
+        theme::set(theme);
+    }
+    if args.bell || common.bell {
+        bell::enable();
+    }
+    Ok(config)
+}
@@ -0,0 +1,36 @@
+use std::io::{stdout, Write};
+
+use log::debug;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Put [text] on the clipboard, with the OSC 52 escape sequence. It's the
+/// terminal that sets the clipboard, so it works over SSH too, but only in
+/// terminals that support it (others ignore it, and inside tmux it needs
+/// `set -g set-clipboard on`)
+pub fn copy(text: &str) -> Result<(), String> {
+    debug!("copying {} bytes to the clipboard", text.len());
+    let mut stdout = stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))
+        .and_then(|()| stdout.flush())
+        .map_err(|e| format!("failed to copy to the clipboard: {e}"))
+}
+
+// standard base64, padded with '='
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (k, &b)| n | (b as u32) << (16 - 8 * k));
+        for k in 0..4 {
+            if k <= chunk.len() {
+                encoded.push(BASE64[(n >> (18 - 6 * k) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
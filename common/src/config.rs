@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer};
+
+use crate::theme::Theme;
+
+// settings every game's section can have (see [CommonConfig])
+const COMMON_KEYS: [&str; 2] = ["theme", "bell"];
+
+/// Settings from the config file: one table per game, e.g.
+///
+/// ```toml
+/// [langton]
+/// tick_ms = 5
+/// palette = "colors"
+///
+/// [langton.keys]
+/// quit = ["x", "ctrl+c"]
+/// ```
+#[derive(Debug, Default)]
+pub struct Config {
+    table: toml::Table,
+}
+
+impl Config {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Read the config file at [path], or at the default path if not given.
+    /// A missing default config file is fine (and gives an empty config).
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => {
+                return Ok(Self::default())
+            }
+            Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+        };
+        let table = text
+            .parse()
+            .map_err(|e| format!("bad config file {}: {}", path.display(), one_line(e)))?;
+        Ok(Self { table })
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Settings for one game (all defaults if the file has no section for it)
+    pub fn section<T: DeserializeOwned + Default>(&self, name: &str) -> Result<T, String> {
+        match self.table.get(name) {
+            Some(section) => parse_section(name, section.clone()),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// Same as [Config::section], with the settings every game has split off
+    pub fn game_section<T: DeserializeOwned + Default>(
+        &self,
+        name: &str,
+    ) -> Result<(CommonConfig, T), String> {
+        let Some(toml::Value::Table(section)) = self.table.get(name) else {
+            return Ok((self.section(name)?, self.section(name)?));
+        };
+        let (common, rest): (toml::Table, toml::Table) = section
+            .clone()
+            .into_iter()
+            .partition(|(key, _)| COMMON_KEYS.contains(&key.as_str()));
+        Ok((
+            parse_section(name, common.into())?,
+            parse_section(name, rest.into())?,
+        ))
+    }
+}
+
+/// Settings from every game's section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommonConfig {
+    /// color theme, e.g. "gruvbox"
+    #[serde(deserialize_with = "from_str")]
+    pub theme: Option<&'static Theme>,
+    /// ring the terminal bell for things worth hearing about
+    pub bell: bool,
+}
+
+/// `$XDG_CONFIG_HOME/crossterm-games/config.toml`,
+/// or `~/.config/crossterm-games/config.toml`
+pub fn default_path() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_dir.join("crossterm-games").join("config.toml"))
+}
+
+// settings from the [name] section
+fn parse_section<T: DeserializeOwned>(name: &str, section: toml::Value) -> Result<T, String> {
+    section
+        .try_into()
+        .map_err(|e| format!("bad [{name}] config: {}", one_line(e)))
+}
+
+// toml errors can span several lines (with a snippet of the file)
+fn one_line(e: impl fmt::Display) -> String {
+    e.to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Key remappings from a `[<game>.keys]` table: action name -> key(s)
+pub type KeyMap = HashMap<String, Keys>;
+
+/// One key, or a list of keys
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Keys {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Keys {
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Keys::One(key) => std::slice::from_ref(key),
+            Keys::Many(keys) => keys,
+        }
+    }
+}
+
+/// Deserialize an optional setting with its [FromStr] impl (the same parsing
+/// its command-line flag uses), for `#[serde(deserialize_with = ...)]`
+pub fn from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| s.parse().map_err(D::Error::custom))
+        .transpose()
+}
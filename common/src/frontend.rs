@@ -0,0 +1,54 @@
+use crate::point::Point;
+
+/// A game's rules and state, with nothing about how it's shown or played,
+/// so the same game can run in a terminal, a web page, or anywhere else
+pub trait Core {
+    /// Something a player can do
+    type Action;
+    /// What a move led to (e.g. whether it won the game)
+    type Outcome;
+    /// Everything a frontend needs to know to draw one cell of the board
+    type Cell;
+
+    /// Board size, as (height, width)
+    fn size(&self) -> (usize, usize);
+
+    /// Cell at [p], counting from the top left of the board
+    fn cell(&self, p: Point) -> Self::Cell;
+
+    fn act(&mut self, action: Self::Action) -> Self::Outcome;
+
+    /// Move forward one time-step (for games that run by themselves)
+    fn tick(&mut self) {}
+
+    /// Whether the game has been won or lost (games that go on forever never are)
+    fn is_over(&self) -> bool {
+        false
+    }
+}
+
+/// Something that shows a game core to a player, and takes their moves
+pub trait Frontend<C: Core> {
+    /// Show the game as it is now
+    fn draw(&mut self, core: &C);
+
+    /// Moves the player has made since the last time they were asked for
+    fn actions(&mut self) -> Vec<C::Action>;
+
+    /// Hear what a move led to (e.g. to show a message)
+    fn outcome(&mut self, _outcome: C::Outcome) {}
+}
+
+/// One frame of a game: make the player's moves, run [ticks] time-steps,
+/// and redraw. Meant to be called whenever the frontend is ready to draw
+/// (e.g. on each animation frame in a browser)
+pub fn frame<C: Core, F: Frontend<C>>(core: &mut C, frontend: &mut F, ticks: u32) {
+    for action in frontend.actions() {
+        let outcome = core.act(action);
+        frontend.outcome(outcome);
+    }
+    for _ in 0..ticks {
+        core.tick();
+    }
+    frontend.draw(core);
+}
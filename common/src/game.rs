@@ -0,0 +1,380 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::time::{Duration, Instant, SystemTime};
+
+use crossterm::event::{Event, EventStream};
+use crossterm::style::{PrintStyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use futures::StreamExt;
+use log::{debug, info, warn};
+use tokio::runtime;
+use tokio::time::{self, MissedTickBehavior};
+
+use crate::achievements;
+use crate::bell;
+use crate::broadcast;
+use crate::cast::Output;
+use crate::input::{self, Macro, MacroAction, MacroKey};
+use crate::locale;
+use crate::replay::{self, Input};
+use crate::stats::{Outcome, Stats};
+use crate::style;
+use crate::terminal::text_width;
+use crate::theme::{self, Role};
+
+/// Interface shared by every game, so the code around them (launcher,
+/// recorder, renderers) doesn't need to know which one is running
+pub trait Game {
+    /// Short name the game is recorded under in the stats (same as its subcommand)
+    fn name(&self) -> &'static str;
+
+    /// React to a terminal event (keypress, mouse click, resize, ...).
+    /// After a resize the whole screen needs drawing again
+    fn handle_input(&mut self, event: Event);
+
+    /// Advance one tick of game time
+    fn tick(&mut self);
+
+    /// Draw the whole screen (called once a frame, so games that only change
+    /// now and then can skip drawing when nothing has)
+    fn render(&self, out: &mut dyn Write) -> io::Result<()>;
+
+    /// Forget what's on screen, so the next render draws everything (only
+    /// needed by games that skip drawing when nothing has changed)
+    fn invalidate(&self) {}
+
+    /// Time between ticks (None for games that only change on input)
+    fn tick_rate(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Smallest terminal (columns, rows) the game can be drawn in. While the
+    /// terminal is smaller, a "too small" screen is shown instead
+    fn min_size(&self) -> (u16, u16) {
+        (0, 0)
+    }
+
+    /// Whether the game is finished (won, lost or quit)
+    fn is_over(&self) -> bool;
+
+    /// How the game ended, once it's over
+    fn outcome(&self) -> Outcome {
+        Outcome::Quit
+    }
+}
+
+/// Frames per second [play] runs games at
+pub const DEFAULT_FPS: u32 = 60;
+
+// how long an achievement toast stays up
+const TOAST_TIME: Duration = Duration::from_secs(4);
+
+// how long the toast saying a macro is being recorded stays up (as long as
+// it's being recorded, or a day, at most)
+const RECORDING_TOAST_TIME: Duration = Duration::from_secs(24 * 60 * 60);
+
+// most ticks to catch up on in one frame, before giving up on the backlog
+const MAX_TICKS_PER_FRAME: u64 = 100;
+
+/// Fixed-timestep loop: input is handled as it comes in (from crossterm's
+/// async event stream, or a recorded session being replayed), and each frame
+/// runs however many ticks are due and draws once
+pub struct GameLoop {
+    frame: Duration,
+}
+
+impl GameLoop {
+    /// Loop running at [fps] frames per second
+    pub fn new(fps: u32) -> Self {
+        Self {
+            frame: Duration::from_secs(1) / fps.max(1),
+        }
+    }
+
+    /// Run a game until it's over
+    pub fn run(&self, game: &mut dyn Game) -> io::Result<()> {
+        let mut out = Output::new();
+        self.run_with(game, |game, too_small, toasts| {
+            if broadcast::take_joined() {
+                game.invalidate();
+            }
+            match too_small {
+                Some(min_size) => draw_too_small(&mut out, min_size)?,
+                None => {
+                    game.render(&mut out)?;
+                    draw_toasts(&mut out, toasts)?;
+                }
+            }
+            out.flush()
+        })
+    }
+
+    // run the loop, drawing each frame with [draw]. It's passed the game's
+    // minimum size when the terminal is too small for it, and the toasts to
+    // show on top of the game
+    pub(crate) fn run_with<G: Game + ?Sized>(
+        &self,
+        game: &mut G,
+        draw: impl FnMut(&G, Option<(u16, u16)>, &[String]) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()?;
+        let started = SystemTime::now();
+        let timer = Instant::now();
+        info!("{} started ({:?} per frame)", game.name(), self.frame);
+        runtime.block_on(self.run_async(game, draw))?;
+        info!(
+            "{} ended after {:.1}s: {:?}",
+            game.name(),
+            timer.elapsed().as_secs_f64(),
+            game.outcome()
+        );
+
+        // stats are nice to have, and not worth failing the game over
+        // (replays don't count, since they've been played already)
+        if !replay::is_replaying() {
+            if let Err(e) = Stats::record(game.name(), game.outcome(), started, timer.elapsed()) {
+                warn!("failed to record stats: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    // wait on terminal events and the frame timer at the same time, so input
+    // is handled as soon as it arrives, and frames go out on schedule. When
+    // replaying, recorded input is handed over between ticks instead, at the
+    // same tick it originally came in on
+    async fn run_async<G: Game + ?Sized>(
+        &self,
+        game: &mut G,
+        mut draw: impl FnMut(&G, Option<(u16, u16)>, &[String]) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let timer = Instant::now();
+        let mut ticks = 0; // ticks run so far
+        replay::start(game.name());
+        let mut replayed: Option<VecDeque<Input>> = replay::take_inputs().map(VecDeque::from);
+        let mut events = EventStream::new();
+        let mut frames = time::interval(self.frame);
+        frames.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut next_tick: Option<Instant> = None;
+        let mut size = terminal::size().ok();
+        let mut too_small_shown = false;
+        let mut toasts: Vec<(String, Instant)> = Vec::new(); // text, and when to take it down
+        let mut keys_macro = Macro::default();
+        loop {
+            tokio::select! {
+                event = events.next(), if !game.is_over() => {
+                    // (no more events means there's no terminal left to play in)
+                    let Some(event) = event.transpose()? else {
+                        warn!("terminal event stream ended");
+                        return Ok(());
+                    };
+                    debug!("input {event:?}");
+                    if let Event::Resize(cols, rows) = event {
+                        size = Some((cols, rows));
+                        too_small_shown = false;
+                    } else if replayed.is_some() {
+                        // any key stops a replay, and nothing else gets
+                        // through besides resizes
+                        if let Event::Key(_) = event {
+                            info!("replay stopped by a key press");
+                            return Ok(());
+                        }
+                        continue;
+                    }
+                    // (what's recorded is what the game got, macros played out)
+                    for event in play_macro(game, &mut keys_macro, event, &mut toasts) {
+                        replay::push(timer.elapsed(), ticks, &event);
+                        game.handle_input(event);
+                    }
+                }
+
+                _ = frames.tick() => {
+                    match &mut replayed {
+                        Some(inputs) => replay_due(game, &mut next_tick, &mut ticks, inputs, timer.elapsed()),
+                        None => ticks += run_due_ticks(game, &mut next_tick, MAX_TICKS_PER_FRAME),
+                    }
+                    update_toasts(game, &mut toasts);
+                    let (min_cols, min_rows) = game.min_size();
+                    if size.is_some_and(|(cols, rows)| cols < min_cols || rows < min_rows) {
+                        if !too_small_shown {
+                            debug!("terminal too small, need {min_cols}x{min_rows}");
+                            draw(game, Some((min_cols, min_rows)), &[])?;
+                            too_small_shown = true;
+                        }
+                    } else {
+                        let texts: Vec<_> = toasts.iter().map(|(text, _)| text.clone()).collect();
+                        draw(game, None, &texts)?;
+                    }
+                    if game.is_over() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Run a game until it's over at [DEFAULT_FPS], feeding it terminal events
+/// and ticking it at its tick rate
+pub fn play(game: &mut dyn Game) -> io::Result<()> {
+    GameLoop::new(DEFAULT_FPS).run(game)
+}
+
+// tick at a steady rate, independent of the frame rate, catching up on any
+// ticks that came due since the last frame (up to [max_ticks] of them).
+// Returns how many ran
+fn run_due_ticks<G: Game + ?Sized>(
+    game: &mut G,
+    next_tick: &mut Option<Instant>,
+    max_ticks: u64,
+) -> u64 {
+    let Some(rate) = game.tick_rate() else {
+        *next_tick = None;
+        return 0;
+    };
+    let deadline = next_tick.get_or_insert(Instant::now() + rate);
+    let mut n_ticks = 0;
+    while Instant::now() >= *deadline && n_ticks < max_ticks.min(MAX_TICKS_PER_FRAME) {
+        game.tick();
+        *deadline += rate;
+        n_ticks += 1;
+    }
+    if n_ticks == MAX_TICKS_PER_FRAME {
+        debug!("{n_ticks} ticks in one frame, dropping the rest of the backlog");
+        *deadline = Instant::now() + rate;
+    }
+    n_ticks
+}
+
+// run the due ticks of a replay, stopping at each recorded input to hand it
+// over once as many ticks have run as when it came in, and it's been as long
+fn replay_due<G: Game + ?Sized>(
+    game: &mut G,
+    next_tick: &mut Option<Instant>,
+    ticks: &mut u64,
+    inputs: &mut VecDeque<Input>,
+    elapsed: Duration,
+) {
+    loop {
+        let max_ticks = inputs.front().map_or(MAX_TICKS_PER_FRAME, |input| {
+            input.tick.saturating_sub(*ticks)
+        });
+        *ticks += run_due_ticks(game, next_tick, max_ticks);
+        let due =
+            |input: &Input| input.tick <= *ticks && Duration::from_millis(input.at) <= elapsed;
+        if !inputs.front().is_some_and(due) {
+            return;
+        }
+        if let Some(input) = inputs.pop_front() {
+            debug!("replayed input {:?} at tick {}", input.event, input.tick);
+            game.handle_input(input.event);
+        }
+    }
+}
+
+// the events [event] comes to once [keys_macro] has seen it: itself, the
+// keys of the macro it plays back, or none for the other macro keys (which
+// put up a toast saying what they did, as does the key filling a macro up)
+fn play_macro<G: Game + ?Sized>(
+    game: &G,
+    keys_macro: &mut Macro,
+    event: Event,
+    toasts: &mut Vec<(String, Instant)>,
+) -> Vec<Event> {
+    let Event::Key(key_event) = event else {
+        return vec![event];
+    };
+    let now = Instant::now();
+    let recording = format!(
+        "● {}{}",
+        locale::text("macro.recording"),
+        key_hint(MacroAction::Record, "macro.stop")
+    );
+    let handled = keys_macro.handle(key_event);
+    // (the key that fills a macro up is the game's too)
+    let passed = match handled {
+        MacroKey::Full(_) => vec![Event::Key(key_event)],
+        _ => Vec::new(),
+    };
+    let text = match handled {
+        MacroKey::Pass => return vec![event],
+        MacroKey::Ignored => return Vec::new(),
+        MacroKey::Started => {
+            toasts.push((recording, now + RECORDING_TOAST_TIME));
+            return Vec::new();
+        }
+        MacroKey::Stopped(0) => locale::text("macro.nothing").to_string(),
+        MacroKey::Stopped(n) | MacroKey::Full(n) => format!(
+            "{} {n}{}",
+            locale::text("macro.recorded"),
+            key_hint(MacroAction::Play, "macro.play")
+        ),
+        MacroKey::Play(keys) if !keys.is_empty() => {
+            info!("playing back a macro of {} keys", keys.len());
+            return keys.into_iter().map(Event::Key).collect();
+        }
+        MacroKey::Play(_) => format!(
+            "{}{}",
+            locale::text("macro.none"),
+            key_hint(MacroAction::Record, "macro.record")
+        ),
+    };
+    toasts.retain(|(text, _)| *text != recording);
+    game.invalidate();
+    toasts.push((text, now + TOAST_TIME));
+    passed
+}
+
+// "   <key> [label]", for the key bound to [action] (nothing if there isn't one)
+fn key_hint(action: MacroAction, label: &str) -> String {
+    input::macro_key(action).map_or(String::new(), |key| {
+        format!("   <{key}> {}", locale::text(label))
+    })
+}
+
+// put up toasts for newly unlocked achievements, and take down old ones
+// (making the game draw over where they were)
+fn update_toasts<G: Game + ?Sized>(game: &G, toasts: &mut Vec<(String, Instant)>) {
+    let now = Instant::now();
+    for achievement in achievements::take_unlocked() {
+        bell::ring("achievement");
+        toasts.push((format!("★ {achievement}"), now + TOAST_TIME));
+    }
+    let n_toasts = toasts.len();
+    toasts.retain(|&(_, until)| until > now);
+    if toasts.len() < n_toasts {
+        game.invalidate();
+    }
+}
+
+// toasts stacked in the top-right corner of the screen
+fn draw_toasts(out: &mut dyn Write, toasts: &[String]) -> io::Result<()> {
+    let cols = terminal::size().map_or(0, |(cols, _)| cols);
+    for (row, text) in toasts.iter().enumerate() {
+        let text = format!(" {text} ");
+        let col = cols.saturating_sub(text_width(&text));
+        let text = if style::color_enabled() {
+            text.with(theme::color(Role::Text))
+                .on(theme::color(Role::Success))
+        } else {
+            text.reverse()
+        };
+        out.queue(cursor::MoveTo(col, row as u16))?
+            .queue(PrintStyledContent(text.bold()))?;
+    }
+    Ok(())
+}
+
+// placeholder screen for when the game doesn't fit in the terminal
+fn draw_too_small(out: &mut dyn Write, (cols, rows): (u16, u16)) -> io::Result<()> {
+    out.queue(terminal::Clear(terminal::ClearType::All))?
+        .queue(cursor::MoveTo(0, 0))?;
+    write!(out, "{}", too_small_message((cols, rows)))
+}
+
+// what to show instead of a game that needs a (cols, rows) terminal
+pub(crate) fn too_small_message((cols, rows): (u16, u16)) -> String {
+    format!("terminal too small (need {cols}x{rows})")
+}
@@ -0,0 +1,175 @@
+use std::ops::{Deref, DerefMut, Index, IndexMut};
+
+use ndarray::{azip, s, Array2};
+
+use crate::point::Point;
+
+/// How the edges of a grid connect up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Topology {
+    /// nothing lies beyond the edges
+    Bounded,
+    /// edges wrap around to the opposite side
+    Torus,
+}
+
+/// Fixed-size 2D grid of cells, indexed by [Point].
+/// Derefs to the underlying ndarray for whole-grid operations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid2D<T> {
+    cells: Array2<T>,
+}
+
+impl<T> Grid2D<T> {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// (height x width) grid of default cells
+    pub fn new(height: usize, width: usize) -> Self
+    where
+        T: Clone + Default,
+    {
+        Self::from_array(Array2::default((height, width)))
+    }
+
+    pub fn from_array(cells: Array2<T>) -> Self {
+        Self { cells }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Cell at [p] (None if outside the grid)
+    pub fn get(&self, p: impl Into<Point>) -> Option<&T> {
+        self.cells.get(p.into().tuple())
+    }
+
+    pub fn get_mut(&mut self, p: impl Into<Point>) -> Option<&mut T> {
+        self.cells.get_mut(p.into().tuple())
+    }
+
+    /// The (up to 8) cells surrounding [p]
+    pub fn neighbors(
+        &self,
+        p: impl Into<Point>,
+        topology: Topology,
+    ) -> impl Iterator<Item = Point> {
+        let (gridh, gridw) = self.cells.dim();
+        let Point(i0, j0) = p.into();
+        let offsets = [-1isize, 0, 1];
+        offsets
+            .into_iter()
+            .flat_map(move |di| offsets.into_iter().map(move |dj| (di, dj)))
+            .filter(|&offset| offset != (0, 0))
+            .filter_map(move |(di, dj)| match topology {
+                Topology::Bounded => {
+                    let i = i0.checked_add_signed(di).filter(|&i| i < gridh)?;
+                    let j = j0.checked_add_signed(dj).filter(|&j| j < gridw)?;
+                    Some(Point(i, j))
+                }
+                Topology::Torus => Some(Point(
+                    (i0 + gridh).wrapping_add_signed(di) % gridh,
+                    (j0 + gridw).wrapping_add_signed(dj) % gridw,
+                )),
+            })
+    }
+
+    /// Count each cell's set neighbors into [counts], in one pass over a
+    /// padded copy of the grid built in [pad] (much faster than calling
+    /// [Grid2D::neighbors] on every cell). Keeping [pad] around between calls
+    /// saves allocating it each time (it's resized to fit if it doesn't)
+    #[allow(clippy::reversed_empty_ranges)]
+    pub fn count_neighbors(
+        &self,
+        topology: Topology,
+        pad: &mut Array2<u32>,
+        counts: &mut Grid2D<u32>,
+    ) where
+        T: Copy + Into<u32>,
+    {
+        let (gridh, gridw) = self.cells.dim();
+
+        // copy of the grid (as u32) with 1 layer of padding
+        if pad.dim() != (gridh + 2, gridw + 2) {
+            *pad = Array2::zeros((gridh + 2, gridw + 2));
+        }
+        azip!((p in pad.slice_mut(s![1..-1, 1..-1]), &c in &self.cells) *p = c.into());
+
+        // on a torus, padding is copied from the opposite edge
+        // (rows first, so that full columns carry the corners along),
+        // and otherwise it's zeros
+        if topology == Topology::Torus {
+            for j in 1..=gridw {
+                pad[(0, j)] = pad[(gridh, j)];
+                pad[(gridh + 1, j)] = pad[(1, j)];
+            }
+            for i in 0..gridh + 2 {
+                pad[(i, 0)] = pad[(i, gridw)];
+                pad[(i, gridw + 1)] = pad[(i, 1)];
+            }
+        } else {
+            pad.row_mut(0).fill(0);
+            pad.row_mut(gridh + 1).fill(0);
+            pad.column_mut(0).fill(0);
+            pad.column_mut(gridw + 1).fill(0);
+        }
+
+        // add up/down/left/right neighbors
+        azip!((
+            x in &mut counts.cells,
+            &d  in &pad.slice(s![2..  , 1..-1]), // lower neighbors
+            &u  in &pad.slice(s![ ..-2, 1..-1]), // upper neighbors
+            &r  in &pad.slice(s![1..-1, 2..  ]), // right neighbors
+            &l  in &pad.slice(s![1..-1,  ..-2]), // left neighbors
+        ) *x = d + u + l + r);
+
+        // add diagonal neighbors
+        azip!((
+            x in &mut counts.cells,
+            &dr in &pad.slice(s![2..  , 2..  ]), // lower-right neighbors
+            &ur in &pad.slice(s![ ..-2, 2..  ]), // upper-right neighbors
+            &dl in &pad.slice(s![2..  ,  ..-2]), // lower-left neighbors
+            &ul in &pad.slice(s![ ..-2,  ..-2])  // upper-left neighbors
+        ) *x = *x + dr + dl + ur + ul);
+    }
+
+    /// Same as [Grid2D::count_neighbors], into a new grid
+    pub fn neighbor_counts(&self, topology: Topology) -> Grid2D<u32>
+    where
+        T: Copy + Into<u32>,
+    {
+        let mut counts = Grid2D::from_array(Array2::zeros(self.cells.raw_dim()));
+        self.count_neighbors(topology, &mut Array2::zeros((0, 0)), &mut counts);
+        counts
+    }
+}
+
+impl<T> Deref for Grid2D<T> {
+    type Target = Array2<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.cells
+    }
+}
+
+impl<T> DerefMut for Grid2D<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.cells
+    }
+}
+
+impl<T, P: Into<Point>> Index<P> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, p: P) -> &T {
+        &self.cells[p.into().tuple()]
+    }
+}
+
+impl<T, P: Into<Point>> IndexMut<P> for Grid2D<T> {
+    fn index_mut(&mut self, p: P) -> &mut T {
+        &mut self.cells[p.into().tuple()]
+    }
+}
@@ -0,0 +1,358 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use log::debug;
+
+use crate::config::KeyMap;
+
+/// A key, plus any modifiers that have to be held down with it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// A key on its own
+    pub const fn key(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    /// A character key on its own
+    pub const fn char(c: char) -> Self {
+        Self::key(KeyCode::Char(c))
+    }
+
+    /// Ctrl + a character key
+    pub const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Whether a keypress triggers this binding. Shift is ignored for
+    /// characters, since it's already part of the character ('+' vs '=')
+    pub fn matches(&self, key_event: KeyEvent) -> bool {
+        let mut modifiers = key_event.modifiers;
+        if let KeyCode::Char(_) = key_event.code {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        self.code == key_event.code && self.modifiers == modifiers
+    }
+}
+
+// names for the keys that aren't single characters
+const KEY_NAMES: [(&str, KeyCode); 15] = [
+    ("space", KeyCode::Char(' ')),
+    ("enter", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("backtab", KeyCode::BackTab),
+    ("esc", KeyCode::Esc),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+];
+
+// Accepts a key name or character, with optional modifiers: "q", "space", "ctrl+c", "alt+up"
+impl FromStr for KeyBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // split off modifiers, leaving the key ("+" itself is a valid key)
+        let (modifier_names, key) = match s.rsplit_once('+') {
+            Some((rest, "")) => (rest.strip_suffix('+').unwrap_or(rest), "+"),
+            Some((rest, key)) => (rest, key),
+            None => ("", s),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier '{name}' in key '{s}'")),
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => KEY_NAMES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|&(_, code)| code)
+                .ok_or(format!("unknown key '{key}'"))?,
+        };
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl"),
+            (KeyModifiers::ALT, "alt"),
+            (KeyModifiers::SHIFT, "shift"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match KEY_NAMES.iter().find(|&&(_, code)| code == self.code) {
+            Some((name, _)) => write!(f, "{name}"),
+            None => match self.code {
+                KeyCode::Char(c) => write!(f, "{c}"),
+                code => write!(f, "{code:?}"),
+            },
+        }
+    }
+}
+
+/// Table of which keys trigger which of a game's actions
+#[derive(Debug, Clone)]
+pub struct Bindings<A> {
+    table: Vec<(KeyBinding, A)>,
+}
+
+impl<A: Clone> Bindings<A> {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(table: &[(KeyBinding, A)]) -> Self {
+        Self {
+            table: table.to_vec(),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Action triggered by a keypress (ignoring key releases and repeats)
+    pub fn action(&self, key_event: KeyEvent) -> Option<A> {
+        if key_event.kind != KeyEventKind::Press {
+            return None;
+        }
+        let action = self
+            .table
+            .iter()
+            .find(|(key, _)| key.matches(key_event))
+            .map(|(_, action)| action.clone());
+        if action.is_none() {
+            let key = KeyBinding {
+                code: key_event.code,
+                modifiers: key_event.modifiers,
+            };
+            debug!("nothing bound to {key}");
+        }
+        action
+    }
+
+    /// Make [key] trigger [action], replacing whatever it did before
+    pub fn bind(&mut self, key: KeyBinding, action: A) {
+        self.table.retain(|(bound, _)| *bound != key);
+        self.table.push((key, action));
+    }
+}
+
+impl<A: Clone + PartialEq> Bindings<A> {
+    /// Remove every key bound to [action]
+    pub fn unbind(&mut self, action: &A) {
+        self.table.retain(|(_, bound)| bound != action);
+    }
+
+    /// Bind the keys in [keymap] to the actions they name (looked up in
+    /// [actions], or [MACRO_ACTIONS], which every game has), replacing those
+    /// actions' default keys
+    pub fn remap(&mut self, keymap: &KeyMap, actions: &[(&str, A)]) -> Result<(), String> {
+        // (the macro keys are the same in every game, so start from their
+        // defaults rather than whatever the last game set them to)
+        let mut macro_bindings = Bindings::new(&MACRO_KEYS);
+        for (name, keys) in keymap {
+            if let Some(&(_, action)) = MACRO_ACTIONS
+                .iter()
+                .find(|(macro_name, _)| macro_name == name)
+            {
+                macro_bindings.unbind(&action);
+                for key in keys.as_slice() {
+                    macro_bindings.bind(key.parse::<KeyBinding>()?, action);
+                }
+                continue;
+            }
+            let action = actions
+                .iter()
+                .find(|(action_name, _)| action_name == name)
+                .map(|(_, action)| action.clone())
+                .ok_or_else(|| {
+                    let names: Vec<_> = actions
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .chain(MACRO_ACTIONS.iter().map(|(name, _)| *name))
+                        .collect();
+                    format!(
+                        "unknown action '{name}' (expected one of {})",
+                        names.join(", ")
+                    )
+                })?;
+            self.unbind(&action);
+            for key in keys.as_slice() {
+                self.bind(key.parse::<KeyBinding>()?, action.clone());
+            }
+        }
+        *MACRO_BINDINGS.lock().unwrap() = Some(macro_bindings);
+        Ok(())
+    }
+}
+
+/// What the macro keys do, in every game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MacroAction {
+    /// start (and stop) recording a [Macro]
+    Record,
+    /// play back the [Macro] recorded last
+    Play,
+}
+
+/// Default macro keys
+pub const MACRO_KEYS: [(KeyBinding, MacroAction); 2] = [
+    (KeyBinding::ctrl('r'), MacroAction::Record),
+    (KeyBinding::ctrl('e'), MacroAction::Play),
+];
+
+/// Macro actions, by the names a game's `[<game>.keys]` config can remap them with
+pub const MACRO_ACTIONS: [(&str, MacroAction); 2] = [
+    ("macro_record", MacroAction::Record),
+    ("macro_play", MacroAction::Play),
+];
+
+// the macro keys, as last remapped (None until then, for the defaults)
+static MACRO_BINDINGS: Mutex<Option<Bindings<MacroAction>>> = Mutex::new(None);
+
+// most keys a macro holds, so a recording left on doesn't grow forever
+const MACRO_MAX_KEYS: usize = 100;
+
+/// A short run of keypresses, recorded once and then played back with a
+/// single key (see [MacroAction])
+#[derive(Debug, Default)]
+pub struct Macro {
+    keys: Vec<KeyEvent>,
+    recording: Option<Vec<KeyEvent>>, // keys so far, while recording
+}
+
+/// What a keypress meant to a [Macro]
+#[derive(Debug, PartialEq)]
+pub enum MacroKey {
+    /// Not a macro key: for the game (and recorded, if recording)
+    Pass,
+    /// Recording started
+    Started,
+    /// Recording stopped, with this many keys recorded
+    Stopped(usize),
+    /// Recording stopped on its own, with the macro full at this many keys
+    /// (the last of them, this one, is for the game too)
+    Full(usize),
+    /// Keys to play back (none if nothing's been recorded)
+    Play(Vec<KeyEvent>),
+    /// A macro key that does nothing (being released)
+    Ignored,
+}
+
+impl Macro {
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Record or play back on the macro keys, and take down other keys while
+    /// recording. The play key also stops a recording (without playing it),
+    /// and recording stops on its own once it's [MACRO_MAX_KEYS] long
+    pub fn handle(&mut self, key_event: KeyEvent) -> MacroKey {
+        let action = macro_action(key_event);
+        let is_record = action == Some(MacroAction::Record);
+        let is_play = action == Some(MacroAction::Play);
+        if key_event.kind != KeyEventKind::Press {
+            // (releases of the macro keys are theirs too)
+            return if is_record || is_play {
+                MacroKey::Ignored
+            } else {
+                MacroKey::Pass
+            };
+        }
+        match (self.recording.take(), is_record || is_play) {
+            (Some(keys), true) => self.stop(keys),
+            (Some(mut keys), false) => {
+                keys.push(key_event);
+                if keys.len() >= MACRO_MAX_KEYS {
+                    self.keys = keys;
+                    return MacroKey::Full(self.keys.len());
+                }
+                self.recording = Some(keys);
+                MacroKey::Pass
+            }
+            (None, _) if is_record => {
+                self.recording = Some(Vec::new());
+                MacroKey::Started
+            }
+            (None, _) if is_play => MacroKey::Play(self.keys.clone()),
+            (None, _) => MacroKey::Pass,
+        }
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn stop(&mut self, keys: Vec<KeyEvent>) -> MacroKey {
+        // (stopping straight away keeps the old macro)
+        if !keys.is_empty() {
+            self.keys = keys;
+        }
+        MacroKey::Stopped(self.keys.len())
+    }
+}
+
+/// First key bound to [action], if it has any (for showing in help)
+pub fn macro_key(action: MacroAction) -> Option<KeyBinding> {
+    let macro_bindings = MACRO_BINDINGS.lock().unwrap();
+    let table = macro_bindings
+        .as_ref()
+        .map_or(&MACRO_KEYS[..], |b| &b.table);
+    table
+        .iter()
+        .find(|(_, bound)| *bound == action)
+        .map(|&(key, _)| key)
+}
+
+// macro action a keypress (or release) is for
+fn macro_action(key_event: KeyEvent) -> Option<MacroAction> {
+    let macro_bindings = MACRO_BINDINGS.lock().unwrap();
+    let table = macro_bindings
+        .as_ref()
+        .map_or(&MACRO_KEYS[..], |b| &b.table);
+    table
+        .iter()
+        .find(|(key, _)| key.matches(key_event))
+        .map(|&(_, action)| action)
+}
@@ -0,0 +1,57 @@
+//! Pieces shared by all the games
+
+pub mod frontend;
+pub mod grid;
+pub mod player;
+pub mod point;
+pub use frontend::{Core, Frontend};
+pub use grid::{Grid2D, Topology};
+pub use player::Player;
+pub use point::Point;
+
+#[cfg(feature = "terminal")]
+pub mod achievements;
+#[cfg(feature = "terminal")]
+pub mod bell;
+#[cfg(feature = "terminal")]
+pub mod blocks;
+#[cfg(feature = "terminal")]
+pub mod broadcast;
+#[cfg(feature = "terminal")]
+pub mod cast;
+#[cfg(feature = "terminal")]
+pub mod cli;
+#[cfg(feature = "terminal")]
+pub mod clipboard;
+#[cfg(feature = "terminal")]
+pub mod config;
+#[cfg(feature = "terminal")]
+pub mod game;
+#[cfg(feature = "terminal")]
+pub mod input;
+#[cfg(feature = "terminal")]
+pub mod locale;
+#[cfg(feature = "terminal")]
+pub mod logging;
+#[cfg(feature = "terminal")]
+pub mod replay;
+#[cfg(feature = "terminal")]
+pub mod snapshot;
+#[cfg(feature = "terminal")]
+pub mod stats;
+#[cfg(feature = "terminal")]
+pub mod style;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+#[cfg(feature = "terminal")]
+pub mod theme;
+#[cfg(feature = "ratatui")]
+pub mod tui;
+#[cfg(feature = "terminal")]
+pub use config::Config;
+#[cfg(feature = "terminal")]
+pub use game::{play, Game, GameLoop};
+#[cfg(feature = "terminal")]
+pub use input::{Bindings, KeyBinding};
+#[cfg(feature = "terminal")]
+pub use terminal::{install_panic_hook, TerminalGuard};
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+/// A language the games' text has been translated into
+#[derive(Debug)]
+pub struct Locale {
+    /// ISO 639-1 code, e.g. "de"
+    pub code: &'static str,
+    pub name: &'static str,
+    source: &'static str, // TOML table of texts
+}
+
+/// Every language there's a translation for (English first, since it has
+/// every text and the others fall back to it)
+pub static LOCALES: [Locale; 3] = [
+    Locale {
+        code: "en",
+        name: "English",
+        source: include_str!("../locales/en.toml"),
+    },
+    Locale {
+        code: "de",
+        name: "Deutsch",
+        source: include_str!("../locales/de.toml"),
+    },
+    Locale {
+        code: "ja",
+        name: "日本語",
+        source: include_str!("../locales/ja.toml"),
+    },
+];
+
+// index into LOCALES
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+// each locale's texts by dotted key (e.g. "mines.win"), parsed on first use
+static TABLES: OnceLock<Vec<HashMap<String, String>>> = OnceLock::new();
+
+impl Default for &'static Locale {
+    fn default() -> Self {
+        &LOCALES[0]
+    }
+}
+
+impl FromStr for &'static Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LOCALES
+            .iter()
+            .find(|locale| locale.code == s)
+            .ok_or_else(|| {
+                let codes: Vec<_> = LOCALES.iter().map(|locale| locale.code).collect();
+                format!(
+                    "unknown language '{s}' (expected one of {})",
+                    codes.join(", ")
+                )
+            })
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)
+    }
+}
+
+/// Switch every game's text to [locale]
+pub fn set(locale: &'static Locale) {
+    let k = LOCALES
+        .iter()
+        .position(|l| l.code == locale.code)
+        .unwrap_or(0);
+    CURRENT.store(k, Ordering::Relaxed);
+}
+
+pub fn current() -> &'static Locale {
+    &LOCALES[CURRENT.load(Ordering::Relaxed)]
+}
+
+/// Locale for the language in `$LC_ALL`, `$LC_MESSAGES` or `$LANG` (e.g.
+/// "de_DE.UTF-8"), if there's a translation for it
+pub fn from_env() -> Option<&'static Locale> {
+    let lang = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|lang| !lang.is_empty())?;
+    let code = lang.split(['_', '.', '@']).next()?;
+    code.parse().ok()
+}
+
+/// Text for [key] in the current language (or English, if it hasn't been
+/// translated), with `\r\n` line endings for raw mode
+pub fn text(key: &str) -> &'static str {
+    let tables = TABLES.get_or_init(|| LOCALES.iter().map(parse).collect());
+    tables[CURRENT.load(Ordering::Relaxed)]
+        .get(key)
+        .or_else(|| tables[0].get(key))
+        .map(String::as_str)
+        .unwrap_or_else(|| panic!("no text called '{key}'"))
+}
+
+// flatten a locale's tables into dotted keys
+fn parse(locale: &Locale) -> HashMap<String, String> {
+    let table: toml::Table = locale
+        .source
+        .parse()
+        .unwrap_or_else(|e| panic!("bad {} texts: {e}", locale.code));
+    let mut texts = HashMap::new();
+    for (section, entries) in table {
+        let Some(entries) = entries.as_table() else {
+            continue;
+        };
+        for (name, text) in entries {
+            if let Some(text) = text.as_str() {
+                texts.insert(format!("{section}.{name}"), text.replace('\n', "\r\n"));
+            }
+        }
+    }
+    texts
+}
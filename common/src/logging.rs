@@ -0,0 +1,76 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Logger writing to a file (stdout being taken up by the game screen)
+struct FileLogger {
+    file: Mutex<File>,
+    started: Instant,
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    // e.g. "  12.345 DEBUG common::game: input Resize(80, 24)"
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let mut file = self.file.lock().unwrap();
+        writeln!(
+            file,
+            "{:8.3} {:<5} {}: {}",
+            self.started.elapsed().as_secs_f64(),
+            record.level(),
+            record.target(),
+            record.args()
+        )
+        .ok();
+    }
+
+    fn flush(&self) {
+        self.file.lock().unwrap().flush().ok();
+    }
+}
+
+/// Send log records at [level] and above to [path] (or [default_path]),
+/// starting the file afresh. Returns where the log is going
+pub fn init(level: LevelFilter, path: Option<&Path>) -> Result<PathBuf, String> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => default_path().ok_or("nowhere to keep the log (HOME isn't set)")?,
+    };
+    let create = || {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        File::create(&path)
+    };
+    let file = create().map_err(|e| format!("failed to create {}: {e}", path.display()))?;
+    let logger = FileLogger {
+        file: Mutex::new(file),
+        started: Instant::now(),
+    };
+    log::set_boxed_logger(Box::new(logger)).map_err(|e| e.to_string())?;
+    log::set_max_level(level);
+    Ok(path)
+}
+
+/// `$XDG_STATE_HOME/crossterm-games/log`,
+/// or `~/.local/state/crossterm-games/log`
+pub fn default_path() -> Option<PathBuf> {
+    let state_dir = match env::var_os("XDG_STATE_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?)
+            .join(".local")
+            .join("state"),
+    };
+    Some(state_dir.join("crossterm-games").join("log"))
+}
@@ -0,0 +1,51 @@
+use crate::frontend::Core;
+
+/// Something that decides the moves in a game: a person at a frontend, a
+/// script, or a computer player
+pub trait Player<C: Core> {
+    /// Next move to make in the game as it stands (None if there's nothing
+    /// left the player wants to do)
+    fn choose_action(&mut self, view: &C) -> Option<C::Action>;
+}
+
+/// Player making a fixed list of moves, in order (e.g. to set up a position,
+/// or to script a test game)
+pub struct Scripted<I> {
+    moves: I,
+}
+
+impl<I> Scripted<I> {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(moves: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            moves: moves.into_iter(),
+        }
+    }
+}
+
+impl<C: Core, I: Iterator<Item = C::Action>> Player<C> for Scripted<I> {
+    fn choose_action(&mut self, _view: &C) -> Option<C::Action> {
+        self.moves.next()
+    }
+}
+
+/// Let [player] play [core] until the game is over, the player stops, or
+/// [max_moves] moves have been made (whichever comes first). Returns what
+/// each move led to
+pub fn play<C: Core, P: Player<C>>(
+    core: &mut C,
+    player: &mut P,
+    max_moves: usize,
+) -> Vec<C::Outcome> {
+    let mut outcomes = Vec::new();
+    while outcomes.len() < max_moves && !core.is_over() {
+        let Some(action) = player.choose_action(core) else {
+            break;
+        };
+        outcomes.push(core.act(action));
+    }
+    outcomes
+}
@@ -1,7 +1,7 @@
 use std::fmt;
 
-#[derive(Clone, Copy)]
-pub struct Point (pub usize, pub usize);
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point(pub usize, pub usize);
 
 impl Point {
     pub fn origin() -> Self {
@@ -22,3 +22,9 @@ impl fmt::Display for Point {
         writeln!(f, "({}, {})", self.0, self.1)
     }
 }
+
+impl From<(usize, usize)> for Point {
+    fn from((i, j): (usize, usize)) -> Self {
+        Self(i, j)
+    }
+}
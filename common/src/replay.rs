@@ -0,0 +1,141 @@
+use std::mem;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crossterm::event::Event;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::snapshot::{Snapshot, Versioned};
+
+/// Everything needed to play a game session over again: how the game was
+/// launched, its random seed, and every input it got
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// the game played (its [crate::Game::name]), filled in once it starts
+    #[serde(skip)]
+    pub game: String,
+    /// launcher command line, program name included
+    pub args: Vec<String>,
+    /// seed for the game's random numbers (None for games without any)
+    pub seed: Option<u64>,
+    pub inputs: Vec<Input>,
+}
+
+/// One terminal event, and when it came in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Input {
+    /// milliseconds since the game loop started
+    pub at: u64,
+    /// game ticks run before it (which is what keeps replays in step)
+    pub tick: u64,
+    pub event: Event,
+}
+
+// what the next game loop does with its input
+enum Mode {
+    Record(Session),
+    Replay(Session),
+}
+
+static MODE: Mutex<Option<Mode>> = Mutex::new(None);
+
+impl Session {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Session with no inputs yet
+    pub fn new(args: Vec<String>, seed: Option<u64>) -> Self {
+        Self {
+            game: String::new(),
+            args,
+            seed,
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Read a session written by [Session::save]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let snapshot = Snapshot::load(path)?;
+        let mut session: Self = snapshot
+            .state()
+            .map_err(|e| format!("bad recording {}: {e}", path.display()))?;
+        session.game = snapshot.game;
+        Ok(session)
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Write the session out as a [Snapshot]
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        Snapshot::new(&self.game, self.seed, self)?.save(path)
+    }
+}
+
+impl Versioned for Session {
+    const VERSION: u32 = 1;
+
+    // recordings from before snapshots were sessions just as they are now
+    fn migrate(_version: u32, state: Value) -> Result<Value, String> {
+        Ok(state)
+    }
+}
+
+/// Record the inputs of the next game session into [session]
+pub fn record(session: Session) {
+    *MODE.lock().unwrap() = Some(Mode::Record(session));
+}
+
+/// Feed the next game session's inputs from [session] instead of the terminal
+pub fn replay(session: Session) {
+    *MODE.lock().unwrap() = Some(Mode::Replay(session));
+}
+
+/// Whether a recorded session is being played back (which shouldn't count
+/// towards the stats or achievements)
+pub fn is_replaying() -> bool {
+    matches!(*MODE.lock().unwrap(), Some(Mode::Replay(_)))
+}
+
+/// Whether a session is being recorded
+pub fn is_recording() -> bool {
+    matches!(*MODE.lock().unwrap(), Some(Mode::Record(_)))
+}
+
+// note which game the session being recorded (if any) is of, as it starts
+pub(crate) fn start(game: &str) {
+    if let Some(Mode::Record(session)) = &mut *MODE.lock().unwrap() {
+        session.game = game.to_string();
+    }
+}
+
+// inputs to play back, if replaying
+pub(crate) fn take_inputs() -> Option<Vec<Input>> {
+    match &mut *MODE.lock().unwrap() {
+        Some(Mode::Replay(session)) => Some(mem::take(&mut session.inputs)),
+        _ => None,
+    }
+}
+
+// add an input to the session being recorded, if any
+pub(crate) fn push(at: Duration, tick: u64, event: &Event) {
+    if let Some(Mode::Record(session)) = &mut *MODE.lock().unwrap() {
+        session.inputs.push(Input {
+            at: at.as_millis() as u64,
+            tick,
+            event: event.clone(),
+        });
+    }
+}
+
+/// Stop recording or replaying, returning the recorded session (if recording)
+pub fn stop() -> Option<Session> {
+    match MODE.lock().unwrap().take() {
+        Some(Mode::Record(session)) => Some(session),
+        _ => None,
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Version of the [Snapshot] container itself (not of what's in it)
+pub const FORMAT: u32 = 1;
+
+/// The file every game saves to: which game it's from, the version of the
+/// game's state, the seed, the state itself (as the game serializes it), and
+/// metadata like when it was saved. Games only need to make their state
+/// [Versioned]; the container is the same for all of them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// [FORMAT] of the container when it was saved
+    pub format: u32,
+    /// the game the state belongs to, as in [crate::Game::name]
+    pub game: String,
+    /// [Versioned::VERSION] of the state when it was saved
+    pub version: u32,
+    /// seed for the game's random numbers (None for games without any)
+    pub seed: Option<u64>,
+    pub state: Value,
+    /// anything else worth keeping, like "saved" (unix time, in seconds) and
+    /// "build" (the version of the program that saved it)
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+/// Game state that can be saved in a [Snapshot], and read back after its
+/// layout changes
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Version of the state's layout, bumped whenever a change to it means
+    /// older saves no longer deserialize as they are
+    const VERSION: u32;
+
+    /// Bring a state saved at [version] up to the next version (called as
+    /// many times as it takes to reach [Self::VERSION]). Older saves are
+    /// refused unless a game says how to read them
+    fn migrate(version: u32, _state: Value) -> Result<Value, String> {
+        Err(format!(
+            "saves from version {version} can't be read any more"
+        ))
+    }
+}
+
+impl Snapshot {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Snapshot of [state], saved by [game] now
+    pub fn new<T: Versioned>(game: &str, seed: Option<u64>, state: &T) -> Result<Self, String> {
+        let state = serde_json::to_value(state).map_err(|e| e.to_string())?;
+        let saved = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Ok(Self {
+            format: FORMAT,
+            game: game.to_string(),
+            version: T::VERSION,
+            seed,
+            state,
+            metadata: BTreeMap::from([
+                ("saved".to_string(), saved.to_string()),
+                ("build".to_string(), env!("CARGO_PKG_VERSION").to_string()),
+            ]),
+        })
+    }
+
+    /// Read a snapshot written by [Snapshot::save]. A file from before
+    /// snapshots (a game's bare state) reads as version 0 of that state,
+    /// for [Versioned::migrate] to bring up to date
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let bad = |e: String| format!("bad save file {}: {e}", path.display());
+        let value: Value = serde_json::from_str(&text).map_err(|e| bad(e.to_string()))?;
+        if value.get("format").is_none() {
+            return Ok(Self {
+                format: FORMAT,
+                game: String::new(),
+                version: 0,
+                seed: value.get("seed").and_then(Value::as_u64),
+                state: value,
+                metadata: BTreeMap::new(),
+            });
+        }
+        let snapshot: Self = serde_json::from_value(value).map_err(|e| bad(e.to_string()))?;
+        if snapshot.format > FORMAT {
+            return Err(bad(format!(
+                "saved in format {}, newer than this program reads ({FORMAT})",
+                snapshot.format
+            )));
+        }
+        Ok(snapshot)
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Write the snapshot out as JSON
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+
+    /// The state saved, migrated up to the current version if it's older
+    pub fn state<T: Versioned>(&self) -> Result<T, String> {
+        if self.version > T::VERSION {
+            return Err(format!(
+                "saved at version {}, newer than this program reads ({})",
+                self.version,
+                T::VERSION
+            ));
+        }
+        let mut state = self.state.clone();
+        for version in self.version..T::VERSION {
+            state = T::migrate(version, state)?;
+        }
+        serde_json::from_value(state).map_err(|e| e.to_string())
+    }
+}
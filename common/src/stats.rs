@@ -0,0 +1,395 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::achievements::ACHIEVEMENTS;
+
+/// Most scores kept in each game's high-score table
+pub const HIGH_SCORES: usize = 10;
+
+/// Typing tests averaged over for the recent speed and accuracy
+pub const RECENT_TESTS: usize = 10;
+
+// roguelike runs listed, from the best
+const SHOWN_RUNS: usize = 5;
+
+/// How a game ended
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Won,
+    Lost,
+    Quit,
+}
+
+/// One finished game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Play {
+    pub game: String,
+    pub started: u64, // unix time, in seconds
+    pub seconds: u64,
+    pub outcome: Outcome,
+}
+
+/// One finished typing test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TypingTest {
+    pub started: u64, // unix time, in seconds
+    pub seconds: u64,
+    pub wpm: f64,
+    pub accuracy: f64, // percent of keystrokes that were right
+}
+
+/// One roguelike run, from the start to the player's death (or quitting)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RogueRun {
+    pub started: u64, // unix time, in seconds
+    pub turns: u64,
+    pub depth: u32,
+    pub score: u64,
+    /// the monster the player was killed by (none if they quit)
+    pub killed_by: Option<String>,
+}
+
+/// Every game played so far, kept in the data dir as JSON
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Stats {
+    pub plays: Vec<Play>,
+    /// unix time each achievement was unlocked at, by id
+    #[serde(default)]
+    pub achievements: BTreeMap<String, u64>,
+    /// chips carried over from one session to the next, by game
+    #[serde(default)]
+    pub bankrolls: BTreeMap<String, u64>,
+    /// best scores so far, highest first (up to [HIGH_SCORES]), by game
+    #[serde(default)]
+    pub high_scores: BTreeMap<String, Vec<u64>>,
+    /// every typing test finished, oldest first
+    #[serde(default)]
+    pub typing_tests: Vec<TypingTest>,
+    /// every roguelike run, oldest first
+    #[serde(default)]
+    pub rogue_runs: Vec<RogueRun>,
+}
+
+impl Stats {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Read the stats file (no file yet means no plays yet)
+    pub fn load() -> Result<Self, String> {
+        let Some(path) = default_path() else {
+            return Ok(Self::default());
+        };
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+        };
+        serde_json::from_str(&text).map_err(|e| format!("bad stats file {}: {e}", path.display()))
+    }
+
+    /////////////
+    // Statics //
+    /////////////
+
+    /// Change the stats file with [change], holding a lock on it from
+    /// reading it to writing it back (so that games running at once, like
+    /// the servers', don't lose each other's changes)
+    pub fn update<R>(change: impl FnOnce(&mut Self) -> R) -> Result<R, String> {
+        let path = default_path().ok_or("nowhere to keep stats (HOME isn't set)")?;
+        let lock_path = path.with_extension("lock");
+        let lock = || -> io::Result<File> {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let file = File::options()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&lock_path)?;
+            file.lock()?;
+            Ok(file)
+        };
+        // (unlocked when dropped)
+        let _lock = lock().map_err(|e| format!("failed to lock {}: {e}", lock_path.display()))?;
+        let mut stats = Self::load()?;
+        let result = change(&mut stats);
+        stats.save(&path)?;
+        Ok(result)
+    }
+
+    /// Add a play of [game] that started at [started] and lasted [duration]
+    pub fn record(
+        game: &str,
+        outcome: Outcome,
+        started: SystemTime,
+        duration: Duration,
+    ) -> Result<(), String> {
+        Self::update(|stats| {
+            stats.plays.push(Play {
+                game: game.to_string(),
+                started: started
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                seconds: duration.as_secs(),
+                outcome,
+            })
+        })
+    }
+
+    /// Chips [game] left off with last session, if it's been played
+    pub fn bankroll(game: &str) -> Result<Option<u64>, String> {
+        Ok(Self::load()?.bankrolls.get(game).copied())
+    }
+
+    /// Keep [chips] for the next session of [game]
+    pub fn save_bankroll(game: &str, chips: u64) -> Result<(), String> {
+        Self::update(|stats| {
+            stats.bankrolls.insert(game.to_string(), chips);
+        })
+    }
+
+    /// [game]'s high-score table, highest first
+    pub fn high_scores(game: &str) -> Result<Vec<u64>, String> {
+        Ok(Self::load()?
+            .high_scores
+            .get(game)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    /// Put [score] in [game]'s high-score table, if it's good enough.
+    /// Returns the place it got (0 for the top), if any
+    pub fn add_high_score(game: &str, score: u64) -> Result<Option<usize>, String> {
+        Self::update(|stats| {
+            let table = stats.high_scores.entry(game.to_string()).or_default();
+            // (after the scores it beats, and any it only ties with)
+            let place = table.partition_point(|&best| best >= score);
+            if place >= HIGH_SCORES {
+                return None;
+            }
+            table.insert(place, score);
+            table.truncate(HIGH_SCORES);
+            Some(place)
+        })
+    }
+
+    /// Every typing test finished so far, oldest first
+    pub fn typing_tests() -> Result<Vec<TypingTest>, String> {
+        Ok(Self::load()?.typing_tests)
+    }
+
+    /// Add a finished typing test to the ones kept
+    pub fn add_typing_test(test: TypingTest) -> Result<(), String> {
+        Self::update(|stats| stats.typing_tests.push(test))
+    }
+
+    /// Every roguelike run so far, oldest first
+    pub fn rogue_runs() -> Result<Vec<RogueRun>, String> {
+        Ok(Self::load()?.rogue_runs)
+    }
+
+    /// Add a roguelike run that's over to the ones kept
+    pub fn add_rogue_run(run: RogueRun) -> Result<(), String> {
+        Self::update(|stats| stats.rogue_runs.push(run))
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Totals for each game, in alphabetical order
+    pub fn summaries(&self) -> Vec<Summary> {
+        let mut summaries: BTreeMap<&str, Summary> = BTreeMap::new();
+        for play in &self.plays {
+            let summary = summaries
+                .entry(&play.game)
+                .or_insert_with(|| Summary::new(&play.game));
+            summary.add(play);
+        }
+        summaries.into_values().collect()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // write the stats file to [path], by way of a temporary file renamed over
+    // it, so that it's never left half-written
+    fn save(&self, path: &Path) -> Result<(), String> {
+        let tmp_path = path.with_extension("json.tmp");
+        let write = || -> io::Result<()> {
+            fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+            fs::rename(&tmp_path, path)
+        };
+        write().map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+}
+
+/// Aggregate table, one row per game
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let summaries = self.summaries();
+        if summaries.is_empty() {
+            writeln!(f, "no games played yet")?;
+        } else {
+            writeln!(
+                f,
+                "{:<10} {:>6} {:>6} {:>6} {:>6} {:>12} {:>10}",
+                "game", "plays", "won", "lost", "quit", "time played", "best win"
+            )?;
+            for summary in summaries {
+                writeln!(f, "{summary}")?;
+            }
+        }
+
+        if !self.bankrolls.is_empty() {
+            writeln!(f, "\nbankrolls:")?;
+            for (game, chips) in &self.bankrolls {
+                writeln!(f, "{game:<10} {chips:>6}")?;
+            }
+        }
+
+        if !self.high_scores.is_empty() {
+            writeln!(f, "\nhigh scores:")?;
+            for (game, scores) in &self.high_scores {
+                let scores: Vec<String> = scores.iter().map(u64::to_string).collect();
+                writeln!(f, "{game:<10} {}", scores.join(" "))?;
+            }
+        }
+
+        if !self.typing_tests.is_empty() {
+            let tests = &self.typing_tests;
+            let best = tests.iter().map(|test| test.wpm).fold(0.0, f64::max);
+            let recent = &tests[tests.len().saturating_sub(RECENT_TESTS)..];
+            let n = recent.len() as f64;
+            writeln!(f, "\ntyping tests:")?;
+            writeln!(f, "{:<10} {:>6}", "tests", tests.len())?;
+            writeln!(f, "{:<10} {best:>6.0} wpm", "best")?;
+            writeln!(
+                f,
+                "{:<10} {:>6.0} wpm, {:.0}% accuracy (average of the last {})",
+                "recent",
+                recent.iter().map(|test| test.wpm).sum::<f64>() / n,
+                recent.iter().map(|test| test.accuracy).sum::<f64>() / n,
+                recent.len()
+            )?;
+        }
+
+        if !self.rogue_runs.is_empty() {
+            let mut runs: Vec<&RogueRun> = self.rogue_runs.iter().collect();
+            runs.sort_by_key(|run| Reverse(run.score));
+            writeln!(f, "\nroguelike runs ({}), best first:", runs.len())?;
+            for run in runs.iter().take(SHOWN_RUNS) {
+                let end = match &run.killed_by {
+                    Some(monster) => format!("killed by a {monster}"),
+                    None => "quit".to_string(),
+                };
+                writeln!(f, "{:>6} on level {:<3} {end}", run.score, run.depth)?;
+            }
+        }
+
+        writeln!(
+            f,
+            "\nachievements ({}/{}):",
+            self.achievements.len(),
+            ACHIEVEMENTS.len()
+        )?;
+        for achievement in &ACHIEVEMENTS {
+            let mark = if self.achievements.contains_key(achievement.id) {
+                "x"
+            } else {
+                " "
+            };
+            writeln!(f, "[{mark}] {achievement}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Totals over every play of one game
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub game: String,
+    pub plays: usize,
+    pub won: usize,
+    pub lost: usize,
+    pub quit: usize,
+    pub seconds: u64,
+    pub best_win: Option<u64>, // quickest win, in seconds
+}
+
+impl Summary {
+    fn new(game: &str) -> Self {
+        Self {
+            game: game.to_string(),
+            plays: 0,
+            won: 0,
+            lost: 0,
+            quit: 0,
+            seconds: 0,
+            best_win: None,
+        }
+    }
+
+    fn add(&mut self, play: &Play) {
+        self.plays += 1;
+        self.seconds += play.seconds;
+        match play.outcome {
+            Outcome::Won => {
+                self.won += 1;
+                self.best_win = Some(self.best_win.map_or(play.seconds, |s| s.min(play.seconds)));
+            }
+            Outcome::Lost => self.lost += 1,
+            Outcome::Quit => self.quit += 1,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let best_win = self.best_win.map_or("-".to_string(), fmt_seconds);
+        write!(
+            f,
+            "{:<10} {:>6} {:>6} {:>6} {:>6} {:>12} {:>10}",
+            self.game,
+            self.plays,
+            self.won,
+            self.lost,
+            self.quit,
+            fmt_seconds(self.seconds),
+            best_win
+        )
+    }
+}
+
+// e.g. "41s", "3m 05s", "1h 02m"
+fn fmt_seconds(seconds: u64) -> String {
+    match (seconds / 3600, seconds / 60 % 60, seconds % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s:02}s"),
+        (h, m, _) => format!("{h}h {m:02}m"),
+    }
+}
+
+/// `$XDG_DATA_HOME/crossterm-games/stats.json`,
+/// or `~/.local/share/crossterm-games/stats.json`
+pub fn default_path() -> Option<PathBuf> {
+    let data_dir = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?)
+            .join(".local")
+            .join("share"),
+    };
+    Some(data_dir.join("crossterm-games").join("stats.json"))
+}
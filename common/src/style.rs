@@ -0,0 +1,36 @@
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossterm::style;
+
+// set by --no-color
+static COLOR_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn off colored output everywhere (for --no-color)
+pub fn disable_color() {
+    COLOR_DISABLED.store(true, Ordering::Relaxed);
+    style::force_color_output(false);
+}
+
+/// Whether to draw in color: not if NO_COLOR is set (https://no-color.org)
+/// or color has been turned off with [disable_color]. Renderers that tell
+/// things apart by color alone should switch to distinct symbols when it's off.
+pub fn color_enabled() -> bool {
+    !COLOR_DISABLED.load(Ordering::Relaxed)
+        && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+/// Whether the locale asks for UTF-8, going by the first of `$LC_ALL`,
+/// `$LC_CTYPE` and `$LANG` that's set (and taking it that it does if none
+/// are). Renderers drawing symbols outside ASCII should fall back to plain
+/// letters when it doesn't.
+pub fn utf8_locale() -> bool {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|lang| !lang.is_empty())
+        .is_none_or(|lang| {
+            let lang = lang.to_ascii_lowercase();
+            lang.contains("utf-8") || lang.contains("utf8")
+        })
+}
@@ -0,0 +1,85 @@
+use std::io::{self, stdout};
+use std::panic;
+use std::sync::Once;
+
+use crossterm::{
+    cursor, event, execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use unicode_width::UnicodeWidthStr;
+
+// enough for any cell (see [padding])
+const SPACES: &str = "                ";
+
+/// Width of the widest line of [text], in terminal columns (wide glyphs like
+/// emoji and CJK characters take up two)
+pub fn text_width(text: &str) -> u16 {
+    text.lines()
+        .map(|line| line.trim_end_matches('\r').width())
+        .max()
+        .unwrap_or(0) as u16
+}
+
+/// Spaces to put after [text] to make it take up [width] columns, so that
+/// glyphs narrower than the cells they're drawn in keep the grid lined up
+pub fn padding(text: &str, width: usize) -> &'static str {
+    let n = width.saturating_sub(text.width()).min(SPACES.len());
+    &SPACES[..n]
+}
+
+/// Puts the terminal into full-screen game mode (alternate screen, hidden
+/// cursor, raw mode) for as long as it's alive, and puts everything back
+/// when dropped
+pub struct TerminalGuard {
+    mouse: bool, // whether mouse capture needs turning off again
+}
+
+impl TerminalGuard {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> io::Result<Self> {
+        install_panic_hook();
+        execute!(stdout(), EnterAlternateScreen, cursor::Hide)?;
+        terminal::enable_raw_mode()?;
+        Ok(Self { mouse: false })
+    }
+
+    /// Same as [TerminalGuard::new], but also reports mouse events
+    pub fn with_mouse() -> io::Result<Self> {
+        let mut guard = Self::new()?;
+        execute!(stdout(), event::EnableMouseCapture)?;
+        guard.mouse = true;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore(self.mouse);
+    }
+}
+
+/// Make panics put the terminal back to normal before printing their message,
+/// which would otherwise be lost on the alternate screen (safe to call repeatedly)
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            log::error!("{info}");
+            restore(true);
+            default_hook(info);
+        }));
+    });
+}
+
+// restore as much as possible, even if some of it fails
+fn restore(mouse: bool) {
+    let _ = terminal::disable_raw_mode();
+    if mouse {
+        let _ = execute!(stdout(), event::DisableMouseCapture);
+    }
+    let _ = execute!(stdout(), LeaveAlternateScreen, cursor::Show);
+}
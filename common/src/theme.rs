@@ -0,0 +1,197 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crossterm::style::Color;
+
+/// What a color is used for, the same way in every game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    /// ordinary foreground (numbers, labels)
+    Text,
+    /// things in the background (empty squares, walls)
+    Muted,
+    /// the cursor and selected items
+    Accent,
+    /// things placed on the board (flags, the ant)
+    Marker,
+    /// squares that haven't been uncovered
+    Hidden,
+    /// mines, errors and losing
+    Danger,
+    /// winning
+    Success,
+}
+
+/// A named set of colors for each [Role], plus a ramp of colors for cell
+/// states (state 0 is empty) in Life and Langton's Ant
+#[derive(Debug, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    text: Color,
+    muted: Color,
+    accent: Color,
+    marker: Color,
+    hidden: Color,
+    danger: Color,
+    success: Color,
+    pub cells: [Color; 9],
+}
+
+const fn rgb(hex: u32) -> Color {
+    Color::Rgb {
+        r: (hex >> 16) as u8,
+        g: (hex >> 8) as u8,
+        b: hex as u8,
+    }
+}
+
+/// Bundled themes (the first is the default)
+pub static THEMES: [Theme; 4] = [
+    Theme {
+        name: "default",
+        text: Color::White,
+        muted: Color::DarkGrey,
+        accent: Color::Cyan,
+        marker: Color::DarkYellow,
+        hidden: Color::Blue,
+        danger: Color::Red,
+        success: Color::Magenta,
+        cells: [
+            Color::Black,
+            Color::White,
+            Color::Red,
+            Color::Yellow,
+            Color::Green,
+            Color::Cyan,
+            Color::Blue,
+            Color::Magenta,
+            Color::DarkGrey,
+        ],
+    },
+    Theme {
+        name: "solarized",
+        text: rgb(0x93a1a1),
+        muted: rgb(0x586e75),
+        accent: rgb(0x2aa198),
+        marker: rgb(0xb58900),
+        hidden: rgb(0x268bd2),
+        danger: rgb(0xdc322f),
+        success: rgb(0x859900),
+        cells: [
+            rgb(0x002b36),
+            rgb(0xeee8d5),
+            rgb(0xdc322f),
+            rgb(0xb58900),
+            rgb(0x859900),
+            rgb(0x2aa198),
+            rgb(0x268bd2),
+            rgb(0x6c71c4),
+            rgb(0xd33682),
+        ],
+    },
+    Theme {
+        name: "gruvbox",
+        text: rgb(0xebdbb2),
+        muted: rgb(0x928374),
+        accent: rgb(0x8ec07c),
+        marker: rgb(0xfabd2f),
+        hidden: rgb(0x458588),
+        danger: rgb(0xfb4934),
+        success: rgb(0xb8bb26),
+        cells: [
+            rgb(0x282828),
+            rgb(0xebdbb2),
+            rgb(0xfb4934),
+            rgb(0xfe8019),
+            rgb(0xfabd2f),
+            rgb(0xb8bb26),
+            rgb(0x83a598),
+            rgb(0xd3869b),
+            rgb(0x8ec07c),
+        ],
+    },
+    Theme {
+        name: "mono",
+        text: Color::White,
+        muted: Color::DarkGrey,
+        accent: Color::White,
+        marker: Color::Grey,
+        hidden: Color::Grey,
+        danger: Color::DarkGrey,
+        success: Color::DarkGrey,
+        cells: [
+            rgb(0x000000),
+            rgb(0xffffff),
+            rgb(0x9e9e9e),
+            rgb(0x5e5e5e),
+            rgb(0xdadada),
+            rgb(0x3a3a3a),
+            rgb(0xbcbcbc),
+            rgb(0x767676),
+            rgb(0x262626),
+        ],
+    },
+];
+
+// index into THEMES of the theme in use
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+impl Theme {
+    /// Color for [role]
+    pub fn color(&self, role: Role) -> Color {
+        match role {
+            Role::Text => self.text,
+            Role::Muted => self.muted,
+            Role::Accent => self.accent,
+            Role::Marker => self.marker,
+            Role::Hidden => self.hidden,
+            Role::Danger => self.danger,
+            Role::Success => self.success,
+        }
+    }
+
+    /// Color for a cell in [state] (cycling if there are more states than colors)
+    pub fn cell(&self, state: u8) -> Color {
+        self.cells[state as usize % self.cells.len()]
+    }
+}
+
+impl Default for &'static Theme {
+    fn default() -> Self {
+        &THEMES[0]
+    }
+}
+
+impl FromStr for &'static Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        THEMES.iter().find(|theme| theme.name == s).ok_or_else(|| {
+            let names: Vec<_> = THEMES.iter().map(|theme| theme.name).collect();
+            format!("unknown theme '{s}' (expected one of {})", names.join(", "))
+        })
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Draw everything with [theme] from now on
+pub fn set(theme: &'static Theme) {
+    let k = THEMES.iter().position(|t| t == theme).unwrap_or(0);
+    CURRENT.store(k, Ordering::Relaxed);
+}
+
+/// Theme in use
+pub fn current() -> &'static Theme {
+    &THEMES[CURRENT.load(Ordering::Relaxed)]
+}
+
+/// Color for [role] in the theme in use
+pub fn color(role: Role) -> Color {
+    current().color(role)
+}
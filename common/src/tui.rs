@@ -0,0 +1,221 @@
+use std::fmt;
+use std::io;
+
+use crossterm::style::StyledContent;
+use ratatui::backend::CrosstermBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, Clear, Paragraph, Widget};
+use ratatui::{Frame, Terminal};
+use unicode_width::UnicodeWidthStr;
+
+use crate::broadcast;
+use crate::cast::Output;
+use crate::game::{self, Game, GameLoop, DEFAULT_FPS};
+use crate::style;
+use crate::theme::{self, Role};
+
+/// Game that can also draw itself with ratatui widgets, instead of writing
+/// escape codes straight to the terminal
+pub trait TuiGame: Game {
+    /// Draw the whole screen
+    fn draw(&self, frame: &mut Frame);
+}
+
+/// Run a game until it's over, like [crate::play], but drawing it through ratatui
+pub fn play(game: &mut dyn TuiGame) -> io::Result<()> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(Output::new()))?;
+    terminal.clear()?;
+    GameLoop::new(DEFAULT_FPS).run_with(game, |game, too_small, toasts| {
+        // (ratatui only draws what changed since the last frame, unless cleared)
+        if broadcast::take_joined() {
+            terminal.clear()?;
+        }
+        terminal.draw(|frame| match too_small {
+            Some(min_size) => {
+                let message = game::too_small_message(min_size);
+                frame.render_widget(Paragraph::new(message), frame.size());
+            }
+            None => {
+                game.draw(frame);
+                frame.render_widget(Toasts(toasts), frame.size());
+            }
+        })?;
+        Ok(())
+    })
+}
+
+/// Split the screen into the area for the board, and a status bar
+/// [status_height] rows tall underneath it
+pub fn split(area: Rect, status_height: u16) -> (Rect, Rect) {
+    let [board, status] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(status_height)]).areas(area);
+    (board, status)
+}
+
+// (width x height) rectangle in the middle of [area], cut down to fit in it
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+/// Convert text styled for crossterm into a ratatui span
+pub fn span<D: fmt::Display>(styled: &StyledContent<D>) -> Span<'static> {
+    Span::styled(styled.content().to_string(), Style::from(*styled.style()))
+}
+
+/// Rows of cells in a titled border, in the top-left corner of its area
+/// (whatever doesn't fit is cut off)
+pub struct Board<'a> {
+    rows: Vec<Line<'a>>,
+    title: Line<'a>,
+}
+
+impl<'a> Board<'a> {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(rows: Vec<Line<'a>>) -> Self {
+        Self {
+            rows,
+            title: Line::default(),
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<Line<'a>>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Space taken up by the whole board and its border, as (cols, rows)
+    pub fn size(&self) -> (u16, u16) {
+        let cols = self.rows.iter().map(Line::width).max().unwrap_or(0) as u16;
+        (
+            cols.saturating_add(2),
+            (self.rows.len() as u16).saturating_add(2),
+        )
+    }
+}
+
+impl Widget for Board<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (cols, rows) = self.size();
+        let area = Rect {
+            width: cols.min(area.width),
+            height: rows.min(area.height),
+            ..area
+        };
+        Paragraph::new(self.rows)
+            .block(Block::bordered().title(self.title))
+            .render(area, buf);
+    }
+}
+
+/// Status lines, with a dimmed block of key help underneath
+pub struct StatusBar<'a> {
+    lines: Vec<Line<'a>>,
+    help: &'a str,
+}
+
+impl<'a> StatusBar<'a> {
+    pub fn new(help: &'a str) -> Self {
+        Self {
+            lines: Vec::new(),
+            help,
+        }
+    }
+
+    /// Add a status line (above the help)
+    pub fn line(mut self, line: impl Into<Line<'a>>) -> Self {
+        self.lines.push(line.into());
+        self
+    }
+
+    /// Number of rows needed to show everything
+    pub fn height(&self) -> u16 {
+        (self.lines.len() + self.help.lines().count()) as u16
+    }
+}
+
+impl Widget for StatusBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dim = Style::new().add_modifier(Modifier::DIM);
+        let help = self
+            .help
+            .lines()
+            .map(|line| Line::styled(line.trim_end_matches('\r'), dim));
+        let text: Text = self.lines.into_iter().chain(help).collect();
+        Paragraph::new(text).render(area, buf);
+    }
+}
+
+/// Modal box of centered text, drawn over the middle of whatever's underneath
+pub struct Dialog<'a> {
+    title: &'a str,
+    text: Text<'a>,
+    style: Style,
+}
+
+impl<'a> Dialog<'a> {
+    pub fn new(title: &'a str, text: impl Into<Text<'a>>) -> Self {
+        Self {
+            title,
+            text: text.into(),
+            style: Style::new(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Widget for Dialog<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = self.text.width().max(self.title.width()) as u16 + 4;
+        let height = self.text.height() as u16 + 2;
+        let area = centered(area, width, height);
+        Clear.render(area, buf);
+        Paragraph::new(self.text)
+            .alignment(Alignment::Center)
+            .block(Block::bordered().title(self.title))
+            .style(self.style)
+            .render(area, buf);
+    }
+}
+
+// toasts stacked in the top-right corner of the screen
+struct Toasts<'a>(&'a [String]);
+
+impl Widget for Toasts<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let style = if style::color_enabled() {
+            Style::new()
+                .fg(theme::color(Role::Text).into())
+                .bg(theme::color(Role::Success).into())
+        } else {
+            Style::new().add_modifier(Modifier::REVERSED)
+        };
+        let style = style.add_modifier(Modifier::BOLD);
+        for (row, text) in self.0.iter().enumerate().take(area.height as usize) {
+            let text = format!(" {text} ");
+            let width = (Line::from(text.as_str()).width() as u16).min(area.width);
+            let x = area.x + area.width - width;
+            buf.set_stringn(x, area.y + row as u16, &text, width as usize, style);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use common::{Core, Player, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::connectfour::{ConnectFour, Disc, COLS};
+
+/// Deepest search allowed (each extra move looked ahead takes several
+/// times as long)
+pub const MAX_DEPTH: u32 = 9;
+
+// score of a won position, less the moves it takes to get there
+const WIN: i32 = 1_000_000;
+
+// columns to try first: the middle ones, which usually turn out best, so
+// alpha-beta can rule out more of the rest
+const COLUMN_ORDER: [usize; COLS] = [3, 2, 4, 1, 5, 0, 6];
+
+/// Computer player looking [depth] moves ahead with alpha-beta pruned
+/// minimax, and weighing up the positions it gets to by the lines of four
+/// each side could still fill. Picks at random between equally good moves
+pub struct AlphaBeta {
+    depth: u32,
+    rng: StdRng, // the same seed always picks the same way
+}
+
+impl AlphaBeta {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Player looking [depth] moves ahead (1 to [MAX_DEPTH])
+    pub fn new(depth: u32, seed: u64) -> Self {
+        Self {
+            depth: depth.clamp(1, MAX_DEPTH),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Player<ConnectFour> for AlphaBeta {
+    fn choose_action(&mut self, game: &ConnectFour) -> Option<usize> {
+        if game.is_over() {
+            return None;
+        }
+        let me = game.turn();
+        let mut scored = Vec::new();
+        for col in COLUMN_ORDER {
+            if game.landing_row(col).is_none() {
+                continue;
+            }
+            let mut next = game.clone();
+            next.drop_disc(col);
+            let score = search(&next, me, self.depth - 1, 1, -WIN - 1, WIN + 1);
+            scored.push((col, score));
+        }
+        let best = scored.iter().map(|&(_, s)| s).max()?;
+        let best_cols: Vec<_> = scored
+            .into_iter()
+            .filter(|&(_, s)| s == best)
+            .map(|(col, _)| col)
+            .collect();
+        let &col = best_cols.choose(&mut self.rng)?;
+        debug!("alpha-beta plays column {col} for {best}");
+        Some(col)
+    }
+}
+
+// how good [game] is for [me], looking [depth] more moves ahead, with
+// [ply] moves made since the search started. Scores outside of
+// (alpha, beta) are only bounds, since they can't change the choice
+fn search(
+    game: &ConnectFour,
+    me: Disc,
+    depth: u32,
+    ply: i32,
+    mut alpha: i32,
+    mut beta: i32,
+) -> i32 {
+    match game.winner() {
+        Some(disc) if disc == me => return WIN - ply,
+        Some(_) => return ply - WIN,
+        None if game.is_full() => return 0,
+        None if depth == 0 => return evaluate(game, me),
+        None => {}
+    }
+    let maximizing = game.turn() == me;
+    let mut best = if maximizing { -WIN - 1 } else { WIN + 1 };
+    for col in COLUMN_ORDER {
+        if game.landing_row(col).is_none() {
+            continue;
+        }
+        let mut next = game.clone();
+        next.drop_disc(col);
+        let score = search(&next, me, depth - 1, ply + 1, alpha, beta);
+        if maximizing {
+            best = best.max(score);
+            alpha = alpha.max(score);
+        } else {
+            best = best.min(score);
+            beta = beta.min(score);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+// rough worth of a position that isn't over to [me]: lines of four with
+// some of [me]'s discs and none of the other side's count for, the other
+// way round against, and discs in the middle column a little extra
+fn evaluate(game: &ConnectFour, me: Disc) -> i32 {
+    let mut score = 0;
+    for window in ConnectFour::windows() {
+        let (mine, theirs) = window
+            .iter()
+            .fold((0, 0), |(mine, theirs), &p| match game.cell(p) {
+                Some(disc) if disc == me => (mine + 1, theirs),
+                Some(_) => (mine, theirs + 1),
+                None => (mine, theirs),
+            });
+        score += match (mine, theirs) {
+            (3, 0) => 50,
+            (2, 0) => 10,
+            (1, 0) => 1,
+            (0, 3) => -60,
+            (0, 2) => -10,
+            (0, 1) => -1,
+            _ => 0,
+        };
+    }
+    let center = COLS / 2;
+    for i in 0..game.size().0 {
+        match game.cell(Point(i, center)) {
+            Some(disc) if disc == me => score += 3,
+            Some(_) => score -= 3,
+            None => {}
+        }
+    }
+    score
+}
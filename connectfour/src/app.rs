@@ -0,0 +1,435 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{Color, StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Player, Point, TerminalGuard};
+
+use crate::alphabeta::{AlphaBeta, MAX_DEPTH};
+use crate::cli::Args;
+use crate::config::ConnectFourConfig;
+use crate::connectfour::{ConnectFour, Disc, MoveResult, COLS, ROWS};
+use crate::connectfourui::{self, ConnectFourUI, ConnectFourUIAction};
+
+// how far ahead the computer looks when nothing else sets it
+const DEFAULT_DEPTH: u32 = 5;
+
+// time a falling disc takes to pass each row
+const FALL_TICK: Duration = Duration::from_millis(40);
+
+// terminal columns each square takes up, including the space before it
+const SQUARE_COLS: usize = 2;
+
+// lines of text around the grid (cursor, bottom edge, column numbers,
+// gap, turn, score, message)
+const STATUS_ROWS: usize = 7;
+
+// a disc on its way down a column, after being dropped. It's already in
+// the game core, in the row it lands in, and only shown falling
+struct Falling {
+    col: usize,
+    row: usize, // where it's got to
+    res: MoveResult,
+}
+
+/// Connect Four app (the game being played, the computer player if there
+/// is one, the score over every game so far, and UI state)
+pub struct ConnectFourApp {
+    game: ConnectFour,
+    ui: ConnectFourUI,
+    computer: Option<AlphaBeta>, // none for two players at one keyboard
+    computer_disc: Disc,         // (swapping every game)
+    falling: Option<Falling>,
+    wins: (u32, u32), // you and the computer's, or red and yellow's
+    draws: u32,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last game decided against the computer
+    over: bool,
+}
+
+impl ConnectFourApp {
+    /// Game against [computer] playing [computer_disc], or between two
+    /// people without one
+    pub fn new(computer: Option<AlphaBeta>, computer_disc: Disc) -> Self {
+        Self {
+            game: ConnectFour::new(),
+            ui: ConnectFourUI::new(),
+            computer,
+            computer_disc,
+            falling: None,
+            wins: (0, 0),
+            draws: 0,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn is_computers_turn(&self) -> bool {
+        self.computer.is_some() && self.game.turn() == self.computer_disc && !self.game.is_over()
+    }
+
+    fn new_game(&mut self) {
+        self.game = ConnectFour::new();
+        self.computer_disc = self.computer_disc.other();
+        self.falling = None;
+        self.ui.set_cursor(COLS / 2);
+        self.message = "".to_string().reset();
+    }
+
+    // drop a disc down [col], and set it falling (what it led to only
+    // shows once it lands)
+    fn drop_disc(&mut self, col: usize) {
+        let disc = self.game.turn();
+        let res = self.game.drop_disc(col);
+        debug!("{disc:?} down column {col}: {res:?}");
+        match res {
+            MoveResult::Dropped(_) | MoveResult::Won(_) | MoveResult::Draw => {
+                self.falling = Some(Falling { col, row: 0, res });
+                self.message = "".to_string().reset();
+            }
+            MoveResult::ColumnFull | MoveResult::GameOver => self.handle_res(res),
+        }
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        let again = locale::text("connectfour.again");
+        match res {
+            MoveResult::Won(disc) if self.computer.is_none() => {
+                bell::ring("win");
+                match disc {
+                    Disc::Red => self.wins.0 += 1,
+                    Disc::Yellow => self.wins.1 += 1,
+                }
+                let wins = locale::text("connectfour.wins");
+                self.message = format!("{} {wins} {again}", disc_name(disc))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Won(disc) if disc == self.computer_disc => {
+                bell::ring("lose");
+                self.wins.1 += 1;
+                self.outcome = Outcome::Lost;
+                self.message = format!("{} {again}", locale::text("connectfour.lose"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+            }
+            MoveResult::Won(_) => {
+                bell::ring("win");
+                self.wins.0 += 1;
+                self.outcome = Outcome::Won;
+                self.message = format!("{} {again}", locale::text("connectfour.win"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Draw => {
+                self.draws += 1;
+                self.message = format!("{} {again}", locale::text("connectfour.draw")).bold();
+            }
+            MoveResult::ColumnFull => {
+                bell::ring("invalid move");
+                self.message = locale::text("connectfour.full")
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            MoveResult::GameOver => bell::ring("invalid move"),
+            MoveResult::Dropped(_) => self.message = "".to_string().reset(),
+        }
+    }
+
+    // what to show on square [p]: the disc there, unless it's still
+    // falling, or the falling disc, if it's got that far
+    fn shown_disc(&self, p: Point) -> Option<Disc> {
+        let Some(falling) = &self.falling else {
+            return self.game.cell(p);
+        };
+        let landing = self.game.last_drop();
+        match p {
+            Point(i, j) if j == falling.col && i == falling.row => {
+                landing.and_then(|q| self.game.cell(q))
+            }
+            _ if Some(p) == landing => None,
+            _ => self.game.cell(p),
+        }
+    }
+
+    /// Lines of the grid and what's around it, each a list of (styled) pieces
+    fn grid_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let muted = |s: &str| s.to_string().with(theme::color(Role::Muted));
+        let winning = self.game.winning_line().filter(|_| self.falling.is_none());
+        let mut lines = Vec::new();
+
+        // cursor over the column the next disc goes down
+        let mut cursor_line = vec![" ".repeat(SQUARE_COLS * (self.ui.get_cursor() + 1)).reset()];
+        if !self.game.is_over() && !self.is_computers_turn() {
+            let turn = self.game.turn();
+            cursor_line.push(match style::color_enabled() {
+                true => "▼".to_string().with(disc_color(turn)),
+                false => "v".to_string().bold(),
+            });
+        }
+        lines.push(cursor_line);
+
+        for i in 0..ROWS {
+            let mut line = vec![muted("│")];
+            for j in 0..COLS {
+                let p = Point(i, j);
+                line.push(" ".to_string().reset());
+                let disc = styled_disc(self.shown_disc(p));
+                match winning.is_some_and(|line| line.contains(&p)) {
+                    true => line.push(disc.on(theme::color(Role::Success))),
+                    false => line.push(disc),
+                }
+            }
+            line.push(muted(" │"));
+            lines.push(line);
+        }
+        lines.push(vec![muted(&format!(
+            "└{}┘",
+            "─".repeat(SQUARE_COLS * COLS + 1)
+        ))]);
+        let numbers: String = (1..=COLS).map(|k| format!(" {k}")).collect();
+        lines.push(vec![muted(&format!(" {numbers}"))]);
+        lines
+    }
+
+    // which side the player is on, and whose turn it is
+    fn turn_line(&self) -> String {
+        let mut parts = Vec::new();
+        if self.computer.is_some() {
+            let you = disc_name(self.computer_disc.other());
+            parts.push(format!("{} {you}", locale::text("connectfour.playing")));
+        }
+        if !self.game.is_over() {
+            let turn = disc_name(self.game.turn());
+            parts.push(format!("{turn} {}", locale::text("connectfour.to_move")));
+        }
+        parts.join("   ")
+    }
+
+    // games won by each side, and drawn
+    fn score_line(&self) -> String {
+        let (first, second) = match self.computer {
+            Some(_) => (
+                locale::text("connectfour.you").to_string(),
+                locale::text("connectfour.computer").to_string(),
+            ),
+            None => (disc_name(Disc::Red), disc_name(Disc::Yellow)),
+        };
+        format!(
+            "{first} {}   {second} {}   {} {}",
+            self.wins.0,
+            self.wins.1,
+            locale::text("connectfour.draws"),
+            self.draws
+        )
+    }
+}
+
+// a side's name, with the disc it plays (a letter, without color)
+fn disc_name(disc: Disc) -> String {
+    let name = match disc {
+        Disc::Red => locale::text("connectfour.red"),
+        Disc::Yellow => locale::text("connectfour.yellow"),
+    };
+    match style::color_enabled() {
+        true => name.to_string(),
+        false => format!("{name} ({})", disc_letter(disc)),
+    }
+}
+
+// (styled) disc on a square: a colored circle, or a letter without color
+// (a muted dot for no disc)
+fn styled_disc(disc: Option<Disc>) -> StyledContent<String> {
+    let Some(disc) = disc else {
+        return "·".to_string().with(theme::color(Role::Muted));
+    };
+    match style::color_enabled() {
+        true => "●".to_string().with(disc_color(disc)),
+        false => disc_letter(disc).to_string().bold(),
+    }
+}
+
+fn disc_color(disc: Disc) -> Color {
+    match disc {
+        Disc::Red => theme::current().cell(2),
+        Disc::Yellow => theme::current().cell(3),
+    }
+}
+
+fn disc_letter(disc: Disc) -> &'static str {
+    match disc {
+        Disc::Red => "X",
+        Disc::Yellow => "O",
+    }
+}
+
+// Pretty-print
+impl fmt::Display for ConnectFourApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.grid_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.turn_line())?;
+        write!(f, "{}\r\n", self.score_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", connectfourui::help_text())
+    }
+}
+
+impl Game for ConnectFourApp {
+    fn name(&self) -> &'static str {
+        "connectfour"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != ConnectFourUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            ConnectFourUIAction::Quit => self.over = true,
+            ConnectFourUIAction::NewGame => self.new_game(),
+            ConnectFourUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the grid");
+                }
+            }
+            // (nothing goes in while a disc is falling, or on the computer's turn)
+            ConnectFourUIAction::Drop | ConnectFourUIAction::Column(_)
+                if self.falling.is_some() || self.is_computers_turn() => {}
+            ConnectFourUIAction::Drop => self.drop_disc(self.ui.get_cursor()),
+            ConnectFourUIAction::Column(col) => {
+                self.ui.set_cursor(col);
+                self.drop_disc(col);
+            }
+            ConnectFourUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // move a falling disc down a row, or let the computer move once
+    // nothing's falling
+    fn tick(&mut self) {
+        if let Some(falling) = &mut self.falling {
+            let landing = self.game.last_drop().map_or(0, |Point(i, _)| i);
+            if falling.row < landing {
+                falling.row += 1;
+            } else {
+                let res = falling.res;
+                self.falling = None;
+                self.handle_res(res);
+            }
+            self.redraw.set(true);
+            return;
+        }
+        if !self.is_computers_turn() {
+            return;
+        }
+        if let Some(col) = self
+            .computer
+            .as_mut()
+            .and_then(|computer| computer.choose_action(&self.game))
+        {
+            self.drop_disc(col);
+            self.redraw.set(true);
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(FALL_TICK)
+    }
+
+    // the grid, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = connectfourui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max((SQUARE_COLS * COLS + 3) as u16),
+            (ROWS + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Connect Four against the computer, or two people at one keyboard
+pub fn run(args: Args) -> Result<(), String> {
+    let config: ConnectFourConfig = common::cli::setup(&args.common, "connectfour")?;
+
+    let depth = args.depth.or(config.depth).unwrap_or(DEFAULT_DEPTH);
+    if !(1..=MAX_DEPTH).contains(&depth) {
+        return Err(format!(
+            "bad [connectfour] config: depth {depth} (goes from 1 to {MAX_DEPTH})"
+        ));
+    }
+    let computer = match args.two_player || config.two_player {
+        true => None,
+        false => {
+            let seed = args.seed.unwrap_or_else(rand::random);
+            info!("connectfour seed {seed}, depth {depth}");
+            Some(AlphaBeta::new(depth, seed))
+        }
+    };
+    let computer_disc = match args.second {
+        true => Disc::Red,
+        false => Disc::Yellow,
+    };
+    let mut app = ConnectFourApp::new(computer, computer_disc);
+    app.ui
+        .bindings
+        .remap(&config.keys, &connectfourui::ACTIONS)
+        .map_err(|e| format!("bad [connectfour.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
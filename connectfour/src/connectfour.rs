@@ -0,0 +1,205 @@
+use common::{Core, Point};
+use log::debug;
+
+/// Board height, in rows
+pub const ROWS: usize = 6;
+/// Board width, in columns
+pub const COLS: usize = 7;
+
+// discs in a row it takes to win
+const CONNECT: usize = 4;
+
+// directions a line of discs can run in, as (rows, cols)
+const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// A player's discs (red always goes first)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Disc {
+    Red,
+    Yellow,
+}
+
+impl Disc {
+    /// The other player's discs
+    pub fn other(self) -> Self {
+        match self {
+            Disc::Red => Disc::Yellow,
+            Disc::Yellow => Disc::Red,
+        }
+    }
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    /// the disc landed in this row
+    Dropped(usize),
+    /// there's no room left in the column
+    ColumnFull,
+    /// the disc just dropped made four in a row
+    Won(Disc),
+    /// the board filled up with nobody winning
+    Draw,
+    /// the game has already ended
+    GameOver,
+}
+
+/// A game of Connect Four: the discs in the (upright) grid, and whose turn it is
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectFour {
+    grid: [[Option<Disc>; COLS]; ROWS], // top row first
+    turn: Disc,
+    winner: Option<Disc>,
+    last_drop: Option<Point>,
+}
+
+impl ConnectFour {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Empty grid, with red to move
+    pub fn new() -> Self {
+        Self {
+            grid: [[None; COLS]; ROWS],
+            turn: Disc::Red,
+            winner: None,
+            last_drop: None,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Drop a disc of whoever's turn it is down column [col]
+    pub fn drop_disc(&mut self, col: usize) -> MoveResult {
+        if self.is_over() {
+            return MoveResult::GameOver;
+        }
+        let Some(row) = self.landing_row(col) else {
+            return MoveResult::ColumnFull;
+        };
+        let disc = self.turn;
+        self.grid[row][col] = Some(disc);
+        self.turn = disc.other();
+        self.last_drop = Some(Point(row, col));
+        if self.line_through(Point(row, col)).is_some() {
+            debug!("{disc:?} wins in column {col}");
+            self.winner = Some(disc);
+            return MoveResult::Won(disc);
+        }
+        if self.is_full() {
+            return MoveResult::Draw;
+        }
+        MoveResult::Dropped(row)
+    }
+
+    /// Row a disc dropped down [col] would land in (None if the column's full)
+    pub fn landing_row(&self, col: usize) -> Option<usize> {
+        (0..ROWS).rev().find(|&row| self.grid[row][col].is_none())
+    }
+
+    /// Columns with room for another disc
+    pub fn open_columns(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..COLS).filter(|&col| self.grid[0][col].is_none())
+    }
+
+    /// Whose turn it is (once the game's over, whoever would have been next)
+    pub fn turn(&self) -> Disc {
+        self.turn
+    }
+
+    pub fn winner(&self) -> Option<Disc> {
+        self.winner
+    }
+
+    /// Where the last disc landed
+    pub fn last_drop(&self) -> Option<Point> {
+        self.last_drop
+    }
+
+    /// The four in a row, once someone has them
+    pub fn winning_line(&self) -> Option<[Point; CONNECT]> {
+        self.winner?;
+        self.line_through(self.last_drop?)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.open_columns().next().is_none()
+    }
+
+    /// Every run of four squares in a line on the board (for weighing up
+    /// positions)
+    pub fn windows() -> impl Iterator<Item = [Point; CONNECT]> {
+        (0..ROWS)
+            .flat_map(|i| (0..COLS).map(move |j| Point(i, j)))
+            .flat_map(|p| DIRECTIONS.map(|dir| run(p, dir)))
+            .flatten()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // four squares in a line through [p] with the same disc as [p], if any
+    fn line_through(&self, p: Point) -> Option<[Point; CONNECT]> {
+        let disc = self.cell(p)?;
+        DIRECTIONS.into_iter().find_map(|(di, dj)| {
+            // back up to the first of the line of discs, then take four
+            let mut start = p;
+            while let Some(q) = offset(start, (-di, -dj)).filter(|&q| self.cell(q) == Some(disc)) {
+                start = q;
+            }
+            let line = run(start, (di, dj))?;
+            line.iter()
+                .all(|&q| self.cell(q) == Some(disc))
+                .then_some(line)
+        })
+    }
+}
+
+impl Default for ConnectFour {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Core for ConnectFour {
+    type Action = usize;
+    type Outcome = MoveResult;
+    type Cell = Option<Disc>;
+
+    fn size(&self) -> (usize, usize) {
+        (ROWS, COLS)
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Option<Disc> {
+        self.grid[i][j]
+    }
+
+    fn act(&mut self, col: usize) -> MoveResult {
+        self.drop_disc(col)
+    }
+
+    fn is_over(&self) -> bool {
+        self.winner.is_some() || self.is_full()
+    }
+}
+
+// [CONNECT] squares in a line from [p] in direction [dir], if they're all
+// on the board
+fn run(p: Point, dir: (isize, isize)) -> Option<[Point; CONNECT]> {
+    let mut line = [p; CONNECT];
+    for k in 1..CONNECT {
+        line[k] = offset(line[k - 1], dir)?;
+    }
+    Some(line)
+}
+
+// square next to [p] in direction [dir], if that's on the board
+fn offset(Point(i, j): Point, (di, dj): (isize, isize)) -> Option<Point> {
+    let i = i.checked_add_signed(di).filter(|&i| i < ROWS)?;
+    let j = j.checked_add_signed(dj).filter(|&j| j < COLS)?;
+    Some(Point(i, j))
+}
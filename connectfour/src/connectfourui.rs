@@ -0,0 +1,116 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::connectfour::COLS;
+
+/// Help lines under the grid, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("connectfour.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectFourUIAction {
+    Wait,
+    Move(MoveDirection),
+    Drop,
+    /// drop a disc straight down column k (counting from 0)
+    Column(usize),
+    NewGame,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, ConnectFourUIAction); 15] = [
+    (
+        KeyBinding::key(KeyCode::Left),
+        ConnectFourUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        ConnectFourUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), ConnectFourUIAction::Drop),
+    (KeyBinding::key(KeyCode::Enter), ConnectFourUIAction::Drop),
+    (KeyBinding::key(KeyCode::Down), ConnectFourUIAction::Drop),
+    (KeyBinding::char('1'), ConnectFourUIAction::Column(0)),
+    (KeyBinding::char('2'), ConnectFourUIAction::Column(1)),
+    (KeyBinding::char('3'), ConnectFourUIAction::Column(2)),
+    (KeyBinding::char('4'), ConnectFourUIAction::Column(3)),
+    (KeyBinding::char('5'), ConnectFourUIAction::Column(4)),
+    (KeyBinding::char('6'), ConnectFourUIAction::Column(5)),
+    (KeyBinding::char('7'), ConnectFourUIAction::Column(6)),
+    (KeyBinding::char('n'), ConnectFourUIAction::NewGame),
+    (KeyBinding::char('q'), ConnectFourUIAction::Quit),
+    (KeyBinding::ctrl('c'), ConnectFourUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, ConnectFourUIAction); 5] = [
+    ("left", ConnectFourUIAction::Move(MoveDirection::Left)),
+    ("right", ConnectFourUIAction::Move(MoveDirection::Right)),
+    ("drop", ConnectFourUIAction::Drop),
+    ("new_game", ConnectFourUIAction::NewGame),
+    ("quit", ConnectFourUIAction::Quit),
+];
+
+pub struct ConnectFourUI {
+    cursor: usize, // column a disc would be dropped down
+    pub bindings: Bindings<ConnectFourUIAction>,
+}
+
+impl ConnectFourUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cursor starting over the middle column
+    pub fn new() -> Self {
+        Self {
+            cursor: COLS / 2,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> ConnectFourUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(ConnectFourUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> ConnectFourUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => ConnectFourUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a column, unless that's off the grid
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let col = match dir {
+            MoveDirection::Left => self.cursor.checked_sub(1),
+            MoveDirection::Right => Some(self.cursor + 1).filter(|&col| col < COLS),
+        };
+        self.cursor = col.ok_or("already at the edge")?;
+        Ok(())
+    }
+
+    pub fn set_cursor(&mut self, col: usize) {
+        self.cursor = col;
+    }
+
+    pub fn get_cursor(&self) -> usize {
+        self.cursor
+    }
+}
@@ -0,0 +1,21 @@
+//! Connect Four: the game core (an upright grid of discs, and whose turn it
+//! is), an alpha-beta computer player, plus the crossterm frontend for
+//! playing it in a terminal, against the computer or another person
+
+mod alphabeta;
+mod connectfour;
+pub use alphabeta::{AlphaBeta, MAX_DEPTH};
+pub use connectfour::{ConnectFour, Disc, MoveResult, COLS, ROWS};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod connectfourui;
+#[cfg(feature = "terminal")]
+pub use app::{run, ConnectFourApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,502 @@
+use std::env;
+use std::io::{self, stdout, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+use common::broadcast;
+use common::cast;
+use common::cli::GameArgs;
+use common::locale::{self, Locale};
+use common::replay::{self, Session};
+use common::stats::Stats;
+use common::theme::{self, Theme};
+use common::{config::KeyMap, Config, TerminalGuard};
+use crossterm::terminal;
+use log::LevelFilter;
+use serde::Deserialize;
+
+mod menu;
+use menu::{Menu, MenuAction};
+#[cfg(any(feature = "ssh", feature = "web"))]
+mod pty;
+#[cfg(feature = "ssh")]
+mod ssh;
+#[cfg(feature = "web")]
+mod web;
+
+/// A collection of small terminal games
+#[derive(Parser)]
+struct Cli {
+    /// Game to launch straight away (shows a menu if left out)
+    #[command(subcommand)]
+    game: Option<Game>,
+
+    /// Draw without colors (also turned off by setting NO_COLOR)
+    #[arg(long)]
+    no_color: bool,
+
+    /// Color theme: default, solarized, gruvbox or mono (passed on to the games)
+    #[arg(long)]
+    theme: Option<&'static Theme>,
+
+    /// Config file to read settings from, instead of
+    /// ~/.config/crossterm-games/config.toml (passed on to the games)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Record the game's random seed and every input to this file, to watch
+    /// it again with the replay subcommand (or, for a file ending in .cast,
+    /// every frame drawn, as an asciinema cast)
+    #[arg(long)]
+    record: Option<PathBuf>,
+
+    /// Stream the game as it's drawn to spectators connecting to this
+    /// address (like 0.0.0.0:7879), who watch with the watch subcommand
+    #[arg(long, value_name = "ADDR")]
+    broadcast: Option<String>,
+
+    /// Language for the games' text: en, de or ja (defaults to the one
+    /// in $LANG, if there's a translation for it)
+    #[arg(long)]
+    lang: Option<&'static Locale>,
+
+    /// Log messages at this level and above (error, warn, info, debug or
+    /// trace) to ~/.local/state/crossterm-games/log
+    #[arg(long)]
+    log_level: Option<LevelFilter>,
+
+    /// File to write the log to instead
+    #[arg(long, requires = "log_level")]
+    log_file: Option<PathBuf>,
+}
+
+/// Settings from the [menu] section of the config file
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MenuConfig {
+    /// color theme for the menu, e.g. "gruvbox"
+    #[serde(deserialize_with = "common::config::from_str")]
+    theme: Option<&'static Theme>,
+    /// keys for each action, e.g. select = "enter"
+    keys: KeyMap,
+}
+
+#[derive(Subcommand)]
+enum Game {
+    Mines(mines::Args),
+    #[command(alias = "gameoflife")]
+    Life(gameoflife::Args),
+    /// Brian's Brain, Life's three-state cousin (the same as life --variant brain)
+    #[command(long_about = None)]
+    Brain(gameoflife::Args),
+    Langton(langton::Args),
+    Sandpile(sandpile::Args),
+    Sand(sand::Args),
+    Snake(snake::Args),
+    Tetris(tetris::Args),
+    Sokoban(sokoban::Args),
+    Sudoku(sudoku::Args),
+    Nonogram(nonogram::Args),
+    Maze(maze::Args),
+    #[command(alias = "ttt")]
+    Tictactoe(tictactoe::Args),
+    #[command(alias = "c4")]
+    Connectfour(connectfour::Args),
+    #[command(alias = "othello")]
+    Reversi(reversi::Args),
+    Battleship(battleship::Args),
+    Hangman(hangman::Args),
+    Mastermind(mastermind::Args),
+    #[command(alias = "15")]
+    Fifteen(fifteen::Args),
+    #[command(alias = "lights")]
+    Lightsout(lightsout::Args),
+    Wumpus(wumpus::Args),
+    #[command(alias = "solitaire")]
+    Klondike(klondike::Args),
+    #[command(alias = "21")]
+    Blackjack(blackjack::Args),
+    Pong(pong::Args),
+    Tron(tron::Args),
+    #[command(alias = "spaceinvaders")]
+    Invaders(invaders::Args),
+    #[command(alias = "dino")]
+    Runner(runner::Args),
+    #[command(alias = "type")]
+    Typing(typing::Args),
+    #[command(alias = "roguelike")]
+    Rogue(rogue::Args),
+    #[command(alias = "draughts")]
+    Checkers(checkers::Args),
+    #[command(alias = "concentration")]
+    Memory(memory::Args),
+    /// Print totals of every game played so far
+    Stats,
+    /// Play back a session recorded with --record
+    Replay {
+        /// File the session was recorded to
+        file: PathBuf,
+    },
+    /// Serve the Minesweeper engine to bots over TCP, as JSON-RPC requests
+    /// and responses a line each
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        addr: String,
+    },
+    /// Host the games over SSH, a game of its own to each connection
+    /// (needs the ssh feature)
+    ServeSsh {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:2222")]
+        addr: String,
+        /// Private key the server proves who it is with, made if it doesn't
+        /// exist (defaults to one kept with the stats)
+        #[arg(long, value_name = "FILE")]
+        host_key: Option<PathBuf>,
+        /// Most games to run at once
+        #[arg(long, default_value_t = 32)]
+        max_players: usize,
+    },
+    /// Host the games on a web page, played in the browser (needs the web
+    /// feature)
+    ServeWeb {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        addr: String,
+        /// Most games to run at once
+        #[arg(long, default_value_t = 32)]
+        max_players: usize,
+    },
+    /// Watch a game someone is playing with --broadcast, read-only
+    Watch {
+        /// Address the game is broadcast on
+        addr: String,
+    },
+}
+
+// subcommands the menu launches
+const GAMES: [&str; 31] = [
+    "mines",
+    "life",
+    "brain",
+    "langton",
+    "sandpile",
+    "sand",
+    "snake",
+    "tetris",
+    "sokoban",
+    "sudoku",
+    "nonogram",
+    "maze",
+    "tictactoe",
+    "connectfour",
+    "reversi",
+    "battleship",
+    "hangman",
+    "mastermind",
+    "fifteen",
+    "lightsout",
+    "wumpus",
+    "klondike",
+    "blackjack",
+    "pong",
+    "tron",
+    "invaders",
+    "runner",
+    "typing",
+    "rogue",
+    "checkers",
+    "memory",
+];
+
+// name of a game in the menu, in the current language
+fn game_name(subcommand: &str) -> &'static str {
+    locale::text(&format!("menu.{subcommand}"))
+}
+
+impl Game {
+    // the game's arguments (None for the subcommands that aren't games)
+    fn args(&mut self) -> Option<&mut dyn GameArgs> {
+        match self {
+            Game::Mines(args) => Some(args),
+            Game::Life(args) | Game::Brain(args) => Some(args),
+            Game::Langton(args) => Some(args),
+            Game::Sandpile(args) => Some(args),
+            Game::Sand(args) => Some(args),
+            Game::Snake(args) => Some(args),
+            Game::Tetris(args) => Some(args),
+            Game::Sokoban(args) => Some(args),
+            Game::Sudoku(args) => Some(args),
+            Game::Nonogram(args) => Some(args),
+            Game::Maze(args) => Some(args),
+            Game::Tictactoe(args) => Some(args),
+            Game::Connectfour(args) => Some(args),
+            Game::Reversi(args) => Some(args),
+            Game::Battleship(args) => Some(args),
+            Game::Hangman(args) => Some(args),
+            Game::Mastermind(args) => Some(args),
+            Game::Fifteen(args) => Some(args),
+            Game::Lightsout(args) => Some(args),
+            Game::Wumpus(args) => Some(args),
+            Game::Klondike(args) => Some(args),
+            Game::Blackjack(args) => Some(args),
+            Game::Pong(args) => Some(args),
+            Game::Tron(args) => Some(args),
+            Game::Invaders(args) => Some(args),
+            Game::Runner(args) => Some(args),
+            Game::Typing(args) => Some(args),
+            Game::Rogue(args) => Some(args),
+            Game::Checkers(args) => Some(args),
+            Game::Memory(args) => Some(args),
+            Game::Stats
+            | Game::Replay { .. }
+            | Game::Serve { .. }
+            | Game::ServeSsh { .. }
+            | Game::ServeWeb { .. }
+            | Game::Watch { .. } => None,
+        }
+    }
+
+    // use the launcher's --config and --theme, unless the game was given its own
+    fn inherit(&mut self, path: &Option<PathBuf>, theme: Option<&'static Theme>) {
+        let Some(args) = self.args() else {
+            return;
+        };
+        let common = args.common();
+        if common.config.is_none() {
+            common.config.clone_from(path);
+        }
+        if common.theme.is_none() {
+            common.theme = theme;
+        }
+    }
+
+    // the game's --seed, for games that use random numbers
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        self.args()?.seed()
+    }
+
+    fn is_game(&self) -> bool {
+        !matches!(
+            self,
+            Game::Stats
+                | Game::Replay { .. }
+                | Game::Serve { .. }
+                | Game::ServeSsh { .. }
+                | Game::ServeWeb { .. }
+                | Game::Watch { .. }
+        )
+    }
+
+    fn run(self) -> Result<(), String> {
+        match self {
+            Game::Mines(args) => mines::run(args),
+            Game::Life(args) => gameoflife::run(args),
+            Game::Brain(mut args) => {
+                args.variant = gameoflife::Variant::Brain;
+                gameoflife::run(args)
+            }
+            Game::Langton(args) => langton::run(args),
+            Game::Sandpile(args) => sandpile::run(args),
+            Game::Sand(args) => sand::run(args),
+            Game::Snake(args) => snake::run(args),
+            Game::Tetris(args) => tetris::run(args),
+            Game::Sokoban(args) => sokoban::run(args),
+            Game::Sudoku(args) => sudoku::run(args),
+            Game::Nonogram(args) => nonogram::run(args),
+            Game::Maze(args) => maze::run(args),
+            Game::Tictactoe(args) => tictactoe::run(args),
+            Game::Connectfour(args) => connectfour::run(args),
+            Game::Reversi(args) => reversi::run(args),
+            Game::Battleship(args) => battleship::run(args),
+            Game::Hangman(args) => hangman::run(args),
+            Game::Mastermind(args) => mastermind::run(args),
+            Game::Fifteen(args) => fifteen::run(args),
+            Game::Lightsout(args) => lightsout::run(args),
+            Game::Wumpus(args) => wumpus::run(args),
+            Game::Klondike(args) => klondike::run(args),
+            Game::Blackjack(args) => blackjack::run(args),
+            Game::Pong(args) => pong::run(args),
+            Game::Tron(args) => tron::run(args),
+            Game::Invaders(args) => invaders::run(args),
+            Game::Runner(args) => runner::run(args),
+            Game::Typing(args) => typing::run(args),
+            Game::Rogue(args) => rogue::run(args),
+            Game::Checkers(args) => checkers::run(args),
+            Game::Memory(args) => memory::run(args),
+            Game::Stats => {
+                print!("{}", Stats::load()?);
+                Ok(())
+            }
+            Game::Replay { file } => play_back(&file),
+            Game::Serve { addr } => mines::serve(&addr),
+            #[cfg(feature = "ssh")]
+            Game::ServeSsh {
+                addr,
+                host_key,
+                max_players,
+            } => ssh::serve(&addr, host_key.as_deref(), max_players),
+            #[cfg(not(feature = "ssh"))]
+            Game::ServeSsh { .. } => {
+                Err("this build can't serve over SSH (rebuild with --features ssh)".into())
+            }
+            #[cfg(feature = "web")]
+            Game::ServeWeb { addr, max_players } => web::serve(&addr, max_players),
+            #[cfg(not(feature = "web"))]
+            Game::ServeWeb { .. } => {
+                Err("this build can't serve web pages (rebuild with --features web)".into())
+            }
+            Game::Watch { addr } => broadcast::watch(&addr),
+        }
+    }
+}
+
+// run a game, saving its seed and inputs (or what it drew, for a .cast
+// file) to [path] afterwards
+fn record(mut game: Game, path: &Path) -> Result<(), String> {
+    if !game.is_game() {
+        return Err("only games can be recorded".into());
+    }
+    if path.extension().is_some_and(|ext| ext == "cast") {
+        let (cols, rows) =
+            terminal::size().map_err(|e| format!("failed to get terminal size: {e}"))?;
+        cast::start(cols, rows);
+        let result = game.run();
+        let cast = cast::stop();
+        result?;
+        return cast.map_or(Ok(()), |cast| cast.save(path));
+    }
+    // pick the seed here, so it can go in the recording
+    let seed = game
+        .seed()
+        .map(|seed| *seed.get_or_insert_with(rand::random));
+    replay::record(Session::new(env::args().collect(), seed));
+    let result = game.run();
+    let session = replay::stop();
+    result?;
+    session.map_or(Ok(()), |session| session.save(path))
+}
+
+// run a game (recording it to [record_to], if given), streaming what it
+// draws to spectators on [addr] while it lasts
+fn broadcast_game(game: Game, addr: &str, record_to: Option<&Path>) -> Result<(), String> {
+    if !game.is_game() {
+        return Err("only games can be broadcast".into());
+    }
+    broadcast::start(addr)?;
+    let result = match record_to {
+        Some(path) => record(game, path),
+        None => game.run(),
+    };
+    broadcast::stop();
+    result
+}
+
+// run a game again from a recording, with the same command line and seed
+fn play_back(path: &Path) -> Result<(), String> {
+    let session = Session::load(path)?;
+    let cli = Cli::try_parse_from(&session.args)
+        .map_err(|e| format!("bad recording {}: {e}", path.display()))?;
+    let Some(mut game) = cli.game.filter(Game::is_game) else {
+        return Err(format!("no game in the recording {}", path.display()));
+    };
+    if cli.no_color {
+        common::style::disable_color();
+    }
+    game.inherit(&cli.config, cli.theme);
+    if let Some(seed) = game.seed() {
+        *seed = session.seed;
+    }
+    replay::replay(session);
+    let result = game.run();
+    replay::stop();
+    result
+}
+
+// show the main menu until the user quits, launching games with default settings
+fn run_menu(config_path: Option<PathBuf>, theme: Option<&'static Theme>) -> Result<(), String> {
+    let config: MenuConfig = Config::load(config_path.as_deref())?.section("menu")?;
+    if let Some(menu_theme) = theme.or(config.theme) {
+        theme::set(menu_theme);
+    }
+    let mut menu = Menu::new(GAMES.iter().map(|game| game_name(game)).collect());
+    menu.bindings
+        .remap(&config.keys, &menu::ACTIONS)
+        .map_err(|e| format!("bad [menu.keys] config: {e}"))?;
+    pick_games(&mut menu, &config_path, theme).map_err(|e| format!("menu failed: {e}"))
+}
+
+fn pick_games(
+    menu: &mut Menu,
+    config_path: &Option<PathBuf>,
+    theme: Option<&'static Theme>,
+) -> io::Result<()> {
+    let mut stdout = stdout();
+    loop {
+        // the guard is dropped before launching, since each game sets up its own screen
+        let guard = TerminalGuard::new()?;
+        let choice = loop {
+            print!("{}", menu);
+            stdout.flush()?;
+            match menu.wait_for_action_block()? {
+                MenuAction::Up => menu.move_selection(true),
+                MenuAction::Down => menu.move_selection(false),
+                MenuAction::Select => break Some(menu.selected()),
+                MenuAction::Quit => break None,
+                MenuAction::Wait => {}
+            }
+        };
+        drop(guard);
+
+        let Some(k) = choice else {
+            return Ok(());
+        };
+        let Some(mut game) = Cli::parse_from(["crossterm-games", GAMES[k]]).game else {
+            unreachable!("menu entries are all subcommands");
+        };
+        game.inherit(config_path, theme);
+        // a crashing game shouldn't take the whole launcher down with it
+        // (the panic hook has already restored the screen and printed the message)
+        menu.message = match panic::catch_unwind(AssertUnwindSafe(|| game.run())) {
+            Ok(Ok(())) => String::new(),
+            Ok(Err(e)) => format!("error: {e}"),
+            Err(_) => format!("error: {} crashed", game_name(GAMES[k])),
+        };
+    }
+}
+
+fn main() {
+    common::install_panic_hook();
+    let cli = Cli::parse();
+    if cli.no_color {
+        common::style::disable_color();
+    }
+    if let Some(lang) = cli.lang.or_else(locale::from_env) {
+        locale::set(lang);
+    }
+    if let Some(level) = cli.log_level {
+        if let Err(e) = common::logging::init(level, cli.log_file.as_deref()) {
+            eprintln!("error: {e}");
+            std::process::exit(1);
+        }
+    }
+    let result = match cli.game {
+        Some(mut game) => {
+            game.inherit(&cli.config, cli.theme);
+            match (cli.broadcast, cli.record) {
+                (Some(addr), path) => broadcast_game(game, &addr, path.as_deref()),
+                (None, Some(path)) => record(game, &path),
+                (None, None) => game.run(),
+            }
+        }
+        None if cli.record.is_some() => Err("--record needs a game to launch".into()),
+        None if cli.broadcast.is_some() => Err("--broadcast needs a game to launch".into()),
+        None => run_menu(cli.config, cli.theme),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
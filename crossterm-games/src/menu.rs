@@ -0,0 +1,122 @@
+use std::fmt;
+use std::io::{self, stdout};
+
+use crossterm::{
+    cursor,
+    event::{read, Event::Key, KeyCode},
+    queue,
+    style::Stylize,
+    terminal,
+};
+
+use common::locale;
+use common::theme::{self, Role};
+use common::{Bindings, KeyBinding};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MenuAction {
+    Wait,
+    Up,
+    Down,
+    Select,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, MenuAction); 11] = [
+    (KeyBinding::key(KeyCode::Up), MenuAction::Up),
+    (KeyBinding::char('w'), MenuAction::Up),
+    (KeyBinding::char('k'), MenuAction::Up),
+    (KeyBinding::key(KeyCode::Down), MenuAction::Down),
+    (KeyBinding::char('s'), MenuAction::Down),
+    (KeyBinding::char('j'), MenuAction::Down),
+    (KeyBinding::key(KeyCode::Enter), MenuAction::Select),
+    (KeyBinding::char(' '), MenuAction::Select),
+    (KeyBinding::char('q'), MenuAction::Quit),
+    (KeyBinding::key(KeyCode::Esc), MenuAction::Quit),
+    (KeyBinding::ctrl('c'), MenuAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, MenuAction); 4] = [
+    ("up", MenuAction::Up),
+    ("down", MenuAction::Down),
+    ("select", MenuAction::Select),
+    ("quit", MenuAction::Quit),
+];
+
+/// Main menu: a list of games to pick from
+pub struct Menu {
+    items: Vec<&'static str>,
+    selected: usize,
+    pub bindings: Bindings<MenuAction>,
+    pub message: String,
+}
+
+impl Menu {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(items: Vec<&'static str>) -> Self {
+        Self {
+            items,
+            selected: 0,
+            bindings: Bindings::new(&BINDINGS),
+            message: String::new(),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Move the selection, wrapping around at either end
+    pub fn move_selection(&mut self, up: bool) {
+        let n = self.items.len();
+        self.selected = if up {
+            (self.selected + n - 1) % n
+        } else {
+            (self.selected + 1) % n
+        };
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<MenuAction> {
+        if let Key(key_event) = read()? {
+            return Ok(self.bindings.action(key_event).unwrap_or(MenuAction::Wait));
+        }
+        Ok(MenuAction::Wait)
+    }
+}
+
+// Pretty-print
+impl fmt::Display for Menu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // clear screen
+        queue!(
+            stdout(),
+            cursor::MoveTo(0, 0),
+            terminal::Clear(terminal::ClearType::All)
+        )
+        .unwrap();
+
+        let title = format!("=== {} ===", locale::text("menu.title"));
+        write!(f, "{}\r\n\r\n", title.bold())?;
+        for (k, item) in self.items.iter().enumerate() {
+            if k == self.selected {
+                write!(f, " > {}\r\n", item.bold().with(theme::color(Role::Accent)))?;
+            } else {
+                write!(f, "   {item}\r\n")?;
+            }
+        }
+        write!(f, "\r\n{}\r\n", locale::text("menu.help"))?;
+        write!(f, "{}\r\n", self.message)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+//! Copies of the launcher run in pseudo-terminals of their own, for the
+//! servers that host the games to other people's terminals (over SSH, or on
+//! a web page)
+
+use std::env;
+use std::io::{self, Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use portable_pty::{native_pty_system, Child, ChildKiller, CommandBuilder, MasterPty, PtySize};
+
+use crate::GAMES;
+
+/// The launcher running in a pseudo-terminal, to send keys to, resize and
+/// (when dropped) kill. What it draws comes out of its [Output]
+pub struct Terminal {
+    pty: Box<dyn MasterPty + Send>,
+    input: Box<dyn Write + Send>,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+}
+
+/// What the launcher in a [Terminal] draws, until it exits
+pub struct Output {
+    reader: Box<dyn Read + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+/// How many games can run at once, shared between connections
+pub struct Slots {
+    taken: AtomicUsize,
+    max: usize,
+}
+
+/// Room for one game, given back when dropped
+pub struct Slot(Arc<Slots>);
+
+/// The game called [name], if there is one. Servers only take games' names
+/// from players, since other arguments could have the launcher read or
+/// write files on the server
+pub fn game(name: &str) -> Option<&'static str> {
+    GAMES.iter().copied().find(|&game| game == name)
+}
+
+/// Start the menu (or [game], see [game]) in a terminal [cols] by [rows],
+/// with [term] for $TERM and [env] besides
+pub fn spawn(
+    game: Option<&str>,
+    (cols, rows): (u16, u16),
+    term: &str,
+    env: &[(String, String)],
+) -> Result<(Terminal, Output), String> {
+    let exe = env::current_exe().map_err(|e| e.to_string())?;
+    let mut command = CommandBuilder::new(exe);
+    command.args(game);
+    command.env("TERM", term);
+    for (name, value) in env {
+        command.env(name, value);
+    }
+    // (so anything a game saves lands out of the way)
+    command.cwd(env::temp_dir());
+
+    let pty = native_pty_system()
+        .openpty(pty_size(cols, rows))
+        .map_err(|e| e.to_string())?;
+    let child = pty
+        .slave
+        .spawn_command(command)
+        .map_err(|e| e.to_string())?;
+    // (the output only ends once no one has the terminal open)
+    drop(pty.slave);
+    let reader = pty.master.try_clone_reader().map_err(|e| e.to_string())?;
+    let input = pty.master.take_writer().map_err(|e| e.to_string())?;
+    let terminal = Terminal {
+        pty: pty.master,
+        input,
+        killer: child.clone_killer(),
+    };
+    Ok((terminal, Output { reader, child }))
+}
+
+fn pty_size(cols: u16, rows: u16) -> PtySize {
+    PtySize {
+        rows: rows.max(1),
+        cols: cols.max(1),
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+impl Terminal {
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Type [data] into the terminal
+    pub fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.input.write_all(data)?;
+        self.input.flush()
+    }
+
+    pub fn resize(&self, cols: u16, rows: u16) -> Result<(), String> {
+        self.pty
+            .resize(pty_size(cols, rows))
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // (it may well have exited already)
+        self.killer.kill().ok();
+    }
+}
+
+impl Output {
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Wait for the launcher to exit, returning its exit code
+    pub fn wait(mut self) -> u32 {
+        self.child.wait().map_or(1, |status| status.exit_code())
+    }
+}
+
+impl Read for Output {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.reader.read(buf)
+    }
+}
+
+impl Slots {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(max: usize) -> Arc<Self> {
+        Arc::new(Self {
+            taken: AtomicUsize::new(0),
+            max,
+        })
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Room for another game, unless [Slots::new]'s max are running
+    pub fn take(self: &Arc<Self>) -> Option<Slot> {
+        let taken = self.taken.fetch_add(1, Ordering::Relaxed);
+        if taken >= self.max {
+            self.taken.fetch_sub(1, Ordering::Relaxed);
+            return None;
+        }
+        Some(Slot(self.clone()))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.taken.fetch_sub(1, Ordering::Relaxed);
+    }
+}
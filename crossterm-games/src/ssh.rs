@@ -0,0 +1,303 @@
+//! The games hosted over SSH, so anyone can play with nothing but an SSH
+//! client. Each connection gets a copy of the launcher running in a
+//! pseudo-terminal of its own, sized to the player's, with the menu (or the
+//! game named in the command, as in `ssh -t -p 2222 host tetris`).
+//!
+//! Anyone can log in, under any name and without a password.
+
+use std::fs;
+use std::io::Read;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+use rand::rngs::OsRng;
+use russh::keys::ssh_key::LineEnding;
+use russh::keys::{Algorithm, PrivateKey};
+use russh::server::{Auth, Config, Handler, Msg, Server, Session};
+use russh::{Channel, ChannelId, CryptoVec, MethodKind, MethodSet, Pty};
+use tokio::runtime;
+
+use crate::pty::{self, Slots, Terminal};
+use crate::GAMES;
+
+// connections idle this long are dropped
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+// environment variables players' SSH clients may pass on to their game
+const PASSED_ENV: [&str; 4] = ["LANG", "LC_ALL", "LC_MESSAGES", "NO_COLOR"];
+
+/// Serve the games on [addr] to up to [max_players] players at a time, with
+/// the host key in [host_key] (made on the first run), or in the data
+/// directory if None
+pub fn serve(addr: &str, host_key: Option<&Path>, max_players: usize) -> Result<(), String> {
+    let key_path = match host_key {
+        Some(path) => path.to_path_buf(),
+        None => default_key_path().ok_or("nowhere to keep the host key (HOME isn't set)")?,
+    };
+    let config = Arc::new(Config {
+        methods: MethodSet::from(&[MethodKind::None][..]),
+        keys: vec![load_or_make_key(&key_path)?],
+        inactivity_timeout: Some(IDLE_TIMEOUT),
+        ..Config::default()
+    });
+    let runtime = runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("failed to start the server: {e}"))?;
+    let mut games = Games {
+        slots: Slots::new(max_players),
+    };
+    println!("serving the games over SSH on {addr} (ssh -t -p <port> <host> [game])");
+    runtime
+        .block_on(games.run_on_address(config, addr))
+        .map_err(|e| format!("failed to serve on {addr}: {e}"))
+}
+
+/// `$XDG_DATA_HOME/crossterm-games/ssh_host_ed25519_key`, next to the stats
+fn default_key_path() -> Option<PathBuf> {
+    let stats = common::stats::default_path()?;
+    Some(stats.with_file_name("ssh_host_ed25519_key"))
+}
+
+// the server's key, made (and saved, so clients recognize the server the
+// next time) if there isn't one yet
+fn load_or_make_key(path: &Path) -> Result<PrivateKey, String> {
+    if path.exists() {
+        return PrivateKey::read_openssh_file(path)
+            .map_err(|e| format!("bad host key {}: {e}", path.display()));
+    }
+    let key = PrivateKey::random(&mut OsRng, Algorithm::Ed25519)
+        .map_err(|e| format!("failed to make a host key: {e}"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+    }
+    key.write_openssh_file(path, LineEnding::LF)
+        .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    info!("made a new host key, {}", path.display());
+    Ok(key)
+}
+
+// the server, handing each connection a [Player]
+struct Games {
+    slots: Arc<Slots>,
+}
+
+impl Server for Games {
+    type Handler = Player;
+
+    fn new_client(&mut self, peer: Option<SocketAddr>) -> Player {
+        let peer = peer.map_or_else(|| "?".to_string(), |addr| addr.to_string());
+        info!("{peer} connected");
+        Player {
+            peer,
+            term: "xterm".into(),
+            env: Vec::new(),
+            size: None,
+            game: None,
+            slots: self.slots.clone(),
+        }
+    }
+}
+
+// one connection, and the game it's playing once it asks for one
+struct Player {
+    peer: String,
+    term: String,               // $TERM of the player's terminal
+    env: Vec<(String, String)>, // other variables passed on (see [PASSED_ENV])
+    size: Option<(u16, u16)>,   // of the terminal, None until one is asked for
+    game: Option<Terminal>,
+    slots: Arc<Slots>,
+}
+
+impl Player {
+    // start the menu (or [game]) on [channel], sending what it draws back
+    // from a thread of its own and hanging up when it exits, or tell the
+    // player why not
+    fn launch(
+        &mut self,
+        channel: ChannelId,
+        game: Option<&str>,
+        session: &mut Session,
+    ) -> Result<(), russh::Error> {
+        session.channel_success(channel)?;
+        let Some(size) = self.size else {
+            return refuse(
+                channel,
+                "the games need a terminal: connect with ssh -t",
+                session,
+            );
+        };
+        let Some(slot) = self.slots.take() else {
+            return refuse(channel, "the server is full, try again later", session);
+        };
+        let (terminal, mut output) = match pty::spawn(game, size, &self.term, &self.env) {
+            Ok(spawned) => spawned,
+            Err(e) => {
+                warn!("failed to start a game for {}: {e}", self.peer);
+                return refuse(channel, "failed to start the game", session);
+            }
+        };
+        info!("{} playing {}", self.peer, game.unwrap_or("from the menu"));
+        self.game = Some(terminal);
+
+        let (runtime, handle) = (runtime::Handle::current(), session.handle());
+        let peer = self.peer.clone();
+        thread::spawn(move || {
+            let mut buf = [0; 4096];
+            loop {
+                let n = match output.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+                let data = CryptoVec::from_slice(&buf[..n]);
+                if runtime.block_on(handle.data(channel, data)).is_err() {
+                    break;
+                }
+            }
+            let status = output.wait();
+            drop(slot);
+            info!("{peer}'s game exited with {status}");
+            runtime.block_on(async {
+                // (the player may well have hung up already)
+                let _ = handle.exit_status_request(channel, status).await;
+                let _ = handle.eof(channel).await;
+                let _ = handle.close(channel).await;
+            });
+        });
+        Ok(())
+    }
+}
+
+impl Drop for Player {
+    fn drop(&mut self) {
+        // (which ends the game, if it's still going)
+        info!("{} disconnected", self.peer);
+    }
+}
+
+impl Handler for Player {
+    type Error = russh::Error;
+
+    async fn auth_none(&mut self, user: &str) -> Result<Auth, Self::Error> {
+        debug!("{} logged in as {user}", self.peer);
+        Ok(Auth::Accept)
+    }
+
+    async fn channel_open_session(
+        &mut self,
+        _channel: Channel<Msg>,
+        _session: &mut Session,
+    ) -> Result<bool, Self::Error> {
+        // (a game to a connection)
+        Ok(self.game.is_none())
+    }
+
+    async fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        term: &str,
+        cols: u32,
+        rows: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _modes: &[(Pty, u32)],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.term = term.to_string();
+        self.size = Some(size(cols, rows));
+        session.channel_success(channel)
+    }
+
+    async fn env_request(
+        &mut self,
+        channel: ChannelId,
+        name: &str,
+        value: &str,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if !PASSED_ENV.contains(&name) {
+            return session.channel_failure(channel);
+        }
+        self.env.push((name.to_string(), value.to_string()));
+        session.channel_success(channel)
+    }
+
+    async fn shell_request(
+        &mut self,
+        channel: ChannelId,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        self.launch(channel, None, session)
+    }
+
+    // only the name of a game is taken as a command (anything else could
+    // have the launcher read or write files on the server)
+    async fn exec_request(
+        &mut self,
+        channel: ChannelId,
+        data: &[u8],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let command = String::from_utf8_lossy(data);
+        let command = command.trim();
+        match pty::game(command) {
+            Some(game) => self.launch(channel, Some(game), session),
+            None => {
+                session.channel_success(channel)?;
+                let games = GAMES.join(", ");
+                let why = format!("no game called '{command}' (try one of: {games})");
+                refuse(channel, &why, session)
+            }
+        }
+    }
+
+    async fn window_change_request(
+        &mut self,
+        _channel: ChannelId,
+        cols: u32,
+        rows: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let (cols, rows) = size(cols, rows);
+        self.size = Some((cols, rows));
+        if let Some(game) = &self.game {
+            game.resize(cols, rows)
+                .unwrap_or_else(|e| warn!("failed to resize {}'s terminal: {e}", self.peer));
+        }
+        Ok(())
+    }
+
+    async fn data(
+        &mut self,
+        _channel: ChannelId,
+        data: &[u8],
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if let Some(game) = &mut self.game {
+            // (a game that's gone is noticed when its output ends)
+            game.write(data)
+                .unwrap_or_else(|e| debug!("failed to pass on {}'s input: {e}", self.peer));
+        }
+        Ok(())
+    }
+}
+
+// a terminal's (cols, rows), as SSH gives them
+fn size(cols: u32, rows: u32) -> (u16, u16) {
+    let clamp = |n: u32| n.clamp(1, u16::MAX as u32) as u16;
+    (clamp(cols), clamp(rows))
+}
+
+// tell the player [why] there's no game for them, and hang up
+fn refuse(channel: ChannelId, why: &str, session: &mut Session) -> Result<(), russh::Error> {
+    session.extended_data(channel, 1, CryptoVec::from(format!("{why}\r\n")))?;
+    session.exit_status_request(channel, 1)?;
+    session.eof(channel)?;
+    session.close(channel)
+}
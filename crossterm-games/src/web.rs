@@ -0,0 +1,269 @@
+//! The games on a web page, for people to try without installing anything.
+//! The page (`web/index.html`, built in) runs xterm.js, and opens a
+//! WebSocket back to the server, which starts a copy of the launcher in a
+//! pseudo-terminal of its own for it. What the launcher draws goes to the
+//! page as binary messages, and the page sends back JSON text messages:
+//!
+//! ```text
+//! {"input": "q"}
+//! {"resize": [80, 24]}
+//! ```
+//!
+//! The page's URL can name a game to start straight away (`/?game=tetris`),
+//! instead of the menu. Only the page itself may open a game: a WebSocket
+//! from a page on any other site (its `Origin` not matching the `Host` it
+//! connected to) is turned away.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use log::{debug, info, warn};
+use serde::Deserialize;
+use tungstenite::{Message, WebSocket};
+
+use crate::pty::{self, Slots, Terminal};
+
+const PAGE: &str = include_str!("../web/index.html");
+
+// how long to wait on the page for input before passing on what the game drew
+const POLL: Duration = Duration::from_millis(10);
+
+// how long a connection gets to send its request (and finish the handshake)
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// connections let in at once besides the games, while they fetch the page
+// (or wait for a game), so a pile of idle ones can't take up every thread
+const MAX_WAITING: usize = 16;
+
+// size of the terminal, if the page doesn't say
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+// a message from the page
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PageMessage {
+    // keys typed (or pasted)
+    Input(String),
+    // the terminal's new (cols, rows)
+    Resize(u16, u16),
+}
+
+/// Serve the page on [addr], running up to [max_players] games at a time
+pub fn serve(addr: &str, max_players: usize) -> Result<(), String> {
+    let listener =
+        TcpListener::bind(addr).map_err(|e| format!("failed to listen on {addr}: {e}"))?;
+    println!("serving the games on http://{addr}/");
+    let slots = Slots::new(max_players);
+    let connections = Slots::new(max_players + MAX_WAITING);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                // (hanging up on any more, rather than queueing them up)
+                let Some(connection_slot) = connections.take() else {
+                    debug!("too many connections, hanging up on one");
+                    continue;
+                };
+                let slots = slots.clone();
+                thread::spawn(move || {
+                    connection(stream, &slots);
+                    drop(connection_slot);
+                });
+            }
+            Err(e) => warn!("failed to accept a connection: {e}"),
+        }
+    }
+    Ok(())
+}
+
+// answer a connection: with the page, or (for the WebSocket it opens) a game
+fn connection(stream: TcpStream, slots: &Arc<Slots>) {
+    let peer = stream
+        .peer_addr()
+        .map_or_else(|_| "?".to_string(), |addr| addr.to_string());
+    if let Err(e) = stream.set_read_timeout(Some(REQUEST_TIMEOUT)) {
+        info!("{peer}: {e}");
+        return;
+    }
+    // (the request is left to read for whichever answers it)
+    let mut buf = [0; 4096];
+    let n = match stream.peek(&mut buf) {
+        Ok(n) if n > 0 => n,
+        // (hung up, or sent nothing in time)
+        _ => return,
+    };
+    let head = String::from_utf8_lossy(&buf[..n]);
+    let path = head.split_whitespace().nth(1).unwrap_or("/");
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+    let upgrade = header(&head, "upgrade")
+        .is_some_and(|value| value.to_ascii_lowercase().contains("websocket"));
+    let result = if upgrade && !same_origin(&head) {
+        debug!("refusing a WebSocket from another site");
+        respond(stream, "403 Forbidden", "text/plain", "forbidden\n", n).map_err(|e| e.to_string())
+    } else if upgrade {
+        play(stream, query, &peer, slots)
+    } else {
+        send_page(stream, path, n).map_err(|e| e.to_string())
+    };
+    if let Err(e) = result {
+        info!("{peer}: {e}");
+    }
+}
+
+// the page itself, for a GET of /, and nothing else, after reading out the
+// [len] bytes of the request (so closing doesn't reset the connection)
+fn send_page(stream: TcpStream, path: &str, len: usize) -> io::Result<()> {
+    let (status, kind, body) = match path {
+        "/" => ("200 OK", "text/html", PAGE),
+        _ => ("404 Not Found", "text/plain", "not found\n"),
+    };
+    debug!("GET {path}: {status}");
+    respond(stream, status, kind, body, len)
+}
+
+// answer with [status] and a [kind] [body], after reading out the [len] bytes
+// of the request
+fn respond(
+    mut stream: TcpStream,
+    status: &str,
+    kind: &str,
+    body: &str,
+    len: usize,
+) -> io::Result<()> {
+    stream.read_exact(&mut vec![0; len])?;
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {kind}; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+// value of the [name] header in the request [head], if it has one
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+    })
+}
+
+// whether the request [head] came from a page served from the host it
+// connected to (so not a page on some other site, which browsers would
+// otherwise happily open WebSockets to the games from)
+fn same_origin(head: &str) -> bool {
+    let (Some(origin), Some(host)) = (header(head, "origin"), header(head, "host")) else {
+        return false;
+    };
+    let origin = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+        .unwrap_or(origin);
+    origin.eq_ignore_ascii_case(host)
+}
+
+// run a game for the page at the other end of [stream], which asked for one
+// with [query], until either ends
+fn play(stream: TcpStream, query: &str, peer: &str, slots: &Arc<Slots>) -> Result<(), String> {
+    let mut socket =
+        tungstenite::accept(stream).map_err(|e| format!("WebSocket handshake failed: {e}"))?;
+    let params: HashMap<&str, &str> = query
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .collect();
+
+    let game = match params.get("game") {
+        Some(&name) => match pty::game(name) {
+            Some(game) => Some(game),
+            None => return refuse(&mut socket, &format!("no game called '{name}'")),
+        },
+        None => None,
+    };
+    let size = match (params.get("cols"), params.get("rows")) {
+        (Some(cols), Some(rows)) => (cols.parse(), rows.parse()),
+        _ => (Ok(DEFAULT_SIZE.0), Ok(DEFAULT_SIZE.1)),
+    };
+    let (Ok(cols), Ok(rows)) = size else {
+        return refuse(&mut socket, "bad terminal size");
+    };
+    let Some(slot) = slots.take() else {
+        return refuse(&mut socket, "the server is full, try again later");
+    };
+    let (mut terminal, mut output) = pty::spawn(game, (cols, rows), "xterm-256color", &[])?;
+    info!("{peer} playing {}", game.unwrap_or("from the menu"));
+
+    // what the game draws, read from a thread of its own
+    let (drawn, frames) = mpsc::channel();
+    let who = peer.to_string();
+    thread::spawn(move || {
+        let mut buf = [0; 4096];
+        loop {
+            let n = match output.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            if drawn.send(buf[..n].to_vec()).is_err() {
+                break;
+            }
+        }
+        let status = output.wait();
+        drop(slot);
+        info!("{who}'s game exited with {status}");
+    });
+
+    socket
+        .get_mut()
+        .set_read_timeout(Some(POLL))
+        .map_err(|e| e.to_string())?;
+    loop {
+        loop {
+            match frames.try_recv() {
+                Ok(frame) => socket
+                    .write(Message::binary(frame))
+                    .map_err(|e| e.to_string())?,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // (the page may well be gone already)
+                    socket.close(None).ok();
+                    socket.flush().ok();
+                    return Ok(());
+                }
+            }
+        }
+        socket.flush().map_err(|e| e.to_string())?;
+        match socket.read() {
+            Ok(Message::Text(text)) => handle(&text, &mut terminal, peer),
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) => {}
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+// pass on a message from the page to the game's terminal
+fn handle(message: &str, terminal: &mut Terminal, peer: &str) {
+    let result = match serde_json::from_str(message) {
+        Ok(PageMessage::Input(keys)) => terminal.write(keys.as_bytes()).map_err(|e| e.to_string()),
+        Ok(PageMessage::Resize(cols, rows)) => terminal.resize(cols, rows),
+        Err(e) => Err(format!("bad message {message}: {e}")),
+    };
+    if let Err(e) = result {
+        debug!("{peer}: {e}");
+    }
+}
+
+// tell the page [why] there's no game for it, and hang up
+fn refuse(socket: &mut WebSocket<TcpStream>, why: &str) -> Result<(), String> {
+    let message = Message::binary(format!("{why}\r\n").into_bytes());
+    socket.send(message).map_err(|e| e.to_string())?;
+    socket.close(None).map_err(|e| e.to_string())?;
+    socket.flush().map_err(|e| e.to_string())
+}
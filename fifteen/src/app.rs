@@ -0,0 +1,342 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{Game, Player, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::FifteenConfig;
+use crate::fifteen::{Fifteen, Slide, SlideResult};
+use crate::fifteenui::{self, FifteenUI, FifteenUIAction};
+use crate::solver::Solver;
+
+// tiles across when nothing else sets it
+const DEFAULT_SIZE: u8 = 4;
+
+// fewest and most tiles across there can be
+const MIN_SIZE: u8 = 3;
+const MAX_SIZE: u8 = 8;
+
+// terminal columns each tile takes up, and the gap between them
+const TILE_COLS: usize = 4;
+const GAP_COLS: usize = 1;
+
+// lines of text under the tiles (gap, moves and time, message)
+const STATUS_ROWS: usize = 3;
+
+// how often the clock is checked (it only shows whole seconds)
+const CLOCK_TICK: Duration = Duration::from_millis(100);
+
+// time between the solver's slides on the smallest puzzle (bigger ones
+// take far more slides, so they go quicker)
+const SOLVER_TICK: Duration = Duration::from_millis(150);
+
+/// Sliding puzzle app (the puzzle, the solver while it's putting it in
+/// order, the clock, and UI state)
+pub struct FifteenApp {
+    puzzle: Fifteen,
+    ui: FifteenUI,
+    solver: Option<Solver>, // while it's sliding the tiles
+    solved_by_solver: bool,
+    rng: StdRng,              // for shuffling
+    started: Option<Instant>, // at the first slide
+    elapsed: Duration,        // frozen once the puzzle's solved
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last puzzle solved, unless the solver did it
+    over: bool,
+}
+
+impl FifteenApp {
+    /// Puzzles [size] tiles across, shuffled with [seed]
+    pub fn new(size: u8, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self {
+            puzzle: Fifteen::shuffled(size as usize, rng.gen()),
+            ui: FifteenUI::new(),
+            solver: None,
+            solved_by_solver: false,
+            rng,
+            started: None,
+            elapsed: Duration::ZERO,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_game(&mut self) {
+        self.puzzle = Fifteen::shuffled(self.puzzle.size(), self.rng.gen());
+        self.solver = None;
+        self.solved_by_solver = false;
+        self.started = None;
+        self.elapsed = Duration::ZERO;
+        self.message = "".to_string().reset();
+    }
+
+    fn slide(&mut self, slide: Slide) {
+        if self.started.is_none() && !self.puzzle.is_solved() {
+            self.started = Some(Instant::now());
+        }
+        let res = self.puzzle.slide(slide);
+        self.handle_res(res);
+    }
+
+    // start the solver putting the puzzle in order, or stop it
+    fn toggle_solver(&mut self) {
+        if self.solver.take().is_some() {
+            self.message = "".to_string().reset();
+            return;
+        }
+        if self.puzzle.is_solved() {
+            bell::ring("invalid move");
+            return;
+        }
+        let mut solver = Solver::new();
+        // (working out the first slide works out whether the whole way
+        // there is the shortest)
+        let Some(slide) = solver.choose_action(&self.puzzle) else {
+            bell::ring("invalid move");
+            return;
+        };
+        self.message = match solver.optimal() {
+            Some(slides) => format!(
+                "{} ({slides} {})",
+                locale::text("fifteen.solving"),
+                locale::text("fifteen.fewest")
+            ),
+            None => locale::text("fifteen.solving").to_string(),
+        }
+        .with(theme::color(Role::Accent));
+        self.solved_by_solver = true;
+        self.solver = Some(solver);
+        self.slide(slide);
+    }
+
+    fn handle_res(&mut self, res: SlideResult) {
+        match res {
+            SlideResult::Solved(moves) => {
+                self.solver = None;
+                self.elapsed = self.started.map(|t| t.elapsed()).unwrap_or_default();
+                if self.solved_by_solver {
+                    self.message = locale::text("fifteen.solved_by_solver")
+                        .to_string()
+                        .with(theme::color(Role::Accent));
+                    return;
+                }
+                bell::ring("win");
+                self.outcome = Outcome::Won;
+                self.message = format!(
+                    "{} ({moves} {}, {}s) {}",
+                    locale::text("fifteen.win"),
+                    locale::text("fifteen.slides"),
+                    self.elapsed.as_secs(),
+                    locale::text("fifteen.again")
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success));
+            }
+            SlideResult::Blocked | SlideResult::GameOver => bell::ring("invalid move"),
+            SlideResult::Moved => {}
+        }
+    }
+
+    // time between the solver's slides
+    fn solver_tick(&self) -> Duration {
+        SOLVER_TICK / (self.puzzle.size() as u32 - 2)
+    }
+
+    /// Rows of tiles, a (styled) piece per tile, with a blank line
+    /// between rows: tiles in their places stand out
+    fn tile_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let size = self.puzzle.size();
+        let mut lines = Vec::new();
+        for i in 0..size {
+            if i > 0 {
+                lines.push(vec!["".to_string().reset()]);
+            }
+            let mut line = Vec::new();
+            for j in 0..size {
+                if j > 0 {
+                    line.push(" ".repeat(GAP_COLS).reset());
+                }
+                let k = i * size + j;
+                line.push(match self.puzzle.tiles()[k] {
+                    0 => " ".repeat(TILE_COLS).reset(),
+                    tile if self.puzzle.is_in_place(k) => format!("{tile:>3} ")
+                        .bold()
+                        .with(theme::color(Role::Accent))
+                        .reverse(),
+                    tile => format!("{tile:>3} ")
+                        .with(theme::color(Role::Text))
+                        .reverse(),
+                });
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    // slides made, and time taken
+    fn counters(&self) -> String {
+        format!(
+            "{} {}   {} {}s",
+            locale::text("fifteen.slides_made"),
+            self.puzzle.moves(),
+            locale::text("fifteen.time"),
+            self.elapsed.as_secs()
+        )
+    }
+}
+
+// Pretty-print
+impl fmt::Display for FifteenApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.tile_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.counters())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", fifteenui::help_text())
+    }
+}
+
+impl Game for FifteenApp {
+    fn name(&self) -> &'static str {
+        "fifteen"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != FifteenUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            FifteenUIAction::Quit => self.over = true,
+            FifteenUIAction::NewGame => self.new_game(),
+            FifteenUIAction::Slide(slide) => {
+                // (taking over from the solver)
+                if self.solver.take().is_some() {
+                    self.message = "".to_string().reset();
+                }
+                self.slide(slide);
+            }
+            FifteenUIAction::ToggleSolver => self.toggle_solver(),
+            FifteenUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // keep the clock running, and the solver sliding tiles
+    fn tick(&mut self) {
+        if let Some(solver) = &mut self.solver {
+            match solver.choose_action(&self.puzzle) {
+                Some(slide) => self.slide(slide),
+                None => self.solver = None,
+            }
+            self.redraw.set(true);
+        }
+        if self.puzzle.is_solved() {
+            return;
+        }
+        let Some(started) = self.started else {
+            return;
+        };
+        let elapsed = started.elapsed();
+        if elapsed.as_secs() != self.elapsed.as_secs() {
+            self.redraw.set(true);
+        }
+        self.elapsed = elapsed;
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        match self.solver {
+            Some(_) => Some(self.solver_tick()),
+            None => Some(CLOCK_TICK),
+        }
+    }
+
+    // the tiles, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = fifteenui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        let size = self.puzzle.size();
+        (
+            cols.max((size * (TILE_COLS + GAP_COLS)) as u16),
+            (2 * size - 1 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play a sliding puzzle, with a solver to show how it's done
+pub fn run(args: Args) -> Result<(), String> {
+    let config: FifteenConfig = common::cli::setup(&args.common, "fifteen")?;
+
+    let size = args.size.or(config.size).unwrap_or(DEFAULT_SIZE);
+    if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+        return Err(format!(
+            "bad [fifteen] config: size {size} (goes from {MIN_SIZE} to {MAX_SIZE})"
+        ));
+    }
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("fifteen seed {seed}, size {size}");
+
+    let mut app = FifteenApp::new(size, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &fifteenui::ACTIONS)
+        .map_err(|e| format!("bad [fifteen.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [fifteen] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FifteenConfig {
+    /// tiles across (and down), from 3 to 8
+    pub size: Option<u8>,
+    /// keys for each action, e.g. solver = "?"
+    pub keys: KeyMap,
+}
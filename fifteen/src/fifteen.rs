@@ -0,0 +1,216 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// Way a tile slides, into the gap next to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slide {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Slide {
+    pub const ALL: [Slide; 4] = [Slide::Up, Slide::Down, Slide::Left, Slide::Right];
+
+    /// The slide that undoes this one
+    pub fn opposite(self) -> Slide {
+        match self {
+            Slide::Up => Slide::Down,
+            Slide::Down => Slide::Up,
+            Slide::Left => Slide::Right,
+            Slide::Right => Slide::Left,
+        }
+    }
+}
+
+/// What a slide led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlideResult {
+    Moved,
+    /// the slide put every tile in place, after this many
+    Solved(usize),
+    /// there's no tile on that side of the gap
+    Blocked,
+    /// the puzzle's already solved
+    GameOver,
+}
+
+/// A sliding puzzle: tiles numbered from 1 in a square with one gap, to be
+/// put in order (1 top left, the gap bottom right)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fifteen {
+    size: usize,
+    tiles: Vec<u8>, // row by row, 0 for the gap
+    gap: usize,     // where the 0 is
+    moves: usize,
+}
+
+impl Fifteen {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Puzzle [size] tiles across, already in order
+    pub fn solved(size: usize) -> Self {
+        let count = size * size;
+        let mut tiles: Vec<u8> = (1..count as u8).collect();
+        tiles.push(0);
+        Self {
+            size,
+            tiles,
+            gap: count - 1,
+            moves: 0,
+        }
+    }
+
+    /// Puzzle [size] tiles across, shuffled (the same seed always gives the
+    /// same puzzle). Only half of all shuffles can be solved, so if this
+    /// one can't, two tiles are swapped to make one that can
+    pub fn shuffled(size: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut puzzle = Self::solved(size);
+        while puzzle.is_solved() {
+            puzzle.tiles.shuffle(&mut rng);
+            if !is_solvable(size, &puzzle.tiles) {
+                let (a, b) = match puzzle.tiles[..2].contains(&0) {
+                    true => (size * size - 2, size * size - 1),
+                    false => (0, 1),
+                };
+                puzzle.tiles.swap(a, b);
+            }
+            puzzle.gap = puzzle.tiles.iter().position(|&t| t == 0).unwrap_or(0);
+        }
+        debug!("shuffled: {:?}", puzzle.tiles);
+        puzzle
+    }
+
+    /// Puzzle laid out as [tiles] (row by row, 0 for the gap), if that's a
+    /// square with every tile once that can be put in order
+    pub fn from_tiles(tiles: Vec<u8>) -> Result<Self, String> {
+        let size = (tiles.len() as f64).sqrt() as usize;
+        if size * size != tiles.len() || size < 2 {
+            return Err(format!("{} tiles don't make a square", tiles.len()));
+        }
+        let mut sorted = tiles.clone();
+        sorted.sort_unstable();
+        if sorted.iter().enumerate().any(|(k, &t)| t as usize != k) {
+            return Err("each tile has to be there once".to_string());
+        }
+        if !is_solvable(size, &tiles) {
+            return Err("that can't be put in order".to_string());
+        }
+        let gap = tiles.iter().position(|&t| t == 0).unwrap_or(0);
+        Ok(Self {
+            size,
+            tiles,
+            gap,
+            moves: 0,
+        })
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Slide the tile on the [slide] side of the gap into it
+    pub fn slide(&mut self, slide: Slide) -> SlideResult {
+        if self.is_solved() {
+            return SlideResult::GameOver;
+        }
+        let Some(from) = self.tile_beside_gap(slide) else {
+            return SlideResult::Blocked;
+        };
+        self.tiles.swap(from, self.gap);
+        self.gap = from;
+        self.moves += 1;
+        match self.is_solved() {
+            true => SlideResult::Solved(self.moves),
+            false => SlideResult::Moved,
+        }
+    }
+
+    /// Where the tile that would slide [slide] is, if there is one (it's
+    /// on the opposite side of the gap)
+    pub fn tile_beside_gap(&self, slide: Slide) -> Option<usize> {
+        let (i, j) = (self.gap / self.size, self.gap % self.size);
+        let last = self.size - 1;
+        match slide {
+            Slide::Up => (i < last).then(|| self.gap + self.size),
+            Slide::Down => (i > 0).then(|| self.gap - self.size),
+            Slide::Left => (j < last).then(|| self.gap + 1),
+            Slide::Right => (j > 0).then(|| self.gap - 1),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Tiles row by row, 0 for the gap
+    pub fn tiles(&self) -> &[u8] {
+        &self.tiles
+    }
+
+    /// Where the gap is in [tiles]
+    pub fn gap(&self) -> usize {
+        self.gap
+    }
+
+    /// Slides made so far
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// Whether the tile at [k] is where it belongs
+    pub fn is_in_place(&self, k: usize) -> bool {
+        self.tiles[k] as usize == k + 1
+    }
+
+    pub fn is_solved(&self) -> bool {
+        (0..self.tiles.len() - 1).all(|k| self.is_in_place(k))
+    }
+}
+
+/// Whether [tiles] ([size] across, row by row, 0 for the gap) can be put
+/// in order. Each slide either keeps the gap on its row and doesn't change
+/// the order of the tiles, or moves it a row and moves one tile past
+/// [size] - 1 others. So across an odd size the count of pairs out of
+/// order is always even, and across an even one it's even plus the rows
+/// the gap is from the bottom
+pub fn is_solvable(size: usize, tiles: &[u8]) -> bool {
+    let numbered: Vec<u8> = tiles.iter().copied().filter(|&t| t != 0).collect();
+    let inversions = (0..numbered.len())
+        .flat_map(|a| (a + 1..numbered.len()).map(move |b| (a, b)))
+        .filter(|&(a, b)| numbered[a] > numbered[b])
+        .count();
+    let gap_row = tiles.iter().position(|&t| t == 0).unwrap_or(0) / size;
+    match size % 2 {
+        1 => inversions.is_multiple_of(2),
+        _ => (inversions + size - 1 - gap_row).is_multiple_of(2),
+    }
+}
+
+impl Core for Fifteen {
+    type Action = Slide;
+    type Outcome = SlideResult;
+    /// The number on the tile, or none for the gap
+    type Cell = Option<u8>;
+
+    fn size(&self) -> (usize, usize) {
+        (self.size, self.size)
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Option<u8> {
+        Some(self.tiles[i * self.size + j]).filter(|&t| t != 0)
+    }
+
+    fn act(&mut self, slide: Slide) -> SlideResult {
+        self.slide(slide)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_solved()
+    }
+}
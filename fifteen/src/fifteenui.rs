@@ -0,0 +1,90 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::fifteen::Slide;
+
+/// Help lines under the puzzle, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("fifteen.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FifteenUIAction {
+    Wait,
+    /// slide the tile on the other side of the gap this way
+    Slide(Slide),
+    /// start or stop the solver putting the puzzle in order
+    ToggleSolver,
+    NewGame,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, FifteenUIAction); 8] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        FifteenUIAction::Slide(Slide::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        FifteenUIAction::Slide(Slide::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        FifteenUIAction::Slide(Slide::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        FifteenUIAction::Slide(Slide::Right),
+    ),
+    (KeyBinding::char('s'), FifteenUIAction::ToggleSolver),
+    (KeyBinding::char('n'), FifteenUIAction::NewGame),
+    (KeyBinding::char('q'), FifteenUIAction::Quit),
+    (KeyBinding::ctrl('c'), FifteenUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, FifteenUIAction); 7] = [
+    ("up", FifteenUIAction::Slide(Slide::Up)),
+    ("down", FifteenUIAction::Slide(Slide::Down)),
+    ("left", FifteenUIAction::Slide(Slide::Left)),
+    ("right", FifteenUIAction::Slide(Slide::Right)),
+    ("solver", FifteenUIAction::ToggleSolver),
+    ("new_game", FifteenUIAction::NewGame),
+    ("quit", FifteenUIAction::Quit),
+];
+
+pub struct FifteenUI {
+    pub bindings: Bindings<FifteenUIAction>,
+}
+
+impl FifteenUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> FifteenUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(FifteenUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> FifteenUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => FifteenUIAction::Wait,
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Sliding puzzle (the 15-puzzle, at any size): the game core, an IDA*
+//! solver, plus the crossterm frontend for playing it in a terminal
+
+mod fifteen;
+mod solver;
+pub use fifteen::{is_solvable, Fifteen, Slide, SlideResult};
+pub use solver::{solve, Solution, Solver};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod fifteenui;
+#[cfg(feature = "terminal")]
+pub use app::{run, FifteenApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
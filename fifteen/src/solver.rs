@@ -0,0 +1,444 @@
+use common::{Core, Player};
+use log::debug;
+
+use crate::fifteen::{Fifteen, Slide};
+
+// most positions to look at in each search for a way to put a whole puzzle
+// in order, before giving up on it and putting it in order a row or column
+// at a time instead (down to the last three by three)
+const NODE_LIMIT: usize = 100_000;
+
+// biggest puzzle worth searching as a whole
+const WHOLE_SIZE: usize = 4;
+
+// most positions to look at in each search putting a tile in place, before
+// going on to one that counts the slides still to make more heavily
+const PLACING_LIMIT: usize = 10_000;
+
+// how heavily each search counts the slides still to make (in halves: 2
+// is the real count, which finds the fewest slides there can be; more
+// finds a way quicker, but a longer way round)
+const WEIGHTS: [usize; 6] = [2, 3, 4, 6, 10, 20];
+
+// size of the corner left to put in order all at once
+const LAST_CORNER: usize = 3;
+
+/// A way to put a puzzle in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub slides: Vec<Slide>,
+    /// whether there's no shorter way
+    pub optimal: bool,
+}
+
+/// Find a way to put [puzzle] in order, with IDA*: depth-first searches
+/// going a little deeper each time, cut off where the slides made so far
+/// plus a lower bound on the slides still to make (each tile's distance
+/// from its place, plus two for each pair of tiles in their row or column
+/// that have to get past each other) gets too big. Small puzzles are
+/// searched as a whole, and bigger ones (or small ones that take too long)
+/// a tile at a time, the way people do them. Returns none if a search
+/// gives up
+pub fn solve(puzzle: &Fifteen) -> Option<Solution> {
+    if let Some(solution) = solve_whole(puzzle) {
+        return Some(solution);
+    }
+    let mut puzzle = puzzle.clone();
+    let mut slides = Vec::new();
+    for stage in stages(puzzle.size()) {
+        slides.extend(stage.search(&mut puzzle)?);
+    }
+    debug!("solved a tile at a time, in {} slides", slides.len());
+    Some(Solution {
+        slides,
+        optimal: false,
+    })
+}
+
+// a way to put all of [puzzle] in order at once, if it's small enough and
+// one turns up soon enough
+fn solve_whole(puzzle: &Fifteen) -> Option<Solution> {
+    if puzzle.size() > WHOLE_SIZE {
+        return None;
+    }
+    let stage = Stage::whole(puzzle.size());
+    WEIGHTS[..3].iter().find_map(|&weight| {
+        let slides = Search::new(puzzle, &stage, weight).run(NODE_LIMIT)?;
+        debug!("solved in {} slides (weight {weight})", slides.len());
+        Some(Solution {
+            slides,
+            optimal: weight == WEIGHTS[0],
+        })
+    })
+}
+
+/// Computer player sliding tiles the way [solve] does, working out the
+/// slides a stage at a time (so the work's spread out between slides), and
+/// starting again whenever the puzzle isn't where it left it
+pub struct Solver {
+    plan: Vec<Slide>,   // slides still to make in this stage, the next one last
+    expected: Vec<u8>,  // tiles the next slide should be made on
+    stages: Vec<Stage>, // still to go, the next one last
+    optimal: Option<usize>,
+}
+
+impl Solver {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            plan: Vec::new(),
+            expected: Vec::new(),
+            stages: Vec::new(),
+            optimal: None,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// The slides it'll take, if they're known to be the fewest there can
+    /// be (only for small puzzles)
+    pub fn optimal(&self) -> Option<usize> {
+        self.optimal
+    }
+
+    // start putting [puzzle] in order
+    fn start(&mut self, puzzle: &Fifteen) {
+        self.optimal = None;
+        self.stages.clear();
+        match solve_whole(puzzle) {
+            Some(solution) => {
+                if solution.optimal {
+                    self.optimal = Some(solution.slides.len());
+                }
+                self.plan = solution.slides.into_iter().rev().collect();
+            }
+            None => {
+                self.plan.clear();
+                self.stages = stages(puzzle.size());
+                self.stages.reverse();
+            }
+        }
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player<Fifteen> for Solver {
+    fn choose_action(&mut self, puzzle: &Fifteen) -> Option<Slide> {
+        if puzzle.is_over() {
+            return None;
+        }
+        if self.expected != puzzle.tiles() {
+            self.start(puzzle);
+        }
+        while self.plan.is_empty() {
+            let stage = self.stages.pop()?;
+            let mut after = puzzle.clone();
+            self.plan = stage.search(&mut after)?;
+            self.plan.reverse();
+        }
+        let slide = self.plan.pop()?;
+        let mut next = puzzle.clone();
+        next.slide(slide);
+        self.expected = next.tiles().to_vec();
+        Some(slide)
+    }
+}
+
+// a step of the way to putting a puzzle in order: tiles to get to squares
+// (fetching one of them), without touching squares done with
+struct Stage {
+    targets: Vec<Option<usize>>, // by tile number
+    fixed: Vec<bool>,            // by square
+    lead: Option<u8>,
+}
+
+impl Stage {
+    // every tile to its place
+    fn whole(size: usize) -> Self {
+        let count = size * size;
+        Self {
+            targets: (0..count).map(|tile| tile.checked_sub(1)).collect(),
+            fixed: vec![false; count],
+            lead: None,
+        }
+    }
+
+    // slides for this stage, made on [puzzle], searching less and less
+    // carefully until they're found
+    fn search(&self, puzzle: &mut Fifteen) -> Option<Vec<Slide>> {
+        let slides = WEIGHTS
+            .iter()
+            .find_map(|&weight| Search::new(puzzle, self, weight).run(PLACING_LIMIT))?;
+        for &slide in &slides {
+            puzzle.slide(slide);
+        }
+        Some(slides)
+    }
+}
+
+// the stages putting a puzzle [size] across in order: a row or column at a
+// time (whichever's longer), a tile at a time, then the corner left
+fn stages(size: usize) -> Vec<Stage> {
+    let mut stages = Vec::new();
+    let mut stage = Stage {
+        targets: vec![None; size * size],
+        fixed: vec![false; size * size],
+        lead: None,
+    };
+    let mut next = |stage: &Stage, tile: usize, k: usize, lead: bool| {
+        let mut targets = stage.targets.clone();
+        targets[tile] = Some(k);
+        stages.push(Stage {
+            targets,
+            fixed: stage.fixed.clone(),
+            lead: lead.then_some(tile as u8),
+        });
+    };
+    let (mut top, mut left) = (0, 0);
+    while size - top > LAST_CORNER || size - left > LAST_CORNER {
+        let across = size - top >= size - left;
+        let (line, inward): (Vec<usize>, usize) = match across {
+            true => ((left..size).map(|j| top * size + j).collect(), size),
+            false => ((top..size).map(|i| i * size + left).collect(), 1),
+        };
+        let (last, next_to_last) = (line[line.len() - 1], line[line.len() - 2]);
+        for &k in &line[..line.len() - 2] {
+            next(&stage, k + 1, k, true);
+            stage.targets[k + 1] = Some(k);
+        }
+        // the last one can't go in after the one before it: it goes where
+        // that one does, with that one just inside it, and both go round
+        // into place together
+        next(&stage, last + 1, next_to_last, true);
+        stage.targets[last + 1] = Some(next_to_last);
+        next(&stage, next_to_last + 1, next_to_last + inward, true);
+        stage.targets[last + 1] = Some(last);
+        stage.targets[next_to_last + 1] = Some(next_to_last);
+        next(&stage, next_to_last + 1, next_to_last, false);
+
+        for &k in &line {
+            stage.fixed[k] = true;
+        }
+        match across {
+            true => top += 1,
+            false => left += 1,
+        }
+    }
+    let whole = Stage::whole(size);
+    stages.push(Stage {
+        fixed: stage.fixed,
+        ..whole
+    });
+    stages
+}
+
+// one depth-first search, with the puzzle changed in place as it goes
+struct Search<'a> {
+    size: usize,
+    tiles: Vec<u8>,
+    gap: usize,
+    targets: &'a [Option<usize>], // where tiles have to get to, by number
+    fixed: &'a [bool],            // squares not to touch
+    lead: Option<(u8, usize)>,    // tile the gap should go and fetch, and where it is
+    weight: usize,
+    path: Vec<Slide>,
+    nodes: usize,
+    node_limit: usize,
+}
+
+impl<'a> Search<'a> {
+    fn new(puzzle: &Fifteen, stage: &'a Stage, weight: usize) -> Self {
+        Self {
+            size: puzzle.size(),
+            tiles: puzzle.tiles().to_vec(),
+            gap: puzzle.gap(),
+            targets: &stage.targets,
+            fixed: &stage.fixed,
+            lead: stage.lead.and_then(|lead| {
+                let k = puzzle.tiles().iter().position(|&t| t == lead)?;
+                Some((lead, k))
+            }),
+            weight,
+            path: Vec::new(),
+            nodes: 0,
+            node_limit: 0,
+        }
+    }
+
+    // search deeper each time, until a way's found or [node_limit]
+    // positions have been looked at
+    fn run(&mut self, node_limit: usize) -> Option<Vec<Slide>> {
+        self.node_limit = node_limit;
+        let h = self.estimate();
+        let mut bound = self.weight * h;
+        loop {
+            match self.dfs(0, h, bound)? {
+                Ok(()) => return Some(std::mem::take(&mut self.path)),
+                Err(next) => bound = next,
+            }
+        }
+    }
+
+    // look below the position [g] slides in, [h] from the goal by the
+    // estimate. Ok if it found the way, else the smallest cost past the
+    // bound seen (to search up to next), or none if out of positions
+    fn dfs(&mut self, g: usize, h: usize, bound: usize) -> Option<Result<(), usize>> {
+        let cost = 2 * g + self.weight * h;
+        if cost > bound {
+            return Some(Err(cost));
+        }
+        if h == 0 {
+            return Some(Ok(()));
+        }
+        self.nodes += 1;
+        if self.nodes > self.node_limit {
+            return None;
+        }
+        let mut next_bound = usize::MAX;
+        for slide in Slide::ALL {
+            if self.path.last() == Some(&slide.opposite()) {
+                continue;
+            }
+            let Some(from) = self.tile_beside_gap(slide) else {
+                continue;
+            };
+            let to = self.gap;
+            let h_next = self.slide(from, h);
+            self.path.push(slide);
+            match self.dfs(g + 1, h_next, bound)? {
+                Ok(()) => return Some(Ok(())),
+                Err(cost) => next_bound = next_bound.min(cost),
+            }
+            self.path.pop();
+            self.slide(to, h_next); // (back where it came from)
+        }
+        Some(Err(next_bound))
+    }
+
+    // as for the puzzle, leaving the fixed squares alone
+    fn tile_beside_gap(&self, slide: Slide) -> Option<usize> {
+        let (i, j) = (self.gap / self.size, self.gap % self.size);
+        let last = self.size - 1;
+        let from = match slide {
+            Slide::Up => (i < last).then(|| self.gap + self.size),
+            Slide::Down => (i > 0).then(|| self.gap - self.size),
+            Slide::Left => (j < last).then(|| self.gap + 1),
+            Slide::Right => (j > 0).then(|| self.gap - 1),
+        }?;
+        (!self.fixed[from]).then_some(from)
+    }
+
+    // slide the tile at [from] into the gap, and return the new estimate
+    // (the one before being [h]). Only the tile's distance changes, and the
+    // conflicts in the lines it leaves and joins
+    fn slide(&mut self, from: usize, h: usize) -> usize {
+        let to = self.gap;
+        let tile = self.tiles[from];
+        let lines = |k: usize| match from / self.size == to / self.size {
+            // moving along a row: it changes columns
+            true => (false, k % self.size),
+            false => (true, k / self.size),
+        };
+        let (rows, old_line) = lines(from);
+        let (_, new_line) = lines(to);
+        let before = self.distance(tile, from)
+            + self.conflicts(rows, old_line)
+            + self.conflicts(rows, new_line)
+            + self.fetching();
+        self.tiles.swap(from, to);
+        self.gap = from;
+        if let Some((lead, at)) = &mut self.lead {
+            if *lead == tile {
+                *at = to;
+            }
+        }
+        let after = self.distance(tile, to)
+            + self.conflicts(rows, old_line)
+            + self.conflicts(rows, new_line)
+            + self.fetching();
+        h + after - before
+    }
+
+    // lower bound on the slides to make: each tile's distance from its
+    // place, and the conflicts in every row and column (plus the gap's
+    // way to the lead tile)
+    fn estimate(&self) -> usize {
+        let distances: usize = (0..self.tiles.len())
+            .map(|k| self.distance(self.tiles[k], k))
+            .sum();
+        let conflicts: usize = (0..self.size)
+            .map(|line| self.conflicts(true, line) + self.conflicts(false, line))
+            .sum();
+        distances + conflicts + self.fetching()
+    }
+
+    // slides the gap needs to get next to the lead tile, if it's not in
+    // place yet (only a guide, as the gap needn't go near it if other
+    // tiles push it along)
+    fn fetching(&self) -> usize {
+        let Some((lead, k)) = self.lead else {
+            return 0;
+        };
+        if self.targets[lead as usize] == Some(k) {
+            return 0;
+        }
+        let (a, b) = (self.gap, k);
+        (a / self.size).abs_diff(b / self.size) + (a % self.size).abs_diff(b % self.size) - 1
+    }
+
+    // rows and columns between [tile] at [k] and where it has to get to
+    // (if anywhere)
+    fn distance(&self, tile: u8, k: usize) -> usize {
+        let Some(goal) = self.targets[tile as usize] else {
+            return 0;
+        };
+        (k / self.size).abs_diff(goal / self.size) + (k % self.size).abs_diff(goal % self.size)
+    }
+
+    // slides on top of the tiles' distances that row (or column) [line]
+    // needs: of the tiles in it that belong in it, all but the most that
+    // are already in order have to step out of it and back
+    fn conflicts(&self, row: bool, line: usize) -> usize {
+        let mut goals = [0; 16]; // where in the line the tiles go
+        let mut count = 0;
+        for n in 0..self.size {
+            let k = match row {
+                true => line * self.size + n,
+                false => n * self.size + line,
+            };
+            let Some(goal) = self.targets[self.tiles[k] as usize] else {
+                continue;
+            };
+            let (goal_line, goal_at) = match row {
+                true => (goal / self.size, goal % self.size),
+                false => (goal % self.size, goal / self.size),
+            };
+            if goal_line == line {
+                goals[count] = goal_at;
+                count += 1;
+            }
+        }
+        // the longest run in order, picking and choosing
+        let mut longest = [0; 16];
+        for a in 0..count {
+            longest[a] = 1
+                + (0..a)
+                    .filter(|&b| goals[b] < goals[a])
+                    .map(|b| longest[b])
+                    .max()
+                    .unwrap_or(0);
+        }
+        let in_order = longest[..count].iter().copied().max().unwrap_or(0);
+        2 * (count - in_order)
+    }
+}
@@ -0,0 +1,74 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use gif::{Encoder, Frame, Repeat};
+
+use crate::gameoflife::GameOfLife;
+
+// frames draw each cell as a square of this many pixels, or fewer for grids
+// that would come out more than [MAX_PIXELS] across
+const CELL_PIXELS: usize = 4;
+const MAX_PIXELS: usize = 1024;
+
+// colors of dead cells, live ones, and live ones of the second color (or
+// dying, in Brian's Brain), indexed by cell state
+const PALETTE: [u8; 9] = [0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00];
+
+/// Run [generations] generations, taking a frame of the grid every [every]th
+/// (and of the first and last), and encode them as an animated GIF that
+/// shows each frame for [delay] and loops forever
+pub fn record(
+    game: &mut GameOfLife,
+    generations: u32,
+    every: u32,
+    delay: Duration,
+) -> Result<Vec<u8>, String> {
+    let (height, width) = game.dim();
+    let scale = (MAX_PIXELS / height.max(width)).clamp(1, CELL_PIXELS);
+    let (img_h, img_w) = (height * scale, width * scale);
+    let (Ok(gif_h), Ok(gif_w)) = (u16::try_from(img_h), u16::try_from(img_w)) else {
+        return Err(format!("a {width}x{height} grid is too big for a GIF"));
+    };
+    let delay = (delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+
+    let mut image = Vec::new();
+    let mut encoder = Encoder::new(&mut image, gif_w, gif_h, &PALETTE).map_err(gif_error)?;
+    encoder.set_repeat(Repeat::Infinite).map_err(gif_error)?;
+    let mut add_frame = |game: &GameOfLife| {
+        let pixels: Vec<u8> = game
+            .cell_states()
+            .rows()
+            .into_iter()
+            .flat_map(|row| {
+                let row: Vec<u8> = row
+                    .iter()
+                    .flat_map(|&state| [state; CELL_PIXELS].into_iter().take(scale))
+                    .collect();
+                vec![row; scale]
+            })
+            .flatten()
+            .collect();
+        let frame = Frame {
+            width: gif_w,
+            height: gif_h,
+            delay,
+            buffer: Cow::Owned(pixels),
+            ..Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(gif_error)
+    };
+
+    add_frame(game)?;
+    for n in 1..=generations {
+        game.tick();
+        if n.is_multiple_of(every) || n == generations {
+            add_frame(game)?;
+        }
+    }
+    drop(encoder);
+    Ok(image)
+}
+
+fn gif_error(e: gif::EncodingError) -> String {
+    format!("GIF encoding failed: {e}")
+}
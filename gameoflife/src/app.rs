@@ -0,0 +1,524 @@
+use std::cell::Cell;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::{cursor, event::Event, style::Stylize, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::achievements;
+use common::clipboard;
+use common::terminal::text_width;
+use common::theme;
+use common::{Game, TerminalGuard};
+
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use crate::animation;
+use crate::cli::Args;
+use crate::config::LifeConfig;
+use crate::cycle::CycleDetector;
+use crate::gameoflife::{GameOfLife, Variant};
+use crate::lifeui::{self, LifeUI, LifeUIAction};
+use crate::render::RenderMode;
+use crate::soup::SoupSearch;
+use crate::viewport::Viewport;
+use crate::{macrocell, rle, textgrid};
+
+// terminal rows taken up by the status bar and help text
+const STATUS_ROWS: usize = 7;
+
+// terminal (row, col) of the grid's top-left corner (inside a border with ratatui)
+#[cfg(not(feature = "ratatui"))]
+const GRID_ORIGIN: (u16, u16) = (0, 0);
+#[cfg(feature = "ratatui")]
+const GRID_ORIGIN: (u16, u16) = (1, 1);
+
+// speed when neither the command line nor the config file sets one
+const DEFAULT_TICK: Duration = Duration::from_millis(100);
+
+/// Game of Life app (simulation + UI state)
+pub struct LifeApp {
+    game: GameOfLife,
+    ui: LifeUI,
+    viewport: Viewport,
+    render_mode: RenderMode,
+    tick: Duration,
+    render_every: u32,
+    cycles: CycleDetector,
+    period: Option<u32>, // period of the cycle the grid has settled into, once detected
+    pub stop_on_cycle: bool,
+    message: String,
+    redraw: Cell<bool>, // whether anything changed since the last frame
+    over: bool,
+}
+
+impl LifeApp {
+    pub fn new(
+        game: GameOfLife,
+        render_mode: RenderMode,
+        tick: Duration,
+        render_every: u32,
+    ) -> Self {
+        let (height, width) = game.dim();
+        let mut app = Self {
+            game,
+            ui: LifeUI::new(height, width),
+            viewport: Viewport::new(height, width),
+            render_mode,
+            tick,
+            render_every,
+            cycles: CycleDetector::default(),
+            period: None,
+            stop_on_cycle: false,
+            message: String::new(),
+            redraw: Cell::new(true),
+            over: false,
+        };
+        app.fit_viewport();
+        app.check_cycle();
+        app
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // resize the viewport to fit the terminal, leaving room for the status bar
+    fn fit_viewport(&mut self) {
+        if let Ok((cols, rows)) = terminal::size() {
+            self.viewport.fit(
+                (rows as usize).saturating_sub(STATUS_ROWS),
+                (cols as usize).saturating_sub(2 * GRID_ORIGIN.1 as usize),
+                self.render_mode,
+            );
+        }
+    }
+
+    // look for a repeat of an earlier generation, until one is found.
+    // returns whether a cycle was just detected
+    fn check_cycle(&mut self) -> bool {
+        if self.period.is_some() {
+            return false;
+        }
+
+        self.period = self
+            .cycles
+            .observe(self.game.state_hash(), self.game.nstep());
+        let Some(period) = self.period else {
+            return false;
+        };
+        info!(
+            "cycle of period {period} detected at generation {}",
+            self.game.nstep()
+        );
+        achievements::unlock("life-cycle");
+        if self.stop_on_cycle {
+            self.ui.paused = true;
+        }
+        true
+    }
+
+    // start looking for cycles afresh (after the grid is edited)
+    fn forget_cycle(&mut self) {
+        self.cycles.reset();
+        self.period = None;
+        self.check_cycle();
+    }
+
+    // set every cell drawn at a terminal position, and move the cursor there
+    fn paint(&mut self, row: u16, col: u16, live: bool) {
+        let (Some(row), Some(col)) = (
+            row.checked_sub(GRID_ORIGIN.0),
+            col.checked_sub(GRID_ORIGIN.1),
+        ) else {
+            return;
+        };
+        let (top, left, height, width) = self.render_mode.cells_at(row, col);
+        for i in top..top + height {
+            for j in left..left + width {
+                if let Some((gi, gj)) = self.viewport.to_global((i, j)) {
+                    self.game.set(gi, gj, live);
+                    self.ui.reset_cursor((gi, gj));
+                }
+            }
+        }
+    }
+
+    // current pattern as RLE, commented with the generation it's from
+    fn pattern_rle(&self) -> String {
+        format!(
+            "#C generation {} of a crossterm-games session\n{}",
+            self.game.nstep(),
+            rle::encode(self.game.pattern(), self.game.rule())
+        )
+    }
+
+    // write current pattern to an RLE file, returning a status message
+    fn export_rle(&self) -> String {
+        let path = format!("life-{}.rle", self.game.nstep());
+        match fs::write(&path, self.pattern_rle()) {
+            Ok(()) => format!("exported pattern to {path}"),
+            Err(e) => format!("export failed: {e}"),
+        }
+    }
+
+    // copy current pattern to the clipboard as RLE, returning a status message
+    fn copy_rle(&self) -> String {
+        match clipboard::copy(&self.pattern_rle()) {
+            Ok(()) => "copied pattern to the clipboard".to_string(),
+            Err(e) => e,
+        }
+    }
+}
+
+// Pretty-print
+impl fmt::Display for LifeApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // only show the cursor while editing
+        let cursor = if self.ui.paused {
+            self.viewport.to_local(self.ui.get_cursor())
+        } else {
+            None
+        };
+        let cell_states = self.game.cell_states();
+        let lines = self
+            .render_mode
+            .render(self.viewport.slice(cell_states.view()), cursor);
+
+        // draw minimap to the right of the grid
+        let minimap = self.viewport.minimap();
+        for (k, line) in lines.iter().enumerate() {
+            write!(f, "{line}")?;
+            if let Some(minimap_line) = minimap.get(k) {
+                write!(f, " {minimap_line}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+
+        // status bar
+        write!(f, "\r\n=== STEP {} ===", self.game.nstep())?;
+        if self.game.variant() == Variant::Immigration {
+            let (first, second) = self.game.population_by_color();
+            write!(
+                f,
+                " {} {first} vs {} {second}",
+                "■".with(theme::current().cell(1)).bold(),
+                "■".with(theme::current().cell(2)).bold()
+            )?;
+        }
+        if self.game.variant() == Variant::Brain {
+            write!(
+                f,
+                " {} {} on, {} {} dying",
+                "■".with(theme::current().cell(1)).bold(),
+                self.game.population(),
+                "■".with(theme::current().cell(2)).bold(),
+                self.game.n_dying()
+            )?;
+        }
+        if let Some(period) = self.period {
+            write!(f, " [cycle of period {period} detected]")?;
+        }
+        if self.ui.paused {
+            write!(f, " [editing]")?;
+        }
+        write!(f, "\r\n{}\r\n", lifeui::help_text())?;
+        write!(f, "{}\r\n", self.message)?;
+
+        Ok(())
+    }
+}
+
+impl Game for LifeApp {
+    fn name(&self) -> &'static str {
+        config_section(self.game.variant())
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        if let Event::Resize(..) = event {
+            self.fit_viewport();
+            self.redraw.set(true);
+            return;
+        }
+
+        let user_action = self.ui.match_event_to_action(event);
+
+        // any user action shows up immediately
+        if !matches!(user_action, LifeUIAction::Wait) {
+            debug!("{user_action:?}");
+            self.redraw.set(true);
+        }
+
+        match user_action {
+            LifeUIAction::Quit => self.over = true,
+            LifeUIAction::Export => self.message = self.export_rle(),
+            LifeUIAction::Copy => self.message = self.copy_rle(),
+            LifeUIAction::Pause => {
+                self.ui.paused = !self.ui.paused;
+                self.viewport.follow(self.ui.get_cursor());
+            }
+            LifeUIAction::CycleRender => {
+                self.render_mode = self.render_mode.next();
+                self.fit_viewport();
+            }
+            LifeUIAction::ToggleMinimap => {
+                self.viewport.show_minimap = !self.viewport.show_minimap;
+                self.fit_viewport();
+            }
+            // arrows move the cursor while editing, and pan the view otherwise
+            LifeUIAction::Move(movedir) if self.ui.paused => {
+                self.ui.move_cursor(movedir);
+                self.viewport.follow(self.ui.get_cursor());
+            }
+            LifeUIAction::Move(movedir) | LifeUIAction::Pan(movedir) => {
+                self.viewport.pan(movedir, self.render_mode)
+            }
+            LifeUIAction::Toggle => {
+                if self.ui.paused {
+                    let (i, j) = self.ui.get_cursor();
+                    self.game.toggle(i, j);
+                    self.forget_cycle();
+                }
+            }
+            LifeUIAction::Paint { row, col, live } => {
+                if self.ui.paused {
+                    self.paint(row, col, live);
+                    self.forget_cycle();
+                }
+            }
+            LifeUIAction::Wait => {}
+        }
+    }
+
+    // only draw every [render_every]th generation
+    fn tick(&mut self) {
+        if self.ui.paused {
+            return;
+        }
+        self.game.tick();
+        if self.game.nstep() == 10_000 {
+            achievements::unlock("life-10k");
+        }
+        let new_cycle = self.check_cycle();
+        if new_cycle || self.game.nstep().is_multiple_of(self.render_every) {
+            self.redraw.set(true);
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.tick)
+    }
+
+    // the viewport shrinks to fit, but the help text needs to stay on its lines
+    fn min_size(&self) -> (u16, u16) {
+        (text_width(lifeui::help_text()), STATUS_ROWS as u16 + 1)
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+}
+
+// read a text grid from --input, or from stdin if something was piped in
+fn read_input(args: &Args) -> Result<Option<String>, String> {
+    let read_stdin = || {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("failed to read stdin: {e}"))?;
+        Ok(Some(text))
+    };
+
+    match &args.input {
+        Some(path) if path.as_os_str() == "-" => read_stdin(),
+        Some(path) => fs::read_to_string(path)
+            .map(Some)
+            .map_err(|e| format!("failed to read {}: {e}", path.display())),
+        None if args.pattern.is_none() && !io::stdin().is_terminal() => {
+            // nothing piped in (e.g. stdin is /dev/null)
+            read_stdin().map(|text| text.filter(|t| !t.trim().is_empty()))
+        }
+        None => Ok(None),
+    }
+}
+
+// build the starting grid from a text grid, a pattern file, or a random seed
+// (also returns the seed, if one was used)
+fn build_game(args: &Args) -> Result<(GameOfLife, Option<u64>), String> {
+    let input = read_input(args)?;
+    let (game, pattern_rule, seed) = match (&input, &args.pattern) {
+        (Some(text), _) => {
+            let grid = textgrid::decode(text)?;
+            let game = GameOfLife::from_pattern(args.height, args.width, grid.view());
+            (game, None, None)
+        }
+        (None, Some(path)) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+            let (pattern, rule) = if text.starts_with("[M2]") {
+                macrocell::decode(&text)?
+            } else {
+                rle::decode(&text)?
+            };
+            let game = GameOfLife::from_pattern(args.height, args.width, pattern.view());
+            (game, rule, None)
+        }
+        (None, None) => {
+            let seed = args.seed.unwrap_or_else(rand::random);
+            let game = GameOfLife::random(args.height, args.width, args.fill, seed);
+            (game, None, Some(seed))
+        }
+    };
+
+    let rule = args
+        .rule
+        .or(pattern_rule)
+        .unwrap_or_else(|| args.variant.default_rule());
+    let game = game
+        .with_variant(args.variant)
+        .with_rule(rule)
+        .with_boundary(args.boundary.unwrap_or(args.variant.default_boundary()));
+    Ok((game, seed))
+}
+
+// run without a UI and print final statistics
+fn run_headless(mut game: GameOfLife, generations: u32) {
+    let start = Instant::now();
+    game.tick_n(generations);
+    let elapsed = start.elapsed().as_secs_f64();
+
+    println!("generations:  {}", game.nstep());
+    println!("rule:         {}", game.rule());
+    println!("population:   {}", game.population());
+    if game.variant() == Variant::Immigration {
+        let (first, second) = game.population_by_color();
+        println!("by color:     {first} white, {second} red");
+    }
+    if game.variant() == Variant::Brain {
+        println!("dying:        {}", game.n_dying());
+    }
+    match game.bounding_box() {
+        Some((top, left, height, width)) => {
+            println!("bounding box: {width}x{height} at ({top}, {left})")
+        }
+        None => println!("bounding box: (empty)"),
+    }
+    println!(
+        "elapsed:      {:.3}s ({:.1} generations/s)",
+        elapsed,
+        generations as f64 / elapsed
+    );
+}
+
+// run without a UI, saving the generations as an animated GIF at [path]
+fn run_gif(mut game: GameOfLife, args: &Args, path: &Path) -> Result<(), String> {
+    let generations = args.generations.unwrap_or_default();
+    let delay = args.tick_ms.map_or(DEFAULT_TICK, Duration::from_millis);
+    let gif = animation::record(&mut game, generations, args.render_every, delay)?;
+    fs::write(path, gif).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    println!("saved {generations} generations to {}", path.display());
+    Ok(())
+}
+
+// run random soups, appending interesting ones to the report file
+fn run_soup_search(args: &Args, n_soups: u64) -> io::Result<()> {
+    let search = SoupSearch {
+        soup_size: args.soup_size,
+        fill_ratio: args.fill,
+        rule: args.rule.unwrap_or_default(),
+        max_generations: args.generations.unwrap_or(10_000),
+        min_period: args.min_period,
+    };
+    let mut report = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&args.report)?;
+
+    let first_seed = args.seed.unwrap_or_else(rand::random);
+    let mut n_found = 0;
+    for k in 0..n_soups {
+        let result = search.run(first_seed.wrapping_add(k));
+        if search.is_interesting(&result) {
+            println!("seed {}: {}", result.seed, result.outcome);
+            write!(report, "{result}")?;
+            n_found += 1;
+        }
+    }
+
+    println!(
+        "searched {n_soups} soups, found {n_found} (written to {})",
+        args.report.display()
+    );
+    Ok(())
+}
+
+// Brian's Brain is played as a game of its own, with its own settings (and stats)
+fn config_section(variant: Variant) -> &'static str {
+    match variant {
+        Variant::Life | Variant::Immigration => "life",
+        Variant::Brain => "brain",
+    }
+}
+
+/// Run the Game of Life as set up on the command line
+pub fn run(mut args: Args) -> Result<(), String> {
+    let section = config_section(args.variant);
+    let config: LifeConfig = common::cli::setup(&args.common, section)?;
+    args.tick_ms = args.tick_ms.or(config.tick_ms);
+    args.render = args.render.or(config.render);
+
+    if let Some(n_soups) = args.soups {
+        return run_soup_search(&args, n_soups).map_err(|e| format!("soup search failed: {e}"));
+    }
+    let (game, seed) = build_game(&args)?;
+
+    if let Some(path) = &args.gif {
+        return run_gif(game, &args, path);
+    }
+    if args.headless {
+        run_headless(game, args.generations.unwrap_or_default());
+        return Ok(());
+    }
+
+    let mut app = LifeApp::new(
+        game,
+        args.render.unwrap_or(RenderMode::HalfBlock),
+        args.tick_ms.map_or(DEFAULT_TICK, Duration::from_millis),
+        args.render_every,
+    );
+    app.ui
+        .bindings
+        .remap(&config.keys, &lifeui::ACTIONS)
+        .map_err(|e| format!("bad [{section}.keys] config: {e}"))?;
+    app.stop_on_cycle = args.stop_on_cycle;
+    if let Some(seed) = seed {
+        info!("{section} seed {seed}");
+        app.message = format!("seed {seed}");
+    }
+
+    let _guard =
+        TerminalGuard::with_mouse().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
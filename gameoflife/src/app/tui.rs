@@ -0,0 +1,73 @@
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use common::theme;
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use crate::gameoflife::Variant;
+use crate::lifeui;
+
+use super::LifeApp;
+
+impl TuiGame for LifeApp {
+    fn draw(&self, frame: &mut Frame) {
+        // only show the cursor while editing
+        let cursor = if self.ui.paused {
+            self.viewport.to_local(self.ui.get_cursor())
+        } else {
+            None
+        };
+        let cell_states = self.game.cell_states();
+        let rows = self
+            .render_mode
+            .render_chars(self.viewport.slice(cell_states.view()), cursor)
+            .iter()
+            .map(|row| row.iter().map(tui::span).collect())
+            .collect();
+        let board = Board::new(rows).title(format!(" step {} ", self.game.nstep()));
+
+        let mut status = Vec::new();
+        if self.game.variant() == Variant::Immigration {
+            let (first, second) = self.game.population_by_color();
+            let square = |state| Span::from("■").fg(theme::current().cell(state)).bold();
+            status.extend([square(1), format!(" {first} vs ").into(), square(2)]);
+            status.push(format!(" {second}").into());
+        }
+        if self.game.variant() == Variant::Brain {
+            let square = |state| Span::from("■").fg(theme::current().cell(state)).bold();
+            let (on, dying) = (self.game.population(), self.game.n_dying());
+            status.extend([square(1), format!(" {on} on, ").into(), square(2)]);
+            status.push(format!(" {dying} dying").into());
+        }
+        if let Some(period) = self.period {
+            status.push(format!(" [cycle of period {period} detected]").into());
+        }
+        if self.ui.paused {
+            status.push(" [editing]".into());
+        }
+        let status_bar = StatusBar::new(lifeui::help_text())
+            .line(status)
+            .line(self.message.as_str());
+
+        let (board_area, status_area) = tui::split(frame.size(), status_bar.height());
+        let (board_cols, _) = board.size();
+        frame.render_widget(board, board_area);
+        frame.render_widget(status_bar, status_area);
+
+        // minimap to the right of the grid
+        let minimap = self.viewport.minimap();
+        if !minimap.is_empty() {
+            let left = board_cols + 1;
+            let area = Rect {
+                x: board_area.x + left.min(board_area.width),
+                width: board_area.width.saturating_sub(left),
+                ..board_area
+            };
+            let lines: Vec<Line> = minimap.into_iter().map(Line::from).collect();
+            frame.render_widget(Paragraph::new(lines), area);
+        }
+    }
+}
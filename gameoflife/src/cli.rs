@@ -0,0 +1,123 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+use crate::gameoflife::{Boundary, Variant};
+use crate::render::RenderMode;
+use crate::rule::Rule;
+
+/// Conway's Game of Life
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on losing, winning, invalid moves and achievements")
+}))]
+pub struct Args {
+    /// Grid width (in cells)
+    #[arg(long, default_value_t = 30)]
+    pub width: usize,
+
+    /// Grid height (in cells)
+    #[arg(long, default_value_t = 40)]
+    pub height: usize,
+
+    /// Ratio of live cells in a random grid
+    #[arg(long, default_value_t = 0.3, value_parser = parse_ratio)]
+    pub fill: f64,
+
+    /// Variant of Life: life, immigration (two competing colors), or brain
+    /// (Brian's Brain, where cells spend a generation dying)
+    #[arg(long, default_value = "life")]
+    pub variant: Variant,
+
+    /// Birth/survival rule, e.g. B36/S23 [default: the pattern's rule, or B3/S23
+    /// (B2/S for Brian's Brain)]
+    #[arg(long)]
+    pub rule: Option<Rule>,
+
+    /// Random seed (picked at random if not given; soup search uses consecutive seeds)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Milliseconds between generations [default: 100]
+    #[arg(long)]
+    pub tick_ms: Option<u64>,
+
+    /// Only draw every Nth generation
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub render_every: u32,
+
+    /// Pause once the grid repeats an earlier generation
+    /// (instead of just reporting the cycle's period)
+    #[arg(long)]
+    pub stop_on_cycle: bool,
+
+    /// What lies beyond the grid edges: dead or torus [default: dead (torus
+    /// for Brian's Brain, whose spaceships would otherwise fly off the grid)]
+    #[arg(long)]
+    pub boundary: Option<Boundary>,
+
+    /// How cells are drawn: emoji, half-block, or braille [default: half-block]
+    #[arg(long)]
+    pub render: Option<RenderMode>,
+
+    /// Start from a pattern file (RLE, or Golly's macrocell format) instead
+    /// of a random grid
+    #[arg(long)]
+    pub pattern: Option<PathBuf>,
+
+    /// Start from a text grid of '.' and '#' rows ('-' reads stdin, which is
+    /// also used when something is piped in)
+    #[arg(long, conflicts_with = "pattern")]
+    pub input: Option<PathBuf>,
+
+    /// Run without drawing anything and print a summary at the end
+    #[arg(long, requires = "generations")]
+    pub headless: bool,
+
+    /// Number of generations to run in headless mode or for a GIF
+    /// (or the most to run each soup for in soup search)
+    #[arg(long)]
+    pub generations: Option<u32>,
+
+    /// Run without drawing anything, saving the grid every --render-every
+    /// generations as a frame of an animated GIF (each shown for --tick-ms)
+    #[arg(long, requires = "generations", conflicts_with = "headless")]
+    pub gif: Option<PathBuf>,
+
+    /// Search this many random soups for oscillators and spaceships
+    #[arg(long, conflicts_with_all = ["headless", "gif", "pattern", "input"])]
+    pub soups: Option<u64>,
+
+    /// Side length of each random soup
+    #[arg(long, default_value_t = 16)]
+    pub soup_size: usize,
+
+    /// Shortest oscillator period worth reporting in soup search
+    #[arg(long, default_value_t = 3)]
+    pub min_period: u32,
+
+    /// File that soup search results are appended to
+    #[arg(long, default_value = "soups.rle")]
+    pub report: PathBuf,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
+
+fn parse_ratio(s: &str) -> Result<f64, String> {
+    match s.parse() {
+        Ok(x) if (0.0..=1.0).contains(&x) => Ok(x),
+        _ => Err(format!("'{s}' is not a ratio between 0 and 1")),
+    }
+}
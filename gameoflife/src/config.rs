@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+use crate::render::RenderMode;
+
+/// Settings from the [life] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LifeConfig {
+    /// milliseconds between generations
+    pub tick_ms: Option<u64>,
+    /// how cells are drawn: emoji, half-block, or braille
+    #[serde(deserialize_with = "common::config::from_str")]
+    pub render: Option<RenderMode>,
+    /// keys for each action, e.g. pause = "space"
+    pub keys: KeyMap,
+}
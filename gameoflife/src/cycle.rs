@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+/// Spots exact repeats in a sequence of grid states
+/// by remembering the hash of every generation seen so far
+#[derive(Default)]
+pub struct CycleDetector {
+    seen: HashMap<u64, u32>, // state hash -> generation it was first seen at
+}
+
+impl CycleDetector {
+    /// Record the state at a generation.
+    /// Returns the period if the same state was seen before.
+    pub fn observe(&mut self, state_hash: u64, nstep: u32) -> Option<u32> {
+        match self.seen.insert(state_hash, nstep) {
+            Some(prev) if prev < nstep => Some(nstep - prev),
+            _ => None,
+        }
+    }
+
+    /// Forget all states seen so far (e.g. after the grid was edited)
+    pub fn reset(&mut self) {
+        self.seen.clear();
+    }
+}
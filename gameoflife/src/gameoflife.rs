@@ -1,15 +1,112 @@
-use std::{fmt, io};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
-use crossterm::queue;
-use crossterm::cursor::MoveTo;
-use crossterm::terminal::{Clear, ClearType};
-use ndarray::{azip, s, Array, Array2};
+use common::{Core, Grid2D, Point, Topology};
+use ndarray::{azip, s, Array, Array2, ArrayView2, Zip};
 use rand::distributions::{Bernoulli, Distribution};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::rule::Rule;
+
+/// What lies beyond the edges of the grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Boundary {
+    /// cells outside the grid are always dead
+    Dead,
+    /// edges wrap around to the opposite side
+    Torus,
+}
+
+impl From<Boundary> for Topology {
+    fn from(boundary: Boundary) -> Self {
+        match boundary {
+            Boundary::Dead => Topology::Bounded,
+            Boundary::Torus => Topology::Torus,
+        }
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dead" => Ok(Boundary::Dead),
+            "torus" => Ok(Boundary::Torus),
+            _ => Err(format!("unknown boundary '{s}' (expected dead or torus)")),
+        }
+    }
+}
+
+/// Which flavor of Life is being played
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Variant {
+    /// classic single-color Life
+    Life,
+    /// two colors of live cells; newborn cells take the majority color of their parents
+    Immigration,
+    /// Brian's Brain: cells that don't survive spend a generation dying, when
+    /// they can't come back to life or count as neighbors (with its usual B2/S
+    /// rule, that's every live cell)
+    Brain,
+}
+
+impl Variant {
+    /// Rule to play by when none is given
+    pub fn default_rule(self) -> Rule {
+        match self {
+            Variant::Life | Variant::Immigration => Rule::conway(),
+            Variant::Brain => Rule::brain(),
+        }
+    }
+
+    /// Boundary to play with when none is given
+    pub fn default_boundary(self) -> Boundary {
+        match self {
+            Variant::Life | Variant::Immigration => Boundary::Dead,
+            Variant::Brain => Boundary::Torus,
+        }
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "life" => Ok(Variant::Life),
+            "immigration" => Ok(Variant::Immigration),
+            "brain" => Ok(Variant::Brain),
+            _ => Err(format!(
+                "unknown variant '{s}' (expected life, immigration or brain)"
+            )),
+        }
+    }
+}
+
+/// A change a player can make to the grid
+#[derive(Clone, Copy)]
+pub enum Edit {
+    /// same as [GameOfLife::toggle]
+    Toggle(Point),
+    Set(Point, bool),
+}
 
 /// Game of Life state (grid and step counter)
 pub struct GameOfLife {
-    grid: Array2<bool>,
+    grid: Grid2D<bool>,
+    colors: Grid2D<bool>, // colors[i,j] == true if (i,j) has the second color (immigration only)
+    dying: Grid2D<bool>,  // dying[i,j] == true if (i,j) is dying (brain only)
+    variant: Variant,
+    rule: Rule,
+    boundary: Boundary,
     nstep: u32,
+    // scratch buffers, reused on every tick
+    neighbors: Grid2D<u32>,         // neighbors[i,j] == # of live neighbors
+    colored: Grid2D<bool>,          // live second-color cells (immigration only)
+    colored_neighbors: Grid2D<u32>, // same as neighbors, but only counting those
+    pad: Array2<u32>,               // padded grid the neighbors are counted over
 }
 
 impl GameOfLife {
@@ -17,119 +114,295 @@ impl GameOfLife {
     // Constructors //
     //////////////////
 
-    /// Make a randomized grid with a specified ratio of active cells
-    pub fn random(height: usize, width: usize, fill_ratio: f64) -> Self {
+    /// Make a randomized grid with a specified ratio of active cells.
+    /// The same seed always gives the same grid.
+    pub fn random(height: usize, width: usize, fill_ratio: f64, seed: u64) -> Self {
         // ncell = number of cells in grid
         let ncell = height * width;
 
         // make bernoulli iterator, capped at [ncell] values
-        let rng = rand::thread_rng();
+        let mut rng = StdRng::seed_from_u64(seed);
         let bernoulli = Bernoulli::new(fill_ratio)
             .expect("bad fill ratio (should be between 0 - 1)")
-            .sample_iter(rng)
+            .sample_iter(&mut rng)
             .take(ncell);
 
         // build grid from iterator
         let grid = Array::from_iter(bernoulli)
             .into_shape([height, width])
             .unwrap();
+
+        // give each cell a coin-flip color, in case the variant uses them
+        let coin_flips = Bernoulli::new(0.5)
+            .unwrap()
+            .sample_iter(&mut rng)
+            .take(ncell);
+        let colors = Array::from_iter(coin_flips)
+            .into_shape([height, width])
+            .unwrap();
+
+        Self {
+            colors: Grid2D::from_array(colors),
+            ..Self::from_grid(grid)
+        }
+    }
+
+    /// Make a grid of at least (height x width) with a pattern in the middle
+    pub fn from_pattern(height: usize, width: usize, pattern: ArrayView2<bool>) -> Self {
+        let (ph, pw) = pattern.dim();
+        let (height, width) = (height.max(ph), width.max(pw));
+        let (top, left) = ((height - ph) / 2, (width - pw) / 2);
+
+        let mut grid = Array2::default((height, width));
+        grid.slice_mut(s![top..top + ph, left..left + pw])
+            .assign(&pattern);
+        Self::from_grid(grid)
+    }
+
+    // wrap a grid with the default variant, rule, and boundary
+    fn from_grid(grid: Array2<bool>) -> Self {
+        let (gridh, gridw) = grid.dim();
         Self {
-            grid: grid,
+            colors: Grid2D::new(gridh, gridw),
+            dying: Grid2D::new(gridh, gridw),
+            neighbors: Grid2D::new(gridh, gridw),
+            colored: Grid2D::new(gridh, gridw),
+            colored_neighbors: Grid2D::new(gridh, gridw),
+            pad: Array2::zeros((gridh + 2, gridw + 2)),
+            grid: Grid2D::from_array(grid),
+            variant: Variant::Life,
+            rule: Rule::default(),
+            boundary: Boundary::Dead,
             nstep: 0,
         }
     }
 
+    /// Play a different variant of Life
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Use a different birth/survival rule
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Use a different boundary behavior
+    pub fn with_boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
     /////////////
     // Publics //
     /////////////
 
     /// Move forward one time-step
     pub fn tick(&mut self) {
+        let rule = self.rule;
+        let topology = self.boundary.into();
+
         // build array where (x,y) -> # of live neighbors
-        let neighbors_grid = self.num_neighbors_grid();
+        self.grid
+            .count_neighbors(topology, &mut self.pad, &mut self.neighbors);
+
+        // newborn cells take the majority color of their neighbors
+        if self.variant == Variant::Immigration {
+            azip!((x in &mut *self.colored, &c in &*self.grid, &k in &*self.colors) *x = c && k);
+            self.colored
+                .count_neighbors(topology, &mut self.pad, &mut self.colored_neighbors);
+
+            azip!((
+                color in &mut *self.colors,
+                &c in &*self.grid,
+                &n in &*self.neighbors,
+                &n2 in &*self.colored_neighbors,
+            ) if !c && rule.transition(false, n) {
+                *color = 2 * n2 > n
+            });
+        }
 
         // update each cell
-        for ((x, y), c) in self.grid.indexed_iter_mut() {
-            let newstate = Self::transition(*c, *neighbors_grid.get((x, y)).unwrap());
-            *c = newstate;
+        if self.variant == Variant::Brain {
+            // cells that don't survive start dying, and dying cells are dead a
+            // generation later (they're not live, so don't count as neighbors)
+            azip!((c in &mut *self.grid, d in &mut *self.dying, &n in &*self.neighbors) {
+                (*c, *d) = match (*c, *d) {
+                    (true, _) => (rule.transition(true, n), !rule.transition(true, n)),
+                    (false, true) => (false, false),
+                    (false, false) => (rule.transition(false, n), false),
+                };
+            });
+        } else {
+            azip!((c in &mut *self.grid, &n in &*self.neighbors) *c = rule.transition(*c, n));
         }
 
         // increment counter
         self.nstep += 1;
     }
 
+    /// Move forward [n] time-steps
+    pub fn tick_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    /// Flip cell (i,j) between live and dead
+    /// (in the immigration variant: dead -> first color -> second color -> dead,
+    /// and in Brian's Brain: dead -> live -> dying -> dead)
+    pub fn toggle(&mut self, i: usize, j: usize) {
+        let (Some(c), Some(color), Some(dying)) = (
+            self.grid.get_mut((i, j)),
+            self.colors.get_mut((i, j)),
+            self.dying.get_mut((i, j)),
+        ) else {
+            return;
+        };
+        match (self.variant, *c, *color, *dying) {
+            (Variant::Immigration, true, false, _) => *color = true,
+            (Variant::Brain, true, _, _) => (*c, *dying) = (false, true),
+            (_, false, _, false) => (*c, *color) = (true, false),
+            (_, false, _, true) => *dying = false,
+            (_, true, _, _) => *c = false,
+        }
+    }
+
+    /// Set cell (i,j) to live or dead
+    pub fn set(&mut self, i: usize, j: usize, live: bool) {
+        if let (Some(c), Some(color), Some(dying)) = (
+            self.grid.get_mut((i, j)),
+            self.colors.get_mut((i, j)),
+            self.dying.get_mut((i, j)),
+        ) {
+            (*c, *color, *dying) = (live, false, false);
+        }
+    }
+
+    /// Read-only view of the grid
+    pub fn view(&self) -> ArrayView2<'_, bool> {
+        self.grid.view()
+    }
+
+    /// Cell states for drawing: 0 for dead cells, and 1 + color for live cells
+    /// (dying cells in Brian's Brain are drawn as the second color)
+    pub fn cell_states(&self) -> Array2<u8> {
+        Zip::from(&*self.grid)
+            .and(&*self.colors)
+            .and(&*self.dying)
+            .map_collect(|&c, &color, &dying| self.cell_state(c, color, dying))
+    }
+
+    /// Grid size as (height, width)
+    pub fn dim(&self) -> (usize, usize) {
+        self.grid.dim()
+    }
+
+    /// Hash of the current grid (equal grids have equal hashes)
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.grid.hash(&mut hasher);
+        match self.variant {
+            Variant::Immigration => (&*self.grid & &*self.colors).hash(&mut hasher),
+            Variant::Brain => self.dying.hash(&mut hasher),
+            Variant::Life => {}
+        }
+        hasher.finish()
+    }
+
+    /// Number of live cells
+    pub fn population(&self) -> usize {
+        self.grid.iter().filter(|&&c| c).count()
+    }
+
+    /// Number of live cells of the (first, second) color
+    pub fn population_by_color(&self) -> (usize, usize) {
+        let second = Zip::from(&*self.grid)
+            .and(&*self.colors)
+            .fold(0, |n, &c, &color| n + (c && color) as usize);
+        (self.population() - second, second)
+    }
+
+    /// Number of dying cells (always 0 outside of Brian's Brain)
+    pub fn n_dying(&self) -> usize {
+        self.dying.iter().filter(|&&d| d).count()
+    }
+
+    /// Bounding box of live cells, as (top, left, height, width)
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        let live = || {
+            self.grid
+                .indexed_iter()
+                .filter(|(_, &c)| c)
+                .map(|(ix, _)| ix)
+        };
+        let top = live().map(|(i, _)| i).min()?;
+        let bottom = live().map(|(i, _)| i).max()?;
+        let left = live().map(|(_, j)| j).min()?;
+        let right = live().map(|(_, j)| j).max()?;
+        Some((top, left, bottom - top + 1, right - left + 1))
+    }
+
+    /// Smallest part of the grid that contains every live cell
+    pub fn pattern(&self) -> ArrayView2<'_, bool> {
+        let (top, left, height, width) = self.bounding_box().unwrap_or((0, 0, 0, 0));
+        self.grid.slice(s![top..top + height, left..left + width])
+    }
+
+    /// Variant of Life being played
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Birth/survival rule in use
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Number of generations run so far
+    pub fn nstep(&self) -> u32 {
+        self.nstep
+    }
+
     //////////////
     // Privates //
     //////////////
 
-    /// cell state transition function
-    fn transition(live_cell: bool, n_neighbors: u32) -> bool {
-        if live_cell {
-            [2, 3].contains(&n_neighbors)
-        } else {
-            n_neighbors == 3
+    // 0 for a dead cell, and 1 + color for a live one (or 2 for a dying one)
+    fn cell_state(&self, live: bool, color: bool, dying: bool) -> u8 {
+        match (live, self.variant == Variant::Immigration && color, dying) {
+            (false, _, false) => 0,
+            (false, _, true) => 2,
+            (true, false, _) => 1,
+            (true, true, _) => 2,
         }
     }
+}
 
-    /// get each cell's number of neighbors
-    fn num_neighbors_grid(&self) -> Array2<u32> {
-        // create copy of grid (as u32) with 1 layer of zero-padding
-        let (gridh, gridw) = self.grid.dim();
-        let mut grid_pad: Array2<u32> = Array2::zeros((gridh + 2, gridw + 2));
-        self.grid
-            .mapv(|x| x as u32)
-            .assign_to(grid_pad.slice_mut(s![1..-1, 1..-1]));
-
-        // num-neighbors array
-        let mut nn: Array2<u32> = Array2::zeros(self.grid.raw_dim());
-
-        // add up/down/left/right neighbors
-        azip!((
-            x in &mut nn,
-            &d  in &grid_pad.slice(s![2..  , 1..-1]), // lower neighbors
-            &u  in &grid_pad.slice(s![ ..-2, 1..-1]), // upper neighbors
-            &r  in &grid_pad.slice(s![1..-1, 2..  ]), // right neighbors
-            &l  in &grid_pad.slice(s![1..-1,  ..-2]), // left neighbors
-        ) *x = d + u + l + r);
-
-        // add diagonal neighbors
-        azip!((
-            x in &mut nn,
-            &dr in &grid_pad.slice(s![2..  , 2..  ]), // lower-right neighbors
-            &ur in &grid_pad.slice(s![ ..-2, 2..  ]), // upper-right neighbors
-            &dl in &grid_pad.slice(s![2..  ,  ..-2]), // lower-left neighbors
-            &ul in &grid_pad.slice(s![ ..-2,  ..-2])  // upper-left neighbors
-        ) *x = *x + dr + dl + ur + ul);
-
-        nn
+impl Core for GameOfLife {
+    type Action = Edit;
+    type Outcome = ();
+    // same as in [GameOfLife::cell_states]
+    type Cell = u8;
+
+    fn size(&self) -> (usize, usize) {
+        self.dim()
+    }
+
+    fn cell(&self, p: Point) -> u8 {
+        self.cell_state(self.grid[p], self.colors[p], self.dying[p])
+    }
+
+    fn act(&mut self, edit: Edit) {
+        match edit {
+            Edit::Toggle(Point(i, j)) => self.toggle(i, j),
+            Edit::Set(Point(i, j), live) => self.set(i, j, live),
+        }
     }
-}
 
-impl fmt::Display for GameOfLife {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // clear screen
-        queue!(io::stdout(), Clear(ClearType::All), MoveTo(0, 0)).expect("display e");
-
-        // make grid lines
-        let print_lines: Vec<Vec<&str>> = self
-            .grid
-            .outer_iter()
-            .map(|row| row.iter().map(|&x| if x { "⬛️" } else { "⬜️" }).collect())
-            .collect();
-
-        // write lines
-        let print_lines_joined = print_lines
-            .iter()
-            .map(|chars| chars.join(""))
-            .collect::<Vec<String>>()
-            .join("\n")
-            + "\n";
-        writeln!(f, "{}", print_lines_joined)?;
-
-        // status bar
-        writeln!(f)?;
-        writeln!(f, "=== STEP {} ===\n", self.nstep)?;
-
-        Ok(())
+    fn tick(&mut self) {
+        GameOfLife::tick(self);
     }
 }
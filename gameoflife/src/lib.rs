@@ -0,0 +1,33 @@
+//! Conway's Game of Life: the game core (the grid, its rules, and pattern
+//! files), plus the crossterm frontend for playing it in a terminal
+
+mod cycle;
+mod gameoflife;
+pub mod macrocell;
+pub mod rle;
+mod rule;
+mod soup;
+pub mod textgrid;
+pub use cycle::CycleDetector;
+pub use gameoflife::{Boundary, Edit, GameOfLife, Variant};
+pub use rule::Rule;
+pub use soup::{SoupOutcome, SoupResult, SoupSearch};
+
+#[cfg(feature = "terminal")]
+mod animation;
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod lifeui;
+#[cfg(feature = "terminal")]
+mod render;
+#[cfg(feature = "terminal")]
+mod viewport;
+#[cfg(feature = "terminal")]
+pub use app::{run, LifeApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
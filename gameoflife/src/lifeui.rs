@@ -0,0 +1,173 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use common::{locale, Bindings, KeyBinding};
+
+/// Help lines under the grid, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("life.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LifeUIAction {
+    Wait,
+    Move(MoveDirection),
+    Pan(MoveDirection),
+    Toggle,
+    Paint { row: u16, col: u16, live: bool },
+    Pause,
+    CycleRender,
+    ToggleMinimap,
+    Export,
+    Copy,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, LifeUIAction); 16] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        LifeUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        LifeUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        LifeUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        LifeUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char('w'), LifeUIAction::Pan(MoveDirection::Up)),
+    (
+        KeyBinding::char('s'),
+        LifeUIAction::Pan(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::char('a'),
+        LifeUIAction::Pan(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::char('d'),
+        LifeUIAction::Pan(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), LifeUIAction::Toggle),
+    (KeyBinding::char('p'), LifeUIAction::Pause),
+    (KeyBinding::char('r'), LifeUIAction::CycleRender),
+    (KeyBinding::char('m'), LifeUIAction::ToggleMinimap),
+    (KeyBinding::char('e'), LifeUIAction::Export),
+    (KeyBinding::char('c'), LifeUIAction::Copy),
+    (KeyBinding::char('q'), LifeUIAction::Quit),
+    (KeyBinding::ctrl('c'), LifeUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, LifeUIAction); 15] = [
+    ("up", LifeUIAction::Move(MoveDirection::Up)),
+    ("down", LifeUIAction::Move(MoveDirection::Down)),
+    ("left", LifeUIAction::Move(MoveDirection::Left)),
+    ("right", LifeUIAction::Move(MoveDirection::Right)),
+    ("pan_up", LifeUIAction::Pan(MoveDirection::Up)),
+    ("pan_down", LifeUIAction::Pan(MoveDirection::Down)),
+    ("pan_left", LifeUIAction::Pan(MoveDirection::Left)),
+    ("pan_right", LifeUIAction::Pan(MoveDirection::Right)),
+    ("toggle", LifeUIAction::Toggle),
+    ("pause", LifeUIAction::Pause),
+    ("render", LifeUIAction::CycleRender),
+    ("minimap", LifeUIAction::ToggleMinimap),
+    ("export", LifeUIAction::Export),
+    ("copy", LifeUIAction::Copy),
+    ("quit", LifeUIAction::Quit),
+];
+
+pub struct LifeUI {
+    gridh: usize,
+    gridw: usize,
+    cursor: (usize, usize),
+    pub paused: bool,
+    pub bindings: Bindings<LifeUIAction>,
+}
+
+impl LifeUI {
+    /////////////
+    // Statics //
+    /////////////
+
+    // left button draws, right button erases
+    fn match_mouse_to_action(mouse_event: MouseEvent) -> LifeUIAction {
+        let live =
+            match mouse_event.kind {
+                MouseEventKind::Down(MouseButton::Left)
+                | MouseEventKind::Drag(MouseButton::Left) => true,
+                MouseEventKind::Down(MouseButton::Right)
+                | MouseEventKind::Drag(MouseButton::Right) => false,
+                _ => return LifeUIAction::Wait,
+            };
+        LifeUIAction::Paint {
+            row: mouse_event.row,
+            col: mouse_event.column,
+            live,
+        }
+    }
+
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            gridh: height,
+            gridw: width,
+            cursor: (height / 2, width / 2),
+            paused: false,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> LifeUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(LifeUIAction::Wait)
+    }
+
+    pub fn match_event_to_action(&self, event: Event) -> LifeUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            Event::Mouse(mouse_event) => Self::match_mouse_to_action(mouse_event),
+            _ => LifeUIAction::Wait,
+        }
+    }
+
+    // move cursor one cell, stopping at the grid edges
+    pub fn move_cursor(&mut self, dir: MoveDirection) {
+        let (i, j) = self.cursor;
+        self.cursor = match dir {
+            MoveDirection::Up => (i.saturating_sub(1), j),
+            MoveDirection::Down => ((i + 1).min(self.gridh - 1), j),
+            MoveDirection::Left => (i, j.saturating_sub(1)),
+            MoveDirection::Right => (i, (j + 1).min(self.gridw - 1)),
+        };
+    }
+
+    pub fn get_cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    pub fn reset_cursor(&mut self, (i, j): (usize, usize)) {
+        self.cursor = (i.min(self.gridh - 1), j.min(self.gridw - 1));
+    }
+}
@@ -0,0 +1,195 @@
+use ndarray::Array2;
+
+use crate::rule::Rule;
+
+// the most cells a decoded pattern may span, since the grid it's loaded into
+// keeps every cell (macrocell files can describe patterns far bigger)
+pub(crate) const MAX_CELLS: u64 = 1 << 26;
+
+// the biggest quadtree node a pattern can be built from, 2^63 cells across
+const MAX_LEVEL: u32 = 63;
+
+// a node of the quadtree, 2^level cells across
+enum Node {
+    // 8x8 cells listed out (or 2x2, in files for rules with more states),
+    // as the live cells' (row, col)
+    Leaf { level: u32, cells: Vec<(u64, u64)> },
+    // four nodes a level down (northwest, northeast, southwest, southeast),
+    // numbered from 1, with 0 for an empty one
+    Quad { level: u32, children: [usize; 4] },
+}
+
+impl Node {
+    fn level(&self) -> u32 {
+        match self {
+            Node::Leaf { level, .. } | Node::Quad { level, .. } => *level,
+        }
+    }
+}
+
+// the live cells of a node, as the rows and columns (first and last) they
+// span from its top left corner; None for a node with none
+type Bounds = Option<(u64, u64, u64, u64)>;
+
+/// Decode a pattern in Golly's macrocell format (a quadtree, where repeated
+/// parts are written once), along with its rule (if the file has one)
+pub fn decode(mc: &str) -> Result<(Array2<bool>, Option<Rule>), String> {
+    let mut lines = mc.lines().map(str::trim);
+    if !lines.next().is_some_and(|line| line.starts_with("[M2]")) {
+        return Err("not a macrocell file (the first line should be [M2])".into());
+    }
+
+    let mut rule = None;
+    let mut nodes: Vec<Node> = Vec::new();
+    for line in lines {
+        if let Some(value) = line.strip_prefix("#R") {
+            rule = Some(value.trim().parse()?);
+        } else if line.is_empty() || line.starts_with('#') {
+            continue;
+        } else if line.starts_with(['.', '*', '$']) {
+            nodes.push(leaf(line)?);
+        } else {
+            nodes.push(quad(line, &nodes)?);
+        }
+    }
+
+    let bounds = bounds(&nodes);
+    let Some((top, left, bottom, right)) = bounds.last().copied().flatten() else {
+        return Ok((Array2::default((0, 0)), rule));
+    };
+    let (height, width) = (bottom - top + 1, right - left + 1);
+    let cells = height.checked_mul(width);
+    if cells.is_none_or(|cells| cells > MAX_CELLS) {
+        return Err(format!(
+            "pattern is {width}x{height} cells, too big to load (at most {MAX_CELLS} cells)"
+        ));
+    }
+    let mut pattern = Array2::default((height as usize, width as usize));
+    place(&nodes, &bounds, nodes.len(), (0, 0), &mut |(i, j)| {
+        pattern[((i - top) as usize, (j - left) as usize)] = true;
+    });
+    Ok((pattern, rule))
+}
+
+// an 8x8 leaf: '.' a dead cell, '*' a live one and '$' the end of a row
+// (with dead cells left off the ends)
+fn leaf(line: &str) -> Result<Node, String> {
+    let mut cells = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for c in line.chars() {
+        match c {
+            // (every row ends in '$', the last one too, so only cells can
+            // fall outside the leaf)
+            '.' | '*' if i >= 8 || j >= 8 => {
+                return Err(format!("leaf '{line}' is bigger than 8x8"));
+            }
+            '.' => j += 1,
+            '*' => {
+                cells.push((i, j));
+                j += 1;
+            }
+            '$' => (i, j) = (i + 1, 0),
+            _ => return Err(format!("unexpected character '{c}' in leaf '{line}'")),
+        }
+    }
+    Ok(Node::Leaf { level: 3, cells })
+}
+
+// "<level> <nw> <ne> <sw> <se>", a node made of four earlier ones (or, at
+// level 1, of four cells' states)
+fn quad(line: &str, nodes: &[Node]) -> Result<Node, String> {
+    let numbers = line
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| format!("bad node '{line}'"))?;
+    let &[level, nw, ne, sw, se] = numbers.as_slice() else {
+        return Err(format!("bad node '{line}'"));
+    };
+    let level = level as u32;
+    if level == 1 {
+        let cells = [(0, 0), (0, 1), (1, 0), (1, 1)];
+        let cells = [nw, ne, sw, se]
+            .into_iter()
+            .zip(cells)
+            .filter_map(|(state, cell)| (state != 0).then_some(cell))
+            .collect();
+        return Ok(Node::Leaf { level, cells });
+    }
+    if !(2..=MAX_LEVEL).contains(&level) {
+        return Err(format!("node '{line}' has a level out of range"));
+    }
+    for child in [nw, ne, sw, se] {
+        if child == 0 {
+            continue;
+        }
+        match nodes.get(child - 1) {
+            Some(node) if node.level() == level - 1 => {}
+            Some(_) => return Err(format!("node '{line}' has a child of the wrong size")),
+            None => return Err(format!("node '{line}' refers to a node not yet defined")),
+        }
+    }
+    Ok(Node::Quad {
+        level,
+        children: [nw, ne, sw, se],
+    })
+}
+
+// top left corner of each of a node's children, from its own
+fn offsets(level: u32) -> [(u64, u64); 4] {
+    let half = 1 << (level - 1);
+    [(0, 0), (0, half), (half, 0), (half, half)]
+}
+
+// the bounds of every node, worked out from its children's (which always
+// come first)
+fn bounds(nodes: &[Node]) -> Vec<Bounds> {
+    let mut bounds: Vec<Bounds> = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let b = match node {
+            Node::Leaf { cells, .. } => cells.iter().map(|&(i, j)| (i, j, i, j)).reduce(union),
+            Node::Quad { level, children } => children
+                .iter()
+                .zip(offsets(*level))
+                .filter(|&(&child, _)| child != 0)
+                .filter_map(|(&child, (di, dj))| {
+                    bounds[child - 1].map(|(t, l, b, r)| (t + di, l + dj, b + di, r + dj))
+                })
+                .reduce(union),
+        };
+        bounds.push(b);
+    }
+    bounds
+}
+
+fn union(
+    (t0, l0, b0, r0): (u64, u64, u64, u64),
+    (t1, l1, b1, r1): (u64, u64, u64, u64),
+) -> (u64, u64, u64, u64) {
+    (t0.min(t1), l0.min(l1), b0.max(b1), r0.max(r1))
+}
+
+// call [set] with every live cell of node [n], placed from [origin]
+fn place(
+    nodes: &[Node],
+    bounds: &[Bounds],
+    n: usize,
+    (i0, j0): (u64, u64),
+    set: &mut impl FnMut((u64, u64)),
+) {
+    if n == 0 || bounds[n - 1].is_none() {
+        return;
+    }
+    match &nodes[n - 1] {
+        Node::Leaf { cells, .. } => {
+            for &(i, j) in cells {
+                set((i0 + i, j0 + j));
+            }
+        }
+        Node::Quad { level, children } => {
+            for (&child, (di, dj)) in children.iter().zip(offsets(*level)) {
+                place(nodes, bounds, child, (i0 + di, j0 + dj), set);
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use std::str::FromStr;
+
+use common::terminal::padding;
+use common::theme::{self, Role};
+use common::{blocks, style, Point};
+use crossterm::style::{StyledContent, Stylize};
+use ndarray::ArrayView2;
+
+// cells are drawn from their state: 0 is dead, and 1, 2, ... are live cells of each color
+const LIVE_STRS: [&str; 2] = ["⬛️", "🟥"];
+const DEAD_STR: &str = "⬜️";
+const CURSOR_LIVE_STR: &str = "🟪";
+const CURSOR_DEAD_STR: &str = "🟦";
+// terminal columns each emoji cell takes up (narrower glyphs are padded out)
+const EMOJI_COLS: usize = 2;
+
+// number of colors live cells can have
+const N_LIVE_COLORS: usize = 2;
+
+// without color, half-blocks are drawn by shape: indexed by (upper live, lower live)
+const BLOCK_SHAPES: [[char; 2]; 2] = [[' ', '▄'], ['▀', '█']];
+
+// braille dots are numbered column-wise, with the bottom row added later:
+// 1 4
+// 2 5
+// 3 6
+// 7 8
+const BRAILLE_BLANK: u32 = 0x2800;
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// How grid cells are packed into terminal characters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderMode {
+    /// one emoji square per cell
+    Emoji,
+    /// 2 cells per half-block character (one above the other)
+    HalfBlock,
+    /// 2x4 cells per braille character
+    Braille,
+}
+
+impl FromStr for RenderMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "emoji" => Ok(RenderMode::Emoji),
+            "half-block" => Ok(RenderMode::HalfBlock),
+            "braille" => Ok(RenderMode::Braille),
+            _ => Err(format!(
+                "unknown render mode '{s}' (expected emoji, half-block, or braille)"
+            )),
+        }
+    }
+}
+
+impl RenderMode {
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Number of grid cells drawn by one character, as (rows, cols)
+    pub fn block_size(&self) -> (usize, usize) {
+        match self {
+            RenderMode::Emoji => (1, 1),
+            RenderMode::HalfBlock => (2, 1),
+            RenderMode::Braille => (4, 2),
+        }
+    }
+
+    /// Number of terminal columns taken up by one character
+    pub fn char_width(&self) -> usize {
+        match self {
+            RenderMode::Emoji => EMOJI_COLS,
+            RenderMode::HalfBlock | RenderMode::Braille => 1,
+        }
+    }
+
+    /// Grid cells drawn at a terminal position (relative to the top-left of the drawing),
+    /// as (top, left, height, width)
+    pub fn cells_at(&self, row: u16, col: u16) -> (usize, usize, usize, usize) {
+        let (blockh, blockw) = self.block_size();
+        let block_col = col as usize / self.char_width();
+        (row as usize * blockh, block_col * blockw, blockh, blockw)
+    }
+
+    /// Next render mode (used to cycle through modes at runtime)
+    pub fn next(&self) -> Self {
+        match self {
+            RenderMode::Emoji => RenderMode::HalfBlock,
+            RenderMode::HalfBlock => RenderMode::Braille,
+            RenderMode::Braille => RenderMode::Emoji,
+        }
+    }
+
+    /// Render grid of cell states as one string per terminal row.
+    /// The cell under `cursor` (if any) is highlighted.
+    pub fn render(&self, grid: ArrayView2<u8>, cursor: Option<(usize, usize)>) -> Vec<String> {
+        self.render_chars(grid, cursor)
+            .into_iter()
+            .map(|row| row.iter().map(ToString::to_string).collect())
+            .collect()
+    }
+
+    /// Same as [RenderMode::render], but with each (styled) character separate
+    pub fn render_chars(
+        &self,
+        grid: ArrayView2<u8>,
+        cursor: Option<(usize, usize)>,
+    ) -> Vec<Vec<StyledContent<String>>> {
+        match self {
+            RenderMode::Emoji => Self::render_emoji(grid, cursor),
+            RenderMode::HalfBlock if !style::color_enabled() => {
+                Self::render_half_block_shapes(grid, cursor)
+            }
+            RenderMode::HalfBlock => Self::render_half_block(grid, cursor),
+            RenderMode::Braille => Self::render_braille(grid, cursor),
+        }
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn render_emoji(
+        grid: ArrayView2<u8>,
+        cursor: Option<(usize, usize)>,
+    ) -> Vec<Vec<StyledContent<String>>> {
+        grid.outer_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, &state)| match (cursor == Some((i, j)), state) {
+                        (false, 0) => DEAD_STR,
+                        (false, state) => LIVE_STRS[state as usize - 1],
+                        (true, 0) => CURSOR_DEAD_STR,
+                        (true, _) => CURSOR_LIVE_STR,
+                    })
+                    .map(|emoji| format!("{emoji}{}", padding(emoji, EMOJI_COLS)).stylize())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn render_half_block(
+        grid: ArrayView2<u8>,
+        cursor: Option<(usize, usize)>,
+    ) -> Vec<Vec<StyledContent<String>>> {
+        let (gridh, gridw) = grid.dim();
+        let theme = theme::current();
+        blocks::half_blocks(gridh, gridw, |Point(i, j)| {
+            match (cursor == Some((i, j)), grid[(i, j)]) {
+                (false, state) => theme.cell(state),
+                (true, 0) => theme.color(Role::Marker),
+                (true, _) => theme.color(Role::Accent),
+            }
+        })
+    }
+
+    // same as render_half_block, but with the shape of each character showing
+    // which cells are live, and the cursor's character in reverse video
+    fn render_half_block_shapes(
+        grid: ArrayView2<u8>,
+        cursor: Option<(usize, usize)>,
+    ) -> Vec<Vec<StyledContent<String>>> {
+        let (gridh, gridw) = grid.dim();
+        let live = |i: usize, j: usize| grid.get((i, j)).is_some_and(|&state| state > 0);
+
+        (0..gridh)
+            .step_by(2)
+            .map(|i| {
+                (0..gridw)
+                    .map(|j| {
+                        let chr = BLOCK_SHAPES[live(i, j) as usize][live(i + 1, j) as usize];
+                        match cursor {
+                            Some((ci, cj)) if ci / 2 == i / 2 && cj == j => {
+                                chr.to_string().reverse()
+                            }
+                            _ => chr.to_string().stylize(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn render_braille(
+        grid: ArrayView2<u8>,
+        cursor: Option<(usize, usize)>,
+    ) -> Vec<Vec<StyledContent<String>>> {
+        let (gridh, gridw) = grid.dim();
+        let (blockh, blockw) = RenderMode::Braille.block_size();
+
+        let mut lines = Vec::with_capacity(gridh.div_ceil(blockh));
+        for bi in 0..gridh.div_ceil(blockh) {
+            let mut line = Vec::with_capacity(gridw.div_ceil(blockw));
+            for bj in 0..gridw.div_ceil(blockw) {
+                // collect the dots of every live cell in this block,
+                // and count how many live cells there are of each color
+                let mut bits = 0;
+                let mut color_counts = [0; N_LIVE_COLORS];
+                for (di, dots_row) in BRAILLE_DOTS.iter().enumerate() {
+                    for (dj, &dot) in dots_row.iter().enumerate() {
+                        match grid.get((bi * blockh + di, bj * blockw + dj)) {
+                            None | Some(0) => {}
+                            Some(&state) => {
+                                bits |= dot;
+                                color_counts[state as usize - 1] += 1;
+                            }
+                        }
+                    }
+                }
+
+                // the cursor inverts its own dot and highlights the whole character
+                let cursor_dot = cursor.and_then(|(ci, cj)| {
+                    if ci / blockh == bi && cj / blockw == bj {
+                        Some(BRAILLE_DOTS[ci % blockh][cj % blockw])
+                    } else {
+                        None
+                    }
+                });
+
+                let chr = char::from_u32(BRAILLE_BLANK + (bits ^ cursor_dot.unwrap_or(0)))
+                    .expect("invalid braille codepoint");
+                // a character can only have one color, so go with the most common one
+                // (uncolored if that's the first color)
+                let majority = (0..color_counts.len())
+                    .rev()
+                    .max_by_key(|&k| color_counts[k])
+                    .unwrap();
+                let chr = chr.to_string();
+                line.push(match (cursor_dot, majority) {
+                    (Some(_), _) => chr.with(theme::color(Role::Accent)).bold(),
+                    (None, k) => chr.with(theme::current().cell(k as u8 + 1)),
+                });
+            }
+            lines.push(line);
+        }
+
+        lines
+    }
+}
@@ -0,0 +1,144 @@
+use ndarray::{Array2, ArrayView2};
+
+use crate::macrocell::MAX_CELLS;
+use crate::rule::Rule;
+
+// RLE lines shouldn't exceed 70 characters
+const MAX_LINE_LEN: usize = 70;
+
+/// Encode a pattern in run-length-encoded format
+/// (the format used by Golly and the LifeWiki)
+pub fn encode(pattern: ArrayView2<bool>, rule: Rule) -> String {
+    let (height, width) = pattern.dim();
+
+    // build run tokens like "3o", "b", "2$"
+    let mut tokens: Vec<String> = Vec::new();
+    let mut pending_rows = 0;
+    for row in pattern.outer_iter() {
+        let cells: Vec<bool> = row.iter().copied().collect();
+        let row_len = cells.iter().rposition(|&c| c).map_or(0, |n| n + 1);
+
+        // finish previous row(s) only once we know this row has cells
+        if row_len > 0 && pending_rows > 0 {
+            tokens.push(run_token(pending_rows, '$'));
+            pending_rows = 0;
+        }
+        for run in cells[..row_len].chunk_by(|a, b| a == b) {
+            tokens.push(run_token(run.len(), if run[0] { 'o' } else { 'b' }));
+        }
+        pending_rows += 1;
+    }
+    tokens.push("!".into());
+
+    // header
+    let mut rle = format!("x = {width}, y = {height}, rule = {rule}\n");
+
+    // wrap tokens without splitting any of them across lines
+    let mut line = String::new();
+    for token in tokens {
+        if line.len() + token.len() > MAX_LINE_LEN {
+            rle += &line;
+            rle.push('\n');
+            line.clear();
+        }
+        line += &token;
+    }
+    rle += &line;
+    rle.push('\n');
+
+    rle
+}
+
+/// Decode an RLE pattern, along with its rule (if the header has one)
+pub fn decode(rle: &str) -> Result<(Array2<bool>, Option<Rule>), String> {
+    let mut lines = rle
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    // header: "x = <width>, y = <height>[, rule = <rule>]"
+    let header = lines.next().ok_or("RLE file is empty")?;
+    let (mut width, mut height, mut rule) = (0, 0, None);
+    for field in header.split(',') {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or(format!("bad RLE header '{header}'"))?;
+        let value = value.trim();
+        match key.trim() {
+            "x" => width = value.parse().map_err(|_| format!("bad width '{value}'"))?,
+            "y" => height = value.parse().map_err(|_| format!("bad height '{value}'"))?,
+            "rule" => rule = Some(value.parse()?),
+            _ => {}
+        }
+    }
+    check_size(height, width)?;
+
+    // body: runs of cells, ending in '!'
+    let mut live_cells = Vec::new();
+    let (mut i, mut j): (usize, usize) = (0, 0);
+    let mut right = 0; // furthest column any live cell so far reaches
+    let mut count = String::new();
+    'body: for line in lines {
+        for c in line.chars() {
+            let n: usize = if count.is_empty() {
+                1
+            } else {
+                count
+                    .parse()
+                    .map_err(|_| format!("bad run count '{count}'"))?
+            };
+            match c {
+                '0'..='9' => {
+                    count.push(c);
+                    continue;
+                }
+                '!' => break 'body,
+                '$' => (i, j) = (i.saturating_add(n), 0),
+                'b' | '.' => j = j.saturating_add(n),
+                c if c.is_ascii_alphabetic() => {
+                    // (before listing out a run that'd make the pattern too big)
+                    right = right.max(j.saturating_add(n));
+                    check_size(i.saturating_add(1), right)?;
+                    live_cells.extend((j..j + n).map(|jj| (i, jj)));
+                    j += n;
+                }
+                c if c.is_whitespace() => {}
+                _ => return Err(format!("unexpected character '{c}' in RLE")),
+            }
+            count.clear();
+        }
+    }
+
+    // grow the grid if the header was too small
+    for &(i, j) in &live_cells {
+        height = height.max(i + 1);
+        width = width.max(j + 1);
+    }
+    check_size(height, width)?;
+    let mut pattern = Array2::default((height, width));
+    for ix in live_cells {
+        pattern[ix] = true;
+    }
+
+    Ok((pattern, rule))
+}
+
+// error if a [height] x [width] pattern is too big to load
+fn check_size(height: usize, width: usize) -> Result<(), String> {
+    let cells = (height as u64).checked_mul(width as u64);
+    if cells.is_none_or(|cells| cells > MAX_CELLS) {
+        return Err(format!(
+            "pattern is {width}x{height} cells, too big to load (at most {MAX_CELLS} cells)"
+        ));
+    }
+    Ok(())
+}
+
+// "<count><tag>", with the count left out for runs of one
+fn run_token(count: usize, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{count}{tag}")
+    }
+}
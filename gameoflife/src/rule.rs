@@ -0,0 +1,104 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Life-like birth/survival rule (e.g. B3/S23 for Conway's Life)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule {
+    birth: [bool; 9],   // birth[n] == true if a dead cell with n neighbors comes alive
+    survive: [bool; 9], // survive[n] == true if a live cell with n neighbors stays alive
+}
+
+impl Rule {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Conway's original rule, B3/S23
+    pub fn conway() -> Self {
+        "B3/S23".parse().unwrap()
+    }
+
+    /// Brian's Brain, B2/S (every live cell starts dying a generation after it's born)
+    pub fn brain() -> Self {
+        "B2/S".parse().unwrap()
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Next state of a cell given its state and number of live neighbors
+    pub fn transition(&self, live_cell: bool, n_neighbors: u32) -> bool {
+        let table = if live_cell {
+            &self.survive
+        } else {
+            &self.birth
+        };
+        table[n_neighbors as usize]
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // parse a list of neighbor counts like "23"
+    fn parse_counts(digits: &str) -> Result<[bool; 9], String> {
+        let mut counts = [false; 9];
+        for c in digits.chars() {
+            match c.to_digit(10) {
+                Some(n) if n <= 8 => counts[n as usize] = true,
+                _ => return Err(format!("invalid neighbor count '{c}'")),
+            }
+        }
+        Ok(counts)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+// Accepts "B3/S23", "S23/B3", and the older "23/3" (survival/birth) notation
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, second) = s
+            .split_once('/')
+            .ok_or(format!("rule '{s}' should look like B3/S23"))?;
+
+        let mut birth = None;
+        let mut survive = None;
+        for part in [first, second] {
+            match part.chars().next().map(|c| c.to_ascii_uppercase()) {
+                Some('B') => birth = Some(Self::parse_counts(&part[1..])?),
+                Some('S') => survive = Some(Self::parse_counts(&part[1..])?),
+                _ => {}
+            }
+        }
+
+        // no B/S prefixes: survival/birth notation
+        match (birth, survive) {
+            (Some(birth), Some(survive)) => Ok(Self { birth, survive }),
+            (None, None) => Ok(Self {
+                birth: Self::parse_counts(second)?,
+                survive: Self::parse_counts(first)?,
+            }),
+            _ => Err(format!("rule '{s}' needs both a B and an S part")),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = |table: &[bool; 9]| -> String {
+            (0..9)
+                .filter(|&n| table[n])
+                .map(|n| n.to_string())
+                .collect()
+        };
+        write!(f, "B{}/S{}", counts(&self.birth), counts(&self.survive))
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+
+use crate::cycle::CycleDetector;
+use crate::gameoflife::{Boundary, GameOfLife};
+use crate::rle;
+use crate::rule::Rule;
+
+// dead space around each soup, so that objects have room to escape
+const MARGIN: usize = 48;
+
+/// How a soup ended up
+pub enum SoupOutcome {
+    /// settled into a cycle (period 1 for still lifes)
+    Stable { period: u32 },
+    /// live cells reached the edge of the grid (most likely a spaceship)
+    Escaped,
+    /// still changing after the generation limit
+    Unsettled,
+}
+
+/// Result of running one random soup
+pub struct SoupResult {
+    pub seed: u64,
+    pub outcome: SoupOutcome,
+    pub nstep: u32,
+    pub population: usize,
+    pub soup_rle: String,
+}
+
+/// Settings for a search over random soups
+pub struct SoupSearch {
+    pub soup_size: usize,
+    pub fill_ratio: f64,
+    pub rule: Rule,
+    pub max_generations: u32,
+    pub min_period: u32,
+}
+
+impl SoupSearch {
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Run the soup made from [seed] until it settles, escapes, or times out
+    pub fn run(&self, seed: u64) -> SoupResult {
+        let soup = GameOfLife::random(self.soup_size, self.soup_size, self.fill_ratio, seed);
+        let grid_size = self.soup_size + 2 * MARGIN;
+        let mut game = GameOfLife::from_pattern(grid_size, grid_size, soup.view())
+            .with_rule(self.rule)
+            .with_boundary(Boundary::Dead);
+
+        let mut cycles = CycleDetector::default();
+        let outcome = loop {
+            if let Some(period) = cycles.observe(game.state_hash(), game.nstep()) {
+                break SoupOutcome::Stable { period };
+            }
+            if Self::touches_edge(&game) {
+                break SoupOutcome::Escaped;
+            }
+            if game.nstep() >= self.max_generations {
+                break SoupOutcome::Unsettled;
+            }
+            game.tick();
+        };
+
+        SoupResult {
+            seed,
+            outcome,
+            nstep: game.nstep(),
+            population: game.population(),
+            soup_rle: rle::encode(soup.view(), self.rule),
+        }
+    }
+
+    /// Whether a result is worth reporting
+    pub fn is_interesting(&self, result: &SoupResult) -> bool {
+        match result.outcome {
+            SoupOutcome::Stable { period } => period >= self.min_period,
+            SoupOutcome::Escaped | SoupOutcome::Unsettled => true,
+        }
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn touches_edge(game: &GameOfLife) -> bool {
+        let (gridh, gridw) = game.dim();
+        match game.bounding_box() {
+            Some((top, left, height, width)) => {
+                top == 0 || left == 0 || top + height == gridh || left + width == gridw
+            }
+            None => false,
+        }
+    }
+}
+
+impl fmt::Display for SoupOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SoupOutcome::Stable { period: 1 } => write!(f, "still life"),
+            SoupOutcome::Stable { period } => write!(f, "oscillator with period {period}"),
+            SoupOutcome::Escaped => write!(f, "escaping object (spaceship?)"),
+            SoupOutcome::Unsettled => write!(f, "still unsettled"),
+        }
+    }
+}
+
+// Report entry: summary line, followed by the soup as RLE
+impl fmt::Display for SoupResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "#C seed {}: {} at generation {} (population {})",
+            self.seed, self.outcome, self.nstep, self.population
+        )?;
+        write!(f, "{}", self.soup_rle)
+    }
+}
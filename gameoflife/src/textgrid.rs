@@ -0,0 +1,35 @@
+use ndarray::Array2;
+
+/// Decode a plain-text grid, one row per line.
+/// `#` (or `O`) is a live cell and `.` is a dead cell;
+/// short rows are padded with dead cells, and lines starting with `!` are comments.
+pub fn decode(text: &str) -> Result<Array2<bool>, String> {
+    let rows = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.starts_with('!'))
+        .map(|line| {
+            line.chars()
+                .map(|c| match c {
+                    '#' | 'O' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(format!("unexpected character '{c}' in grid")),
+                })
+                .collect::<Result<Vec<bool>, String>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let height = rows.len();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if height == 0 || width == 0 {
+        return Err("input grid is empty".into());
+    }
+
+    let mut grid = Array2::default((height, width));
+    for (i, row) in rows.iter().enumerate() {
+        for (j, &live) in row.iter().enumerate() {
+            grid[(i, j)] = live;
+        }
+    }
+    Ok(grid)
+}
@@ -0,0 +1,142 @@
+use ndarray::{s, ArrayView2};
+
+use crate::lifeui::MoveDirection;
+use crate::render::RenderMode;
+
+// size of the minimap (in terminal characters)
+const MINIMAP_H: usize = 8;
+const MINIMAP_W: usize = 16;
+const MINIMAP_VISIBLE: char = '█';
+const MINIMAP_HIDDEN: char = '░';
+
+/// Window onto the part of the grid that fits in the terminal
+pub struct Viewport {
+    gridh: usize,
+    gridw: usize,
+    top: usize,
+    left: usize,
+    height: usize,
+    width: usize,
+    pub show_minimap: bool,
+}
+
+impl Viewport {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Start with the whole grid in view
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            gridh: height,
+            gridw: width,
+            top: 0,
+            left: 0,
+            height,
+            width,
+            show_minimap: true,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Resize viewport to fit in a (rows x cols) area of the terminal
+    pub fn fit(&mut self, rows: usize, cols: usize, mode: RenderMode) {
+        let (blockh, blockw) = mode.block_size();
+        let fit_cols = |cols: usize| self.gridw.min(cols / mode.char_width() * blockw);
+        self.height = self.gridh.min(rows * blockh).max(1);
+        self.width = fit_cols(cols).max(1);
+
+        // leave room for the minimap if the grid doesn't fit
+        if self.show_minimap && !self.shows_whole_grid() {
+            self.width = fit_cols(cols.saturating_sub(MINIMAP_W + 1)).max(1);
+        }
+
+        self.clamp();
+    }
+
+    /// Scroll one character's worth of cells in the given direction
+    pub fn pan(&mut self, dir: MoveDirection, mode: RenderMode) {
+        let (blockh, blockw) = mode.block_size();
+        match dir {
+            MoveDirection::Up => self.top = self.top.saturating_sub(blockh),
+            MoveDirection::Down => self.top += blockh,
+            MoveDirection::Left => self.left = self.left.saturating_sub(blockw),
+            MoveDirection::Right => self.left += blockw,
+        }
+        self.clamp();
+    }
+
+    /// Scroll just enough to bring grid cell (i,j) into view
+    pub fn follow(&mut self, (i, j): (usize, usize)) {
+        self.top = self.top.min(i).max((i + 1).saturating_sub(self.height));
+        self.left = self.left.min(j).max((j + 1).saturating_sub(self.width));
+        self.clamp();
+    }
+
+    /// Whether the entire grid is visible
+    pub fn shows_whole_grid(&self) -> bool {
+        self.height >= self.gridh && self.width >= self.gridw
+    }
+
+    /// Visible part of the grid
+    pub fn slice<'a, T>(&self, grid: ArrayView2<'a, T>) -> ArrayView2<'a, T> {
+        grid.slice_move(s![
+            self.top..self.top + self.height,
+            self.left..self.left + self.width
+        ])
+    }
+
+    /// Convert grid coordinates to viewport coordinates (if visible)
+    pub fn to_local(&self, (i, j): (usize, usize)) -> Option<(usize, usize)> {
+        let (li, lj) = (i.checked_sub(self.top)?, j.checked_sub(self.left)?);
+        (li < self.height && lj < self.width).then_some((li, lj))
+    }
+
+    /// Convert viewport coordinates to grid coordinates (if inside the viewport)
+    pub fn to_global(&self, (li, lj): (usize, usize)) -> Option<(usize, usize)> {
+        (li < self.height && lj < self.width).then_some((li + self.top, lj + self.left))
+    }
+
+    /// Minimap lines showing which region of the grid is in view
+    /// (empty if the minimap is off or not needed)
+    pub fn minimap(&self) -> Vec<String> {
+        if !self.show_minimap || self.shows_whole_grid() {
+            return Vec::new();
+        }
+
+        // which minimap rows/cols overlap the viewport
+        let overlaps = |start: usize, len: usize, total: usize, n: usize, k: usize| {
+            let lo = k * total / n;
+            let hi = ((k + 1) * total / n).max(lo + 1);
+            lo < start + len && start < hi
+        };
+        (0..MINIMAP_H)
+            .map(|mi| {
+                (0..MINIMAP_W)
+                    .map(|mj| {
+                        if overlaps(self.top, self.height, self.gridh, MINIMAP_H, mi)
+                            && overlaps(self.left, self.width, self.gridw, MINIMAP_W, mj)
+                        {
+                            MINIMAP_VISIBLE
+                        } else {
+                            MINIMAP_HIDDEN
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // keep viewport inside the grid
+    fn clamp(&mut self) {
+        self.top = self.top.min(self.gridh - self.height);
+        self.left = self.left.min(self.gridw - self.width);
+    }
+}
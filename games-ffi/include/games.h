@@ -0,0 +1,116 @@
+/*
+ * C API for the Minesweeper, Game of Life and Langton's Ant cores, from
+ * libgames (built by `cargo build --release -p games-ffi`, as
+ * target/release/libgames.so and libgames.a).
+ *
+ * Each core is an opaque pointer made by its _new function and freed with
+ * its _free function. Calls that fail return NULL or -1, and
+ * games_last_error() says why; passing NULL for a core fails the same way.
+ * Squares and cells are (row, column) from the top left, counting from 0.
+ * None of it is thread-safe: use a core from one thread at a time.
+ */
+
+#ifndef GAMES_H
+#define GAMES_H
+
+#include <stdint.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Why the last call on this thread that returned NULL or -1 failed (good
+ * until another call fails) */
+const char *games_last_error(void);
+
+/* Minesweeper */
+
+typedef struct MineField MineField;
+
+/* what mines_reveal() and mines_flag() return */
+#define MINES_OK 0
+#define MINES_WIN 1
+#define MINES_LOSE 2
+
+/* what mines_square() returns for squares that aren't counts of mines */
+#define MINES_HIDDEN 9
+#define MINES_FLAG 10
+#define MINES_MINE 11
+
+/* most squares a board made by mines_new() can have across or down */
+#define MINES_MAX_SIDE 1000
+
+/* A board with n_mines laid out from seed (the same seed always lays them
+ * out the same way), or NULL if they don't fit or the board is too big.
+ * A mine under the first square revealed is moved */
+MineField *mines_new(size_t height, size_t width, size_t n_mines, uint64_t seed);
+/* A board set up exactly from text, one character a square and a row to a
+ * line: '.' hidden, '*' a hidden mine, '@' a flagged mine, '!' a flag
+ * without a mine, '_' or '0' to '8' revealed, and 'X' a revealed mine */
+MineField *mines_parse(const char *text);
+void mines_free(MineField *field);
+
+/* MINES_OK, MINES_WIN or MINES_LOSE */
+int32_t mines_reveal(MineField *field, size_t i, size_t j);
+/* flags the square, or takes its flag off again */
+int32_t mines_flag(MineField *field, size_t i, size_t j);
+/* the count of mines around a revealed square, or MINES_HIDDEN, MINES_FLAG
+ * or MINES_MINE */
+int32_t mines_square(const MineField *field, size_t i, size_t j);
+int32_t mines_height(const MineField *field);
+int32_t mines_width(const MineField *field);
+int32_t mines_n_mines(const MineField *field);
+/* 1 once the game's been won or lost, 0 until then */
+int32_t mines_is_over(const MineField *field);
+
+/* Game of Life */
+
+typedef struct GameOfLife GameOfLife;
+
+/* A grid with each cell live with chance fill_ratio (from seed), playing
+ * variant ("life", "immigration" or "brain") by rule (like "B3/S23") with
+ * boundary ("dead" or "torus"); NULL for any of those picks the default */
+GameOfLife *life_new(size_t height, size_t width, double fill_ratio, uint64_t seed,
+                     const char *variant, const char *rule, const char *boundary);
+void life_free(GameOfLife *life);
+
+/* runs n generations */
+int32_t life_step(GameOfLife *life, uint32_t n);
+/* brings a cell to life (live != 0), or kills it */
+int32_t life_set(GameOfLife *life, size_t i, size_t j, int32_t live);
+/* 0 dead, 1 live, 2 live with the second color (immigration) or dying
+ * (Brian's Brain) */
+int32_t life_cell(const GameOfLife *life, size_t i, size_t j);
+int32_t life_height(const GameOfLife *life);
+int32_t life_width(const GameOfLife *life);
+int64_t life_population(const GameOfLife *life);
+int64_t life_generation(const GameOfLife *life);
+
+/* Langton's Ant */
+
+typedef struct Langton Langton;
+
+/* An ant at (row, col) on an endless plane, facing direction ("up",
+ * "down", "left" or "right") and turning by rule (like "RL", a turn for
+ * each state); NULL for either picks the default (right, and RL) */
+Langton *langton_new(int64_t row, int64_t col, const char *direction, const char *rule);
+void langton_free(Langton *ant);
+
+/* moves the ant n steps */
+int32_t langton_step(Langton *ant, uint32_t n);
+/* the state of any cell on the plane, from 0 */
+int32_t langton_state(const Langton *ant, int64_t row, int64_t col);
+int32_t langton_position(const Langton *ant, int64_t *row, int64_t *col);
+/* the part of the plane worth drawing: every cell not in state 0, and the
+ * ant */
+int32_t langton_extent(const Langton *ant, int64_t *top, int64_t *left,
+                       int64_t *height, int64_t *width);
+int64_t langton_steps(const Langton *ant);
+int64_t langton_colored(const Langton *ant);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GAMES_H */
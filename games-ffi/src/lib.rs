@@ -0,0 +1,404 @@
+//! C bindings for the Minesweeper, Game of Life and Langton's Ant cores, so
+//! frontends not written in Rust (and embedded uses) can drive them. Each
+//! core is an opaque pointer that's created, stepped, queried and freed
+//! again; include/games.h declares the whole API.
+//!
+//! Calls that fail return NULL or -1, and [games_last_error] says why.
+//! Passing NULL where a core is expected fails the same way, rather than
+//! crashing
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+
+use common::{Core, Point};
+use gameoflife::{Boundary, GameOfLife, Variant};
+use langton::{Direction, Langton, Turmite};
+use mines::{MineField, MoveResult, SquareView};
+
+// what mines_reveal() and mines_flag() return
+const MINES_OK: i32 = 0;
+const MINES_WIN: i32 = 1;
+const MINES_LOSE: i32 = 2;
+
+// what mines_square() returns for squares that aren't counts of mines
+const MINES_HIDDEN: i32 = 9;
+const MINES_FLAG: i32 = 10;
+const MINES_MINE: i32 = 11;
+
+const FAILED: i32 = -1;
+
+thread_local! {
+    // why the last call on this thread to fail did
+    static LAST_ERROR: RefCell<CString> = RefCell::default();
+}
+
+/// Why the last call on this thread that returned NULL or -1 failed: a
+/// string that stays good until another call fails (empty if none has)
+#[no_mangle]
+pub extern "C" fn games_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
+
+// note why a call failed, for games_last_error()
+fn fail(e: impl Into<Vec<u8>>) {
+    let e = CString::new(e).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = e);
+}
+
+// run [f] on the core, if there is one, returning -1 if there isn't or [f]
+// fails
+fn with<T>(core: Option<T>, f: impl FnOnce(T) -> Result<i32, String>) -> i32 {
+    match core
+        .ok_or_else(|| "no core given (NULL)".to_string())
+        .and_then(f)
+    {
+        Ok(n) => n,
+        Err(e) => {
+            fail(e);
+            FAILED
+        }
+    }
+}
+
+// [s] as a string, or None if it's NULL
+//
+// Safety: [s] has to be NULL or a NUL-terminated string
+unsafe fn string<'a>(s: *const c_char) -> Result<Option<&'a str>, String> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map(Some)
+        .map_err(|e| format!("string isn't UTF-8: {e}"))
+}
+
+// (i, j), if it's on a board of [size]
+fn on_board((height, width): (usize, usize), i: usize, j: usize) -> Result<Point, String> {
+    match i < height && j < width {
+        true => Ok(Point(i, j)),
+        false => Err(format!("({i}, {j}) is off the {width}x{height} board")),
+    }
+}
+
+/////////////////
+// Minesweeper //
+/////////////////
+
+/// A height x width Minesweeper board with n_mines laid out from seed (the
+/// same seed always lays them out the same way), or NULL if they don't fit
+/// (or the board is more than MINES_MAX_SIDE squares across or down)
+#[no_mangle]
+pub extern "C" fn mines_new(
+    height: usize,
+    width: usize,
+    n_mines: usize,
+    seed: u64,
+) -> Option<Box<MineField>> {
+    if let Err(e) = mines::check_board(height, width, n_mines) {
+        fail(e);
+        return None;
+    }
+    Some(Box::new(MineField::with_n_mines(
+        height, width, n_mines, seed,
+    )))
+}
+
+/// A board set up exactly from text (see `MineField`'s `FromStr`), or NULL
+/// if it doesn't read as one
+///
+/// # Safety
+///
+/// [text] has to be NULL or a NUL-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn mines_parse(text: *const c_char) -> Option<Box<MineField>> {
+    let field = string(text).and_then(|text| text.ok_or("no board given (NULL)")?.parse());
+    match field {
+        Ok(field) => Some(Box::new(field)),
+        Err(e) => {
+            fail(e);
+            None
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn mines_free(_field: Option<Box<MineField>>) {}
+
+/// Reveal the square at (i, j): MINES_OK, MINES_WIN or MINES_LOSE
+#[no_mangle]
+pub extern "C" fn mines_reveal(field: Option<&mut MineField>, i: usize, j: usize) -> i32 {
+    with(field, |field| {
+        let p = on_board(field.size(), i, j)?;
+        mines_result(field.reveal(&p))
+    })
+}
+
+/// Put a flag on the square at (i, j), or take it off again: MINES_OK
+#[no_mangle]
+pub extern "C" fn mines_flag(field: Option<&mut MineField>, i: usize, j: usize) -> i32 {
+    with(field, |field| {
+        let p = on_board(field.size(), i, j)?;
+        mines_result(field.toggle_flag(&p))
+    })
+}
+
+/// The square at (i, j) as the player sees it: how many mines are around
+/// it if it's revealed, or MINES_HIDDEN, MINES_FLAG or MINES_MINE
+#[no_mangle]
+pub extern "C" fn mines_square(field: Option<&MineField>, i: usize, j: usize) -> i32 {
+    with(field, |field| {
+        let p = on_board(field.size(), i, j)?;
+        Ok(match field.cell(p) {
+            SquareView::Hidden => MINES_HIDDEN,
+            SquareView::Flag => MINES_FLAG,
+            SquareView::Mine => MINES_MINE,
+            SquareView::Revealed(n) => n as i32,
+        })
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn mines_height(field: Option<&MineField>) -> i32 {
+    with(field, |field| Ok(field.size().0 as i32))
+}
+
+#[no_mangle]
+pub extern "C" fn mines_width(field: Option<&MineField>) -> i32 {
+    with(field, |field| Ok(field.size().1 as i32))
+}
+
+#[no_mangle]
+pub extern "C" fn mines_n_mines(field: Option<&MineField>) -> i32 {
+    with(field, |field| Ok(field.n_mines() as i32))
+}
+
+/// 1 once the game's been won or lost, and 0 until then
+#[no_mangle]
+pub extern "C" fn mines_is_over(field: Option<&MineField>) -> i32 {
+    with(field, |field| Ok(field.is_over() as i32))
+}
+
+fn mines_result(res: MoveResult) -> Result<i32, String> {
+    match res {
+        MoveResult::Ok => Ok(MINES_OK),
+        MoveResult::Win => Ok(MINES_WIN),
+        MoveResult::Lose => Ok(MINES_LOSE),
+        MoveResult::Err(e) => Err(e),
+    }
+}
+
+//////////////////
+// Game of Life //
+//////////////////
+
+/// A height x width grid with cells live at random (each one with chance
+/// fill_ratio, from seed), playing [variant] ("life", "immigration" or
+/// "brain") by [rule] (like "B3/S23") with [boundary] ("dead" or "torus").
+/// NULL for any of those picks the default; NULL is returned if one of them
+/// doesn't make sense
+///
+/// # Safety
+///
+/// [variant], [rule] and [boundary] have to be NULL or NUL-terminated strings
+#[no_mangle]
+pub unsafe extern "C" fn life_new(
+    height: usize,
+    width: usize,
+    fill_ratio: f64,
+    seed: u64,
+    variant: *const c_char,
+    rule: *const c_char,
+    boundary: *const c_char,
+) -> Option<Box<GameOfLife>> {
+    let life = (|| {
+        if height == 0 || width == 0 {
+            return Err("grid has no cells".to_string());
+        }
+        if !(0.0..=1.0).contains(&fill_ratio) {
+            return Err(format!("fill ratio {fill_ratio} isn't between 0 and 1"));
+        }
+        let variant = match string(variant)? {
+            Some(variant) => variant.parse()?,
+            None => Variant::Life,
+        };
+        let rule = match string(rule)? {
+            Some(rule) => rule.parse()?,
+            None => variant.default_rule(),
+        };
+        let boundary: Boundary = match string(boundary)? {
+            Some(boundary) => boundary.parse()?,
+            None => variant.default_boundary(),
+        };
+        Ok(GameOfLife::random(height, width, fill_ratio, seed)
+            .with_variant(variant)
+            .with_rule(rule)
+            .with_boundary(boundary))
+    })();
+    match life {
+        Ok(life) => Some(Box::new(life)),
+        Err(e) => {
+            fail(e);
+            None
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn life_free(_life: Option<Box<GameOfLife>>) {}
+
+/// Run [n] generations
+#[no_mangle]
+pub extern "C" fn life_step(life: Option<&mut GameOfLife>, n: u32) -> i32 {
+    with(life, |life| {
+        life.tick_n(n);
+        Ok(0)
+    })
+}
+
+/// Bring the cell at (i, j) to life (live != 0), or kill it
+#[no_mangle]
+pub extern "C" fn life_set(life: Option<&mut GameOfLife>, i: usize, j: usize, live: i32) -> i32 {
+    with(life, |life| {
+        on_board(life.dim(), i, j)?;
+        life.set(i, j, live != 0);
+        Ok(0)
+    })
+}
+
+/// The cell at (i, j): 0 if it's dead, 1 if it's live, and 2 if it's live
+/// with the second color (immigration) or dying (Brian's Brain)
+#[no_mangle]
+pub extern "C" fn life_cell(life: Option<&GameOfLife>, i: usize, j: usize) -> i32 {
+    with(life, |life| {
+        let p = on_board(life.dim(), i, j)?;
+        Ok(life.cell(p) as i32)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn life_height(life: Option<&GameOfLife>) -> i32 {
+    with(life, |life| Ok(life.dim().0 as i32))
+}
+
+#[no_mangle]
+pub extern "C" fn life_width(life: Option<&GameOfLife>) -> i32 {
+    with(life, |life| Ok(life.dim().1 as i32))
+}
+
+/// Number of live cells
+#[no_mangle]
+pub extern "C" fn life_population(life: Option<&GameOfLife>) -> i64 {
+    life.map_or(FAILED.into(), |life| life.population() as i64)
+}
+
+/// Number of generations run so far
+#[no_mangle]
+pub extern "C" fn life_generation(life: Option<&GameOfLife>) -> i64 {
+    life.map_or(FAILED.into(), |life| life.nstep().into())
+}
+
+///////////////////
+// Langton's Ant //
+///////////////////
+
+/// An ant at (row, col) on an endless plane of cells in state 0, facing
+/// [direction] ("up", "down", "left" or "right") and turning by [rule]
+/// (like "RL", one turn for each state). NULL for either picks the default
+/// (right, and RL); NULL is returned if one of them doesn't make sense
+///
+/// # Safety
+///
+/// [direction] and [rule] have to be NULL or NUL-terminated strings
+#[no_mangle]
+pub unsafe extern "C" fn langton_new(
+    row: i64,
+    col: i64,
+    direction: *const c_char,
+    rule: *const c_char,
+) -> Option<Box<Langton>> {
+    let ant = (|| {
+        let direction = match string(direction)? {
+            Some(direction) => direction.parse()?,
+            None => Direction::Right,
+        };
+        let rule = match string(rule)? {
+            Some(rule) => rule.parse()?,
+            None => Default::default(),
+        };
+        Ok::<_, String>(Langton::new((row, col), direction).with_rule(rule))
+    })();
+    match ant {
+        Ok(ant) => Some(Box::new(ant)),
+        Err(e) => {
+            fail(e);
+            None
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn langton_free(_ant: Option<Box<Langton>>) {}
+
+/// Move the ant [n] steps
+#[no_mangle]
+pub extern "C" fn langton_step(ant: Option<&mut Langton>, n: u32) -> i32 {
+    with(ant, |ant| {
+        ant.tick_n(n);
+        Ok(0)
+    })
+}
+
+/// State of the cell at (row, col), from 0
+#[no_mangle]
+pub extern "C" fn langton_state(ant: Option<&Langton>, row: i64, col: i64) -> i32 {
+    with(ant, |ant| Ok(ant.state_at((row, col)).into()))
+}
+
+/// Where the ant is, written to [row] and [col]
+#[no_mangle]
+pub extern "C" fn langton_position(
+    ant: Option<&Langton>,
+    row: Option<&mut i64>,
+    col: Option<&mut i64>,
+) -> i32 {
+    with(ant, |ant| {
+        let (i, j) = ant.ant_pos();
+        let (row, col) = row.zip(col).ok_or("nowhere to write the position (NULL)")?;
+        (*row, *col) = (i, j);
+        Ok(0)
+    })
+}
+
+/// The part of the plane worth drawing (every cell that isn't in state 0,
+/// and the ant), written to [top], [left], [height] and [width]
+#[no_mangle]
+pub extern "C" fn langton_extent(
+    ant: Option<&Langton>,
+    top: Option<&mut i64>,
+    left: Option<&mut i64>,
+    height: Option<&mut i64>,
+    width: Option<&mut i64>,
+) -> i32 {
+    with(ant, |ant| {
+        let (t, l, h, w) = ant.extent();
+        let ((top, left), (height, width)) = top
+            .zip(left)
+            .zip(height.zip(width))
+            .ok_or("nowhere to write the extent (NULL)")?;
+        (*top, *left, *height, *width) = (t, l, h as i64, w as i64);
+        Ok(0)
+    })
+}
+
+/// Number of steps taken so far
+#[no_mangle]
+pub extern "C" fn langton_steps(ant: Option<&Langton>) -> i64 {
+    ant.map_or(FAILED.into(), |ant| ant.nstep().into())
+}
+
+/// Number of cells that aren't in state 0
+#[no_mangle]
+pub extern "C" fn langton_colored(ant: Option<&Langton>) -> i64 {
+    ant.map_or(FAILED.into(), |ant| ant.n_colored() as i64)
+}
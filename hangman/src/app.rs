@@ -0,0 +1,377 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{Game, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::HangmanConfig;
+use crate::hangman::{GuessResult, Hangman, MAX_WRONG};
+use crate::hangmanui::{self, HangmanUI, HangmanUIAction};
+use crate::wordlist::{WordList, BUNDLED};
+
+// the letters, as laid out on a keyboard
+const KEYBOARD: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+// lines of the gallows, however far along it is
+const GALLOWS_ROWS: usize = 7;
+
+// lines of text around the gallows and the keyboard (category, gap, word,
+// two gaps, misses, games won, message)
+const STATUS_ROWS: usize = 8;
+
+/// Hangman app (the word being guessed, the words to pick the next one
+/// from, the score over every word so far, and UI state)
+pub struct HangmanApp {
+    game: Hangman,
+    ui: HangmanUI,
+    words: WordList,
+    category: Option<usize>, // the one picked, or none for any of them
+    word_category: usize,    // the one the word came from
+    rng: StdRng,             // for picking words
+    wins: u32,
+    losses: u32,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last word guessed or not
+    over: bool,
+}
+
+impl HangmanApp {
+    /// Guess words from [words], in [category] (or any of them), picked
+    /// with [seed]
+    pub fn new(words: WordList, category: Option<usize>, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (word_category, word) = words.pick(category, &mut rng);
+        Self {
+            game: Hangman::new(word),
+            ui: HangmanUI::new(),
+            words,
+            category,
+            word_category,
+            rng,
+            wins: 0,
+            losses: 0,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_word(&mut self) {
+        let (word_category, word) = self.words.pick(self.category, &mut self.rng);
+        debug!(
+            "new word from {}",
+            self.words.categories()[word_category].name
+        );
+        self.game = Hangman::new(word);
+        self.word_category = word_category;
+        self.message = "".to_string().reset();
+    }
+
+    // pick the category after this one (after the last comes any of them)
+    fn next_category(&mut self) {
+        let count = self.words.categories().len();
+        self.category = match self.category {
+            None => Some(0),
+            Some(k) if k + 1 < count => Some(k + 1),
+            Some(_) => None,
+        };
+        self.new_word();
+    }
+
+    fn handle_res(&mut self, res: GuessResult) {
+        let again = locale::text("hangman.again");
+        match res {
+            GuessResult::Won => {
+                bell::ring("win");
+                self.wins += 1;
+                self.outcome = Outcome::Won;
+                self.message = format!("{} {again}", locale::text("hangman.win"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            GuessResult::Lost => {
+                bell::ring("lose");
+                self.losses += 1;
+                self.outcome = Outcome::Lost;
+                self.message = format!(
+                    "{} {}. {again}",
+                    locale::text("hangman.lose"),
+                    self.game.word()
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Danger));
+            }
+            GuessResult::AlreadyGuessed => {
+                bell::ring("invalid move");
+                self.message = locale::text("hangman.already")
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            GuessResult::GameOver | GuessResult::NotALetter => bell::ring("invalid move"),
+            GuessResult::Right(_) | GuessResult::Wrong => self.message = "".to_string().reset(),
+        }
+    }
+
+    // which category the word's from
+    fn title(&self) -> String {
+        let name = &self.words.categories()[self.word_category].name;
+        let mut title = format!("{}: {name}", locale::text("hangman.category"));
+        if self.category.is_none() {
+            title = format!("{title} ({})", locale::text("hangman.any"));
+        }
+        title
+    }
+
+    /// Lines of the gallows, with as much of the figure as there've been
+    /// wrong guesses
+    fn gallows_lines(&self) -> [String; GALLOWS_ROWS] {
+        let wrong = self.game.wrong_guesses();
+        let part = |from: usize, drawn: &str| match wrong >= from {
+            true => drawn.to_string(),
+            false => "      |".to_string(),
+        };
+        [
+            "  +---+".to_string(),
+            "  |   |".to_string(),
+            part(1, "  O   |"),
+            match wrong {
+                0 | 1 => "      |".to_string(),
+                2 => "  |   |".to_string(),
+                3 => " /|   |".to_string(),
+                _ => " /|\\  |".to_string(),
+            },
+            match wrong {
+                5 => " /    |".to_string(),
+                _ => part(6, " / \\  |"),
+            },
+            "      |".to_string(),
+            "=========".to_string(),
+        ]
+    }
+
+    // (styled) gallows, in red once the game's lost
+    fn styled_gallows(&self) -> Vec<StyledContent<String>> {
+        let role = match self.game.is_lost() {
+            true => Role::Danger,
+            false => Role::Text,
+        };
+        self.gallows_lines()
+            .into_iter()
+            .map(|line| line.with(theme::color(role)))
+            .collect()
+    }
+
+    /// The word, a (styled) piece per letter: blanks for letters not
+    /// guessed yet, filled in once the game's lost
+    fn word_line(&self) -> Vec<StyledContent<String>> {
+        let lost = self.game.is_lost();
+        self.game
+            .revealed()
+            .into_iter()
+            .zip(self.game.word().chars())
+            .map(|(shown, c)| match shown {
+                Some(c) => format!("{c} ").bold(),
+                None if lost => format!("{c} ").with(theme::color(Role::Danger)),
+                None => "_ ".to_string().reset(),
+            })
+            .collect()
+    }
+
+    /// Rows of the keyboard, a (styled) piece per letter: letters found in
+    /// the word in capitals, and ones that weren't dotted out
+    fn keyboard_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        KEYBOARD
+            .iter()
+            .enumerate()
+            .map(|(k, row)| {
+                let mut line = vec![" ".repeat(k).reset()];
+                for c in row.chars() {
+                    line.push(match self.game.guessed(c) {
+                        None => format!("{c} ").with(theme::color(Role::Text)),
+                        Some(true) => format!("{} ", c.to_ascii_uppercase())
+                            .bold()
+                            .with(theme::color(Role::Success)),
+                        Some(false) => "· ".to_string().with(theme::color(Role::Muted)),
+                    });
+                }
+                line
+            })
+            .collect()
+    }
+
+    // wrong guesses, out of the most there can be
+    fn misses_line(&self) -> String {
+        format!(
+            "{} {}/{MAX_WRONG}",
+            locale::text("hangman.misses"),
+            self.game.wrong_guesses()
+        )
+    }
+
+    // words guessed, and not
+    fn score_line(&self) -> String {
+        format!(
+            "{} {}   {} {}",
+            locale::text("hangman.won"),
+            self.wins,
+            locale::text("hangman.lost"),
+            self.losses
+        )
+    }
+}
+
+// Pretty-print
+impl fmt::Display for HangmanApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\r\n", self.title().bold())?;
+        for line in self.styled_gallows() {
+            write!(f, "{line}\r\n")?;
+        }
+        write!(f, "\r\n")?;
+        for piece in self.word_line() {
+            write!(f, "{piece}")?;
+        }
+        write!(f, "\r\n\r\n")?;
+        for line in self.keyboard_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.misses_line())?;
+        write!(f, "{}\r\n", self.score_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", hangmanui::help_text())
+    }
+}
+
+impl Game for HangmanApp {
+    fn name(&self) -> &'static str {
+        "hangman"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != HangmanUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            HangmanUIAction::Quit => self.over = true,
+            HangmanUIAction::NewWord => self.new_word(),
+            HangmanUIAction::NextCategory => self.next_category(),
+            HangmanUIAction::Guess(c) => {
+                let res = self.game.guess(c);
+                self.handle_res(res);
+            }
+            HangmanUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    // the longest word, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = hangmanui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        let longest = self
+            .words
+            .categories()
+            .iter()
+            .flat_map(|category| &category.words)
+            .map(|word| 2 * word.len())
+            .max()
+            .unwrap_or_default();
+        (
+            cols.max(longest as u16),
+            (GALLOWS_ROWS + KEYBOARD.len() + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+// words from the file at [path], or the bundled ones
+fn load_words(args: &Args, config: &HangmanConfig) -> Result<WordList, String> {
+    match args.words.as_ref().or(config.words.as_ref()) {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+            WordList::parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+        }
+        None => WordList::parse(BUNDLED),
+    }
+}
+
+/// Play hangman with the bundled words, or a list given on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: HangmanConfig = common::cli::setup(&args.common, "hangman")?;
+
+    let words = load_words(&args, &config)?;
+    let category = match args.category.as_ref().or(config.category.as_ref()) {
+        Some(name) => Some(words.find(name).ok_or_else(|| {
+            let names: Vec<_> = words.categories().iter().map(|c| c.name.as_str()).collect();
+            format!("no category '{name}' (there's {})", names.join(", "))
+        })?),
+        None => None,
+    };
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("hangman seed {seed}");
+
+    let mut app = HangmanApp::new(words, category, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &hangmanui::ACTIONS)
+        .map_err(|e| format!("bad [hangman.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
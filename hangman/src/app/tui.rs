@@ -0,0 +1,35 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::HangmanApp;
+use crate::hangmanui;
+
+impl TuiGame for HangmanApp {
+    fn draw(&self, frame: &mut Frame) {
+        let mut rows: Vec<Line> = self
+            .styled_gallows()
+            .iter()
+            .map(|line| Line::from(tui::span(line)))
+            .collect();
+        rows.push(Line::default());
+        rows.push(self.word_line().iter().map(tui::span).collect());
+        rows.push(Line::default());
+        rows.extend(
+            self.keyboard_lines()
+                .iter()
+                .map(|line| line.iter().map(tui::span).collect::<Line>()),
+        );
+        let board = Board::new(rows);
+
+        let status = StatusBar::new(hangmanui::help_text())
+            .line(self.title())
+            .line(self.misses_line())
+            .line(self.score_line())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
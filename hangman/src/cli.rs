@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Hangman
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on repeated guesses, wins and achievements")
+}))]
+pub struct Args {
+    /// Category to pick words from, e.g. animals or countries
+    /// [default: any of them, unless the config file picks one]
+    #[arg(long)]
+    pub category: Option<String>,
+
+    /// Word list to play, a [category] line before each category's words,
+    /// one to a line [default: the words that come with the game]
+    #[arg(long)]
+    pub words: Option<PathBuf>,
+
+    /// Random seed for picking words (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [hangman] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HangmanConfig {
+    /// category to pick words from, e.g. "animals"
+    pub category: Option<String>,
+    /// word list to play instead of the bundled one
+    pub words: Option<PathBuf>,
+    /// keys for each action, e.g. new_word = "enter"
+    pub keys: KeyMap,
+}
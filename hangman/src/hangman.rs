@@ -0,0 +1,132 @@
+use common::{Core, Point};
+use log::debug;
+
+/// Wrong guesses it takes to lose (head, body, two arms and two legs)
+pub const MAX_WRONG: usize = 6;
+
+/// What a guess led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuessResult {
+    /// the letter's in the word this many times
+    Right(usize),
+    Wrong,
+    /// the letter's been guessed before
+    AlreadyGuessed,
+    /// that's not a letter
+    NotALetter,
+    /// the guess filled in the last of the word
+    Won,
+    /// the guess was the last wrong one allowed
+    Lost,
+    /// the game has already ended
+    GameOver,
+}
+
+/// A game of hangman: the word to guess, and the letters guessed so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hangman {
+    word: Vec<char>,
+    guessed: Vec<char>, // in the order they were guessed
+}
+
+impl Hangman {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Game guessing [word] (its letters, that is: spaces and hyphens are
+    /// given away)
+    pub fn new(word: &str) -> Self {
+        Self {
+            word: word.to_lowercase().chars().collect(),
+            guessed: Vec::new(),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Guess that [c] is in the word
+    pub fn guess(&mut self, c: char) -> GuessResult {
+        if self.is_over() {
+            return GuessResult::GameOver;
+        }
+        if !c.is_ascii_alphabetic() {
+            return GuessResult::NotALetter;
+        }
+        let c = c.to_ascii_lowercase();
+        if self.guessed.contains(&c) {
+            return GuessResult::AlreadyGuessed;
+        }
+        self.guessed.push(c);
+        let count = self.word.iter().filter(|&&w| w == c).count();
+        debug!("guessed {c}: {count} in the word");
+        match count {
+            0 if self.is_lost() => GuessResult::Lost,
+            0 => GuessResult::Wrong,
+            _ if self.is_won() => GuessResult::Won,
+            _ => GuessResult::Right(count),
+        }
+    }
+
+    /// The word, with None for each letter not guessed yet
+    pub fn revealed(&self) -> Vec<Option<char>> {
+        (0..self.word.len())
+            .map(|k| self.cell(Point(0, k)))
+            .collect()
+    }
+
+    /// The whole word (to show once the game's lost)
+    pub fn word(&self) -> String {
+        self.word.iter().collect()
+    }
+
+    /// Whether [c] has been guessed, and if so, whether it's in the word
+    pub fn guessed(&self, c: char) -> Option<bool> {
+        let c = c.to_ascii_lowercase();
+        self.guessed.contains(&c).then(|| self.word.contains(&c))
+    }
+
+    /// Letters guessed that aren't in the word
+    pub fn wrong_guesses(&self) -> usize {
+        self.guessed
+            .iter()
+            .filter(|c| !self.word.contains(c))
+            .count()
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.word
+            .iter()
+            .all(|c| !c.is_ascii_alphabetic() || self.guessed.contains(c))
+    }
+
+    pub fn is_lost(&self) -> bool {
+        self.wrong_guesses() >= MAX_WRONG
+    }
+}
+
+impl Core for Hangman {
+    type Action = char;
+    type Outcome = GuessResult;
+    /// A letter of the word, if it's been guessed (or isn't a letter)
+    type Cell = Option<char>;
+
+    fn size(&self) -> (usize, usize) {
+        (1, self.word.len())
+    }
+
+    fn cell(&self, Point(_, j): Point) -> Option<char> {
+        let c = self.word[j];
+        (!c.is_ascii_alphabetic() || self.guessed.contains(&c)).then_some(c)
+    }
+
+    fn act(&mut self, c: char) -> GuessResult {
+        self.guess(c)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+}
@@ -0,0 +1,81 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use common::{locale, Bindings, KeyBinding};
+
+/// Help lines under the word, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("hangman.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HangmanUIAction {
+    Wait,
+    /// guess a letter (any letter key that isn't bound to something else)
+    Guess(char),
+    NewWord,
+    /// move on to the next category, with a new word from it
+    NextCategory,
+    Quit,
+}
+
+// default controls (the letters are all for guessing, so these keep off
+// them)
+const BINDINGS: [(KeyBinding, HangmanUIAction); 4] = [
+    (KeyBinding::key(KeyCode::Enter), HangmanUIAction::NewWord),
+    (KeyBinding::key(KeyCode::Tab), HangmanUIAction::NextCategory),
+    (KeyBinding::key(KeyCode::Esc), HangmanUIAction::Quit),
+    (KeyBinding::ctrl('c'), HangmanUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, HangmanUIAction); 3] = [
+    ("new_word", HangmanUIAction::NewWord),
+    ("next_category", HangmanUIAction::NextCategory),
+    ("quit", HangmanUIAction::Quit),
+];
+
+pub struct HangmanUI {
+    pub bindings: Bindings<HangmanUIAction>,
+}
+
+impl HangmanUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> HangmanUIAction {
+        if let Some(action) = self.bindings.action(key_event) {
+            return action;
+        }
+        let mut modifiers = key_event.modifiers;
+        modifiers.remove(KeyModifiers::SHIFT);
+        match key_event.code {
+            KeyCode::Char(c)
+                if c.is_ascii_alphabetic()
+                    && modifiers.is_empty()
+                    && key_event.kind == KeyEventKind::Press =>
+            {
+                HangmanUIAction::Guess(c.to_ascii_lowercase())
+            }
+            _ => HangmanUIAction::Wait,
+        }
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> HangmanUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => HangmanUIAction::Wait,
+        }
+    }
+}
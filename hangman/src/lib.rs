@@ -0,0 +1,21 @@
+//! Hangman: the game core (a word and the letters guessed at it), a bundled
+//! word list in categories, plus the crossterm frontend for playing it in
+//! a terminal
+
+mod hangman;
+mod wordlist;
+pub use hangman::{GuessResult, Hangman, MAX_WRONG};
+pub use wordlist::{Category, WordList, BUNDLED};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod hangmanui;
+#[cfg(feature = "terminal")]
+pub use app::{run, HangmanApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,86 @@
+use rand::{seq::SliceRandom, Rng};
+
+/// Words bundled with the game
+pub const BUNDLED: &str = include_str!("../words/bundled.txt");
+
+/// A named group of words to guess
+#[derive(Debug, Clone, PartialEq)]
+pub struct Category {
+    pub name: String,
+    pub words: Vec<String>,
+}
+
+/// Words to guess, by category
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordList {
+    categories: Vec<Category>,
+}
+
+impl WordList {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Word list in the bundled format: a `[name]` line starts a category,
+    /// and each line after it is one word (letters, with spaces or hyphens
+    /// between words). Blank lines and `# comments` are skipped
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut categories: Vec<Category> = Vec::new();
+        for (k, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                categories.push(Category {
+                    name: name.trim().to_lowercase(),
+                    words: Vec::new(),
+                });
+                continue;
+            }
+            let Some(category) = categories.last_mut() else {
+                return Err(format!("line {}: word before any [category]", k + 1));
+            };
+            if !line
+                .chars()
+                .all(|c| c.is_ascii_alphabetic() || c == ' ' || c == '-')
+            {
+                return Err(format!(
+                    "line {}: '{line}' isn't made of letters (and spaces or hyphens)",
+                    k + 1
+                ));
+            }
+            category.words.push(line.to_lowercase());
+        }
+        categories.retain(|category| !category.words.is_empty());
+        if categories.is_empty() {
+            return Err("no words found".into());
+        }
+        Ok(Self { categories })
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// Index of the category called [name]
+    pub fn find(&self, name: &str) -> Option<usize> {
+        let name = name.to_lowercase();
+        self.categories.iter().position(|c| c.name == name)
+    }
+
+    /// A word at random from the category at [index], or from any of them
+    /// (None), along with the index of the category it's from
+    pub fn pick(&self, index: Option<usize>, rng: &mut impl Rng) -> (usize, &str) {
+        let index = index.unwrap_or_else(|| rng.gen_range(0..self.categories.len()));
+        let word = self.categories[index]
+            .words
+            .choose(rng)
+            .expect("categories have words");
+        (index, word)
+    }
+}
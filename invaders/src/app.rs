@@ -0,0 +1,291 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::InvadersConfig;
+use crate::invaders::{Invaders, Square, Step, SHIELD_HP};
+use crate::invadersui::{self, InvadersUI, InvadersUIAction};
+
+// time between ticks (a shot goes up a row every tick)
+const TICK: Duration = Duration::from_millis(30);
+
+// ticks a message about the game (a lost cannon, a new wave) stays up
+const MESSAGE_TICKS: u32 = 60;
+
+// terminal columns each square takes up (so squares come out roughly square)
+const SQUARE_COLS: usize = 2;
+
+// lines of text under the field (status, message, exit prompt)
+const STATUS_ROWS: usize = 3;
+
+/// Space Invaders app (game + UI state)
+pub struct InvadersApp {
+    invaders: Invaders,
+    ui: InvadersUI,
+    message: StyledContent<String>,
+    message_ticks: u32, // left before the message goes (0 to keep it up)
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless the last cannon went
+    over: bool,
+}
+
+impl InvadersApp {
+    pub fn new(height: usize, width: usize, seed: u64) -> Self {
+        Self {
+            invaders: Invaders::new(height, width, seed),
+            ui: InvadersUI::new(),
+            message: locale::text("invaders.start").to_string().reset(),
+            message_ticks: 0,
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn handle_step(&mut self, step: Step) {
+        match step {
+            Step::Over => {
+                bell::ring("lose");
+                self.over = true;
+                self.outcome = Outcome::Lost;
+                self.message = format!(
+                    "{} {} {}",
+                    locale::text("invaders.game_over_message"),
+                    locale::text("invaders.final_score"),
+                    self.invaders.score()
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Danger));
+            }
+            Step::Killed => {
+                bell::ring("crash");
+                self.flash(locale::text("invaders.hit").to_string().bold());
+            }
+            Step::Cleared => {
+                bell::ring("wave cleared");
+                let text = format!(
+                    "{} {}!",
+                    locale::text("invaders.wave"),
+                    self.invaders.wave()
+                );
+                self.flash(text.bold().with(theme::color(Role::Success)));
+            }
+            Step::Hit(points) => debug!("hit for {points}, score {}", self.invaders.score()),
+            Step::Moved => {}
+        }
+    }
+
+    // put up a message for a moment
+    fn flash(&mut self, message: StyledContent<String>) {
+        self.message = message;
+        self.message_ticks = MESSAGE_TICKS;
+    }
+
+    // (styled) string for a square: blocks of color, or ASCII without color
+    fn styled_square(&self, sq: Square) -> StyledContent<&'static str> {
+        let (role, plain, block) = match sq {
+            Square::Empty => return "  ".reset(),
+            Square::Alien(0, pose) => (Role::Danger, "/\\", if pose { "▛▜" } else { "▙▟" }),
+            Square::Alien(1, pose) => (Role::Marker, "{}", if pose { "▛▜" } else { "▙▟" }),
+            Square::Alien(_, pose) => (Role::Accent, "<>", if pose { "▛▜" } else { "▙▟" }),
+            Square::Cannon => (Role::Success, "AA", "▟▙"),
+            Square::Shot => (Role::Text, "||", "▕▏"),
+            Square::Bomb => (Role::Danger, "**", "▗▖"),
+            Square::Shield(hp) if hp == SHIELD_HP => (Role::Success, "##", "██"),
+            Square::Shield(hp) if hp > 1 => (Role::Success, "==", "▓▓"),
+            Square::Shield(_) => (Role::Success, "..", "░░"),
+        };
+        if style::color_enabled() {
+            block.with(theme::color(role))
+        } else {
+            plain.bold()
+        }
+    }
+
+    // score, cannons left and wave, plus [paused] while paused
+    fn status(&self) -> String {
+        let mut status = format!(
+            "{} {}   {} {}   {} {}",
+            locale::text("invaders.score"),
+            self.invaders.score(),
+            locale::text("invaders.lives"),
+            self.invaders.lives(),
+            locale::text("invaders.wave"),
+            self.invaders.wave()
+        );
+        if self.ui.paused && !self.over {
+            status += &format!(" [{}]", locale::text("invaders.paused"));
+        }
+        status
+    }
+
+    // rows of the field, as (styled) squares
+    fn field_rows(&self) -> impl Iterator<Item = Vec<StyledContent<&'static str>>> + '_ {
+        let (height, width) = self.invaders.size();
+        (0..height).map(move |i| {
+            (0..width)
+                .map(|j| self.styled_square(self.invaders.cell(Point(i, j))))
+                .collect()
+        })
+    }
+}
+
+// Pretty-print
+impl fmt::Display for InvadersApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, width) = self.invaders.size();
+        let edge = "─".repeat(SQUARE_COLS * width);
+        let wall = "│".with(theme::color(Role::Muted));
+        write!(
+            f,
+            "{}\r\n",
+            format!("┌{edge}┐").with(theme::color(Role::Muted))
+        )?;
+        for row in self.field_rows() {
+            write!(f, "{wall}")?;
+            for sq in row {
+                write!(f, "{sq}")?;
+            }
+            write!(f, "{wall}\r\n")?;
+        }
+        write!(
+            f,
+            "{}\r\n",
+            format!("└{edge}┘").with(theme::color(Role::Muted))
+        )?;
+
+        write!(f, "{}\r\n", self.status())?;
+        write!(f, "{}\r\n", self.message)?;
+        if self.over {
+            write!(f, "{}\r\n", locale::text("invaders.exit"))?;
+        } else {
+            write!(f, "{}\r\n", invadersui::help_text())?;
+        }
+        Ok(())
+    }
+}
+
+impl Game for InvadersApp {
+    fn name(&self) -> &'static str {
+        "invaders"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != InvadersUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            InvadersUIAction::Quit => self.over = true,
+            InvadersUIAction::Pause => self.ui.paused = !self.ui.paused,
+            // moving or firing also gets the game going again
+            InvadersUIAction::Cannon(action) => {
+                self.ui.paused = false;
+                self.invaders.act(action);
+            }
+            InvadersUIAction::Wait => {}
+        }
+        if !self.ui.paused && self.message_ticks == 0 && !self.over {
+            self.message = "".to_string().reset();
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {
+        if self.ui.paused || self.over {
+            return;
+        }
+        if self.message_ticks > 0 {
+            self.message_ticks -= 1;
+            if self.message_ticks == 0 {
+                self.message = "".to_string().reset();
+            }
+        }
+        let step = self.invaders.step();
+        self.handle_step(step);
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(TICK)
+    }
+
+    // the field with its walls, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.invaders.size();
+        let cols = (SQUARE_COLS * width + 2) as u16;
+        let help = invadersui::help_text();
+        (
+            cols.max(text_width(help)),
+            (height + 2 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Space Invaders on the field chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: InvadersConfig = common::cli::setup(&args.common, "invaders")?;
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("invaders seed {seed}");
+    let mut app = InvadersApp::new(args.height as usize, args.width as usize, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &invadersui::ACTIONS)
+        .map_err(|e| format!("bad [invaders.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the field up until a key is pressed, unless the player quit
+    if app.outcome != Outcome::Quit {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+
+use super::InvadersApp;
+use crate::invadersui;
+
+impl TuiGame for InvadersApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .field_rows()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let title = format!(
+            " {} {} ",
+            locale::text("invaders.score"),
+            self.invaders.score()
+        );
+        let board = Board::new(rows).title(title);
+
+        let message = tui::span(&self.message);
+        let status = StatusBar::new(invadersui::help_text())
+            .line(self.status())
+            .line(message.clone());
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+
+        if self.over {
+            let mut lines = vec![Line::from(locale::text("invaders.exit"))];
+            if !message.content.is_empty() {
+                lines.insert(0, Line::from(message));
+            }
+            let title = format!(" {} ", locale::text("invaders.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Space Invaders
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on losing a cannon, clearing a wave and achievements")
+}))]
+pub struct Args {
+    /// Field height (in squares)
+    #[arg(long, default_value_t = 18, value_parser = clap::value_parser!(u16).range(12..))]
+    pub height: u16,
+
+    /// Field width (in squares)
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u16).range(17..))]
+    pub width: u16,
+
+    /// Random seed for the aliens' bombs (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
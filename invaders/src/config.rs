@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [invaders] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InvadersConfig {
+    /// keys for each action, e.g. fire = ["space", "up"]
+    pub keys: KeyMap,
+}
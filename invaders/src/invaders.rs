@@ -0,0 +1,480 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// rows and columns of aliens in each wave
+const ALIEN_ROWS: usize = 5;
+const ALIEN_COLS: usize = 8;
+
+// squares from one alien's column to the next
+const ALIEN_STRIDE: usize = 2;
+
+// row the top of the first wave starts on, and how much lower each wave
+// after it starts (down to [MAX_START_ROW])
+const START_ROW: usize = 1;
+const MAX_START_ROW: usize = 4;
+
+// points for an alien of each kind (the top row is worth the most)
+const POINTS: [u32; 3] = [30, 20, 10];
+
+// ticks between the formation's steps with every alien left, with one left,
+// and how much quicker each wave after the first marches
+const SLOWEST_MARCH: u32 = 24;
+const FASTEST_MARCH: u32 = 1;
+const WAVE_SPEEDUP: f64 = 0.85;
+
+// ticks a bomb takes to drop a row (shots go up a row every tick)
+const BOMB_TICKS: u32 = 2;
+
+// most bombs falling at once, and the chance of a new one each tick (going
+// up with each wave, to [MAX_FIRE_CHANCE])
+const MAX_BOMBS: usize = 3;
+const FIRE_CHANCE: f64 = 0.03;
+const WAVE_FIRE_CHANCE: f64 = 0.01;
+const MAX_FIRE_CHANCE: f64 = 0.1;
+
+// shields: how many, their size in squares, and the hits each square takes
+const SHIELDS: usize = 4;
+const SHIELD_SIZE: (usize, usize) = (2, 3);
+
+/// Hits a square of shield takes to knock out
+pub const SHIELD_HP: u8 = 3;
+
+// cannons to start with
+const LIVES: u32 = 3;
+
+// ticks everything holds still after the cannon is hit
+const RESPAWN_WAIT: u32 = 40;
+
+/// Which way to move the cannon, or to fire it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Left,
+    Right,
+    Fire,
+}
+
+/// What's on one square of the field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Empty,
+    /// an alien of a kind (0 for the top row, worth the most, up to 2), in
+    /// one of the two poses it switches between as it marches
+    Alien(usize, bool),
+    Cannon,
+    Shot,
+    Bomb,
+    /// part of a shield, with the hits it has left
+    Shield(u8),
+}
+
+/// What happened on a tick (the most important thing, if several did)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Moved,
+    /// the shot hit an alien, for some points
+    Hit(u32),
+    /// every alien in the wave is gone, and the next one's coming in
+    Cleared,
+    /// a bomb hit the cannon
+    Killed,
+    /// the last cannon was hit, or the aliens landed
+    Over,
+}
+
+/// Space Invaders: rows of aliens marching side to side and down towards
+/// the cannon at the bottom, dropping bombs, with shields in between
+pub struct Invaders {
+    height: usize,
+    width: usize,
+    aliens: [[bool; ALIEN_COLS]; ALIEN_ROWS], // which are left
+    formation: Point,                         // where the top-left alien would be
+    heading_right: bool,
+    pose: bool,       // flips every step the aliens take
+    shields: Vec<u8>, // row by row, hits left on each square
+    cannon: usize,    // column (always on the bottom row)
+    shot: Option<Point>,
+    bombs: Vec<Point>,
+    score: u32,
+    lives: u32,
+    wave: u32,
+    ticks: u32,
+    wait: u32, // ticks left holding still
+    over: bool,
+    rng: StdRng, // for when and where bombs drop
+}
+
+impl Invaders {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Field [height] rows by [width] columns, with the first wave of aliens
+    /// at the top. The same seed always drops the same bombs
+    pub fn new(height: usize, width: usize, seed: u64) -> Self {
+        assert!(
+            height >= 12 && width > ALIEN_STRIDE * ALIEN_COLS,
+            "field too small for Space Invaders"
+        );
+        let mut invaders = Self {
+            height,
+            width,
+            aliens: [[true; ALIEN_COLS]; ALIEN_ROWS],
+            formation: Point(START_ROW, 0),
+            heading_right: true,
+            pose: false,
+            shields: vec![0; height * width],
+            cannon: width / 2,
+            shot: None,
+            bombs: Vec::new(),
+            score: 0,
+            lives: LIVES,
+            wave: 1,
+            ticks: 0,
+            wait: 0,
+            over: false,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        invaders.build_shields();
+        invaders
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Move the cannon a square, or fire it (if its last shot is gone).
+    /// Does nothing while everything's holding still
+    pub fn act(&mut self, action: Action) {
+        if self.over || self.wait > 0 {
+            return;
+        }
+        match action {
+            Action::Left => self.cannon = self.cannon.saturating_sub(1),
+            Action::Right => self.cannon = (self.cannon + 1).min(self.width - 1),
+            Action::Fire if self.shot.is_none() => {
+                self.shot = Some(Point(self.height - 1, self.cannon));
+            }
+            Action::Fire => {}
+        }
+    }
+
+    /// Move everything on a tick: the shot, the bombs, and (every so often,
+    /// more often the fewer are left) the aliens
+    pub fn step(&mut self) -> Step {
+        if self.over {
+            return Step::Over;
+        }
+        if self.wait > 0 {
+            self.wait -= 1;
+            return Step::Moved;
+        }
+        self.ticks += 1;
+        let mut step = self.move_shot();
+        if self.ticks.is_multiple_of(BOMB_TICKS) && self.move_bombs() {
+            return self.lose_cannon();
+        }
+        if self.ticks.is_multiple_of(self.march_ticks()) {
+            if let Some(points) = self.march() {
+                step = Step::Hit(points);
+            }
+            if self.landed() {
+                debug!("aliens landed on wave {}", self.wave);
+                self.over = true;
+                return Step::Over;
+            }
+        }
+        self.drop_bomb();
+        if self.aliens_left() == 0 {
+            self.next_wave();
+            return Step::Cleared;
+        }
+        step
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Cannons left (counting the one in play)
+    pub fn lives(&self) -> u32 {
+        self.lives
+    }
+
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    pub fn aliens_left(&self) -> usize {
+        self.aliens.iter().flatten().filter(|&&alive| alive).count()
+    }
+
+    /// Ticks between the aliens' steps, now
+    pub fn march_ticks(&self) -> u32 {
+        let left = self.aliens_left() as f64 / (ALIEN_ROWS * ALIEN_COLS) as f64;
+        let ticks = FASTEST_MARCH as f64 + (SLOWEST_MARCH - FASTEST_MARCH) as f64 * left;
+        let ticks = ticks * WAVE_SPEEDUP.powi(self.wave as i32 - 1);
+        (ticks.round() as u32).max(FASTEST_MARCH)
+    }
+
+    /// Whether everything's holding still, after the cannon was hit
+    pub fn is_waiting(&self) -> bool {
+        self.wait > 0
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn index(&self, Point(i, j): Point) -> usize {
+        i * self.width + j
+    }
+
+    // four shields spread out evenly, a little above the cannon
+    fn build_shields(&mut self) {
+        let (rows, cols) = SHIELD_SIZE;
+        let top = self.height - 2 - rows;
+        for k in 0..SHIELDS {
+            let middle = self.width * (2 * k + 1) / (2 * SHIELDS);
+            for i in top..top + rows {
+                for j in middle - cols / 2..middle - cols / 2 + cols {
+                    let k = self.index(Point(i, j));
+                    self.shields[k] = SHIELD_HP;
+                }
+            }
+        }
+    }
+
+    // square the alien in [row] and [col] of the formation is on
+    fn alien_at(&self, row: usize, col: usize) -> Point {
+        Point(
+            self.formation.0 + row,
+            self.formation.1 + ALIEN_STRIDE * col,
+        )
+    }
+
+    // (row, column) in the formation of the alien on [p], if there is one
+    fn alien_on(&self, Point(i, j): Point) -> Option<(usize, usize)> {
+        let (row, offset) = (
+            i.checked_sub(self.formation.0)?,
+            j.checked_sub(self.formation.1)?,
+        );
+        let col = offset / ALIEN_STRIDE;
+        let alive = offset % ALIEN_STRIDE == 0
+            && row < ALIEN_ROWS
+            && col < ALIEN_COLS
+            && self.aliens[row][col];
+        alive.then_some((row, col))
+    }
+
+    // take a hit off the shield on [p], if there's one there
+    fn hit_shield(&mut self, p: Point) -> bool {
+        let k = self.index(p);
+        if self.shields[k] == 0 {
+            return false;
+        }
+        self.shields[k] -= 1;
+        true
+    }
+
+    // knock out the alien in [row] and [col] of the formation, for its points
+    fn kill(&mut self, (row, col): (usize, usize)) -> u32 {
+        self.aliens[row][col] = false;
+        let points = POINTS[Self::kind(row)];
+        self.score += points;
+        points
+    }
+
+    // kind of alien on each row of the formation
+    fn kind(row: usize) -> usize {
+        match row {
+            0 => 0,
+            1 | 2 => 1,
+            _ => 2,
+        }
+    }
+
+    // move the shot up a row, into whatever it hits there
+    fn move_shot(&mut self) -> Step {
+        let Some(Point(i, j)) = self.shot else {
+            return Step::Moved;
+        };
+        let Some(i) = i.checked_sub(1) else {
+            self.shot = None;
+            return Step::Moved;
+        };
+        let p = Point(i, j);
+        self.shot = Some(p);
+        if let Some(alien) = self.alien_on(p) {
+            self.shot = None;
+            return Step::Hit(self.kill(alien));
+        }
+        if self.hit_shield(p) {
+            self.shot = None;
+        } else if let Some(k) = self.bombs.iter().position(|&bomb| bomb == p) {
+            self.bombs.swap_remove(k);
+            self.shot = None;
+        }
+        Step::Moved
+    }
+
+    // drop the bombs a row, into whatever they hit there. Returns whether
+    // one hit the cannon
+    fn move_bombs(&mut self) -> bool {
+        let cannon = Point(self.height - 1, self.cannon);
+        let mut hit = false;
+        let mut bombs = std::mem::take(&mut self.bombs);
+        bombs.retain_mut(|bomb| {
+            // (the cannon may have moved under it)
+            if *bomb == cannon {
+                hit = true;
+                return false;
+            }
+            bomb.0 += 1;
+            if bomb.0 >= self.height || self.hit_shield(*bomb) {
+                return false;
+            }
+            if *bomb == cannon {
+                hit = true;
+                return false;
+            }
+            if self.shot == Some(*bomb) {
+                self.shot = None;
+                return false;
+            }
+            true
+        });
+        self.bombs = bombs;
+        hit
+    }
+
+    // take a step sideways, or down and turn round at the edge. Aliens
+    // crush the shields they walk into. Returns the points for an alien
+    // walking into the shot
+    fn march(&mut self) -> Option<u32> {
+        self.pose = !self.pose;
+        let cols: Vec<usize> = (0..ALIEN_COLS)
+            .filter(|&col| (0..ALIEN_ROWS).any(|row| self.aliens[row][col]))
+            .collect();
+        let (first, last) = (*cols.first()?, *cols.last()?);
+        let Point(i, j) = self.formation;
+        let left_edge = j + ALIEN_STRIDE * first;
+        let right_edge = j + ALIEN_STRIDE * last;
+        self.formation = match self.heading_right {
+            true if right_edge + 1 < self.width => Point(i, j + 1),
+            false if left_edge > 0 => Point(i, j - 1),
+            _ => {
+                self.heading_right = !self.heading_right;
+                Point(i + 1, j)
+            }
+        };
+
+        for row in 0..ALIEN_ROWS {
+            for col in 0..ALIEN_COLS {
+                let p = self.alien_at(row, col);
+                if self.aliens[row][col] && p.0 < self.height {
+                    let k = self.index(p);
+                    self.shields[k] = 0;
+                }
+            }
+        }
+        let alien = self.shot.and_then(|p| self.alien_on(p))?;
+        self.shot = None;
+        Some(self.kill(alien))
+    }
+
+    // whether the lowest alien left has come down to the cannon's row
+    fn landed(&self) -> bool {
+        let bottom = (0..ALIEN_ROWS)
+            .rev()
+            .find(|&row| self.aliens[row].contains(&true));
+        bottom.is_some_and(|row| self.formation.0 + row >= self.height - 1)
+    }
+
+    // maybe have the lowest alien in a column drop a bomb
+    fn drop_bomb(&mut self) {
+        let chance = (FIRE_CHANCE + WAVE_FIRE_CHANCE * (self.wave - 1) as f64).min(MAX_FIRE_CHANCE);
+        if self.bombs.len() >= MAX_BOMBS || !self.rng.gen_bool(chance) {
+            return;
+        }
+        let bombers: Vec<Point> = (0..ALIEN_COLS)
+            .filter_map(|col| {
+                let row = (0..ALIEN_ROWS).rev().find(|&row| self.aliens[row][col])?;
+                Some(self.alien_at(row, col))
+            })
+            .collect();
+        if bombers.is_empty() {
+            return;
+        }
+        let Point(i, j) = bombers[self.rng.gen_range(0..bombers.len())];
+        self.bombs.push(Point(i + 1, j));
+    }
+
+    // lose a cannon (and the game with the last one), clearing the bombs
+    // and holding still for a moment before the next
+    fn lose_cannon(&mut self) -> Step {
+        self.lives -= 1;
+        debug!("cannon hit, {} left, score {}", self.lives, self.score);
+        self.bombs.clear();
+        self.shot = None;
+        if self.lives == 0 {
+            self.over = true;
+            return Step::Over;
+        }
+        self.wait = RESPAWN_WAIT;
+        Step::Killed
+    }
+
+    // a new wave, starting lower (and marching faster) than the last, with
+    // the shields rebuilt
+    fn next_wave(&mut self) {
+        self.wave += 1;
+        debug!("wave {}, score {}", self.wave, self.score);
+        let top = (START_ROW + self.wave as usize - 1).min(MAX_START_ROW);
+        self.aliens = [[true; ALIEN_COLS]; ALIEN_ROWS];
+        self.formation = Point(top, 0);
+        self.heading_right = true;
+        self.shot = None;
+        self.bombs.clear();
+        self.shields.fill(0);
+        self.build_shields();
+    }
+}
+
+impl Core for Invaders {
+    type Action = Action;
+    type Outcome = ();
+    type Cell = Square;
+
+    fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    fn cell(&self, p: Point) -> Square {
+        if let Some((row, _)) = self.alien_on(p) {
+            Square::Alien(Self::kind(row), self.pose)
+        } else if self.shot == Some(p) {
+            Square::Shot
+        } else if self.bombs.contains(&p) {
+            Square::Bomb
+        } else if p == Point(self.height - 1, self.cannon) && !self.over {
+            Square::Cannon
+        } else if self.shields[self.index(p)] > 0 {
+            Square::Shield(self.shields[self.index(p)])
+        } else {
+            Square::Empty
+        }
+    }
+
+    fn act(&mut self, action: Action) {
+        Invaders::act(self, action)
+    }
+
+    fn tick(&mut self) {
+        self.step();
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+}
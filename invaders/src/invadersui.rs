@@ -0,0 +1,110 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::invaders::Action;
+
+/// Help lines under the field, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("invaders.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvadersUIAction {
+    Wait,
+    Cannon(Action),
+    Pause,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, InvadersUIAction); 10] = [
+    (
+        KeyBinding::key(KeyCode::Left),
+        InvadersUIAction::Cannon(Action::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        InvadersUIAction::Cannon(Action::Right),
+    ),
+    (
+        KeyBinding::char('a'),
+        InvadersUIAction::Cannon(Action::Left),
+    ),
+    (
+        KeyBinding::char('d'),
+        InvadersUIAction::Cannon(Action::Right),
+    ),
+    (
+        KeyBinding::char(' '),
+        InvadersUIAction::Cannon(Action::Fire),
+    ),
+    (
+        KeyBinding::key(KeyCode::Up),
+        InvadersUIAction::Cannon(Action::Fire),
+    ),
+    (
+        KeyBinding::char('w'),
+        InvadersUIAction::Cannon(Action::Fire),
+    ),
+    (KeyBinding::char('p'), InvadersUIAction::Pause),
+    (KeyBinding::char('q'), InvadersUIAction::Quit),
+    (KeyBinding::ctrl('c'), InvadersUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, InvadersUIAction); 5] = [
+    ("left", InvadersUIAction::Cannon(Action::Left)),
+    ("right", InvadersUIAction::Cannon(Action::Right)),
+    ("fire", InvadersUIAction::Cannon(Action::Fire)),
+    ("pause", InvadersUIAction::Pause),
+    ("quit", InvadersUIAction::Quit),
+];
+
+pub struct InvadersUI {
+    pub paused: bool,
+    pub bindings: Bindings<InvadersUIAction>,
+}
+
+impl InvadersUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Starts out paused, until the cannon moves or fires
+    pub fn new() -> Self {
+        Self {
+            paused: true,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> InvadersUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(InvadersUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> InvadersUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => InvadersUIAction::Wait,
+        }
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<InvadersUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Space Invaders: the game core (waves of aliens marching down on a cannon,
+//! the shots and bombs between them, and the shields in the way), plus the
+//! crossterm frontend for playing it in a terminal
+
+mod invaders;
+pub use invaders::{Action, Invaders, Square, Step, SHIELD_HP};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod invadersui;
+#[cfg(feature = "terminal")]
+pub use app::{run, InvadersApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,530 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::Event;
+use crossterm::style::{ContentStyle, StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Game, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::KlondikeConfig;
+use crate::klondike::{Card, Klondike, Move, MoveResult, Pile, Suit, COLUMNS};
+use crate::klondikeui::{self, KlondikeUI, KlondikeUIAction};
+
+// cards turned over at a time when nothing else sets it
+const DEFAULT_DRAW: u8 = 1;
+
+// fewest and most cards that can be turned over at a time
+const MIN_DRAW: u8 = 1;
+const MAX_DRAW: u8 = 3;
+
+// terminal columns each card takes up, and each pile (the card and a gap)
+const CARD_COLS: usize = 3;
+const SLOT_COLS: usize = 6;
+
+// columns between the cards fanned out on the waste, drawing three at a time
+const FAN_COLS: usize = 4;
+
+// line the columns start on, under the stock, waste and foundations
+const TABLEAU_ROW: usize = 2;
+
+// lines of text under the cards (gap, moves and time, message)
+const STATUS_ROWS: usize = 3;
+
+// terminal (row, col) of the top-left card (inside a border with ratatui)
+#[cfg(not(feature = "ratatui"))]
+const GRID_ORIGIN: (u16, u16) = (0, 0);
+#[cfg(feature = "ratatui")]
+const GRID_ORIGIN: (u16, u16) = (1, 1);
+
+// how often the clock is checked (it only shows whole seconds)
+const CLOCK_TICK: Duration = Duration::from_millis(100);
+
+// time between the cards going up to the foundations once the game plays
+// itself out
+const FINISH_TICK: Duration = Duration::from_millis(60);
+
+/// Klondike app (the game, the cards picked up, the clock, and UI state)
+pub struct KlondikeApp {
+    game: Klondike,
+    ui: KlondikeUI,
+    held: Option<(Pile, usize)>, // the pile cards were picked up from, and how many
+    ascii: bool,                 // suits as letters
+    rng: StdRng,                 // for shuffling
+    started: Option<Instant>,    // at the first move
+    elapsed: Duration,           // frozen once the game's won
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome,
+    over: bool,
+}
+
+impl KlondikeApp {
+    /// Games turning over [draw] cards at a time, with decks shuffled with
+    /// [seed], and suits drawn as letters if [ascii]
+    pub fn new(draw: u8, ascii: bool, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self {
+            game: Klondike::new(draw as usize, rng.gen()),
+            ui: KlondikeUI::new(),
+            held: None,
+            ascii,
+            rng,
+            started: None,
+            elapsed: Duration::ZERO,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_game(&mut self) {
+        self.game = Klondike::new(self.game.draw_count(), self.rng.gen());
+        self.ui.set_cursor(Pile::Stock, 1);
+        self.held = None;
+        self.started = None;
+        self.elapsed = Duration::ZERO;
+        self.message = "".to_string().reset();
+    }
+
+    // pick up the top [count] cards of [pile], or put down the ones picked
+    // up onto it (drawing, if it's the stock)
+    fn pick_or_drop(&mut self, pile: Pile, count: usize) {
+        match self.held {
+            _ if pile == Pile::Stock => self.play(Move::Draw),
+            Some((from, _)) if from == pile => self.held = None,
+            Some((from, held)) => self.play(Move::Shift {
+                from,
+                count: held,
+                to: pile,
+            }),
+            None if count == 0 || count > self.game.face_up(pile) => {
+                bell::ring("invalid move");
+            }
+            None => self.held = Some((pile, count)),
+        }
+    }
+
+    // send the top card of [pile] (or the one picked up) up to its foundation
+    fn send_up(&mut self, pile: Pile) {
+        let pile = match self.held {
+            Some((from, 1)) => from,
+            _ => pile,
+        };
+        match self.game.to_foundation(pile) {
+            Some(mv) => self.play(mv),
+            None => bell::ring("invalid move"),
+        }
+    }
+
+    // click on a card (or a pile): pick it up or put cards down on it, or
+    // with the right button, send it up to its foundation
+    fn click(&mut self, row: u16, col: u16, right: bool) {
+        let (Some(row), Some(col)) = (
+            row.checked_sub(GRID_ORIGIN.0),
+            col.checked_sub(GRID_ORIGIN.1),
+        ) else {
+            return;
+        };
+        let Some((pile, count)) = self.pile_at(row as usize, col as usize) else {
+            return;
+        };
+        let face_up = self.game.face_up(pile);
+        self.ui.set_cursor(pile, count.min(face_up).max(1));
+        match right {
+            true => self.send_up(pile),
+            false => self.pick_or_drop(pile, count),
+        }
+    }
+
+    // pile drawn at a position on the board, and the cards from the top of
+    // it down to the one there (0 if it's face down)
+    fn pile_at(&self, row: usize, col: usize) -> Option<(Pile, usize)> {
+        let k = col / SLOT_COLS;
+        if row == 0 && (SLOT_COLS..3 * SLOT_COLS).contains(&col) {
+            // (the waste, fanned out or not)
+            return Some((Pile::Waste, 1));
+        }
+        if k >= COLUMNS || col % SLOT_COLS >= CARD_COLS {
+            return None;
+        }
+        if row == 0 {
+            return Some((klondikeui::top_pile(k)?, 1));
+        }
+        let i = row.checked_sub(TABLEAU_ROW)?;
+        let pile = Pile::Column(k);
+        let len = self.game.pile(pile).len();
+        let count = match i < self.game.face_down(k) {
+            true => 0,
+            false => len.saturating_sub(i).max(1),
+        };
+        Some((pile, count))
+    }
+
+    fn play(&mut self, mv: Move) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+        debug!("{mv:?}");
+        let res = self.game.play(mv);
+        debug!("{res:?}");
+        self.handle_res(res);
+    }
+
+    fn undo(&mut self) {
+        if self.game.is_won() || !self.game.undo() {
+            bell::ring("invalid move");
+            return;
+        }
+        self.held = None;
+        self.ui.fit_cursor(&self.game);
+        self.message = "".to_string().reset();
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        match res {
+            MoveResult::Moved => {
+                self.held = None;
+                self.ui.fit_cursor(&self.game);
+                self.message = match self.game.can_finish() {
+                    true => locale::text("klondike.finishing")
+                        .to_string()
+                        .with(theme::color(Role::Accent)),
+                    false => "".to_string().reset(),
+                };
+            }
+            MoveResult::Won(moves) => {
+                bell::ring("win");
+                self.held = None;
+                self.ui.fit_cursor(&self.game);
+                self.outcome = Outcome::Won;
+                self.elapsed = self.started.map(|t| t.elapsed()).unwrap_or_default();
+                self.message = format!(
+                    "{} ({moves} {}, {}s) {}",
+                    locale::text("klondike.win"),
+                    locale::text("klondike.moves"),
+                    self.elapsed.as_secs(),
+                    locale::text("klondike.again")
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success));
+            }
+            MoveResult::Invalid | MoveResult::GameOver => bell::ring("invalid move"),
+        }
+    }
+
+    /// Lines of the board: the stock, waste and foundations, a gap, and the
+    /// columns, each line a list of (styled) pieces
+    fn board_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let mut lines = vec![self.top_line(), vec![]];
+        let depth = (0..COLUMNS)
+            .map(|k| self.game.pile(Pile::Column(k)).len())
+            .max()
+            .unwrap_or_default()
+            .max(1);
+        for i in 0..depth {
+            let mut line = Vec::new();
+            for k in 0..COLUMNS {
+                let pile = Pile::Column(k);
+                let cards = self.game.pile(pile);
+                let piece = match cards.get(i) {
+                    Some(_) if i < self.game.face_down(k) => self.face_down(),
+                    Some(&card) => self.card(card, pile, cards.len() - i),
+                    None if i == 0 => self.empty("[ ]", pile),
+                    None => " ".repeat(CARD_COLS).reset(),
+                };
+                line.push(piece);
+                line.push(" ".repeat(SLOT_COLS - CARD_COLS).reset());
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    // the stock, the waste (the last few cards drawn fanned out) and the
+    // foundations
+    fn top_line(&self) -> Vec<StyledContent<String>> {
+        let gap = || " ".repeat(SLOT_COLS - CARD_COLS).reset();
+        let mut line = vec![
+            match self.game.pile(Pile::Stock).is_empty() {
+                true => self.empty("[ ]", Pile::Stock),
+                false => self.face_down(),
+            },
+            gap(),
+        ];
+
+        let waste = self.game.pile(Pile::Waste);
+        let fanned = waste.len().min(self.game.draw_count());
+        let mut cols = 0;
+        for (i, &card) in waste[waste.len() - fanned..].iter().enumerate() {
+            let from_top = fanned - i;
+            line.push(match from_top {
+                1 => self.card(card, Pile::Waste, 1),
+                _ => card_style(card).apply(self.card_text(card)),
+            });
+            line.push(" ".repeat(FAN_COLS - CARD_COLS).reset());
+            cols += FAN_COLS;
+        }
+        if fanned == 0 {
+            line.push(self.empty("[ ]", Pile::Waste));
+            cols += CARD_COLS;
+        }
+        line.push(" ".repeat(2 * SLOT_COLS - cols).reset());
+
+        for (f, &suit) in Suit::ALL.iter().enumerate() {
+            let pile = Pile::Foundation(f);
+            line.push(match self.game.pile(pile).last() {
+                Some(&card) => self.card(card, pile, 1),
+                None => self.empty(&format!("[{}]", self.suit_text(suit)), pile),
+            });
+            line.push(gap());
+        }
+        line
+    }
+
+    // a face up card [from_top] cards down from the top of [pile], marked
+    // if it's under the cursor or picked up
+    fn card(&self, card: Card, pile: Pile, from_top: usize) -> StyledContent<String> {
+        let mut style = card_style(card);
+        if self
+            .held
+            .is_some_and(|(from, count)| from == pile && from_top <= count)
+        {
+            style = style.bold().underlined().with(theme::color(Role::Accent));
+        }
+        if self.is_under_cursor(pile, from_top) {
+            style = style.reverse();
+        }
+        style.apply(self.card_text(card))
+    }
+
+    // whether a card is among the ones the cursor takes in
+    fn is_under_cursor(&self, pile: Pile, from_top: usize) -> bool {
+        let (cursor, depth) = self.ui.get_cursor();
+        cursor == pile && from_top <= depth
+    }
+
+    fn face_down(&self) -> StyledContent<String> {
+        let back = match self.ascii {
+            true => "#",
+            false => "▒",
+        };
+        back.repeat(CARD_COLS).with(theme::color(Role::Hidden))
+    }
+
+    // a pile with no cards in it
+    fn empty(&self, text: &str, pile: Pile) -> StyledContent<String> {
+        let style = ContentStyle::new().with(theme::color(Role::Muted));
+        let style = match self.ui.get_cursor().0 == pile {
+            true => style.reverse(),
+            false => style,
+        };
+        style.apply(text.to_string())
+    }
+
+    fn card_text(&self, card: Card) -> String {
+        let rank = match card.rank {
+            1 => "A".to_string(),
+            11 => "J".to_string(),
+            12 => "Q".to_string(),
+            13 => "K".to_string(),
+            rank => rank.to_string(),
+        };
+        format!("{rank:>2}{}", self.suit_text(card.suit))
+    }
+
+    fn suit_text(&self, suit: Suit) -> &'static str {
+        match (suit, self.ascii) {
+            (Suit::Spades, false) => "♠",
+            (Suit::Hearts, false) => "♥",
+            (Suit::Diamonds, false) => "♦",
+            (Suit::Clubs, false) => "♣",
+            (Suit::Spades, true) => "S",
+            (Suit::Hearts, true) => "H",
+            (Suit::Diamonds, true) => "D",
+            (Suit::Clubs, true) => "C",
+        }
+    }
+
+    // moves made, time taken, and cards drawn at a time
+    fn counters(&self) -> String {
+        format!(
+            "{} {}   {} {}s   {} {}",
+            locale::text("klondike.moves_made"),
+            self.game.moves(),
+            locale::text("klondike.time"),
+            self.elapsed.as_secs(),
+            locale::text("klondike.drawing"),
+            self.game.draw_count()
+        )
+    }
+}
+
+// red suits in one color, black in another
+fn card_style(card: Card) -> ContentStyle {
+    let role = match card.suit.is_red() {
+        true => Role::Danger,
+        false => Role::Text,
+    };
+    ContentStyle::new().bold().with(theme::color(role))
+}
+
+// Pretty-print
+impl fmt::Display for KlondikeApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.board_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.counters())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", klondikeui::help_text())
+    }
+}
+
+impl Game for KlondikeApp {
+    fn name(&self) -> &'static str {
+        "klondike"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != KlondikeUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            KlondikeUIAction::Quit => self.over = true,
+            KlondikeUIAction::NewGame => self.new_game(),
+            KlondikeUIAction::Move(dir) => {
+                if let Err(e) = self.ui.move_cursor(dir, &self.game) {
+                    bell::ring("edge of the board");
+                    debug!("{e}");
+                }
+            }
+            KlondikeUIAction::Select => {
+                let (pile, count) = self.ui.get_cursor();
+                self.pick_or_drop(pile, count);
+            }
+            KlondikeUIAction::Draw => self.play(Move::Draw),
+            KlondikeUIAction::Foundation => self.send_up(self.ui.get_cursor().0),
+            KlondikeUIAction::Cancel => self.held = None,
+            KlondikeUIAction::Undo => self.undo(),
+            KlondikeUIAction::Click { row, col, right } => self.click(row, col, right),
+            KlondikeUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // keep the clock running, and once every card's face up, send them up
+    // to the foundations one by one
+    fn tick(&mut self) {
+        if self.game.is_won() {
+            return;
+        }
+        if self.game.can_finish() {
+            if let Some(mv) = self.game.finishing_move() {
+                self.play(mv);
+                self.redraw.set(true);
+            }
+        }
+        let Some(started) = self.started else {
+            return;
+        };
+        let elapsed = started.elapsed();
+        if elapsed.as_secs() != self.elapsed.as_secs() {
+            self.redraw.set(true);
+        }
+        if !self.game.is_won() {
+            self.elapsed = elapsed;
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        match self.game.can_finish() {
+            true => Some(FINISH_TICK),
+            false => Some(CLOCK_TICK),
+        }
+    }
+
+    // the board as it is now, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = klondikeui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max((COLUMNS * SLOT_COLS) as u16),
+            (self.board_lines().len() + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Klondike solitaire, with the keyboard or the mouse
+pub fn run(args: Args) -> Result<(), String> {
+    let config: KlondikeConfig = common::cli::setup(&args.common, "klondike")?;
+
+    let draw = args.draw.or(config.draw).unwrap_or(DEFAULT_DRAW);
+    if !(MIN_DRAW..=MAX_DRAW).contains(&draw) {
+        return Err(format!(
+            "bad [klondike] config: draw {draw} (goes from {MIN_DRAW} to {MAX_DRAW})"
+        ));
+    }
+    let ascii = args.ascii || config.ascii || !style::utf8_locale();
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("klondike seed {seed}, draw {draw}");
+
+    let mut app = KlondikeApp::new(draw, ascii, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &klondikeui::ACTIONS)
+        .map_err(|e| format!("bad [klondike.keys] config: {e}"))?;
+
+    let _guard =
+        TerminalGuard::with_mouse().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::KlondikeApp;
+use crate::klondikeui;
+
+impl TuiGame for KlondikeApp {
+    fn draw(&self, frame: &mut Frame) {
+        let board = Board::new(
+            self.board_lines()
+                .iter()
+                .map(|line| line.iter().map(tui::span).collect::<Line>())
+                .collect(),
+        );
+        let status = StatusBar::new(klondikeui::help_text())
+            .line(self.counters())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
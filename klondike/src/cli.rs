@@ -0,0 +1,36 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Klondike solitaire
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on invalid moves, wins and achievements")
+}))]
+pub struct Args {
+    /// Cards turned over from the stock at a time, from 1 to 3
+    /// [default: 1, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=3))]
+    pub draw: Option<u8>,
+
+    /// Draw suits as letters (S, H, D, C) instead of symbols (also done
+    /// when the locale isn't UTF-8)
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Random seed for shuffling the deck (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [klondike] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KlondikeConfig {
+    /// cards turned over from the stock at a time, from 1 to 3
+    pub draw: Option<u8>,
+    /// draw suits as letters instead of symbols
+    pub ascii: bool,
+    /// keys for each action, e.g. draw = "s"
+    pub keys: KeyMap,
+}
@@ -0,0 +1,337 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// Columns of cards dealt out
+pub const COLUMNS: usize = 7;
+
+/// Foundations the cards are built up on, one per suit
+pub const FOUNDATIONS: usize = 4;
+
+/// Highest rank (the King; the Ace is 1)
+pub const KING: u8 = 13;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suit {
+    Spades,
+    Hearts,
+    Diamonds,
+    Clubs,
+}
+
+impl Suit {
+    /// In the order of their foundations
+    pub const ALL: [Suit; FOUNDATIONS] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+    pub fn is_red(self) -> bool {
+        matches!(self, Suit::Hearts | Suit::Diamonds)
+    }
+
+    // which foundation the suit's built up on
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Card {
+    /// from 1 (Ace) to [KING]
+    pub rank: u8,
+    pub suit: Suit,
+}
+
+impl Card {
+    // whether [self] can go on [other] in a column: one lower, the other
+    // color
+    fn fits_on(self, other: Card) -> bool {
+        self.rank + 1 == other.rank && self.suit.is_red() != other.suit.is_red()
+    }
+}
+
+/// A place cards are in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pile {
+    /// face down, to draw from
+    Stock,
+    /// drawn, the last one on top
+    Waste,
+    Foundation(usize),
+    Column(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    /// turn cards over from the stock onto the waste (or, once the stock's
+    /// run out, the waste back over onto the stock)
+    Draw,
+    /// move the top [count] cards of one pile onto another
+    Shift { from: Pile, count: usize, to: Pile },
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    Moved,
+    /// every card's on its foundation, after this many moves
+    Won(usize),
+    /// against the rules
+    Invalid,
+    /// the game's already won
+    GameOver,
+}
+
+// where every card is
+#[derive(Debug, Clone, PartialEq)]
+struct Layout {
+    stock: Vec<Card>, // the top card last
+    waste: Vec<Card>,
+    foundations: [Vec<Card>; FOUNDATIONS],
+    columns: [Vec<Card>; COLUMNS],
+    face_down: [usize; COLUMNS], // cards at the bottom of each column still face down
+}
+
+/// Klondike solitaire: 52 cards, 28 of them dealt into [COLUMNS] columns
+/// (the top card of each face up), to be built up on [FOUNDATIONS] by suit
+/// from Ace to King, drawing from the rest one or three at a time
+#[derive(Debug, Clone)]
+pub struct Klondike {
+    layout: Layout,
+    draw: usize,
+    moves: usize,
+    history: Vec<Layout>, // before every move, for undoing
+}
+
+impl Klondike {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Deal a shuffled deck (the same seed always deals the same cards),
+    /// turning over [draw] cards at a time from the stock
+    pub fn new(draw: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut deck: Vec<Card> = Suit::ALL
+            .iter()
+            .flat_map(|&suit| (1..=KING).map(move |rank| Card { rank, suit }))
+            .collect();
+        deck.shuffle(&mut rng);
+        let columns = std::array::from_fn(|k| deck.split_off(deck.len() - (k + 1)));
+        debug!("dealt {columns:?}, stock {deck:?}");
+        Self {
+            layout: Layout {
+                stock: deck,
+                waste: Vec::new(),
+                foundations: Default::default(),
+                columns,
+                face_down: std::array::from_fn(|k| k),
+            },
+            draw,
+            moves: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn play(&mut self, mv: Move) -> MoveResult {
+        if self.is_won() {
+            return MoveResult::GameOver;
+        }
+        if !self.is_legal(mv) {
+            return MoveResult::Invalid;
+        }
+        self.history.push(self.layout.clone());
+        let layout = &mut self.layout;
+        match mv {
+            Move::Draw if layout.stock.is_empty() => {
+                layout.stock = layout.waste.drain(..).rev().collect();
+            }
+            Move::Draw => {
+                // (one at a time, so the last turned over ends up on top)
+                let drawn = layout.stock.len().saturating_sub(self.draw);
+                let cards = layout.stock.split_off(drawn);
+                layout.waste.extend(cards.into_iter().rev());
+            }
+            Move::Shift { from, count, to } => {
+                let from_pile = self.pile_mut(from);
+                let cards = from_pile.split_off(from_pile.len() - count);
+                self.pile_mut(to).extend(cards);
+                // (turning over the card underneath)
+                if let Pile::Column(k) = from {
+                    let layout = &mut self.layout;
+                    let top = layout.columns[k].len().saturating_sub(1);
+                    layout.face_down[k] = layout.face_down[k].min(top);
+                }
+            }
+        }
+        self.moves += 1;
+        match self.is_won() {
+            true => MoveResult::Won(self.moves),
+            false => MoveResult::Moved,
+        }
+    }
+
+    /// Whether [mv] is allowed: moving cards face up on top of a pile, one
+    /// at a time unless it's a column onto another, onto a foundation of
+    /// their suit one rank up from the top card (an Ace on an empty one),
+    /// or onto a column one rank down from its top card in the other color
+    /// (a King on an empty one)
+    pub fn is_legal(&self, mv: Move) -> bool {
+        let Move::Shift { from, count, to } = mv else {
+            return !(self.layout.stock.is_empty() && self.layout.waste.is_empty());
+        };
+        if count == 0 || count > self.face_up(from) || from == to {
+            return false;
+        }
+        let pile = self.pile(from);
+        let bottom = pile[pile.len() - count];
+        match to {
+            Pile::Stock | Pile::Waste => false,
+            Pile::Foundation(f) => {
+                count == 1
+                    && bottom.suit.index() == f
+                    && bottom.rank as usize == self.layout.foundations[f].len() + 1
+            }
+            Pile::Column(k) => {
+                (count == 1 || matches!(from, Pile::Column(_)))
+                    && match self.layout.columns[k].last() {
+                        Some(&top) => bottom.fits_on(top),
+                        None => bottom.rank == KING,
+                    }
+            }
+        }
+    }
+
+    /// Put everything back the way it was before the last move (counting
+    /// as a move itself), if there was one
+    pub fn undo(&mut self) -> bool {
+        let Some(layout) = self.history.pop() else {
+            return false;
+        };
+        self.layout = layout;
+        self.moves += 1;
+        true
+    }
+
+    /// Move taking the top card of [from] up to its foundation, if it can go
+    pub fn to_foundation(&self, from: Pile) -> Option<Move> {
+        let card = *self.pile(from).last()?;
+        let mv = Move::Shift {
+            from,
+            count: 1,
+            to: Pile::Foundation(card.suit.index()),
+        };
+        self.is_legal(mv).then_some(mv)
+    }
+
+    /// Whether the rest of the game plays itself: every card is face up in
+    /// the columns or on the foundations, so they can all go up in order
+    pub fn can_finish(&self) -> bool {
+        let layout = &self.layout;
+        !self.is_won()
+            && layout.stock.is_empty()
+            && layout.waste.is_empty()
+            && layout.face_down.iter().all(|&n| n == 0)
+    }
+
+    /// Next move of finishing the game off: the lowest card that can go up
+    /// to its foundation
+    pub fn finishing_move(&self) -> Option<Move> {
+        let piles = (0..COLUMNS).map(Pile::Column).chain([Pile::Waste]);
+        piles
+            .filter_map(|pile| Some((self.pile(pile).last()?.rank, self.to_foundation(pile)?)))
+            .min_by_key(|&(rank, _)| rank)
+            .map(|(_, mv)| mv)
+    }
+
+    /// Cards in [pile], from the bottom up
+    pub fn pile(&self, pile: Pile) -> &[Card] {
+        let layout = &self.layout;
+        match pile {
+            Pile::Stock => &layout.stock,
+            Pile::Waste => &layout.waste,
+            Pile::Foundation(f) => &layout.foundations[f],
+            Pile::Column(k) => &layout.columns[k],
+        }
+    }
+
+    /// Cards on top of [pile] that are face up, and can be picked up
+    /// (the top one, except in a column, where it's all the face up ones)
+    pub fn face_up(&self, pile: Pile) -> usize {
+        let len = self.pile(pile).len();
+        match pile {
+            Pile::Stock => 0,
+            Pile::Column(k) => len - self.layout.face_down[k],
+            _ => len.min(1),
+        }
+    }
+
+    /// Cards at the bottom of column [k] still face down
+    pub fn face_down(&self, k: usize) -> usize {
+        self.layout.face_down[k]
+    }
+
+    /// Cards turned over from the stock at a time
+    pub fn draw_count(&self) -> usize {
+        self.draw
+    }
+
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.layout
+            .foundations
+            .iter()
+            .all(|foundation| foundation.len() == KING as usize)
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn pile_mut(&mut self, pile: Pile) -> &mut Vec<Card> {
+        let layout = &mut self.layout;
+        match pile {
+            Pile::Stock => &mut layout.stock,
+            Pile::Waste => &mut layout.waste,
+            Pile::Foundation(f) => &mut layout.foundations[f],
+            Pile::Column(k) => &mut layout.columns[k],
+        }
+    }
+}
+
+impl Core for Klondike {
+    type Action = Move;
+    type Outcome = MoveResult;
+    /// The card at that depth in that column, if it's there and face up
+    type Cell = Option<Card>;
+
+    fn size(&self) -> (usize, usize) {
+        let deepest = self.layout.columns.iter().map(Vec::len).max();
+        (deepest.unwrap_or_default(), COLUMNS)
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Option<Card> {
+        let column = &self.layout.columns[j];
+        column
+            .get(i)
+            .copied()
+            .filter(|_| i >= self.layout.face_down[j])
+    }
+
+    fn act(&mut self, mv: Move) -> MoveResult {
+        self.play(mv)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_won()
+    }
+}
@@ -0,0 +1,206 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::klondike::{Klondike, Pile, COLUMNS, FOUNDATIONS};
+
+/// Help lines under the cards, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("klondike.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum KlondikeUIAction {
+    Wait,
+    Move(MoveDirection),
+    /// pick up the cards at the cursor, or put down the ones picked up
+    Select,
+    /// turn over cards from the stock
+    Draw,
+    /// send the card at the cursor up to its foundation
+    Foundation,
+    /// put down the cards picked up where they were
+    Cancel,
+    Undo,
+    /// pick up or put down the cards at a terminal position (or with the
+    /// right button, send the card there up to its foundation)
+    Click {
+        row: u16,
+        col: u16,
+        right: bool,
+    },
+    NewGame,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, KlondikeUIAction); 14] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        KlondikeUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        KlondikeUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        KlondikeUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        KlondikeUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), KlondikeUIAction::Select),
+    (KeyBinding::key(KeyCode::Enter), KlondikeUIAction::Select),
+    (KeyBinding::char('d'), KlondikeUIAction::Draw),
+    (KeyBinding::key(KeyCode::Tab), KlondikeUIAction::Draw),
+    (KeyBinding::char('f'), KlondikeUIAction::Foundation),
+    (KeyBinding::key(KeyCode::Esc), KlondikeUIAction::Cancel),
+    (KeyBinding::char('u'), KlondikeUIAction::Undo),
+    (KeyBinding::char('n'), KlondikeUIAction::NewGame),
+    (KeyBinding::char('q'), KlondikeUIAction::Quit),
+    (KeyBinding::ctrl('c'), KlondikeUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, KlondikeUIAction); 11] = [
+    ("up", KlondikeUIAction::Move(MoveDirection::Up)),
+    ("down", KlondikeUIAction::Move(MoveDirection::Down)),
+    ("left", KlondikeUIAction::Move(MoveDirection::Left)),
+    ("right", KlondikeUIAction::Move(MoveDirection::Right)),
+    ("select", KlondikeUIAction::Select),
+    ("draw", KlondikeUIAction::Draw),
+    ("foundation", KlondikeUIAction::Foundation),
+    ("cancel", KlondikeUIAction::Cancel),
+    ("undo", KlondikeUIAction::Undo),
+    ("new_game", KlondikeUIAction::NewGame),
+    ("quit", KlondikeUIAction::Quit),
+];
+
+/// Slot in the top row each pile there is in (the one after the waste is
+/// left empty, so the foundations line up over the last four columns)
+pub fn slot(pile: Pile) -> usize {
+    match pile {
+        Pile::Stock => 0,
+        Pile::Waste => 1,
+        Pile::Foundation(f) => COLUMNS - FOUNDATIONS + f,
+        Pile::Column(k) => k,
+    }
+}
+
+/// Pile in the top row at slot [k], if there is one
+pub fn top_pile(k: usize) -> Option<Pile> {
+    match k {
+        0 => Some(Pile::Stock),
+        1 => Some(Pile::Waste),
+        k if k >= COLUMNS - FOUNDATIONS => Some(Pile::Foundation(k + FOUNDATIONS - COLUMNS)),
+        _ => None,
+    }
+}
+
+pub struct KlondikeUI {
+    cursor: Pile,
+    depth: usize, // cards down from the top of the pile the cursor takes in
+    pub bindings: Bindings<KlondikeUIAction>,
+}
+
+impl KlondikeUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cursor starting on the stock
+    pub fn new() -> Self {
+        Self {
+            cursor: Pile::Stock,
+            depth: 1,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> KlondikeUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(KlondikeUIAction::Wait)
+    }
+
+    // left button picks up and puts down, right button sends a card up
+    fn match_mouse_to_action(mouse_event: MouseEvent) -> KlondikeUIAction {
+        let right = match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => false,
+            MouseEventKind::Down(MouseButton::Right) => true,
+            _ => return KlondikeUIAction::Wait,
+        };
+        KlondikeUIAction::Click {
+            row: mouse_event.row,
+            col: mouse_event.column,
+            right,
+        }
+    }
+
+    pub fn match_event_to_action(&self, event: Event) -> KlondikeUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            Event::Mouse(mouse_event) => Self::match_mouse_to_action(mouse_event),
+            _ => KlondikeUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a pile across, or (in a column) a card up or down
+    /// the cards face up, and off the top of them to the pile above.
+    /// Fails at the edges
+    pub fn move_cursor(&mut self, dir: MoveDirection, game: &Klondike) -> Result<(), String> {
+        let k = slot(self.cursor);
+        let in_column = matches!(self.cursor, Pile::Column(_));
+        let (cursor, depth) = match (dir, in_column) {
+            (MoveDirection::Up, true) if self.depth < game.face_up(self.cursor) => {
+                (self.cursor, self.depth + 1)
+            }
+            (MoveDirection::Up, true) => (top_pile(k).unwrap_or(Pile::Waste), 1),
+            (MoveDirection::Down, true) if self.depth > 1 => (self.cursor, self.depth - 1),
+            (MoveDirection::Down, false) => (Pile::Column(k), 1),
+            (MoveDirection::Left, true) if k > 0 => (Pile::Column(k - 1), 1),
+            (MoveDirection::Right, true) if k + 1 < COLUMNS => (Pile::Column(k + 1), 1),
+            (MoveDirection::Left, false) => {
+                let left = (0..k).rev().find_map(top_pile);
+                (left.ok_or("already at the edge")?, 1)
+            }
+            (MoveDirection::Right, false) => {
+                let right = (k + 1..COLUMNS).find_map(top_pile);
+                (right.ok_or("already at the edge")?, 1)
+            }
+            _ => return Err("already at the edge".into()),
+        };
+        self.cursor = cursor;
+        self.depth = depth;
+        Ok(())
+    }
+
+    /// Put the cursor on [pile], taking in [depth] cards
+    pub fn set_cursor(&mut self, pile: Pile, depth: usize) {
+        self.cursor = pile;
+        self.depth = depth;
+    }
+
+    /// Keep the cursor on cards that are there and face up, after a move
+    pub fn fit_cursor(&mut self, game: &Klondike) {
+        self.depth = self.depth.min(game.face_up(self.cursor)).max(1);
+    }
+
+    pub fn get_cursor(&self) -> (Pile, usize) {
+        (self.cursor, self.depth)
+    }
+}
@@ -0,0 +1,20 @@
+//! Klondike solitaire: the game core (dealing, the rules for building on
+//! the columns and foundations, drawing one or three at a time, undo, and
+//! playing out the end once every card's face up), plus the crossterm
+//! frontend for playing it in a terminal with the keyboard or the mouse
+
+mod klondike;
+pub use klondike::{Card, Klondike, Move, MoveResult, Pile, Suit, COLUMNS, FOUNDATIONS, KING};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod klondikeui;
+#[cfg(feature = "terminal")]
+pub use app::{run, KlondikeApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
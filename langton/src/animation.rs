@@ -0,0 +1,115 @@
+use std::borrow::Cow;
+use std::collections::hash_map::{Entry, HashMap};
+use std::time::Duration;
+
+use gif::{Encoder, Frame, Repeat};
+
+use crate::image::{cell_rgb, CELL_PIXELS};
+use crate::palette::Palette;
+use crate::turmite::Turmite;
+
+type Rgb = (u8, u8, u8);
+
+// one frame's worth of cells: the region it covers (see [Turmite::extent]),
+// as (top, left, height, width), and the color of each cell in it
+struct Snapshot {
+    region: (i64, i64, usize, usize),
+    cells: Vec<Rgb>,
+}
+
+impl Snapshot {
+    fn take(langton: &dyn Turmite, palette: &Palette) -> Self {
+        let region @ (top, left, height, width) = langton.extent();
+        let cells = (top..top + height as i64)
+            .flat_map(|i| (left..left + width as i64).map(move |j| (i, j)))
+            .map(|pos| cell_rgb(langton, palette, pos))
+            .collect();
+        Self { region, cells }
+    }
+}
+
+/// Move the ant until it's taken [steps] steps (or halted), taking a frame
+/// every [every]th step (and of the first and last), and encode them as an
+/// animated GIF that shows each frame for [delay] and loops forever. Every
+/// frame covers all the ground the ant gets to, so the picture stays still
+pub fn record(
+    langton: &mut dyn Turmite,
+    steps: u32,
+    every: u32,
+    palette: &Palette,
+    delay: Duration,
+) -> Result<Vec<u8>, String> {
+    let mut snapshots = vec![Snapshot::take(langton, palette)];
+    while langton.nstep() < steps && !langton.halted() {
+        langton.tick();
+        let last = langton.nstep() == steps || langton.halted();
+        if langton.nstep().is_multiple_of(every) || last {
+            snapshots.push(Snapshot::take(langton, palette));
+        }
+    }
+    encode(&snapshots, palette.rgb(0), delay)
+}
+
+fn encode(snapshots: &[Snapshot], background: Rgb, delay: Duration) -> Result<Vec<u8>, String> {
+    // the region every snapshot fits in
+    let regions = || snapshots.iter().map(|s| s.region);
+    let top = regions().map(|(t, _, _, _)| t).min().unwrap_or(0);
+    let left = regions().map(|(_, l, _, _)| l).min().unwrap_or(0);
+    let bottom = regions()
+        .map(|(t, _, h, _)| t + h as i64)
+        .max()
+        .unwrap_or(0);
+    let right = regions()
+        .map(|(_, l, _, w)| l + w as i64)
+        .max()
+        .unwrap_or(0);
+    let (height, width) = ((bottom - top) as usize, (right - left) as usize);
+    let (img_h, img_w) = (height * CELL_PIXELS, width * CELL_PIXELS);
+    let (Ok(gif_h), Ok(gif_w)) = (u16::try_from(img_h), u16::try_from(img_w)) else {
+        return Err(format!(
+            "the ant covered {width}x{height} cells, too many for a GIF"
+        ));
+    };
+    let delay = (delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+
+    // a palette of every color used (few, since the colors of states cycle)
+    let mut colors = vec![background];
+    let mut index = HashMap::from([(background, 0)]);
+    for &rgb in snapshots.iter().flat_map(|s| &s.cells) {
+        if let Entry::Vacant(entry) = index.entry(rgb) {
+            entry.insert(u8::try_from(colors.len()).map_err(|_| "more than 256 colors for a GIF")?);
+            colors.push(rgb);
+        }
+    }
+    let palette: Vec<u8> = colors.iter().flat_map(|&(r, g, b)| [r, g, b]).collect();
+
+    let mut image = Vec::new();
+    let mut encoder = Encoder::new(&mut image, gif_w, gif_h, &palette).map_err(gif_error)?;
+    encoder.set_repeat(Repeat::Infinite).map_err(gif_error)?;
+    for snapshot in snapshots {
+        let mut pixels = vec![0; img_w * img_h];
+        let (s_top, s_left, _, s_width) = snapshot.region;
+        for (k, rgb) in snapshot.cells.iter().enumerate() {
+            let i = (s_top - top) as usize + k / s_width;
+            let j = (s_left - left) as usize + k % s_width;
+            for y in i * CELL_PIXELS..(i + 1) * CELL_PIXELS {
+                pixels[y * img_w + j * CELL_PIXELS..y * img_w + (j + 1) * CELL_PIXELS]
+                    .fill(index[rgb]);
+            }
+        }
+        let frame = Frame {
+            width: gif_w,
+            height: gif_h,
+            delay,
+            buffer: Cow::Owned(pixels),
+            ..Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(gif_error)?;
+    }
+    drop(encoder);
+    Ok(image)
+}
+
+fn gif_error(e: gif::EncodingError) -> String {
+    format!("GIF encoding failed: {e}")
+}
@@ -0,0 +1,76 @@
+use crossterm::event::{Event, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+/// Help lines under the grid, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("langton.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AntUIAction {
+    Wait,
+    Pause,
+    Step,
+    SpeedUp,
+    SlowDown,
+    Export,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, AntUIAction); 9] = [
+    (KeyBinding::char(' '), AntUIAction::Pause),
+    (KeyBinding::char('p'), AntUIAction::Pause),
+    (KeyBinding::char('.'), AntUIAction::Step),
+    (KeyBinding::char('+'), AntUIAction::SpeedUp),
+    (KeyBinding::char('='), AntUIAction::SpeedUp),
+    (KeyBinding::char('-'), AntUIAction::SlowDown),
+    (KeyBinding::char('e'), AntUIAction::Export),
+    (KeyBinding::char('q'), AntUIAction::Quit),
+    (KeyBinding::ctrl('c'), AntUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, AntUIAction); 6] = [
+    ("pause", AntUIAction::Pause),
+    ("step", AntUIAction::Step),
+    ("speed_up", AntUIAction::SpeedUp),
+    ("slow_down", AntUIAction::SlowDown),
+    ("export", AntUIAction::Export),
+    ("quit", AntUIAction::Quit),
+];
+
+pub struct AntUI {
+    pub paused: bool,
+    pub bindings: Bindings<AntUIAction>,
+}
+
+impl AntUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            paused: false,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> AntUIAction {
+        self.bindings.action(key_event).unwrap_or(AntUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> AntUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => AntUIAction::Wait,
+        }
+    }
+}
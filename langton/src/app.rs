@@ -0,0 +1,476 @@
+use std::cell::Cell;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crossterm::{cursor, event::Event, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::achievements;
+use common::terminal::text_width;
+use common::{Game, TerminalGuard};
+
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use crate::animation;
+use crate::antui::{self, AntUI, AntUIAction};
+use crate::cli::Args;
+use crate::config::LangtonConfig;
+use crate::hex::{HexLangton, HexRule};
+use crate::highway::{Highway, HighwayDetector};
+use crate::image::{self, ImageFormat};
+use crate::langton::{Boundary, Langton};
+use crate::palette::{Palette, CELL_COLS};
+use crate::rule::Rule;
+use crate::stats::{self, Stats};
+use crate::textgrid;
+use crate::turmite::Turmite;
+use crate::window::Window;
+
+// speed when neither the command line nor the config file sets one
+const DEFAULT_TICK: Duration = Duration::from_millis(20);
+
+// lines of text around the grid (position, status bar, stats, help, message)
+const STATUS_ROWS: usize = 8;
+
+// terminal columns next to the cells (odd hex rows are indented by one,
+// and ratatui draws a border)
+#[cfg(not(feature = "ratatui"))]
+const GRID_MARGIN_COLS: usize = 1;
+#[cfg(feature = "ratatui")]
+const GRID_MARGIN_COLS: usize = 3;
+
+// range of speeds reachable with +/-
+const MIN_TICK: Duration = Duration::from_millis(1);
+const MAX_TICK: Duration = Duration::from_secs(2);
+
+/// Langton's Ant app (simulation + UI state)
+pub struct LangtonApp {
+    langton: Box<dyn Turmite>,
+    start: (i64, i64), // where the ant started out
+    ui: AntUI,
+    window: Window,
+    tick: Duration,
+    render_every: u32,
+    highways: HighwayDetector,
+    highway: Option<Highway>, // highway the ant has settled into, once detected
+    pub max_steps: Option<u32>,
+    pub stop_on_highway: bool,
+    pub image_format: ImageFormat,
+    pub palette: Palette,
+    message: String,
+    redraw: Cell<bool>, // whether anything changed since the last frame
+    over: bool,
+}
+
+impl LangtonApp {
+    pub fn new(
+        langton: Box<dyn Turmite>,
+        window: Window,
+        tick: Duration,
+        render_every: u32,
+    ) -> Self {
+        Self {
+            start: langton.ant_pos(),
+            langton,
+            ui: AntUI::new(),
+            window,
+            tick,
+            render_every,
+            highways: HighwayDetector::new(),
+            highway: None,
+            max_steps: None,
+            stop_on_highway: false,
+            image_format: ImageFormat::Png,
+            palette: Palette::default(),
+            message: String::new(),
+            redraw: Cell::new(true),
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        self.fit_window();
+        self.request_redraw();
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // fit the window to the terminal, keeping the status bar on screen
+    // (cells are [CELL_COLS] columns wide)
+    fn fit_window(&mut self) {
+        if let Ok((cols, rows)) = terminal::size() {
+            self.window.fit(
+                (rows as usize).saturating_sub(STATUS_ROWS),
+                (cols as usize).saturating_sub(GRID_MARGIN_COLS) / CELL_COLS,
+            );
+        }
+    }
+
+    // draw the next frame, with the ant in view
+    fn request_redraw(&mut self) {
+        self.window.follow(self.langton.ant_pos());
+        self.redraw.set(true);
+    }
+
+    // advance the ant one step, watching for a highway until one is found
+    fn step(&mut self) {
+        self.langton.tick();
+        if self.langton.nstep() == 100_000 {
+            achievements::unlock("langton-100k");
+        }
+        if self.highway.is_some() {
+            return;
+        }
+
+        self.highway = self
+            .highways
+            .observe(self.langton.ant_pos(), self.langton.nstep());
+        let Some(highway) = self.highway else {
+            return;
+        };
+        info!(
+            "highway of period {} detected at step {}",
+            highway.period,
+            self.langton.nstep()
+        );
+        achievements::unlock("langton-highway");
+        if self.stop_on_highway {
+            self.ui.paused = true;
+        }
+    }
+
+    // rule, speed, and anything notable about the run, for the status bar
+    fn status(&self) -> String {
+        let mut status = format!(
+            "rule {} ({}ms/step)",
+            self.langton.rule_string(),
+            self.tick.as_millis()
+        );
+        if let Some(highway) = self.highway {
+            status += &format!(
+                " [highway of period {} since step {}]",
+                highway.period, highway.start
+            );
+        }
+        if self.langton.halted() {
+            status += " [stopped at the edge]";
+        } else if self.ui.paused {
+            status += " [paused]";
+        }
+        status
+    }
+
+    // write the grid to an image file, returning a status message
+    fn export_image(&self) -> String {
+        let path = format!(
+            "langton-{}.{}",
+            self.langton.nstep(),
+            self.image_format.extension()
+        );
+        match image::encode(self.langton.as_ref(), &self.palette, self.image_format)
+            .and_then(|image| fs::write(&path, image).map_err(|e| e.to_string()))
+        {
+            Ok(()) => format!("exported grid to {path}"),
+            Err(e) => format!("export failed: {e}"),
+        }
+    }
+}
+
+// Pretty-print
+impl fmt::Display for LangtonApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (i, j) = self.langton.ant_pos();
+        write!(f, "ant is at position ({i},{j})\r\n")?;
+        for row in self.palette.render(self.langton.as_ref(), &self.window) {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+
+        // status bar
+        write!(
+            f,
+            "\r\n=== STEP {} === {}",
+            self.langton.nstep(),
+            self.status()
+        )?;
+        write!(
+            f,
+            "\r\n{}",
+            Stats::measure(self.langton.as_ref(), self.start)
+        )?;
+        write!(f, "\r\n{}\r\n", antui::help_text())?;
+        write!(f, "{}\r\n", self.message)?;
+
+        Ok(())
+    }
+}
+
+impl Game for LangtonApp {
+    fn name(&self) -> &'static str {
+        "langton"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        if let Event::Resize(..) = event {
+            self.fit_window();
+            self.request_redraw();
+            return;
+        }
+
+        let user_action = self.ui.match_event_to_action(event);
+        let acted = !matches!(user_action, AntUIAction::Wait);
+        if acted {
+            debug!("{user_action:?}");
+        }
+
+        match user_action {
+            AntUIAction::Quit => self.over = true,
+            AntUIAction::Export => self.message = self.export_image(),
+            AntUIAction::Pause => self.ui.paused = !self.ui.paused,
+            AntUIAction::Step => {
+                self.ui.paused = true;
+                self.step();
+            }
+            AntUIAction::SpeedUp => self.tick = (self.tick / 2).max(MIN_TICK),
+            AntUIAction::SlowDown => self.tick = (self.tick * 2).clamp(MIN_TICK, MAX_TICK),
+            AntUIAction::Wait => {}
+        }
+
+        // any user action shows up immediately
+        if acted {
+            self.request_redraw();
+        }
+    }
+
+    // only draw every [render_every]th step
+    fn tick(&mut self) {
+        if self.ui.paused {
+            return;
+        }
+        self.step();
+        let mut redraw = self.langton.nstep().is_multiple_of(self.render_every);
+        redraw |= self.highway.is_some() && self.ui.paused;
+        if self.max_steps == Some(self.langton.nstep()) || self.langton.halted() {
+            info!("stopped at step {}", self.langton.nstep());
+            self.ui.paused = true;
+            redraw = true;
+        }
+        if redraw {
+            self.request_redraw();
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.tick)
+    }
+
+    // the window shrinks to fit, but the help text needs to stay on its lines
+    fn min_size(&self) -> (u16, u16) {
+        (text_width(antui::help_text()), STATUS_ROWS as u16 + 1)
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+}
+
+// set up the ant (and any starting grid) from the command line
+fn build_langton(args: &Args) -> Result<Box<dyn Turmite>, String> {
+    let start = (
+        args.row.unwrap_or(args.height as i64 / 2),
+        args.col.unwrap_or(args.width as i64 / 2),
+    );
+    if args.boundary != Boundary::Infinite
+        && !((0..args.height as i64).contains(&start.0)
+            && (0..args.width as i64).contains(&start.1))
+    {
+        return Err(format!(
+            "starting position ({}, {}) is outside the {}x{} grid",
+            start.0, start.1, args.height, args.width
+        ));
+    }
+
+    let cells = match &args.input {
+        Some(path) => {
+            let text = if path.as_os_str() == "-" {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|e| format!("failed to read stdin: {e}"))?;
+                text
+            } else {
+                fs::read_to_string(path)
+                    .map_err(|e| format!("failed to read {}: {e}", path.display()))?
+            };
+            textgrid::decode(&text)?
+        }
+        None => Vec::new(),
+    };
+
+    let langton: Box<dyn Turmite> = if args.hex {
+        let rule = match &args.rule {
+            Some(rule) => rule.parse::<HexRule>()?,
+            None => HexRule::default(),
+        };
+        Box::new(
+            HexLangton::new(start, args.direction.into())
+                .with_rule(rule)
+                .with_cells(&cells),
+        )
+    } else {
+        let rule = match &args.rule {
+            Some(rule) => rule.parse::<Rule>()?,
+            None => Rule::default(),
+        };
+        Box::new(
+            Langton::new(start, args.direction)
+                .with_boundary(args.boundary, (args.height, args.width))
+                .with_rule(rule)
+                .with_cells(&cells),
+        )
+    };
+    if langton.is_wall(start) {
+        return Err(format!("the ant can't start inside a wall at {start:?}"));
+    }
+    Ok(langton)
+}
+
+// run without a UI, optionally logging stats to a CSV file, and print a summary
+fn run_headless(mut langton: Box<dyn Turmite>, steps: u32, args: &Args) -> io::Result<()> {
+    let start = langton.ant_pos();
+    let mut csv = match &args.stats_csv {
+        Some(path) => {
+            let mut csv = BufWriter::new(File::create(path)?);
+            writeln!(csv, "{}", stats::CSV_HEADER)?;
+            writeln!(csv, "{}", Stats::measure(langton.as_ref(), start).csv_row())?;
+            Some(csv)
+        }
+        None => None,
+    };
+
+    let timer = Instant::now();
+    let mut highways = HighwayDetector::new();
+    let mut highway = None;
+    while langton.nstep() < steps && !langton.halted() {
+        if highway.is_none() {
+            langton.tick();
+            highway = highways.observe(langton.ant_pos(), langton.nstep());
+        } else {
+            // once there's a highway, only the steps logged are worth stopping at
+            let next = match csv {
+                Some(_) => {
+                    (langton.nstep() / args.stats_every + 1).saturating_mul(args.stats_every)
+                }
+                None => steps,
+            };
+            langton.tick_n(next.min(steps) - langton.nstep());
+        }
+
+        // log every [stats_every]th step, and the last one
+        let last = langton.nstep() == steps || langton.halted();
+        if let Some(csv) = &mut csv {
+            if langton.nstep().is_multiple_of(args.stats_every) || last {
+                writeln!(csv, "{}", Stats::measure(langton.as_ref(), start).csv_row())?;
+            }
+        }
+    }
+    let elapsed = timer.elapsed().as_secs_f64();
+
+    let stats = Stats::measure(langton.as_ref(), start);
+    println!("steps:        {}", langton.nstep());
+    println!("rule:         {}", langton.rule_string());
+    println!("colored:      {}", stats.n_colored);
+    println!("bounding box: {}x{}", stats.bbox.1, stats.bbox.0);
+    println!(
+        "displacement: ({}, {}), distance {:.1}",
+        stats.displacement.0,
+        stats.displacement.1,
+        stats.distance()
+    );
+    match highway {
+        Some(highway) => println!(
+            "highway:      period {} since step {}",
+            highway.period, highway.start
+        ),
+        None => println!("highway:      (none)"),
+    }
+    if langton.halted() {
+        println!("(the ant stopped at the edge)");
+    }
+    println!(
+        "elapsed:      {:.3}s ({:.1} steps/s)",
+        elapsed,
+        langton.nstep() as f64 / elapsed
+    );
+    Ok(())
+}
+
+// run without a UI, saving the steps as an animated GIF at [path]
+fn run_gif(mut langton: Box<dyn Turmite>, args: &Args, path: &Path) -> Result<(), String> {
+    let steps = args.steps.unwrap_or_default();
+    let delay = args.tick_ms.map_or(DEFAULT_TICK, Duration::from_millis);
+    let palette = args.palette.clone().unwrap_or_default();
+    let gif = animation::record(langton.as_mut(), steps, args.render_every, &palette, delay)?;
+    fs::write(path, gif).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    println!("saved {} steps to {}", langton.nstep(), path.display());
+    Ok(())
+}
+
+/// Run Langton's Ant as set up on the command line
+pub fn run(mut args: Args) -> Result<(), String> {
+    let config: LangtonConfig = common::cli::setup(&args.common, "langton")?;
+    args.tick_ms = args.tick_ms.or(config.tick_ms);
+    args.palette = args.palette.or(config.palette);
+
+    let langton = build_langton(&args)?;
+
+    if let Some(path) = &args.gif {
+        return run_gif(langton, &args, path);
+    }
+    if args.headless {
+        let steps = args.steps.unwrap_or_default();
+        return run_headless(langton, steps, &args)
+            .map_err(|e| format!("headless run failed: {e}"));
+    }
+
+    let mut app = LangtonApp::new(
+        langton,
+        Window::new(args.height, args.width),
+        args.tick_ms.map_or(DEFAULT_TICK, Duration::from_millis),
+        args.render_every,
+    );
+    app.ui
+        .bindings
+        .remap(&config.keys, &antui::ACTIONS)
+        .map_err(|e| format!("bad [langton.keys] config: {e}"))?;
+    app.max_steps = args.steps;
+    app.stop_on_highway = args.stop_on_highway;
+    app.image_format = args.image_format;
+    app.palette = args.palette.unwrap_or_default();
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use crate::antui;
+use crate::stats::Stats;
+
+use super::LangtonApp;
+
+impl TuiGame for LangtonApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .palette
+            .render(self.langton.as_ref(), &self.window)
+            .iter()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows).title(format!(" step {} ", self.langton.nstep()));
+
+        let (i, j) = self.langton.ant_pos();
+        let status_bar = StatusBar::new(antui::help_text())
+            .line(format!("ant at ({i},{j})   {}", self.status()))
+            .line(Stats::measure(self.langton.as_ref(), self.start).to_string())
+            .line(self.message.as_str());
+
+        let (board_area, status_area) = tui::split(frame.size(), status_bar.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status_bar, status_area);
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+use crate::image::ImageFormat;
+use crate::langton::{Boundary, Direction};
+use crate::palette::Palette;
+
+/// Langton's Ant
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on losing, winning, invalid moves and achievements")
+}))]
+pub struct Args {
+    /// Grid height, or height of the visible part of an infinite plane (in cells)
+    #[arg(long, default_value_t = 40)]
+    pub height: usize,
+
+    /// Grid width, or width of the visible part of an infinite plane (in cells)
+    #[arg(long, default_value_t = 50)]
+    pub width: usize,
+
+    /// Pause after this many steps (runs forever if not given),
+    /// or the number of steps to run in headless mode
+    #[arg(long)]
+    pub steps: Option<u32>,
+
+    /// Milliseconds between steps [default: 20]
+    #[arg(long)]
+    pub tick_ms: Option<u64>,
+
+    /// Row the ant starts on [default: middle of the starting view]
+    #[arg(long, allow_negative_numbers = true)]
+    pub row: Option<i64>,
+
+    /// Column the ant starts on [default: middle of the starting view]
+    #[arg(long, allow_negative_numbers = true)]
+    pub col: Option<i64>,
+
+    /// Direction the ant starts out facing: up, down, left or right
+    /// (on a hex grid, up and down mean north-east and south-west)
+    #[arg(long, default_value = "right")]
+    pub direction: Direction,
+
+    /// Turmite rule: the turn taken on each cell state, as a string of
+    /// L (left), R (right), N (no turn) and U (u-turn), e.g. LLRR [default: RL].
+    /// Hex rules use N, R1, R2, U, L2 and L1 [default: L2NNL1L2L1]
+    #[arg(long)]
+    pub rule: Option<String>,
+
+    /// Walk a grid of hexagonal cells instead of squares
+    #[arg(long, conflicts_with = "boundary")]
+    pub hex: bool,
+
+    /// How cell states are drawn: emoji, colors, or a comma-separated list of
+    /// colors (names like dark_red or hex codes like #ff8800), one per state
+    /// [default: emoji]
+    #[arg(long)]
+    pub palette: Option<Palette>,
+
+    /// What happens at the grid edges: infinite (no edges), torus (wrap around),
+    /// reflect (turn around), or stop
+    /// (hex grids are always infinite)
+    #[arg(long, default_value = "infinite")]
+    pub boundary: Boundary,
+
+    /// Pause once the ant starts building a highway
+    /// (instead of just reporting when it began)
+    #[arg(long)]
+    pub stop_on_highway: bool,
+
+    /// Format of images exported with <e>: png, or pbm (black and white)
+    #[arg(long, default_value = "png")]
+    pub image_format: ImageFormat,
+
+    /// Start from a text grid with its top-left corner at (0,0): '.' and '#' for
+    /// states 0 and 1, digits for other states, and 'X' for walls ('-' reads stdin)
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+
+    /// Run without drawing anything and print a summary at the end
+    #[arg(long, requires = "steps")]
+    pub headless: bool,
+
+    /// Run without drawing anything, saving the grid every --render-every
+    /// steps as a frame of an animated GIF (each shown for --tick-ms)
+    #[arg(long, requires = "steps", conflicts_with = "headless")]
+    pub gif: Option<PathBuf>,
+
+    /// In headless mode, log statistics to this CSV file (for plotting)
+    #[arg(long, requires = "headless")]
+    pub stats_csv: Option<PathBuf>,
+
+    /// Log statistics every Nth step
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    pub stats_every: u32,
+
+    /// Only draw every Nth step
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub render_every: u32,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+}
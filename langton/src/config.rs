@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+use crate::palette::Palette;
+
+/// Settings from the [langton] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LangtonConfig {
+    /// milliseconds between steps
+    pub tick_ms: Option<u64>,
+    /// how cell states are drawn (same format as --palette)
+    #[serde(deserialize_with = "common::config::from_str")]
+    pub palette: Option<Palette>,
+    /// keys for each action, e.g. quit = ["x", "ctrl+c"]
+    pub keys: KeyMap,
+}
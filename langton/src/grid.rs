@@ -0,0 +1,84 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::textgrid::{Cell, Cells};
+
+/// Sparse plane of cells; anything not stored is in state 0
+#[derive(Default)]
+pub struct Grid {
+    cells: HashMap<(i64, i64), u8>,
+    walls: HashSet<(i64, i64)>,
+}
+
+impl Grid {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Plane with pre-set cells (e.g. from a text grid)
+    pub fn from_cells(cells: &Cells) -> Self {
+        let mut grid = Self::default();
+        for &(pos, cell) in cells {
+            match cell {
+                Cell::State(state) => grid.set(pos, state),
+                Cell::Wall => {
+                    grid.walls.insert(pos);
+                }
+            }
+        }
+        grid
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn get(&self, pos: (i64, i64)) -> u8 {
+        self.cells.get(&pos).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, pos: (i64, i64), state: u8) {
+        if state == 0 {
+            self.cells.remove(&pos);
+        } else {
+            self.cells.insert(pos, state);
+        }
+    }
+
+    pub fn is_wall(&self, pos: (i64, i64)) -> bool {
+        self.walls.contains(&pos)
+    }
+
+    /// Number of cells that aren't in state 0
+    pub fn n_colored(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Smallest region (top, left, height, width) holding every colored cell
+    /// and wall, as well as [pos]
+    pub fn extent(&self, pos: (i64, i64)) -> (i64, i64, usize, usize) {
+        Self::bounds(self.cells.keys().chain(&self.walls).chain([&pos])).unwrap()
+    }
+
+    /// Smallest region (top, left, height, width) holding every colored cell
+    pub fn bounding_box(&self) -> Option<(i64, i64, usize, usize)> {
+        Self::bounds(self.cells.keys())
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn bounds<'a>(
+        positions: impl Iterator<Item = &'a (i64, i64)>,
+    ) -> Option<(i64, i64, usize, usize)> {
+        let mut bounds: Option<(i64, i64, i64, i64)> = None;
+        for &(i, j) in positions {
+            let (top, left, bottom, right) = bounds.unwrap_or((i, j, i, j));
+            bounds = Some((top.min(i), left.min(j), bottom.max(i), right.max(j)));
+        }
+        bounds.map(|(top, left, bottom, right)| {
+            let (height, width) = (bottom - top + 1, right - left + 1);
+            (top, left, height as usize, width as usize)
+        })
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::langton::Direction;
+use crate::textgrid::Cells;
+use crate::turmite::{Heading, Turmite};
+
+// cell states are stored as u8
+const MAX_STATES: usize = u8::MAX as usize + 1;
+
+/// Direction a hex ant is facing. Rows are laid out "odd-r" style,
+/// with every odd row shifted half a cell to the right
+// (clockwise order, so turning is just a step around the enum)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// Direction faced after turning [sixths] of a full turn clockwise
+    pub fn turn(self, sixths: u8) -> Self {
+        Self::ALL[(self as usize + sixths as usize) % 6]
+    }
+
+    /// (row, col) step taken when moving this way from a cell on row [row]
+    pub fn unit(self, row: i64) -> (i64, i64) {
+        // diagonal steps from an odd row land one column further right
+        let shift = row.rem_euclid(2);
+        match self {
+            HexDirection::East => (0, 1),
+            HexDirection::SouthEast => (1, shift),
+            HexDirection::SouthWest => (1, shift - 1),
+            HexDirection::West => (0, -1),
+            HexDirection::NorthWest => (-1, shift - 1),
+            HexDirection::NorthEast => (-1, shift),
+        }
+    }
+}
+
+// hex cells have no straight up or down, so those start the ant off diagonally
+impl From<Direction> for HexDirection {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::Up => HexDirection::NorthEast,
+            Direction::Right => HexDirection::East,
+            Direction::Down => HexDirection::SouthWest,
+            Direction::Left => HexDirection::West,
+        }
+    }
+}
+
+/// Hex turmite rule: the turn taken on each cell state, e.g. "L2NNL1L2L1".
+/// Turns are N (none), R1/L1 (60 degrees right/left), R2/L2 (120 degrees)
+/// and U (u-turn); cell states advance just like on a square grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct HexRule {
+    turns: Vec<u8>, // sixths of a full turn clockwise
+}
+
+impl HexRule {
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Turn taken on a cell in [state], in sixths of a full turn clockwise
+    pub fn turn(&self, state: u8) -> u8 {
+        self.turns[state as usize % self.turns.len()]
+    }
+
+    /// State a cell moves on to once the ant leaves it
+    pub fn next_state(&self, state: u8) -> u8 {
+        ((state as usize % self.turns.len() + 1) % self.turns.len()) as u8
+    }
+}
+
+impl Default for HexRule {
+    fn default() -> Self {
+        "L2NNL1L2L1".parse().unwrap()
+    }
+}
+
+// Accepts strings of N, R1, R2, U, L2 and L1 (a bare L or R means L1 or R1)
+impl FromStr for HexRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut turns = Vec::new();
+        let mut chars = s.chars().map(|c| c.to_ascii_uppercase()).peekable();
+        while let Some(c) = chars.next() {
+            let amount = match chars.peek() {
+                Some('1') | Some('2') if c == 'L' || c == 'R' => {
+                    chars.next().unwrap().to_digit(10).unwrap() as u8
+                }
+                _ => 1,
+            };
+            turns.push(match c {
+                'N' => 0,
+                'R' => amount,
+                'U' => 3,
+                'L' => 6 - amount,
+                _ => {
+                    return Err(format!(
+                        "invalid turn '{c}' (expected N, R1, R2, U, L2 or L1)"
+                    ))
+                }
+            });
+        }
+
+        if turns.is_empty() || turns.len() > MAX_STATES {
+            return Err(format!("rule should have 1 to {MAX_STATES} turns"));
+        }
+        Ok(Self { turns })
+    }
+}
+
+impl fmt::Display for HexRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &turn in &self.turns {
+            let s = ["N", "R1", "R2", "U", "L2", "L1"][turn as usize];
+            write!(f, "{s}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Ant on an (infinite) plane of hexagonal cells
+pub struct HexLangton {
+    grid: Grid,
+    pos: (i64, i64),
+    dir: HexDirection,
+    rule: HexRule,
+    nstep: u32,
+}
+
+impl HexLangton {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Empty plane with the ant at (row, col), facing [dir]
+    pub fn new(pos: (i64, i64), dir: HexDirection) -> Self {
+        Self {
+            grid: Grid::default(),
+            pos,
+            dir,
+            rule: HexRule::default(),
+            nstep: 0,
+        }
+    }
+
+    pub fn with_rule(mut self, rule: HexRule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Pre-set cells (e.g. from a text grid)
+    pub fn with_cells(mut self, cells: &Cells) -> Self {
+        self.grid = Grid::from_cells(cells);
+        self
+    }
+}
+
+impl Turmite for HexLangton {
+    fn tick(&mut self) {
+        // turn ant according to the state of its cell, then advance the cell
+        let state = self.grid.get(self.pos);
+        self.dir = self.dir.turn(self.rule.turn(state));
+        self.grid.set(self.pos, self.rule.next_state(state));
+
+        // move ant (walls turn it around on the spot)
+        let (di, dj) = self.dir.unit(self.pos.0);
+        let next = (self.pos.0 + di, self.pos.1 + dj);
+        if self.grid.is_wall(next) {
+            self.dir = self.dir.turn(3);
+        } else {
+            self.pos = next;
+        }
+
+        self.nstep += 1;
+    }
+
+    fn nstep(&self) -> u32 {
+        self.nstep
+    }
+
+    fn rule_string(&self) -> String {
+        format!("{} (hex)", self.rule)
+    }
+
+    fn ant_pos(&self) -> (i64, i64) {
+        self.pos
+    }
+
+    fn heading(&self) -> Heading {
+        Heading::Hex(self.dir)
+    }
+
+    fn n_colored(&self) -> usize {
+        self.grid.n_colored()
+    }
+
+    fn state_at(&self, pos: (i64, i64)) -> u8 {
+        self.grid.get(pos)
+    }
+
+    fn is_wall(&self, pos: (i64, i64)) -> bool {
+        self.grid.is_wall(pos)
+    }
+
+    fn extent(&self) -> (i64, i64, usize, usize) {
+        self.grid.extent(self.pos)
+    }
+
+    fn bounding_box(&self) -> Option<(i64, i64, usize, usize)> {
+        self.grid.bounding_box()
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+
+// longest highway period looked for (Langton's ant builds one of period 104)
+const MAX_PERIOD: usize = 512;
+// how many times the ant's path must repeat before it counts as a highway
+const MIN_REPEATS: u32 = 10;
+
+/// Periodic stretch of the ant's path that carries it steadily off in one direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Highway {
+    pub period: u32,
+    pub start: u32, // step at which the periodic motion began
+}
+
+/// Spots highways by watching for a period p such that the ant's last few
+/// moves match the moves it made p steps earlier
+pub struct HighwayDetector {
+    positions: VecDeque<(i64, i64)>, // recent ant positions, oldest first
+    runs: Vec<u32>,                  // runs[p] == number of moves in a row repeating with period p
+}
+
+impl Default for HighwayDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HighwayDetector {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            positions: VecDeque::with_capacity(MAX_PERIOD + 2),
+            runs: vec![0; MAX_PERIOD + 1],
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Record the ant's position after a step.
+    /// Returns the (shortest-period) highway, once the path has repeated enough.
+    pub fn observe(&mut self, pos: (i64, i64), nstep: u32) -> Option<Highway> {
+        self.positions.push_back(pos);
+        if self.positions.len() > MAX_PERIOD + 2 {
+            self.positions.pop_front();
+        }
+
+        let n = self.positions.len();
+        let drift = |t: usize, p: usize| {
+            let ((i1, j1), (i0, j0)) = (self.positions[t], self.positions[t - p]);
+            (i1 - i0, j1 - j0)
+        };
+
+        // the latest move repeats the one p steps ago iff
+        // the ant drifted the same way over the last p steps as over the p before that
+        let mut highway = None;
+        for p in 1..=MAX_PERIOD.min(n.saturating_sub(2)) {
+            let now = drift(n - 1, p);
+            if now == drift(n - 2, p) && now != (0, 0) {
+                self.runs[p] += 1;
+            } else {
+                self.runs[p] = 0;
+            }
+
+            if highway.is_none() && self.runs[p] >= MIN_REPEATS * p as u32 {
+                highway = Some(Highway {
+                    period: p as u32,
+                    start: (nstep + 1).saturating_sub(self.runs[p] + p as u32),
+                });
+            }
+        }
+        highway
+    }
+}
@@ -0,0 +1,107 @@
+use std::str::FromStr;
+
+use crate::palette::Palette;
+use crate::turmite::Turmite;
+
+// exported images draw each cell as a square of this many pixels
+pub const CELL_PIXELS: usize = 4;
+
+/// File format for exported images
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    /// black-and-white bitmap (any state other than 0, and walls, are black)
+    Pbm,
+    /// full-color PNG, using the palette's colors
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Pbm => "pbm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pbm" => Ok(ImageFormat::Pbm),
+            "png" => Ok(ImageFormat::Png),
+            _ => Err(format!("unknown image format '{s}' (expected pbm or png)")),
+        }
+    }
+}
+
+/// Encode the interesting part of the grid (see [Turmite::extent]) as an
+/// image, colored with [palette] if the format has color
+pub fn encode(
+    langton: &dyn Turmite,
+    palette: &Palette,
+    format: ImageFormat,
+) -> Result<Vec<u8>, String> {
+    let (top, left, height, width) = langton.extent();
+    let (img_h, img_w) = (height * CELL_PIXELS, width * CELL_PIXELS);
+
+    // grid position of each pixel
+    let pixels = (0..img_h).flat_map(|y| {
+        (0..img_w).map(move |x| {
+            (
+                top + (y / CELL_PIXELS) as i64,
+                left + (x / CELL_PIXELS) as i64,
+            )
+        })
+    });
+
+    match format {
+        ImageFormat::Pbm => {
+            // binary PBM: rows of packed bits, 1 == black
+            let mut image = format!("P4\n{img_w} {img_h}\n").into_bytes();
+            let bits: Vec<bool> = pixels
+                .map(|pos| langton.state_at(pos) != 0 || langton.is_wall(pos))
+                .collect();
+            for row in bits.chunks(img_w) {
+                for byte in row.chunks(8) {
+                    image.push(
+                        byte.iter()
+                            .enumerate()
+                            .fold(0, |acc, (k, &black)| acc | ((black as u8) << (7 - k))),
+                    );
+                }
+            }
+            Ok(image)
+        }
+        ImageFormat::Png => {
+            let rgb: Vec<u8> = pixels
+                .flat_map(|pos| {
+                    let (r, g, b) = cell_rgb(langton, palette, pos);
+                    [r, g, b]
+                })
+                .collect();
+
+            let mut image = Vec::new();
+            let mut encoder = png::Encoder::new(&mut image, img_w as u32, img_h as u32);
+            encoder.set_color(png::ColorType::Rgb);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder
+                .write_header()
+                .and_then(|mut writer| writer.write_image_data(&rgb))
+                .map_err(|e| format!("PNG encoding failed: {e}"))?;
+            Ok(image)
+        }
+    }
+}
+
+/// Color of the cell at [pos] in images: the ant's, a wall's, or its state's
+pub fn cell_rgb(langton: &dyn Turmite, palette: &Palette, pos: (i64, i64)) -> (u8, u8, u8) {
+    if pos == langton.ant_pos() {
+        palette.ant_rgb()
+    } else if langton.is_wall(pos) {
+        palette.wall_rgb()
+    } else {
+        palette.rgb(langton.state_at(pos))
+    }
+}
@@ -0,0 +1,271 @@
+use std::str::FromStr;
+
+use crate::grid::Grid;
+use crate::rule::{Rule, Turn};
+use crate::textgrid::Cells;
+use crate::turmite::{Heading, Turmite};
+
+/// Direction the ant is facing
+// (clockwise order, so turning is just a step around the enum)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Direction faced after taking [turn]
+    pub fn turn(self, turn: Turn) -> Self {
+        let quarter_turns = match turn {
+            Turn::Straight => 0,
+            Turn::Right => 1,
+            Turn::Back => 2,
+            Turn::Left => 3,
+        };
+        Self::ALL[(self as usize + quarter_turns) % 4]
+    }
+
+    /// (row, col) step taken when moving this way
+    pub fn unit(self) -> (i64, i64) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "up" => Ok(Direction::Up),
+            "down" => Ok(Direction::Down),
+            "left" => Ok(Direction::Left),
+            "right" => Ok(Direction::Right),
+            _ => Err(format!(
+                "unknown direction '{s}' (expected up, down, left or right)"
+            )),
+        }
+    }
+}
+
+/// What happens when the ant walks off the edge of the grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Boundary {
+    /// there is no edge: the plane goes on forever
+    Infinite,
+    /// edges wrap around to the opposite side
+    Torus,
+    /// the ant turns around at the edges
+    Reflect,
+    /// the ant stops for good at the edges
+    Stop,
+}
+
+impl FromStr for Boundary {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "infinite" => Ok(Boundary::Infinite),
+            "torus" => Ok(Boundary::Torus),
+            "reflect" => Ok(Boundary::Reflect),
+            "stop" => Ok(Boundary::Stop),
+            _ => Err(format!(
+                "unknown boundary '{s}' (expected infinite, torus, reflect or stop)"
+            )),
+        }
+    }
+}
+
+pub struct Langton {
+    grid: Grid,
+    ant: Ant,
+    rule: Rule,
+    boundary: Boundary,
+    size: (i64, i64), // grid height and width (unless it's infinite)
+    halted: bool,     // whether the ant has stopped at an edge
+    nstep: u32,
+}
+
+impl Langton {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Empty plane with the ant at (row, col), facing [dir]
+    pub fn new(pos: (i64, i64), dir: Direction) -> Self {
+        Self {
+            grid: Grid::default(),
+            ant: Ant { pos, dir },
+            rule: Rule::default(),
+            boundary: Boundary::Infinite,
+            size: (0, 0),
+            halted: false,
+            nstep: 0,
+        }
+    }
+
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    /// Pre-set cells (e.g. from a text grid)
+    pub fn with_cells(mut self, cells: &Cells) -> Self {
+        self.grid = Grid::from_cells(cells);
+        self
+    }
+
+    /// Confine the ant to a (height x width) grid, unless [boundary] is infinite
+    pub fn with_boundary(mut self, boundary: Boundary, (height, width): (usize, usize)) -> Self {
+        if boundary != Boundary::Infinite {
+            let (i, j) = self.ant.pos;
+            assert!(
+                self.in_bounds(i, j, height as i64, width as i64),
+                "ant must start inside the grid"
+            );
+        }
+        self.boundary = boundary;
+        self.size = (height as i64, width as i64);
+        self
+    }
+
+    fn in_bounds(&self, i: i64, j: i64, height: i64, width: i64) -> bool {
+        (0..height).contains(&i) && (0..width).contains(&j)
+    }
+
+    fn move_ant(&mut self) {
+        let step = |(i, j): (i64, i64), dir: Direction| {
+            let (di, dj) = dir.unit();
+            (i + di, j + dj)
+        };
+        let (height, width) = self.size;
+        let (i, j) = step(self.ant.pos, self.ant.dir);
+        let next = if self.boundary == Boundary::Infinite || self.in_bounds(i, j, height, width) {
+            Some((i, j))
+        } else {
+            // ant is about to walk off the edge
+            match self.boundary {
+                Boundary::Torus => Some((i.rem_euclid(height), j.rem_euclid(width))),
+                Boundary::Reflect => {
+                    self.ant.turn(Turn::Back);
+                    let (i, j) = step(self.ant.pos, self.ant.dir);
+                    // (a grid one cell wide leaves nowhere to go)
+                    self.in_bounds(i, j, height, width).then_some((i, j))
+                }
+                Boundary::Stop => {
+                    self.halted = true;
+                    None
+                }
+                Boundary::Infinite => unreachable!(),
+            }
+        };
+
+        // walls turn the ant around on the spot
+        match next {
+            Some(pos) if self.grid.is_wall(pos) => self.ant.turn(Turn::Back),
+            Some(pos) => self.ant.pos = pos,
+            None => {}
+        }
+    }
+}
+
+impl Turmite for Langton {
+    fn tick(&mut self) {
+        if self.halted {
+            return;
+        }
+
+        // turn ant according to the state of its square
+        let state = self.grid.get(self.ant.pos);
+        self.ant.turn(self.rule.turn(state));
+        // advance square to its next state
+        self.grid.set(self.ant.pos, self.rule.next_state(state));
+        // move ant
+        self.move_ant();
+        // increment step counter
+        self.nstep += 1;
+    }
+
+    // (stops early once the ant has halted)
+    fn tick_n(&mut self, n: u32) {
+        for _ in 0..n {
+            if self.halted {
+                break;
+            }
+            self.tick();
+        }
+    }
+
+    fn nstep(&self) -> u32 {
+        self.nstep
+    }
+
+    fn rule_string(&self) -> String {
+        self.rule.to_string()
+    }
+
+    // (the ant stops at the edge of a grid with the stop boundary)
+    fn halted(&self) -> bool {
+        self.halted
+    }
+
+    fn ant_pos(&self) -> (i64, i64) {
+        self.ant.pos
+    }
+
+    fn heading(&self) -> Heading {
+        Heading::Square(self.ant.dir)
+    }
+
+    fn n_colored(&self) -> usize {
+        self.grid.n_colored()
+    }
+
+    fn state_at(&self, pos: (i64, i64)) -> u8 {
+        self.grid.get(pos)
+    }
+
+    fn is_wall(&self, pos: (i64, i64)) -> bool {
+        self.grid.is_wall(pos)
+    }
+
+    // the whole grid if it's bounded, or else everything that's been
+    // colored or walled off (plus the ant itself)
+    fn extent(&self) -> (i64, i64, usize, usize) {
+        if self.boundary != Boundary::Infinite {
+            let (height, width) = self.size;
+            return (0, 0, height as usize, width as usize);
+        }
+        self.grid.extent(self.ant.pos)
+    }
+
+    fn bounding_box(&self) -> Option<(i64, i64, usize, usize)> {
+        self.grid.bounding_box()
+    }
+}
+
+#[derive(Debug)]
+struct Ant {
+    pos: (i64, i64),
+    dir: Direction,
+}
+
+impl Ant {
+    fn turn(&mut self, turn: Turn) {
+        self.dir = self.dir.turn(turn);
+    }
+}
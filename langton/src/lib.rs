@@ -0,0 +1,39 @@
+//! Langton's Ant: the game core (ants on square and hex grids, their rules,
+//! and highway detection), plus the crossterm frontend for watching them in
+//! a terminal
+
+mod grid;
+mod hex;
+mod highway;
+mod langton;
+mod rule;
+pub mod stats;
+pub mod textgrid;
+mod turmite;
+pub use hex::{HexDirection, HexLangton, HexRule};
+pub use highway::{Highway, HighwayDetector};
+pub use langton::{Boundary, Direction, Langton};
+pub use rule::{Rule, Turn};
+pub use stats::Stats;
+pub use turmite::{CellView, Heading, Turmite};
+
+#[cfg(feature = "terminal")]
+mod animation;
+#[cfg(feature = "terminal")]
+mod antui;
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod image;
+#[cfg(feature = "terminal")]
+mod palette;
+#[cfg(feature = "terminal")]
+mod window;
+#[cfg(feature = "terminal")]
+pub use app::{run, LangtonApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,250 @@
+use std::str::FromStr;
+
+use common::style;
+use common::terminal::padding;
+use common::theme::{self, Role};
+use crossterm::style::{Color, StyledContent, Stylize};
+
+use crate::hex::HexDirection;
+use crate::langton::Direction;
+use crate::turmite::{CellView, Heading, Turmite};
+use crate::window::Window;
+
+// emoji squares for each cell state
+const EMOJI_STRS: [&str; 9] = ["⬛️", "⬜️", "🟥", "🟧", "🟨", "🟩", "🟦", "🟪", "🟫"];
+// ant arrows, in the same order as Direction
+const EMOJI_ANT_STRS: [&str; 4] = ["⬆️", "➡️", "⬇️", "⬅️"];
+// hex ant arrows, in the same order as HexDirection
+const EMOJI_HEX_ANT_STRS: [&str; 6] = ["➡️", "↘️", "↙️", "⬅️", "↖️", "↗️"];
+// roughly what the emoji squares look like, for exported images
+const EMOJI_RGB: [(u8, u8, u8); 9] = [
+    (0, 0, 0),
+    (255, 255, 255),
+    (221, 46, 68),
+    (244, 144, 12),
+    (253, 203, 88),
+    (120, 177, 89),
+    (85, 172, 238),
+    (170, 142, 214),
+    (193, 105, 79),
+];
+
+// arrows for drawing the ant on top of a colored cell
+const COLOR_ANT_STRS: [&str; 4] = ["↑ ", "→ ", "↓ ", "← "];
+const COLOR_HEX_ANT_STRS: [&str; 6] = ["→ ", "↘ ", "↙ ", "← ", "↖ ", "↗ "];
+
+// without color, the "colors" palette falls back to shading each state differently
+const SHADE_STRS: [&str; 9] = ["  ", "██", "▒▒", "░░", "::", "++", "##", "%%", "@@"];
+
+// walls look the same in every palette
+const WALL_STR: &str = "▓▓";
+
+/// Terminal columns each cell takes up
+pub const CELL_COLS: usize = 2;
+
+/// How each cell state is drawn (cycling through the palette if a rule has
+/// more states than it has entries)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Palette {
+    /// colored emoji squares
+    #[default]
+    Emoji,
+    /// blocks of terminal colors (the current theme's cell colors if empty)
+    Colors(Vec<Color>),
+}
+
+impl Palette {
+    /////////////
+    // Publics //
+    /////////////
+
+    /// String for a cell in [state]
+    pub fn cell_str(&self, state: u8) -> StyledContent<&'static str> {
+        match self {
+            Palette::Emoji => EMOJI_STRS[state as usize % EMOJI_STRS.len()].stylize(),
+            Palette::Colors(_) if !style::color_enabled() => {
+                let k = state as usize % self.n_colors();
+                SHADE_STRS[k % SHADE_STRS.len()].stylize()
+            }
+            Palette::Colors(_) => "██".with(self.color(state)),
+        }
+    }
+
+    /// String for the ant, standing on a cell in [state]
+    pub fn ant_str(&self, dir: Direction, state: u8) -> StyledContent<&'static str> {
+        let i = dir as usize;
+        self.ant_glyph(EMOJI_ANT_STRS[i], COLOR_ANT_STRS[i], state)
+    }
+
+    /// String for a hex ant, standing on a cell in [state]
+    pub fn hex_ant_str(&self, dir: HexDirection, state: u8) -> StyledContent<&'static str> {
+        let i = dir as usize;
+        self.ant_glyph(EMOJI_HEX_ANT_STRS[i], COLOR_HEX_ANT_STRS[i], state)
+    }
+
+    /// String for whatever is in a cell
+    pub fn view_str(&self, view: CellView) -> StyledContent<&'static str> {
+        match view {
+            CellView::Ant(Heading::Square(dir), state) => self.ant_str(dir, state),
+            CellView::Ant(Heading::Hex(dir), state) => self.hex_ant_str(dir, state),
+            CellView::Wall => self.wall_str(),
+            CellView::State(state) => self.cell_str(state),
+        }
+    }
+
+    /// Draw the part of [turmite]'s plane inside [window], as a row of
+    /// (styled) strings for each row of cells
+    pub fn render(
+        &self,
+        turmite: &dyn Turmite,
+        window: &Window,
+    ) -> Vec<Vec<StyledContent<&'static str>>> {
+        // odd hex rows are indented by half a cell (one character)
+        let hex = matches!(turmite.heading(), Heading::Hex(_));
+        window
+            .rows()
+            .map(|i| {
+                let indent = hex.then(|| if i.rem_euclid(2) == 1 { " " } else { "" });
+                let cells = window
+                    .cols()
+                    .map(|j| self.view_str(turmite.view((i, j))))
+                    .flat_map(padded);
+                indent
+                    .map(Stylize::stylize)
+                    .into_iter()
+                    .chain(cells)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// String for an impassable wall
+    pub fn wall_str(&self) -> StyledContent<&'static str> {
+        WALL_STR
+            .with(theme::color(Role::Muted))
+            .on(theme::color(Role::Text))
+    }
+
+    /// Pixel color for a cell in [state], in exported images
+    pub fn rgb(&self, state: u8) -> (u8, u8, u8) {
+        match self {
+            Palette::Emoji => EMOJI_RGB[state as usize % EMOJI_RGB.len()],
+            Palette::Colors(_) => Self::color_rgb(self.color(state)),
+        }
+    }
+
+    /// Pixel color for walls, in exported images
+    pub fn wall_rgb(&self) -> (u8, u8, u8) {
+        Self::color_rgb(theme::color(Role::Muted))
+    }
+
+    /// Pixel color for the ant, in exported images
+    pub fn ant_rgb(&self) -> (u8, u8, u8) {
+        Self::color_rgb(theme::color(Role::Marker))
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // emoji arrow, or plain arrow over the cell's color
+    fn ant_glyph(
+        &self,
+        emoji: &'static str,
+        arrow: &'static str,
+        state: u8,
+    ) -> StyledContent<&'static str> {
+        match self {
+            Palette::Emoji => emoji.stylize(),
+            Palette::Colors(_) if !style::color_enabled() => arrow.bold().reverse(),
+            Palette::Colors(_) => arrow
+                .with(theme::color(Role::Marker))
+                .on(self.color(state))
+                .bold(),
+        }
+    }
+
+    // number of distinct colors before the palette cycles
+    fn n_colors(&self) -> usize {
+        match self {
+            Palette::Colors(colors) if !colors.is_empty() => colors.len(),
+            Palette::Colors(_) => theme::current().cells.len(),
+            Palette::Emoji => EMOJI_STRS.len(),
+        }
+    }
+
+    // terminal color for a cell in [state]
+    fn color(&self, state: u8) -> Color {
+        let k = state as usize % self.n_colors();
+        match self {
+            Palette::Colors(colors) if !colors.is_empty() => colors[k],
+            _ => theme::current().cells[k],
+        }
+    }
+
+    // typical xterm values for the named terminal colors
+    fn color_rgb(color: Color) -> (u8, u8, u8) {
+        match color {
+            Color::Rgb { r, g, b } => (r, g, b),
+            Color::Black => (0, 0, 0),
+            Color::DarkGrey => (127, 127, 127),
+            Color::Red => (255, 0, 0),
+            Color::DarkRed => (205, 0, 0),
+            Color::Green => (0, 255, 0),
+            Color::DarkGreen => (0, 205, 0),
+            Color::Yellow => (255, 255, 0),
+            Color::DarkYellow => (205, 205, 0),
+            Color::Blue => (92, 92, 255),
+            Color::DarkBlue => (0, 0, 238),
+            Color::Magenta => (255, 0, 255),
+            Color::DarkMagenta => (205, 0, 205),
+            Color::Cyan => (0, 255, 255),
+            Color::DarkCyan => (0, 205, 205),
+            Color::White => (255, 255, 255),
+            Color::Grey => (229, 229, 229),
+            _ => (255, 255, 255),
+        }
+    }
+
+    // color name (as in crossterm, e.g. "dark_red") or hex code like "#ff8800"
+    fn parse_color(s: &str) -> Result<Color, String> {
+        let invalid = || format!("invalid color '{s}'");
+        match s.strip_prefix('#') {
+            Some(hex) if hex.len() == 6 && hex.is_ascii() => {
+                let channel = |k: usize| u8::from_str_radix(&hex[k..k + 2], 16);
+                let (r, g, b) = (channel(0), channel(2), channel(4));
+                Ok(Color::Rgb {
+                    r: r.map_err(|_| invalid())?,
+                    g: g.map_err(|_| invalid())?,
+                    b: b.map_err(|_| invalid())?,
+                })
+            }
+            Some(_) => Err(invalid()),
+            None => Color::try_from(s).map_err(|_| invalid()),
+        }
+    }
+}
+
+// Accepts "emoji", "colors", or a comma-separated list of colors (one per state)
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "emoji" => Ok(Palette::Emoji),
+            "colors" => Ok(Palette::Colors(Vec::new())),
+            _ => s
+                .split(',')
+                .map(|color| Self::parse_color(color.trim()))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Palette::Colors),
+        }
+    }
+}
+
+/// A cell's string, followed by the padding it needs to fill [CELL_COLS]
+/// columns (if its glyph is narrower)
+pub fn padded(cell: StyledContent<&'static str>) -> [StyledContent<&'static str>; 2] {
+    let pad = padding(cell.content(), CELL_COLS);
+    [cell, pad.stylize()]
+}
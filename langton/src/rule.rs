@@ -0,0 +1,93 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Way the ant turns when it leaves a cell
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+    Straight,
+    /// turn around
+    Back,
+}
+
+/// Turmite rule: the turn taken on each cell state, e.g. "RL" for Langton's Ant.
+/// A cell in state k makes the ant take the kth turn, then moves on to state k+1
+/// (wrapping back to state 0 after the last one)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    turns: Vec<Turn>,
+}
+
+// cell states are stored as u8
+const MAX_STATES: usize = u8::MAX as usize + 1;
+
+impl Rule {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Langton's original ant, RL
+    pub fn langton() -> Self {
+        "RL".parse().unwrap()
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Turn taken on a cell in [state]
+    /// (states past the last one, e.g. from a starting grid, wrap around)
+    pub fn turn(&self, state: u8) -> Turn {
+        self.turns[state as usize % self.turns.len()]
+    }
+
+    /// State a cell moves on to once the ant leaves it
+    pub fn next_state(&self, state: u8) -> u8 {
+        ((state as usize % self.turns.len() + 1) % self.turns.len()) as u8
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::langton()
+    }
+}
+
+// Accepts strings of L (left), R (right), N (no turn) and U (u-turn)
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let turns = s
+            .chars()
+            .map(|c| match c.to_ascii_uppercase() {
+                'L' => Ok(Turn::Left),
+                'R' => Ok(Turn::Right),
+                'N' => Ok(Turn::Straight),
+                'U' => Ok(Turn::Back),
+                _ => Err(format!("invalid turn '{c}' (expected L, R, N or U)")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if turns.is_empty() || turns.len() > MAX_STATES {
+            return Err(format!("rule should have 1 to {MAX_STATES} turns"));
+        }
+        Ok(Self { turns })
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for turn in &self.turns {
+            let c = match turn {
+                Turn::Left => 'L',
+                Turn::Right => 'R',
+                Turn::Straight => 'N',
+                Turn::Back => 'U',
+            };
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+use crate::turmite::Turmite;
+
+/// Column names for [Stats::csv_row]
+pub const CSV_HEADER: &str =
+    "step,colored,bbox_height,bbox_width,displacement_rows,displacement_cols,distance";
+
+/// Snapshot of how far a run has got
+pub struct Stats {
+    pub nstep: u32,
+    pub n_colored: usize,
+    pub bbox: (usize, usize),     // height and width of the colored region
+    pub displacement: (i64, i64), // how far the ant is from where it started
+}
+
+impl Stats {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Measure [turmite], whose ant started out at [start]
+    pub fn measure(turmite: &dyn Turmite, start: (i64, i64)) -> Self {
+        let (i, j) = turmite.ant_pos();
+        Self {
+            nstep: turmite.nstep(),
+            n_colored: turmite.n_colored(),
+            bbox: turmite
+                .bounding_box()
+                .map_or((0, 0), |(_, _, height, width)| (height, width)),
+            displacement: (i - start.0, j - start.1),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Straight-line distance from the ant's starting point
+    pub fn distance(&self) -> f64 {
+        let (di, dj) = self.displacement;
+        ((di * di + dj * dj) as f64).sqrt()
+    }
+
+    pub fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{:.3}",
+            self.nstep,
+            self.n_colored,
+            self.bbox.0,
+            self.bbox.1,
+            self.displacement.0,
+            self.displacement.1,
+            self.distance()
+        )
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "colored: {}   bounding box: {}x{}   displacement: ({}, {}), distance {:.1}",
+            self.n_colored,
+            self.bbox.1,
+            self.bbox.0,
+            self.displacement.0,
+            self.displacement.1,
+            self.distance()
+        )
+    }
+}
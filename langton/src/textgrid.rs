@@ -0,0 +1,38 @@
+/// Contents of a cell in a starting grid
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cell {
+    State(u8),
+    /// impassable: the ant turns around rather than walk into it
+    Wall,
+}
+
+/// Cells of a starting grid, keyed by (row, col)
+pub type Cells = Vec<((i64, i64), Cell)>;
+
+/// Decode a plain-text starting grid, one row per line, with its top-left
+/// corner at (0,0). `.` is a cell in state 0, `#` one in state 1, a digit one in
+/// that state, and `X` a wall; lines starting with `!` are comments.
+/// Only cells that aren't in state 0 are returned.
+pub fn decode(text: &str) -> Result<Cells, String> {
+    let mut cells = Vec::new();
+    let rows = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.starts_with('!'));
+    for (i, row) in rows.enumerate() {
+        for (j, c) in row.chars().enumerate() {
+            let cell = match c {
+                '.' => continue,
+                '#' => Cell::State(1),
+                'X' | 'x' => Cell::Wall,
+                _ => match c.to_digit(10) {
+                    Some(0) => continue,
+                    Some(state) => Cell::State(state as u8),
+                    None => return Err(format!("unexpected character '{c}' in grid")),
+                },
+            };
+            cells.push(((i as i64, j as i64), cell));
+        }
+    }
+    Ok(cells)
+}
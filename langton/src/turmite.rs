@@ -0,0 +1,100 @@
+use std::convert::Infallible;
+
+use common::{Core, Point};
+
+use crate::hex::HexDirection;
+use crate::langton::Direction;
+
+/// Which way an ant is facing, on whichever shape of cells it walks over
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Heading {
+    Square(Direction),
+    Hex(HexDirection),
+}
+
+/// What's in one cell of the plane, as far as drawing it goes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellView {
+    /// the ant, standing on a cell in some state
+    Ant(Heading, u8),
+    Wall,
+    State(u8),
+}
+
+/// An ant walking over a plane of cells, whatever shape the cells are
+pub trait Turmite {
+    /// Move the ant one step
+    fn tick(&mut self);
+
+    /// Move the ant [n] steps
+    fn tick_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    fn nstep(&self) -> u32;
+
+    /// Turn rule, in the notation it was given in
+    fn rule_string(&self) -> String;
+
+    /// Whether the ant has stopped for good
+    fn halted(&self) -> bool {
+        false
+    }
+
+    /// Position of the ant, as (row, col)
+    fn ant_pos(&self) -> (i64, i64);
+
+    fn heading(&self) -> Heading;
+
+    /// Number of cells that aren't in state 0
+    fn n_colored(&self) -> usize;
+
+    fn state_at(&self, pos: (i64, i64)) -> u8;
+
+    fn is_wall(&self, pos: (i64, i64)) -> bool;
+
+    /// Region (top, left, height, width) worth looking at
+    fn extent(&self) -> (i64, i64, usize, usize);
+
+    /// Smallest region (top, left, height, width) holding every colored cell
+    fn bounding_box(&self) -> Option<(i64, i64, usize, usize)>;
+
+    /// What to draw at [pos]
+    fn view(&self, pos: (i64, i64)) -> CellView {
+        if pos == self.ant_pos() {
+            CellView::Ant(self.heading(), self.state_at(pos))
+        } else if self.is_wall(pos) {
+            CellView::Wall
+        } else {
+            CellView::State(self.state_at(pos))
+        }
+    }
+}
+
+// the board is the turmite's extent (which grows as the ant wanders off),
+// and there's nothing for a player to do but watch
+impl Core for Box<dyn Turmite> {
+    type Action = Infallible;
+    type Outcome = ();
+    type Cell = CellView;
+
+    fn size(&self) -> (usize, usize) {
+        let (_, _, height, width) = self.extent();
+        (height, width)
+    }
+
+    fn cell(&self, Point(i, j): Point) -> CellView {
+        let (top, left, _, _) = self.extent();
+        self.view((top + i as i64, left + j as i64))
+    }
+
+    fn act(&mut self, action: Infallible) {
+        match action {}
+    }
+
+    fn tick(&mut self) {
+        Turmite::tick(self.as_mut());
+    }
+}
@@ -0,0 +1,57 @@
+/// Window onto the part of the (infinite) plane that's drawn
+pub struct Window {
+    top: i64,
+    left: i64,
+    height: usize,
+    width: usize,
+    max_height: usize, // size asked for, before fitting it to the terminal
+    max_width: usize,
+}
+
+impl Window {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            top: 0,
+            left: 0,
+            height,
+            width,
+            max_height: height,
+            max_width: width,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Recenter the window on (i,j) if it has wandered out of view
+    pub fn follow(&mut self, (i, j): (i64, i64)) {
+        if !(self.top..self.top + self.height as i64).contains(&i) {
+            self.top = i - self.height as i64 / 2;
+        }
+        if !(self.left..self.left + self.width as i64).contains(&j) {
+            self.left = j - self.width as i64 / 2;
+        }
+    }
+
+    /// Shrink the window to fit in a (rows x cols) area of cells, or grow it
+    /// back up to its original size
+    pub fn fit(&mut self, rows: usize, cols: usize) {
+        self.height = self.max_height.min(rows).max(1);
+        self.width = self.max_width.min(cols).max(1);
+    }
+
+    /// Plane coordinates of each visible row
+    pub fn rows(&self) -> impl Iterator<Item = i64> {
+        self.top..self.top + self.height as i64
+    }
+
+    /// Plane coordinates of each visible column
+    pub fn cols(&self) -> impl Iterator<Item = i64> {
+        self.left..self.left + self.width as i64
+    }
+}
@@ -0,0 +1,292 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::LightsOutConfig;
+use crate::lightsout::{LightsOut, PressResult};
+use crate::lightsoutui::{self, LightsOutUI, LightsOutUIAction};
+use crate::solver::solve;
+
+// lights across when nothing else sets it
+const DEFAULT_SIZE: u8 = 5;
+
+// fewest and most lights across there can be
+const MIN_SIZE: u8 = 3;
+const MAX_SIZE: u8 = 10;
+
+// terminal columns each light takes up (two for the light, and one either
+// side for the cursor or a hint mark)
+const LIGHT_COLS: usize = 4;
+
+// lines of text under the lights (gap, presses, hint, message)
+const STATUS_ROWS: usize = 4;
+
+/// Lights Out app (the lights, the fewest presses they could be put out
+/// in, the solver's presses if they're being shown, and UI state)
+pub struct LightsOutApp {
+    game: LightsOut,
+    ui: LightsOutUI,
+    fewest: usize,            // presses the lights could've been put out in
+    hint: Option<Vec<Point>>, // buttons the solver would press, while they're shown
+    rng: StdRng,              // for picking which lights start on
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last lights put out
+    over: bool,
+}
+
+impl LightsOutApp {
+    /// Lights [size] across, switched on with [seed]
+    pub fn new(size: u8, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let game = LightsOut::random(size as usize, rng.gen());
+        Self {
+            fewest: fewest_presses(&game),
+            game,
+            ui: LightsOutUI::new(size as usize),
+            hint: None,
+            rng,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_game(&mut self) {
+        self.game = LightsOut::random(self.game.size(), self.rng.gen());
+        self.fewest = fewest_presses(&self.game);
+        if self.hint.is_some() {
+            self.hint = solve(&self.game);
+        }
+        self.message = "".to_string().reset();
+    }
+
+    fn press(&mut self, p: Point) {
+        let res = self.game.press(p);
+        debug!("press at ({}, {}): {res:?}", p.0, p.1);
+        if self.hint.is_some() {
+            self.hint = solve(&self.game);
+        }
+        self.handle_res(res);
+    }
+
+    fn toggle_hint(&mut self) {
+        self.hint = match self.hint {
+            Some(_) => None,
+            None => solve(&self.game),
+        };
+    }
+
+    fn handle_res(&mut self, res: PressResult) {
+        match res {
+            PressResult::Solved(presses) => {
+                bell::ring("win");
+                self.outcome = Outcome::Won;
+                self.message = format!(
+                    "{} ({presses} {}, {} {}) {}",
+                    locale::text("lightsout.win"),
+                    locale::text("lightsout.presses"),
+                    locale::text("lightsout.fewest"),
+                    self.fewest,
+                    locale::text("lightsout.again")
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success));
+            }
+            PressResult::GameOver => bell::ring("invalid move"),
+            PressResult::Toggled => {}
+        }
+    }
+
+    // (styled) pieces for a light: on or off between a pair of marks, which
+    // are brackets at the cursor, and parentheses on a button the solver
+    // would press
+    fn styled_light(&self, p: Point) -> [StyledContent<String>; 3] {
+        let light = match self.game.is_lit(p) {
+            true => "██".to_string().with(theme::color(Role::Marker)),
+            false => "··".to_string().with(theme::color(Role::Muted)),
+        };
+        let hinted = self.hint.as_ref().is_some_and(|hint| hint.contains(&p));
+        let (left, right) = match (p == self.ui.get_cursor(), hinted) {
+            (true, _) => ("[", "]"),
+            (false, true) => ("(", ")"),
+            (false, false) => (" ", " "),
+        };
+        let mark = |s: &str| match hinted {
+            true => s.to_string().bold().with(theme::color(Role::Accent)),
+            false => s.to_string().bold().with(theme::color(Role::Text)),
+        };
+        [mark(left), light, mark(right)]
+    }
+
+    /// Rows of lights, each a list of (styled) pieces
+    fn light_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let size = self.game.size();
+        (0..size)
+            .map(|i| {
+                (0..size)
+                    .flat_map(|j| self.styled_light(Point(i, j)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // presses so far, the fewest there could've been, and lights still on
+    fn counters(&self) -> String {
+        format!(
+            "{} {}   {} {}   {} {}",
+            locale::text("lightsout.presses_made"),
+            self.game.presses(),
+            locale::text("lightsout.fewest_label"),
+            self.fewest,
+            locale::text("lightsout.lit"),
+            self.game.lit_count()
+        )
+    }
+
+    // how many of the marked presses are left, while they're shown
+    fn hint_line(&self) -> String {
+        match &self.hint {
+            Some(hint) if !self.game.is_solved() => format!(
+                "{} {} {}",
+                locale::text("lightsout.hint"),
+                hint.len(),
+                locale::text("lightsout.presses")
+            ),
+            _ => "".to_string(),
+        }
+    }
+}
+
+// presses the lights can be put out in (they always can, the way they're
+// switched on)
+fn fewest_presses(game: &LightsOut) -> usize {
+    solve(game).map(|presses| presses.len()).unwrap_or_default()
+}
+
+// Pretty-print
+impl fmt::Display for LightsOutApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.light_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.counters())?;
+        write!(f, "{}\r\n", self.hint_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", lightsoutui::help_text())
+    }
+}
+
+impl Game for LightsOutApp {
+    fn name(&self) -> &'static str {
+        "lightsout"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != LightsOutUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            LightsOutUIAction::Quit => self.over = true,
+            LightsOutUIAction::NewGame => self.new_game(),
+            LightsOutUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the board");
+                }
+            }
+            LightsOutUIAction::Press => self.press(self.ui.get_cursor()),
+            LightsOutUIAction::ToggleHint => self.toggle_hint(),
+            LightsOutUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    // the lights, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = lightsoutui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        let size = self.game.size();
+        (
+            cols.max((size * LIGHT_COLS) as u16),
+            (size + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Lights Out, with the solver's presses to show if wanted
+pub fn run(args: Args) -> Result<(), String> {
+    let config: LightsOutConfig = common::cli::setup(&args.common, "lightsout")?;
+
+    let size = args.size.or(config.size).unwrap_or(DEFAULT_SIZE);
+    if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+        return Err(format!(
+            "bad [lightsout] config: size {size} (goes from {MIN_SIZE} to {MAX_SIZE})"
+        ));
+    }
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("lightsout seed {seed}, size {size}");
+
+    let mut app = LightsOutApp::new(size, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &lightsoutui::ACTIONS)
+        .map_err(|e| format!("bad [lightsout.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
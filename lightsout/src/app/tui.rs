@@ -0,0 +1,26 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::LightsOutApp;
+use crate::lightsoutui;
+
+impl TuiGame for LightsOutApp {
+    fn draw(&self, frame: &mut Frame) {
+        let board = Board::new(
+            self.light_lines()
+                .iter()
+                .map(|line| line.iter().map(tui::span).collect::<Line>())
+                .collect(),
+        );
+
+        let status = StatusBar::new(lightsoutui::help_text())
+            .line(self.counters())
+            .line(self.hint_line())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
@@ -0,0 +1,31 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Lights Out
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on putting the lights out and achievements")
+}))]
+pub struct Args {
+    /// Lights across (and down), from 3 to 10
+    /// [default: 5, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(3..=10))]
+    pub size: Option<u8>,
+
+    /// Random seed for which lights start on (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [lightsout] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LightsOutConfig {
+    /// lights across (and down), from 3 to 10
+    pub size: Option<u8>,
+    /// keys for each action, e.g. hint = "?"
+    pub keys: KeyMap,
+}
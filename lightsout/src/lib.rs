@@ -0,0 +1,22 @@
+//! Lights Out: the game core (a square of lights, each pressed to switch it
+//! and its neighbours), a solver working out the fewest presses by linear
+//! algebra over GF(2), plus the crossterm frontend for playing it in a
+//! terminal
+
+mod lightsout;
+mod solver;
+pub use lightsout::{LightsOut, PressResult};
+pub use solver::{solve, Solver};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod lightsoutui;
+#[cfg(feature = "terminal")]
+pub use app::{run, LightsOutApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
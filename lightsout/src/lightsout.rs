@@ -0,0 +1,149 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// What pressing a button led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PressResult {
+    Toggled,
+    /// the press put every light out, after this many
+    Solved(usize),
+    /// the lights are already all out
+    GameOver,
+}
+
+/// Lights Out: a square of lights, each a button that switches it and the
+/// (up to four) lights next to it on or off, to be put out
+#[derive(Debug, Clone, PartialEq)]
+pub struct LightsOut {
+    size: usize,
+    lit: Vec<bool>, // row by row
+    presses: usize,
+}
+
+impl LightsOut {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Lights [size] across, some on (the same seed always gives the same
+    /// ones). They're switched on by pressing buttons at random, so pressing
+    /// the same ones again always puts them out
+    pub fn random(size: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Self::from_lights(size, vec![false; size * size]);
+        while game.is_solved() {
+            for i in 0..size {
+                for j in 0..size {
+                    if rng.gen_bool(0.5) {
+                        game.toggle(Point(i, j));
+                    }
+                }
+            }
+        }
+        debug!("lights: {:?}", game.lit);
+        game
+    }
+
+    /// Lights [size] across, [lit] (row by row) on. Not every pattern can
+    /// be put out: see [crate::solve]
+    pub fn from_lights(size: usize, lit: Vec<bool>) -> Self {
+        assert_eq!(lit.len(), size * size, "lights don't fit");
+        Self {
+            size,
+            lit,
+            presses: 0,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Press the button at [p], switching it and its neighbours
+    pub fn press(&mut self, p: Point) -> PressResult {
+        if self.is_solved() {
+            return PressResult::GameOver;
+        }
+        self.toggle(p);
+        self.presses += 1;
+        match self.is_solved() {
+            true => PressResult::Solved(self.presses),
+            false => PressResult::Toggled,
+        }
+    }
+
+    /// Lights the button at [p] switches: itself, and its neighbours
+    pub fn switched_by(&self, Point(i, j): Point) -> impl Iterator<Item = Point> {
+        let size = self.size;
+        [
+            Some(Point(i, j)),
+            i.checked_sub(1).map(|i| Point(i, j)),
+            Some(i + 1).filter(|&i| i < size).map(|i| Point(i, j)),
+            j.checked_sub(1).map(|j| Point(i, j)),
+            Some(j + 1).filter(|&j| j < size).map(|j| Point(i, j)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_lit(&self, Point(i, j): Point) -> bool {
+        self.lit[i * self.size + j]
+    }
+
+    /// Lights row by row, on or off
+    pub fn lights(&self) -> &[bool] {
+        &self.lit
+    }
+
+    /// Lights still on
+    pub fn lit_count(&self) -> usize {
+        self.lit.iter().filter(|&&on| on).count()
+    }
+
+    /// Buttons pressed so far
+    pub fn presses(&self) -> usize {
+        self.presses
+    }
+
+    pub fn is_solved(&self) -> bool {
+        !self.lit.contains(&true)
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn toggle(&mut self, p: Point) {
+        for Point(i, j) in self.switched_by(p).collect::<Vec<_>>() {
+            self.lit[i * self.size + j] ^= true;
+        }
+    }
+}
+
+impl Core for LightsOut {
+    type Action = Point;
+    type Outcome = PressResult;
+    /// Whether the light's on
+    type Cell = bool;
+
+    fn size(&self) -> (usize, usize) {
+        (self.size, self.size)
+    }
+
+    fn cell(&self, p: Point) -> bool {
+        self.is_lit(p)
+    }
+
+    fn act(&mut self, p: Point) -> PressResult {
+        self.press(p)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_solved()
+    }
+}
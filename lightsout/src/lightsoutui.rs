@@ -0,0 +1,125 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+/// Help lines under the lights, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("lightsout.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LightsOutUIAction {
+    Wait,
+    Move(MoveDirection),
+    /// press the button under the cursor
+    Press,
+    /// show or hide the buttons the solver would press
+    ToggleHint,
+    NewGame,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, LightsOutUIAction); 10] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        LightsOutUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        LightsOutUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        LightsOutUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        LightsOutUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), LightsOutUIAction::Press),
+    (KeyBinding::key(KeyCode::Enter), LightsOutUIAction::Press),
+    (KeyBinding::char('h'), LightsOutUIAction::ToggleHint),
+    (KeyBinding::char('n'), LightsOutUIAction::NewGame),
+    (KeyBinding::char('q'), LightsOutUIAction::Quit),
+    (KeyBinding::ctrl('c'), LightsOutUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, LightsOutUIAction); 8] = [
+    ("up", LightsOutUIAction::Move(MoveDirection::Up)),
+    ("down", LightsOutUIAction::Move(MoveDirection::Down)),
+    ("left", LightsOutUIAction::Move(MoveDirection::Left)),
+    ("right", LightsOutUIAction::Move(MoveDirection::Right)),
+    ("press", LightsOutUIAction::Press),
+    ("hint", LightsOutUIAction::ToggleHint),
+    ("new_game", LightsOutUIAction::NewGame),
+    ("quit", LightsOutUIAction::Quit),
+];
+
+pub struct LightsOutUI {
+    cursor: Point,
+    size: usize,
+    pub bindings: Bindings<LightsOutUIAction>,
+}
+
+impl LightsOutUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cursor starting in the middle of lights [size] across
+    pub fn new(size: usize) -> Self {
+        Self {
+            cursor: Point(size / 2, size / 2),
+            size,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> LightsOutUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(LightsOutUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> LightsOutUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => LightsOutUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a light, unless that's off the board
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let Point(i, j) = self.cursor;
+        let (i, j) = match dir {
+            MoveDirection::Up => (i.checked_sub(1), Some(j)),
+            MoveDirection::Down => (Some(i + 1).filter(|&i| i < self.size), Some(j)),
+            MoveDirection::Left => (Some(i), j.checked_sub(1)),
+            MoveDirection::Right => (Some(i), Some(j + 1).filter(|&j| j < self.size)),
+        };
+        let (Some(i), Some(j)) = (i, j) else {
+            return Err("already at the edge".into());
+        };
+        self.cursor = Point(i, j);
+        Ok(())
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+}
@@ -0,0 +1,160 @@
+use common::{Core, Player, Point};
+use log::debug;
+
+use crate::lightsout::LightsOut;
+
+// most buttons whose presses can go either way (on some sizes, pressing
+// certain sets of buttons changes nothing, so some solutions are longer
+// than others) for every way to be tried in search of the fewest presses.
+// Up to 10 across there are at most 8 (on 4x4 and 9x9)
+const MAX_FREE: usize = 16;
+
+/// Buttons to press (each once, in any order) to put every light out, as
+/// few as there can be, or none if the lights can't be put out.
+///
+/// Pressing a button twice undoes it, and the order presses come in makes
+/// no difference, so all that matters is which buttons get pressed: a bit
+/// for each. Each light then has to be switched an odd number of times if
+/// it's on, and an even one if it's off, which is a set of linear equations
+/// (one per light, adding up the buttons next to it, mod 2) solved by
+/// Gaussian elimination
+pub fn solve(game: &LightsOut) -> Option<Vec<Point>> {
+    let size = game.size();
+    let n = size * size;
+    let point = |k: usize| Point(k / size, k % size);
+
+    // a row per light: the buttons switching it, then whether it's on
+    let mut rows: Vec<Bits> = (0..n)
+        .map(|k| {
+            let mut row = Bits::new(n + 1);
+            for Point(i, j) in game.switched_by(point(k)) {
+                row.flip(i * size + j);
+            }
+            if game.lights()[k] {
+                row.flip(n);
+            }
+            row
+        })
+        .collect();
+
+    // reduced row echelon form: each pivot column has a single 1, in its row
+    let mut pivots = Vec::new(); // column of each row's leading 1
+    for col in 0..n {
+        let r = pivots.len();
+        let Some(found) = (r..n).find(|&k| rows[k].get(col)) else {
+            continue;
+        };
+        rows.swap(r, found);
+        let pivot = rows[r].clone();
+        for (k, row) in rows.iter_mut().enumerate() {
+            if k != r && row.get(col) {
+                row.xor(&pivot);
+            }
+        }
+        pivots.push(col);
+    }
+    // rows past the pivots have no buttons left in them, so a light there
+    // would have to be switched by pressing nothing
+    if rows[pivots.len()..].iter().any(|row| row.get(n)) {
+        return None;
+    }
+
+    // one solution (leaving every free button unpressed), and the sets of
+    // buttons that change nothing (a free button, plus the pivot buttons
+    // undoing it), any of which can be pressed on top of it
+    let mut presses = Bits::new(n);
+    for (r, &col) in pivots.iter().enumerate() {
+        if rows[r].get(n) {
+            presses.flip(col);
+        }
+    }
+    let free: Vec<usize> = (0..n).filter(|col| !pivots.contains(col)).collect();
+    let quiet: Vec<Bits> = free
+        .iter()
+        .map(|&f| {
+            let mut bits = Bits::new(n);
+            bits.flip(f);
+            for (r, &col) in pivots.iter().enumerate() {
+                if rows[r].get(f) {
+                    bits.flip(col);
+                }
+            }
+            bits
+        })
+        .collect();
+
+    // try every mix of those, one change at a time (in Gray code order)
+    let mut fewest = presses.clone();
+    if quiet.len() <= MAX_FREE {
+        for k in 1..1usize << quiet.len() {
+            presses.xor(&quiet[k.trailing_zeros() as usize]);
+            if presses.count() < fewest.count() {
+                fewest = presses.clone();
+            }
+        }
+    }
+    debug!(
+        "{} presses put the lights out ({} free buttons)",
+        fewest.count(),
+        free.len()
+    );
+    Some((0..n).filter(|&k| fewest.get(k)).map(point).collect())
+}
+
+/// Computer player (and hint-giver) pressing the buttons of the shortest
+/// way to put the lights out, a row at a time from the top
+pub struct Solver;
+
+impl Solver {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player<LightsOut> for Solver {
+    fn choose_action(&mut self, game: &LightsOut) -> Option<Point> {
+        if game.is_over() {
+            return None;
+        }
+        solve(game)?.into_iter().next()
+    }
+}
+
+// bits packed into words (lights out of 10 across take 100 buttons, more
+// than fit in one)
+#[derive(Clone)]
+struct Bits(Vec<u64>);
+
+impl Bits {
+    fn new(len: usize) -> Self {
+        Self(vec![0; len.div_ceil(64)])
+    }
+
+    fn get(&self, k: usize) -> bool {
+        self.0[k / 64] >> (k % 64) & 1 == 1
+    }
+
+    fn flip(&mut self, k: usize) {
+        self.0[k / 64] ^= 1 << (k % 64);
+    }
+
+    fn xor(&mut self, other: &Bits) {
+        for (a, b) in self.0.iter_mut().zip(&other.0) {
+            *a ^= b;
+        }
+    }
+
+    fn count(&self) -> u32 {
+        self.0.iter().map(|w| w.count_ones()).sum()
+    }
+}
@@ -0,0 +1,453 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Player, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::MastermindConfig;
+use crate::mastermind::{GuessResult, Mastermind, Score};
+use crate::mastermindui::{self, MastermindUI, MastermindUIAction};
+use crate::solver::{candidates, Solver};
+
+// code length, colors and guesses when nothing else sets them
+const DEFAULT_LENGTH: u8 = 4;
+const DEFAULT_COLORS: u8 = 6;
+const DEFAULT_GUESSES: u8 = 10;
+
+// most pegs and colors there can be (colors go up to the digit keys, and
+// the theme's colors for them)
+const MAX_LENGTH: u8 = 6;
+const MAX_COLORS: u8 = 8;
+const MAX_GUESSES: u8 = 20;
+
+// terminal columns each peg takes up, including the space after it
+const PEG_COLS: usize = 2;
+
+// columns before the pegs of each row (its number)
+const NUMBER_COLS: usize = 4;
+
+// lines of text around the rows of guesses (code, rule, gap, colors,
+// solver, score, message)
+const STATUS_ROWS: usize = 7;
+
+/// Mastermind app (the code being broken, the guess being put together,
+/// the solver and its best next guess, the score over every game so far,
+/// and UI state)
+pub struct MastermindApp {
+    game: Mastermind,
+    ui: MastermindUI,
+    entry: Vec<u8>, // the guess being put together
+    solver: Solver,
+    show_solver: bool,
+    hint: Option<(Vec<u8>, usize)>, // best next guess, and the codes still possible
+    rng: StdRng,                    // for picking codes
+    wins: u32,
+    losses: u32,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last code broken or not
+    over: bool,
+}
+
+impl MastermindApp {
+    /// Break codes [length] pegs long of [colors] colors, in [guesses],
+    /// picked with [seed]
+    pub fn new(length: u8, colors: u8, guesses: u8, show_solver: bool, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let game = Mastermind::random(length as usize, colors, guesses as usize, rng.gen());
+        let mut app = Self {
+            game,
+            ui: MastermindUI::new(),
+            entry: Vec::new(),
+            solver: Solver::new(),
+            show_solver,
+            hint: None,
+            rng,
+            wins: 0,
+            losses: 0,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        };
+        app.update_hint();
+        app
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_game(&mut self) {
+        let (length, colors) = (self.game.length(), self.game.colors());
+        let max_guesses = self.game.max_guesses();
+        self.game = Mastermind::random(length, colors, max_guesses, self.rng.gen());
+        self.entry.clear();
+        self.message = "".to_string().reset();
+        self.update_hint();
+    }
+
+    // work out the solver's best next guess, if it's showing
+    fn update_hint(&mut self) {
+        self.hint = match self.show_solver {
+            true => self.solver_guess(),
+            false => None,
+        };
+    }
+
+    // the solver's best next guess, and how many codes are still possible
+    fn solver_guess(&mut self) -> Option<(Vec<u8>, usize)> {
+        let guess = self.solver.choose_action(&self.game)?;
+        Some((guess, candidates(&self.game).len()))
+    }
+
+    fn add_peg(&mut self, peg: u8) {
+        if self.game.is_over()
+            || peg >= self.game.colors()
+            || self.entry.len() == self.game.length()
+        {
+            bell::ring("invalid move");
+            return;
+        }
+        self.entry.push(peg);
+    }
+
+    fn submit(&mut self) {
+        if !self.game.is_over() && self.entry.len() < self.game.length() {
+            bell::ring("invalid move");
+            self.message = locale::text("mastermind.unfinished")
+                .to_string()
+                .with(theme::color(Role::Danger));
+            return;
+        }
+        let res = self.game.guess(&self.entry);
+        self.handle_res(res);
+    }
+
+    // fill the guess in with the solver's
+    fn use_solver(&mut self) {
+        if self.hint.is_none() {
+            self.hint = self.solver_guess();
+        }
+        match &self.hint {
+            Some((guess, _)) => self.entry = guess.clone(),
+            None => bell::ring("invalid move"),
+        }
+    }
+
+    fn handle_res(&mut self, res: GuessResult) {
+        let again = locale::text("mastermind.again");
+        match res {
+            GuessResult::Won(guesses) => {
+                bell::ring("win");
+                self.wins += 1;
+                self.outcome = Outcome::Won;
+                self.message = format!(
+                    "{} ({} {guesses}) {again}",
+                    locale::text("mastermind.win"),
+                    locale::text("mastermind.guesses")
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success));
+            }
+            GuessResult::Lost => {
+                bell::ring("lose");
+                self.losses += 1;
+                self.outcome = Outcome::Lost;
+                self.message = format!("{} {again}", locale::text("mastermind.lose"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+            }
+            GuessResult::GameOver | GuessResult::Invalid => {
+                bell::ring("invalid move");
+                return;
+            }
+            GuessResult::Scored(_) => self.message = "".to_string().reset(),
+        }
+        self.entry.clear();
+        self.update_hint();
+    }
+
+    /// The code, hidden until the game's over
+    fn code_line(&self) -> Vec<StyledContent<String>> {
+        let mut line = vec![" ".repeat(NUMBER_COLS).reset()];
+        for &peg in self.game.secret() {
+            line.push(match self.game.is_over() {
+                true => styled_peg(peg),
+                false => "?".to_string().with(theme::color(Role::Hidden)),
+            });
+            line.push(" ".to_string().reset());
+        }
+        line
+    }
+
+    // columns a row of guesses takes up, with its score
+    fn board_cols(&self) -> usize {
+        NUMBER_COLS + PEG_COLS * self.game.length() + 1 + self.game.length()
+    }
+
+    // rule between the code and the guesses
+    fn rule_line(&self) -> StyledContent<String> {
+        "─"
+            .repeat(self.board_cols())
+            .with(theme::color(Role::Muted))
+    }
+
+    /// Rows of guesses, a (styled) piece per peg: each guess made with its
+    /// score, the one being put together, and dots for the ones to come
+    fn guess_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let guesses = self.game.guesses();
+        let length = self.game.length();
+        (0..self.game.max_guesses())
+            .map(|i| {
+                let current = i == guesses.len() && !self.game.is_over();
+                let number = format!("{:>2}  ", i + 1);
+                let mut line = vec![match current {
+                    true => number.bold().with(theme::color(Role::Accent)),
+                    false => number.with(theme::color(Role::Muted)),
+                }];
+                for j in 0..length {
+                    let peg = match (guesses.get(i), current) {
+                        (Some((code, _)), _) => Some(code[j]),
+                        (None, true) => self.entry.get(j).copied(),
+                        (None, false) => None,
+                    };
+                    line.push(match peg {
+                        Some(peg) => styled_peg(peg),
+                        None if current => "_".to_string().with(theme::color(Role::Accent)),
+                        None => "·".to_string().with(theme::color(Role::Muted)),
+                    });
+                    line.push(" ".to_string().reset());
+                }
+                if let Some(&(_, score)) = guesses.get(i) {
+                    line.push(" ".to_string().reset());
+                    line.extend(score_pegs(score, length));
+                }
+                line
+            })
+            .collect()
+    }
+
+    /// Which key's which color, a (styled) piece per color
+    fn colors_line(&self) -> Vec<StyledContent<String>> {
+        let mut line = vec![locale::text("mastermind.colors").to_string().reset()];
+        for peg in 0..self.game.colors() {
+            line.push(format!(" {}", peg + 1).with(theme::color(Role::Text)));
+            if style::color_enabled() {
+                line.push(styled_peg(peg));
+            }
+        }
+        line
+    }
+
+    /// The solver's best next guess, if it's showing
+    fn solver_line(&self) -> Vec<StyledContent<String>> {
+        let Some((guess, left)) = &self.hint else {
+            return vec!["".to_string().reset()];
+        };
+        let mut line = vec![format!("{} ", locale::text("mastermind.best")).reset()];
+        for &peg in guess {
+            line.push(styled_peg(peg));
+            line.push(" ".to_string().reset());
+        }
+        line.push(
+            format!(" ({left} {})", locale::text("mastermind.possible"))
+                .with(theme::color(Role::Muted)),
+        );
+        line
+    }
+
+    // guesses made, out of the most there can be, and games won and lost
+    fn score_line(&self) -> String {
+        format!(
+            "{} {}/{}   {} {}   {} {}",
+            locale::text("mastermind.guesses"),
+            self.game.guesses().len(),
+            self.game.max_guesses(),
+            locale::text("mastermind.won"),
+            self.wins,
+            locale::text("mastermind.lost"),
+            self.losses
+        )
+    }
+}
+
+// Pretty-print
+impl fmt::Display for MastermindApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![self.code_line(), vec![self.rule_line()]];
+        lines.extend(self.guess_lines());
+        for line in lines {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n")?;
+        for line in [self.colors_line(), self.solver_line()] {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "{}\r\n", self.score_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", mastermindui::help_text())
+    }
+}
+
+impl Game for MastermindApp {
+    fn name(&self) -> &'static str {
+        "mastermind"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != MastermindUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            MastermindUIAction::Quit => self.over = true,
+            MastermindUIAction::NewGame => self.new_game(),
+            MastermindUIAction::Peg(peg) => self.add_peg(peg),
+            MastermindUIAction::Erase => {
+                if self.entry.pop().is_none() {
+                    bell::ring("invalid move");
+                }
+            }
+            MastermindUIAction::Submit => self.submit(),
+            MastermindUIAction::ToggleSolver => {
+                self.show_solver = !self.show_solver;
+                self.update_hint();
+            }
+            MastermindUIAction::UseSolver => self.use_solver(),
+            MastermindUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    // the rows of guesses and the solver's line, and the help text on its
+    // lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = mastermindui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        let codes = (self.game.colors() as usize).pow(self.game.length() as u32);
+        let solver = text_width(&format!(
+            "{}  ({codes} {})",
+            locale::text("mastermind.best"),
+            locale::text("mastermind.possible")
+        )) as usize
+            + PEG_COLS * self.game.length();
+        (
+            cols.max(solver.max(self.board_cols()) as u16),
+            (self.game.max_guesses() + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// A peg of color [peg] (counting from 0): a dot in the theme's color for
+/// it, or its digit without colors
+fn styled_peg(peg: u8) -> StyledContent<String> {
+    match style::color_enabled() {
+        true => "●".to_string().with(theme::current().cell(peg + 1)),
+        false => (peg + 1).to_string().bold(),
+    }
+}
+
+/// Score pegs for [score] of a guess [length] pegs long: a black one for
+/// each peg in the right place, a white one for each right color in the
+/// wrong place, and dots for the rest
+fn score_pegs(score: Score, length: usize) -> Vec<StyledContent<String>> {
+    let mut pegs = Vec::new();
+    for _ in 0..score.exact {
+        pegs.push("●".to_string().bold().with(theme::color(Role::Text)));
+    }
+    for _ in 0..score.near {
+        pegs.push("○".to_string().with(theme::color(Role::Text)));
+    }
+    for _ in score.exact + score.near..length {
+        pegs.push("·".to_string().with(theme::color(Role::Muted)));
+    }
+    pegs
+}
+
+/// Play Mastermind, with the solver's best next guess on hand
+pub fn run(args: Args) -> Result<(), String> {
+    let config: MastermindConfig = common::cli::setup(&args.common, "mastermind")?;
+
+    let length = args.length.or(config.length).unwrap_or(DEFAULT_LENGTH);
+    if !(2..=MAX_LENGTH).contains(&length) {
+        return Err(format!(
+            "bad [mastermind] config: length {length} (goes from 2 to {MAX_LENGTH})"
+        ));
+    }
+    let colors = args.colors.or(config.colors).unwrap_or(DEFAULT_COLORS);
+    if !(2..=MAX_COLORS).contains(&colors) {
+        return Err(format!(
+            "bad [mastermind] config: colors {colors} (goes from 2 to {MAX_COLORS})"
+        ));
+    }
+    let guesses = args.guesses.or(config.guesses).unwrap_or(DEFAULT_GUESSES);
+    if !(1..=MAX_GUESSES).contains(&guesses) {
+        return Err(format!(
+            "bad [mastermind] config: guesses {guesses} (goes from 1 to {MAX_GUESSES})"
+        ));
+    }
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("mastermind seed {seed}, {length} pegs of {colors} colors");
+
+    let show_solver = args.solver || config.solver;
+    let mut app = MastermindApp::new(length, colors, guesses, show_solver, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &mastermindui::ACTIONS)
+        .map_err(|e| format!("bad [mastermind.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
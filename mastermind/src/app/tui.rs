@@ -0,0 +1,31 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::MastermindApp;
+use crate::mastermindui;
+
+impl TuiGame for MastermindApp {
+    fn draw(&self, frame: &mut Frame) {
+        let mut rows: Vec<Line> = vec![
+            self.code_line().iter().map(tui::span).collect(),
+            Line::from(tui::span(&self.rule_line())),
+        ];
+        rows.extend(
+            self.guess_lines()
+                .iter()
+                .map(|line| line.iter().map(tui::span).collect::<Line>()),
+        );
+        let board = Board::new(rows);
+
+        let status = StatusBar::new(mastermindui::help_text())
+            .line(self.colors_line().iter().map(tui::span).collect::<Line>())
+            .line(self.solver_line().iter().map(tui::span).collect::<Line>())
+            .line(self.score_line())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
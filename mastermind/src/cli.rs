@@ -0,0 +1,45 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Mastermind
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on unfinished guesses, wins and achievements")
+}))]
+pub struct Args {
+    /// Pegs in the code, from 2 to 6
+    /// [default: 4, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(2..=6))]
+    pub length: Option<u8>,
+
+    /// Colors a peg can be, from 2 to 8
+    /// [default: 6, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(2..=8))]
+    pub colors: Option<u8>,
+
+    /// Guesses allowed before the code is given away
+    /// [default: 10, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=20))]
+    pub guesses: Option<u8>,
+
+    /// Start with the solver's best next guess showing (h shows and hides it)
+    #[arg(long)]
+    pub solver: bool,
+
+    /// Random seed for picking codes (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [mastermind] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MastermindConfig {
+    /// pegs in the code, from 2 to 6
+    pub length: Option<u8>,
+    /// colors a peg can be, from 2 to 8
+    pub colors: Option<u8>,
+    /// guesses allowed, from 1 to 20
+    pub guesses: Option<u8>,
+    /// start with the solver's best next guess showing
+    pub solver: bool,
+    /// keys for each action, e.g. submit = ["enter", "space"]
+    pub keys: KeyMap,
+}
@@ -0,0 +1,21 @@
+//! Mastermind: the game core (a secret code of colored pegs, and the
+//! guesses at it), a minimax solver, plus the crossterm frontend for
+//! playing it in a terminal
+
+mod mastermind;
+mod solver;
+pub use mastermind::{score, GuessResult, Mastermind, Score};
+pub use solver::{candidates, Solver};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod mastermindui;
+#[cfg(feature = "terminal")]
+pub use app::{run, MastermindApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
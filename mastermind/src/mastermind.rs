@@ -0,0 +1,164 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// How close a guess is: pegs of the right color in the right place, and
+/// pegs of a right color in the wrong place
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub exact: usize,
+    pub near: usize,
+}
+
+/// Score of [guess] against [secret] (codes the same length, of colors
+/// counted from 0)
+pub fn score(secret: &[u8], guess: &[u8]) -> Score {
+    let exact = secret.iter().zip(guess).filter(|(s, g)| s == g).count();
+    // pegs of each color in both, counted once per color
+    let common: usize = guess
+        .iter()
+        .enumerate()
+        .filter(|&(k, peg)| !guess[..k].contains(peg))
+        .map(|(_, peg)| {
+            let count = |code: &[u8]| code.iter().filter(|&p| p == peg).count();
+            count(secret).min(count(guess))
+        })
+        .sum();
+    Score {
+        exact,
+        near: common - exact,
+    }
+}
+
+/// What a guess led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GuessResult {
+    Scored(Score),
+    /// the guess was the code, after this many
+    Won(usize),
+    /// the guess was wrong, and the last one allowed
+    Lost,
+    /// the guess is the wrong length, or has a color not in the game
+    Invalid,
+    /// the game has already ended
+    GameOver,
+}
+
+/// A game of Mastermind: the secret code, and the guesses at it so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mastermind {
+    secret: Vec<u8>,
+    colors: u8,
+    max_guesses: usize,
+    guesses: Vec<(Vec<u8>, Score)>,
+}
+
+impl Mastermind {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Game breaking [secret] (colors from 0 to [colors] - 1) in at most
+    /// [max_guesses]
+    pub fn new(secret: Vec<u8>, colors: u8, max_guesses: usize) -> Self {
+        assert!(
+            secret.iter().all(|&peg| peg < colors),
+            "code has colors outside the game"
+        );
+        Self {
+            secret,
+            colors,
+            max_guesses,
+            guesses: Vec::new(),
+        }
+    }
+
+    /// Game with a random code [length] pegs long (the same seed always
+    /// gives the same code)
+    pub fn random(length: usize, colors: u8, max_guesses: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let secret = (0..length).map(|_| rng.gen_range(0..colors)).collect();
+        Self::new(secret, colors, max_guesses)
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Guess that the code is [code], and hear how close that is
+    pub fn guess(&mut self, code: &[u8]) -> GuessResult {
+        if self.is_over() {
+            return GuessResult::GameOver;
+        }
+        if code.len() != self.secret.len() || code.iter().any(|&peg| peg >= self.colors) {
+            return GuessResult::Invalid;
+        }
+        let score = score(&self.secret, code);
+        debug!("guess {code:?}: {score:?}");
+        self.guesses.push((code.to_vec(), score));
+        if self.is_won() {
+            GuessResult::Won(self.guesses.len())
+        } else if self.is_lost() {
+            GuessResult::Lost
+        } else {
+            GuessResult::Scored(score)
+        }
+    }
+
+    /// Pegs in the code
+    pub fn length(&self) -> usize {
+        self.secret.len()
+    }
+
+    /// Colors a peg can be
+    pub fn colors(&self) -> u8 {
+        self.colors
+    }
+
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
+
+    /// Every guess so far, with its score
+    pub fn guesses(&self) -> &[(Vec<u8>, Score)] {
+        &self.guesses
+    }
+
+    /// The code being broken (for showing once the game's over)
+    pub fn secret(&self) -> &[u8] {
+        &self.secret
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.guesses
+            .last()
+            .is_some_and(|(_, score)| score.exact == self.length())
+    }
+
+    pub fn is_lost(&self) -> bool {
+        !self.is_won() && self.guesses.len() >= self.max_guesses
+    }
+}
+
+impl Core for Mastermind {
+    type Action = Vec<u8>;
+    type Outcome = GuessResult;
+    /// A peg of a guess made, or nothing in rows not guessed yet
+    type Cell = Option<u8>;
+
+    fn size(&self) -> (usize, usize) {
+        (self.max_guesses, self.length())
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Option<u8> {
+        self.guesses.get(i).map(|(code, _)| code[j])
+    }
+
+    fn act(&mut self, code: Vec<u8>) -> GuessResult {
+        self.guess(&code)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_won() || self.is_lost()
+    }
+}
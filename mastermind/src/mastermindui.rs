@@ -0,0 +1,91 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+/// Help lines under the board, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("mastermind.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MastermindUIAction {
+    Wait,
+    /// put a peg of color k (counting from 0) next in the guess
+    Peg(u8),
+    /// take the last peg back out of the guess
+    Erase,
+    Submit,
+    /// show or hide the solver's best next guess
+    ToggleSolver,
+    /// fill the guess in with the solver's best next guess
+    UseSolver,
+    NewGame,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, MastermindUIAction); 16] = [
+    (KeyBinding::char('1'), MastermindUIAction::Peg(0)),
+    (KeyBinding::char('2'), MastermindUIAction::Peg(1)),
+    (KeyBinding::char('3'), MastermindUIAction::Peg(2)),
+    (KeyBinding::char('4'), MastermindUIAction::Peg(3)),
+    (KeyBinding::char('5'), MastermindUIAction::Peg(4)),
+    (KeyBinding::char('6'), MastermindUIAction::Peg(5)),
+    (KeyBinding::char('7'), MastermindUIAction::Peg(6)),
+    (KeyBinding::char('8'), MastermindUIAction::Peg(7)),
+    (
+        KeyBinding::key(KeyCode::Backspace),
+        MastermindUIAction::Erase,
+    ),
+    (KeyBinding::key(KeyCode::Enter), MastermindUIAction::Submit),
+    (KeyBinding::char(' '), MastermindUIAction::Submit),
+    (KeyBinding::char('h'), MastermindUIAction::ToggleSolver),
+    (KeyBinding::key(KeyCode::Tab), MastermindUIAction::UseSolver),
+    (KeyBinding::char('n'), MastermindUIAction::NewGame),
+    (KeyBinding::char('q'), MastermindUIAction::Quit),
+    (KeyBinding::ctrl('c'), MastermindUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, MastermindUIAction); 6] = [
+    ("erase", MastermindUIAction::Erase),
+    ("submit", MastermindUIAction::Submit),
+    ("toggle_solver", MastermindUIAction::ToggleSolver),
+    ("use_solver", MastermindUIAction::UseSolver),
+    ("new_game", MastermindUIAction::NewGame),
+    ("quit", MastermindUIAction::Quit),
+];
+
+pub struct MastermindUI {
+    pub bindings: Bindings<MastermindUIAction>,
+}
+
+impl MastermindUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> MastermindUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(MastermindUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> MastermindUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => MastermindUIAction::Wait,
+        }
+    }
+}
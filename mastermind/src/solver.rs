@@ -0,0 +1,184 @@
+use common::{Core, Player};
+use log::debug;
+
+use crate::mastermind::{score, Mastermind};
+
+// most guesses-against-codes scored to pick a guess. Small games look at
+// every guess worth making (Knuth's way, never taking more than five
+// guesses at four pegs of six colors); bigger ones only at a sample of
+// both, which is quick and nearly as good
+const WORK_LIMIT: usize = 1_000_000;
+
+/// Computer player (and hint-giver) guessing the code that leaves the
+/// fewest codes possible whatever its score turns out to be (the minimax
+/// rule). Ties go to a code that could still be the answer, then to the
+/// lowest. Always guesses the same way
+pub struct Solver;
+
+impl Solver {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player<Mastermind> for Solver {
+    fn choose_action(&mut self, game: &Mastermind) -> Option<Vec<u8>> {
+        if game.is_over() {
+            return None;
+        }
+        let candidates: Vec<Counted> = candidates(game)
+            .into_iter()
+            .map(|code| Counted::new(code, game.colors()))
+            .collect();
+        if candidates.len() <= 2 {
+            return candidates.into_iter().next().map(|code| code.pegs);
+        }
+        // colors no guess has had yet are all alike, so of guesses that
+        // only differ in which of those they use, one will do
+        let fresh: Vec<u8> = (0..game.colors())
+            .filter(|c| game.guesses().iter().all(|(guess, _)| !guess.contains(c)))
+            .collect();
+        let pool: Vec<Counted> = all_codes(game.length(), game.colors())
+            .into_iter()
+            .filter(|code| is_first_of_its_kind(code, &fresh))
+            .map(|code| Counted::new(code, game.colors()))
+            .collect();
+        // too many to go through them all: an even spread of each instead
+        let (pool, sample) = match pool.len() * candidates.len() <= WORK_LIMIT {
+            true => (
+                spread(&pool, pool.len()),
+                spread(&candidates, candidates.len()),
+            ),
+            false => {
+                let pool = spread(&pool, (WORK_LIMIT as f64).sqrt() as usize);
+                let take = WORK_LIMIT / pool.len();
+                (pool, spread(&candidates, take))
+            }
+        };
+
+        let length = game.length();
+        let mut best: Option<(usize, bool, &Counted)> = None; // (worst case, possible?, guess)
+        let mut counts = vec![0; (length + 1) * (length + 1)];
+        for guess in pool {
+            counts.fill(0);
+            for code in &sample {
+                let (exact, common) = code.compare(guess);
+                counts[exact * (length + 1) + common - exact] += 1;
+            }
+            let worst = counts.iter().copied().max().unwrap_or_default();
+            let possible = candidates
+                .binary_search_by(|code| code.pegs.cmp(&guess.pegs))
+                .is_ok();
+            let better = match best {
+                None => true,
+                Some((best_worst, best_possible, _)) => {
+                    worst < best_worst || (worst == best_worst && possible && !best_possible)
+                }
+            };
+            if better {
+                best = Some((worst, possible, guess));
+            }
+        }
+        let (worst, _, guess) = best?;
+        debug!(
+            "solver guesses {:?}: at worst {worst} of {} left",
+            guess.pegs,
+            candidates.len()
+        );
+        Some(guess.pegs.clone())
+    }
+}
+
+/// Codes that would have scored every guess so far the way it did, lowest
+/// first
+pub fn candidates(game: &Mastermind) -> Vec<Vec<u8>> {
+    all_codes(game.length(), game.colors())
+        .into_iter()
+        .filter(|code| {
+            game.guesses()
+                .iter()
+                .all(|(guess, s)| score(code, guess) == *s)
+        })
+        .collect()
+}
+
+// a code, with how many pegs it has of each color (scoring two codes this
+// way is much quicker than with score, which the solver does millions of
+// times)
+struct Counted {
+    pegs: Vec<u8>,
+    counts: Vec<u8>,
+}
+
+impl Counted {
+    fn new(pegs: Vec<u8>, colors: u8) -> Self {
+        let mut counts = vec![0; colors as usize];
+        for &peg in &pegs {
+            counts[peg as usize] += 1;
+        }
+        Self { pegs, counts }
+    }
+
+    // pegs in the same place, and pegs of the same color anywhere
+    fn compare(&self, other: &Counted) -> (usize, usize) {
+        let exact = self
+            .pegs
+            .iter()
+            .zip(&other.pegs)
+            .filter(|(a, b)| a == b)
+            .count();
+        let common = self
+            .counts
+            .iter()
+            .zip(&other.counts)
+            .map(|(&a, &b)| a.min(b) as usize)
+            .sum();
+        (exact, common)
+    }
+}
+
+// whether [code] brings in the [fresh] colors (ones no guess has had) in
+// order, lowest first: every other code using them is the same guess with
+// the colors swapped round, and would split the codes left the same way
+fn is_first_of_its_kind(code: &[u8], fresh: &[u8]) -> bool {
+    let mut next = 0; // of fresh, the one to bring in next
+    for peg in code {
+        match fresh.iter().position(|c| c == peg) {
+            Some(k) if k == next => next += 1,
+            Some(k) if k > next => return false,
+            _ => {}
+        }
+    }
+    true
+}
+
+// at most [count] of [codes], spread evenly through them
+fn spread(codes: &[Counted], count: usize) -> Vec<&Counted> {
+    let step = codes.len().div_ceil(count).max(1);
+    codes.iter().step_by(step).collect()
+}
+
+// every code [length] pegs long, of [colors] colors, lowest first
+fn all_codes(length: usize, colors: u8) -> Vec<Vec<u8>> {
+    let mut codes = Vec::new();
+    let mut code = vec![0; length];
+    loop {
+        codes.push(code.clone());
+        // count up, the last peg fastest
+        let Some(k) = (0..length).rev().find(|&k| code[k] + 1 < colors) else {
+            return codes;
+        };
+        code[k] += 1;
+        code[k + 1..].fill(0);
+    }
+}
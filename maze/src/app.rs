@@ -0,0 +1,256 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{Core, Game, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::MazeConfig;
+use crate::generator::Algorithm;
+use crate::maze::{Maze, Move, MoveResult};
+use crate::mazeui::{self, MazeUI, MazeUIAction};
+use crate::render;
+
+// maze size when neither the command line nor the config file sets one
+// (just fits an 80x24 terminal)
+const DEFAULT_HEIGHT: u16 = 11;
+const DEFAULT_WIDTH: u16 = 38;
+
+// speed of the solver's search when nothing else sets one
+const DEFAULT_SOLVE_TICK: Duration = Duration::from_millis(30);
+
+// lines of text around the maze (title, gap, counters, message)
+const STATUS_ROWS: usize = 4;
+
+/// Maze app (the maze being explored, how to make the next one, and UI state)
+pub struct MazeApp {
+    maze: Maze,
+    ui: MazeUI,
+    size: (usize, usize),
+    algorithm: Algorithm,
+    rng: StdRng, // for the seeds of new mazes
+    solve_tick: Duration,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // won once any maze has been escaped
+    over: bool,
+}
+
+impl MazeApp {
+    /// Explore (height x width) mazes made with [algorithm], starting with
+    /// the one from [seed]
+    pub fn new(
+        (height, width): (usize, usize),
+        algorithm: Algorithm,
+        seed: u64,
+        solve_tick: Duration,
+    ) -> Self {
+        Self {
+            maze: Maze::generate(height, width, algorithm, seed),
+            ui: MazeUI::new(),
+            size: (height, width),
+            algorithm,
+            rng: StdRng::seed_from_u64(seed),
+            solve_tick,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_maze(&mut self) {
+        let (height, width) = self.size;
+        let seed = self.rng.gen();
+        info!("maze seed {seed}");
+        self.maze = Maze::generate(height, width, self.algorithm, seed);
+        self.message = "".to_string().reset();
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        match res {
+            MoveResult::Escaped => {
+                bell::ring("win");
+                self.outcome = Outcome::Won;
+                let mut text = locale::text("maze.win").to_string();
+                if self.maze.moves() as usize == self.maze.shortest() {
+                    text = format!("{} {text}", locale::text("maze.perfect"));
+                }
+                self.message = text
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Blocked => bell::ring("invalid move"),
+            MoveResult::Moved | MoveResult::Solving | MoveResult::Restarted => {
+                self.message = "".to_string().reset()
+            }
+        }
+    }
+
+    fn counters(&self) -> String {
+        format!(
+            "{} {}   {} {}",
+            locale::text("maze.moves"),
+            self.maze.moves(),
+            locale::text("maze.shortest"),
+            self.maze.shortest()
+        )
+    }
+
+    // maze size and how it was made
+    fn title(&self) -> String {
+        let (height, width) = self.size;
+        let algorithm = match self.algorithm {
+            Algorithm::Backtracker => "backtracker",
+            Algorithm::Kruskal => "kruskal",
+        };
+        format!("{height}x{width} ({algorithm})")
+    }
+}
+
+// Pretty-print
+impl fmt::Display for MazeApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\r\n", self.title().bold())?;
+        for row in render::render(&self.maze) {
+            for block in row {
+                write!(f, "{block}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.counters())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", mazeui::help_text())
+    }
+}
+
+impl Game for MazeApp {
+    fn name(&self) -> &'static str {
+        "maze"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != MazeUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            MazeUIAction::Quit => self.over = true,
+            MazeUIAction::NewMaze => self.new_maze(),
+            // (once out, the way to go is a new maze, or back to the start)
+            MazeUIAction::Move(Move::Walk(_)) if self.maze.is_escaped() => {}
+            MazeUIAction::Move(mv) => {
+                let res = self.maze.make_move(mv);
+                self.handle_res(res);
+            }
+            MazeUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // spread the solver's search, while it's looking
+    fn tick(&mut self) {
+        if self.maze.is_solving() {
+            self.maze.tick();
+            self.redraw.set(true);
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.solve_tick)
+    }
+
+    // the whole maze, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.size;
+        let help = mazeui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max(render::cols(width) as u16),
+            (render::rows(height) + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Explore mazes generated as set up on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: MazeConfig = common::cli::setup(&args.common, "maze")?;
+
+    let height = args.height.or(config.height).unwrap_or(DEFAULT_HEIGHT);
+    let width = args.width.or(config.width).unwrap_or(DEFAULT_WIDTH);
+    if !(2..=100).contains(&height) || !(2..=100).contains(&width) {
+        return Err(format!(
+            "bad [maze] config: {width}x{height} maze (sides go from 2 to 100)"
+        ));
+    }
+    let algorithm = args
+        .algorithm
+        .or(config.algorithm)
+        .unwrap_or(Algorithm::Backtracker);
+    let solve_tick = args
+        .solve_ms
+        .or(config.solve_ms)
+        .map_or(DEFAULT_SOLVE_TICK, Duration::from_millis);
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("maze seed {seed}");
+
+    let mut app = MazeApp::new(
+        (height as usize, width as usize),
+        algorithm,
+        seed,
+        solve_tick.max(Duration::from_millis(1)),
+    );
+    app.ui
+        .bindings
+        .remap(&config.keys, &mazeui::ACTIONS)
+        .map_err(|e| format!("bad [maze.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
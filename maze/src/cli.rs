@@ -0,0 +1,47 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+use crate::generator::Algorithm;
+
+/// Maze
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on walking into walls, escaping and achievements")
+}))]
+pub struct Args {
+    /// Maze height (in squares) [default: 11, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2..=100))]
+    pub height: Option<u16>,
+
+    /// Maze width (in squares) [default: 38, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2..=100))]
+    pub width: Option<u16>,
+
+    /// How mazes are carved out: backtracker (long winding corridors) or
+    /// kruskal (lots of short dead ends)
+    /// [default: backtracker, unless the config file picks another]
+    #[arg(long)]
+    pub algorithm: Option<Algorithm>,
+
+    /// Milliseconds between steps of the solver's search
+    /// [default: 30, unless the config file picks another]
+    #[arg(long)]
+    pub solve_ms: Option<u64>,
+
+    /// Random seed for generating the first maze (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
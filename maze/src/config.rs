@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+use crate::generator::Algorithm;
+
+/// Settings from the [maze] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MazeConfig {
+    /// maze height, in squares
+    pub height: Option<u16>,
+    /// maze width, in squares
+    pub width: Option<u16>,
+    /// how mazes are carved out: "backtracker" or "kruskal"
+    #[serde(deserialize_with = "common::config::from_str")]
+    pub algorithm: Option<Algorithm>,
+    /// milliseconds between steps of the solver's search
+    pub solve_ms: Option<u64>,
+    /// keys for each action, e.g. solve = ["h", "?"]
+    pub keys: KeyMap,
+}
@@ -0,0 +1,100 @@
+use std::str::FromStr;
+
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use common::Point;
+
+use crate::layout::{Direction, Layout};
+
+/// How a maze is carved out. Both make perfect mazes (exactly one way
+/// between any two squares), but they look different: the backtracker
+/// digs long winding corridors, Kruskal's lots of short dead ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+    Backtracker,
+    Kruskal,
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "backtracker" => Ok(Algorithm::Backtracker),
+            "kruskal" => Ok(Algorithm::Kruskal),
+            _ => Err(format!(
+                "unknown algorithm '{s}' (expected backtracker or kruskal)"
+            )),
+        }
+    }
+}
+
+/// A random (height x width) maze. The same seed always gives the same maze
+pub fn generate(height: usize, width: usize, algorithm: Algorithm, seed: u64) -> Layout {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut layout = Layout::closed(height, width);
+    match algorithm {
+        Algorithm::Backtracker => backtracker(&mut layout, &mut rng),
+        Algorithm::Kruskal => kruskal(&mut layout, &mut rng),
+    }
+    debug!("generated a {height}x{width} maze with {algorithm:?}");
+    layout
+}
+
+// random depth-first walk: dig into a random unvisited neighbor, and
+// back up to the last square that has one whenever there isn't one
+fn backtracker(layout: &mut Layout, rng: &mut StdRng) {
+    let (height, width) = layout.size();
+    let mut visited = vec![false; height * width];
+    let mut stack = vec![Point(0, 0)];
+    visited[0] = true;
+    while let Some(&p) = stack.last() {
+        let unvisited: Vec<_> = Direction::ALL
+            .into_iter()
+            .filter_map(|dir| Some((dir, layout.step(p, dir)?)))
+            .filter(|&(_, q)| !visited[layout.index(q)])
+            .collect();
+        let Some(&(dir, q)) = unvisited.choose(rng) else {
+            stack.pop();
+            continue;
+        };
+        layout.open(p, dir);
+        visited[layout.index(q)] = true;
+        stack.push(q);
+    }
+}
+
+// knock down walls in a random order, skipping any between squares that
+// are already connected (tracked with a union-find over the squares)
+fn kruskal(layout: &mut Layout, rng: &mut StdRng) {
+    let (height, width) = layout.size();
+    let mut walls: Vec<_> = (0..height)
+        .flat_map(|i| (0..width).map(move |j| Point(i, j)))
+        .flat_map(|p| [(p, Direction::Right), (p, Direction::Down)])
+        .filter(|&(p, dir)| layout.step(p, dir).is_some())
+        .collect();
+    walls.shuffle(rng);
+
+    let mut parent: Vec<_> = (0..height * width).collect();
+    for (p, dir) in walls {
+        let q = layout.step(p, dir).expect("walls were checked");
+        let (a, b) = (
+            find(&mut parent, layout.index(p)),
+            find(&mut parent, layout.index(q)),
+        );
+        if a != b {
+            parent[a] = b;
+            layout.open(p, dir);
+        }
+    }
+}
+
+// set that square [k] is in (halving the path to it on the way)
+fn find(parent: &mut [usize], mut k: usize) -> usize {
+    while parent[k] != k {
+        parent[k] = parent[parent[k]];
+        k = parent[k];
+    }
+    k
+}
@@ -0,0 +1,107 @@
+use common::Point;
+
+/// Direction to walk (or knock a wall down) in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+}
+
+/// The walls of a (height x width) maze, stored as whether each square is
+/// open to the square right of it and to the square below it
+#[derive(Debug, Clone)]
+pub struct Layout {
+    height: usize,
+    width: usize,
+    east: Vec<bool>,
+    south: Vec<bool>,
+}
+
+impl Layout {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Grid with every wall up
+    pub fn closed(height: usize, width: usize) -> Self {
+        assert!(height > 0 && width > 0, "maze needs at least one square");
+        Self {
+            height,
+            width,
+            east: vec![false; height * width],
+            south: vec![false; height * width],
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Size in squares, as (height, width)
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Square next to [p] in [dir], if that's on the grid
+    pub fn step(&self, Point(i, j): Point, dir: Direction) -> Option<Point> {
+        let (i, j) = match dir {
+            Direction::Up => (i.checked_sub(1)?, j),
+            Direction::Down => (i + 1, j),
+            Direction::Left => (i, j.checked_sub(1)?),
+            Direction::Right => (i, j + 1),
+        };
+        (i < self.height && j < self.width).then_some(Point(i, j))
+    }
+
+    /// Whether there's no wall on the [dir] side of [p] (the outside
+    /// walls are always up)
+    pub fn is_open(&self, p: Point, dir: Direction) -> bool {
+        let Some(q) = self.step(p, dir) else {
+            return false;
+        };
+        match dir {
+            Direction::Right => self.east[self.index(p)],
+            Direction::Down => self.south[self.index(p)],
+            Direction::Left => self.east[self.index(q)],
+            Direction::Up => self.south[self.index(q)],
+        }
+    }
+
+    /// Knock down the wall on the [dir] side of [p] (unless it's an outside wall)
+    pub fn open(&mut self, p: Point, dir: Direction) {
+        let Some(q) = self.step(p, dir) else {
+            return;
+        };
+        let (kp, kq) = (self.index(p), self.index(q));
+        match dir {
+            Direction::Right => self.east[kp] = true,
+            Direction::Down => self.south[kp] = true,
+            Direction::Left => self.east[kq] = true,
+            Direction::Up => self.south[kq] = true,
+        }
+    }
+
+    /// Squares that can be walked to from [p] in one step
+    pub fn exits(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter(move |&dir| self.is_open(p, dir))
+            .filter_map(move |dir| self.step(p, dir))
+    }
+
+    /// Index of [p] into per-square lists, a row at a time
+    pub fn index(&self, Point(i, j): Point) -> usize {
+        i * self.width + j
+    }
+}
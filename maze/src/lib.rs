@@ -0,0 +1,26 @@
+//! Mazes: the game core (a maze, the player walking it, and a solver that
+//! can be watched looking for the way out), generators for random mazes,
+//! plus the crossterm frontend for exploring them in a terminal
+
+mod generator;
+mod layout;
+mod maze;
+pub mod solver;
+pub use generator::{generate, Algorithm};
+pub use layout::{Direction, Layout};
+pub use maze::{Maze, Move, MoveResult, SquareView};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod mazeui;
+#[cfg(feature = "terminal")]
+mod render;
+#[cfg(feature = "terminal")]
+pub use app::{run, MazeApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,230 @@
+use common::{Core, Point};
+use log::debug;
+
+use crate::generator::{self, Algorithm};
+use crate::layout::{Direction, Layout};
+use crate::solver::{self, Solver};
+
+/// Something the player can do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Move {
+    Walk(Direction),
+    /// set the solver looking for the shortest way out from where the player is
+    Solve,
+    /// go back to the start, forgetting every step taken
+    Restart,
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    Moved,
+    /// a wall is in the way
+    Blocked,
+    /// the player made it to the exit
+    Escaped,
+    Solving,
+    Restarted,
+}
+
+/// What's on one square of the maze, or in the gap between two squares
+/// (the most interesting thing, when there's more than one)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SquareView {
+    Player,
+    Exit,
+    /// on the shortest way out, once the solver has found it
+    Path,
+    /// somewhere the player has been
+    Trail,
+    /// somewhere the solver has looked
+    Explored,
+    Empty,
+}
+
+/// A maze being explored: its walls, the player, the way they've come,
+/// and the solver, if they've asked it for the way out
+pub struct Maze {
+    layout: Layout,
+    start: Point,
+    exit: Point,
+    player: Point,
+    trail: Vec<bool>, // squares the player has been on
+    moves: u32,
+    shortest: usize, // steps from the start to the exit
+    solver: Option<Solver>,
+}
+
+impl Maze {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Player in the top left corner of [layout], with the exit in the
+    /// bottom right
+    pub fn new(layout: Layout) -> Self {
+        let (height, width) = layout.size();
+        let (start, exit) = (Point(0, 0), Point(height - 1, width - 1));
+        let shortest = solver::shortest_path_len(&layout, start, exit);
+        let mut trail = vec![false; height * width];
+        trail[0] = true;
+        Self {
+            layout,
+            start,
+            exit,
+            player: start,
+            trail,
+            moves: 0,
+            shortest,
+            solver: None,
+        }
+    }
+
+    /// A random (height x width) maze (the same seed always gives the same one)
+    pub fn generate(height: usize, width: usize, algorithm: Algorithm, seed: u64) -> Self {
+        Self::new(generator::generate(height, width, algorithm, seed))
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn make_move(&mut self, mv: Move) -> MoveResult {
+        match mv {
+            Move::Walk(dir) => self.walk(dir),
+            Move::Solve => {
+                self.solver = Some(Solver::new(&self.layout, self.player, self.exit));
+                MoveResult::Solving
+            }
+            Move::Restart => {
+                self.player = self.start;
+                self.trail.fill(false);
+                self.trail[self.layout.index(self.start)] = true;
+                self.moves = 0;
+                self.solver = None;
+                MoveResult::Restarted
+            }
+        }
+    }
+
+    /// Steps taken since the start
+    pub fn moves(&self) -> u32 {
+        self.moves
+    }
+
+    /// Fewest steps it takes to get from the start to the exit
+    pub fn shortest(&self) -> usize {
+        self.shortest
+    }
+
+    pub fn is_escaped(&self) -> bool {
+        self.player == self.exit
+    }
+
+    /// Whether the solver is still looking for the way out
+    pub fn is_solving(&self) -> bool {
+        self.solver.as_ref().is_some_and(|solver| !solver.is_done())
+    }
+
+    /// What's in the gap on the [dir] side of [p] (None if there's a wall)
+    pub fn passage(&self, p: Point, dir: Direction) -> Option<SquareView> {
+        if !self.layout.is_open(p, dir) {
+            return None;
+        }
+        let q = self.layout.step(p, dir)?;
+        // (in a perfect maze, two open neighbors on the path are always
+        // next to each other along it, and the same goes for the trail)
+        let on_trail = |p: Point| self.trail[self.layout.index(p)];
+        Some(if self.is_on_path(p) && self.is_on_path(q) {
+            SquareView::Path
+        } else if on_trail(p) && on_trail(q) {
+            SquareView::Trail
+        } else if self.is_explored(p) && self.is_explored(q) {
+            SquareView::Explored
+        } else {
+            SquareView::Empty
+        })
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn walk(&mut self, dir: Direction) -> MoveResult {
+        if !self.layout.is_open(self.player, dir) {
+            return MoveResult::Blocked;
+        }
+        let Some(p) = self.layout.step(self.player, dir) else {
+            return MoveResult::Blocked;
+        };
+        self.player = p;
+        self.trail[self.layout.index(p)] = true;
+        self.moves += 1;
+        if p == self.exit {
+            debug!(
+                "escaped in {} moves (shortest {})",
+                self.moves, self.shortest
+            );
+            return MoveResult::Escaped;
+        }
+        MoveResult::Moved
+    }
+
+    fn is_on_path(&self, p: Point) -> bool {
+        let k = self.layout.index(p);
+        self.solver
+            .as_ref()
+            .is_some_and(|solver| solver.is_on_path(k))
+    }
+
+    fn is_explored(&self, p: Point) -> bool {
+        let k = self.layout.index(p);
+        self.solver
+            .as_ref()
+            .is_some_and(|solver| solver.is_explored(k))
+    }
+
+    // what the square at [p] is marked with, ignoring the player and exit
+    fn mark(&self, p: Point) -> SquareView {
+        if self.is_on_path(p) {
+            SquareView::Path
+        } else if self.trail[self.layout.index(p)] {
+            SquareView::Trail
+        } else if self.is_explored(p) {
+            SquareView::Explored
+        } else {
+            SquareView::Empty
+        }
+    }
+}
+
+impl Core for Maze {
+    type Action = Move;
+    type Outcome = MoveResult;
+    type Cell = SquareView;
+
+    fn size(&self) -> (usize, usize) {
+        self.layout.size()
+    }
+
+    fn cell(&self, p: Point) -> SquareView {
+        if p == self.player {
+            SquareView::Player
+        } else if p == self.exit {
+            SquareView::Exit
+        } else {
+            self.mark(p)
+        }
+    }
+
+    fn act(&mut self, mv: Move) -> MoveResult {
+        self.make_move(mv)
+    }
+
+    // spread the solver's search one step further
+    fn tick(&mut self) {
+        if let Some(solver) = &mut self.solver {
+            solver.step(&self.layout);
+        }
+    }
+}
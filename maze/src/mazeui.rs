@@ -0,0 +1,108 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::layout::Direction;
+use crate::maze::Move;
+
+/// Help lines under the maze, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("maze.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MazeUIAction {
+    Wait,
+    Move(Move),
+    NewMaze,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, MazeUIAction); 15] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        MazeUIAction::Move(Move::Walk(Direction::Up)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        MazeUIAction::Move(Move::Walk(Direction::Down)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        MazeUIAction::Move(Move::Walk(Direction::Left)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        MazeUIAction::Move(Move::Walk(Direction::Right)),
+    ),
+    (
+        KeyBinding::char('w'),
+        MazeUIAction::Move(Move::Walk(Direction::Up)),
+    ),
+    (
+        KeyBinding::char('s'),
+        MazeUIAction::Move(Move::Walk(Direction::Down)),
+    ),
+    (
+        KeyBinding::char('a'),
+        MazeUIAction::Move(Move::Walk(Direction::Left)),
+    ),
+    (
+        KeyBinding::char('d'),
+        MazeUIAction::Move(Move::Walk(Direction::Right)),
+    ),
+    (KeyBinding::char('h'), MazeUIAction::Move(Move::Solve)),
+    (KeyBinding::char('?'), MazeUIAction::Move(Move::Solve)),
+    (KeyBinding::char('r'), MazeUIAction::Move(Move::Restart)),
+    (KeyBinding::char('n'), MazeUIAction::NewMaze),
+    (KeyBinding::key(KeyCode::Enter), MazeUIAction::NewMaze),
+    (KeyBinding::char('q'), MazeUIAction::Quit),
+    (KeyBinding::ctrl('c'), MazeUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, MazeUIAction); 8] = [
+    ("up", MazeUIAction::Move(Move::Walk(Direction::Up))),
+    ("down", MazeUIAction::Move(Move::Walk(Direction::Down))),
+    ("left", MazeUIAction::Move(Move::Walk(Direction::Left))),
+    ("right", MazeUIAction::Move(Move::Walk(Direction::Right))),
+    ("solve", MazeUIAction::Move(Move::Solve)),
+    ("restart", MazeUIAction::Move(Move::Restart)),
+    ("new_maze", MazeUIAction::NewMaze),
+    ("quit", MazeUIAction::Quit),
+];
+
+pub struct MazeUI {
+    pub bindings: Bindings<MazeUIAction>,
+}
+
+impl MazeUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> MazeUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(MazeUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> MazeUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => MazeUIAction::Wait,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use common::theme::{self, Role};
+use common::{blocks, style, Core, Point};
+use crossterm::style::{Color, StyledContent, Stylize};
+
+use crate::layout::Direction;
+use crate::maze::{Maze, SquareView};
+
+/// Terminal rows taken up by a maze [height] squares tall
+pub fn rows(height: usize) -> usize {
+    if style::color_enabled() {
+        blocks::half_block_rows(2 * height + 1)
+    } else {
+        2 * height + 1
+    }
+}
+
+/// Terminal columns taken up by a maze [width] squares wide
+pub fn cols(width: usize) -> usize {
+    2 * width + 1
+}
+
+/// Draw the maze as one list of (styled) characters per terminal row.
+/// Squares and the walls between them each take up a block, so a maze of
+/// (height x width) squares is (2 height + 1) x (2 width + 1) blocks. In
+/// color, the blocks are drawn as half-blocks, two to each character
+pub fn render(maze: &Maze) -> Vec<Vec<StyledContent<String>>> {
+    let (height, width) = maze.size();
+    let (blocks_h, blocks_w) = (2 * height + 1, cols(width));
+    if style::color_enabled() {
+        let theme = theme::current();
+        let color = |view: Option<SquareView>| -> Color {
+            match view {
+                None => theme.color(Role::Text),
+                Some(SquareView::Player) => theme.color(Role::Accent),
+                Some(SquareView::Exit) => theme.color(Role::Success),
+                Some(SquareView::Path) => theme.color(Role::Marker),
+                Some(SquareView::Trail) => theme.color(Role::Muted),
+                Some(SquareView::Explored) => theme.color(Role::Hidden),
+                Some(SquareView::Empty) => theme.cell(0),
+            }
+        };
+        return blocks::half_blocks(blocks_h, blocks_w, |p| color(block(maze, p)));
+    }
+    (0..blocks_h)
+        .map(|i| {
+            (0..blocks_w)
+                .map(|j| glyph(block(maze, Point(i, j))).to_string().stylize())
+                .collect()
+        })
+        .collect()
+}
+
+// without color, what's on each block is told apart by its character
+fn glyph(view: Option<SquareView>) -> char {
+    match view {
+        None => '#',
+        Some(SquareView::Player) => '@',
+        Some(SquareView::Exit) => 'E',
+        Some(SquareView::Path) => '*',
+        Some(SquareView::Trail) => '.',
+        Some(SquareView::Explored) => ',',
+        Some(SquareView::Empty) => ' ',
+    }
+}
+
+// what's on block (i, j): a square when both are odd, the gap between two
+// squares when just one is, and a wall corner otherwise (None for walls)
+fn block(maze: &Maze, Point(i, j): Point) -> Option<SquareView> {
+    let square = |k: usize| (k % 2 == 1).then_some(k / 2);
+    match (square(i), square(j)) {
+        (Some(si), Some(sj)) => Some(maze.cell(Point(si, sj))),
+        (Some(si), None) if j > 0 => maze.passage(Point(si, j / 2 - 1), Direction::Right),
+        (None, Some(sj)) if i > 0 => maze.passage(Point(i / 2 - 1, sj), Direction::Down),
+        _ => None,
+    }
+}
@@ -0,0 +1,115 @@
+use common::Point;
+
+use crate::layout::Layout;
+
+/// Breadth-first search for the shortest way between two squares, run a
+/// step at a time so it can be watched spreading out through the maze
+pub struct Solver {
+    to: Point,
+    came_from: Vec<Option<Point>>, // for each square reached so far
+    frontier: Vec<Point>,          // squares reached on the last step
+    on_path: Vec<bool>,            // squares on the way, once it's found
+    path_len: Option<usize>,
+}
+
+impl Solver {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Search of [layout] from [from] to [to], not started yet
+    pub fn new(layout: &Layout, from: Point, to: Point) -> Self {
+        let (height, width) = layout.size();
+        let mut came_from = vec![None; height * width];
+        came_from[layout.index(from)] = Some(from);
+        Self {
+            to,
+            came_from,
+            frontier: vec![from],
+            on_path: vec![false; height * width],
+            path_len: None,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Reach the squares one step further out. Returns whether the
+    /// search is over
+    pub fn step(&mut self, layout: &Layout) -> bool {
+        if self.is_done() {
+            return true;
+        }
+        let mut next = Vec::new();
+        for &p in &self.frontier {
+            for q in layout.exits(p) {
+                let k = layout.index(q);
+                if self.came_from[k].is_none() {
+                    self.came_from[k] = Some(p);
+                    next.push(q);
+                }
+            }
+        }
+        self.frontier = next;
+        if self.came_from[layout.index(self.to)].is_some() {
+            self.trace_path(layout);
+        } else if self.frontier.is_empty() {
+            // (only in a maze with no way through)
+            self.path_len = Some(0);
+        }
+        self.is_done()
+    }
+
+    /// Run the search to the end
+    pub fn finish(&mut self, layout: &Layout) {
+        while !self.step(layout) {}
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.path_len.is_some()
+    }
+
+    /// Whether the search has got to square [k] (see [Layout::index])
+    pub fn is_explored(&self, k: usize) -> bool {
+        self.came_from[k].is_some()
+    }
+
+    /// Whether square [k] is on the shortest way (false until it's found)
+    pub fn is_on_path(&self, k: usize) -> bool {
+        self.on_path[k]
+    }
+
+    /// Steps along the shortest way, once it's found
+    pub fn path_len(&self) -> Option<usize> {
+        self.path_len
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // follow the trail of squares back from the goal
+    fn trace_path(&mut self, layout: &Layout) {
+        let mut p = self.to;
+        let mut len = 0;
+        loop {
+            let k = layout.index(p);
+            self.on_path[k] = true;
+            let prev = self.came_from[k].expect("squares on the path were reached");
+            if prev == p {
+                break;
+            }
+            p = prev;
+            len += 1;
+        }
+        self.path_len = Some(len);
+    }
+}
+
+/// Steps along the shortest way from [from] to [to]
+pub fn shortest_path_len(layout: &Layout, from: Point, to: Point) -> usize {
+    let mut solver = Solver::new(layout, from, to);
+    solver.finish(layout);
+    solver.path_len().unwrap_or_default()
+}
@@ -0,0 +1,484 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::MemoryConfig;
+use crate::memory::{Card, FlipResult, Memory};
+use crate::memoryui::{self, MemoryUI, MemoryUIAction};
+
+// rows of cards, and cards in each row, when nothing else sets them
+const DEFAULT_HEIGHT: u16 = 4;
+const DEFAULT_WIDTH: u16 = 4;
+
+// most rows and cards in each row there can be (the fewest is two)
+const MAX_HEIGHT: u16 = 6;
+const MAX_WIDTH: u16 = 8;
+
+// terminal columns each card takes up, and the gap between them (which
+// the cursor's brackets go in)
+const CARD_COLS: usize = 3;
+const GAP_COLS: usize = 1;
+
+// lines of text under the cards (gap, counters, message)
+const STATUS_ROWS: usize = 3;
+
+// time each step of a card turning over takes
+const FLIP_TICK: Duration = Duration::from_millis(60);
+
+// steps a card takes to turn over: the side it started on narrowing, then
+// edge-on, then the other side widening
+const FLIP_FRAMES: usize = 3;
+
+// time two cards that don't match are left up for, before they're covered
+// again (unless the next card's turned up first)
+const REVEAL: Duration = Duration::from_millis(1200);
+
+// a card partway through turning over
+struct Turning {
+    at: Point,
+    face: u8,
+    up: bool, // being turned face up (or back down)
+    frame: usize,
+}
+
+/// Memory app (the cards, the ones turning over, the fewest moves a game's
+/// been finished in so far, and UI state)
+pub struct MemoryApp {
+    game: Memory,
+    ui: MemoryUI,
+    rng: StdRng, // for dealing each game
+    turning: Vec<Turning>,
+    pending: Option<FlipResult>, // what the last card led to, once it's turned up
+    reveal: u32,                 // ticks till two cards that don't match are covered
+    fewest: Option<usize>,       // moves, playing alone
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last game finished alone
+    over: bool,
+}
+
+impl MemoryApp {
+    /// [rows] by [cols] cards for [players], dealt with [seed]
+    pub fn new(rows: usize, cols: usize, players: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self {
+            game: Memory::new(rows, cols, players, rng.gen()),
+            ui: MemoryUI::new((rows, cols)),
+            rng,
+            turning: Vec::new(),
+            pending: None,
+            reveal: 0,
+            fewest: None,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_game(&mut self) {
+        let (rows, cols) = self.game.size();
+        self.game = Memory::new(rows, cols, self.game.players(), self.rng.gen());
+        self.turning.clear();
+        self.pending = None;
+        self.reveal = 0;
+        self.message = "".to_string().reset();
+    }
+
+    // turn up the card under the cursor, and start it turning over (what
+    // it led to only shows once it's up). Two cards left up that don't
+    // match are covered first, without waiting for them
+    fn flip(&mut self) {
+        let p = self.ui.get_cursor();
+        self.cover();
+        let res = self.game.flip(p);
+        debug!("card ({}, {}): {res:?}", p.0, p.1);
+        match res {
+            FlipResult::First
+            | FlipResult::Matched
+            | FlipResult::Mismatched
+            | FlipResult::Finished => {
+                if let Card::Up(face) | Card::Matched(face) = self.game.cell(p) {
+                    self.turning.push(Turning {
+                        at: p,
+                        face,
+                        up: true,
+                        frame: 0,
+                    });
+                }
+                self.pending = Some(res);
+            }
+            FlipResult::Illegal | FlipResult::GameOver => self.handle_res(res),
+        }
+    }
+
+    // turn two cards that don't match back down (passing the turn on), and
+    // start them turning over
+    fn cover(&mut self) {
+        self.reveal = 0;
+        if !self.game.is_showing_mismatch() {
+            return;
+        }
+        let (rows, cols) = self.game.size();
+        let up: Vec<(Point, u8)> = (0..rows)
+            .flat_map(|i| (0..cols).map(move |j| Point(i, j)))
+            .filter_map(|p| match self.game.cell(p) {
+                Card::Up(face) => Some((p, face)),
+                _ => None,
+            })
+            .collect();
+        self.game.cover();
+        for (at, face) in up {
+            self.turning.retain(|turning| turning.at != at);
+            self.turning.push(Turning {
+                at,
+                face,
+                up: false,
+                frame: 0,
+            });
+        }
+    }
+
+    fn handle_res(&mut self, res: FlipResult) {
+        let again = locale::text("memory.again");
+        match res {
+            FlipResult::First => {}
+            FlipResult::Matched => {
+                bell::ring("pair");
+                let mut message = locale::text("memory.pair").to_string();
+                if self.game.players() > 1 {
+                    let player = player_name(self.game.turn());
+                    message += &format!(" {player} {}", locale::text("memory.goes_again"));
+                }
+                self.message = message.bold().with(theme::color(Role::Success));
+            }
+            FlipResult::Mismatched => {
+                self.reveal = (REVEAL.as_millis() / FLIP_TICK.as_millis()) as u32;
+            }
+            FlipResult::Finished if self.game.players() > 1 => {
+                bell::ring("win");
+                let result = match self.game.winner() {
+                    Some(player) => {
+                        format!("{} {}", player_name(player), locale::text("memory.wins"))
+                    }
+                    None => locale::text("memory.tie").to_string(),
+                };
+                self.message = format!("{result} {again}")
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            FlipResult::Finished => {
+                bell::ring("win");
+                self.outcome = Outcome::Won;
+                let moves = self.game.moves();
+                let mut message = locale::text("memory.win").to_string();
+                if self.fewest.is_some_and(|fewest| moves < fewest) {
+                    message += &format!(" {}", locale::text("memory.new_fewest"));
+                }
+                self.fewest = Some(self.fewest.map_or(moves, |fewest| fewest.min(moves)));
+                self.message = format!("{message} {again}")
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            FlipResult::Illegal => {
+                bell::ring("invalid move");
+                self.message = locale::text("memory.already_up")
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            FlipResult::GameOver => bell::ring("invalid move"),
+        }
+    }
+
+    // (styled) pieces of the card at [p], CARD_COLS wide altogether: its
+    // back, or its face once it's up (or taken), or partway through
+    // turning over
+    fn card_pieces(&self, p: Point) -> Vec<StyledContent<String>> {
+        if let Some(turning) = self.turning.iter().find(|turning| turning.at == p) {
+            let showing_face = match turning.frame {
+                0 => !turning.up,
+                1 => return vec![edge()],
+                _ => turning.up,
+            };
+            let middle = match showing_face {
+                true => face(turning.face, 1),
+                false => back(1),
+            };
+            return vec![" ".to_string().reset(), middle, " ".to_string().reset()];
+        }
+        match self.game.cell(p) {
+            Card::Down => vec![back(CARD_COLS)],
+            Card::Up(f) => vec![face(f, CARD_COLS)],
+            Card::Matched(f) => vec![taken(f)],
+        }
+    }
+
+    /// Rows of cards, with a blank line between rows, each a list of
+    /// (styled) pieces. The cursor's card is in brackets
+    fn card_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let (rows, cols) = self.game.size();
+        let Point(ci, cj) = self.ui.get_cursor();
+        let bracket = |s: &str| s.to_string().bold().with(theme::color(Role::Accent));
+        let mut lines = Vec::new();
+        for i in 0..rows {
+            if i > 0 {
+                lines.push(vec!["".to_string().reset()]);
+            }
+            let mut line = Vec::new();
+            for j in 0..=cols {
+                line.push(match (i == ci, j) {
+                    (true, j) if j == cj => bracket("["),
+                    (true, j) if j == cj + 1 => bracket("]"),
+                    _ => " ".repeat(GAP_COLS).reset(),
+                });
+                if j < cols {
+                    line.extend(self.card_pieces(Point(i, j)));
+                }
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    // moves made and pairs left (and the fewest moves so far), or the
+    // pairs each player has and whose turn it is
+    fn counters(&self) -> String {
+        if self.game.players() > 1 {
+            let mut parts: Vec<String> = (0..self.game.players())
+                .map(|player| format!("{}: {}", player_name(player), self.game.pairs(player)))
+                .collect();
+            if !self.game.is_finished() {
+                let turn = player_name(self.game.turn());
+                parts.push(format!("{turn} {}", locale::text("memory.to_play")));
+            }
+            return parts.join("   ");
+        }
+        let mut counters = format!(
+            "{} {}   {} {}",
+            locale::text("memory.moves"),
+            self.game.moves(),
+            locale::text("memory.pairs_left"),
+            self.game.pairs_left()
+        );
+        if let Some(fewest) = self.fewest {
+            counters += &format!("   {} {fewest}", locale::text("memory.fewest"));
+        }
+        counters
+    }
+}
+
+// a player's name, from "1"
+fn player_name(player: usize) -> String {
+    format!("{} {}", locale::text("memory.player"), player + 1)
+}
+
+// the back of a card, [cols] wide: a block of color, or hashes without
+fn back(cols: usize) -> StyledContent<String> {
+    match style::color_enabled() {
+        true => " ".repeat(cols).on(theme::color(Role::Hidden)),
+        false => "#".repeat(cols).reset(),
+    }
+}
+
+// the face of a card, [cols] wide (the letter, and a space either side at
+// full width): the letter on a color of its own, or in bold without
+fn face(f: u8, cols: usize) -> StyledContent<String> {
+    let letter = (b'A' + f) as char;
+    let face = match cols {
+        1 => letter.to_string(),
+        _ => format!(" {letter} "),
+    };
+    match style::color_enabled() {
+        true => face.bold().with(theme::color(Role::Text)).on(face_color(f)),
+        false => face.bold(),
+    }
+}
+
+// a card taken as one of a pair: the letter alone, in its color (or in
+// lower case without)
+fn taken(f: u8) -> StyledContent<String> {
+    let letter = (b'A' + f) as char;
+    match style::color_enabled() {
+        true => format!(" {letter} ").with(face_color(f)),
+        false => format!(" {} ", letter.to_ascii_lowercase()).dim(),
+    }
+}
+
+// a card halfway through turning over, edge-on
+fn edge() -> StyledContent<String> {
+    match style::utf8_locale() {
+        true => " │ ".to_string().bold(),
+        false => " | ".to_string().bold(),
+    }
+}
+
+// color of a face, from the theme's cell colors (skipping the empty one)
+fn face_color(f: u8) -> crossterm::style::Color {
+    theme::current().cell(1 + f % 8)
+}
+
+// Pretty-print
+impl fmt::Display for MemoryApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.card_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.counters())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", memoryui::help_text())
+    }
+}
+
+impl Game for MemoryApp {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != MemoryUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            MemoryUIAction::Quit => self.over = true,
+            MemoryUIAction::NewGame => self.new_game(),
+            MemoryUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the cards");
+                }
+            }
+            MemoryUIAction::Flip => {
+                self.message = "".to_string().reset();
+                self.flip();
+            }
+            MemoryUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // turn the cards turning over a step further, or cover two that don't
+    // match once they've been up long enough
+    fn tick(&mut self) {
+        if !self.turning.is_empty() {
+            for turning in &mut self.turning {
+                turning.frame += 1;
+            }
+            self.turning.retain(|turning| turning.frame < FLIP_FRAMES);
+            if self.turning.is_empty() {
+                if let Some(res) = self.pending.take() {
+                    self.handle_res(res);
+                }
+            }
+            self.redraw.set(true);
+            return;
+        }
+        if self.reveal > 0 {
+            self.reveal -= 1;
+            if self.reveal == 0 {
+                self.cover();
+                self.redraw.set(true);
+            }
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(FLIP_TICK)
+    }
+
+    // the cards, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = memoryui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        let (rows, card_cols) = self.game.size();
+        (
+            cols.max((card_cols * (CARD_COLS + GAP_COLS) + GAP_COLS) as u16),
+            (2 * rows - 1 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play memory, alone or two people at one keyboard
+pub fn run(args: Args) -> Result<(), String> {
+    let config: MemoryConfig = common::cli::setup(&args.common, "memory")?;
+
+    let height = args.height.or(config.height).unwrap_or(DEFAULT_HEIGHT);
+    let width = args.width.or(config.width).unwrap_or(DEFAULT_WIDTH);
+    if !(2..=MAX_HEIGHT).contains(&height) || !(2..=MAX_WIDTH).contains(&width) {
+        return Err(format!(
+            "bad [memory] config: {width}x{height} cards (from 2 to {MAX_WIDTH} across, and 2 to {MAX_HEIGHT} down)"
+        ));
+    }
+    if !(height * width).is_multiple_of(2) {
+        return Err(format!(
+            "bad [memory] config: {width}x{height} cards (there has to be an even number, to make pairs)"
+        ));
+    }
+    let players = match args.two_player || config.two_player {
+        true => 2,
+        false => 1,
+    };
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("memory seed {seed}, {width}x{height} cards, {players} players");
+
+    let mut app = MemoryApp::new(height as usize, width as usize, players, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &memoryui::ACTIONS)
+        .map_err(|e| format!("bad [memory.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
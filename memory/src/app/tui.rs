@@ -0,0 +1,25 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::MemoryApp;
+use crate::memoryui;
+
+impl TuiGame for MemoryApp {
+    fn draw(&self, frame: &mut Frame) {
+        let board = Board::new(
+            self.card_lines()
+                .iter()
+                .map(|line| line.iter().map(tui::span).collect::<Line>())
+                .collect(),
+        );
+
+        let status = StatusBar::new(memoryui::help_text())
+            .line(self.counters())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
@@ -0,0 +1,41 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Memory (concentration): find the pairs, turning up two cards at a time
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on cards already up, pairs, wins and achievements")
+}))]
+pub struct Args {
+    /// Rows of cards, from 2 to 6 (the cards have to come out even)
+    /// [default: 4, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2..=6))]
+    pub height: Option<u16>,
+
+    /// Cards in each row, from 2 to 8
+    /// [default: 4, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2..=8))]
+    pub width: Option<u16>,
+
+    /// Two people take turns at the same keyboard, each going on until
+    /// they turn up two cards that don't match
+    #[arg(long)]
+    pub two_player: bool,
+
+    /// Random seed for dealing the first game (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [memory] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MemoryConfig {
+    /// rows of cards
+    pub height: Option<u16>,
+    /// cards in each row
+    pub width: Option<u16>,
+    /// two people taking turns at the same keyboard
+    pub two_player: bool,
+    /// keys for each action, e.g. flip = ["space", "enter"]
+    pub keys: KeyMap,
+}
@@ -0,0 +1,19 @@
+//! Memory (concentration): the game core (pairs of cards dealt face down,
+//! and whose turn it is to turn two up), plus the crossterm frontend for
+//! playing it in a terminal, alone or with two people taking turns
+
+mod memory;
+pub use memory::{Card, FlipResult, Memory, MAX_PAIRS};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod memoryui;
+#[cfg(feature = "terminal")]
+pub use app::{run, MemoryApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,225 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// Most pairs there can be (one for each letter of the alphabet)
+pub const MAX_PAIRS: usize = 26;
+
+/// What can be seen of a card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Card {
+    Down,
+    /// turned up this move, showing its face (from 0)
+    Up(u8),
+    /// taken as one of a pair, and left showing its face
+    Matched(u8),
+}
+
+/// What turning a card up led to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlipResult {
+    /// the first card of the move
+    First,
+    /// the second card matched the first, so the player takes the pair and
+    /// goes again
+    Matched,
+    /// the second card didn't match the first: both stay up until they're
+    /// covered again, and then it's the next player's turn
+    Mismatched,
+    /// the second card matched the first, and that was the last pair
+    Finished,
+    /// the card's already face up
+    Illegal,
+    /// every pair has been taken already
+    GameOver,
+}
+
+/// A game of memory (concentration): pairs of cards laid out face down in
+/// a grid, two turned up each move, and kept by the player who turned them
+/// if they match. With more than one player, a player goes on until they
+/// turn up two that don't
+#[derive(Debug, Clone, PartialEq)]
+pub struct Memory {
+    rows: usize,
+    cols: usize,
+    faces: Vec<u8>,    // row by row
+    taken: Vec<bool>,  // cards matched already
+    up: Vec<usize>,    // cards turned up this move (two, till they're covered)
+    pairs: Vec<usize>, // taken by each player
+    turn: usize,       // whose turn it is, from 0
+    moves: usize,      // pairs of cards turned up, by everyone
+}
+
+impl Memory {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// [rows] by [cols] cards (an even number of them, making at most
+    /// [MAX_PAIRS] pairs) for [players] to take turns at. The same seed
+    /// always deals the same way
+    pub fn new(rows: usize, cols: usize, players: usize, seed: u64) -> Self {
+        let count = rows * cols;
+        assert!(count.is_multiple_of(2), "cards come in pairs");
+        assert!(count / 2 <= MAX_PAIRS, "too many pairs");
+        assert!(players > 0, "somebody has to play");
+        let mut rng = StdRng::seed_from_u64(seed);
+        // (a different set of letters each game, when there aren't enough
+        // pairs for all of them)
+        let letters: Vec<u8> = (0..MAX_PAIRS as u8).collect();
+        let mut faces: Vec<u8> = letters
+            .choose_multiple(&mut rng, count / 2)
+            .flat_map(|&face| [face, face])
+            .collect();
+        faces.shuffle(&mut rng);
+        Self {
+            rows,
+            cols,
+            faces,
+            taken: vec![false; count],
+            up: Vec::new(),
+            pairs: vec![0; players],
+            turn: 0,
+            moves: 0,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Turn up the card at [p]. Two cards left up from the last move
+    /// (not matching) are covered first, passing the turn on
+    pub fn flip(&mut self, p: Point) -> FlipResult {
+        if self.is_finished() {
+            return FlipResult::GameOver;
+        }
+        self.cover();
+        let k = self.index(p);
+        if self.taken[k] || self.up.contains(&k) {
+            return FlipResult::Illegal;
+        }
+        self.up.push(k);
+        let [first, second] = self.up[..] else {
+            return FlipResult::First;
+        };
+        self.moves += 1;
+        if self.faces[first] != self.faces[second] {
+            return FlipResult::Mismatched;
+        }
+        self.taken[first] = true;
+        self.taken[second] = true;
+        self.up.clear();
+        self.pairs[self.turn] += 1;
+        debug!(
+            "player {} takes a pair ({} now), move {}",
+            self.turn + 1,
+            self.pairs[self.turn],
+            self.moves
+        );
+        match self.is_finished() {
+            true => FlipResult::Finished,
+            false => FlipResult::Matched,
+        }
+    }
+
+    /// Turn the two cards left up by a move that didn't match back down,
+    /// and pass the turn on to the next player. Returns where they were
+    /// (none if there weren't two up)
+    pub fn cover(&mut self) -> Vec<Point> {
+        if self.up.len() < 2 {
+            return Vec::new();
+        }
+        self.turn = (self.turn + 1) % self.pairs.len();
+        let up = std::mem::take(&mut self.up);
+        up.into_iter().map(|k| self.point(k)).collect()
+    }
+
+    /// Whether two cards that don't match are still up, waiting to be
+    /// covered
+    pub fn is_showing_mismatch(&self) -> bool {
+        self.up.len() == 2
+    }
+
+    /// Whose turn it is, from 0
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    pub fn players(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Pairs taken by [player] (from 0)
+    pub fn pairs(&self, player: usize) -> usize {
+        self.pairs[player]
+    }
+
+    /// Pairs still down
+    pub fn pairs_left(&self) -> usize {
+        self.taken.iter().filter(|&&taken| !taken).count() / 2
+    }
+
+    /// Moves made (each two cards turned up) by every player
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// The player who took the most pairs, once they're all taken (None
+    /// until then, or if it's a tie)
+    pub fn winner(&self) -> Option<usize> {
+        if !self.is_finished() {
+            return None;
+        }
+        let most = self.pairs.iter().max()?;
+        match self.pairs.iter().filter(|&pairs| pairs == most).count() {
+            1 => self.pairs.iter().position(|pairs| pairs == most),
+            _ => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.taken.iter().all(|&taken| taken)
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn index(&self, Point(i, j): Point) -> usize {
+        i * self.cols + j
+    }
+
+    fn point(&self, k: usize) -> Point {
+        Point(k / self.cols, k % self.cols)
+    }
+}
+
+impl Core for Memory {
+    type Action = Point;
+    type Outcome = FlipResult;
+    type Cell = Card;
+
+    fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn cell(&self, p: Point) -> Card {
+        let k = self.index(p);
+        if self.taken[k] {
+            Card::Matched(self.faces[k])
+        } else if self.up.contains(&k) {
+            Card::Up(self.faces[k])
+        } else {
+            Card::Down
+        }
+    }
+
+    fn act(&mut self, p: Point) -> FlipResult {
+        self.flip(p)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_finished()
+    }
+}
@@ -0,0 +1,121 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+/// Help lines under the cards, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("memory.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MemoryUIAction {
+    Wait,
+    Move(MoveDirection),
+    /// turn up the card under the cursor
+    Flip,
+    NewGame,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, MemoryUIAction); 9] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        MemoryUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        MemoryUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        MemoryUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        MemoryUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), MemoryUIAction::Flip),
+    (KeyBinding::key(KeyCode::Enter), MemoryUIAction::Flip),
+    (KeyBinding::char('n'), MemoryUIAction::NewGame),
+    (KeyBinding::char('q'), MemoryUIAction::Quit),
+    (KeyBinding::ctrl('c'), MemoryUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, MemoryUIAction); 7] = [
+    ("up", MemoryUIAction::Move(MoveDirection::Up)),
+    ("down", MemoryUIAction::Move(MoveDirection::Down)),
+    ("left", MemoryUIAction::Move(MoveDirection::Left)),
+    ("right", MemoryUIAction::Move(MoveDirection::Right)),
+    ("flip", MemoryUIAction::Flip),
+    ("new_game", MemoryUIAction::NewGame),
+    ("quit", MemoryUIAction::Quit),
+];
+
+pub struct MemoryUI {
+    cursor: Point,
+    size: (usize, usize),
+    pub bindings: Bindings<MemoryUIAction>,
+}
+
+impl MemoryUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cursor starting top left of (rows, columns) [size] of cards
+    pub fn new(size: (usize, usize)) -> Self {
+        Self {
+            cursor: Point(0, 0),
+            size,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> MemoryUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(MemoryUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> MemoryUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => MemoryUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a card, unless that's off the board
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let Point(i, j) = self.cursor;
+        let (i, j) = match dir {
+            MoveDirection::Up => (i.checked_sub(1), Some(j)),
+            MoveDirection::Down => (Some(i + 1).filter(|&i| i < self.size.0), Some(j)),
+            MoveDirection::Left => (Some(i), j.checked_sub(1)),
+            MoveDirection::Right => (Some(i), Some(j + 1).filter(|&j| j < self.size.1)),
+        };
+        let (Some(i), Some(j)) = (i, j) else {
+            return Err("already at the edge".into());
+        };
+        self.cursor = Point(i, j);
+        Ok(())
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+}
@@ -0,0 +1,172 @@
+//! Python bindings for the Minesweeper core, so agents can be trained and
+//! tested against the rules the game itself plays by:
+//!
+//! ```python
+//! from minesweeper import MineField
+//!
+//! field = MineField(16, 30, 99, seed=1)
+//! field.reveal(8, 15)   # "ok", "win" or "lose"
+//! field.flag(0, 0)
+//! field.view()          # rows of counts, or HIDDEN / FLAG / MINE
+//! ```
+
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+use common::{Core, Point};
+use mines::{MoveResult, SquareView};
+
+// what view() shows for squares that aren't counts of mines
+const HIDDEN: i32 = -1;
+const FLAG: i32 = -2;
+const MINE: i32 = -3;
+
+/// A Minesweeper board: mines hidden among height x width squares, which are
+/// revealed (showing how many mines are around them) or flagged one move at
+/// a time. Squares are (row, column) from the top left, counting from 0
+#[pyclass(module = "minesweeper")]
+struct MineField {
+    field: mines::MineField,
+}
+
+#[pymethods]
+impl MineField {
+    /// A board with n_mines laid out at random: the same seed always lays
+    /// them out the same way (picked at random if not given). A mine under
+    /// the first square revealed is moved somewhere else
+    #[new]
+    #[pyo3(signature = (height, width, n_mines, seed = None))]
+    fn new(height: usize, width: usize, n_mines: usize, seed: Option<u64>) -> PyResult<Self> {
+        mines::check_board(height, width, n_mines).map_err(PyValueError::new_err)?;
+        let seed = seed.unwrap_or_else(rand::random);
+        Ok(Self {
+            field: mines::MineField::with_n_mines(height, width, n_mines, seed),
+        })
+    }
+
+    /// A board set up exactly from text, one character a square and a row
+    /// to a line: "." hidden, "*" a hidden mine, "@" a flagged mine, "!" a
+    /// flag without a mine, "_" or "0" to "8" revealed, and "X" a revealed
+    /// mine
+    #[staticmethod]
+    fn parse(text: &str) -> PyResult<Self> {
+        text.parse()
+            .map(|field| Self { field })
+            .map_err(PyValueError::new_err)
+    }
+
+    /// Reveal the square at (i, j), and the squares around it if there are
+    /// no mines there. Revealing a square already showing its count reveals
+    /// the squares around it, if as many of them are flagged. Returns "ok",
+    /// "win" (every square without a mine revealed) or "lose"
+    fn reveal(&mut self, i: usize, j: usize) -> PyResult<&'static str> {
+        let p = self.playable(i, j)?;
+        outcome(self.field.reveal(&p))
+    }
+
+    /// Put a flag on the square at (i, j), or take it off again
+    fn flag(&mut self, i: usize, j: usize) -> PyResult<()> {
+        let p = self.playable(i, j)?;
+        outcome(self.field.toggle_flag(&p)).map(|_| ())
+    }
+
+    /// The board as the player sees it, a list of rows: the count of mines
+    /// around each revealed square, or HIDDEN, FLAG or MINE
+    fn view(&self) -> Vec<Vec<i32>> {
+        let (height, width) = self.field.size();
+        (0..height)
+            .map(|i| {
+                (0..width)
+                    .map(|j| match self.field.cell(Point(i, j)) {
+                        SquareView::Hidden => HIDDEN,
+                        SquareView::Flag => FLAG,
+                        SquareView::Mine => MINE,
+                        SquareView::Revealed(n) => n as i32,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        self.field.size().0
+    }
+
+    #[getter]
+    fn width(&self) -> usize {
+        self.field.size().1
+    }
+
+    #[getter]
+    fn n_mines(&self) -> u32 {
+        self.field.n_mines()
+    }
+
+    /// Whether the game's been won or lost (which shows every mine)
+    fn is_over(&self) -> bool {
+        self.field.is_over()
+    }
+
+    /// The board as the player sees it, in the same characters parse()
+    /// reads (hidden squares all show as ".")
+    fn __str__(&self) -> String {
+        self.view()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&square| match square {
+                        HIDDEN => '.',
+                        FLAG => '@',
+                        MINE => 'X',
+                        0 => '_',
+                        n => char::from(b'0' + n as u8),
+                    })
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        let (height, width) = self.field.size();
+        format!(
+            "<MineField {width}x{height}, mines={}>",
+            self.field.n_mines()
+        )
+    }
+}
+
+impl MineField {
+    // the square at (i, j), if it's on the board and the game's still on
+    fn playable(&self, i: usize, j: usize) -> PyResult<Point> {
+        let (height, width) = self.field.size();
+        if i >= height || j >= width {
+            return Err(PyIndexError::new_err(format!(
+                "({i}, {j}) is off the {width}x{height} board"
+            )));
+        }
+        if self.field.is_over() {
+            return Err(PyValueError::new_err("the game is over"));
+        }
+        Ok(Point(i, j))
+    }
+}
+
+fn outcome(res: MoveResult) -> PyResult<&'static str> {
+    match res {
+        MoveResult::Ok => Ok("ok"),
+        MoveResult::Win => Ok("win"),
+        MoveResult::Lose => Ok("lose"),
+        MoveResult::Err(e) => Err(PyValueError::new_err(e)),
+    }
+}
+
+#[pymodule]
+fn minesweeper(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<MineField>()?;
+    m.add("HIDDEN", HIDDEN)?;
+    m.add("FLAG", FLAG)?;
+    m.add("MINE", MINE)?;
+    Ok(())
+}
@@ -0,0 +1,866 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::Event;
+use crossterm::style::{ContentStyle, StyledContent, Stylize};
+use crossterm::terminal;
+use crossterm::{cursor, QueueableCommand};
+use log::{debug, info};
+
+use common::achievements;
+use common::bell;
+use common::clipboard;
+use common::locale;
+use common::player;
+use common::replay;
+use common::stats::Outcome;
+use common::terminal::padding;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::bot::Bot;
+use crate::cli::Args;
+use crate::config::{Difficulty, Layout, MinesConfig};
+use crate::image::{self, ImageFormat};
+use crate::mbf;
+use crate::mines::{self, MineField, Move, MoveResult, SquareView};
+use crate::mineui::{self, MineUI, MineUIAction, UIMode};
+use crate::solver::{self, Hint, Judgement};
+use crate::viewport::{Viewport, MINIMAP_H, MINIMAP_W};
+
+const DIGIT_STRS: [&str; 9] = ["_", "1", "2", "3", "4", "5", "6", "7", "8"];
+const HIDDEN_STR: &str = "#";
+const MINE_STR: &str = "X";
+const FLAG_STR: &str = "@";
+
+// squares in the copied board, Wordle-style: hidden, empty, 1-8, flag, mine
+const SHARE_HIDDEN: &str = "🟩";
+const SHARE_DIGITS: [&str; 9] = ["⬜", "1️⃣", "2️⃣", "3️⃣", "4️⃣", "5️⃣", "6️⃣", "7️⃣", "8️⃣"];
+const SHARE_FLAG: &str = "🚩";
+const SHARE_MINE: &str = "💣";
+
+// terminal columns each square takes up in the spaced layout, including the
+// space after it (narrower glyphs are padded out)
+const SQUARE_COLS: usize = 2;
+
+// squares between the thin separators of the compact layout
+const GROUP_COLS: usize = 5;
+const SEPARATOR: &str = "│";
+
+// terminal column of the first square (inside a border with ratatui)
+#[cfg(not(feature = "ratatui"))]
+const GRID_LEFT: u16 = 0;
+#[cfg(feature = "ratatui")]
+const GRID_LEFT: u16 = 1;
+
+// terminal (rows, cols) taken up besides the squares: by the lines under
+// them, and the border around them with ratatui
+#[cfg(not(feature = "ratatui"))]
+const FRAME: (usize, usize) = (4, 0);
+#[cfg(feature = "ratatui")]
+const FRAME: (usize, usize) = (5, 2);
+
+// terminal columns of squares to show at least, next to the minimap, on
+// boards that don't fit
+const MIN_VIEW_COLS: usize = 10;
+
+// how often the clock is checked (it only shows whole seconds)
+const CLOCK_TICK: Duration = Duration::from_millis(100);
+
+// ticks the squares still to flag or reveal around a number stay lit for,
+// after selecting it before its flags are all placed (see auto_chord)
+const FLASH_TICKS: u32 = 4;
+
+pub struct MineSweeper {
+    gridh: usize,
+    gridw: usize,
+    field: MineField,
+    start: MineField, // the board before the first move, to judge the moves on
+    moves: Vec<Move>,
+    seed: u64, // the mines were laid out from, to name exported images by
+    ui: MineUI,
+    view: Viewport, // the part of the board that fits in the terminal
+    pub image_format: ImageFormat,
+    pub auto_chord: bool, // selecting a number chords, in either mode
+    pub no_flags: bool,   // NF: no flag mode, and stats apart as mines-nf
+    pub layout: Layout,
+    flashed: Vec<Point>,   // squares lit up around a number not yet chorded
+    flash_ticks: u32,      // left before they go out
+    hint: Option<Point>,   // square pointed out by the last hint, until the next move
+    pub retryable: bool,   // whether a lost board can be tried again
+    analysis: Vec<String>, // what the solver makes of the guesses, once the game's over
+    message: StyledContent<String>,
+    started: Option<Instant>, // time of the first move
+    elapsed: Duration,        // frozen once the game ends
+    redraw: Cell<bool>,
+    help_open: bool,
+    outcome: Outcome, // quit, unless the game was won or lost
+    compact: bool,    // rows without blank lines between them, for short terminals
+    over: bool,       // won, lost or quit
+}
+
+impl MineSweeper {
+    pub fn with_n_mines(height: usize, width: usize, n_mines: usize, seed: u64) -> Self {
+        Self::with_field(MineField::with_n_mines(height, width, n_mines, seed), seed)
+    }
+
+    pub fn with_mine_ratio(height: usize, width: usize, fill_ratio: f64, seed: u64) -> Self {
+        Self::with_field(
+            MineField::with_mine_ratio(height, width, fill_ratio, seed),
+            seed,
+        )
+    }
+
+    // game on [field], whose mines were laid out from [seed]
+    fn with_field(field: MineField, seed: u64) -> Self {
+        let (height, width) = field.size();
+        Self {
+            gridh: height,
+            gridw: width,
+            start: field.clone(),
+            field,
+            moves: Vec::new(),
+            seed,
+            ui: MineUI::new(height, width),
+            view: Viewport::new(height, width),
+            image_format: ImageFormat::Png,
+            auto_chord: false,
+            no_flags: false,
+            layout: Layout::Spaced,
+            flashed: Vec::new(),
+            flash_ticks: 0,
+            hint: None,
+            retryable: false,
+            analysis: Vec::new(),
+            message: StyledContent::new(ContentStyle::default(), "".into()),
+            started: None,
+            elapsed: Duration::ZERO,
+            redraw: Cell::new(true),
+            help_open: false,
+            outcome: Outcome::Quit,
+            compact: false,
+            over: false,
+        }
+    }
+
+    // Default beginner / intermediate / expert boards
+    pub fn new_beginner(seed: u64) -> Self {
+        Self::new_level(Difficulty::Beginner, seed)
+    }
+
+    pub fn new_intermediate(seed: u64) -> Self {
+        Self::new_level(Difficulty::Intermediate, seed)
+    }
+
+    pub fn new_expert(seed: u64) -> Self {
+        Self::new_level(Difficulty::Expert, seed)
+    }
+
+    fn new_level(level: Difficulty, seed: u64) -> Self {
+        let (height, width, n_mines) = level.board();
+        Self::with_n_mines(height, width, n_mines, seed)
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        self.fit_terminal();
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // squeeze the rows together if the board doesn't fit double-spaced (or
+    // always, in the compact layout), and show as much of the board as fits,
+    // next to the minimap if that isn't all of it
+    fn fit_terminal(&mut self) {
+        let Ok((cols, rows)) = terminal::size() else {
+            return;
+        };
+        let rows = (rows as usize).saturating_sub(FRAME.0);
+        let cols = (cols as usize).saturating_sub(FRAME.1);
+        self.compact = self.layout == Layout::Compact || rows < 2 * self.gridh;
+        let row_step = self.grid_rows().1 as usize;
+        let mut width = self.squares_in(cols);
+        if width < self.gridw || rows / row_step < self.gridh {
+            width = self.squares_in(cols.saturating_sub(MINIMAP_W + 1));
+        }
+        self.view.fit(rows / row_step, width, self.ui.get_cursor());
+    }
+
+    // how many squares fit side by side in [cols] terminal columns (however
+    // the separators fall, in the compact layout)
+    fn squares_in(&self, cols: usize) -> usize {
+        match self.layout {
+            _ if self.board_cols() <= cols => self.gridw,
+            Layout::Spaced => cols / SQUARE_COLS,
+            Layout::Compact => cols.saturating_sub(1) * GROUP_COLS / (GROUP_COLS + 1),
+        }
+    }
+
+    // what goes after square [sq_str] in column [j] of a row: padding out to
+    // SQUARE_COLS when spaced, or a separator after every GROUP_COLS squares
+    // when compact
+    fn after_square(&self, sq_str: &str, j: usize) -> &'static str {
+        match self.layout {
+            Layout::Spaced => padding(sq_str, SQUARE_COLS),
+            Layout::Compact
+                if (j + 1).is_multiple_of(GROUP_COLS) && j + 1 < self.view.cols().end =>
+            {
+                SEPARATOR
+            }
+            Layout::Compact => padding(sq_str, 1),
+        }
+    }
+
+    // terminal columns a whole row of squares takes up
+    fn board_cols(&self) -> usize {
+        match self.layout {
+            Layout::Spaced => SQUARE_COLS * self.gridw,
+            Layout::Compact => self.gridw + self.gridw.saturating_sub(1) / GROUP_COLS,
+        }
+    }
+
+    // terminal column (from the left of the squares in view) square column
+    // [j] starts at, when it's in view
+    fn square_col(&self, j: usize) -> usize {
+        let left = self.view.cols().start;
+        match self.layout {
+            Layout::Spaced => SQUARE_COLS * (j - left),
+            Layout::Compact => j - left + j / GROUP_COLS - left / GROUP_COLS,
+        }
+    }
+
+    // terminal row of the first row of squares, and the rows from one row of
+    // squares to the next (inside a border with ratatui, which doesn't space
+    // them out)
+    fn grid_rows(&self) -> (u16, u16) {
+        if cfg!(feature = "ratatui") || self.compact {
+            (1, 1)
+        } else {
+            (2, 2)
+        }
+    }
+
+    // square drawn at a terminal position, if there's one there (rather
+    // than a blank line or a separator)
+    fn square_at(&self, row: u16, col: u16) -> Option<Point> {
+        let (top, row_step) = self.grid_rows();
+        let row = row.checked_sub(top)?;
+        let col = col.checked_sub(GRID_LEFT)? as usize;
+        if row % row_step != 0 {
+            return None;
+        }
+        let square_cols = match self.layout {
+            Layout::Spaced => SQUARE_COLS,
+            Layout::Compact => 1,
+        };
+        let lj = self
+            .view
+            .cols()
+            .position(|j| (self.square_col(j)..self.square_col(j) + square_cols).contains(&col))?;
+        self.view.to_global(((row / row_step) as usize, lj))
+    }
+
+    // the minimap, with the part in view lit up (or reversed, without color)
+    fn styled_minimap(&self) -> Vec<Vec<StyledContent<char>>> {
+        let minimap = self.view.minimap(&self.field);
+        let style = |(glyph, in_view): (char, bool)| match (in_view, style::color_enabled()) {
+            (false, true) => glyph.with(theme::color(Role::Hidden)),
+            (false, false) => glyph.reset(),
+            (true, true) => glyph.with(theme::color(Role::Accent)),
+            (true, false) => glyph.reverse(),
+        };
+        minimap
+            .into_iter()
+            .map(|row| row.into_iter().map(style).collect())
+            .collect()
+    }
+
+    // reveal or flag (or chord) at the cursor, as the mode says
+    fn select(&mut self, mode: UIMode) {
+        let p = self.ui.get_cursor();
+        self.hint = None;
+        let started = *self.started.get_or_insert_with(Instant::now);
+        let move_res = match (&mode, self.field.view_sq(&p)) {
+            (_, Some(SquareView::Revealed(nn))) if self.auto_chord && nn > 0 => self.auto_chord(&p),
+            (UIMode::Reveal, _) => self.act(Move::Reveal(p)),
+            (UIMode::Flag, _) => self.act(Move::ToggleFlag(p)),
+        };
+        debug!("{mode:?} at ({}, {}): {move_res:?}", p.0, p.1);
+        self.over = !self.handle_res(&move_res);
+        self.elapsed = started.elapsed();
+        if self.outcome != Outcome::Quit {
+            self.analyze();
+        }
+        if self.outcome == Outcome::Won {
+            self.unlock_achievements();
+        }
+    }
+
+    // select the square clicked on, or flag it with the right button
+    fn click(&mut self, row: u16, col: u16, flag: bool) {
+        let Some(p) = self.square_at(row, col) else {
+            return;
+        };
+        self.message = "".to_string().reset();
+        // (the cursor can't be off the board, since the square is on it)
+        self.ui.reset_cursor(p).ok();
+        if !flag {
+            self.select(self.ui.mode.clone());
+        } else if self.no_flags {
+            bell::ring("invalid move");
+            self.message = self.fmt_err_msg(locale::text("mines.no_flags").to_string());
+        } else {
+            self.select(UIMode::Flag);
+        }
+    }
+
+    // output indicates whether to keep looping
+    fn handle_res(&mut self, res: &MoveResult) -> bool {
+        match res {
+            MoveResult::Lose => {
+                bell::ring("mine");
+                self.outcome = Outcome::Lost;
+                self.message = locale::text("mines.lose")
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+                false
+            }
+            MoveResult::Win => {
+                bell::ring("win");
+                self.outcome = Outcome::Won;
+                self.message = locale::text("mines.win")
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+                false
+            }
+            MoveResult::Err(ref msg) => {
+                bell::ring("invalid move");
+                self.message = self.fmt_err_msg(msg.to_string());
+                true
+            }
+            MoveResult::Ok => {
+                self.message = "".to_string().reset();
+                true
+            }
+        }
+    }
+
+    fn fmt_err_msg<D: fmt::Display + Stylize<Styled = StyledContent<D>>>(
+        &mut self,
+        msg: D,
+    ) -> StyledContent<D> {
+        msg.with(theme::color(Role::Danger))
+    }
+
+    // chord around the number at [p] if its flags are all placed, or light up
+    // the squares around it still hidden
+    fn auto_chord(&mut self, p: &Point) -> MoveResult {
+        if self.field.is_satisfied(p) {
+            return self.act(Move::Reveal(*p));
+        }
+        self.flashed = self.field.hidden_neighbors(p);
+        self.flash_ticks = FLASH_TICKS;
+        MoveResult::Ok
+    }
+
+    // make a move, keeping it for the analysis at the end
+    fn act(&mut self, action: Move) -> MoveResult {
+        self.moves.push(action);
+        self.field.act(action)
+    }
+
+    // sum up what the solver makes of the moves that risked a mine: how many
+    // there were, how many of those had a safe square to reveal instead,
+    // and (if the game was lost) what the last one was up against
+    fn analyze(&mut self) {
+        let judgements = solver::judge(self.start.clone(), &self.moves);
+        let guesses: Vec<&Judgement> = judgements
+            .iter()
+            .flatten()
+            .filter(|judgement| judgement.is_guess())
+            .collect();
+        let percent = |chance: f64| format!("{:.1}%", 100.0 * chance);
+        self.analysis.clear();
+        if self.outcome == Outcome::Lost {
+            let chorded = match self.moves.last() {
+                Some(Move::Reveal(p)) => {
+                    matches!(self.field.view_sq(p), Some(SquareView::Revealed(_)))
+                }
+                _ => false,
+            };
+            match judgements.last() {
+                Some(Some(judgement)) if judgement.forced => {
+                    self.analysis
+                        .push(locale::text("mines.analysis_forced").into());
+                }
+                Some(Some(judgement)) => self.analysis.push(format!(
+                    "{} {} ({} {})",
+                    locale::text("mines.analysis_guess"),
+                    percent(judgement.chance),
+                    locale::text("mines.analysis_best"),
+                    percent(judgement.best)
+                )),
+                _ if chorded => self
+                    .analysis
+                    .push(locale::text("mines.analysis_chord").into()),
+                _ => {}
+            }
+        }
+        let mut summary = format!(
+            "{} {}",
+            locale::text("mines.analysis_guesses"),
+            guesses.len()
+        );
+        let missed = guesses.iter().filter(|judgement| judgement.forced).count();
+        if missed > 0 {
+            let label = locale::text("mines.analysis_missed");
+            summary.push_str(&format!(", {label} {missed}"));
+        }
+        self.analysis.push(summary);
+    }
+
+    // point out where to go next (see [solver::hint]), and what's known
+    // about the square
+    fn show_hint(&mut self) {
+        let hint = solver::hint(&self.field);
+        debug!("hint: {hint:?}");
+        let text = match hint {
+            Some(Hint::Safe(_)) => locale::text("mines.hint_safe").to_string(),
+            Some(Hint::Mine(_)) => locale::text("mines.hint_mine").to_string(),
+            Some(Hint::Guess(_, chance)) => {
+                let guess = locale::text("mines.hint_guess");
+                format!("{guess} {:.1}%", 100.0 * chance)
+            }
+            None if self.started.is_none() => locale::text("mines.hint_first").to_string(),
+            None => locale::text("mines.hint_none").to_string(),
+        };
+        self.hint = hint.map(|(Hint::Safe(p) | Hint::Mine(p) | Hint::Guess(p, _))| p);
+        if let Some(p) = self.hint {
+            self.view.follow(p);
+        }
+        self.message = text.with(theme::color(Role::Accent));
+    }
+
+    // (styled) string for the square at [p], highlighted if the cursor is on
+    // it, it's lit up or a hint points it out
+    fn styled_square(&self, sq: SquareView, p: Point) -> StyledContent<&'static str> {
+        let sq_str = match sq {
+            SquareView::Hidden => HIDDEN_STR.with(theme::color(Role::Hidden)),
+            SquareView::Flag => FLAG_STR.with(theme::color(Role::Marker)),
+            SquareView::Mine => MINE_STR.with(theme::color(Role::Danger)),
+            SquareView::Revealed(0) => DIGIT_STRS[0].with(theme::color(Role::Muted)),
+            SquareView::Revealed(nn) => DIGIT_STRS[nn as usize].with(theme::color(Role::Text)),
+        };
+        if self.flashed.contains(&p) {
+            if !style::color_enabled() {
+                return sq_str.bold().reverse();
+            }
+            return sq_str
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Danger));
+        }
+        if p != self.ui.get_cursor() && self.hint == Some(p) {
+            if !style::color_enabled() {
+                return sq_str.bold().underlined();
+            }
+            return sq_str
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success));
+        }
+        if p != self.ui.get_cursor() {
+            return sq_str;
+        }
+
+        // (without color, the mode shows as an underline instead)
+        match (&self.ui.mode, style::color_enabled()) {
+            (UIMode::Reveal, true) => sq_str.bold().with(theme::color(Role::Accent)),
+            (UIMode::Flag, true) => sq_str.bold().with(theme::color(Role::Marker)),
+            (UIMode::Reveal, false) => sq_str.bold().reverse(),
+            (UIMode::Flag, false) => sq_str.bold().reverse().underlined(),
+        }
+    }
+
+    // the expert board, won in some time
+    fn unlock_achievements(&self) {
+        achievements::unlock("mines-win");
+        if (self.gridh, self.gridw, self.field.n_mines()) == (16, 30, 99) {
+            achievements::unlock("mines-expert");
+            if self.elapsed < Duration::from_secs(100) {
+                achievements::unlock("mines-expert-100s");
+            }
+        }
+    }
+
+    // write the board as it's shown to an image file, returning its path
+    fn export_image(&self) -> Result<String, String> {
+        let path = format!("mines-{}.{}", self.seed, self.image_format.extension());
+        image::encode(&self.field, self.image_format)
+            .and_then(|image| fs::write(&path, image).map_err(|e| e.to_string()))
+            .map(|()| path)
+    }
+
+    // the board as a grid of emoji to paste anywhere, under a line with its
+    // size, mines, time and how it went
+    fn share_text(&self) -> String {
+        let result = match self.outcome {
+            Outcome::Won => " ✅",
+            Outcome::Lost => " 💥",
+            Outcome::Quit => "",
+        };
+        let mut text = format!(
+            "Minesweeper {}x{}{} 💣{} ⏱️{}s{result}\n",
+            self.gridw,
+            self.gridh,
+            if self.no_flags { " NF" } else { "" },
+            self.field.n_mines(),
+            self.elapsed.as_secs()
+        );
+        for (sq_ix, sq) in self.field.get_view_iter().enumerate() {
+            text.push_str(match sq {
+                SquareView::Hidden => SHARE_HIDDEN,
+                SquareView::Flag => SHARE_FLAG,
+                SquareView::Mine => SHARE_MINE,
+                SquareView::Revealed(n) => SHARE_DIGITS[n as usize],
+            });
+            if (sq_ix + 1) % self.gridw == 0 {
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    // copy the board to the clipboard (see [share_text])
+    fn copy_board(&self) -> Result<(), String> {
+        clipboard::copy(&self.share_text())
+    }
+
+    // whether the game's been lost on a board that can be tried again
+    fn offers_retry(&self) -> bool {
+        self.retryable && self.outcome == Outcome::Lost
+    }
+
+    /// Show the help screen until the next keypress
+    pub fn show_help(&mut self) {
+        self.help_open = true;
+        self.redraw.set(true);
+    }
+}
+
+// Pretty-print
+impl fmt::Display for MineSweeper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row_spacer = if self.compact { "\r\n" } else { "\r\n\r\n" };
+
+        let (rows, cols) = (self.view.rows(), self.view.cols());
+        let board_iter = self.field.get_view_iter();
+        for (sq_ix, sq) in board_iter.enumerate() {
+            // get coordinates of this square (if it's in view)
+            let sqi = sq_ix / self.gridw;
+            let sqj = sq_ix.rem_euclid(self.gridw);
+            if !rows.contains(&sqi) || !cols.contains(&sqj) {
+                continue;
+            }
+            let sq_str = self.styled_square(sq, Point::new(sqi, sqj));
+
+            // start new row
+            if sqj == cols.start {
+                write!(f, "{row_spacer}")?;
+            }
+
+            // draw square
+            write!(f, "{sq_str}{}", self.after_square(sq_str.content(), sqj))?;
+        }
+
+        // draw horizontal axis at the bottom
+        write!(f, "{row_spacer}")?;
+
+        // print clock and message
+        let time = locale::text("mines.time");
+        write!(f, "{time} {}s", self.elapsed.as_secs())?;
+        if self.no_flags {
+            write!(f, "   ({})", locale::text("mines.nf"))?;
+        }
+        write!(f, "\r\n")?;
+        write!(f, "{}\r\n", self.message)?;
+        for line in &self.analysis {
+            write!(f, "{line}\r\n")?;
+        }
+        if self.offers_retry() {
+            write!(f, "{}\r\n", locale::text("mines.retry"))?;
+        }
+        if self.over {
+            write!(f, "{}", locale::text("mines.exit"))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Game for MineSweeper {
+    // (no-flag games are their own discipline, kept apart in the stats)
+    fn name(&self) -> &'static str {
+        if self.no_flags {
+            "mines-nf"
+        } else {
+            "mines"
+        }
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        if let Event::Resize(..) = event {
+            self.fit_terminal();
+        }
+        // any key closes the help screen
+        if self.help_open && matches!(event, Event::Key(_)) {
+            self.help_open = false;
+            self.redraw.set(true);
+            return;
+        }
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != MineUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            MineUIAction::Quit => self.over = true,
+            MineUIAction::Help => self.show_help(),
+            MineUIAction::Wait => {}
+            MineUIAction::Mode(UIMode::Flag) | MineUIAction::ToggleMode if self.no_flags => {
+                bell::ring("invalid move");
+                self.message = self.fmt_err_msg(locale::text("mines.no_flags").to_string());
+            }
+            MineUIAction::Mode(newmode) => self.ui.mode = newmode,
+            MineUIAction::ToggleMode => self.ui.toggle_mode(),
+            MineUIAction::Export => {
+                self.message = match self.export_image() {
+                    Ok(path) => format!("{} {path}", locale::text("mines.exported")).reset(),
+                    Err(e) => {
+                        bell::ring("invalid move");
+                        self.fmt_err_msg(format!("{} {e}", locale::text("mines.export_failed")))
+                    }
+                };
+            }
+            MineUIAction::Copy => {
+                self.message = match self.copy_board() {
+                    Ok(()) => locale::text("mines.copied").to_string().reset(),
+                    Err(e) => self.fmt_err_msg(e),
+                };
+            }
+            MineUIAction::Hint => self.show_hint(),
+            // (only once the game's lost, on the way out)
+            MineUIAction::Retry => {}
+            MineUIAction::Move(movedir) => {
+                self.message = "".to_string().reset();
+                if self.ui.move_cursor(movedir).is_err() {
+                    bell::ring("edge of the board");
+                }
+                self.view.follow(self.ui.get_cursor());
+            }
+            MineUIAction::Select => self.select(self.ui.mode.clone()),
+            MineUIAction::Click { row, col, flag } => self.click(row, col, flag),
+        }
+        self.redraw.set(true);
+    }
+
+    // keep the clock running between moves, and put out lit up squares
+    fn tick(&mut self) {
+        if self.flash_ticks > 0 {
+            self.flash_ticks -= 1;
+            if self.flash_ticks == 0 {
+                self.flashed.clear();
+                self.redraw.set(true);
+            }
+        }
+        let Some(started) = self.started else {
+            return;
+        };
+        let elapsed = started.elapsed();
+        if elapsed.as_secs() != self.elapsed.as_secs() {
+            self.redraw.set(true);
+        }
+        self.elapsed = elapsed;
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        if self.help_open {
+            return write!(out, "\r\n{}\r\n", mineui::help_text());
+        }
+        write!(out, "{self}")?;
+
+        // minimap in the top-right corner of the board, a column clear of
+        // the squares
+        let left = self.square_col(self.view.cols().end - 1) + SQUARE_COLS;
+        let (top, _) = self.grid_rows();
+        for (k, line) in self.styled_minimap().iter().enumerate() {
+            out.queue(cursor::MoveTo(GRID_LEFT + left as u16, top + k as u16))?;
+            for glyph in line {
+                write!(out, "{glyph}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(CLOCK_TICK)
+    }
+
+    // every square, and at least one line per row, or failing that some of
+    // them next to the minimap
+    fn min_size(&self) -> (u16, u16) {
+        let cols = self.board_cols().min(MIN_VIEW_COLS + MINIMAP_W + 1);
+        let rows = self.gridh.min(MINIMAP_H);
+        ((cols + FRAME.1) as u16, (rows + FRAME.0) as u16)
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+// let the bot play [n_games] games on a (height, width, mines) board, one
+// seed after another, and print how it did
+fn run_bot((height, width, n_mines): (usize, usize, usize), first_seed: u64, n_games: u32) {
+    let timer = Instant::now();
+    let (mut n_won, mut n_moves) = (0, 0);
+    for k in 0..n_games {
+        let seed = first_seed.wrapping_add(k as u64);
+        let mut field = MineField::with_n_mines(height, width, n_mines, seed);
+        let outcomes = player::play(&mut field, &mut Bot::new(seed), height * width * 2);
+        if outcomes.last() == Some(&MoveResult::Win) {
+            n_won += 1;
+        }
+        n_moves += outcomes.len();
+    }
+    let elapsed = timer.elapsed().as_secs_f64();
+
+    let n_games = n_games.max(1) as f64;
+    println!("board:   {width}x{height}, {n_mines} mines (seeds from {first_seed})");
+    println!("won:     {n_won} ({:.1}%)", 100.0 * n_won as f64 / n_games);
+    println!("moves:   {:.1} per game", n_moves as f64 / n_games);
+    println!(
+        "elapsed: {elapsed:.3}s ({:.2}ms per game)",
+        1000.0 * elapsed / n_games
+    );
+}
+
+/// Play Minesweeper on the board chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: MinesConfig = common::cli::setup(&args.common, "mines")?;
+    let level = if args.expert {
+        Some(Difficulty::Expert)
+    } else if args.intermediate {
+        Some(Difficulty::Intermediate)
+    } else if args.beginner {
+        Some(Difficulty::Beginner)
+    } else {
+        config.difficulty
+    };
+
+    let (height, width, n_mines) = match (args.height, args.width, args.mines) {
+        (Some(height), Some(width), Some(n_mines)) => {
+            mines::check_board(height, width, n_mines)?;
+            (height, width, n_mines)
+        }
+        _ => level.unwrap_or(Difficulty::Beginner).board(),
+    };
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    if let Some(n_games) = args.bot {
+        run_bot((height, width, n_mines), seed, n_games);
+        return Ok(());
+    }
+    if let Some(path) = &args.save_board {
+        let field = MineField::with_n_mines(height, width, n_mines, seed);
+        mbf::encode(&field)
+            .and_then(|mbf| fs::write(path, mbf).map_err(|e| e.to_string()))
+            .map_err(|e| format!("{} {e}", locale::text("mines.export_failed")))?;
+        println!("{} {}", locale::text("mines.exported"), path.display());
+        return Ok(());
+    }
+    info!("mines seed {seed}");
+    let mbf = match &args.board {
+        Some(path) => {
+            Some(fs::read(path).map_err(|e| format!("failed to read {}: {e}", path.display()))?)
+        }
+        None => None,
+    };
+    // (each try at the board starts from the same seed, so gets the same mines)
+    let new_game = || -> Result<MineSweeper, String> {
+        let field = match (&mbf, &args.board) {
+            (Some(mbf), Some(path)) => {
+                mbf::decode(mbf, seed).map_err(|e| format!("bad board {}: {e}", path.display()))?
+            }
+            _ => MineField::with_n_mines(height, width, n_mines, seed),
+        };
+        let mut game = MineSweeper::with_field(field, seed);
+        game.image_format = args.image_format;
+        game.auto_chord = args.auto_chord || config.auto_chord;
+        game.no_flags = args.no_flags || config.no_flags;
+        game.layout = args.layout.or(config.layout).unwrap_or_default();
+        // (a recording or replay only covers the one game)
+        game.retryable = !replay::is_recording() && !replay::is_replaying();
+        game.ui
+            .bindings
+            .remap(&config.keys, &mineui::ACTIONS)
+            .map_err(|e| format!("bad [mines.keys] config: {e}"))?;
+        Ok(game)
+    };
+    let mut game = new_game()?;
+
+    let guard =
+        TerminalGuard::with_mouse().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    game.show_help();
+    let action = loop {
+        game.game_loop().map_err(|e| e.to_string())?;
+        // (the finished board can still be saved or copied, on the way out,
+        // or a lost one tried again)
+        let action = game.ui.wait_for_action_block().ok();
+        if action == Some(MineUIAction::Retry) && game.offers_retry() {
+            info!("trying the board from seed {seed} again");
+            game = new_game()?;
+            continue;
+        }
+        break action;
+    };
+    drop(guard);
+    match action {
+        Some(MineUIAction::Export) => {
+            let path = game
+                .export_image()
+                .map_err(|e| format!("{} {e}", locale::text("mines.export_failed")))?;
+            println!("{} {path}", locale::text("mines.exported"));
+        }
+        Some(MineUIAction::Copy) => {
+            game.copy_board()?;
+            println!("{}", locale::text("mines.copied"));
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use ratatui::layout::Rect;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+use common::Point;
+
+use super::MineSweeper;
+use crate::mineui::{self, UIMode};
+
+impl TuiGame for MineSweeper {
+    fn draw(&self, frame: &mut Frame) {
+        let squares: Vec<_> = self.field.get_view_iter().collect();
+        let rows = squares
+            .chunks(self.gridw)
+            .enumerate()
+            .filter(|(i, _)| self.view.rows().contains(i))
+            .map(|(i, row)| {
+                let cols = self.view.cols();
+                let spans = row[cols.clone()].iter().zip(cols).flat_map(|(&sq, j)| {
+                    let styled = self.styled_square(sq, Point::new(i, j));
+                    let after = self.after_square(styled.content(), j);
+                    [tui::span(&styled), Span::raw(after)]
+                });
+                Line::from_iter(spans)
+            })
+            .collect();
+        let mode = match self.ui.mode {
+            UIMode::Reveal => locale::text("mines.reveal"),
+            UIMode::Flag => locale::text("mines.flag"),
+        };
+        let title = if self.no_flags {
+            format!(" {mode} ({}) ", locale::text("mines.nf"))
+        } else {
+            format!(" {mode} ")
+        };
+        let board = Board::new(rows).title(title);
+
+        let message = tui::span(&self.message);
+        let time = locale::text("mines.time");
+        let status = StatusBar::new(locale::text("mines.status_help"))
+            .line(format!("{time} {}s", self.elapsed.as_secs()))
+            .line(message.clone());
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        let (board_cols, _) = board.size();
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+
+        // minimap to the right of the board
+        let minimap = self.styled_minimap();
+        if !minimap.is_empty() {
+            let left = board_cols + 1;
+            let area = Rect {
+                x: board_area.x + left.min(board_area.width),
+                width: board_area.width.saturating_sub(left),
+                ..board_area
+            };
+            let lines: Vec<Line> = minimap
+                .iter()
+                .map(|row| row.iter().map(tui::span).collect())
+                .collect();
+            frame.render_widget(Paragraph::new(lines), area);
+        }
+
+        if self.help_open {
+            let title = format!(" {} ", locale::text("mines.help_title"));
+            let text = mineui::help_text().replace('\r', "");
+            frame.render_widget(Dialog::new(&title, text), board_area);
+        } else if self.over {
+            let mut lines = vec![Line::from(locale::text("mines.exit"))];
+            if self.offers_retry() {
+                lines.insert(0, Line::from(locale::text("mines.retry")));
+            }
+            for line in self.analysis.iter().rev() {
+                lines.insert(0, Line::from(line.as_str()));
+            }
+            if !message.content.is_empty() {
+                lines.insert(0, Line::from(message));
+            }
+            let title = format!(" {} ", locale::text("mines.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use common::{Core, Player, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::mines::{MineField, Move, SquareView};
+use crate::solver;
+
+/// Computer player: makes whatever moves the numbers on the board make
+/// certain, and guesses when there aren't any
+pub struct Bot {
+    rng: StdRng, // for guessing (the same seed always guesses the same way)
+}
+
+impl Bot {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Player<MineField> for Bot {
+    fn choose_action(&mut self, field: &MineField) -> Option<Move> {
+        if let Some(action) = solver::certain_move(field) {
+            return Some(action);
+        }
+
+        // nothing's certain
+        let (height, width) = field.size();
+        let hidden: Vec<_> = (0..height)
+            .flat_map(|i| (0..width).map(move |j| Point(i, j)))
+            .filter(|&p| matches!(field.cell(p), SquareView::Hidden))
+            .collect();
+        let &p = hidden.choose(&mut self.rng)?;
+        debug!(
+            "bot guessing at ({}, {}), out of {}",
+            p.0,
+            p.1,
+            hidden.len()
+        );
+        Some(Move::Reveal(p))
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+use crate::config::Layout;
+use crate::image::ImageFormat;
+
+/// Minesweeper
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on losing, winning, invalid moves and achievements")
+}))]
+pub struct Args {
+    /// 8x8 board with 10 mines (the default, unless the config file picks another)
+    #[arg(long, group = "level")]
+    pub beginner: bool,
+
+    /// 16x16 board with 40 mines
+    #[arg(long, group = "level")]
+    pub intermediate: bool,
+
+    /// 30x16 board with 99 mines
+    #[arg(long, group = "level")]
+    pub expert: bool,
+
+    /// Custom board height (needs --width and --mines too)
+    #[arg(long, requires_all = ["width", "mines"], conflicts_with = "level")]
+    pub height: Option<usize>,
+
+    /// Custom board width
+    #[arg(long, requires_all = ["height", "mines"], conflicts_with = "level")]
+    pub width: Option<usize>,
+
+    /// Number of mines on a custom board
+    #[arg(long, requires_all = ["height", "width"], conflicts_with = "level")]
+    pub mines: Option<usize>,
+
+    /// Random seed for laying out the mines (picked at random if not given;
+    /// --bot uses consecutive seeds)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Play a board loaded from an MBF file (the format other Minesweeper
+    /// programs share boards in), instead of one laid out at random
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["level", "height", "bot", "save_board"])]
+    pub board: Option<PathBuf>,
+
+    /// Save the board --seed lays out to an MBF file, instead of playing it
+    #[arg(long, value_name = "FILE", conflicts_with = "bot")]
+    pub save_board: Option<PathBuf>,
+
+    /// Let the computer play this many games without a UI, and sum up how it did
+    #[arg(long, value_name = "GAMES")]
+    pub bot: Option<u32>,
+
+    /// Chord on selecting a number whose flags are all placed, in flag mode
+    /// too (or light up the squares around it left to flag, if they aren't)
+    #[arg(long)]
+    pub auto_chord: bool,
+
+    /// Play without flags (NF), which is kept in the stats as its own game,
+    /// mines-nf, for its own wins and best times
+    #[arg(long, alias = "nf")]
+    pub no_flags: bool,
+
+    /// Board layout: spaced (two columns to a square, the default), or
+    /// compact (one, with thin separators), for narrow terminals
+    #[arg(long)]
+    pub layout: Option<Layout>,
+
+    /// Format of images saved with <e>: png, or svg
+    #[arg(long, default_value = "png")]
+    pub image_format: ImageFormat,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
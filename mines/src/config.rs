@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Standard board sizes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Expert,
+}
+
+impl Difficulty {
+    /// Board size and mine count, as (height, width, mines)
+    pub fn board(self) -> (usize, usize, usize) {
+        match self {
+            Difficulty::Beginner => (8, 8, 10),
+            Difficulty::Intermediate => (16, 16, 40),
+            Difficulty::Expert => (16, 30, 99),
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "beginner" => Ok(Difficulty::Beginner),
+            "intermediate" => Ok(Difficulty::Intermediate),
+            "expert" => Ok(Difficulty::Expert),
+            _ => Err(format!(
+                "unknown difficulty '{s}' (expected beginner, intermediate or expert)"
+            )),
+        }
+    }
+}
+
+/// How the squares are spaced out on screen
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Layout {
+    /// Two columns to a square, and a blank line between rows if there's room
+    #[default]
+    Spaced,
+    /// A column to a square, with a thin separator every few, and no blank
+    /// lines, so expert boards fit in narrow terminals
+    Compact,
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "spaced" => Ok(Layout::Spaced),
+            "compact" => Ok(Layout::Compact),
+            _ => Err(format!("unknown layout '{s}' (expected spaced or compact)")),
+        }
+    }
+}
+
+/// Settings from the [mines] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MinesConfig {
+    /// board to play when none is picked on the command line
+    #[serde(deserialize_with = "common::config::from_str")]
+    pub difficulty: Option<Difficulty>,
+    /// "spaced" or "compact" (see [Layout])
+    #[serde(deserialize_with = "common::config::from_str")]
+    pub layout: Option<Layout>,
+    /// selecting a number with all its flags placed chords, in either mode
+    pub auto_chord: bool,
+    /// play without flags, with stats of its own (see --no-flags)
+    pub no_flags: bool,
+    /// keys for each action, e.g. select = ["space", "enter"]
+    pub keys: KeyMap,
+}
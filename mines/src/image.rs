@@ -0,0 +1,332 @@
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use common::{Core, Point};
+
+use crate::mines::{MineField, SquareView};
+
+// squares are drawn [TILE] pixels across, the way the classic game has them,
+// with a gray border [MARGIN] pixels wide around the board (sizes are in
+// pixels of the board, before [SCALE])
+const TILE: usize = 16;
+const MARGIN: usize = 8;
+
+// images are shown at twice the size, to stay sharp on screens that scale up
+const SCALE: usize = 2;
+
+type Rgb = (u8, u8, u8);
+
+const GRAY: Rgb = (0xc0, 0xc0, 0xc0);
+const LIGHT: Rgb = (0xff, 0xff, 0xff);
+const SHADOW: Rgb = (0x80, 0x80, 0x80);
+const BLACK: Rgb = (0x00, 0x00, 0x00);
+const RED: Rgb = (0xff, 0x00, 0x00);
+
+// the classic color of each count of neighboring mines, from 1
+const DIGIT_COLORS: [Rgb; 8] = [
+    (0x00, 0x00, 0xff),
+    (0x00, 0x80, 0x00),
+    (0xff, 0x00, 0x00),
+    (0x00, 0x00, 0x80),
+    (0x80, 0x00, 0x00),
+    (0x00, 0x80, 0x80),
+    (0x00, 0x00, 0x00),
+    (0x80, 0x80, 0x80),
+];
+
+// counts of neighboring mines, from 1, drawn at twice this size ('#' is the
+// digit's color)
+const DIGITS: [[&str; 7]; 8] = [
+    [
+        "..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###.",
+    ],
+    [
+        ".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####",
+    ],
+    [
+        "####.", "....#", "....#", ".###.", "....#", "....#", "####.",
+    ],
+    [
+        "...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#.",
+    ],
+    [
+        "#####", "#....", "####.", "....#", "....#", "#...#", ".###.",
+    ],
+    [
+        ".###.", "#....", "#....", "####.", "#...#", "#...#", ".###.",
+    ],
+    [
+        "#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#...",
+    ],
+    [
+        ".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###.",
+    ],
+];
+
+// '#' is black and 'o' a white glint
+const MINE: [&str; 13] = [
+    "......#......",
+    "......#......",
+    "..#.#####.#..",
+    "...#######...",
+    "..##oo#####..",
+    "..##oo#####..",
+    "#############",
+    "..#########..",
+    "..#########..",
+    "...#######...",
+    "..#.#####.#..",
+    "......#......",
+    "......#......",
+];
+
+// 'r' is red and '#' black
+const FLAG: [&str; 10] = [
+    "....rr#...",
+    "..rrrr#...",
+    "rrrrrr#...",
+    "..rrrr#...",
+    "....rr#...",
+    "......#...",
+    "......#...",
+    ".....###..",
+    "...#######",
+    "...#######",
+];
+
+/// File format for exported images
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    /// PNG
+    Png,
+    /// SVG, with each kind of square drawn once and reused
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "png" => Ok(ImageFormat::Png),
+            "svg" => Ok(ImageFormat::Svg),
+            _ => Err(format!("unknown image format '{s}' (expected png or svg)")),
+        }
+    }
+}
+
+// a filled rectangle, as (x, y, width, height, color) in image pixels
+type Rect = (usize, usize, usize, usize, Rgb);
+
+/// Encode the board as it's shown (squares not yet revealed stay hidden)
+/// as an image with the look of the classic game: raised gray squares,
+/// sunken revealed ones with colored counts, flags and mines
+pub fn encode(field: &MineField, format: ImageFormat) -> Result<Vec<u8>, String> {
+    let (height, width) = field.size();
+    let squares: Vec<(Point, SquareView)> = (0..height)
+        .flat_map(|i| (0..width).map(move |j| Point(i, j)))
+        .map(|p| (p, field.cell(p)))
+        .collect();
+    let size = (width * TILE + 2 * MARGIN, height * TILE + 2 * MARGIN);
+    match format {
+        ImageFormat::Png => png(size, &squares),
+        ImageFormat::Svg => Ok(svg(size, &squares).into_bytes()),
+    }
+}
+
+// every square painted into a grid of pixels, scaled up and PNG encoded
+fn png((img_w, img_h): (usize, usize), squares: &[(Point, SquareView)]) -> Result<Vec<u8>, String> {
+    let mut pixels = vec![GRAY; img_w * img_h];
+    let mut paint = |(x0, y0): (usize, usize), rects: &[Rect]| {
+        for &(x, y, w, h, color) in rects {
+            for row in y0 + y..y0 + y + h {
+                pixels[row * img_w + x0 + x..row * img_w + x0 + x + w].fill(color);
+            }
+        }
+    };
+    paint((0, 0), &frame(img_w, img_h));
+    for &(p, sq) in squares {
+        paint(origin(p), &tile(sq));
+    }
+
+    let rgb: Vec<u8> = pixels
+        .chunks(img_w)
+        .flat_map(|row| {
+            let row: Vec<u8> = row
+                .iter()
+                .flat_map(|&(r, g, b)| [[r, g, b]; SCALE])
+                .flatten()
+                .collect();
+            vec![row; SCALE]
+        })
+        .flatten()
+        .collect();
+
+    let mut image = Vec::new();
+    let (png_w, png_h) = (img_w * SCALE, img_h * SCALE);
+    let mut encoder = png::Encoder::new(&mut image, png_w as u32, png_h as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&rgb))
+        .map_err(|e| format!("PNG encoding failed: {e}"))?;
+    Ok(image)
+}
+
+// each kind of square on the board defined once, then placed everywhere
+// it's needed
+fn svg((img_w, img_h): (usize, usize), squares: &[(Point, SquareView)]) -> String {
+    let (svg_w, svg_h) = (img_w * SCALE, img_h * SCALE);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{svg_w}\" height=\"{svg_h}\" \
+         viewBox=\"0 0 {img_w} {img_h}\" shape-rendering=\"crispEdges\">\n"
+    );
+    let kinds: BTreeSet<String> = squares.iter().map(|&(_, sq)| tile_id(sq)).collect();
+    svg += "<defs>\n";
+    for id in &kinds {
+        let &(_, sq) = squares
+            .iter()
+            .find(|&&(_, sq)| &tile_id(sq) == id)
+            .expect("a square of each kind");
+        svg += &format!("<g id=\"{id}\">{}</g>\n", svg_rects(&tile(sq)));
+    }
+    svg += "</defs>\n";
+    svg += &svg_rects(&[(0, 0, img_w, img_h, GRAY)]);
+    svg += &svg_rects(&frame(img_w, img_h));
+    svg += "\n";
+    for &(p, sq) in squares {
+        let (x, y) = origin(p);
+        svg += &format!("<use href=\"#{}\" x=\"{x}\" y=\"{y}\"/>\n", tile_id(sq));
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+fn svg_rects(rects: &[Rect]) -> String {
+    rects
+        .iter()
+        .map(|&(x, y, w, h, (r, g, b))| {
+            format!(
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" \
+                 fill=\"#{r:02x}{g:02x}{b:02x}\"/>"
+            )
+        })
+        .collect()
+}
+
+// name a kind of square goes by in an SVG
+fn tile_id(sq: SquareView) -> String {
+    match sq {
+        SquareView::Hidden => "hidden".to_string(),
+        SquareView::Flag => "flag".to_string(),
+        SquareView::Mine => "mine".to_string(),
+        SquareView::Revealed(n) => format!("n{n}"),
+    }
+}
+
+// top left corner of the square at [p], in image pixels
+fn origin(Point(i, j): Point) -> (usize, usize) {
+    (MARGIN + j * TILE, MARGIN + i * TILE)
+}
+
+// the board sunk into the gray around it
+fn frame(img_w: usize, img_h: usize) -> Vec<Rect> {
+    let (w, h) = (img_w - 2 * MARGIN, img_h - 2 * MARGIN);
+    let edge = MARGIN / 2;
+    let (x, y) = (MARGIN - edge, MARGIN - edge);
+    let (outer_w, outer_h) = (w + 2 * edge, h + 2 * edge);
+    vec![
+        (x, y + outer_h - edge, outer_w, edge, LIGHT),
+        (x + outer_w - edge, y, edge, outer_h, LIGHT),
+        (x, y, outer_w - edge, edge, SHADOW),
+        (x, y, edge, outer_h - edge, SHADOW),
+    ]
+}
+
+// a square, drawn from its top left corner
+fn tile(sq: SquareView) -> Vec<Rect> {
+    match sq {
+        SquareView::Hidden => raised(),
+        SquareView::Flag => {
+            let mut rects = raised();
+            rects.extend(bitmap(&FLAG, (3, 3), 1, |c| match c {
+                'r' => Some(RED),
+                '#' => Some(BLACK),
+                _ => None,
+            }));
+            rects
+        }
+        SquareView::Mine => {
+            let mut rects = sunken();
+            rects.extend(bitmap(&MINE, (2, 2), 1, |c| match c {
+                '#' => Some(BLACK),
+                'o' => Some(LIGHT),
+                _ => None,
+            }));
+            rects
+        }
+        SquareView::Revealed(0) => sunken(),
+        SquareView::Revealed(n) => {
+            let color = DIGIT_COLORS[n as usize - 1];
+            let mut rects = sunken();
+            rects.extend(bitmap(&DIGITS[n as usize - 1], (3, 1), 2, |c| {
+                (c == '#').then_some(color)
+            }));
+            rects
+        }
+    }
+}
+
+// a square still to be revealed: lit from the top left
+fn raised() -> Vec<Rect> {
+    let edge = 2;
+    vec![
+        (0, 0, TILE, TILE, GRAY),
+        (0, 0, TILE - 1, edge, LIGHT),
+        (0, 0, edge, TILE - 1, LIGHT),
+        (1, TILE - edge, TILE - 1, edge, SHADOW),
+        (TILE - edge, 1, edge, TILE - 1, SHADOW),
+    ]
+}
+
+// a revealed square: flat, with a grid line along its top and left
+fn sunken() -> Vec<Rect> {
+    vec![
+        (0, 0, TILE, TILE, GRAY),
+        (0, 0, TILE, 1, SHADOW),
+        (0, 0, 1, TILE, SHADOW),
+    ]
+}
+
+// the pixels of [art] (as colored by [color]) from [at], each [scale]
+// image pixels across, merging runs of a color along each row
+fn bitmap(
+    art: &[&str],
+    (x0, y0): (usize, usize),
+    scale: usize,
+    color: impl Fn(char) -> Option<Rgb>,
+) -> Vec<Rect> {
+    let mut rects = Vec::new();
+    for (y, row) in art.iter().enumerate() {
+        let row: Vec<char> = row.chars().collect();
+        let mut x = 0;
+        while x < row.len() {
+            let run = row[x..].iter().take_while(|&&c| c == row[x]).count();
+            if let Some(rgb) = color(row[x]) {
+                rects.push((x0 + x * scale, y0 + y * scale, run * scale, scale, rgb));
+            }
+            x += run;
+        }
+    }
+    rects
+}
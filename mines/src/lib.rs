@@ -0,0 +1,32 @@
+//! Minesweeper: the game core (a minefield and its rules), plus the
+//! crossterm frontend for playing it in a terminal
+
+mod bot;
+pub mod mbf;
+mod mines;
+pub mod solver;
+pub use bot::Bot;
+pub use mines::{check_board, MineField, Move, MoveResult, SquareView, MAX_SIDE};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod image;
+#[cfg(feature = "terminal")]
+mod mineui;
+#[cfg(feature = "terminal")]
+mod race;
+#[cfg(feature = "terminal")]
+mod server;
+#[cfg(feature = "terminal")]
+mod viewport;
+#[cfg(feature = "terminal")]
+pub use app::{run, MineSweeper};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
+#[cfg(feature = "terminal")]
+pub use server::serve;
@@ -0,0 +1,66 @@
+use common::Grid2D;
+
+use crate::mines::MineField;
+
+// an MBF board can't be more than a byte's worth of squares across or down
+const MAX_SIDE: usize = u8::MAX as usize;
+
+/// Decode a board in MBF, the binary format Minesweeper Arbiter and other
+/// clones share boards in: the width and height (a byte each), the number
+/// of mines (two bytes, big-endian), then each mine's column and row (a byte
+/// each). A mine under the first square revealed is moved as if from [seed]
+pub fn decode(mbf: &[u8], seed: u64) -> Result<MineField, String> {
+    let [width, height, n_hi, n_lo, mines @ ..] = mbf else {
+        return Err("MBF board is too short".into());
+    };
+    let (width, height) = (*width as usize, *height as usize);
+    let n_mines = u16::from_be_bytes([*n_hi, *n_lo]) as usize;
+    if width == 0 || height == 0 {
+        return Err("MBF board has no squares".into());
+    }
+    if mines.len() != 2 * n_mines {
+        return Err(format!(
+            "MBF board says it has {n_mines} mines, but has {} bytes of them (2 a mine)",
+            mines.len()
+        ));
+    }
+    if n_mines >= width * height {
+        return Err(format!(
+            "{n_mines} mines won't fit on a {width}x{height} board"
+        ));
+    }
+
+    let mut grid = Grid2D::new(height, width);
+    for mine in mines.chunks(2) {
+        let (j, i) = (mine[0] as usize, mine[1] as usize);
+        match grid.get_mut((i, j)) {
+            None => return Err(format!("mine at ({j}, {i}) is off the board")),
+            Some(true) => return Err(format!("two mines at ({j}, {i})")),
+            Some(square) => *square = true,
+        }
+    }
+    Ok(MineField::with_mines(grid, seed))
+}
+
+/// Encode where the board's mines are in MBF (see [decode])
+pub fn encode(field: &MineField) -> Result<Vec<u8>, String> {
+    let mines = field.mines();
+    let (height, width) = mines.dim();
+    if height > MAX_SIDE || width > MAX_SIDE {
+        return Err(format!(
+            "a {width}x{height} board is too big for MBF (at most {MAX_SIDE}x{MAX_SIDE})"
+        ));
+    }
+    let positions: Vec<(usize, usize)> = mines
+        .indexed_iter()
+        .filter(|&(_, &mine)| mine)
+        .map(|(p, _)| p)
+        .collect();
+
+    let mut mbf = vec![width as u8, height as u8];
+    mbf.extend((positions.len() as u16).to_be_bytes());
+    for (i, j) in positions {
+        mbf.extend([j as u8, i as u8]);
+    }
+    Ok(mbf)
+}
@@ -1,14 +1,17 @@
 use std::fmt;
+use std::str::FromStr;
 
+use common::{Core, Grid2D, Point, Topology};
 use itertools::izip;
-use ndarray::{azip, s, Array, Array2, Zip};
+use log::debug;
+use ndarray::{azip, Array, Zip};
 use rand::{
     distributions::{Bernoulli, Distribution},
+    rngs::StdRng,
     seq::SliceRandom,
+    SeedableRng,
 };
 
-use crate::Point;
-
 // values to show on revealed non-mine squares
 // const DIGIT_STRS: [&str; 9] = ["⬜️", "1", "2", "3", "4", "5", "6", "7", "8"];
 const DIGIT_STRS: [&str; 9] = ["_", "1", "2", "3", "4", "5", "6", "7", "8"];
@@ -16,6 +19,27 @@ const HIDDEN_STR: &str = "#";
 const MINE_STR: &str = "X";
 const FLAG_STR: &str = "@";
 
+/// Most squares a board can have across or down (so a board can't be asked
+/// for that's too big to lay out)
+pub const MAX_SIDE: usize = 1000;
+
+/// Check that a height x width board can be laid out with n_mines on it
+/// (leaving at least one square free)
+pub fn check_board(height: usize, width: usize, n_mines: usize) -> Result<(), String> {
+    if !(1..=MAX_SIDE).contains(&height) || !(1..=MAX_SIDE).contains(&width) {
+        return Err(format!(
+            "boards are 1 to {MAX_SIDE} squares high and wide, not {width}x{height}"
+        ));
+    }
+    if n_mines >= height * width {
+        return Err(format!(
+            "{n_mines} mines won't fit on a {width}x{height} board"
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
 pub enum SquareView {
     Hidden,
     Flag,
@@ -33,61 +57,31 @@ pub enum MoveResult {
     Err(String),
 }
 
+/// A move a player can make on a minefield
+#[derive(Clone, Copy)]
+pub enum Move {
+    Reveal(Point),
+    ToggleFlag(Point),
+}
+
+#[derive(Clone)]
 pub struct MineField {
-    mines: Array2<bool>,    // mines[i,j] == true if mine is at (i,j)
-    neighbors: Array2<u32>, // neighbors[i,j] == # of neighboring mines
-    revealed: Array2<bool>, // revealed[i,j] == true if (i,j) has been revealed
-    flagged: Array2<bool>,  // flagged[i,j] == true if flag has been placed at (i,j)
+    mines: Grid2D<bool>,    // mines[i,j] == true if mine is at (i,j)
+    neighbors: Grid2D<u32>, // neighbors[i,j] == # of neighboring mines
+    revealed: Grid2D<bool>, // revealed[i,j] == true if (i,j) has been revealed
+    flagged: Grid2D<bool>,  // flagged[i,j] == true if flag has been placed at (i,j)
     n_revealed: u32,
-    dim: (usize, usize),
+    rng: StdRng, // for placing mines (and moving them off a first click)
 }
 
 impl MineField {
-    //////////
-    // Statics
-    //////////
-
-    // count how many neighboring mines each square has
-    // only need to call this once when building the minefield
-    fn n_neighbors_grid(mines: &Array2<bool>) -> Array2<u32> {
-        // mines has size (M,N)
-        // create copy of mines (as u32) with 1 layer of zero-padding
-        let (gridh, gridw) = mines.dim(); // (M, N)
-        let mut mines_pad: Array2<u32> = Array2::zeros((gridh + 2, gridw + 2)); // size (M+2, N+2)
-        mines
-            .mapv(|x| x as u32)
-            .assign_to(mines_pad.slice_mut(s![1..-1, 1..-1]));
-
-        // final array
-        let mut nn: Array2<u32> = Array2::zeros(mines.raw_dim());
-
-        // add up/down/left/right neighbors
-        azip!((
-            x in &mut nn,
-            &d  in &mines_pad.slice(s![2..  , 1..-1]), // lower neighbors
-            &u  in &mines_pad.slice(s![ ..-2, 1..-1]), // upper neighbors
-            &r  in &mines_pad.slice(s![1..-1, 2..  ]), // right neighbors
-            &l  in &mines_pad.slice(s![1..-1,  ..-2]), // left neighbors
-        ) *x = d + u + l + r);
-
-        // add diagonal neighbors
-        azip!((
-            x in &mut nn,
-            &dr in &mines_pad.slice(s![2..  , 2..  ]), // lower-right neighbors
-            &ur in &mines_pad.slice(s![ ..-2, 2..  ]), // upper-right neighbors
-            &dl in &mines_pad.slice(s![2..  ,  ..-2]), // lower-left neighbors
-            &ul in &mines_pad.slice(s![ ..-2,  ..-2])  // upper-left neighbors
-        ) *x = *x + dr + dl + ur + ul);
-
-        nn
-    }
-
     ///////////////
     // Constructors
     ///////////////
 
     // build a minefield with a given # of mines
-    pub fn with_n_mines(height: usize, width: usize, n_mines: usize) -> Self {
+    // (the same seed always gives the same minefield)
+    pub fn with_n_mines(height: usize, width: usize, n_mines: usize, seed: u64) -> Self {
         // check inputs
         let n_cells = height * width;
         assert!(height > 0 && width > 0, "grid size must be non-zero!");
@@ -100,64 +94,83 @@ impl MineField {
         );
 
         // build mine field
-        // let mut rng = rand::thread_rng();
-        let mine_ixs = rand::seq::index::sample(&mut rand::thread_rng(), n_cells, n_mines);
-        let mut mines = Array2::default([height, width]);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mine_ixs = rand::seq::index::sample(&mut rng, n_cells, n_mines);
+        let mut mines = Grid2D::new(height, width);
         for ix in mine_ixs {
             let i = ix / width;
             let j = ix.rem_euclid(width);
-            *mines.get_mut((i, j)).unwrap() = true;
+            mines[(i, j)] = true;
         }
 
         // build other struct fields
-        let revealed = Array2::default(mines.raw_dim());
-        let neighbors = Self::n_neighbors_grid(&mines);
-        let flagged = Array2::default(mines.raw_dim());
-        let dim = mines.dim();
+        let revealed = Grid2D::new(height, width);
+        let neighbors = mines.neighbor_counts(Topology::Bounded);
+        let flagged = Grid2D::new(height, width);
 
         Self {
-            mines: mines,
-            neighbors: neighbors,
-            revealed: revealed,
-            flagged: flagged,
+            mines,
+            neighbors,
+            revealed,
+            flagged,
             n_revealed: 0,
-            dim: dim,
+            rng,
         }
     }
 
     // build a minefield with a given ratio of mines
     #[allow(dead_code)]
-    pub fn with_mine_ratio(height: usize, width: usize, fill_ratio: f64) -> Self {
+    pub fn with_mine_ratio(height: usize, width: usize, fill_ratio: f64, seed: u64) -> Self {
         // check inputs
         let n_cells = height * width;
         assert!(height > 0 && width > 0, "grid size must be non-zero!");
 
         // build mine field
-        let rng = rand::thread_rng();
+        let mut rng = StdRng::seed_from_u64(seed);
         let bernoulli = Bernoulli::new(fill_ratio)
             .expect("bad fill ratio (should be between 0 - 1)")
-            .sample_iter(rng)
+            .sample_iter(&mut rng)
             .take(n_cells);
-        let mines = Array::from_iter(bernoulli)
-            .into_shape([height, width])
-            .unwrap();
+        let mines = Grid2D::from_array(
+            Array::from_iter(bernoulli)
+                .into_shape([height, width])
+                .unwrap(),
+        );
 
         // build other struct fields
-        let revealed = Array2::default(mines.raw_dim());
-        let neighbors = Self::n_neighbors_grid(&mines);
-        let flagged = Array2::default(mines.raw_dim());
-        let dim = mines.dim();
+        let revealed = Grid2D::new(height, width);
+        let neighbors = mines.neighbor_counts(Topology::Bounded);
+        let flagged = Grid2D::new(height, width);
+
+        Self {
+            mines,
+            neighbors,
+            revealed,
+            flagged,
+            n_revealed: 0,
+            rng,
+        }
+    }
 
+    // build a minefield with mines exactly where [mines] has them
+    // (a mine under the first move is moved as if from [seed])
+    pub(crate) fn with_mines(mines: Grid2D<bool>, seed: u64) -> Self {
+        let (height, width) = mines.dim();
         Self {
-            mines: mines,
-            neighbors: neighbors,
-            revealed: revealed,
-            flagged: flagged,
+            neighbors: mines.neighbor_counts(Topology::Bounded),
+            mines,
+            revealed: Grid2D::new(height, width),
+            flagged: Grid2D::new(height, width),
             n_revealed: 0,
-            dim: dim,
+            rng: StdRng::seed_from_u64(seed),
         }
     }
 
+    // where the mines are (which the player isn't meant to see)
+    pub(crate) fn mines(&self) -> &Grid2D<bool> {
+        &self.mines
+    }
+
     ///////////
     // Privates
     ///////////
@@ -165,44 +178,25 @@ impl MineField {
     // check whether square has mine,
     // without fully revealing it
     fn peek_mine(&self, p: &Point) -> Option<&bool> {
-        self.mines.get(p.tuple())
+        self.mines.get(*p)
     }
 
     // check whether square is revealed
     fn is_revealed(&self, p: &Point) -> Option<bool> {
-        match self.revealed.get(p.tuple()) {
-            Some(x) => Some(x).copied(),
-            None => None,
-        }
+        self.revealed.get(*p).copied()
     }
 
     fn neighbors_iter(&self, p: &Point) -> impl Iterator<Item = Point> {
-        let (gridh, gridw) = self.dim;
-        let &Point(i0, j0) = p;
-        let imin = i0.max(1) - 1;
-        let jmin = j0.max(1) - 1;
-        let imax = (i0 + 1).min(gridh - 1);
-        let jmax = (j0 + 1).min(gridw - 1);
-
-        (imin..=imax).flat_map(move |i| {
-            (jmin..=jmax).filter_map(move |j| {
-                if i0 == i && j0 == j {
-                    None
-                } else {
-                    Some(Point::new(i, j))
-                }
-            })
-        })
+        self.mines.neighbors(*p, Topology::Bounded)
     }
 
     // game is won if all non-mines have been revealed
     fn game_won(&self) -> bool {
         // zip(self.revealed.iter(), self.mines.iter())
         //     .all(|(&revealed, &mine)| {revealed || mine})
-        let n_mines: u32 = self.mines.iter().map(|&x| x as u32).sum();
         let n_squares = self.mines.len() as u32;
 
-        self.n_revealed == n_squares - n_mines
+        self.n_revealed == n_squares - self.n_mines()
 
         // let all_mines_flagged = Zip::from(&self.mines).and(&self.flagged)
         //     .all(|&m, &f| m == f);
@@ -210,19 +204,17 @@ impl MineField {
 
     fn move_mine(&mut self, mine: &Point) -> Result<(), String> {
         // get reference to mine, throw error if not actually a mine
-        let old_mine_ref = self.mines.get_mut(mine.tuple()).unwrap();
-        if !*old_mine_ref {
+        if !self.mines[*mine] {
             return Err(format!("{} is not a mine", &mine));
         }
 
         // pick a random non-mine square
-        let mut rng = rand::thread_rng();
         loop {
             let square_ptr = self
                 .mines
                 .as_slice_mut()
                 .expect("'mines' array is non-contiguous??")
-                .choose_mut(&mut rng)
+                .choose_mut(&mut self.rng)
                 .expect("'mines' is empty??");
             if !*square_ptr {
                 // set random square as mine
@@ -232,11 +224,10 @@ impl MineField {
         }
 
         // unset old mine
-        let old_mine_ref = self.mines.get_mut(mine.tuple()).unwrap();
-        *old_mine_ref = false;
+        self.mines[*mine] = false;
 
         // recompute num neighbors grid
-        self.neighbors = Self::n_neighbors_grid(&self.mines);
+        self.neighbors = self.mines.neighbor_counts(Topology::Bounded);
 
         Ok(())
     }
@@ -255,14 +246,7 @@ impl MineField {
     }
 
     fn chord(&mut self, p: &Point) -> MoveResult {
-        let nn_mines: u32 = *self.neighbors.get(p.tuple()).unwrap();
-        let nn_flags = self
-            .neighbors_iter(p)
-            .map(|p| self.is_flag(&p).unwrap() as u32)
-            .sum();
-
-        // only chord if # of neighboring flags == # of neighboring mines
-        if nn_mines == nn_flags {
+        if self.is_satisfied(p) {
             self.reveal_neighbors(p)
         } else {
             MoveResult::Ok
@@ -271,7 +255,7 @@ impl MineField {
 
     // reveal all mines after game is over
     fn reveal_all_mines(&mut self) {
-        azip!((r in &mut self.revealed, &m in &self.mines)
+        azip!((r in &mut *self.revealed, &m in &*self.mines)
             if m { *r = true }
         );
     }
@@ -287,7 +271,7 @@ impl MineField {
         }
 
         // flip flagged state
-        if let Some(flagged) = self.flagged.get_mut(p.tuple()) {
+        if let Some(flagged) = self.flagged.get_mut(*p) {
             *flagged = !*flagged;
             MoveResult::Ok
         } else {
@@ -295,19 +279,40 @@ impl MineField {
         }
     }
 
+    pub fn n_mines(&self) -> u32 {
+        self.mines.iter().map(|&x| x as u32).sum()
+    }
+
     pub fn is_flag(&self, p: &Point) -> Option<bool> {
-        self.flagged.get(p.tuple()).copied()
+        self.flagged.get(*p).copied()
+    }
+
+    // whether revealed square [p] has as many flags around it as mines
+    // (revealing it again chords, revealing all its other neighbors)
+    pub fn is_satisfied(&self, p: &Point) -> bool {
+        let nn_flags: u32 = self
+            .neighbors_iter(p)
+            .map(|p| self.is_flag(&p).unwrap() as u32)
+            .sum();
+        self.is_revealed(p) == Some(true) && self.neighbors[*p] == nn_flags
+    }
+
+    // hidden, unflagged squares around [p]
+    pub fn hidden_neighbors(&self, p: &Point) -> Vec<Point> {
+        self.neighbors_iter(p)
+            .filter(|p| matches!(self.view_sq(p), Some(SquareView::Hidden)))
+            .collect()
     }
 
     pub fn view_sq(&self, p: &Point) -> Option<SquareView> {
-        let revealed = self.is_revealed(&p)?;
-        let ismine = self.peek_mine(&p)?;
-        let isflag = self.is_flag(&p)?;
+        let revealed = self.is_revealed(p)?;
+        let ismine = self.peek_mine(p)?;
+        let isflag = self.is_flag(p)?;
 
         Some(match (revealed, ismine, isflag) {
             (false, _, false) => SquareView::Hidden,
             (false, _, true) => SquareView::Flag,
-            (true, false, _) => SquareView::Revealed(*self.neighbors.get(p.tuple()).unwrap()),
+            (true, false, _) => SquareView::Revealed(self.neighbors[*p]),
             (true, true, _) => SquareView::Mine,
         })
     }
@@ -354,8 +359,7 @@ impl MineField {
             Some(SquareView::Revealed(_)) => return self.chord(p),
             Some(SquareView::Hidden) => {
                 // if hidden, mark square as revealed
-                let rev = self.revealed.get_mut(p.tuple()).unwrap();
-                *rev = true;
+                self.revealed[*p] = true;
                 self.n_revealed += 1;
             }
             _ => (),
@@ -366,6 +370,7 @@ impl MineField {
         if *self.peek_mine(p).unwrap() {
             // if this is 1st move, move the mine
             if self.n_revealed == 1 {
+                debug!("moving the mine under the first move at ({}, {})", p.0, p.1);
                 self.move_mine(p).unwrap();
             } else {
                 self.reveal_all_mines();
@@ -374,7 +379,7 @@ impl MineField {
         }
 
         // if 0 neighbors, reveal all neighbors (recursively?)
-        let nn = *self.neighbors.get(p.tuple()).unwrap();
+        let nn = self.neighbors[*p];
         if nn == 0 {
             self.reveal_neighbors(p);
         }
@@ -389,14 +394,114 @@ impl MineField {
     }
 }
 
+impl Core for MineField {
+    type Action = Move;
+    type Outcome = MoveResult;
+    type Cell = SquareView;
+
+    fn size(&self) -> (usize, usize) {
+        self.mines.dim()
+    }
+
+    fn cell(&self, p: Point) -> SquareView {
+        self.view_sq(&p).expect("cell outside the minefield")
+    }
+
+    fn act(&mut self, action: Move) -> MoveResult {
+        match action {
+            Move::Reveal(p) => self.reveal(&p),
+            Move::ToggleFlag(p) => self.toggle_flag(&p),
+        }
+    }
+
+    // winning and losing both uncover every mine
+    fn is_over(&self) -> bool {
+        izip!(self.revealed.iter(), self.mines.iter()).any(|(&revealed, &mine)| revealed && mine)
+    }
+}
+
+/// A board a row to a line, one character a square (spaces between squares
+/// and blank lines are skipped): `.` hidden, `*` a hidden mine, `@` a flagged
+/// mine, `!` a flag on a square without a mine, `_` or `0` to `8` revealed
+/// (showing how many mines are around it, which has to be right) and `X` a
+/// revealed mine. Mines moved off a first move are placed as if from seed 0
+impl FromStr for MineField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<Vec<char>> = s
+            .lines()
+            .map(|line| line.chars().filter(|c| !c.is_whitespace()).collect())
+            .filter(|row: &Vec<char>| !row.is_empty())
+            .collect();
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if height == 0 {
+            return Err("board has no squares".into());
+        }
+        if let Some(i) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "row {i} has {} squares (the first has {width})",
+                rows[i].len()
+            ));
+        }
+
+        let mut mines = Grid2D::new(height, width);
+        let mut revealed = Grid2D::new(height, width);
+        let mut flagged = Grid2D::new(height, width);
+        let mut shown = Vec::new(); // revealed squares, and the counts they show
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                match c {
+                    '.' => {}
+                    '*' => mines[(i, j)] = true,
+                    '@' => {
+                        mines[(i, j)] = true;
+                        flagged[(i, j)] = true;
+                    }
+                    '!' => flagged[(i, j)] = true,
+                    'X' => {
+                        mines[(i, j)] = true;
+                        revealed[(i, j)] = true;
+                    }
+                    '_' | '0'..='8' => {
+                        revealed[(i, j)] = true;
+                        shown.push(((i, j), c.to_digit(10).unwrap_or(0)));
+                    }
+                    _ => return Err(format!("unexpected '{c}' on the board")),
+                }
+            }
+        }
+        if mines.iter().all(|&mine| mine) {
+            return Err("board has nothing but mines".into());
+        }
+        let neighbors = mines.neighbor_counts(Topology::Bounded);
+        if let Some(&((i, j), n)) = shown.iter().find(|&&(p, n)| neighbors[p] != n) {
+            return Err(format!(
+                "square ({i}, {j}) shows {n}, but the mines around it come to {}",
+                neighbors[(i, j)]
+            ));
+        }
+
+        Ok(Self {
+            mines,
+            neighbors,
+            revealed,
+            flagged,
+            n_revealed: shown.len() as u32,
+            rng: StdRng::seed_from_u64(0),
+        })
+    }
+}
+
 // Pretty-print
 impl fmt::Display for MineField {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // zip iterator of mines(bool), revealed(bool), and neighbors(u32)
-        let sqdata_zip = Zip::from(&self.mines)
-            .and(&self.revealed)
-            .and(&self.neighbors)
-            .and(&self.flagged);
+        let sqdata_zip = Zip::from(&*self.mines)
+            .and(&*self.revealed)
+            .and(&*self.neighbors)
+            .and(&*self.flagged);
         // print grid lines
         let print_lines = sqdata_zip.map_collect(|&mine, &rev, &nn, &flag| {
             match (mine, rev, nn, flag) {
@@ -436,7 +541,7 @@ impl fmt::Display for MineField {
         for h_ax_lbl in (0..self.mines.ncols()).map(ax_labeller) {
             write_res = write_res.and(write!(f, "{:2}", h_ax_lbl));
         }
-        write_res = write_res.and(write!(f, "\n"));
+        write_res = write_res.and(writeln!(f));
 
         write_res
     }
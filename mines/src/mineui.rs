@@ -1,29 +1,39 @@
-use std::{io, time::Duration};
+use std::io;
 
-use crossterm::event::{poll, read, Event::Key, KeyCode, KeyEvent};
+use crossterm::event::{
+    read, Event, Event::Key, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind,
+};
 
-use crate::Point;
+use common::{locale, Bindings, KeyBinding, Point};
 
-pub const HELP_TEXT: &str = "
-Use the arrow keys to move.\r
-Press <space> to select the highlighted square.\r
-Press <tab> to switch between reveal and flag mode.\r
-Press <h> to show this help screen.\r
-Press <q> to quit.\r
-";
+/// Help screen, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("mines.help")
+}
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MineUIAction {
     Wait,
     Move(MoveDirection),
     Mode(UIMode),
     ToggleMode,
     Select,
+    /// select the square at a terminal position (or with the right button,
+    /// flag it, whichever the mode)
+    Click {
+        row: u16,
+        col: u16,
+        flag: bool,
+    },
+    Export,
+    Copy,
+    Hint,
+    Retry,
     Help,
     Quit,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum MoveDirection {
     Up,
     Down,
@@ -31,40 +41,70 @@ pub enum MoveDirection {
     Right,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum UIMode {
     Flag,
     Reveal,
 }
 
+// default controls
+const BINDINGS: [(KeyBinding, MineUIAction); 15] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        MineUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        MineUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        MineUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        MineUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), MineUIAction::Select),
+    (KeyBinding::char('f'), MineUIAction::Mode(UIMode::Flag)),
+    (KeyBinding::char('r'), MineUIAction::Mode(UIMode::Reveal)),
+    (KeyBinding::key(KeyCode::Tab), MineUIAction::ToggleMode),
+    (KeyBinding::char('e'), MineUIAction::Export),
+    (KeyBinding::char('c'), MineUIAction::Copy),
+    (KeyBinding::char('g'), MineUIAction::Hint),
+    (KeyBinding::char('t'), MineUIAction::Retry),
+    (KeyBinding::char('h'), MineUIAction::Help),
+    (KeyBinding::char('q'), MineUIAction::Quit),
+    (KeyBinding::ctrl('c'), MineUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, MineUIAction); 14] = [
+    ("up", MineUIAction::Move(MoveDirection::Up)),
+    ("down", MineUIAction::Move(MoveDirection::Down)),
+    ("left", MineUIAction::Move(MoveDirection::Left)),
+    ("right", MineUIAction::Move(MoveDirection::Right)),
+    ("select", MineUIAction::Select),
+    ("flag_mode", MineUIAction::Mode(UIMode::Flag)),
+    ("reveal_mode", MineUIAction::Mode(UIMode::Reveal)),
+    ("toggle_mode", MineUIAction::ToggleMode),
+    ("export", MineUIAction::Export),
+    ("copy", MineUIAction::Copy),
+    ("hint", MineUIAction::Hint),
+    ("retry", MineUIAction::Retry),
+    ("help", MineUIAction::Help),
+    ("quit", MineUIAction::Quit),
+];
+
 pub struct MineUI {
     gridh: usize,
     gridw: usize,
     cursor: Point,
     pub mode: UIMode,
+    pub bindings: Bindings<MineUIAction>,
 }
 
 impl MineUI {
-    /////////////
-    // Statics //
-    /////////////
-
-    fn match_key_to_action(key_event: KeyEvent) -> MineUIAction {
-        match key_event.code {
-            KeyCode::Up => MineUIAction::Move(MoveDirection::Up),
-            KeyCode::Down => MineUIAction::Move(MoveDirection::Down),
-            KeyCode::Left => MineUIAction::Move(MoveDirection::Left),
-            KeyCode::Right => MineUIAction::Move(MoveDirection::Right),
-            KeyCode::Char(' ') => MineUIAction::Select,
-            KeyCode::Char('f') => MineUIAction::Mode(UIMode::Flag),
-            KeyCode::Char('r') => MineUIAction::Mode(UIMode::Reveal),
-            KeyCode::Tab => MineUIAction::ToggleMode,
-            KeyCode::Char('h') => MineUIAction::Help,
-            KeyCode::Char('q') => MineUIAction::Quit,
-            _ => MineUIAction::Wait,
-        }
-    }
-
     ///////////////////
     // Contstructors //
     ///////////////////
@@ -75,6 +115,7 @@ impl MineUI {
             gridw: width,
             cursor: Point::origin(),
             mode: UIMode::Reveal,
+            bindings: Bindings::new(&BINDINGS),
         }
     }
 
@@ -82,6 +123,35 @@ impl MineUI {
     // Publics //
     /////////////
 
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> MineUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(MineUIAction::Wait)
+    }
+
+    // left button selects, right button flags
+    fn match_mouse_to_action(mouse_event: MouseEvent) -> MineUIAction {
+        let flag = match mouse_event.kind {
+            MouseEventKind::Down(MouseButton::Left) => false,
+            MouseEventKind::Down(MouseButton::Right) => true,
+            _ => return MineUIAction::Wait,
+        };
+        MineUIAction::Click {
+            row: mouse_event.row,
+            col: mouse_event.column,
+            flag,
+        }
+    }
+
+    // only keypresses and clicks do anything
+    pub fn match_event_to_action(&self, event: Event) -> MineUIAction {
+        match event {
+            Key(key_event) => self.match_key_to_action(key_event),
+            Event::Mouse(mouse_event) => Self::match_mouse_to_action(mouse_event),
+            _ => MineUIAction::Wait,
+        }
+    }
+
     pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
         let cur_i = self.cursor.0 as u32;
         let cur_j = self.cursor.1 as u32;
@@ -139,33 +209,11 @@ impl MineUI {
         loop {
             let read_res = read();
             if let Key(key_event) = read_res? {
-                action = Self::match_key_to_action(key_event);
+                action = self.match_key_to_action(key_event);
                 break;
             }
         }
 
         Ok(action)
     }
-
-    // poll with a timeout
-    #[allow(dead_code)]
-    pub fn wait_for_action_poll(&self, timeout: u64) -> io::Result<MineUIAction> {
-        let action: MineUIAction;
-        let read_res = read();
-        if poll(Duration::from_secs(timeout))? {
-            // event happened
-            if let Key(key_event) = read_res? {
-                // keystroke event
-                action = Self::match_key_to_action(key_event);
-            } else {
-                // non-keystroke event
-                action = MineUIAction::Wait;
-            }
-        } else {
-            // no event happened
-            action = MineUIAction::Wait;
-        }
-
-        Ok(action)
-    }
 }
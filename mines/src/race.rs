@@ -0,0 +1,199 @@
+//! Head-to-head races: two players each get a copy of the same board (laid
+//! out from the same seed, drawn at random for every round), and the first to clear it wins the round. If
+//! both hit a mine instead, the one who got further (revealed more squares)
+//! wins it, or it's a draw if they got as far. Rounds go on, on a new board
+//! each, until one player has won most of the best of N (or the other has
+//! forfeited the match by leaving).
+
+use common::{Core, Point};
+
+use crate::mines::{MineField, Move, MoveResult, SquareView};
+
+/// Players a race is between
+pub const PLAYERS: usize = 2;
+
+/// A race, from the first player setting it up to one of them winning the match
+pub struct Race {
+    board: (usize, usize, usize), // (height, width, mines) of every round's board
+    seeds: Vec<u64>,              // of every round's board so far, this one's too
+    best_of: u32,
+    joined: usize,
+    fields: Vec<MineField>, // each player's copy of this round's board, once both are in
+    lost: [Option<usize>; PLAYERS], // squares revealed by each player who hit a mine this round
+    wins: [u32; PLAYERS],
+    rounds: Vec<Option<usize>>, // who won each round that's been decided (None for a draw)
+    forfeited: Option<usize>,   // player who gave the match up, if one has
+}
+
+impl Race {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// A race on (height, width, mines) boards over the best of [best_of]
+    /// rounds, with the first player in
+    pub fn new(board: (usize, usize, usize), best_of: u32) -> Self {
+        Self {
+            board,
+            seeds: Vec::new(),
+            best_of,
+            joined: 1,
+            fields: Vec::new(),
+            lost: [None; PLAYERS],
+            wins: [0; PLAYERS],
+            rounds: Vec::new(),
+            forfeited: None,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Let the next player in, starting the first round once they all are.
+    /// Returns their number, or None if the race is full
+    pub fn join(&mut self) -> Option<usize> {
+        if self.joined == PLAYERS {
+            return None;
+        }
+        self.joined += 1;
+        if self.joined == PLAYERS {
+            self.start_round();
+        }
+        Some(self.joined - 1)
+    }
+
+    /// Make [player]'s move on their board. Once it decides the round, the
+    /// next one starts straight away (unless that was the match)
+    pub fn play(&mut self, player: usize, action: Move) -> Result<MoveResult, String> {
+        if self.winner().is_some() {
+            return Err("the race is over".into());
+        }
+        let Some(field) = self.fields.get_mut(player) else {
+            return Err("the race hasn't started: waiting for another player".into());
+        };
+        if self.lost[player].is_some() {
+            return Err("you're out of this round: waiting for the other player".into());
+        }
+        let result = field.act(action);
+        match result {
+            MoveResult::Win => self.end_round(Some(player)),
+            MoveResult::Lose => {
+                self.lost[player] = Some(progress(field));
+                self.settle_losses();
+            }
+            _ => {}
+        }
+        Ok(result)
+    }
+
+    /// Give the match up for [player] (who's left), handing this round and
+    /// the match to the other. Returns false if there was no match left to
+    /// give up: it hadn't started, or was already over
+    pub fn forfeit(&mut self, player: usize) -> bool {
+        if self.fields.is_empty() || self.winner().is_some() {
+            return false;
+        }
+        self.forfeited = Some(player);
+        let other = (0..PLAYERS).find(|&other| other != player);
+        self.end_round(other);
+        true
+    }
+
+    /// [player]'s copy of this round's board (None until the race starts)
+    pub fn field(&self, player: usize) -> Option<&MineField> {
+        self.fields.get(player)
+    }
+
+    /// Round being played (or the last one, once the match is over), from 1
+    pub fn round(&self) -> usize {
+        self.rounds.len() + usize::from(self.winner().is_none())
+    }
+
+    /// (height, width, mines) of every round's board
+    pub fn board(&self) -> (usize, usize, usize) {
+        self.board
+    }
+
+    pub fn best_of(&self) -> u32 {
+        self.best_of
+    }
+
+    /// Seeds of the boards of the rounds decided so far (this round's is
+    /// kept back, since it would give its mines away)
+    pub fn seeds(&self) -> &[u64] {
+        &self.seeds[..self.rounds.len()]
+    }
+
+    /// Rounds each player has won so far
+    pub fn wins(&self) -> [u32; PLAYERS] {
+        self.wins
+    }
+
+    /// Who won each round decided so far (None for a draw)
+    pub fn rounds(&self) -> &[Option<usize>] {
+        &self.rounds
+    }
+
+    /// The player who's won most of the rounds, once one has (or the one
+    /// left, once the other has forfeited)
+    pub fn winner(&self) -> Option<usize> {
+        match self.forfeited {
+            Some(quitter) => (0..PLAYERS).find(|&player| player != quitter),
+            None => (0..PLAYERS).find(|&player| self.wins[player] > self.best_of / 2),
+        }
+    }
+
+    /// The player who forfeited the match, if one did
+    pub fn forfeited(&self) -> Option<usize> {
+        self.forfeited
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // deal everyone a copy of the round's board, on a seed of its own (so
+    // no round's gives the next one's mines away)
+    fn start_round(&mut self) {
+        let (height, width, n_mines) = self.board;
+        let seed = rand::random();
+        self.seeds.push(seed);
+        let field = MineField::with_n_mines(height, width, n_mines, seed);
+        self.fields = vec![field; PLAYERS];
+        self.lost = [None; PLAYERS];
+    }
+
+    // once everyone's hit a mine, whoever got furthest wins the round
+    fn settle_losses(&mut self) {
+        let Some(progress) = self.lost.iter().copied().collect::<Option<Vec<_>>>() else {
+            return;
+        };
+        let best = progress.iter().max().copied().unwrap_or(0);
+        let mut furthest = (0..PLAYERS).filter(|&player| progress[player] == best);
+        match (furthest.next(), furthest.next()) {
+            (Some(player), None) => self.end_round(Some(player)),
+            _ => self.end_round(None),
+        }
+    }
+
+    fn end_round(&mut self, winner: Option<usize>) {
+        self.rounds.push(winner);
+        if let Some(player) = winner {
+            self.wins[player] += 1;
+        }
+        // (a draw doesn't count towards the best of N, so could go on for a while)
+        if self.winner().is_none() {
+            self.start_round();
+        }
+    }
+}
+
+// squares revealed on [field] (not counting any mine)
+fn progress(field: &MineField) -> usize {
+    let (height, width) = field.size();
+    (0..height)
+        .flat_map(|i| (0..width).map(move |j| Point(i, j)))
+        .filter(|&p| matches!(field.cell(p), SquareView::Revealed(_)))
+        .count()
+}
@@ -0,0 +1,592 @@
+//! The Minesweeper engine served over TCP, so bots written in any language
+//! can play it (and play each other, on the same seeds). Requests and
+//! responses are JSON-RPC 2.0, one JSON object to a line:
+//!
+//! ```text
+//! -> {"jsonrpc": "2.0", "id": 1, "method": "new_game", "params": {"level": "expert", "seed": 7}}
+//! <- {"jsonrpc": "2.0", "id": 1, "result": {"game": 0, "height": 16, "width": 30, "mines": 99, "seed": 7}}
+//! -> {"jsonrpc": "2.0", "id": 2, "method": "reveal", "params": {"game": 0, "row": 8, "col": 15}}
+//! <- {"jsonrpc": "2.0", "id": 2, "result": {"result": "ok", "over": false, "board": ["....", ...]}}
+//! ```
+//!
+//! Each connection has its own games, numbered from 0.
+//!
+//! Two bots can also race (see [crate::race]): one sets a race up with
+//! `new_race` (taking new_game's params but the seed, plus `best_of`, 3 by
+//! default), the other joins it with `join_race`, and each plays its own
+//! copy of every round's board with `race_reveal` and `race_flag`, answered
+//! with the board and how the match stands (which `race` also answers with,
+//! at any time). Each round's board is on a seed the server picks, only
+//! given out once the round is decided. A move can say which `round` it's
+//! for, so one that crosses a round ending is turned down rather than made
+//! on the next board. When a round ends, the other racer is sent a
+//! `race_round` notification with how the match stands, and if a racer
+//! disconnects mid-match, they forfeit it, and the other is sent a
+//! `race_forfeited` one:
+//!
+//! ```text
+//! -> {"jsonrpc": "2.0", "id": 1, "method": "join_race", "params": {"race": 0}}
+//! <- {"jsonrpc": "2.0", "id": 1, "result": {"race": 0, "player": 1, "height": 16, ...}}
+//! -> {"jsonrpc": "2.0", "id": 2, "method": "race_reveal", "params": {"race": 0, "row": 8, "col": 15}}
+//! <- {"jsonrpc": "2.0", "id": 2, "result": {"result": "ok", "round": 1, "wins": [0, 0], "winner": null, ...}}
+//! ```
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+
+use log::{debug, info, warn};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use common::{Core, Point};
+
+use crate::config::Difficulty;
+use crate::mines::{MineField, Move, MoveResult, SquareView};
+use crate::race::{Race, PLAYERS};
+
+// the longest side of a board a bot can ask for
+const MAX_SIDE: usize = 100;
+
+// rounds a race is the best of, unless new_race says
+const DEFAULT_BEST_OF: u32 = 3;
+
+// a connection's end for sending on, shared with the races it's in (so
+// they can notify it of what the other player does)
+type Writer = Arc<Mutex<TcpStream>>;
+
+// error codes from the JSON-RPC spec
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+// a move the game won't take (no such game, a square off the board, ...)
+const GAME_ERROR: i64 = -32000;
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    // left out for a notification, which gets no response
+    #[serde(default)]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+struct RpcError(i64, String);
+
+// params of new_game: a level, or the board's size and mines
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewGame {
+    #[serde(default, deserialize_with = "common::config::from_str")]
+    level: Option<Difficulty>,
+    height: Option<usize>,
+    width: Option<usize>,
+    mines: Option<usize>,
+    seed: Option<u64>,
+}
+
+// params of new_race: new_game's but the seed (every round's is picked
+// here, so neither player knows it), and the rounds to race
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewRace {
+    #[serde(default, deserialize_with = "common::config::from_str")]
+    level: Option<Difficulty>,
+    height: Option<usize>,
+    width: Option<usize>,
+    mines: Option<usize>,
+    best_of: Option<u32>,
+}
+
+// params of reveal and flag
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Square {
+    game: u64,
+    row: usize,
+    col: usize,
+}
+
+// params of race_reveal and race_flag (the round the move is for, if it
+// says, turning it down once that round is over)
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RaceSquare {
+    race: u64,
+    row: usize,
+    col: usize,
+    round: Option<usize>,
+}
+
+// params of join_race and race
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RaceId {
+    race: u64,
+}
+
+// params of board and close_game
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Game {
+    game: u64,
+}
+
+/// Serve the engine on [addr] (like "127.0.0.1:7878"), a thread for each
+/// connection, until the process is killed
+pub fn serve(addr: &str) -> Result<(), String> {
+    let listener =
+        TcpListener::bind(addr).map_err(|e| format!("failed to listen on {addr}: {e}"))?;
+    println!("serving Minesweeper on {addr} (JSON-RPC, a request to a line)");
+    let races = Arc::new(Mutex::new(Races::default()));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let races = races.clone();
+                thread::spawn(move || session(stream, races));
+            }
+            Err(e) => warn!("failed to accept a connection: {e}"),
+        }
+    }
+    Ok(())
+}
+
+// answer a connection's requests in turn, until it closes
+fn session(stream: TcpStream, races: Arc<Mutex<Races>>) {
+    let peer = stream
+        .peer_addr()
+        .map_or_else(|_| "?".to_string(), |addr| addr.to_string());
+    info!("{peer} connected");
+    let Ok(writer) = stream.try_clone() else {
+        return;
+    };
+    let mut games = Games::new(races, Arc::new(Mutex::new(writer)));
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = games.handle(&line) {
+            if send(&games.writer, &response).is_err() {
+                break;
+            }
+        }
+    }
+    games.leave_races();
+    info!("{peer} disconnected");
+}
+
+// the races going on, shared between connections, along with each one's
+// players that are still connected
+#[derive(Default)]
+struct Races {
+    races: HashMap<u64, (Race, [Option<Writer>; PLAYERS])>,
+    next_id: u64,
+}
+
+// the games being played over one connection, and the races it's in (as
+// which player)
+struct Games {
+    games: HashMap<u64, MineField>,
+    next_id: u64,
+    races: Arc<Mutex<Races>>,
+    racing: HashMap<u64, usize>,
+    writer: Writer,
+}
+
+impl Games {
+    fn new(races: Arc<Mutex<Races>>, writer: Writer) -> Self {
+        Self {
+            games: HashMap::new(),
+            next_id: 0,
+            races,
+            racing: HashMap::new(),
+            writer,
+        }
+    }
+
+    // the response to a line (None for a notification)
+    fn handle(&mut self, line: &str) -> Option<Value> {
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => return Some(error(Value::Null, PARSE_ERROR, e.to_string())),
+        };
+        let request: Request = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(e) => return Some(error(Value::Null, INVALID_REQUEST, e.to_string())),
+        };
+        if request.jsonrpc != "2.0" {
+            let id = request.id.unwrap_or(Value::Null);
+            return Some(error(id, INVALID_REQUEST, "jsonrpc must be \"2.0\"".into()));
+        }
+        debug!("{} {}", request.method, request.params);
+        let result = self.call(&request.method, request.params);
+        let id = request.id?;
+        Some(match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(RpcError(code, message)) => error(id, code, message),
+        })
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "new_game" => self.new_game(params_of(params)?),
+            "reveal" => self.play(params_of(params)?, MineField::reveal),
+            "flag" => self.play(params_of(params)?, MineField::toggle_flag),
+            "board" => {
+                let Game { game } = params_of(params)?;
+                self.field(game).map(view)
+            }
+            "close_game" => {
+                let Game { game } = params_of(params)?;
+                self.games.remove(&game).ok_or_else(|| no_game(game))?;
+                Ok(Value::Null)
+            }
+            "new_race" => self.new_race(params_of(params)?),
+            "join_race" => self.join_race(params_of(params)?),
+            "race_reveal" => self.race_play(params_of(params)?, Move::Reveal),
+            "race_flag" => self.race_play(params_of(params)?, Move::ToggleFlag),
+            "race" => {
+                let RaceId { race } = params_of(params)?;
+                let player = self.player(race)?;
+                let races = lock(&self.races);
+                let (race_state, _) = races.races.get(&race).ok_or_else(|| no_race(race))?;
+                Ok(standing(race, race_state, player))
+            }
+            _ => Err(RpcError(METHOD_NOT_FOUND, format!("no method '{method}'"))),
+        }
+    }
+
+    fn new_game(&mut self, params: NewGame) -> Result<Value, RpcError> {
+        let (height, width, n_mines) = board_of(&params)?;
+        let seed = params.seed.unwrap_or_else(rand::random);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.games
+            .insert(id, MineField::with_n_mines(height, width, n_mines, seed));
+        info!("game {id}: {width}x{height} with {n_mines} mines, seed {seed}");
+        Ok(json!({
+            "game": id,
+            "height": height,
+            "width": width,
+            "mines": n_mines,
+            "seed": seed,
+        }))
+    }
+
+    // reveal or flag a square, answering with how it went and the board
+    fn play(
+        &mut self,
+        Square { game, row, col }: Square,
+        action: fn(&mut MineField, &Point) -> MoveResult,
+    ) -> Result<Value, RpcError> {
+        let field = self.games.get_mut(&game).ok_or_else(|| no_game(game))?;
+        let (height, width) = field.size();
+        if row >= height || col >= width {
+            return Err(RpcError(
+                GAME_ERROR,
+                format!("({row}, {col}) is off the {width}x{height} board"),
+            ));
+        }
+        if field.is_over() {
+            return Err(RpcError(GAME_ERROR, format!("game {game} is over")));
+        }
+        let result = match action(field, &Point(row, col)) {
+            MoveResult::Ok => "ok",
+            MoveResult::Win => "win",
+            MoveResult::Lose => "lose",
+            MoveResult::Err(e) => return Err(RpcError(GAME_ERROR, e)),
+        };
+        let mut response = view(field);
+        response["result"] = json!(result);
+        Ok(response)
+    }
+
+    fn field(&self, game: u64) -> Result<&MineField, RpcError> {
+        self.games.get(&game).ok_or_else(|| no_game(game))
+    }
+
+    // set up a race, with this connection as its first player
+    fn new_race(&mut self, params: NewRace) -> Result<Value, RpcError> {
+        let board = board_of(&NewGame {
+            level: params.level,
+            height: params.height,
+            width: params.width,
+            mines: params.mines,
+            seed: None,
+        })?;
+        let best_of = params.best_of.unwrap_or(DEFAULT_BEST_OF);
+        if best_of == 0 {
+            return Err(invalid("a race is the best of at least 1 round"));
+        }
+        let mut races = lock(&self.races);
+        let id = races.next_id;
+        races.next_id += 1;
+        let mut connected: [Option<Writer>; PLAYERS] = Default::default();
+        connected[0] = Some(self.writer.clone());
+        races
+            .races
+            .insert(id, (Race::new(board, best_of), connected));
+        drop(races);
+        self.racing.insert(id, 0);
+        let (height, width, n_mines) = board;
+        info!("race {id}: best of {best_of} on {width}x{height} with {n_mines} mines");
+        Ok(json!({
+            "race": id,
+            "player": 0,
+            "height": height,
+            "width": width,
+            "mines": n_mines,
+            "best_of": best_of,
+        }))
+    }
+
+    // join a race someone else set up, starting it
+    fn join_race(&mut self, RaceId { race }: RaceId) -> Result<Value, RpcError> {
+        if self.racing.contains_key(&race) {
+            return Err(RpcError(GAME_ERROR, format!("already in race {race}")));
+        }
+        let mut races = lock(&self.races);
+        let (race_state, connected) = races.races.get_mut(&race).ok_or_else(|| no_race(race))?;
+        let player = race_state
+            .join()
+            .ok_or_else(|| RpcError(GAME_ERROR, format!("race {race} is full")))?;
+        connected[player] = Some(self.writer.clone());
+        let (height, width, n_mines) = race_state.board();
+        let best_of = race_state.best_of();
+        drop(races);
+        self.racing.insert(race, player);
+        info!("race {race}: player {player} joined");
+        Ok(json!({
+            "race": race,
+            "player": player,
+            "height": height,
+            "width": width,
+            "mines": n_mines,
+            "best_of": best_of,
+        }))
+    }
+
+    // reveal or flag a square on this connection's board in a race,
+    // answering with how it went and how the match stands (and telling the
+    // other player, if that ended the round)
+    fn race_play(
+        &mut self,
+        RaceSquare {
+            race,
+            row,
+            col,
+            round,
+        }: RaceSquare,
+        action: fn(Point) -> Move,
+    ) -> Result<Value, RpcError> {
+        let player = self.player(race)?;
+        let mut races = lock(&self.races);
+        let (race_state, connected) = races.races.get_mut(&race).ok_or_else(|| no_race(race))?;
+        let (height, width, _) = race_state.board();
+        if row >= height || col >= width {
+            return Err(RpcError(
+                GAME_ERROR,
+                format!("({row}, {col}) is off the {width}x{height} board"),
+            ));
+        }
+        let rounds = race_state.rounds().len();
+        if let Some(round) = round.filter(|&round| round != rounds + 1) {
+            return Err(RpcError(
+                GAME_ERROR,
+                format!(
+                    "round {round} isn't being played: it's round {}",
+                    rounds + 1
+                ),
+            ));
+        }
+        let result = match race_state.play(player, action(Point(row, col))) {
+            Ok(MoveResult::Ok) => "ok",
+            Ok(MoveResult::Win) => "win",
+            Ok(MoveResult::Lose) => "lose",
+            Ok(MoveResult::Err(e)) | Err(e) => return Err(RpcError(GAME_ERROR, e)),
+        };
+        let mut response = standing(race, race_state, player);
+        response["result"] = json!(result);
+        let notifications = if race_state.rounds().len() > rounds {
+            info!("race {race}: round {} over", rounds + 1);
+            notifications(race, race_state, connected, player, "race_round")
+        } else {
+            Vec::new()
+        };
+        drop(races);
+        notify(notifications);
+        Ok(response)
+    }
+
+    // which player this connection is in [race]
+    fn player(&self, race: u64) -> Result<usize, RpcError> {
+        self.racing
+            .get(&race)
+            .copied()
+            .ok_or_else(|| RpcError(GAME_ERROR, format!("not in race {race}")))
+    }
+
+    // leave the races this connection is in, forfeiting any still being
+    // played (and telling the other player so), and forgetting those that
+    // no one is connected to any more
+    fn leave_races(&mut self) {
+        let mut races = lock(&self.races);
+        let mut notifications = Vec::new();
+        for (race, player) in self.racing.drain() {
+            let Some((race_state, connected)) = races.races.get_mut(&race) else {
+                continue;
+            };
+            connected[player] = None;
+            if race_state.forfeit(player) {
+                info!("race {race}: player {player} left, forfeiting");
+                notifications.extend(self::notifications(
+                    race,
+                    race_state,
+                    connected,
+                    player,
+                    "race_forfeited",
+                ));
+            }
+            if connected.iter().all(Option::is_none) {
+                races.races.remove(&race);
+                debug!("race {race} forgotten");
+            }
+        }
+        drop(races);
+        notify(notifications);
+    }
+}
+
+// a [method] notification for each player in [race] but [player], saying
+// how the match stands for them
+fn notifications(
+    race: u64,
+    race_state: &Race,
+    connected: &[Option<Writer>; PLAYERS],
+    player: usize,
+    method: &str,
+) -> Vec<(Writer, Value)> {
+    connected
+        .iter()
+        .enumerate()
+        .filter(|&(other, _)| other != player)
+        .filter_map(|(other, writer)| {
+            let params = standing(race, race_state, other);
+            let notification = json!({"jsonrpc": "2.0", "method": method, "params": params});
+            Some((writer.clone()?, notification))
+        })
+        .collect()
+}
+
+// send each notification (once the races are unlocked, in case a player is
+// slow to read)
+fn notify(notifications: Vec<(Writer, Value)>) {
+    for (writer, notification) in notifications {
+        // (the other player may have just gone)
+        let _ = send(&writer, &notification);
+    }
+}
+
+fn lock(races: &Mutex<Races>) -> MutexGuard<'_, Races> {
+    // (a race left half-updated by a panic is still a race)
+    races.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// (height, width, mines) of the board new_game's params ask for
+fn board_of(params: &NewGame) -> Result<(usize, usize, usize), RpcError> {
+    let (height, width, n_mines) = match (params.level, params.height, params.width, params.mines) {
+        (Some(level), None, None, None) => level.board(),
+        (None, Some(h), Some(w), Some(m)) => (h, w, m),
+        (None, None, None, None) => Difficulty::Beginner.board(),
+        _ => return Err(invalid("give a level, or height, width and mines")),
+    };
+    if !(1..=MAX_SIDE).contains(&height) || !(1..=MAX_SIDE).contains(&width) {
+        return Err(invalid(&format!(
+            "boards are 1 to {MAX_SIDE} squares high and wide"
+        )));
+    }
+    if n_mines >= height * width {
+        return Err(invalid(&format!(
+            "{n_mines} mines won't fit on a {width}x{height} board"
+        )));
+    }
+    Ok((height, width, n_mines))
+}
+
+// how [race] stands for [player]: the round, the rounds won by each player
+// (and who won each one, on which seed), the match's winner once there is
+// one (and whoever forfeited it), and the player's board (once the race has
+// started)
+fn standing(race: u64, race_state: &Race, player: usize) -> Value {
+    let mut response = match race_state.field(player) {
+        Some(field) => view(field),
+        None => json!({"over": false, "board": null}),
+    };
+    response["race"] = json!(race);
+    response["started"] = json!(race_state.field(player).is_some());
+    response["round"] = json!(race_state.round());
+    response["seeds"] = json!(race_state.seeds());
+    response["best_of"] = json!(race_state.best_of());
+    response["wins"] = json!(race_state.wins());
+    response["rounds"] = json!(race_state.rounds());
+    response["winner"] = json!(race_state.winner());
+    response["forfeited"] = json!(race_state.forfeited());
+    response
+}
+
+// send [message] down [writer], on a line of its own
+fn send(writer: &Mutex<TcpStream>, message: &Value) -> io::Result<()> {
+    let mut stream = writer.lock().unwrap_or_else(|e| e.into_inner());
+    writeln!(stream, "{message}")
+}
+
+// the board as the player sees it, a string to a row in the characters
+// MineField's FromStr reads: '.' hidden, '@' flagged, 'X' a mine (once the
+// game's over), and '_' or '1' to '8' revealed
+fn view(field: &MineField) -> Value {
+    let (height, width) = field.size();
+    let board: Vec<String> = (0..height)
+        .map(|i| {
+            (0..width)
+                .map(|j| match field.cell(Point(i, j)) {
+                    SquareView::Hidden => '.',
+                    SquareView::Flag => '@',
+                    SquareView::Mine => 'X',
+                    SquareView::Revealed(0) => '_',
+                    SquareView::Revealed(n) => char::from_digit(n, 10).unwrap_or('?'),
+                })
+                .collect()
+        })
+        .collect();
+    json!({"over": field.is_over(), "board": board})
+}
+
+// a method's params, which may be left out if none are needed
+fn params_of<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| invalid(&e.to_string()))
+}
+
+fn invalid(message: &str) -> RpcError {
+    RpcError(INVALID_PARAMS, message.to_string())
+}
+
+fn no_game(game: u64) -> RpcError {
+    RpcError(GAME_ERROR, format!("no game {game}"))
+}
+
+fn no_race(race: u64) -> RpcError {
+    RpcError(GAME_ERROR, format!("no race {race}"))
+}
+
+fn error(id: Value, code: i64, message: String) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+}
@@ -0,0 +1,472 @@
+//! Working out what the numbers on a minefield say about the squares still
+//! hidden: moves they make certain, and the chance each square has a mine.
+//! Flags are the player's guesses: they only count as mines where the
+//! numbers say so too, and as hidden squares like any other elsewhere (so
+//! a flag in the wrong place can't make a square look safe)
+
+use std::collections::{HashMap, HashSet};
+
+use common::{Core, Point};
+
+use crate::mines::{MineField, Move, SquareView};
+
+// most arrangements of mines tried, over the whole board, before giving up
+// on working out the chances (boards with huge open frontiers can have
+// astronomically many)
+const MAX_STEPS: usize = 1_000_000;
+
+// chances this close to 0 or 1 are certainties, give or take rounding
+const EPSILON: f64 = 1e-9;
+
+/// What the numbers on the board say about where to go next
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    /// a square that certainly has no mine
+    Safe(Point),
+    /// a square that certainly has a mine, to flag
+    Mine(Point),
+    /// nothing's certain: the square least likely to have a mine, and the
+    /// chance (0 to 1) that it has one
+    Guess(Point, f64),
+}
+
+/// How a move revealing a hidden square looked, from what the board showed
+/// just before it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Judgement {
+    /// the chance the square had a mine
+    pub chance: f64,
+    /// the lowest chance any hidden square had
+    pub best: f64,
+    /// whether some square was certainly safe to reveal instead
+    pub forced: bool,
+}
+
+impl Judgement {
+    /// Whether the move risked a mine
+    pub fn is_guess(&self) -> bool {
+        self.chance >= EPSILON
+    }
+}
+
+/// Play [moves] out on [field] (the board as it was before them), judging
+/// each one that reveals a hidden square, as far as [mine_chances] can. The
+/// rest (flags, chords, the first move and any too hard to work out) get None
+pub fn judge(mut field: MineField, moves: &[Move]) -> Vec<Option<Judgement>> {
+    let mut judgements = Vec::with_capacity(moves.len());
+    for &action in moves {
+        let judgement = match action {
+            Move::Reveal(p) if matches!(field.view_sq(&p), Some(SquareView::Hidden)) => {
+                mine_chances(&field).and_then(|chances| {
+                    let &(_, chance) = chances.iter().find(|&&(q, _)| q == p)?;
+                    let best = chances.iter().map(|&(_, c)| c).fold(chance, f64::min);
+                    let forced =
+                        best < EPSILON || matches!(certain_move(&field), Some(Move::Reveal(_)));
+                    Some(Judgement {
+                        chance,
+                        best,
+                        forced,
+                    })
+                })
+            }
+            _ => None,
+        };
+        judgements.push(judgement);
+        field.act(action);
+    }
+    judgements
+}
+
+/// A move the numbers on the board make certain, if any: flagging around a
+/// number with only as many squares left hidden as it says (which are all
+/// mines), or revealing around one whose mines are all certain that way
+pub fn certain_move(field: &MineField) -> Option<Move> {
+    let mines = certain_mines(field);
+    if let Some(&q) = mines.iter().find(|&&q| is_unflagged(field, q)) {
+        return Some(Move::ToggleFlag(q));
+    }
+    let mines: HashSet<Point> = mines.into_iter().collect();
+    squares(field.size()).find_map(|p| {
+        let SquareView::Revealed(n) = field.cell(p) else {
+            return None;
+        };
+        let hidden = around(field, p);
+        let n_certain = hidden.iter().filter(|q| mines.contains(q)).count();
+        let safe = hidden
+            .into_iter()
+            .find(|&q| !mines.contains(&q) && is_unflagged(field, q));
+        safe.filter(|_| n_certain == n as usize).map(Move::Reveal)
+    })
+}
+
+/// Where to go next: a move the numbers make certain, or else the hidden
+/// square least likely to have a mine, over every arrangement of mines the
+/// board allows (which can turn up squares that are safe all the same).
+/// None if nothing's been revealed yet (the first square revealed is never
+/// a mine), or there are too many arrangements to go through
+pub fn hint(field: &MineField) -> Option<Hint> {
+    match certain_move(field) {
+        Some(Move::Reveal(p)) => return Some(Hint::Safe(p)),
+        Some(Move::ToggleFlag(p)) => return Some(Hint::Mine(p)),
+        None => {}
+    }
+    let chances = mine_chances(field)?;
+    let &(p, chance) = chances.iter().min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+    if chance < EPSILON {
+        return Some(Hint::Safe(p));
+    }
+    if let Some(&(q, _)) = chances.iter().find(|&&(_, chance)| chance > 1.0 - EPSILON) {
+        return Some(Hint::Mine(q));
+    }
+    Some(Hint::Guess(p, chance))
+}
+
+/// Every hidden, unflagged square and the chance (0 to 1) it has a mine,
+/// taking every arrangement of the mines that fits the numbers as equally
+/// likely (flags only rule arrangements out where the numbers make them
+/// certain). None before the first move, or when there are too many
+/// arrangements to go through
+pub fn mine_chances(field: &MineField) -> Option<Vec<(Point, f64)>> {
+    let size = field.size();
+    let cells: Vec<(Point, SquareView)> = squares(size).map(|p| (p, field.cell(p))).collect();
+    if !cells
+        .iter()
+        .any(|(_, sq)| matches!(sq, SquareView::Revealed(_)))
+    {
+        return None;
+    }
+    // (flags the numbers bear out are mines, and out of the reckoning)
+    let known: HashSet<Point> = certain_mines(field)
+        .into_iter()
+        .filter(|&q| !is_unflagged(field, q))
+        .collect();
+    let hidden: Vec<Point> = cells
+        .iter()
+        .filter(|(_, sq)| matches!(sq, SquareView::Hidden | SquareView::Flag))
+        .map(|&(p, _)| p)
+        .filter(|p| !known.contains(p))
+        .collect();
+    let n_mines = (field.n_mines() as usize).checked_sub(known.len())?;
+
+    // the hidden squares next to numbers (the frontier), and what each
+    // number says about them
+    let mut frontier: Vec<Point> = Vec::new();
+    let mut index: HashMap<Point, usize> = HashMap::new();
+    let mut constraints = Vec::new();
+    for &(p, sq) in &cells {
+        let SquareView::Revealed(n) = sq else {
+            continue;
+        };
+        let (n_known, around): (Vec<Point>, Vec<Point>) = around(field, p)
+            .into_iter()
+            .partition(|q| known.contains(q));
+        if around.is_empty() {
+            continue;
+        }
+        let squares = around
+            .into_iter()
+            .map(|q| {
+                *index.entry(q).or_insert_with(|| {
+                    frontier.push(q);
+                    frontier.len() - 1
+                })
+            })
+            .collect();
+        let mines = (n as usize).checked_sub(n_known.len())?;
+        constraints.push(Constraint { squares, mines });
+    }
+
+    // arrangements of each separate stretch of frontier, tallied by how
+    // many mines they use
+    let mut steps = 0;
+    let components: Vec<Component> = components(frontier.len(), &constraints)
+        .into_iter()
+        .map(|(squares, constraints)| Component::count(squares, constraints, &mut steps))
+        .collect::<Option<_>>()?;
+
+    // the squares away from the numbers share whatever mines are left over,
+    // so each total of frontier mines is weighed by how many ways they can
+    let n_interior = hidden.len() - frontier.len();
+    let weight = ways(n_interior, n_mines, frontier.len());
+    let all = components.iter().fold(vec![1.0], |dist, component| {
+        convolve(&dist, &component.counts)
+    });
+    let total: f64 = all
+        .iter()
+        .enumerate()
+        .map(|(k, &count)| count * weight(k))
+        .sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let mut chances: HashMap<Point, f64> = HashMap::new();
+    for (c, component) in components.iter().enumerate() {
+        let others = components
+            .iter()
+            .enumerate()
+            .filter(|&(d, _)| d != c)
+            .fold(vec![1.0], |dist, (_, other)| convolve(&dist, &other.counts));
+        for (k, mines) in component.mines.iter().enumerate() {
+            // weight of the rest of the board, given [k] mines here
+            let rest: f64 = others
+                .iter()
+                .enumerate()
+                .map(|(f, &count)| count * weight(k + f))
+                .sum();
+            for (&s, &n) in component.squares.iter().zip(mines) {
+                *chances.entry(frontier[s]).or_default() += n * rest / total;
+            }
+        }
+    }
+    let interior_mines: f64 = all
+        .iter()
+        .enumerate()
+        .map(|(k, &count)| count * weight(k) * n_mines.saturating_sub(k) as f64)
+        .sum::<f64>()
+        / total;
+    let interior_chance = interior_mines / n_interior.max(1) as f64;
+
+    Some(
+        hidden
+            .into_iter()
+            .filter(|&p| is_unflagged(field, p))
+            .map(|p| match index.get(&p) {
+                Some(_) => (p, chances.get(&p).copied().unwrap_or(0.0)),
+                None => (p, interior_chance),
+            })
+            .collect(),
+    )
+}
+
+// one number's say on the frontier: exactly [mines] of [squares] (indexes
+// into the frontier) have mines
+struct Constraint {
+    squares: Vec<usize>,
+    mines: usize,
+}
+
+// a stretch of frontier no number links to the rest, with its arrangements
+// counted: counts[k] of them use k mines, and mines[k][s] of those put one
+// on squares[s]
+struct Component {
+    squares: Vec<usize>,
+    counts: Vec<f64>,
+    mines: Vec<Vec<f64>>,
+}
+
+impl Component {
+    // go through every arrangement of mines on [squares] that [constraints]
+    // allow, adding to [steps] (None once it's past MAX_STEPS)
+    fn count(
+        squares: Vec<usize>,
+        constraints: Vec<&Constraint>,
+        steps: &mut usize,
+    ) -> Option<Self> {
+        let on = squares
+            .iter()
+            .map(|s| {
+                let on_s = constraints.iter().enumerate();
+                on_s.filter(|(_, c)| c.squares.contains(s))
+                    .map(|(c, _)| c)
+                    .collect()
+            })
+            .collect();
+        let mut search = Search {
+            on,
+            left: constraints
+                .iter()
+                .map(|c| (c.mines, c.squares.len()))
+                .collect(),
+            placed: vec![false; squares.len()],
+            counts: vec![0.0; squares.len() + 1],
+            mines: vec![vec![0.0; squares.len()]; squares.len() + 1],
+            steps,
+        };
+        search.run(0)?;
+        let (counts, mines) = (search.counts, search.mines);
+        Some(Self {
+            squares,
+            counts,
+            mines,
+        })
+    }
+}
+
+// the state of a [Component::count] search
+struct Search<'a> {
+    on: Vec<Vec<usize>>,       // constraints on each square
+    left: Vec<(usize, usize)>, // (mines, squares) each constraint has left to place
+    placed: Vec<bool>,         // whether each square has a mine, as far as it's got
+    counts: Vec<f64>,
+    mines: Vec<Vec<f64>>,
+    steps: &'a mut usize,
+}
+
+impl Search<'_> {
+    // try square [at] and on both with and without a mine, backing out of
+    // any arrangement a constraint rules out
+    fn run(&mut self, at: usize) -> Option<()> {
+        *self.steps += 1;
+        if *self.steps > MAX_STEPS {
+            return None;
+        }
+        if at == self.placed.len() {
+            let k = self.placed.iter().filter(|&&mine| mine).count();
+            self.counts[k] += 1.0;
+            for (s, &mine) in self.placed.iter().enumerate() {
+                self.mines[k][s] += mine as u8 as f64;
+            }
+            return Some(());
+        }
+        for mine in [false, true] {
+            self.placed[at] = mine;
+            if self.place(at, mine) {
+                self.run(at + 1)?;
+            }
+            self.unplace(at, mine);
+        }
+        Some(())
+    }
+
+    // count square [s] in to its constraints, returning whether they can
+    // all still be met
+    fn place(&mut self, s: usize, mine: bool) -> bool {
+        let mut ok = true;
+        for &c in &self.on[s] {
+            let (mines, squares) = &mut self.left[c];
+            *squares -= 1;
+            if mine {
+                // (a constraint out of mines is broken, and fixed on unplacing)
+                *mines = mines.wrapping_sub(1);
+            }
+            ok &= *mines <= *squares;
+        }
+        ok
+    }
+
+    // take square [s] back out of its constraints
+    fn unplace(&mut self, s: usize, mine: bool) {
+        for &c in &self.on[s] {
+            let (mines, squares) = &mut self.left[c];
+            *squares += 1;
+            if mine {
+                *mines = mines.wrapping_add(1);
+            }
+        }
+    }
+}
+
+// the frontier's squares split up into stretches no number links, each
+// with the constraints on it (squares in the order constraints reach them,
+// so a search gets to check them early)
+fn components(n_squares: usize, constraints: &[Constraint]) -> Vec<(Vec<usize>, Vec<&Constraint>)> {
+    let mut on_square: Vec<Vec<usize>> = vec![Vec::new(); n_squares];
+    for (c, constraint) in constraints.iter().enumerate() {
+        for &s in &constraint.squares {
+            on_square[s].push(c);
+        }
+    }
+    let mut seen = vec![false; n_squares];
+    let mut used = vec![false; constraints.len()];
+    let mut components = Vec::new();
+    for start in 0..n_squares {
+        if seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let (mut squares, mut cs) = (vec![start], Vec::new());
+        let mut next = 0;
+        while let Some(&s) = squares.get(next) {
+            next += 1;
+            for &c in &on_square[s] {
+                if used[c] {
+                    continue;
+                }
+                used[c] = true;
+                cs.push(&constraints[c]);
+                for &t in &constraints[c].squares {
+                    if !seen[t] {
+                        seen[t] = true;
+                        squares.push(t);
+                    }
+                }
+            }
+        }
+        components.push((squares, cs));
+    }
+    components
+}
+
+// how many ways the [n] squares away from the numbers can hold the mines
+// the frontier leaves over, by how many it takes (relative to the most ways
+// any number it can take leaves, so they neither overflow nor underflow)
+fn ways(n: usize, mines_left: usize, n_frontier: usize) -> impl Fn(usize) -> f64 {
+    let ln_choose = move |k: usize| -> f64 {
+        (1..=k)
+            .map(|i| ((n - k + i) as f64).ln() - (i as f64).ln())
+            .sum()
+    };
+    let fewest = mines_left.saturating_sub(n_frontier);
+    let ln_max = ln_choose((n / 2).clamp(fewest.min(n), mines_left.min(n)));
+    move |frontier_mines| match mines_left.checked_sub(frontier_mines) {
+        Some(k) if k <= n => (ln_choose(k) - ln_max).exp(),
+        _ => 0.0,
+    }
+}
+
+// the distribution of the sum of two counts of mines
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut sum = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            sum[i + j] += x * y;
+        }
+    }
+    sum
+}
+
+// every square of a (height x width) board
+fn squares((height, width): (usize, usize)) -> impl Iterator<Item = Point> {
+    (0..height).flat_map(move |i| (0..width).map(move |j| Point(i, j)))
+}
+
+// squares the numbers alone make certain to be mines, flagged or not: all
+// those left hidden around a number with as many as it says (in the order
+// the numbers come in)
+fn certain_mines(field: &MineField) -> Vec<Point> {
+    let mut seen = HashSet::new();
+    let mut mines = Vec::new();
+    for p in squares(field.size()) {
+        let SquareView::Revealed(n) = field.cell(p) else {
+            continue;
+        };
+        let hidden = around(field, p);
+        if hidden.len() == n as usize {
+            mines.extend(hidden.into_iter().filter(|&q| seen.insert(q)));
+        }
+    }
+    mines
+}
+
+// the squares around [p] still hidden (flagged or not)
+fn around(field: &MineField, p: Point) -> Vec<Point> {
+    neighbors(p, field.size())
+        .filter(|&q| matches!(field.cell(q), SquareView::Hidden | SquareView::Flag))
+        .collect()
+}
+
+// whether [p] is hidden without a flag on it (so it can be revealed or flagged)
+fn is_unflagged(field: &MineField, p: Point) -> bool {
+    matches!(field.cell(p), SquareView::Hidden)
+}
+
+// the (up to 8) squares surrounding [p] on a (height x width) board
+fn neighbors(Point(i, j): Point, (height, width): (usize, usize)) -> impl Iterator<Item = Point> {
+    let rows = i.saturating_sub(1)..(i + 2).min(height);
+    rows.flat_map(move |ni| {
+        let cols = j.saturating_sub(1)..(j + 2).min(width);
+        cols.map(move |nj| Point(ni, nj))
+    })
+    .filter(move |&Point(ni, nj)| (ni, nj) != (i, j))
+}
@@ -0,0 +1,141 @@
+use std::ops::Range;
+
+use common::Point;
+
+use crate::mines::{MineField, SquareView};
+
+// size of the minimap (in terminal characters, at most)
+pub const MINIMAP_H: usize = 8;
+pub const MINIMAP_W: usize = 16;
+
+// minimap glyphs, by how much of the board under them is revealed (or flagged)
+const MINIMAP_HIDDEN: char = '░';
+const MINIMAP_PARTLY: char = '▒';
+const MINIMAP_REVEALED: char = '█';
+
+/// Window onto the part of the board that fits in the terminal
+pub struct Viewport {
+    gridh: usize,
+    gridw: usize,
+    top: usize,
+    left: usize,
+    height: usize,
+    width: usize,
+}
+
+impl Viewport {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Start with the whole board in view
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            gridh: height,
+            gridw: width,
+            top: 0,
+            left: 0,
+            height,
+            width,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Show (up to) [height] rows of [width] squares, keeping [cursor] in view
+    pub fn fit(&mut self, height: usize, width: usize, cursor: Point) {
+        self.height = self.gridh.min(height).max(1);
+        self.width = self.gridw.min(width).max(1);
+        self.follow(cursor);
+    }
+
+    /// Scroll just enough to bring square [p] into view
+    pub fn follow(&mut self, p: Point) {
+        self.top = self.top.min(p.0).max((p.0 + 1).saturating_sub(self.height));
+        self.left = self.left.min(p.1).max((p.1 + 1).saturating_sub(self.width));
+        self.clamp();
+    }
+
+    /// Whether the entire board is visible
+    pub fn shows_whole_grid(&self) -> bool {
+        self.height >= self.gridh && self.width >= self.gridw
+    }
+
+    /// Rows of the board in view
+    pub fn rows(&self) -> Range<usize> {
+        self.top..self.top + self.height
+    }
+
+    /// Columns of the board in view
+    pub fn cols(&self) -> Range<usize> {
+        self.left..self.left + self.width
+    }
+
+    /// Convert viewport coordinates to board coordinates (if inside the viewport)
+    pub fn to_global(&self, (li, lj): (usize, usize)) -> Option<Point> {
+        (li < self.height && lj < self.width).then_some(Point::new(li + self.top, lj + self.left))
+    }
+
+    /// Minimap of the whole board, as rows of glyphs (showing how much of
+    /// the board under each is revealed or flagged) along with whether
+    /// they're in view. Empty if the whole board is in view already
+    pub fn minimap(&self, field: &MineField) -> Vec<Vec<(char, bool)>> {
+        if self.shows_whole_grid() {
+            return Vec::new();
+        }
+
+        // board rows/cols under minimap row/col k of n
+        let span = |k: usize, n: usize, total: usize| {
+            let lo = k * total / n;
+            lo..((k + 1) * total / n).max(lo + 1)
+        };
+        let overlaps = |a: &Range<usize>, b: &Range<usize>| a.start < b.end && b.start < a.end;
+        let (map_h, map_w) = (MINIMAP_H.min(self.gridh), MINIMAP_W.min(self.gridw));
+        (0..map_h)
+            .map(|mi| {
+                let rows = span(mi, map_h, self.gridh);
+                (0..map_w)
+                    .map(|mj| {
+                        let cols = span(mj, map_w, self.gridw);
+                        let in_view =
+                            overlaps(&rows, &self.rows()) && overlaps(&cols, &self.cols());
+                        (minimap_glyph(field, rows.clone(), cols), in_view)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // keep viewport inside the board
+    fn clamp(&mut self) {
+        self.top = self.top.min(self.gridh - self.height);
+        self.left = self.left.min(self.gridw - self.width);
+    }
+}
+
+// minimap glyph for the squares in [rows] x [cols] of the board
+fn minimap_glyph(field: &MineField, rows: Range<usize>, cols: Range<usize>) -> char {
+    let (mut n_squares, mut n_done) = (0, 0);
+    for i in rows {
+        for j in cols.clone() {
+            n_squares += 1;
+            if !matches!(
+                field.view_sq(&Point::new(i, j)),
+                Some(SquareView::Hidden) | None
+            ) {
+                n_done += 1;
+            }
+        }
+    }
+    match n_done {
+        0 => MINIMAP_HIDDEN,
+        n if n == n_squares => MINIMAP_REVEALED,
+        _ => MINIMAP_PARTLY,
+    }
+}
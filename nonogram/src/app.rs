@@ -0,0 +1,314 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::NonogramConfig;
+use crate::nonogram::{Move, MoveResult, Nonogram, SquareView};
+use crate::nonoui::{self, NonoUI, NonoUIAction, UIMode};
+
+// puzzle size when neither the command line nor the config file sets one
+const DEFAULT_SIZE: u16 = 10;
+
+// terminal columns each square (and each column's clue numbers) takes up
+const SQUARE_COLS: usize = 2;
+
+// lines of text under the grid (mode, message)
+const STATUS_ROWS: usize = 2;
+
+/// Nonogram app (puzzle + UI state)
+pub struct NonogramApp {
+    nonogram: Nonogram,
+    ui: NonoUI,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless the puzzle was solved
+    over: bool,
+}
+
+impl NonogramApp {
+    pub fn new(nonogram: Nonogram) -> Self {
+        let (height, width) = nonogram.size();
+        Self {
+            nonogram,
+            ui: NonoUI::new(height, width),
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        match res {
+            MoveResult::Solved => {
+                bell::ring("win");
+                self.over = true;
+                self.outcome = Outcome::Won;
+                self.message = locale::text("nonogram.win")
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Checked(0) => {
+                self.message = locale::text("nonogram.no_mistakes")
+                    .to_string()
+                    .with(theme::color(Role::Success));
+            }
+            MoveResult::Checked(n) => {
+                bell::ring("mistakes");
+                self.message = format!("{}: {n}", locale::text("nonogram.mistakes"))
+                    .with(theme::color(Role::Danger));
+            }
+            MoveResult::Ok => self.message = "".to_string().reset(),
+        }
+    }
+
+    // (styled) string for a square, highlighted if the cursor is on it
+    fn styled_square(&self, sq: SquareView, at_cursor: bool) -> StyledContent<&'static str> {
+        let color = style::color_enabled();
+        let sq_str = match sq {
+            SquareView::Empty => " .".with(theme::color(Role::Muted)),
+            SquareView::Crossed => " x".with(theme::color(Role::Muted)),
+            SquareView::Filled if color => "██".with(theme::color(Role::Text)),
+            SquareView::Filled => "##".bold(),
+            SquareView::Wrong if color => "██".with(theme::color(Role::Danger)),
+            SquareView::Wrong => "!!".bold(),
+        };
+        if !at_cursor {
+            return sq_str;
+        }
+        // (cross mode shows as an underline)
+        match self.ui.mode {
+            UIMode::Fill => sq_str.reverse(),
+            UIMode::Cross => sq_str.reverse().underlined(),
+        }
+    }
+
+    // color for a line's clue: dimmed once it's done, bright under the cursor
+    fn clue_role(done: bool, at_cursor: bool) -> Role {
+        match (done, at_cursor) {
+            (_, true) => Role::Accent,
+            (true, false) => Role::Muted,
+            (false, false) => Role::Text,
+        }
+    }
+
+    /// Lines of the whole puzzle: column clues stacked above the grid, and
+    /// row clues to the left of it, each line a list of (styled) pieces
+    fn puzzle_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let (height, width) = self.nonogram.size();
+        let Point(ci, cj) = self.ui.get_cursor();
+        let row_clues: Vec<String> = (0..height)
+            .map(|i| clue_text(self.nonogram.row_clue(i), " "))
+            .collect();
+        let clues_cols = row_clues.iter().map(|clue| clue.len()).max().unwrap_or(0);
+        let clues_rows = (0..width)
+            .map(|j| self.nonogram.col_clue(j).len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut lines = Vec::new();
+        for r in 0..clues_rows {
+            let mut line = vec![" ".repeat(clues_cols + 1).reset()];
+            for j in 0..width {
+                let clue = self.nonogram.col_clue(j);
+                // (clues sit at the bottom, right above their column)
+                let n = (r + clue.len()).checked_sub(clues_rows).map(|k| clue[k]);
+                let text = match (n, clue.is_empty() && r + 1 == clues_rows) {
+                    (Some(n), _) => format!("{n:>SQUARE_COLS$}"),
+                    (None, true) => format!("{:>SQUARE_COLS$}", 0),
+                    (None, false) => " ".repeat(SQUARE_COLS),
+                };
+                let role = Self::clue_role(self.nonogram.col_done(j), j == cj);
+                line.push(text.with(theme::color(role)));
+            }
+            lines.push(line);
+        }
+
+        for (i, clue) in row_clues.iter().enumerate() {
+            let role = Self::clue_role(self.nonogram.row_done(i), i == ci);
+            let mut line = vec![format!("{clue:>clues_cols$} ").with(theme::color(role))];
+            for j in 0..width {
+                let sq = self.nonogram.cell(Point(i, j));
+                let styled = self.styled_square(sq, (i, j) == (ci, cj));
+                line.push(StyledContent::new(
+                    *styled.style(),
+                    styled.content().to_string(),
+                ));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    fn mode_line(&self) -> String {
+        let mode = match self.ui.mode {
+            UIMode::Fill => locale::text("nonogram.fill"),
+            UIMode::Cross => locale::text("nonogram.cross"),
+        };
+        format!(
+            "[{mode}]   {} {}",
+            locale::text("nonogram.checks"),
+            self.nonogram.checks()
+        )
+    }
+}
+
+// a line's clue, numbers separated by [sep] (0 for a blank line)
+fn clue_text(clue: &[u32], sep: &str) -> String {
+    if clue.is_empty() {
+        return "0".into();
+    }
+    let numbers: Vec<_> = clue.iter().map(u32::to_string).collect();
+    numbers.join(sep)
+}
+
+// Pretty-print
+impl fmt::Display for NonogramApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.puzzle_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.mode_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        if self.over {
+            write!(f, "{}", locale::text("nonogram.exit"))
+        } else {
+            write!(f, "{}", nonoui::help_text())
+        }
+    }
+}
+
+impl Game for NonogramApp {
+    fn name(&self) -> &'static str {
+        "nonogram"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != NonoUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            NonoUIAction::Quit => self.over = true,
+            NonoUIAction::Wait => {}
+            NonoUIAction::Mode(mode) => self.ui.mode = mode,
+            NonoUIAction::ToggleMode => self.ui.toggle_mode(),
+            NonoUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the grid");
+                }
+            }
+            NonoUIAction::Select => {
+                let p = self.ui.get_cursor();
+                let mv = match self.ui.mode {
+                    UIMode::Fill => Move::Fill(p),
+                    UIMode::Cross => Move::Cross(p),
+                };
+                let res = self.nonogram.make_move(mv);
+                self.handle_res(res);
+            }
+            NonoUIAction::Check => {
+                let res = self.nonogram.make_move(Move::Check);
+                self.handle_res(res);
+            }
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    // the clues and grid, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let lines = self.puzzle_lines();
+        let cols = lines[0]
+            .iter()
+            .map(|piece| text_width(piece.content()))
+            .sum::<u16>();
+        let help = nonoui::help_text();
+        let help_cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max(help_cols),
+            (lines.len() + 1 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play a random nonogram of the size chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: NonogramConfig = common::cli::setup(&args.common, "nonogram")?;
+    let height = args.height.or(config.height).unwrap_or(DEFAULT_SIZE);
+    let width = args.width.or(config.width).unwrap_or(DEFAULT_SIZE);
+    if !(2..=30).contains(&height) || !(2..=30).contains(&width) {
+        return Err(format!(
+            "bad [nonogram] config: {width}x{height} puzzle (sides go from 2 to 30)"
+        ));
+    }
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("nonogram seed {seed}");
+    let nonogram = Nonogram::generate(height as usize, width as usize, seed);
+    let mut app = NonogramApp::new(nonogram);
+    app.ui
+        .bindings
+        .remap(&config.keys, &nonoui::ACTIONS)
+        .map_err(|e| format!("bad [nonogram.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the picture up until a key is pressed, unless the player quit
+    if app.outcome != Outcome::Quit {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+
+use super::NonogramApp;
+use crate::nonoui;
+
+impl TuiGame for NonogramApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .puzzle_lines()
+            .iter()
+            .map(|line| line.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows);
+
+        let message = tui::span(&self.message);
+        let status = StatusBar::new(nonoui::help_text())
+            .line(self.mode_line())
+            .line(message.clone());
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+
+        if self.over {
+            let mut lines = vec![Line::from(locale::text("nonogram.exit"))];
+            if !message.content.is_empty() {
+                lines.insert(0, Line::from(message));
+            }
+            let title = format!(" {} ", locale::text("nonogram.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
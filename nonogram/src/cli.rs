@@ -0,0 +1,34 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Nonogram (Picross)
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on finding mistakes, solving and achievements")
+}))]
+pub struct Args {
+    /// Puzzle height (in squares) [default: 10, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2..=30))]
+    pub height: Option<u16>,
+
+    /// Puzzle width (in squares) [default: 10, unless the config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u16).range(2..=30))]
+    pub width: Option<u16>,
+
+    /// Random seed for generating the puzzle (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [nonogram] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NonogramConfig {
+    /// puzzle height, in squares
+    pub height: Option<u16>,
+    /// puzzle width, in squares
+    pub width: Option<u16>,
+    /// keys for each action, e.g. select = ["space", "enter"]
+    pub keys: KeyMap,
+}
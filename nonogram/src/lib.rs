@@ -0,0 +1,20 @@
+//! Nonograms (Picross): the game core (a hidden picture and its clues),
+//! a line-by-line solver for generating fair puzzles, plus the crossterm
+//! frontend for playing them in a terminal
+
+mod nonogram;
+pub mod solver;
+pub use nonogram::{Move, MoveResult, Nonogram, SquareView};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod nonoui;
+#[cfg(feature = "terminal")]
+pub use app::{run, NonogramApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
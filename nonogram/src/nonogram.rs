@@ -0,0 +1,204 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::solver::{self, Known};
+
+// share of squares filled in a generated picture
+const DENSITY: f64 = 0.6;
+
+// random pictures tried before settling for one that needs guessing
+const MAX_ATTEMPTS: u32 = 200;
+
+/// Something the player can do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Move {
+    /// fill a square in, or empty it again
+    Fill(Point),
+    /// cross a square out (as surely empty), or clear the cross
+    Cross(Point),
+    /// show which filled squares are wrong
+    Check,
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    Ok,
+    /// checked, and this many filled squares are wrong
+    Checked(u32),
+    /// every row and column matches its clue
+    Solved,
+}
+
+/// What's on one square, as far as drawing it goes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SquareView {
+    Empty,
+    Filled,
+    Crossed,
+    /// filled, but shouldn't be (only shown after a check)
+    Wrong,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mark {
+    None,
+    Filled,
+    Crossed,
+}
+
+/// A nonogram: a hidden picture, the clues to it along each row and column,
+/// and the squares the player has marked so far
+pub struct Nonogram {
+    height: usize,
+    width: usize,
+    picture: Vec<bool>,
+    marks: Vec<Mark>,
+    row_clues: Vec<Vec<u32>>,
+    col_clues: Vec<Vec<u32>>,
+    checked: bool, // whether wrong squares are shown (until the next mark)
+    checks: u32,
+}
+
+impl Nonogram {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Random (height x width) picture, which can be worked out from its
+    /// clues one line at a time, without guessing (unless none turned up
+    /// after a good many tries). The same seed always gives the same puzzle
+    pub fn generate(height: usize, width: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let picture: Vec<bool> = (0..height * width).map(|_| rng.gen_bool(DENSITY)).collect();
+            let nonogram = Self::from_picture(height, width, picture);
+            let solved = solver::solve(&nonogram.row_clues, &nonogram.col_clues)
+                .is_some_and(|grid| !grid.contains(&Known::Unknown));
+            if solved || attempts == MAX_ATTEMPTS {
+                debug!("generated a {width}x{height} puzzle in {attempts} attempts (solvable: {solved})");
+                return nonogram;
+            }
+        }
+    }
+
+    /// Puzzle for [picture] (a row at a time)
+    pub fn from_picture(height: usize, width: usize, picture: Vec<bool>) -> Self {
+        assert_eq!(picture.len(), height * width, "picture doesn't fit");
+        let row_clues = (0..height)
+            .map(|i| solver::clue((0..width).map(|j| picture[i * width + j])))
+            .collect();
+        let col_clues = (0..width)
+            .map(|j| solver::clue((0..height).map(|i| picture[i * width + j])))
+            .collect();
+        Self {
+            height,
+            width,
+            picture,
+            marks: vec![Mark::None; height * width],
+            row_clues,
+            col_clues,
+            checked: false,
+            checks: 0,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn make_move(&mut self, mv: Move) -> MoveResult {
+        let (p, mark) = match mv {
+            Move::Fill(p) => (p, Mark::Filled),
+            Move::Cross(p) => (p, Mark::Crossed),
+            Move::Check => {
+                self.checked = true;
+                self.checks += 1;
+                let wrong = (0..self.picture.len())
+                    .filter(|&k| self.marks[k] == Mark::Filled && !self.picture[k])
+                    .count();
+                return MoveResult::Checked(wrong as u32);
+            }
+        };
+        let k = self.index(p);
+        self.marks[k] = if self.marks[k] == mark {
+            Mark::None
+        } else {
+            mark
+        };
+        self.checked = false;
+        match self.is_solved() {
+            true => MoveResult::Solved,
+            false => MoveResult::Ok,
+        }
+    }
+
+    /// Every row and column matches its clue
+    pub fn is_solved(&self) -> bool {
+        (0..self.height).all(|i| self.row_done(i)) && (0..self.width).all(|j| self.col_done(j))
+    }
+
+    pub fn row_clue(&self, i: usize) -> &[u32] {
+        &self.row_clues[i]
+    }
+
+    pub fn col_clue(&self, j: usize) -> &[u32] {
+        &self.col_clues[j]
+    }
+
+    /// Whether the squares filled in along row [i] match its clue
+    pub fn row_done(&self, i: usize) -> bool {
+        let line = (0..self.width).map(|j| self.marks[i * self.width + j] == Mark::Filled);
+        solver::clue(line) == self.row_clues[i]
+    }
+
+    /// Whether the squares filled in down column [j] match its clue
+    pub fn col_done(&self, j: usize) -> bool {
+        let line = (0..self.height).map(|i| self.marks[i * self.width + j] == Mark::Filled);
+        solver::clue(line) == self.col_clues[j]
+    }
+
+    /// Times the player has checked for mistakes
+    pub fn checks(&self) -> u32 {
+        self.checks
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn index(&self, Point(i, j): Point) -> usize {
+        i * self.width + j
+    }
+}
+
+impl Core for Nonogram {
+    type Action = Move;
+    type Outcome = MoveResult;
+    type Cell = SquareView;
+
+    fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    fn cell(&self, p: Point) -> SquareView {
+        let k = self.index(p);
+        match self.marks[k] {
+            Mark::Filled if self.checked && !self.picture[k] => SquareView::Wrong,
+            Mark::Filled => SquareView::Filled,
+            Mark::Crossed => SquareView::Crossed,
+            Mark::None => SquareView::Empty,
+        }
+    }
+
+    fn act(&mut self, mv: Move) -> MoveResult {
+        self.make_move(mv)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_solved()
+    }
+}
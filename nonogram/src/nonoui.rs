@@ -0,0 +1,155 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+/// Help lines under the grid, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("nonogram.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NonoUIAction {
+    Wait,
+    Move(MoveDirection),
+    Mode(UIMode),
+    ToggleMode,
+    Select,
+    Check,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// What selecting a square does (like Minesweeper's reveal and flag modes)
+#[derive(Debug, Clone, PartialEq)]
+pub enum UIMode {
+    Fill,
+    Cross,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, NonoUIAction); 11] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        NonoUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        NonoUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        NonoUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        NonoUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), NonoUIAction::Select),
+    (KeyBinding::char('f'), NonoUIAction::Mode(UIMode::Fill)),
+    (KeyBinding::char('x'), NonoUIAction::Mode(UIMode::Cross)),
+    (KeyBinding::key(KeyCode::Tab), NonoUIAction::ToggleMode),
+    (KeyBinding::char('c'), NonoUIAction::Check),
+    (KeyBinding::char('q'), NonoUIAction::Quit),
+    (KeyBinding::ctrl('c'), NonoUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, NonoUIAction); 10] = [
+    ("up", NonoUIAction::Move(MoveDirection::Up)),
+    ("down", NonoUIAction::Move(MoveDirection::Down)),
+    ("left", NonoUIAction::Move(MoveDirection::Left)),
+    ("right", NonoUIAction::Move(MoveDirection::Right)),
+    ("select", NonoUIAction::Select),
+    ("fill_mode", NonoUIAction::Mode(UIMode::Fill)),
+    ("cross_mode", NonoUIAction::Mode(UIMode::Cross)),
+    ("toggle_mode", NonoUIAction::ToggleMode),
+    ("check", NonoUIAction::Check),
+    ("quit", NonoUIAction::Quit),
+];
+
+pub struct NonoUI {
+    gridh: usize,
+    gridw: usize,
+    cursor: Point,
+    pub mode: UIMode,
+    pub bindings: Bindings<NonoUIAction>,
+}
+
+impl NonoUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            gridh: height,
+            gridw: width,
+            cursor: Point::origin(),
+            mode: UIMode::Fill,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> NonoUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(NonoUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> NonoUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => NonoUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a square, unless that's off the grid
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let Point(i, j) = self.cursor;
+        let (i, j) = match dir {
+            MoveDirection::Up => (i.checked_sub(1), Some(j)),
+            MoveDirection::Down => (Some(i + 1).filter(|&i| i < self.gridh), Some(j)),
+            MoveDirection::Left => (Some(i), j.checked_sub(1)),
+            MoveDirection::Right => (Some(i), Some(j + 1).filter(|&j| j < self.gridw)),
+        };
+        let (Some(i), Some(j)) = (i, j) else {
+            return Err("already at the edge".into());
+        };
+        self.cursor = Point(i, j);
+        Ok(())
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            UIMode::Fill => UIMode::Cross,
+            UIMode::Cross => UIMode::Fill,
+        };
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<NonoUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
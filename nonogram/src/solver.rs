@@ -0,0 +1,116 @@
+/// What's known about a square while solving
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Known {
+    Unknown,
+    Filled,
+    Empty,
+}
+
+/// Runs of filled squares along a line, in order (empty for a blank line)
+pub fn clue(line: impl IntoIterator<Item = bool>) -> Vec<u32> {
+    let mut runs = Vec::new();
+    let mut run = 0;
+    for filled in line {
+        if filled {
+            run += 1;
+        } else if run > 0 {
+            runs.push(run);
+            run = 0;
+        }
+    }
+    if run > 0 {
+        runs.push(run);
+    }
+    runs
+}
+
+/// Work out as much of a (height x width) grid as the clues give away, a line
+/// at a time, the way a person would (no guessing). Returns the grid a row
+/// at a time, or None if the clues contradict each other
+pub fn solve(row_clues: &[Vec<u32>], col_clues: &[Vec<u32>]) -> Option<Vec<Known>> {
+    let (height, width) = (row_clues.len(), col_clues.len());
+    let mut grid = vec![Known::Unknown; height * width];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (i, clue) in row_clues.iter().enumerate() {
+            let line: Vec<_> = (0..width).map(|j| grid[i * width + j]).collect();
+            for (j, known) in solve_line(clue, &line)?.into_iter().enumerate() {
+                changed |= grid[i * width + j] != known;
+                grid[i * width + j] = known;
+            }
+        }
+        for (j, clue) in col_clues.iter().enumerate() {
+            let line: Vec<_> = (0..height).map(|i| grid[i * width + j]).collect();
+            for (i, known) in solve_line(clue, &line)?.into_iter().enumerate() {
+                changed |= grid[i * width + j] != known;
+                grid[i * width + j] = known;
+            }
+        }
+    }
+    Some(grid)
+}
+
+// what every way of fitting [clue] into [line] agrees on (None if there's
+// no way at all)
+fn solve_line(clue: &[u32], line: &[Known]) -> Option<Vec<Known>> {
+    let mut agreed = None;
+    let mut arrangement = vec![false; line.len()];
+    place(clue, line, 0, &mut arrangement, &mut agreed);
+    agreed
+}
+
+// try every position for the first run of [clue] from [start] on, and the
+// rest of the runs after it, merging each full arrangement into [agreed]
+fn place(
+    clue: &[u32],
+    line: &[Known],
+    start: usize,
+    arrangement: &mut Vec<bool>,
+    agreed: &mut Option<Vec<Known>>,
+) {
+    let Some((&run, rest)) = clue.split_first() else {
+        // (nothing else can be filled in)
+        if line[start.min(line.len())..].contains(&Known::Filled) {
+            return;
+        }
+        let known = arrangement[..start.min(line.len())]
+            .iter()
+            .map(|&filled| if filled { Known::Filled } else { Known::Empty })
+            .chain((start..line.len()).map(|_| Known::Empty));
+        match agreed {
+            None => *agreed = Some(known.collect()),
+            Some(agreed) => {
+                for (a, k) in agreed.iter_mut().zip(known) {
+                    if *a != k {
+                        *a = Known::Unknown;
+                    }
+                }
+            }
+        }
+        return;
+    };
+    let run = run as usize;
+    // room the rest of the runs need after this one
+    let needed: usize = rest.iter().map(|&r| r as usize + 1).sum();
+    let Some(last) = line.len().checked_sub(needed + run) else {
+        return;
+    };
+    for pos in start..=last {
+        // squares skipped over have to be empty
+        if pos > start && line[pos - 1] == Known::Filled {
+            break;
+        }
+        let fits = !line[pos..pos + run].contains(&Known::Empty)
+            && line.get(pos + run) != Some(&Known::Filled);
+        if !fits {
+            continue;
+        }
+        arrangement[start..pos].fill(false);
+        arrangement[pos..pos + run].fill(true);
+        if pos + run < line.len() {
+            arrangement[pos + run] = false;
+        }
+        place(rest, line, pos + run + 1, arrangement, agreed);
+    }
+}
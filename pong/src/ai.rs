@@ -0,0 +1,54 @@
+use common::{Core, Player};
+
+use crate::pong::{Action, Pong, Side, PADDLE};
+
+// ticks between the computer's paddle moves (it's slower than a person
+// tapping a key, so a steep enough ball gets past it)
+const MOVE_EVERY: u32 = 4;
+
+/// Computer player for one side: it follows the ball once it's coming its
+/// way over the net, and drifts back to the middle the rest of the time
+pub struct Ai {
+    side: Side,
+    ticks: u32,
+}
+
+impl Ai {
+    pub fn new(side: Side) -> Self {
+        Self { side, ticks: 0 }
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+}
+
+impl Player<Pong> for Ai {
+    /// Move to make on this tick, if any
+    fn choose_action(&mut self, pong: &Pong) -> Option<Action> {
+        self.ticks += 1;
+        if !self.ticks.is_multiple_of(MOVE_EVERY) {
+            return None;
+        }
+        let (row, col) = pong.ball();
+        let (height, width) = pong.size();
+        let (height, half) = (height as f64, width as f64 / 2.0);
+        // (it only sees the ball coming once it's over the net)
+        let coming = match self.side {
+            Side::Left => pong.velocity().1 < 0.0 && col < half,
+            Side::Right => pong.velocity().1 > 0.0 && col > half,
+        };
+        let target = match coming {
+            true => row,
+            false => (height - 1.0) / 2.0,
+        };
+        let middle = pong.paddle(self.side) as f64 + (PADDLE as f64 - 1.0) / 2.0;
+        if target < middle - 1.0 {
+            Some(Action::Up(self.side))
+        } else if target > middle + 1.0 {
+            Some(Action::Down(self.side))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Player, Point, TerminalGuard};
+
+use crate::ai::Ai;
+use crate::cli::Args;
+use crate::config::PongConfig;
+use crate::pong::{Action, Pong, Side, Square, Step};
+use crate::pongui::{self, PongUI, PongUIAction};
+
+// time between frames (the ball moves at most a column a frame)
+const TICK: Duration = Duration::from_millis(25);
+
+// points to win when nothing else sets it
+const DEFAULT_POINTS: u32 = 11;
+
+// fewest and most points a game can go to
+const MIN_POINTS: u32 = 1;
+const MAX_POINTS: u32 = 99;
+
+// lines of text under the court (score, message, exit prompt)
+const STATUS_ROWS: usize = 3;
+
+/// Pong app (game + UI state, and the computer player if there is one)
+pub struct PongApp {
+    pong: Pong,
+    ui: PongUI,
+    computer: Option<Ai>, // none for two players at one keyboard
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless a game against the computer was decided
+    over: bool,
+}
+
+impl PongApp {
+    pub fn new(pong: Pong, computer: Option<Ai>) -> Self {
+        Self {
+            pong,
+            ui: PongUI::new(),
+            computer,
+            message: locale::text("pong.start").to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // move a paddle for the person at the keyboard (against the computer,
+    // either set of keys moves theirs)
+    fn move_paddle(&mut self, action: Action) {
+        let action = match (&self.computer, action) {
+            (Some(ai), Action::Up(side)) if side == ai.side() => Action::Up(side.opposite()),
+            (Some(ai), Action::Down(side)) if side == ai.side() => Action::Down(side.opposite()),
+            _ => action,
+        };
+        self.pong.act(action);
+    }
+
+    fn handle_step(&mut self, step: Step) {
+        match step {
+            Step::Won(side) => {
+                self.over = true;
+                let (won, text) = match &self.computer {
+                    Some(ai) if ai.side() == side => (false, locale::text("pong.lose")),
+                    Some(_) => (true, locale::text("pong.win")),
+                    None => (
+                        true,
+                        self.side_text(side, "pong.left_wins", "pong.right_wins"),
+                    ),
+                };
+                if self.computer.is_some() {
+                    self.outcome = if won { Outcome::Won } else { Outcome::Lost };
+                }
+                bell::ring(if won { "win" } else { "lose" });
+                let role = if won { Role::Success } else { Role::Danger };
+                self.message = text
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(role));
+            }
+            Step::Scored(side) => {
+                bell::ring("point");
+                debug!(
+                    "{side:?} scored, {} : {}",
+                    self.pong.score(Side::Left),
+                    self.pong.score(Side::Right)
+                );
+            }
+            Step::Hit(_) | Step::Moved => {}
+        }
+    }
+
+    // text for [side]: "you" or "computer" against the computer, or
+    // [left] and [right] (locale keys) between two people
+    fn side_text(&self, side: Side, left: &str, right: &str) -> &'static str {
+        match (&self.computer, side) {
+            (Some(ai), side) if ai.side() == side => locale::text("pong.computer"),
+            (Some(_), _) => locale::text("pong.you"),
+            (None, Side::Left) => locale::text(left),
+            (None, Side::Right) => locale::text(right),
+        }
+    }
+
+    // (styled) string for a square: blocks of color, or ASCII without color,
+    // with the net down the middle of the court
+    fn styled_square(&self, sq: Square, net: bool) -> StyledContent<&'static str> {
+        let (role, plain, block) = match sq {
+            Square::Empty if net => return "┊".with(theme::color(Role::Muted)),
+            Square::Empty => return " ".reset(),
+            Square::Paddle(Side::Left) => (Role::Accent, "#", "█"),
+            Square::Paddle(Side::Right) => (Role::Marker, "#", "█"),
+            Square::Ball => (Role::Text, "O", "●"),
+        };
+        if style::color_enabled() {
+            block.with(theme::color(role))
+        } else {
+            plain.bold()
+        }
+    }
+
+    // score on each side and points to win, plus [paused] while paused
+    fn status(&self) -> String {
+        let mut status = format!(
+            "{} {} : {} {}   {} {}",
+            self.side_text(Side::Left, "pong.left", "pong.right"),
+            self.pong.score(Side::Left),
+            self.pong.score(Side::Right),
+            self.side_text(Side::Right, "pong.left", "pong.right"),
+            locale::text("pong.first_to"),
+            self.pong.points()
+        );
+        if self.ui.paused && !self.over {
+            status += &format!(" [{}]", locale::text("pong.paused"));
+        }
+        status
+    }
+
+    // rows of the court, as (styled) squares
+    fn court_rows(&self) -> impl Iterator<Item = Vec<StyledContent<&'static str>>> + '_ {
+        let (height, width) = self.pong.size();
+        (0..height).map(move |i| {
+            (0..width)
+                .map(|j| self.styled_square(self.pong.cell(Point(i, j)), j == width / 2))
+                .collect()
+        })
+    }
+}
+
+// Pretty-print
+impl fmt::Display for PongApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, width) = self.pong.size();
+        let edge = "─".repeat(width);
+        let wall = "│".with(theme::color(Role::Muted));
+        write!(
+            f,
+            "{}\r\n",
+            format!("┌{edge}┐").with(theme::color(Role::Muted))
+        )?;
+        for row in self.court_rows() {
+            write!(f, "{wall}")?;
+            for sq in row {
+                write!(f, "{sq}")?;
+            }
+            write!(f, "{wall}\r\n")?;
+        }
+        write!(
+            f,
+            "{}\r\n",
+            format!("└{edge}┘").with(theme::color(Role::Muted))
+        )?;
+
+        write!(f, "{}\r\n", self.status())?;
+        write!(f, "{}\r\n", self.message)?;
+        if self.over {
+            write!(f, "{}\r\n", locale::text("pong.exit"))?;
+        } else {
+            write!(f, "{}\r\n", pongui::help_text(self.computer.is_some()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Game for PongApp {
+    fn name(&self) -> &'static str {
+        "pong"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != PongUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            PongUIAction::Quit => self.over = true,
+            PongUIAction::Pause => self.ui.paused = !self.ui.paused,
+            // moving a paddle also gets the game going again
+            PongUIAction::Move(action) => {
+                self.ui.paused = false;
+                self.move_paddle(action);
+            }
+            PongUIAction::Wait => {}
+        }
+        if !self.ui.paused && !self.over {
+            self.message = "".to_string().reset();
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {
+        if self.ui.paused || self.over {
+            return;
+        }
+        if let Some(action) = self
+            .computer
+            .as_mut()
+            .and_then(|computer| computer.choose_action(&self.pong))
+        {
+            self.pong.act(action);
+        }
+        let step = self.pong.step();
+        self.handle_step(step);
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(TICK)
+    }
+
+    // the court with its walls, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.pong.size();
+        let cols = (width + 2) as u16;
+        let help = pongui::help_text(self.computer.is_some());
+        (
+            cols.max(text_width(help)),
+            (height + 2 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Pong, two people at one keyboard or one against the computer
+pub fn run(args: Args) -> Result<(), String> {
+    let config: PongConfig = common::cli::setup(&args.common, "pong")?;
+
+    let points = args.points.or(config.points).unwrap_or(DEFAULT_POINTS);
+    if !(MIN_POINTS..=MAX_POINTS).contains(&points) {
+        return Err(format!(
+            "bad [pong] config: points {points} (goes from {MIN_POINTS} to {MAX_POINTS})"
+        ));
+    }
+    let computer = (args.ai || config.ai).then(|| Ai::new(Side::Right));
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("pong seed {seed}, to {points} points");
+    let pong = Pong::new(args.height as usize, args.width as usize, points, seed);
+    let mut app = PongApp::new(pong, computer);
+    app.ui
+        .bindings
+        .remap(&config.keys, &pongui::ACTIONS)
+        .map_err(|e| format!("bad [pong.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the court up until a key is pressed, unless the players quit
+    if app.over && app.pong.winner().is_some() {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
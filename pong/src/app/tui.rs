@@ -0,0 +1,38 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+
+use super::PongApp;
+use crate::pong::Side;
+use crate::pongui;
+
+impl TuiGame for PongApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .court_rows()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let title = format!(
+            " {} : {} ",
+            self.pong.score(Side::Left),
+            self.pong.score(Side::Right)
+        );
+        let board = Board::new(rows).title(title);
+
+        let message = tui::span(&self.message);
+        let status = StatusBar::new(pongui::help_text(self.computer.is_some()))
+            .line(self.status())
+            .line(message.clone());
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+
+        if self.over && self.pong.winner().is_some() {
+            let lines = vec![Line::from(message), Line::from(locale::text("pong.exit"))];
+            let title = format!(" {} ", locale::text("pong.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
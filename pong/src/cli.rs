@@ -0,0 +1,44 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Pong
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on points, winning and achievements")
+}))]
+pub struct Args {
+    /// Court height (in rows)
+    #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u16).range(8..))]
+    pub height: u16,
+
+    /// Court width (in columns)
+    #[arg(long, default_value_t = 56, value_parser = clap::value_parser!(u16).range(20..))]
+    pub width: u16,
+
+    /// Points to win, from 1 to 99 [default: 11, unless the config file
+    /// picks another]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=99))]
+    pub points: Option<u32>,
+
+    /// Have the computer play the right paddle (the arrow keys move the
+    /// left one too)
+    #[arg(long)]
+    pub ai: bool,
+
+    /// Random seed for the angle of each serve (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
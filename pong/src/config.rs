@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [pong] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PongConfig {
+    /// points to win
+    pub points: Option<u32>,
+    /// the computer playing the right paddle
+    pub ai: bool,
+    /// keys for each action, e.g. left_up = ["w", "k"]
+    pub keys: KeyMap,
+}
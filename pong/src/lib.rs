@@ -0,0 +1,21 @@
+//! Pong: the game core (two paddles and a ball bouncing between them, with
+//! the score), a computer player for either side, plus the crossterm
+//! frontend for two people at one keyboard, or one against the computer
+
+mod ai;
+mod pong;
+pub use ai::Ai;
+pub use pong::{Action, Pong, Side, Square, Step, PADDLE};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod pongui;
+#[cfg(feature = "terminal")]
+pub use app::{run, PongApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
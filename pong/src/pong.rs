@@ -0,0 +1,305 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Rows each paddle covers
+pub const PADDLE: usize = 4;
+
+// rows a paddle moves at a time
+const PADDLE_STEP: usize = 2;
+
+// columns the ball moves a tick, when served and at most (never more than
+// one, so it can't jump over a paddle)
+const SERVE_SPEED: f64 = 0.5;
+const MAX_SPEED: f64 = 1.0;
+
+// how much faster the ball goes off each paddle
+const SPEEDUP: f64 = 1.05;
+
+// most rows the ball moves for each column, off the very end of a paddle
+// (rows are about twice as tall as columns are wide, so 0.5 is 45 degrees)
+const MAX_SLOPE: f64 = 0.75;
+
+// ticks the ball waits in the middle before each serve
+const SERVE_WAIT: u32 = 40;
+
+/// One side of the court, and the player on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn opposite(self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// move that side's paddle up
+    Up(Side),
+    /// move that side's paddle down
+    Down(Side),
+}
+
+/// What's on one square of the court
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Empty,
+    Paddle(Side),
+    Ball,
+}
+
+/// What happened on a tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Moved,
+    /// the ball came off this side's paddle
+    Hit(Side),
+    /// the ball got past the other side's paddle
+    Scored(Side),
+    /// this side scored the last point needed
+    Won(Side),
+}
+
+/// A game of Pong: two paddles, a ball bouncing between them off the top
+/// and bottom walls, and the points each side has won
+#[derive(Debug, Clone)]
+pub struct Pong {
+    height: usize,
+    width: usize,
+    points: u32,          // to win
+    paddles: [usize; 2],  // the top row of each
+    ball: (f64, f64),     // (row, column) of its middle
+    velocity: (f64, f64), // rows and columns moved a tick
+    scores: [u32; 2],
+    serve_to: Side,
+    wait: u32, // ticks before the next serve
+    winner: Option<Side>,
+    rng: StdRng, // for the angle of each serve
+}
+
+impl Pong {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Court [height] rows by [width] columns, played to [points] points.
+    /// The same seed always serves at the same angles
+    pub fn new(height: usize, width: usize, points: u32, seed: u64) -> Self {
+        assert!(height > PADDLE && width >= 8, "court too small for Pong");
+        let mut rng = StdRng::seed_from_u64(seed);
+        let serve_to = match rng.gen() {
+            true => Side::Left,
+            false => Side::Right,
+        };
+        let top = (height - PADDLE) / 2;
+        Self {
+            height,
+            width,
+            points,
+            paddles: [top, top],
+            ball: Self::middle(height, width),
+            velocity: (0.0, 0.0),
+            scores: [0, 0],
+            serve_to,
+            wait: SERVE_WAIT,
+            winner: None,
+            rng,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn act(&mut self, action: Action) {
+        let (side, up) = match action {
+            Action::Up(side) => (side, true),
+            Action::Down(side) => (side, false),
+        };
+        let top = &mut self.paddles[side.index()];
+        *top = match up {
+            true => top.saturating_sub(PADDLE_STEP),
+            false => (*top + PADDLE_STEP).min(self.height - PADDLE),
+        };
+    }
+
+    /// Move the ball on a tick, bouncing it off walls and paddles, and
+    /// scoring when it gets past one (then waiting a moment to serve again)
+    pub fn step(&mut self) -> Step {
+        if self.winner.is_some() {
+            return Step::Moved;
+        }
+        if self.wait > 0 {
+            self.wait -= 1;
+            if self.wait == 0 {
+                self.serve();
+            }
+            return Step::Moved;
+        }
+
+        let old = self.ball;
+        let (mut row, col) = (old.0 + self.velocity.0, old.1 + self.velocity.1);
+
+        // (off the top and bottom walls)
+        let bottom = (self.height - 1) as f64;
+        if row < 0.0 {
+            row = -row;
+            self.velocity.0 = -self.velocity.0;
+        } else if row > bottom {
+            row = 2.0 * bottom - row;
+            self.velocity.0 = -self.velocity.0;
+        }
+
+        // (off a paddle, if it's there when the ball crosses in front of it)
+        for side in [Side::Left, Side::Right] {
+            let face = self.face(side);
+            let crossed = match side {
+                Side::Left => old.1 >= face && col < face,
+                Side::Right => old.1 <= face && col > face,
+            };
+            if !crossed {
+                continue;
+            }
+            let t = (face - old.1) / (col - old.1);
+            let crossing = old.0 + (row - old.0) * t;
+            let top = self.paddles[side.index()] as f64;
+            if crossing.round() < top || crossing.round() >= top + PADDLE as f64 {
+                continue;
+            }
+            // (further from the middle of the paddle, the steeper it goes off)
+            let offset = (crossing - (top + (PADDLE as f64 - 1.0) / 2.0)) / (PADDLE as f64 / 2.0);
+            let speed = (self.velocity.1.abs() * SPEEDUP).min(MAX_SPEED);
+            self.velocity.1 = match side {
+                Side::Left => speed,
+                Side::Right => -speed,
+            };
+            self.velocity.0 = offset.clamp(-1.0, 1.0) * MAX_SLOPE * speed;
+            self.ball = (row, 2.0 * face - col);
+            return Step::Hit(side);
+        }
+
+        self.ball = (row, col);
+        let scorer = match col {
+            col if col < -0.5 => Side::Right,
+            col if col > self.width as f64 - 0.5 => Side::Left,
+            _ => return Step::Moved,
+        };
+        self.scores[scorer.index()] += 1;
+        debug!("{scorer:?} scored, {:?}", self.scores);
+        if self.scores[scorer.index()] >= self.points {
+            self.winner = Some(scorer);
+            return Step::Won(scorer);
+        }
+        self.serve_to = scorer.opposite();
+        self.ball = Self::middle(self.height, self.width);
+        self.velocity = (0.0, 0.0);
+        self.wait = SERVE_WAIT;
+        Step::Scored(scorer)
+    }
+
+    /// Top row of [side]'s paddle
+    pub fn paddle(&self, side: Side) -> usize {
+        self.paddles[side.index()]
+    }
+
+    /// (row, column) of the middle of the ball
+    pub fn ball(&self) -> (f64, f64) {
+        self.ball
+    }
+
+    /// Rows and columns the ball moves a tick
+    pub fn velocity(&self) -> (f64, f64) {
+        self.velocity
+    }
+
+    /// Column of the ball's middle when it comes off [side]'s paddle
+    pub fn face(&self, side: Side) -> f64 {
+        match side {
+            Side::Left => 2.0,
+            Side::Right => (self.width - 3) as f64,
+        }
+    }
+
+    pub fn score(&self, side: Side) -> u32 {
+        self.scores[side.index()]
+    }
+
+    /// Points to win
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+
+    /// Whether the ball's waiting in the middle to be served
+    pub fn is_serving(&self) -> bool {
+        self.wait > 0
+    }
+
+    pub fn winner(&self) -> Option<Side> {
+        self.winner
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn middle(height: usize, width: usize) -> (f64, f64) {
+        ((height / 2) as f64, (width / 2) as f64)
+    }
+
+    // send the ball off towards [serve_to], at an angle picked at random
+    fn serve(&mut self) {
+        let slope = self.rng.gen_range(-0.5..=0.5) * MAX_SLOPE;
+        self.velocity = match self.serve_to {
+            Side::Left => (slope * SERVE_SPEED, -SERVE_SPEED),
+            Side::Right => (slope * SERVE_SPEED, SERVE_SPEED),
+        };
+    }
+}
+
+impl Core for Pong {
+    type Action = Action;
+    type Outcome = ();
+    type Cell = Square;
+
+    fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Square {
+        let ball = (self.ball.0.round() as usize, self.ball.1.round() as usize);
+        if (i, j) == ball {
+            return Square::Ball;
+        }
+        let paddles = [(Side::Left, 1), (Side::Right, self.width - 2)];
+        for (side, col) in paddles {
+            let top = self.paddle(side);
+            if j == col && (top..top + PADDLE).contains(&i) {
+                return Square::Paddle(side);
+            }
+        }
+        Square::Empty
+    }
+
+    fn act(&mut self, action: Action) {
+        Pong::act(self, action)
+    }
+
+    fn tick(&mut self) {
+        self.step();
+    }
+
+    fn is_over(&self) -> bool {
+        self.winner.is_some()
+    }
+}
@@ -0,0 +1,104 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::pong::{Action, Side};
+
+/// Help lines under the court, in the current language (with the computer
+/// on the right, both sets of keys move the left paddle)
+pub fn help_text(ai: bool) -> &'static str {
+    match ai {
+        true => locale::text("pong.help_ai"),
+        false => locale::text("pong.help"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PongUIAction {
+    Wait,
+    Move(Action),
+    Pause,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, PongUIAction); 8] = [
+    (
+        KeyBinding::char('w'),
+        PongUIAction::Move(Action::Up(Side::Left)),
+    ),
+    (
+        KeyBinding::char('s'),
+        PongUIAction::Move(Action::Down(Side::Left)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Up),
+        PongUIAction::Move(Action::Up(Side::Right)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        PongUIAction::Move(Action::Down(Side::Right)),
+    ),
+    (KeyBinding::char(' '), PongUIAction::Pause),
+    (KeyBinding::char('p'), PongUIAction::Pause),
+    (KeyBinding::char('q'), PongUIAction::Quit),
+    (KeyBinding::ctrl('c'), PongUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, PongUIAction); 6] = [
+    ("left_up", PongUIAction::Move(Action::Up(Side::Left))),
+    ("left_down", PongUIAction::Move(Action::Down(Side::Left))),
+    ("right_up", PongUIAction::Move(Action::Up(Side::Right))),
+    ("right_down", PongUIAction::Move(Action::Down(Side::Right))),
+    ("pause", PongUIAction::Pause),
+    ("quit", PongUIAction::Quit),
+];
+
+pub struct PongUI {
+    pub paused: bool,
+    pub bindings: Bindings<PongUIAction>,
+}
+
+impl PongUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Starts out paused, until a paddle moves
+    pub fn new() -> Self {
+        Self {
+            paused: true,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> PongUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(PongUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> PongUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => PongUIAction::Wait,
+        }
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<PongUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
@@ -0,0 +1,436 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Player, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::ReversiConfig;
+use crate::heuristic::Heuristic;
+use crate::reversi::{MoveResult, Piece, Reversi, SIZE};
+use crate::reversiui::{self, ReversiUI, ReversiUIAction};
+
+// time each ring of pieces around a move takes to turn over
+const FLIP_TICK: Duration = Duration::from_millis(60);
+
+// terminal columns each square takes up, including the space before it
+const SQUARE_COLS: usize = 2;
+
+// lines of text around the board (column letters, gap, turn, pieces,
+// score, message)
+const STATUS_ROWS: usize = 6;
+
+// how a square is shown
+enum Shown {
+    Piece(Piece),
+    /// a piece halfway through turning over
+    Edge,
+    /// empty, and a piece could go there
+    Legal,
+    Empty,
+}
+
+/// Reversi app (the game being played, the computer player if there is
+/// one, the score over every game so far, and UI state)
+pub struct ReversiApp {
+    game: Reversi,
+    ui: ReversiUI,
+    computer: Option<Heuristic>, // none for two players at one keyboard
+    computer_piece: Piece,       // (swapping every game)
+    flipping: Option<(usize, MoveResult)>, // the last move's pieces turning over, ring by ring
+    wins: (u32, u32),            // you and the computer's, or black and white's
+    draws: u32,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last game decided against the computer
+    over: bool,
+}
+
+impl ReversiApp {
+    /// Game against [computer] playing [computer_piece], or between two
+    /// people without one
+    pub fn new(computer: Option<Heuristic>, computer_piece: Piece) -> Self {
+        Self {
+            game: Reversi::new(),
+            ui: ReversiUI::new(),
+            computer,
+            computer_piece,
+            flipping: None,
+            wins: (0, 0),
+            draws: 0,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn is_computers_turn(&self) -> bool {
+        self.computer.is_some() && self.game.turn() == self.computer_piece && !self.game.is_over()
+    }
+
+    fn new_game(&mut self) {
+        self.game = Reversi::new();
+        self.computer_piece = self.computer_piece.other();
+        self.flipping = None;
+        self.ui.set_cursor(Point(SIZE / 2 - 1, SIZE / 2 - 1));
+        self.message = "".to_string().reset();
+    }
+
+    // put a piece on [p], and start turning over what it outflanks (what
+    // the move led to only shows once they're all turned, and stays shown
+    // through the computer's reply)
+    fn place(&mut self, p: Point) {
+        let piece = self.game.turn();
+        let res = self.game.place(p);
+        debug!("{piece:?} at ({}, {}): {res:?}", p.0, p.1);
+        match res {
+            MoveResult::Placed(_) | MoveResult::Passed(_) | MoveResult::Finished(_) => {
+                self.flipping = Some((0, res));
+            }
+            MoveResult::Illegal | MoveResult::GameOver => self.handle_res(res),
+        }
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        let again = locale::text("reversi.again");
+        match res {
+            MoveResult::Finished(_) => match self.game.winner() {
+                Some(piece) if self.computer.is_none() => {
+                    bell::ring("win");
+                    match piece {
+                        Piece::Black => self.wins.0 += 1,
+                        Piece::White => self.wins.1 += 1,
+                    }
+                    let wins = locale::text("reversi.wins");
+                    self.message = format!("{} {wins} {again}", piece_name(piece))
+                        .bold()
+                        .with(theme::color(Role::Text))
+                        .on(theme::color(Role::Success));
+                }
+                Some(piece) if piece == self.computer_piece => {
+                    bell::ring("lose");
+                    self.wins.1 += 1;
+                    self.outcome = Outcome::Lost;
+                    self.message = format!("{} {again}", locale::text("reversi.lose"))
+                        .bold()
+                        .with(theme::color(Role::Text))
+                        .on(theme::color(Role::Danger));
+                }
+                Some(_) => {
+                    bell::ring("win");
+                    self.wins.0 += 1;
+                    self.outcome = Outcome::Won;
+                    self.message = format!("{} {again}", locale::text("reversi.win"))
+                        .bold()
+                        .with(theme::color(Role::Text))
+                        .on(theme::color(Role::Success));
+                }
+                None => {
+                    self.draws += 1;
+                    self.message = format!("{} {again}", locale::text("reversi.draw")).bold();
+                }
+            },
+            MoveResult::Passed(_) => {
+                let passer = piece_name(self.game.turn().other());
+                self.message = format!("{passer} {}", locale::text("reversi.pass")).bold();
+            }
+            MoveResult::Illegal => {
+                bell::ring("invalid move");
+                self.message = locale::text("reversi.illegal")
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            MoveResult::GameOver => bell::ring("invalid move"),
+            MoveResult::Placed(_) => {}
+        }
+    }
+
+    // what to show on square [p]. While the last move's pieces are turning
+    // over, those [frame] squares away or more from it haven't turned yet,
+    // and the ring just reached is edge-on
+    fn shown(&self, p: Point) -> Shown {
+        let piece = self.game.cell(p);
+        if let (Some((frame, _)), Some(piece), Some(from)) =
+            (self.flipping, piece, self.game.last_move())
+        {
+            if self.game.last_flips().contains(&p) {
+                return match distance(p, from).cmp(&frame) {
+                    Ordering::Greater => Shown::Piece(piece.other()),
+                    Ordering::Equal => Shown::Edge,
+                    Ordering::Less => Shown::Piece(piece),
+                };
+            }
+        }
+        match piece {
+            Some(piece) => Shown::Piece(piece),
+            None if self.flipping.is_none()
+                && !self.is_computers_turn()
+                && self.game.is_legal(p) =>
+            {
+                Shown::Legal
+            }
+            None => Shown::Empty,
+        }
+    }
+
+    // (styled) string for a square. The cursor is reversed
+    fn styled_square(&self, p: Point) -> StyledContent<String> {
+        let styled = match self.shown(p) {
+            Shown::Piece(piece) => piece_glyph(piece).to_string().bold(),
+            Shown::Edge => match style::color_enabled() {
+                true => "│".to_string().bold(),
+                false => "|".to_string().bold(),
+            },
+            Shown::Legal => "+".to_string().with(theme::color(Role::Marker)),
+            Shown::Empty => "·".to_string().with(theme::color(Role::Muted)),
+        };
+        if p == self.ui.get_cursor() && !self.game.is_over() && !self.is_computers_turn() {
+            return styled.reverse();
+        }
+        styled
+    }
+
+    /// Lines of the board, with letters over the columns and numbers by
+    /// the rows, each a list of (styled) pieces
+    fn board_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let muted = |s: String| s.with(theme::color(Role::Muted));
+        let letters: String = ('a'..).take(SIZE).map(|c| format!(" {c}")).collect();
+        let mut lines = vec![vec![muted(format!("  {letters}"))]];
+        for i in 0..SIZE {
+            let mut line = vec![muted(format!("{:>2}", i + 1))];
+            for j in 0..SIZE {
+                line.push(" ".to_string().reset());
+                line.push(self.styled_square(Point(i, j)));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    // which side the player is on, and whose turn it is
+    fn turn_line(&self) -> String {
+        let mut parts = Vec::new();
+        if self.computer.is_some() {
+            let you = piece_name(self.computer_piece.other());
+            parts.push(format!("{} {you}", locale::text("reversi.playing")));
+        }
+        if !self.game.is_over() {
+            let turn = piece_name(self.game.turn());
+            parts.push(format!("{turn} {}", locale::text("reversi.to_move")));
+        }
+        parts.join("   ")
+    }
+
+    // pieces each side has on the board
+    fn count_line(&self) -> String {
+        [Piece::Black, Piece::White]
+            .map(|piece| format!("{} {}", piece_name(piece), self.game.count(piece)))
+            .join("   ")
+    }
+
+    // games won by each side, and drawn
+    fn score_line(&self) -> String {
+        let (first, second) = match self.computer {
+            Some(_) => (
+                locale::text("reversi.you").to_string(),
+                locale::text("reversi.computer").to_string(),
+            ),
+            None => (piece_name(Piece::Black), piece_name(Piece::White)),
+        };
+        format!(
+            "{first} {}   {second} {}   {} {}",
+            self.wins.0,
+            self.wins.1,
+            locale::text("reversi.draws"),
+            self.draws
+        )
+    }
+}
+
+// a side's name, with the piece it plays
+fn piece_name(piece: Piece) -> String {
+    let name = match piece {
+        Piece::Black => locale::text("reversi.black"),
+        Piece::White => locale::text("reversi.white"),
+    };
+    format!("{name} ({})", piece_glyph(piece))
+}
+
+// a piece: a filled or hollow circle, or a letter without color
+fn piece_glyph(piece: Piece) -> &'static str {
+    match (piece, style::color_enabled()) {
+        (Piece::Black, true) => "●",
+        (Piece::White, true) => "○",
+        (Piece::Black, false) => "X",
+        (Piece::White, false) => "O",
+    }
+}
+
+// rings of squares out from [q] to [p]
+fn distance(Point(pi, pj): Point, Point(qi, qj): Point) -> usize {
+    pi.abs_diff(qi).max(pj.abs_diff(qj))
+}
+
+// Pretty-print
+impl fmt::Display for ReversiApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.board_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.turn_line())?;
+        write!(f, "{}\r\n", self.count_line())?;
+        write!(f, "{}\r\n", self.score_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", reversiui::help_text())
+    }
+}
+
+impl Game for ReversiApp {
+    fn name(&self) -> &'static str {
+        "reversi"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != ReversiUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            ReversiUIAction::Quit => self.over = true,
+            ReversiUIAction::NewGame => self.new_game(),
+            ReversiUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the board");
+                }
+            }
+            // (nothing goes down while pieces are turning, or on the computer's turn)
+            ReversiUIAction::Select if self.flipping.is_some() || self.is_computers_turn() => {}
+            ReversiUIAction::Select => {
+                self.message = "".to_string().reset();
+                self.place(self.ui.get_cursor());
+            }
+            ReversiUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // turn over the next ring of pieces, or let the computer move once
+    // they're all turned
+    fn tick(&mut self) {
+        if let Some((frame, res)) = self.flipping {
+            let rings = self.game.last_move().map_or(0, |from| {
+                let flips = self.game.last_flips().iter();
+                flips.map(|&p| distance(p, from)).max().unwrap_or(0)
+            });
+            match frame < rings {
+                true => self.flipping = Some((frame + 1, res)),
+                false => {
+                    self.flipping = None;
+                    self.handle_res(res);
+                }
+            }
+            self.redraw.set(true);
+            return;
+        }
+        if !self.is_computers_turn() {
+            return;
+        }
+        if let Some(p) = self
+            .computer
+            .as_mut()
+            .and_then(|computer| computer.choose_action(&self.game))
+        {
+            self.place(p);
+            self.redraw.set(true);
+        }
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(FLIP_TICK)
+    }
+
+    // the board, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = reversiui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max((SQUARE_COLS * (SIZE + 1)) as u16),
+            (SIZE + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Reversi against the computer, or two people at one keyboard
+pub fn run(args: Args) -> Result<(), String> {
+    let config: ReversiConfig = common::cli::setup(&args.common, "reversi")?;
+
+    let computer = match args.two_player || config.two_player {
+        true => None,
+        false => {
+            let seed = args.seed.unwrap_or_else(rand::random);
+            info!("reversi seed {seed}");
+            Some(Heuristic::new(seed))
+        }
+    };
+    let computer_piece = match args.second {
+        true => Piece::Black,
+        false => Piece::White,
+    };
+    let mut app = ReversiApp::new(computer, computer_piece);
+    app.ui
+        .bindings
+        .remap(&config.keys, &reversiui::ACTIONS)
+        .map_err(|e| format!("bad [reversi.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [reversi] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReversiConfig {
+    /// two people taking turns at the same keyboard, instead of playing the computer
+    pub two_player: bool,
+    /// keys for each action, e.g. select = ["space", "enter"]
+    pub keys: KeyMap,
+}
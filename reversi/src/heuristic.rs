@@ -0,0 +1,125 @@
+use common::{Core, Player, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::reversi::{Piece, Reversi, SIZE};
+
+// moves looked ahead, counting the computer's own
+const LOOKAHEAD: u32 = 3;
+
+// score of a won game, ahead of any position still being played
+const WIN: i32 = 1_000_000;
+
+// what holding each square is worth: corners can never be turned over, and
+// the squares next to them tend to give them away (one quarter of the
+// board, mirrored for the rest)
+const WEIGHTS: [[i32; SIZE / 2]; SIZE / 2] = [
+    [100, -20, 10, 5],
+    [-20, -50, -2, -2],
+    [10, -2, -1, -1],
+    [5, -2, -1, -1],
+];
+
+// worth of each move more than the other side has to choose from
+const MOBILITY: i32 = 5;
+
+/// Computer player weighing up positions by the squares each side holds
+/// (corners most of all) and how many moves each has, looking a few moves
+/// ahead. Picks at random between equally good moves
+pub struct Heuristic {
+    rng: StdRng, // the same seed always picks the same way
+}
+
+impl Heuristic {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Player<Reversi> for Heuristic {
+    fn choose_action(&mut self, game: &Reversi) -> Option<Point> {
+        if game.is_over() {
+            return None;
+        }
+        let me = game.turn();
+        let scored: Vec<_> = game
+            .legal_moves()
+            .into_iter()
+            .map(|p| {
+                let mut next = game.clone();
+                next.place(p);
+                (p, search(&next, me, LOOKAHEAD - 1, -WIN - 1, WIN + 1))
+            })
+            .collect();
+        let best = scored.iter().map(|&(_, s)| s).max()?;
+        let best_moves: Vec<_> = scored
+            .into_iter()
+            .filter(|&(_, s)| s == best)
+            .map(|(p, _)| p)
+            .collect();
+        let &p = best_moves.choose(&mut self.rng)?;
+        debug!("heuristic plays ({}, {}) for {best}", p.0, p.1);
+        Some(p)
+    }
+}
+
+// how good [game] is for [me], looking [depth] more moves ahead (alpha-beta
+// pruned, so scores outside of (alpha, beta) are only bounds). A side
+// with no move passes, so the same side can move twice running
+fn search(game: &Reversi, me: Piece, depth: u32, mut alpha: i32, mut beta: i32) -> i32 {
+    if game.is_over() {
+        return match game.winner() {
+            Some(piece) if piece == me => WIN,
+            Some(_) => -WIN,
+            None => 0,
+        };
+    }
+    if depth == 0 {
+        return evaluate(game, me);
+    }
+    let maximizing = game.turn() == me;
+    let mut best = if maximizing { -WIN - 1 } else { WIN + 1 };
+    for p in game.legal_moves() {
+        let mut next = game.clone();
+        next.place(p);
+        let score = search(&next, me, depth - 1, alpha, beta);
+        if maximizing {
+            best = best.max(score);
+            alpha = alpha.max(score);
+        } else {
+            best = best.min(score);
+            beta = beta.min(score);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+// rough worth to [me] of a position that isn't over: the squares each side
+// holds, weighted, and how many more moves [me] would have
+fn evaluate(game: &Reversi, me: Piece) -> i32 {
+    let mut score = 0;
+    for i in 0..SIZE {
+        for j in 0..SIZE {
+            let weight = WEIGHTS[i.min(SIZE - 1 - i)][j.min(SIZE - 1 - j)];
+            match game.cell(Point(i, j)) {
+                Some(piece) if piece == me => score += weight,
+                Some(_) => score -= weight,
+                None => {}
+            }
+        }
+    }
+    let moves = game.legal_moves().len() as i32;
+    match game.turn() == me {
+        true => score + MOBILITY * moves,
+        false => score - MOBILITY * moves,
+    }
+}
@@ -0,0 +1,22 @@
+//! Reversi (Othello): the game core (an 8x8 board, and the pieces each move
+//! turns over), a computer player weighing up corners and mobility, plus
+//! the crossterm frontend for playing it in a terminal, against the
+//! computer or another person
+
+mod heuristic;
+mod reversi;
+pub use heuristic::Heuristic;
+pub use reversi::{MoveResult, Piece, Reversi, SIZE};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod reversiui;
+#[cfg(feature = "terminal")]
+pub use app::{run, ReversiApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,241 @@
+use std::cmp::Ordering;
+
+use common::{Core, Point};
+use log::debug;
+
+/// Board side, in squares
+pub const SIZE: usize = 8;
+
+// directions a line of outflanked pieces can run in, as (rows, cols)
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// A player's pieces (black always goes first)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Piece {
+    Black,
+    White,
+}
+
+impl Piece {
+    /// The other player's pieces
+    pub fn other(self) -> Self {
+        match self {
+            Piece::Black => Piece::White,
+            Piece::White => Piece::Black,
+        }
+    }
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    /// the piece went down and turned over this many of the other side's
+    Placed(usize),
+    /// as Placed, but the other side has no move, so the same side goes again
+    Passed(usize),
+    /// as Placed, and neither side can move any more
+    Finished(usize),
+    /// the square's taken, or a piece there wouldn't outflank anything
+    Illegal,
+    /// the game has already ended
+    GameOver,
+}
+
+/// A game of Reversi: the pieces on the board, whose turn it is, and what
+/// the last move turned over
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reversi {
+    board: [[Option<Piece>; SIZE]; SIZE],
+    turn: Piece,
+    last_move: Option<Point>,
+    last_flips: Vec<Point>,
+}
+
+impl Reversi {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// The usual start (two pieces each, crossed in the middle), with black
+    /// to move
+    pub fn new() -> Self {
+        let mut board = [[None; SIZE]; SIZE];
+        let mid = SIZE / 2;
+        board[mid - 1][mid - 1] = Some(Piece::White);
+        board[mid][mid] = Some(Piece::White);
+        board[mid - 1][mid] = Some(Piece::Black);
+        board[mid][mid - 1] = Some(Piece::Black);
+        Self {
+            board,
+            turn: Piece::Black,
+            last_move: None,
+            last_flips: Vec::new(),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Put a piece of whoever's turn it is on [p], turning over every line
+    /// of the other side's pieces it outflanks
+    pub fn place(&mut self, p: Point) -> MoveResult {
+        if self.is_over() {
+            return MoveResult::GameOver;
+        }
+        let piece = self.turn;
+        let flips = self.flips(p, piece);
+        if flips.is_empty() {
+            return MoveResult::Illegal;
+        }
+        let Point(i, j) = p;
+        self.board[i][j] = Some(piece);
+        for &Point(fi, fj) in &flips {
+            self.board[fi][fj] = Some(piece);
+        }
+        let flipped = flips.len();
+        self.last_move = Some(p);
+        self.last_flips = flips;
+
+        if self.has_move(piece.other()) {
+            self.turn = piece.other();
+            MoveResult::Placed(flipped)
+        } else if self.has_move(piece) {
+            debug!("{:?} has no move, and passes", piece.other());
+            MoveResult::Passed(flipped)
+        } else {
+            debug!(
+                "game over at {} to {}",
+                self.count(Piece::Black),
+                self.count(Piece::White)
+            );
+            MoveResult::Finished(flipped)
+        }
+    }
+
+    /// The other side's pieces a piece of [piece] on [p] would turn over
+    /// (none if the square's taken)
+    pub fn flips(&self, p: Point, piece: Piece) -> Vec<Point> {
+        if self.cell(p).is_some() {
+            return Vec::new();
+        }
+        let mut flips = Vec::new();
+        for dir in DIRECTIONS {
+            // the other side's pieces in a row from [p], closed off by one
+            // of [piece]'s
+            let mut line = Vec::new();
+            let mut q = p;
+            while let Some(next) = offset(q, dir) {
+                match self.cell(next) {
+                    Some(other) if other != piece => line.push(next),
+                    Some(_) => {
+                        flips.append(&mut line);
+                        break;
+                    }
+                    None => break,
+                }
+                q = next;
+            }
+        }
+        flips
+    }
+
+    pub fn is_legal(&self, p: Point) -> bool {
+        !self.flips(p, self.turn).is_empty()
+    }
+
+    /// Squares whoever's turn it is could put a piece on
+    pub fn legal_moves(&self) -> Vec<Point> {
+        squares().filter(|&p| self.is_legal(p)).collect()
+    }
+
+    /// Whose turn it is (once the game's over, whoever moved last)
+    pub fn turn(&self) -> Piece {
+        self.turn
+    }
+
+    /// Pieces of [piece] on the board
+    pub fn count(&self, piece: Piece) -> usize {
+        squares().filter(|&p| self.cell(p) == Some(piece)).count()
+    }
+
+    /// Whoever has more pieces once neither side can move (None while the
+    /// game's on, or if it ends level)
+    pub fn winner(&self) -> Option<Piece> {
+        if !self.is_over() {
+            return None;
+        }
+        match self.count(Piece::Black).cmp(&self.count(Piece::White)) {
+            Ordering::Greater => Some(Piece::Black),
+            Ordering::Less => Some(Piece::White),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// Where the last piece went down
+    pub fn last_move(&self) -> Option<Point> {
+        self.last_move
+    }
+
+    /// Pieces the last move turned over
+    pub fn last_flips(&self) -> &[Point] {
+        &self.last_flips
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn has_move(&self, piece: Piece) -> bool {
+        squares().any(|p| !self.flips(p, piece).is_empty())
+    }
+}
+
+impl Default for Reversi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Core for Reversi {
+    type Action = Point;
+    type Outcome = MoveResult;
+    type Cell = Option<Piece>;
+
+    fn size(&self) -> (usize, usize) {
+        (SIZE, SIZE)
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Option<Piece> {
+        self.board[i][j]
+    }
+
+    fn act(&mut self, p: Point) -> MoveResult {
+        self.place(p)
+    }
+
+    fn is_over(&self) -> bool {
+        !self.has_move(Piece::Black) && !self.has_move(Piece::White)
+    }
+}
+
+// every square on the board, a row at a time
+fn squares() -> impl Iterator<Item = Point> {
+    (0..SIZE).flat_map(|i| (0..SIZE).map(move |j| Point(i, j)))
+}
+
+// square next to [p] in direction [dir], if that's on the board
+fn offset(Point(i, j): Point, (di, dj): (isize, isize)) -> Option<Point> {
+    let i = i.checked_add_signed(di).filter(|&i| i < SIZE)?;
+    let j = j.checked_add_signed(dj).filter(|&j| j < SIZE)?;
+    Some(Point(i, j))
+}
@@ -0,0 +1,124 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+use crate::reversi::SIZE;
+
+/// Help lines under the board, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("reversi.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReversiUIAction {
+    Wait,
+    Move(MoveDirection),
+    Select,
+    NewGame,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, ReversiUIAction); 9] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        ReversiUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        ReversiUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        ReversiUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        ReversiUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), ReversiUIAction::Select),
+    (KeyBinding::key(KeyCode::Enter), ReversiUIAction::Select),
+    (KeyBinding::char('n'), ReversiUIAction::NewGame),
+    (KeyBinding::char('q'), ReversiUIAction::Quit),
+    (KeyBinding::ctrl('c'), ReversiUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, ReversiUIAction); 7] = [
+    ("up", ReversiUIAction::Move(MoveDirection::Up)),
+    ("down", ReversiUIAction::Move(MoveDirection::Down)),
+    ("left", ReversiUIAction::Move(MoveDirection::Left)),
+    ("right", ReversiUIAction::Move(MoveDirection::Right)),
+    ("select", ReversiUIAction::Select),
+    ("new_game", ReversiUIAction::NewGame),
+    ("quit", ReversiUIAction::Quit),
+];
+
+pub struct ReversiUI {
+    cursor: Point,
+    pub bindings: Bindings<ReversiUIAction>,
+}
+
+impl ReversiUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cursor starting by the pieces in the middle
+    pub fn new() -> Self {
+        Self {
+            cursor: Point(SIZE / 2 - 1, SIZE / 2 - 1),
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> ReversiUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(ReversiUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> ReversiUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => ReversiUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a square, unless that's off the board
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let Point(i, j) = self.cursor;
+        let (i, j) = match dir {
+            MoveDirection::Up => (i.checked_sub(1), Some(j)),
+            MoveDirection::Down => (Some(i + 1).filter(|&i| i < SIZE), Some(j)),
+            MoveDirection::Left => (Some(i), j.checked_sub(1)),
+            MoveDirection::Right => (Some(i), Some(j + 1).filter(|&j| j < SIZE)),
+        };
+        let (Some(i), Some(j)) = (i, j) else {
+            return Err("already at the edge".into());
+        };
+        self.cursor = Point(i, j);
+        Ok(())
+    }
+
+    pub fn set_cursor(&mut self, p: Point) {
+        self.cursor = p;
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+}
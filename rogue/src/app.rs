@@ -0,0 +1,397 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info, warn};
+
+use common::bell;
+use common::locale;
+use common::replay;
+use common::stats::{Outcome, RogueRun, Stats};
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::RogueConfig;
+use crate::level::Tile;
+use crate::rogue::{self, Action, Item, Kind, Rogue, Square, Thing, TurnResult};
+use crate::rogueui::{self, RogueUI, RogueUIAction};
+
+// lines of text under the map (status, message)
+const STATUS_ROWS: usize = 2;
+
+/// Roguelike app (the run, the runs before it, and UI state)
+pub struct RogueApp {
+    game: Rogue,
+    ui: RogueUI,
+    runs: Vec<RogueRun>, // oldest first
+    started: SystemTime,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless the player died
+    over: bool,
+}
+
+impl RogueApp {
+    /// A run through [game]'s dungeon, logged along with [runs]
+    pub fn new(game: Rogue, runs: Vec<RogueRun>) -> Self {
+        Self {
+            game,
+            ui: RogueUI::new(),
+            runs,
+            started: SystemTime::now(),
+            message: locale::text("rogue.start").to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn take_turn(&mut self, action: Action) {
+        let res = self.game.take_turn(action);
+        debug!("{res:?}");
+        match res {
+            TurnResult::Went(events) => {
+                let role = match events.iter().any(|e| matches!(e, rogue::Event::HitBy(..))) {
+                    true => Role::Danger,
+                    false => Role::Accent,
+                };
+                self.message = narrate(&events).with(theme::color(role));
+            }
+            TurnResult::Died(events, killer) => {
+                bell::ring("lose");
+                self.over = true;
+                self.outcome = Outcome::Lost;
+                let place = self.log_run(Some(killer));
+                self.message = format!(
+                    "{}   {} {}   {} {}   {} #{place}/{}",
+                    narrate(&events),
+                    locale::text("rogue.killed_by"),
+                    monster_name(killer),
+                    locale::text("rogue.score"),
+                    self.game.score(),
+                    locale::text("rogue.ranked"),
+                    self.runs.len()
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Danger));
+            }
+            TurnResult::Invalid => {
+                bell::ring("invalid move");
+                let why = match action {
+                    Action::Descend => locale::text("rogue.no_stairs"),
+                    Action::Drink => locale::text("rogue.no_potions"),
+                    Action::Read => locale::text("rogue.no_scrolls"),
+                    Action::Move(_) | Action::Rest => "",
+                };
+                self.message = why.to_string().with(theme::color(Role::Danger));
+            }
+            TurnResult::GameOver => bell::ring("invalid move"),
+        }
+    }
+
+    // keep the run that's just ended (in the stats too, unless this is a
+    // replay of one that's been had already). Returns its place among all
+    // the runs, by score (from 1)
+    fn log_run(&mut self, killed_by: Option<Kind>) -> usize {
+        let score = self.game.score();
+        let run = RogueRun {
+            started: self
+                .started
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            turns: u64::from(self.game.turns()),
+            depth: self.game.depth(),
+            score,
+            killed_by: killed_by.map(|kind| kind.name().to_string()),
+        };
+        debug!("run over: {run:?}");
+        if !replay::is_replaying() {
+            // (like the rest of the stats, it isn't worth failing a run over)
+            if let Err(e) = Stats::add_rogue_run(run.clone()) {
+                warn!("failed to log the run: {e}");
+            }
+        }
+        let place = self.runs.iter().filter(|run| run.score > score).count() + 1;
+        self.runs.push(run);
+        place
+    }
+
+    // (styled) square: what's in sight in full color, and what's been
+    // seen before dimmed (without whatever was on it)
+    fn styled_square(&self, sq: Square) -> StyledContent<String> {
+        let tile_glyph = |tile| match tile {
+            Tile::Wall => "#",
+            Tile::Floor => ".",
+            Tile::Stairs => ">",
+        };
+        match sq {
+            Square::Unknown => " ".to_string().reset(),
+            Square::Seen(tile) if style::color_enabled() => {
+                tile_glyph(tile).to_string().with(theme::color(Role::Muted))
+            }
+            Square::Seen(tile) => tile_glyph(tile).to_string().dim(),
+            Square::Visible(Tile::Stairs, None) => {
+                ">".to_string().bold().with(theme::color(Role::Accent))
+            }
+            Square::Visible(tile, None) => {
+                tile_glyph(tile).to_string().with(theme::color(Role::Text))
+            }
+            Square::Visible(_, Some(Thing::Player)) => {
+                let role = match self.game.is_dead() {
+                    true => Role::Danger,
+                    false => Role::Accent,
+                };
+                "@".to_string().bold().with(theme::color(role))
+            }
+            Square::Visible(_, Some(Thing::Monster(kind))) => kind
+                .glyph()
+                .to_string()
+                .bold()
+                .with(theme::color(Role::Danger)),
+            Square::Visible(_, Some(Thing::Item(item))) => {
+                let (glyph, role) = match item {
+                    Item::Gold(_) => ("$", Role::Marker),
+                    Item::Potion => ("!", Role::Success),
+                    Item::Scroll => ("?", Role::Accent),
+                    Item::Weapon => (")", Role::Marker),
+                    Item::Armor => ("[", Role::Marker),
+                };
+                glyph.to_string().bold().with(theme::color(role))
+            }
+        }
+    }
+
+    // rows of the map, as (styled) squares
+    fn map_rows(&self) -> impl Iterator<Item = Vec<StyledContent<String>>> + '_ {
+        let (height, width) = self.game.size();
+        (0..height).map(move |i| {
+            (0..width)
+                .map(|j| self.styled_square(self.game.cell(Point(i, j))))
+                .collect()
+        })
+    }
+
+    // level, hit points, attack and defense, what's being carried, and
+    // the score
+    fn status(&self) -> String {
+        let (hp, max_hp) = self.game.hp();
+        format!(
+            "{} {}   {} {hp}/{max_hp}   {} {}   {} {}   !{}  ?{}  ${}   {} {}",
+            locale::text("rogue.level"),
+            self.game.depth(),
+            locale::text("rogue.hp"),
+            locale::text("rogue.attack"),
+            self.game.attack_points(),
+            locale::text("rogue.defense"),
+            self.game.defense(),
+            self.game.potions(),
+            self.game.scrolls(),
+            self.game.gold(),
+            locale::text("rogue.score"),
+            self.game.score()
+        )
+    }
+}
+
+fn monster_name(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Rat => locale::text("rogue.rat"),
+        Kind::Goblin => locale::text("rogue.goblin"),
+        Kind::Orc => locale::text("rogue.orc"),
+        Kind::Troll => locale::text("rogue.troll"),
+    }
+}
+
+// the monster, at the start of a sentence
+fn the_monster(kind: Kind) -> &'static str {
+    match kind {
+        Kind::Rat => locale::text("rogue.the_rat"),
+        Kind::Goblin => locale::text("rogue.the_goblin"),
+        Kind::Orc => locale::text("rogue.the_orc"),
+        Kind::Troll => locale::text("rogue.the_troll"),
+    }
+}
+
+// what happened during a turn, in words
+fn narrate(events: &[rogue::Event]) -> String {
+    let lines: Vec<String> = events
+        .iter()
+        .map(|&event| match event {
+            rogue::Event::Hit(kind, damage) => format!(
+                "{} {} {damage}",
+                the_monster(kind),
+                locale::text("rogue.takes")
+            ),
+            rogue::Event::Missed(kind) => {
+                format!("{} {}", the_monster(kind), locale::text("rogue.dodges"))
+            }
+            rogue::Event::Killed(kind) => {
+                format!("{} {}", the_monster(kind), locale::text("rogue.dies"))
+            }
+            rogue::Event::HitBy(kind, damage) => format!(
+                "{} {} {damage}",
+                the_monster(kind),
+                locale::text("rogue.hits_you")
+            ),
+            rogue::Event::MissedBy(kind) => {
+                format!("{} {}", the_monster(kind), locale::text("rogue.misses_you"))
+            }
+            rogue::Event::PickedUp(item) => {
+                let item = match item {
+                    Item::Gold(gold) => format!("{gold} {}", locale::text("rogue.gold")),
+                    Item::Potion => locale::text("rogue.potion").to_string(),
+                    Item::Scroll => locale::text("rogue.scroll").to_string(),
+                    Item::Weapon => locale::text("rogue.weapon").to_string(),
+                    Item::Armor => locale::text("rogue.armor").to_string(),
+                };
+                format!("{} {item}", locale::text("rogue.found"))
+            }
+            rogue::Event::Healed(hp) => format!("{} +{hp}", locale::text("rogue.healed")),
+            rogue::Event::Mapped => locale::text("rogue.mapped").to_string(),
+            rogue::Event::Descended(depth) => {
+                format!("{} {depth}", locale::text("rogue.descended"))
+            }
+        })
+        .collect();
+    lines.join("   ")
+}
+
+// Pretty-print
+impl fmt::Display for RogueApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, width) = self.game.size();
+        let edge = "─".repeat(width);
+        let wall = "│".with(theme::color(Role::Muted));
+        write!(
+            f,
+            "{}\r\n",
+            format!("┌{edge}┐").with(theme::color(Role::Muted))
+        )?;
+        for row in self.map_rows() {
+            write!(f, "{wall}")?;
+            for sq in row {
+                write!(f, "{sq}")?;
+            }
+            write!(f, "{wall}\r\n")?;
+        }
+        write!(
+            f,
+            "{}\r\n",
+            format!("└{edge}┘").with(theme::color(Role::Muted))
+        )?;
+
+        write!(f, "{}\r\n", self.status())?;
+        write!(f, "{}\r\n", self.message)?;
+        if self.over {
+            write!(f, "{}\r\n", locale::text("rogue.exit"))?;
+        } else {
+            write!(f, "{}\r\n", rogueui::help_text())?;
+        }
+        Ok(())
+    }
+}
+
+impl Game for RogueApp {
+    fn name(&self) -> &'static str {
+        "rogue"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != RogueUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            RogueUIAction::Quit => {
+                // (a run given up on is logged too, once it's under way)
+                if self.game.turns() > 0 && !self.game.is_dead() {
+                    self.log_run(None);
+                }
+                self.over = true;
+            }
+            RogueUIAction::Turn(action) => self.take_turn(action),
+            RogueUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    // the map with its walls, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.game.size();
+        let cols = (width + 2) as u16;
+        let help = rogueui::help_text();
+        (
+            cols.max(text_width(help)),
+            (height + 2 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Crawl through a dungeon with levels the size chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: RogueConfig = common::cli::setup(&args.common, "rogue")?;
+    // (a stats file that can't be read just means no runs to rank against)
+    let runs = Stats::rogue_runs().unwrap_or_else(|e| {
+        warn!("failed to read the past runs: {e}");
+        Vec::new()
+    });
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("rogue seed {seed}");
+    let game = Rogue::new(args.height as usize, args.width as usize, seed);
+    let mut app = RogueApp::new(game, runs);
+    app.ui
+        .bindings
+        .remap(&config.keys, &rogueui::ACTIONS)
+        .map_err(|e| format!("bad [rogue.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the map up until a key is pressed, unless the player quit
+    if app.outcome != Outcome::Quit {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+
+use super::RogueApp;
+use crate::rogueui;
+
+impl TuiGame for RogueApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .map_rows()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let title = format!(" {} {} ", locale::text("rogue.level"), self.game.depth());
+        let board = Board::new(rows).title(title);
+
+        let message = tui::span(&self.message);
+        let status = StatusBar::new(rogueui::help_text())
+            .line(self.status())
+            .line(message.clone());
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+
+        if self.over {
+            let lines = vec![Line::from(message), Line::from(locale::text("rogue.exit"))];
+            let title = format!(" {} ", locale::text("rogue.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
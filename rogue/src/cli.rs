@@ -0,0 +1,34 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Roguelike dungeon crawl
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on bumping into walls, dying and achievements")
+}))]
+pub struct Args {
+    /// Height of each level (in squares)
+    #[arg(long, default_value_t = 18, value_parser = clap::value_parser!(u16).range(10..))]
+    pub height: u16,
+
+    /// Width of each level (in squares)
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u16).range(30..))]
+    pub width: u16,
+
+    /// Random seed for the dungeon (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,245 @@
+use common::Point;
+use rand::Rng;
+
+// most rooms tried for on a level, and how many places tried for them
+const MAX_ROOMS: usize = 9;
+const ROOM_TRIES: usize = 200;
+
+// room sizes, inside the walls
+const ROOM_HEIGHT: (usize, usize) = (3, 6);
+const ROOM_WIDTH: (usize, usize) = (4, 12);
+
+/// What a square of the dungeon is made of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Wall,
+    Floor,
+    /// the way down to the next level
+    Stairs,
+}
+
+/// A rectangle of floor, inside its walls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Room {
+    pub top: usize,
+    pub left: usize,
+    pub height: usize,
+    pub width: usize,
+}
+
+impl Room {
+    pub fn center(&self) -> Point {
+        Point(self.top + self.height / 2, self.left + self.width / 2)
+    }
+
+    pub fn contains(&self, Point(i, j): Point) -> bool {
+        (self.top..self.top + self.height).contains(&i)
+            && (self.left..self.left + self.width).contains(&j)
+    }
+
+    /// A square of floor in the room, picked at random
+    pub fn random_point(&self, rng: &mut impl Rng) -> Point {
+        Point(
+            rng.gen_range(self.top..self.top + self.height),
+            rng.gen_range(self.left..self.left + self.width),
+        )
+    }
+
+    // whether the rooms overlap, or come within a wall of each other
+    fn is_near(&self, other: &Room) -> bool {
+        self.top <= other.top + other.height + 1
+            && other.top <= self.top + self.height + 1
+            && self.left <= other.left + other.width + 1
+            && other.left <= self.left + self.width + 1
+    }
+}
+
+/// One level of the dungeon: rooms joined up by corridors, walled in, with
+/// stairs down in the last room
+#[derive(Debug, Clone)]
+pub struct Level {
+    height: usize,
+    width: usize,
+    tiles: Vec<Tile>,
+    rooms: Vec<Room>,
+}
+
+impl Level {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Level [height] by [width], with rooms put down at random (never
+    /// fewer than two) and each joined to the one before it by a corridor
+    /// that turns once
+    pub fn generate(height: usize, width: usize, rng: &mut impl Rng) -> Self {
+        assert!(
+            height >= ROOM_HEIGHT.1 + 2 && width >= 2 * (ROOM_WIDTH.1 + 2),
+            "level too small for rooms"
+        );
+        loop {
+            let mut level = Self {
+                height,
+                width,
+                tiles: vec![Tile::Wall; height * width],
+                rooms: Vec::new(),
+            };
+            for _ in 0..ROOM_TRIES {
+                if level.rooms.len() == MAX_ROOMS {
+                    break;
+                }
+                let room_height = rng.gen_range(ROOM_HEIGHT.0..=ROOM_HEIGHT.1);
+                let room_width = rng.gen_range(ROOM_WIDTH.0..=ROOM_WIDTH.1);
+                let room = Room {
+                    top: rng.gen_range(1..height - room_height),
+                    left: rng.gen_range(1..width - room_width),
+                    height: room_height,
+                    width: room_width,
+                };
+                if level.rooms.iter().all(|other| !room.is_near(other)) {
+                    level.rooms.push(room);
+                }
+            }
+            if level.rooms.len() < 2 {
+                continue;
+            }
+            // (joined up left to right, so corridors don't criss-cross much)
+            level.rooms.sort_by_key(|room| room.left);
+            for room in level.rooms.clone() {
+                for i in room.top..room.top + room.height {
+                    for j in room.left..room.left + room.width {
+                        level.set(Point(i, j), Tile::Floor);
+                    }
+                }
+            }
+            for k in 1..level.rooms.len() {
+                let (from, to) = (level.rooms[k - 1].center(), level.rooms[k].center());
+                level.dig(from, to, rng.gen());
+            }
+            let last = level.rooms[level.rooms.len() - 1];
+            let stairs = last.random_point(rng);
+            level.set(stairs, Tile::Stairs);
+            return level;
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn tile(&self, Point(i, j): Point) -> Tile {
+        self.tiles[i * self.width + j]
+    }
+
+    pub fn rooms(&self) -> &[Room] {
+        &self.rooms
+    }
+
+    /// Whether something can stand at [p]
+    pub fn is_open(&self, p: Point) -> bool {
+        self.tile(p) != Tile::Wall
+    }
+
+    /// Squares that can be seen from [from] out to [radius] squares away:
+    /// those with a straight line to them that no wall's in the way of
+    /// (walls themselves are seen, but not past). Indexed like the tiles
+    pub fn visible_from(&self, from: Point, radius: usize) -> Vec<bool> {
+        let mut visible = vec![false; self.height * self.width];
+        let (top, bottom) = (
+            from.0.saturating_sub(radius),
+            (from.0 + radius).min(self.height - 1),
+        );
+        let (left, right) = (
+            from.1.saturating_sub(radius),
+            (from.1 + radius).min(self.width - 1),
+        );
+        for i in top..=bottom {
+            for j in left..=right {
+                let (di, dj) = (i.abs_diff(from.0), j.abs_diff(from.1));
+                if di * di + dj * dj > radius * radius {
+                    continue;
+                }
+                let line = line(from, Point(i, j));
+                // (everything up to the end has to be see-through)
+                if line[..line.len() - 1].iter().all(|&p| self.is_open(p)) {
+                    visible[i * self.width + j] = true;
+                }
+            }
+        }
+        // (and the walls round any floor in sight, so corridors show up
+        // with their sides, which the lines to them mostly miss)
+        let mut walls = Vec::new();
+        for i in top..=bottom {
+            for j in left..=right {
+                if !visible[i * self.width + j] || !self.is_open(Point(i, j)) {
+                    continue;
+                }
+                for wi in i.saturating_sub(1)..=(i + 1).min(self.height - 1) {
+                    for wj in j.saturating_sub(1)..=(j + 1).min(self.width - 1) {
+                        if !self.is_open(Point(wi, wj)) {
+                            walls.push(wi * self.width + wj);
+                        }
+                    }
+                }
+            }
+        }
+        for k in walls {
+            visible[k] = true;
+        }
+        visible
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn set(&mut self, Point(i, j): Point, tile: Tile) {
+        self.tiles[i * self.width + j] = tile;
+    }
+
+    // dig a corridor from [from] to [to], across then down (or down then
+    // across)
+    fn dig(&mut self, from: Point, to: Point, across_first: bool) {
+        let corner = match across_first {
+            true => Point(from.0, to.1),
+            false => Point(to.0, from.1),
+        };
+        for (a, b) in [(from, corner), (corner, to)] {
+            for i in a.0.min(b.0)..=a.0.max(b.0) {
+                for j in a.1.min(b.1)..=a.1.max(b.1) {
+                    if self.tile(Point(i, j)) == Tile::Wall {
+                        self.set(Point(i, j), Tile::Floor);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Squares on the straight line from [from] to [to], both ends included
+/// (Bresenham's)
+pub fn line(from: Point, to: Point) -> Vec<Point> {
+    let (mut i, mut j) = (from.0 as isize, from.1 as isize);
+    let (i1, j1) = (to.0 as isize, to.1 as isize);
+    let (di, dj) = ((i1 - i).abs(), -(j1 - j).abs());
+    let (si, sj) = ((i1 - i).signum(), (j1 - j).signum());
+    let mut err = di + dj;
+    let mut points = vec![from];
+    while (i, j) != (i1, j1) {
+        let e2 = 2 * err;
+        if e2 >= dj {
+            err += dj;
+            i += si;
+        }
+        if e2 <= di {
+            err += di;
+            j += sj;
+        }
+        points.push(Point(i as usize, j as usize));
+    }
+    points
+}
@@ -0,0 +1,22 @@
+//! Roguelike: the game core (a dungeon of rooms and corridors made up at
+//! random, level after level, with monsters to fight and things to pick
+//! up), plus the crossterm frontend for playing it in a terminal, with
+//! every run logged in the stats
+
+mod level;
+mod rogue;
+pub use level::{line, Level, Room, Tile};
+pub use rogue::{Action, Direction, Event, Item, Kind, Rogue, Square, Thing, TurnResult, SIGHT};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod rogueui;
+#[cfg(feature = "terminal")]
+pub use app::{run, RogueApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,665 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::level::{Level, Tile};
+
+/// Squares the player can see out to
+pub const SIGHT: usize = 8;
+
+// what the player starts with
+const START_HP: u32 = 20;
+const START_ATTACK: u32 = 4;
+
+// hit points a potion heals, and the most hit points gained going down
+// each level
+const POTION_HEAL: u32 = 10;
+const HP_PER_LEVEL: u32 = 2;
+
+// turns between each hit point the player heals by resting (or not)
+const REGEN_TURNS: u32 = 10;
+
+// points for each level gone down past the first
+const DEPTH_SCORE: u64 = 50;
+
+// most monsters put on a level
+const MAX_MONSTERS: usize = 12;
+
+/// One of the eight ways to step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// (rows, columns) a step goes
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (-1, 1),
+            Direction::DownLeft => (1, -1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+
+    const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+}
+
+/// The kinds of monster, from the weakest up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// scurries about, only coming for the player half the time
+    Rat,
+    /// runs off when it's badly hurt
+    Goblin,
+    /// comes straight for the player
+    Orc,
+    /// heals a hit point every turn
+    Troll,
+}
+
+impl Kind {
+    pub fn glyph(self) -> char {
+        match self {
+            Kind::Rat => 'r',
+            Kind::Goblin => 'g',
+            Kind::Orc => 'o',
+            Kind::Troll => 'T',
+        }
+    }
+
+    /// Name, for the logs and the stats
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::Rat => "rat",
+            Kind::Goblin => "goblin",
+            Kind::Orc => "orc",
+            Kind::Troll => "troll",
+        }
+    }
+
+    // (hit points, attack, defense, points for killing one, first level
+    // it's met on, how often it turns up)
+    fn stats(self) -> (u32, u32, u32, u64, u32, u32) {
+        match self {
+            Kind::Rat => (3, 2, 0, 5, 1, 4),
+            Kind::Goblin => (6, 3, 0, 10, 1, 3),
+            Kind::Orc => (12, 5, 1, 25, 3, 2),
+            Kind::Troll => (24, 8, 2, 60, 5, 1),
+        }
+    }
+
+    const ALL: [Kind; 4] = [Kind::Rat, Kind::Goblin, Kind::Orc, Kind::Troll];
+}
+
+/// Something lying on the floor, picked up by walking onto it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Item {
+    Gold(u32),
+    /// heals when drunk
+    Potion,
+    /// maps the level when read
+    Scroll,
+    /// a better weapon, for a point more attack
+    Weapon,
+    /// better armor, for a point more defense
+    Armor,
+}
+
+/// What's standing or lying on a square
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Thing {
+    Player,
+    Monster(Kind),
+    Item(Item),
+}
+
+/// What the player knows of a square of the dungeon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    /// never been seen
+    Unknown,
+    /// seen before, but out of sight now (so whatever's on it isn't known)
+    Seen(Tile),
+    /// in sight, with whatever's on it
+    Visible(Tile, Option<Thing>),
+}
+
+/// A turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// step that way, or attack the monster there
+    Move(Direction),
+    /// let a turn go by
+    Rest,
+    /// go down the stairs being stood on
+    Descend,
+    /// drink a potion
+    Drink,
+    /// read a scroll
+    Read,
+}
+
+/// Something that happened during a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// the player hit a monster for this much
+    Hit(Kind, u32),
+    Missed(Kind),
+    Killed(Kind),
+    /// a monster hit the player for this much
+    HitBy(Kind, u32),
+    MissedBy(Kind),
+    PickedUp(Item),
+    /// healed this much by a potion
+    Healed(u32),
+    /// read a scroll, and the level was mapped
+    Mapped,
+    /// went down the stairs, to this level
+    Descended(u32),
+}
+
+/// What a turn led to
+#[derive(Debug, Clone, PartialEq)]
+pub enum TurnResult {
+    /// the crawl goes on, after these
+    Went(Vec<Event>),
+    /// the player was killed by this kind of monster, after these
+    Died(Vec<Event>, Kind),
+    /// a wall's in the way, there's no stairs here, or nothing to drink
+    /// or read
+    Invalid,
+    /// the player's already dead
+    GameOver,
+}
+
+#[derive(Debug, Clone)]
+struct Monster {
+    kind: Kind,
+    pos: Point,
+    hp: u32,
+    awake: bool, // once it's been seen, it comes after the player
+}
+
+/// A dungeon crawl: level after level of rooms and corridors, with
+/// monsters to fight and things to pick up, going down until the player
+/// dies (there's only the one life)
+#[derive(Debug, Clone)]
+pub struct Rogue {
+    height: usize,
+    width: usize,
+    level: Level,
+    depth: u32,
+    player: Point,
+    hp: u32,
+    max_hp: u32,
+    attack: u32,
+    defense: u32,
+    potions: u32,
+    scrolls: u32,
+    gold: u32,
+    kill_score: u64,
+    turns: u32,
+    monsters: Vec<Monster>,
+    items: Vec<(Point, Item)>,
+    seen: Vec<bool>,
+    visible: Vec<bool>,
+    killer: Option<Kind>,
+    rng: StdRng, // for the levels, what's on them, and every fight
+}
+
+impl Rogue {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Crawl through levels [height] by [width]. The same seed always
+    /// makes the same dungeon (as long as it's played the same way)
+    pub fn new(height: usize, width: usize, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let level = Level::generate(height, width, &mut rng);
+        let mut rogue = Self {
+            height,
+            width,
+            level,
+            depth: 1,
+            player: Point(0, 0),
+            hp: START_HP,
+            max_hp: START_HP,
+            attack: START_ATTACK,
+            defense: 0,
+            potions: 0,
+            scrolls: 0,
+            gold: 0,
+            kill_score: 0,
+            turns: 0,
+            monsters: Vec::new(),
+            items: Vec::new(),
+            seen: Vec::new(),
+            visible: Vec::new(),
+            killer: None,
+            rng,
+        };
+        rogue.populate();
+        rogue
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Take a turn, then let the monsters take theirs
+    pub fn take_turn(&mut self, action: Action) -> TurnResult {
+        if self.is_dead() {
+            return TurnResult::GameOver;
+        }
+        let mut events = Vec::new();
+        match action {
+            Action::Move(dir) => {
+                let Some(to) = self.step(self.player, dir) else {
+                    return TurnResult::Invalid;
+                };
+                if !self.level.is_open(to) {
+                    return TurnResult::Invalid;
+                }
+                match self.monster_at(to) {
+                    Some(k) => self.attack(k, &mut events),
+                    None => {
+                        self.player = to;
+                        self.pick_up(&mut events);
+                    }
+                }
+            }
+            Action::Rest => {}
+            Action::Descend => {
+                if self.level.tile(self.player) != Tile::Stairs {
+                    return TurnResult::Invalid;
+                }
+                self.depth += 1;
+                self.max_hp += HP_PER_LEVEL;
+                debug!("down to level {}", self.depth);
+                self.level = Level::generate(self.height, self.width, &mut self.rng);
+                self.populate();
+                self.turns += 1;
+                // (the monsters on a new level haven't noticed yet)
+                return TurnResult::Went(vec![Event::Descended(self.depth)]);
+            }
+            Action::Drink => {
+                if self.potions == 0 {
+                    return TurnResult::Invalid;
+                }
+                self.potions -= 1;
+                let healed = POTION_HEAL.min(self.max_hp - self.hp);
+                self.hp += healed;
+                events.push(Event::Healed(healed));
+            }
+            Action::Read => {
+                if self.scrolls == 0 {
+                    return TurnResult::Invalid;
+                }
+                self.scrolls -= 1;
+                self.map_level();
+                events.push(Event::Mapped);
+            }
+        }
+
+        self.turns += 1;
+        if self.turns.is_multiple_of(REGEN_TURNS) {
+            self.hp = (self.hp + 1).min(self.max_hp);
+        }
+        self.look();
+        for k in 0..self.monsters.len() {
+            self.monster_turn(k, &mut events);
+            if let Some(killer) = self.killer {
+                debug!("killed by a {} on level {}", killer.name(), self.depth);
+                return TurnResult::Died(events, killer);
+            }
+        }
+        TurnResult::Went(events)
+    }
+
+    /// What the player knows of the square at [p]
+    pub fn square(&self, p: Point) -> Square {
+        let k = self.index(p);
+        let tile = self.level.tile(p);
+        if self.visible[k] {
+            let thing = if p == self.player {
+                Some(Thing::Player)
+            } else if let Some(m) = self.monster_at(p) {
+                Some(Thing::Monster(self.monsters[m].kind))
+            } else {
+                self.items
+                    .iter()
+                    .find(|(at, _)| *at == p)
+                    .map(|&(_, item)| Thing::Item(item))
+            };
+            Square::Visible(tile, thing)
+        } else if self.seen[k] {
+            Square::Seen(tile)
+        } else {
+            Square::Unknown
+        }
+    }
+
+    /// Level of the dungeon, from 1
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// (hit points, most hit points)
+    pub fn hp(&self) -> (u32, u32) {
+        (self.hp, self.max_hp)
+    }
+
+    pub fn attack_points(&self) -> u32 {
+        self.attack
+    }
+
+    pub fn defense(&self) -> u32 {
+        self.defense
+    }
+
+    pub fn potions(&self) -> u32 {
+        self.potions
+    }
+
+    pub fn scrolls(&self) -> u32 {
+        self.scrolls
+    }
+
+    pub fn gold(&self) -> u32 {
+        self.gold
+    }
+
+    pub fn turns(&self) -> u32 {
+        self.turns
+    }
+
+    /// Gold picked up, points for every monster killed, and more for each
+    /// level gone down
+    pub fn score(&self) -> u64 {
+        u64::from(self.gold) + self.kill_score + DEPTH_SCORE * u64::from(self.depth - 1)
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.killer.is_some()
+    }
+
+    /// What the player was killed by, once they're dead
+    pub fn killer(&self) -> Option<Kind> {
+        self.killer
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn index(&self, Point(i, j): Point) -> usize {
+        i * self.width + j
+    }
+
+    // square a step in [dir] from [p], if it's on the level
+    fn step(&self, Point(i, j): Point, dir: Direction) -> Option<Point> {
+        let (di, dj) = dir.offset();
+        let i = i.checked_add_signed(di).filter(|&i| i < self.height)?;
+        let j = j.checked_add_signed(dj).filter(|&j| j < self.width)?;
+        Some(Point(i, j))
+    }
+
+    fn monster_at(&self, p: Point) -> Option<usize> {
+        self.monsters.iter().position(|m| m.pos == p)
+    }
+
+    // put the player in the first room of a new level, with monsters in
+    // the rest of them (more, and worse, the deeper it is) and things
+    // lying about
+    fn populate(&mut self) {
+        let rooms = self.level.rooms().to_vec();
+        self.player = rooms[0].random_point(&mut self.rng);
+        self.monsters.clear();
+        self.items.clear();
+        self.seen = vec![false; self.height * self.width];
+
+        let kinds: Vec<Kind> = Kind::ALL
+            .into_iter()
+            .filter(|kind| kind.stats().4 <= self.depth)
+            .collect();
+        let count = (2 + self.depth as usize).min(MAX_MONSTERS);
+        for _ in 0..count {
+            let kind = *kinds
+                .choose_weighted(&mut self.rng, |kind| kind.stats().5)
+                .expect("there are monsters this deep");
+            let room = rooms[1..].choose(&mut self.rng).expect("there are rooms");
+            let pos = room.random_point(&mut self.rng);
+            if self.monster_at(pos).is_none() {
+                self.monsters.push(Monster {
+                    kind,
+                    pos,
+                    hp: kind.stats().0,
+                    awake: false,
+                });
+            }
+        }
+
+        let mut items = Vec::new();
+        for _ in 0..self.rng.gen_range(2..=4) {
+            items.push(Item::Gold(self.rng.gen_range(5..=15) * self.depth));
+        }
+        for (item, chance) in [
+            (Item::Potion, 0.7),
+            (Item::Scroll, 0.3),
+            (Item::Weapon, 0.2),
+            (Item::Armor, 0.2),
+        ] {
+            if self.rng.gen_bool(chance) {
+                items.push(item);
+            }
+        }
+        for item in items {
+            let room = rooms.choose(&mut self.rng).expect("there are rooms");
+            let pos = room.random_point(&mut self.rng);
+            let taken = pos == self.player || self.items.iter().any(|&(at, _)| at == pos);
+            if !taken && self.level.tile(pos) == Tile::Floor {
+                self.items.push((pos, item));
+            }
+        }
+        self.look();
+    }
+
+    // see what's in sight from where the player is now
+    fn look(&mut self) {
+        self.visible = self.level.visible_from(self.player, SIGHT);
+        for (seen, &visible) in self.seen.iter_mut().zip(&self.visible) {
+            *seen |= visible;
+        }
+    }
+
+    // show the whole level, down to the walls round the rooms and
+    // corridors
+    fn map_level(&mut self) {
+        for i in 0..self.height {
+            for j in 0..self.width {
+                let p = Point(i, j);
+                let near_open = self.level.is_open(p)
+                    || Direction::ALL
+                        .iter()
+                        .filter_map(|&dir| self.step(p, dir))
+                        .any(|q| self.level.is_open(q));
+                if near_open {
+                    let k = self.index(p);
+                    self.seen[k] = true;
+                }
+            }
+        }
+    }
+
+    // take whatever's lying where the player's standing
+    fn pick_up(&mut self, events: &mut Vec<Event>) {
+        let player = self.player;
+        let (here, rest) = self.items.iter().partition(|&&(at, _)| at == player);
+        self.items = rest;
+        for (_, item) in here {
+            match item {
+                Item::Gold(gold) => self.gold += gold,
+                Item::Potion => self.potions += 1,
+                Item::Scroll => self.scrolls += 1,
+                Item::Weapon => self.attack += 1,
+                Item::Armor => self.defense += 1,
+            }
+            events.push(Event::PickedUp(item));
+        }
+    }
+
+    // damage done by a blow of [attack] against [defense] (none's a miss)
+    fn roll(&mut self, attack: u32, defense: u32) -> u32 {
+        self.rng
+            .gen_range(1..=attack)
+            .saturating_sub(self.rng.gen_range(0..=defense))
+    }
+
+    // the player attacks the monster at index [k]
+    fn attack(&mut self, k: usize, events: &mut Vec<Event>) {
+        let kind = self.monsters[k].kind;
+        let damage = self.roll(self.attack, kind.stats().2);
+        self.monsters[k].awake = true;
+        if damage == 0 {
+            events.push(Event::Missed(kind));
+        } else if damage >= self.monsters[k].hp {
+            self.monsters.remove(k);
+            self.kill_score += kind.stats().3;
+            events.push(Event::Killed(kind));
+        } else {
+            self.monsters[k].hp -= damage;
+            events.push(Event::Hit(kind, damage));
+        }
+    }
+
+    // the monster at index [k] (if it's still there) wakes up once the
+    // player can see it, then comes for them and attacks (or wanders off,
+    // having lost sight of them)
+    fn monster_turn(&mut self, k: usize, events: &mut Vec<Event>) {
+        let Some(monster) = self.monsters.get(k).cloned() else {
+            return;
+        };
+        let (max_hp, attack, ..) = monster.kind.stats();
+        if monster.kind == Kind::Troll {
+            self.monsters[k].hp = (monster.hp + 1).min(max_hp);
+        }
+        let in_sight = self.visible[self.index(monster.pos)];
+        if in_sight {
+            self.monsters[k].awake = true;
+        }
+        if !self.monsters[k].awake {
+            return;
+        }
+
+        let wander = !in_sight || (monster.kind == Kind::Rat && self.rng.gen_bool(0.5));
+        let flee = monster.kind == Kind::Goblin && monster.hp * 3 <= max_hp;
+        if wander {
+            let dir = *Direction::ALL
+                .choose(&mut self.rng)
+                .expect("there are directions");
+            if let Some(to) = self.step(monster.pos, dir).filter(|&to| self.is_free(to)) {
+                self.monsters[k].pos = to;
+            }
+            return;
+        }
+        // (a goblin with nowhere to run fights on)
+        if flee {
+            if let Some(to) = self.best_step(monster.pos, true) {
+                self.monsters[k].pos = to;
+                return;
+            }
+        }
+        if distance(monster.pos, self.player) == 1 {
+            let damage = self.roll(attack, self.defense);
+            if damage == 0 {
+                events.push(Event::MissedBy(monster.kind));
+            } else {
+                events.push(Event::HitBy(monster.kind, damage));
+                self.hp = self.hp.saturating_sub(damage);
+                if self.hp == 0 {
+                    self.killer = Some(monster.kind);
+                }
+            }
+        } else if let Some(to) = self.best_step(monster.pos, false) {
+            self.monsters[k].pos = to;
+        }
+    }
+
+    // the step from [from] that gets a monster closest to the player (or
+    // furthest away, when [flee]ing), as long as it's better than standing
+    // still
+    fn best_step(&self, from: Point, flee: bool) -> Option<Point> {
+        let away = |p: Point| (distance(p, self.player), sq_distance(p, self.player));
+        let now = away(from);
+        let steps = Direction::ALL
+            .iter()
+            .filter_map(|&dir| self.step(from, dir))
+            .filter(|&to| self.is_free(to));
+        match flee {
+            true => steps
+                .filter(|&to| away(to) > now)
+                .max_by_key(|&to| away(to)),
+            false => steps
+                .filter(|&to| away(to) < now)
+                .min_by_key(|&to| away(to)),
+        }
+    }
+
+    // whether a monster can step onto [p]
+    fn is_free(&self, p: Point) -> bool {
+        self.level.is_open(p) && p != self.player && self.monster_at(p).is_none()
+    }
+}
+
+// steps between two squares, counting diagonal ones as one
+fn distance(a: Point, b: Point) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+fn sq_distance(a: Point, b: Point) -> usize {
+    a.0.abs_diff(b.0).pow(2) + a.1.abs_diff(b.1).pow(2)
+}
+
+impl Core for Rogue {
+    type Action = Action;
+    type Outcome = TurnResult;
+    type Cell = Square;
+
+    fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    fn cell(&self, p: Point) -> Square {
+        self.square(p)
+    }
+
+    fn act(&mut self, action: Action) -> TurnResult {
+        self.take_turn(action)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_dead()
+    }
+}
@@ -0,0 +1,154 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::rogue::{Action, Direction};
+
+/// Help lines under the map, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("rogue.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RogueUIAction {
+    Wait,
+    Turn(Action),
+    Quit,
+}
+
+// default controls (the arrow keys, or hjkl and yubn for diagonals)
+const BINDINGS: [(KeyBinding, RogueUIAction); 20] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        RogueUIAction::Turn(Action::Move(Direction::Up)),
+    ),
+    (
+        KeyBinding::char('k'),
+        RogueUIAction::Turn(Action::Move(Direction::Up)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        RogueUIAction::Turn(Action::Move(Direction::Down)),
+    ),
+    (
+        KeyBinding::char('j'),
+        RogueUIAction::Turn(Action::Move(Direction::Down)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        RogueUIAction::Turn(Action::Move(Direction::Left)),
+    ),
+    (
+        KeyBinding::char('h'),
+        RogueUIAction::Turn(Action::Move(Direction::Left)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        RogueUIAction::Turn(Action::Move(Direction::Right)),
+    ),
+    (
+        KeyBinding::char('l'),
+        RogueUIAction::Turn(Action::Move(Direction::Right)),
+    ),
+    (
+        KeyBinding::char('y'),
+        RogueUIAction::Turn(Action::Move(Direction::UpLeft)),
+    ),
+    (
+        KeyBinding::char('u'),
+        RogueUIAction::Turn(Action::Move(Direction::UpRight)),
+    ),
+    (
+        KeyBinding::char('b'),
+        RogueUIAction::Turn(Action::Move(Direction::DownLeft)),
+    ),
+    (
+        KeyBinding::char('n'),
+        RogueUIAction::Turn(Action::Move(Direction::DownRight)),
+    ),
+    (KeyBinding::char('.'), RogueUIAction::Turn(Action::Rest)),
+    (KeyBinding::char(' '), RogueUIAction::Turn(Action::Rest)),
+    (KeyBinding::char('>'), RogueUIAction::Turn(Action::Descend)),
+    (
+        KeyBinding::key(KeyCode::Enter),
+        RogueUIAction::Turn(Action::Descend),
+    ),
+    (KeyBinding::char('d'), RogueUIAction::Turn(Action::Drink)),
+    (KeyBinding::char('r'), RogueUIAction::Turn(Action::Read)),
+    (KeyBinding::char('q'), RogueUIAction::Quit),
+    (KeyBinding::ctrl('c'), RogueUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, RogueUIAction); 13] = [
+    ("up", RogueUIAction::Turn(Action::Move(Direction::Up))),
+    ("down", RogueUIAction::Turn(Action::Move(Direction::Down))),
+    ("left", RogueUIAction::Turn(Action::Move(Direction::Left))),
+    ("right", RogueUIAction::Turn(Action::Move(Direction::Right))),
+    (
+        "up_left",
+        RogueUIAction::Turn(Action::Move(Direction::UpLeft)),
+    ),
+    (
+        "up_right",
+        RogueUIAction::Turn(Action::Move(Direction::UpRight)),
+    ),
+    (
+        "down_left",
+        RogueUIAction::Turn(Action::Move(Direction::DownLeft)),
+    ),
+    (
+        "down_right",
+        RogueUIAction::Turn(Action::Move(Direction::DownRight)),
+    ),
+    ("rest", RogueUIAction::Turn(Action::Rest)),
+    ("descend", RogueUIAction::Turn(Action::Descend)),
+    ("drink", RogueUIAction::Turn(Action::Drink)),
+    ("read", RogueUIAction::Turn(Action::Read)),
+    ("quit", RogueUIAction::Quit),
+];
+
+pub struct RogueUI {
+    pub bindings: Bindings<RogueUIAction>,
+}
+
+impl RogueUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> RogueUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(RogueUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> RogueUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => RogueUIAction::Wait,
+        }
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<RogueUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
@@ -0,0 +1,312 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info, warn};
+
+use common::bell;
+use common::locale;
+use common::replay;
+use common::stats::{Outcome, Stats, HIGH_SCORES};
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::RunnerConfig;
+use crate::runner::{Runner, Square, Step};
+use crate::runnerui::{self, RunnerUI, RunnerUIAction};
+
+// time between ticks
+const TICK: Duration = Duration::from_millis(30);
+
+// high scores shown on the status line once the run's over
+const SHOWN_SCORES: usize = 5;
+
+// lines of text under the field (status, message, exit prompt)
+const STATUS_ROWS: usize = 3;
+
+/// Endless runner app (game + UI state, and the high-score table)
+pub struct RunnerApp {
+    runner: Runner,
+    ui: RunnerUI,
+    high_scores: Vec<u64>, // highest first
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless the runner crashed
+    over: bool,
+}
+
+impl RunnerApp {
+    pub fn new(height: usize, width: usize, high_scores: Vec<u64>, seed: u64) -> Self {
+        Self {
+            runner: Runner::new(height, width, seed),
+            ui: RunnerUI::new(),
+            high_scores,
+            message: locale::text("runner.start").to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn handle_step(&mut self, step: Step) {
+        match step {
+            Step::Crashed => {
+                bell::ring("crash");
+                self.over = true;
+                self.outcome = Outcome::Lost;
+                let score = self.runner.score();
+                let mut message = format!(
+                    "{} {score} {}",
+                    locale::text("runner.crash"),
+                    locale::text("runner.points")
+                );
+                match self.add_high_score(score) {
+                    Some(0) => message += &format!("  {}", locale::text("runner.new_best")),
+                    Some(place) => {
+                        message += &format!("  {} #{}", locale::text("runner.ranked"), place + 1)
+                    }
+                    None => {}
+                }
+                self.message = message
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+            }
+            Step::Moved => {}
+        }
+    }
+
+    // put [score] in the high-score table, if it makes it (keeping it in the
+    // stats, unless this is a replay of a run that's been had already).
+    // Returns the place it got
+    fn add_high_score(&mut self, score: u64) -> Option<usize> {
+        if score == 0 {
+            return None;
+        }
+        let place = self.high_scores.partition_point(|&best| best >= score);
+        if place >= HIGH_SCORES {
+            return None;
+        }
+        self.high_scores.insert(place, score);
+        self.high_scores.truncate(HIGH_SCORES);
+        debug!("high score #{} with {score}", place + 1);
+        if place == 0 {
+            bell::ring("high score");
+        }
+        if !replay::is_replaying() {
+            // (like the rest of the stats, it isn't worth failing a game over)
+            if let Err(e) = Stats::add_high_score("runner", score) {
+                warn!("failed to save the high score: {e}");
+            }
+        }
+        Some(place)
+    }
+
+    // (styled) string for a square: blocks of color, or ASCII without color
+    fn styled_square(&self, sq: Square) -> StyledContent<&'static str> {
+        let (role, plain, block) = match sq {
+            Square::Empty => return " ".reset(),
+            Square::Runner if self.over => (Role::Danger, "X", "█"),
+            Square::Runner => (Role::Accent, "#", "█"),
+            Square::Cactus => (Role::Success, "|", "█"),
+            Square::Bird(true) => (Role::Danger, "v", "▀"),
+            Square::Bird(false) => (Role::Danger, "^", "▄"),
+        };
+        if style::color_enabled() {
+            block.with(theme::color(role))
+        } else {
+            plain.bold()
+        }
+    }
+
+    // score and the best so far, plus [paused] while paused (once the run's
+    // over, the top of the high-score table instead of the best)
+    fn status(&self) -> String {
+        let mut status = format!(
+            "{} {}   ",
+            locale::text("runner.score"),
+            self.runner.score()
+        );
+        if self.over {
+            let scores: Vec<String> = self
+                .high_scores
+                .iter()
+                .take(SHOWN_SCORES)
+                .map(u64::to_string)
+                .collect();
+            status += &format!(
+                "{} {}",
+                locale::text("runner.high_scores"),
+                scores.join("  ")
+            );
+        } else {
+            let best = self.high_scores.first().copied().unwrap_or(0);
+            status += &format!("{} {best}", locale::text("runner.best"));
+        }
+        if self.ui.paused && !self.over {
+            status += &format!(" [{}]", locale::text("runner.paused"));
+        }
+        status
+    }
+
+    // rows of the field, as (styled) squares
+    fn field_rows(&self) -> impl Iterator<Item = Vec<StyledContent<&'static str>>> + '_ {
+        let (height, width) = self.runner.size();
+        (0..height).map(move |i| {
+            (0..width)
+                .map(|j| self.styled_square(self.runner.cell(Point(i, j))))
+                .collect()
+        })
+    }
+}
+
+// Pretty-print
+impl fmt::Display for RunnerApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, width) = self.runner.size();
+        let edge = "─".repeat(width);
+        let wall = "│".with(theme::color(Role::Muted));
+        write!(
+            f,
+            "{}\r\n",
+            format!("┌{edge}┐").with(theme::color(Role::Muted))
+        )?;
+        for row in self.field_rows() {
+            write!(f, "{wall}")?;
+            for sq in row {
+                write!(f, "{sq}")?;
+            }
+            write!(f, "{wall}\r\n")?;
+        }
+        // (the ground)
+        write!(
+            f,
+            "{}\r\n",
+            format!("└{}┘", "▀".repeat(width)).with(theme::color(Role::Muted))
+        )?;
+
+        write!(f, "{}\r\n", self.status())?;
+        write!(f, "{}\r\n", self.message)?;
+        if self.over {
+            write!(f, "{}\r\n", locale::text("runner.exit"))?;
+        } else {
+            write!(f, "{}\r\n", runnerui::help_text())?;
+        }
+        Ok(())
+    }
+}
+
+impl Game for RunnerApp {
+    fn name(&self) -> &'static str {
+        "runner"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != RunnerUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            RunnerUIAction::Quit => self.over = true,
+            RunnerUIAction::Pause => self.ui.paused = !self.ui.paused,
+            // jumping or ducking also gets the game going again
+            RunnerUIAction::Run(action) => {
+                self.ui.paused = false;
+                self.runner.act(action);
+            }
+            RunnerUIAction::Wait => {}
+        }
+        if !self.ui.paused && !self.over {
+            self.message = "".to_string().reset();
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {
+        if self.ui.paused || self.over {
+            return;
+        }
+        let step = self.runner.step();
+        self.handle_step(step);
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(TICK)
+    }
+
+    // the field with its walls, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.runner.size();
+        let cols = (width + 2) as u16;
+        let help = runnerui::help_text();
+        (
+            cols.max(text_width(help)),
+            (height + 2 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Run the endless runner on the field chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: RunnerConfig = common::cli::setup(&args.common, "runner")?;
+    // (a stats file that can't be read just means an empty table)
+    let high_scores = Stats::high_scores("runner").unwrap_or_else(|e| {
+        warn!("failed to read the high scores: {e}");
+        Vec::new()
+    });
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("runner seed {seed}");
+    let mut app = RunnerApp::new(args.height as usize, args.width as usize, high_scores, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &runnerui::ACTIONS)
+        .map_err(|e| format!("bad [runner.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the field up until a key is pressed, unless the player quit
+    if app.outcome != Outcome::Quit {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Endless runner
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on crashing, high scores and achievements")
+}))]
+pub struct Args {
+    /// Field height above the ground (in rows)
+    #[arg(long, default_value_t = 8, value_parser = clap::value_parser!(u16).range(6..))]
+    pub height: u16,
+
+    /// Field width (in columns)
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u16).range(20..))]
+    pub width: u16,
+
+    /// Random seed for the obstacles (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
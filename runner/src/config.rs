@@ -0,0 +1,12 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [runner] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunnerConfig {
+    /// keys for each action, e.g. jump = ["space", "k"]
+    pub keys: KeyMap,
+}
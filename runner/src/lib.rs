@@ -0,0 +1,19 @@
+//! Endless runner: the game core (a runner jumping and ducking past
+//! obstacles that scroll in faster and faster), plus the crossterm frontend
+//! for playing it in a terminal, with a high-score table kept in the stats
+
+mod runner;
+pub use runner::{Action, Runner, Square, Step, RUNNER_COL};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod runnerui;
+#[cfg(feature = "terminal")]
+pub use app::{run, RunnerApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
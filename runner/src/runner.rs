@@ -0,0 +1,303 @@
+use std::collections::VecDeque;
+
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Column the runner stays in (the world scrolls past it)
+pub const RUNNER_COL: usize = 4;
+
+// rows the runner takes up standing, and ducking
+const STANDING: usize = 2;
+const DUCKING: usize = 1;
+
+// rows a tick the runner goes up at the start of a jump, how much of that
+// gravity takes off each tick, and how fast ducking in the air drops it
+const JUMP_SPEED: f64 = 0.9;
+const GRAVITY: f64 = 0.1;
+const DROP_SPEED: f64 = 1.0;
+
+// ticks a duck lasts (a held key keeps it going, by repeating)
+const DUCK_TICKS: u32 = 20;
+
+// columns a tick the world scrolls by at the start, how much faster it gets
+// for each column run, and the fastest it gets (never more than a column a
+// tick, so nothing slips past the runner between ticks)
+const START_SPEED: f64 = 0.4;
+const ACCELERATION: f64 = 0.0002;
+const MAX_SPEED: f64 = 1.0;
+
+// columns run before birds start turning up
+const BIRDS_FROM: f64 = 300.0;
+
+// columns of clear ground between obstacles, on top of what a jump covers
+// at the speed the world's going
+const MIN_GAP: f64 = 4.0;
+
+// how much longer than that a gap can be at random: up to double at the
+// start, closing in to [MIN_SPREAD] over [SPREAD_DISTANCE] columns run
+const MAX_SPREAD: f64 = 1.0;
+const MIN_SPREAD: f64 = 0.2;
+const SPREAD_DISTANCE: f64 = 10000.0;
+
+/// What the runner can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// jump (only from the ground)
+    Jump,
+    /// duck under a bird, or drop quicker out of a jump
+    Duck,
+}
+
+/// What's on one square of the field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Empty,
+    Runner,
+    Cactus,
+    /// a bird, with its wings up or down
+    Bird(bool),
+}
+
+/// What happened on a tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Moved,
+    /// ran into something
+    Crashed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Cactus,
+    Bird,
+}
+
+// something in the runner's way: its left column (moving left as the world
+// scrolls), and the rows above the ground it takes up
+#[derive(Debug, Clone)]
+struct Obstacle {
+    kind: Kind,
+    col: f64,
+    width: usize,
+    bottom: usize,
+    height: usize,
+}
+
+/// An endless runner: obstacles scroll in from the right towards a runner
+/// who can only jump and duck, faster the further it gets
+#[derive(Debug, Clone)]
+pub struct Runner {
+    height: usize,
+    width: usize,
+    rise: f64,                     // rows above the ground the runner's feet are
+    velocity: f64,                 // rows a tick it's going up (or down, below zero)
+    ducking: u32,                  // ticks left of ducking
+    obstacles: VecDeque<Obstacle>, // left to right
+    speed: f64,                    // columns a tick
+    distance: f64,                 // columns run
+    gap: f64,                      // columns of ground before the next obstacle comes in
+    ticks: u32,
+    over: bool,
+    rng: StdRng, // for the obstacles and the gaps between them
+}
+
+impl Runner {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Field [height] rows above the ground by [width] columns, with the
+    /// runner standing at the left. The same seed always brings the same
+    /// obstacles
+    pub fn new(height: usize, width: usize, seed: u64) -> Self {
+        assert!(height >= 6 && width >= 20, "field too small for a runner");
+        Self {
+            height,
+            width,
+            rise: 0.0,
+            velocity: 0.0,
+            ducking: 0,
+            obstacles: VecDeque::new(),
+            speed: START_SPEED,
+            distance: 0.0,
+            gap: (width - RUNNER_COL) as f64 / 2.0,
+            ticks: 0,
+            over: false,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn act(&mut self, action: Action) {
+        if self.over {
+            return;
+        }
+        match action {
+            Action::Jump if self.on_ground() => {
+                self.velocity = JUMP_SPEED;
+                self.ducking = 0;
+            }
+            Action::Jump => {}
+            Action::Duck if self.on_ground() => self.ducking = DUCK_TICKS,
+            Action::Duck => self.velocity = self.velocity.min(-DROP_SPEED),
+        }
+    }
+
+    /// Move the runner and scroll the world on a tick, bringing in new
+    /// obstacles, and stopping when the runner hits one
+    pub fn step(&mut self) -> Step {
+        if self.over {
+            return Step::Crashed;
+        }
+        self.ticks += 1;
+        self.ducking = self.ducking.saturating_sub(1);
+        if !self.on_ground() || self.velocity > 0.0 {
+            self.rise = (self.rise + self.velocity).max(0.0);
+            self.velocity -= GRAVITY;
+            if self.rise == 0.0 {
+                self.velocity = 0.0;
+            }
+        }
+
+        for obstacle in self.obstacles.iter_mut() {
+            obstacle.col -= self.speed;
+        }
+        while self
+            .obstacles
+            .front()
+            .is_some_and(|o| o.col + (o.width as f64) < 0.0)
+        {
+            self.obstacles.pop_front();
+        }
+        self.gap -= self.speed;
+        if self.gap <= 0.0 {
+            self.bring_in();
+        }
+        self.distance += self.speed;
+        self.speed = (START_SPEED + ACCELERATION * self.distance).min(MAX_SPEED);
+
+        if self.obstacles.iter().any(|o| self.hits(o)) {
+            debug!("crashed at {:.0} columns", self.distance);
+            self.over = true;
+            return Step::Crashed;
+        }
+        Step::Moved
+    }
+
+    /// Columns run, counted whole
+    pub fn score(&self) -> u64 {
+        self.distance as u64
+    }
+
+    /// Columns a tick the world is scrolling by
+    pub fn speed(&self) -> f64 {
+        self.speed
+    }
+
+    pub fn on_ground(&self) -> bool {
+        self.rise == 0.0
+    }
+
+    pub fn is_ducking(&self) -> bool {
+        self.ducking > 0 && self.on_ground()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // rows above the ground the runner takes up, as (bottom, height)
+    fn runner_rows(&self) -> (usize, usize) {
+        let tall = if self.is_ducking() { DUCKING } else { STANDING };
+        (self.rise.round() as usize, tall)
+    }
+
+    // columns an obstacle takes up on screen
+    fn columns(o: &Obstacle) -> (i64, i64) {
+        let left = o.col.round() as i64;
+        (left, left + o.width as i64)
+    }
+
+    fn hits(&self, o: &Obstacle) -> bool {
+        let (left, right) = Self::columns(o);
+        let (bottom, tall) = self.runner_rows();
+        let col = RUNNER_COL as i64;
+        (left..right).contains(&col) && bottom < o.bottom + o.height && o.bottom < bottom + tall
+    }
+
+    // a new obstacle at the right edge, and the gap to leave after it (wide
+    // enough to land a jump over it and take off again, and less roomy the
+    // further the runner's got)
+    fn bring_in(&mut self) {
+        let bird = self.distance >= BIRDS_FROM && self.rng.gen_bool(0.25);
+        let obstacle = match bird {
+            // (low enough to duck under or jump, or high enough to run under)
+            true => Obstacle {
+                kind: Kind::Bird,
+                col: self.width as f64,
+                width: 2,
+                bottom: self.rng.gen_range(1..=STANDING),
+                height: 1,
+            },
+            false => Obstacle {
+                kind: Kind::Cactus,
+                col: self.width as f64,
+                width: self.rng.gen_range(1..=3),
+                bottom: 0,
+                height: self.rng.gen_range(1..=3),
+            },
+        };
+        let airtime = 2.0 * JUMP_SPEED / GRAVITY;
+        let gap = obstacle.width as f64 + MIN_GAP + self.speed * airtime;
+        let spread = (MAX_SPREAD - self.distance / SPREAD_DISTANCE).max(MIN_SPREAD);
+        self.gap = gap * self.rng.gen_range(1.0..1.0 + spread);
+        self.obstacles.push_back(obstacle);
+    }
+}
+
+impl Core for Runner {
+    type Action = Action;
+    type Outcome = ();
+    type Cell = Square;
+
+    fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// (rows count down from the top, with the ground under the bottom one)
+    fn cell(&self, Point(i, j): Point) -> Square {
+        let rise = self.height - 1 - i;
+        let (bottom, tall) = self.runner_rows();
+        if j == RUNNER_COL && (bottom..bottom + tall).contains(&rise) {
+            return Square::Runner;
+        }
+        for o in &self.obstacles {
+            let (left, right) = Self::columns(o);
+            if (left..right).contains(&(j as i64))
+                && (o.bottom..o.bottom + o.height).contains(&rise)
+            {
+                return match o.kind {
+                    Kind::Cactus => Square::Cactus,
+                    Kind::Bird => Square::Bird((self.ticks / 8).is_multiple_of(2)),
+                };
+            }
+        }
+        Square::Empty
+    }
+
+    fn act(&mut self, action: Action) {
+        Runner::act(self, action)
+    }
+
+    fn tick(&mut self) {
+        self.step();
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+}
@@ -0,0 +1,93 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::runner::Action;
+
+/// Help lines under the field, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("runner.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunnerUIAction {
+    Wait,
+    Run(Action),
+    Pause,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, RunnerUIAction); 9] = [
+    (KeyBinding::char(' '), RunnerUIAction::Run(Action::Jump)),
+    (
+        KeyBinding::key(KeyCode::Up),
+        RunnerUIAction::Run(Action::Jump),
+    ),
+    (KeyBinding::char('w'), RunnerUIAction::Run(Action::Jump)),
+    (
+        KeyBinding::key(KeyCode::Down),
+        RunnerUIAction::Run(Action::Duck),
+    ),
+    (KeyBinding::char('s'), RunnerUIAction::Run(Action::Duck)),
+    (KeyBinding::char('p'), RunnerUIAction::Pause),
+    (KeyBinding::key(KeyCode::Esc), RunnerUIAction::Pause),
+    (KeyBinding::char('q'), RunnerUIAction::Quit),
+    (KeyBinding::ctrl('c'), RunnerUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, RunnerUIAction); 4] = [
+    ("jump", RunnerUIAction::Run(Action::Jump)),
+    ("duck", RunnerUIAction::Run(Action::Duck)),
+    ("pause", RunnerUIAction::Pause),
+    ("quit", RunnerUIAction::Quit),
+];
+
+pub struct RunnerUI {
+    pub paused: bool,
+    pub bindings: Bindings<RunnerUIAction>,
+}
+
+impl RunnerUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Starts out paused, until the first jump
+    pub fn new() -> Self {
+        Self {
+            paused: true,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> RunnerUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(RunnerUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> RunnerUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => RunnerUIAction::Wait,
+        }
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<RunnerUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::style::{Color, StyledContent, Stylize};
+use crossterm::{cursor, event::Event, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{blocks, style, Core, Game, Point, TerminalGuard};
+
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use crate::cli::Args;
+use crate::config::SandConfig;
+use crate::sandbox::{Element, Sandbox};
+use crate::sandboxui::{self, SandboxUI, SandboxUIAction};
+
+// speed when neither the command line nor the config file sets one
+const DEFAULT_TICK: Duration = Duration::from_millis(30);
+
+// painting fills a square this many cells out from the cursor on each side
+const BRUSH_RADIUS: usize = 1;
+
+// lines of text under the sandbox (status bar, help)
+const STATUS_ROWS: usize = 4;
+
+// terminal (row, col) of the sandbox's top-left corner (inside a border with ratatui)
+#[cfg(not(feature = "ratatui"))]
+const GRID_ORIGIN: (u16, u16) = (0, 0);
+#[cfg(feature = "ratatui")]
+const GRID_ORIGIN: (u16, u16) = (1, 1);
+
+// without color, cells are drawn by what's in them
+fn glyph(element: Element) -> char {
+    match element {
+        Element::Empty => ' ',
+        Element::Sand => ':',
+        Element::Water => '~',
+        Element::Wall => '#',
+        Element::Fire => '^',
+    }
+}
+
+// with color, from the theme's cell colors
+fn color(element: Element) -> Color {
+    let theme = theme::current();
+    match element {
+        Element::Empty => theme.cell(0),
+        Element::Sand => theme.cell(3),
+        Element::Water => theme.cell(6),
+        Element::Wall => theme.cell(8),
+        Element::Fire => theme.cell(2),
+    }
+}
+
+fn element_name(element: Element) -> &'static str {
+    match element {
+        Element::Empty => "empty",
+        Element::Sand => "sand",
+        Element::Water => "water",
+        Element::Wall => "wall",
+        Element::Fire => "fire",
+    }
+}
+
+/// Falling-sand app (sandbox + UI state)
+pub struct SandApp {
+    sandbox: Sandbox,
+    ui: SandboxUI,
+    tick: Duration,
+    redraw: Cell<bool>, // whether anything changed since the last frame
+    over: bool,
+}
+
+impl SandApp {
+    pub fn new(sandbox: Sandbox, tick: Duration) -> Self {
+        let (height, width) = sandbox.dim();
+        Self {
+            sandbox,
+            ui: SandboxUI::new(height, width),
+            tick,
+            redraw: Cell::new(true),
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // fill the brush's square around [p]
+    fn paint(&mut self, Point(i, j): Point, element: Element) {
+        let (gridh, gridw) = self.sandbox.dim();
+        let rows = i.saturating_sub(BRUSH_RADIUS)..(i + BRUSH_RADIUS + 1).min(gridh);
+        for pi in rows {
+            let cols = j.saturating_sub(BRUSH_RADIUS)..(j + BRUSH_RADIUS + 1).min(gridw);
+            for pj in cols {
+                self.sandbox.paint(Point(pi, pj), element);
+            }
+        }
+    }
+
+    // paint around the cell drawn at a terminal position, and move the cursor there
+    fn draw(&mut self, row: u16, col: u16, element: Element) {
+        let (Some(row), Some(col)) = (
+            row.checked_sub(GRID_ORIGIN.0),
+            col.checked_sub(GRID_ORIGIN.1),
+        ) else {
+            return;
+        };
+        // (half-blocks put two cells in each character)
+        let i = if style::color_enabled() {
+            2 * row as usize
+        } else {
+            row as usize
+        };
+        let (gridh, gridw) = self.sandbox.dim();
+        if i >= gridh || col as usize >= gridw {
+            return;
+        }
+        let p = Point(i, col as usize);
+        self.paint(p, element);
+        self.ui.reset_cursor(p);
+    }
+
+    /// The sandbox, as one list of (styled) characters per terminal row
+    fn cell_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let (gridh, gridw) = self.sandbox.dim();
+        let cursor = self.ui.get_cursor();
+        if style::color_enabled() {
+            return blocks::half_blocks(gridh, gridw, |p| {
+                if p == cursor {
+                    theme::color(Role::Accent)
+                } else {
+                    color(self.sandbox.cell(p))
+                }
+            });
+        }
+        (0..gridh)
+            .map(|i| {
+                (0..gridw)
+                    .map(|j| {
+                        let chr = glyph(self.sandbox.cell(Point(i, j))).to_string();
+                        if cursor == Point(i, j) {
+                            chr.reverse()
+                        } else {
+                            chr.stylize()
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // element being painted, and how much of each there is, for the status bar
+    fn status(&self) -> String {
+        let mut status = format!("painting {}  |", element_name(self.ui.element));
+        for element in sandboxui::ELEMENTS {
+            let n = self.sandbox.count(element);
+            status += &format!("  {} {n}", element_name(element));
+        }
+        if self.ui.paused {
+            status += " [paused]";
+        }
+        status
+    }
+
+    fn rows(&self) -> usize {
+        let (gridh, _) = self.sandbox.dim();
+        if style::color_enabled() {
+            blocks::half_block_rows(gridh)
+        } else {
+            gridh
+        }
+    }
+}
+
+// Pretty-print
+impl fmt::Display for SandApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.cell_lines() {
+            for cell in line {
+                write!(f, "{cell}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+
+        // status bar
+        write!(
+            f,
+            "=== FRAME {} === {}",
+            self.sandbox.nframe(),
+            self.status()
+        )?;
+        write!(f, "\r\n{}\r\n", sandboxui::help_text())
+    }
+}
+
+impl Game for SandApp {
+    fn name(&self) -> &'static str {
+        "sand"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+
+        // any user action shows up immediately
+        if !matches!(user_action, SandboxUIAction::Wait) {
+            debug!("{user_action:?}");
+            self.redraw.set(true);
+        }
+
+        match user_action {
+            SandboxUIAction::Quit => self.over = true,
+            SandboxUIAction::Move(dir) => self.ui.move_cursor(dir),
+            SandboxUIAction::Paint => self.paint(self.ui.get_cursor(), self.ui.element),
+            SandboxUIAction::Erase => self.paint(self.ui.get_cursor(), Element::Empty),
+            SandboxUIAction::Select(element) => self.ui.element = element,
+            SandboxUIAction::NextElement => self.ui.next_element(),
+            SandboxUIAction::Draw { row, col, erase } => {
+                let element = if erase {
+                    Element::Empty
+                } else {
+                    self.ui.element
+                };
+                self.draw(row, col, element);
+            }
+            SandboxUIAction::Pause => self.ui.paused = !self.ui.paused,
+            SandboxUIAction::Clear => self.sandbox.clear(),
+            SandboxUIAction::Wait => {}
+        }
+    }
+
+    fn tick(&mut self) {
+        if self.ui.paused {
+            return;
+        }
+        self.sandbox.tick();
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.tick)
+    }
+
+    // the whole sandbox, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (_, gridw) = self.sandbox.dim();
+        let help = sandboxui::help_text();
+        let cols = text_width(help).max(gridw as u16);
+        (cols, (self.rows() + STATUS_ROWS) as u16)
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+}
+
+/// Open an empty sandbox of the size chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: SandConfig = common::cli::setup(&args.common, "sand")?;
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("sand seed {seed}");
+    let sandbox = Sandbox::new(args.height as usize, args.width as usize, seed);
+    let tick = args
+        .tick_ms
+        .or(config.tick_ms)
+        .map_or(DEFAULT_TICK, Duration::from_millis);
+    let mut app = SandApp::new(sandbox, tick);
+    app.ui
+        .bindings
+        .remap(&config.keys, &sandboxui::ACTIONS)
+        .map_err(|e| format!("bad [sand.keys] config: {e}"))?;
+
+    let _guard =
+        TerminalGuard::with_mouse().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use crate::sandboxui;
+
+use super::SandApp;
+
+impl TuiGame for SandApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .cell_lines()
+            .iter()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows).title(format!(" frame {} ", self.sandbox.nframe()));
+
+        let status_bar = StatusBar::new(sandboxui::help_text()).line(self.status());
+
+        let (board_area, status_area) = tui::split(frame.size(), status_bar.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status_bar, status_area);
+    }
+}
@@ -0,0 +1,38 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Falling sand
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on losing, winning, invalid moves and achievements")
+}))]
+pub struct Args {
+    /// Sandbox height (in cells)
+    #[arg(long, default_value_t = 36, value_parser = clap::value_parser!(u16).range(2..))]
+    pub height: u16,
+
+    /// Sandbox width (in cells)
+    #[arg(long, default_value_t = 64, value_parser = clap::value_parser!(u16).range(2..))]
+    pub width: u16,
+
+    /// Random seed (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Milliseconds between frames [default: 30]
+    #[arg(long)]
+    pub tick_ms: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
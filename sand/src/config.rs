@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [sand] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SandConfig {
+    /// milliseconds between frames
+    pub tick_ms: Option<u64>,
+    /// keys for each action, e.g. fire = "f"
+    pub keys: KeyMap,
+}
@@ -0,0 +1,19 @@
+//! Falling sand: the sandbox core (sand, water, walls and fire, moving a
+//! frame at a time), plus the crossterm frontend for playing with it in a
+//! terminal
+
+mod sandbox;
+pub use sandbox::{Edit, Element, Sandbox};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod sandboxui;
+#[cfg(feature = "terminal")]
+pub use app::{run, SandApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
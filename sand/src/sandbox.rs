@@ -0,0 +1,236 @@
+use common::{Core, Grid2D, Point};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// frames a flame lasts, at most (each one flickers out at random before then)
+const FIRE_LIFE: u8 = 30;
+
+// chance of a flame dying down by one frame, on top of the one it loses
+// every frame (so flames don't all go out at once)
+const FIRE_FLICKER: f64 = 0.3;
+
+/// What fills one cell of the sandbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Element {
+    #[default]
+    Empty,
+    /// falls, piling up in slopes, and sinks through water
+    Sand,
+    /// falls, and spreads out sideways to find its level
+    Water,
+    /// stays put
+    Wall,
+    /// rises and flickers out, and boils away water it touches
+    /// (along with itself)
+    Fire,
+}
+
+/// A change a player can make to the sandbox
+#[derive(Clone, Copy)]
+pub enum Edit {
+    /// fill a cell (painting Empty rubs it out)
+    Paint(Point, Element),
+    /// empty the whole sandbox
+    Clear,
+}
+
+/// Falling-sand sandbox (the grid, and what's in each cell)
+pub struct Sandbox {
+    grid: Grid2D<Element>,
+    fire_life: Grid2D<u8>, // frames left for the flame in each cell (fire only)
+    moved: Grid2D<bool>,   // cells that already moved this frame (scratch buffer)
+    nframe: u32,
+    rng: StdRng, // for which way things slide
+}
+
+impl Sandbox {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Empty (height x width) sandbox, walled in all round. The same seed
+    /// always makes things slide the same way
+    pub fn new(height: usize, width: usize, seed: u64) -> Self {
+        Self {
+            grid: Grid2D::new(height, width),
+            fire_life: Grid2D::new(height, width),
+            moved: Grid2D::new(height, width),
+            nframe: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Fill the cell at [p] (nothing happens outside the grid)
+    pub fn paint(&mut self, p: Point, element: Element) {
+        if let Some(cell) = self.grid.get_mut(p) {
+            *cell = element;
+            self.fire_life[p] = FIRE_LIFE;
+        }
+    }
+
+    /// Empty the whole sandbox
+    pub fn clear(&mut self) {
+        self.grid.fill(Element::Empty);
+    }
+
+    /// Move everything on by one frame. Rows are updated from the bottom up
+    /// (so falling things make room for the ones above them), scanning
+    /// left-to-right and right-to-left on alternate frames
+    pub fn tick(&mut self) {
+        let (gridh, gridw) = self.grid.dim();
+        self.moved.fill(false);
+        let rightwards = self.nframe.is_multiple_of(2);
+        for i in (0..gridh).rev() {
+            for k in 0..gridw {
+                let j = if rightwards { k } else { gridw - 1 - k };
+                let p = Point(i, j);
+                if self.moved[p] {
+                    continue;
+                }
+                match self.grid[p] {
+                    Element::Sand => self.update_sand(p),
+                    Element::Water => self.update_water(p),
+                    Element::Fire => self.update_fire(p),
+                    Element::Empty | Element::Wall => {}
+                }
+            }
+        }
+        self.nframe += 1;
+    }
+
+    /// Number of cells filled with [element]
+    pub fn count(&self, element: Element) -> usize {
+        self.grid.iter().filter(|&&e| e == element).count()
+    }
+
+    /// Number of frames run so far
+    pub fn nframe(&self) -> u32 {
+        self.nframe
+    }
+
+    /// Grid size as (height, width)
+    pub fn dim(&self) -> (usize, usize) {
+        self.grid.dim()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // cell [di] rows down and [dj] columns right of [p] (None if off the grid)
+    fn offset(&self, Point(i, j): Point, di: isize, dj: isize) -> Option<Point> {
+        let (gridh, gridw) = self.grid.dim();
+        let i = i.checked_add_signed(di).filter(|&i| i < gridh)?;
+        let j = j.checked_add_signed(dj).filter(|&j| j < gridw)?;
+        Some(Point(i, j))
+    }
+
+    // -1 or 1, for sliding left or right
+    fn coin_flip(&mut self) -> isize {
+        if self.rng.gen_bool(0.5) {
+            1
+        } else {
+            -1
+        }
+    }
+
+    // move whatever is at [p] to the first of [targets] (as (di, dj)
+    // offsets) that [can_enter] allows, swapping it with what was there.
+    // returns whether it moved
+    fn try_moves(
+        &mut self,
+        p: Point,
+        targets: &[(isize, isize)],
+        can_enter: impl Fn(Element) -> bool,
+    ) -> bool {
+        for &(di, dj) in targets {
+            let Some(q) = self.offset(p, di, dj) else {
+                continue;
+            };
+            if !can_enter(self.grid[q]) || self.moved[q] {
+                continue;
+            }
+            self.grid.swap(p.tuple(), q.tuple());
+            self.fire_life.swap(p.tuple(), q.tuple());
+            self.moved[q] = true;
+            return true;
+        }
+        false
+    }
+
+    // sand falls straight down, or slides down one side or the other,
+    // through empty cells, water and flames
+    fn update_sand(&mut self, p: Point) {
+        let side = self.coin_flip();
+        self.try_moves(p, &[(1, 0), (1, side), (1, -side)], |e| {
+            matches!(e, Element::Empty | Element::Water | Element::Fire)
+        });
+    }
+
+    // water falls like sand (but only into empty cells), and otherwise
+    // flows sideways
+    fn update_water(&mut self, p: Point) {
+        let side = self.coin_flip();
+        self.try_moves(
+            p,
+            &[(1, 0), (1, side), (1, -side), (0, side), (0, -side)],
+            |e| e == Element::Empty,
+        );
+    }
+
+    // flames flicker out after a while, boil away water next to them (going
+    // out themselves), and otherwise drift upwards
+    fn update_fire(&mut self, p: Point) {
+        let flicker = self.rng.gen_bool(FIRE_FLICKER) as u8;
+        let life = &mut self.fire_life[p];
+        *life = life.saturating_sub(1 + flicker);
+        if *life == 0 {
+            self.grid[p] = Element::Empty;
+            return;
+        }
+
+        let neighbors = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+        let water = neighbors
+            .iter()
+            .filter_map(|&(di, dj)| self.offset(p, di, dj))
+            .find(|&q| self.grid[q] == Element::Water);
+        if let Some(q) = water {
+            self.grid[p] = Element::Empty;
+            self.grid[q] = Element::Empty;
+            return;
+        }
+
+        let side = self.coin_flip();
+        self.try_moves(p, &[(-1, side), (-1, 0), (-1, -side)], |e| {
+            e == Element::Empty
+        });
+    }
+}
+
+impl Core for Sandbox {
+    type Action = Edit;
+    type Outcome = ();
+    type Cell = Element;
+
+    fn size(&self) -> (usize, usize) {
+        self.dim()
+    }
+
+    fn cell(&self, p: Point) -> Element {
+        self.grid[p]
+    }
+
+    fn act(&mut self, edit: Edit) {
+        match edit {
+            Edit::Paint(p, element) => self.paint(p, element),
+            Edit::Clear => self.clear(),
+        }
+    }
+
+    fn tick(&mut self) {
+        Sandbox::tick(self);
+    }
+}
@@ -0,0 +1,190 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+use crate::sandbox::Element;
+
+/// Help lines under the sandbox, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("sand.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SandboxUIAction {
+    Wait,
+    Move(MoveDirection),
+    Paint,
+    Erase,
+    Select(Element),
+    NextElement,
+    Draw { row: u16, col: u16, erase: bool },
+    Pause,
+    Clear,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// elements that can be painted, in the order <tab> goes through them
+pub const ELEMENTS: [Element; 4] = [Element::Sand, Element::Water, Element::Wall, Element::Fire];
+
+// default controls
+const BINDINGS: [(KeyBinding, SandboxUIAction); 16] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        SandboxUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        SandboxUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        SandboxUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        SandboxUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), SandboxUIAction::Paint),
+    (KeyBinding::char('x'), SandboxUIAction::Erase),
+    (KeyBinding::key(KeyCode::Backspace), SandboxUIAction::Erase),
+    (
+        KeyBinding::char('1'),
+        SandboxUIAction::Select(Element::Sand),
+    ),
+    (
+        KeyBinding::char('2'),
+        SandboxUIAction::Select(Element::Water),
+    ),
+    (
+        KeyBinding::char('3'),
+        SandboxUIAction::Select(Element::Wall),
+    ),
+    (
+        KeyBinding::char('4'),
+        SandboxUIAction::Select(Element::Fire),
+    ),
+    (KeyBinding::key(KeyCode::Tab), SandboxUIAction::NextElement),
+    (KeyBinding::char('p'), SandboxUIAction::Pause),
+    (KeyBinding::char('c'), SandboxUIAction::Clear),
+    (KeyBinding::char('q'), SandboxUIAction::Quit),
+    (KeyBinding::ctrl('c'), SandboxUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, SandboxUIAction); 14] = [
+    ("up", SandboxUIAction::Move(MoveDirection::Up)),
+    ("down", SandboxUIAction::Move(MoveDirection::Down)),
+    ("left", SandboxUIAction::Move(MoveDirection::Left)),
+    ("right", SandboxUIAction::Move(MoveDirection::Right)),
+    ("paint", SandboxUIAction::Paint),
+    ("erase", SandboxUIAction::Erase),
+    ("sand", SandboxUIAction::Select(Element::Sand)),
+    ("water", SandboxUIAction::Select(Element::Water)),
+    ("wall", SandboxUIAction::Select(Element::Wall)),
+    ("fire", SandboxUIAction::Select(Element::Fire)),
+    ("next_element", SandboxUIAction::NextElement),
+    ("pause", SandboxUIAction::Pause),
+    ("clear", SandboxUIAction::Clear),
+    ("quit", SandboxUIAction::Quit),
+];
+
+pub struct SandboxUI {
+    gridh: usize,
+    gridw: usize,
+    cursor: Point,
+    pub element: Element, // what painting fills cells with
+    pub paused: bool,
+    pub bindings: Bindings<SandboxUIAction>,
+}
+
+impl SandboxUI {
+    /////////////
+    // Statics //
+    /////////////
+
+    // left button draws, right button erases
+    fn match_mouse_to_action(mouse_event: MouseEvent) -> SandboxUIAction {
+        let erase =
+            match mouse_event.kind {
+                MouseEventKind::Down(MouseButton::Left)
+                | MouseEventKind::Drag(MouseButton::Left) => false,
+                MouseEventKind::Down(MouseButton::Right)
+                | MouseEventKind::Drag(MouseButton::Right) => true,
+                _ => return SandboxUIAction::Wait,
+            };
+        SandboxUIAction::Draw {
+            row: mouse_event.row,
+            col: mouse_event.column,
+            erase,
+        }
+    }
+
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            gridh: height,
+            gridw: width,
+            cursor: Point(height / 4, width / 2),
+            element: Element::Sand,
+            paused: false,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> SandboxUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(SandboxUIAction::Wait)
+    }
+
+    pub fn match_event_to_action(&self, event: Event) -> SandboxUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            Event::Mouse(mouse_event) => Self::match_mouse_to_action(mouse_event),
+            _ => SandboxUIAction::Wait,
+        }
+    }
+
+    // move cursor one cell, stopping at the grid edges
+    pub fn move_cursor(&mut self, dir: MoveDirection) {
+        let Point(i, j) = self.cursor;
+        self.cursor = match dir {
+            MoveDirection::Up => Point(i.saturating_sub(1), j),
+            MoveDirection::Down => Point((i + 1).min(self.gridh - 1), j),
+            MoveDirection::Left => Point(i, j.saturating_sub(1)),
+            MoveDirection::Right => Point(i, (j + 1).min(self.gridw - 1)),
+        };
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+
+    pub fn reset_cursor(&mut self, Point(i, j): Point) {
+        self.cursor = Point(i.min(self.gridh - 1), j.min(self.gridw - 1));
+    }
+
+    // paint with the next element along
+    pub fn next_element(&mut self) {
+        let k = ELEMENTS
+            .iter()
+            .position(|&e| e == self.element)
+            .unwrap_or(0);
+        self.element = ELEMENTS[(k + 1) % ELEMENTS.len()];
+    }
+}
@@ -0,0 +1,258 @@
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::{cursor, event::Event, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::terminal::text_width;
+use common::{Game, Point, TerminalGuard};
+
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use crate::cli::Args;
+use crate::config::SandpileConfig;
+use crate::render;
+use crate::sandpile::Sandpile;
+use crate::sandui::{self, SandUI, SandUIAction};
+
+// speed when neither the command line nor the config file sets one
+const DEFAULT_TICK: Duration = Duration::from_millis(20);
+
+// range of speeds reachable with +/-
+const MIN_TICK: Duration = Duration::from_millis(1);
+const MAX_TICK: Duration = Duration::from_secs(2);
+
+// grains dropped on each step of fast-forward
+const FAST_GRAINS: u32 = 256;
+
+// lines of text under the grid (status bar, help, message)
+const STATUS_ROWS: usize = 5;
+
+/// Sandpile app (simulation + UI state)
+pub struct SandpileApp {
+    pile: Sandpile,
+    ui: SandUI,
+    source: Point, // where grains are dropped
+    tick: Duration,
+    avalanche_start: u64, // topples before the last grain was dropped
+    message: String,
+    redraw: Cell<bool>, // whether anything changed since the last frame
+    over: bool,
+}
+
+impl SandpileApp {
+    pub fn new(pile: Sandpile, source: Point, tick: Duration) -> Self {
+        let (height, width) = pile.dim();
+        Self {
+            pile,
+            ui: SandUI::new(height, width),
+            source,
+            tick,
+            avalanche_start: 0,
+            message: String::new(),
+            redraw: Cell::new(true),
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // topple the next wave of an avalanche, or drop a grain once it's over
+    // (in fast-forward, drop a handful and let them settle straight away)
+    fn step(&mut self) {
+        if self.ui.fast {
+            self.pile.drop_grains(self.source, FAST_GRAINS);
+            self.pile.stabilize();
+        } else if self.pile.is_stable() {
+            self.drop_grain(self.source);
+        } else {
+            self.topple_wave();
+        }
+    }
+
+    fn drop_grain(&mut self, p: Point) {
+        if self.pile.is_stable() {
+            self.avalanche_start = self.pile.topples();
+        }
+        self.pile.drop_grains(p, 1);
+    }
+
+    // topple the next wave, reporting the avalanche's size once it's over
+    fn topple_wave(&mut self) {
+        self.pile.topple_wave();
+        if self.pile.is_stable() {
+            let size = self.pile.topples() - self.avalanche_start;
+            self.message = format!("last avalanche: {size} topples");
+        }
+    }
+
+    // counters, speed and mode, for the status bar
+    fn status(&self) -> String {
+        let Point(i, j) = self.source;
+        let mut status = format!(
+            "source ({i},{j})  {} topples  {} lost  {}ms/step",
+            self.pile.topples(),
+            self.pile.lost(),
+            self.tick.as_millis()
+        );
+        if self.ui.fast {
+            status += " [fast-forward]";
+        }
+        if self.ui.paused {
+            status += " [paused]";
+        }
+        status
+    }
+}
+
+// Pretty-print
+impl fmt::Display for SandpileApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in render::render(&self.pile, self.ui.get_cursor()) {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+
+        // status bar
+        write!(
+            f,
+            "\r\n=== GRAINS {} === {}",
+            self.pile.dropped(),
+            self.status()
+        )?;
+        write!(f, "\r\n{}\r\n", sandui::help_text())?;
+        write!(f, "{}\r\n", self.message)?;
+
+        Ok(())
+    }
+}
+
+impl Game for SandpileApp {
+    fn name(&self) -> &'static str {
+        "sandpile"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        let acted = !matches!(user_action, SandUIAction::Wait);
+        if acted {
+            debug!("{user_action:?}");
+        }
+
+        match user_action {
+            SandUIAction::Quit => self.over = true,
+            SandUIAction::Move(dir) => self.ui.move_cursor(dir),
+            SandUIAction::Drop => self.drop_grain(self.ui.get_cursor()),
+            SandUIAction::MoveSource => self.source = self.ui.get_cursor(),
+            SandUIAction::Pause => self.ui.paused = !self.ui.paused,
+            SandUIAction::FastForward => self.ui.fast = !self.ui.fast,
+            SandUIAction::SpeedUp => self.tick = (self.tick / 2).max(MIN_TICK),
+            SandUIAction::SlowDown => self.tick = (self.tick * 2).clamp(MIN_TICK, MAX_TICK),
+            SandUIAction::Clear => self.pile.clear(),
+            SandUIAction::Wait => {}
+        }
+
+        // any user action shows up immediately
+        if acted {
+            self.redraw.set(true);
+        }
+    }
+
+    // while paused, avalanches set off by hand still run their course
+    fn tick(&mut self) {
+        if self.ui.paused && self.pile.is_stable() {
+            return;
+        }
+        if self.ui.paused {
+            self.topple_wave();
+        } else {
+            self.step();
+        }
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.tick)
+    }
+
+    // the whole grid, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.pile.dim();
+        let cols = text_width(sandui::help_text()).max(width as u16);
+        (cols, (render::rows(height) + STATUS_ROWS) as u16)
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+}
+
+/// Run the sandpile as set up on the command line
+pub fn run(mut args: Args) -> Result<(), String> {
+    let config: SandpileConfig = common::cli::setup(&args.common, "sandpile")?;
+    args.tick_ms = args.tick_ms.or(config.tick_ms);
+
+    if args.height == 0 || args.width == 0 {
+        return Err("the grid needs at least one cell".into());
+    }
+    let source = Point(
+        args.row.unwrap_or(args.height / 2),
+        args.col.unwrap_or(args.width / 2),
+    );
+    if source.0 >= args.height || source.1 >= args.width {
+        return Err(format!(
+            "drop point ({}, {}) is outside the {}x{} grid",
+            source.0, source.1, args.height, args.width
+        ));
+    }
+
+    let mut pile = Sandpile::new(args.height, args.width);
+    if args.grains > 0 {
+        pile.drop_grains(source, args.grains);
+        pile.stabilize();
+        info!(
+            "piled up {} grains in {} topples",
+            args.grains,
+            pile.topples()
+        );
+    }
+
+    let mut app = SandpileApp::new(
+        pile,
+        source,
+        args.tick_ms.map_or(DEFAULT_TICK, Duration::from_millis),
+    );
+    app.ui
+        .bindings
+        .remap(&config.keys, &sandui::ACTIONS)
+        .map_err(|e| format!("bad [sandpile.keys] config: {e}"))?;
+    app.ui.fast = args.fast;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
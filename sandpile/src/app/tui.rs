@@ -0,0 +1,27 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use crate::render;
+use crate::sandui;
+
+use super::SandpileApp;
+
+impl TuiGame for SandpileApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = render::render(&self.pile, self.ui.get_cursor())
+            .iter()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows).title(format!(" {} grains ", self.pile.dropped()));
+
+        let status_bar = StatusBar::new(sandui::help_text())
+            .line(self.status())
+            .line(self.message.as_str());
+
+        let (board_area, status_area) = tui::split(frame.size(), status_bar.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status_bar, status_area);
+    }
+}
@@ -0,0 +1,48 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Abelian sandpile
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on losing, winning, invalid moves and achievements")
+}))]
+pub struct Args {
+    /// Grid height (in cells)
+    #[arg(long, default_value_t = 37)]
+    pub height: usize,
+
+    /// Grid width (in cells)
+    #[arg(long, default_value_t = 37)]
+    pub width: usize,
+
+    /// Row grains are dropped on [default: the middle row]
+    #[arg(long)]
+    pub row: Option<usize>,
+
+    /// Column grains are dropped on [default: the middle column]
+    #[arg(long)]
+    pub col: Option<usize>,
+
+    /// Grains to pile up (and topple) before starting, e.g. 100000 for the
+    /// classic fractal
+    #[arg(long, default_value_t = 0)]
+    pub grains: u32,
+
+    /// Start in fast-forward, dropping grains by the handful without
+    /// showing the avalanches
+    #[arg(long)]
+    pub fast: bool,
+
+    /// Milliseconds between steps [default: 20]
+    #[arg(long)]
+    pub tick_ms: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+}
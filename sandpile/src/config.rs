@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [sandpile] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SandpileConfig {
+    /// milliseconds between steps
+    pub tick_ms: Option<u64>,
+    /// keys for each action, e.g. drop = "d"
+    pub keys: KeyMap,
+}
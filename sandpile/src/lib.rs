@@ -0,0 +1,20 @@
+//! Abelian sandpile: the model (grains piling up and toppling onto their
+//! neighbors), plus the crossterm frontend for watching it in a terminal
+
+mod sandpile;
+pub use sandpile::{Sandpile, MAX_STABLE};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod render;
+#[cfg(feature = "terminal")]
+mod sandui;
+#[cfg(feature = "terminal")]
+pub use app::{run, SandpileApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
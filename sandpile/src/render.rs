@@ -0,0 +1,58 @@
+use common::theme::{self, Role};
+use common::{blocks, style, Point};
+use crossterm::style::{StyledContent, Stylize};
+
+use crate::sandpile::{Sandpile, MAX_STABLE};
+
+// without color, piles are drawn by height (with piles about to topple last)
+const HEIGHT_GLYPHS: [char; 5] = [' ', '.', ':', '#', '*'];
+
+/// Terminal rows taken up by a grid [height] cells tall
+pub fn rows(height: usize) -> usize {
+    if style::color_enabled() {
+        blocks::half_block_rows(height)
+    } else {
+        height
+    }
+}
+
+/// Draw the piles as one list of (styled) characters per terminal row,
+/// with the cell under [cursor] highlighted. In color, each character is a
+/// half-block showing two cells (the upper one in the foreground), colored
+/// by the theme's first four cell colors
+pub fn render(pile: &Sandpile, cursor: Point) -> Vec<Vec<StyledContent<String>>> {
+    if style::color_enabled() {
+        render_half_block(pile, cursor)
+    } else {
+        render_glyphs(pile, cursor)
+    }
+}
+
+fn render_half_block(pile: &Sandpile, cursor: Point) -> Vec<Vec<StyledContent<String>>> {
+    let (gridh, gridw) = pile.dim();
+    let theme = theme::current();
+    blocks::half_blocks(gridh, gridw, |p| match pile.height(p) {
+        _ if p == cursor => theme.color(Role::Accent),
+        h if h > MAX_STABLE => theme.color(Role::Danger),
+        h => theme.cell(h as u8),
+    })
+}
+
+fn render_glyphs(pile: &Sandpile, cursor: Point) -> Vec<Vec<StyledContent<String>>> {
+    let (gridh, gridw) = pile.dim();
+    (0..gridh)
+        .map(|i| {
+            (0..gridw)
+                .map(|j| {
+                    let height = pile.height(Point(i, j)).min(MAX_STABLE + 1);
+                    let glyph = HEIGHT_GLYPHS[height as usize].to_string();
+                    if cursor == Point(i, j) {
+                        glyph.reverse()
+                    } else {
+                        glyph.stylize()
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
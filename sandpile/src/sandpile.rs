@@ -0,0 +1,170 @@
+use common::{Core, Grid2D, Point};
+use log::debug;
+
+/// Tallest a pile can be without toppling
+pub const MAX_STABLE: u32 = 3;
+
+// grains a toppling pile hands to each of its (up to 4) neighbors
+const GRAINS_PER_NEIGHBOR: u32 = 1;
+
+/// Abelian sandpile on a grid whose edges grains fall off of
+pub struct Sandpile {
+    grid: Grid2D<u32>,    // grid[i,j] == # of grains piled on (i,j)
+    unstable: Vec<Point>, // every pile taller than [MAX_STABLE], once each
+    dropped: u64,
+    lost: u64, // grains that fell off the edges
+    topples: u64,
+}
+
+impl Sandpile {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Empty (height x width) grid
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            grid: Grid2D::new(height, width),
+            unstable: Vec::new(),
+            dropped: 0,
+            lost: 0,
+            topples: 0,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Add [n] grains to the pile at [p] (nothing happens outside the grid).
+    /// Taller piles are left to topple on the next wave
+    pub fn drop_grains(&mut self, p: Point, n: u32) {
+        if self.grid.get(p).is_none() {
+            return;
+        }
+        self.add(p, n);
+        self.dropped += n as u64;
+    }
+
+    /// Topple every pile that's too tall, all at once (grains they hand on
+    /// only topple their neighbors on the next wave). Returns the number of
+    /// piles that toppled
+    pub fn topple_wave(&mut self) -> usize {
+        let toppling = std::mem::take(&mut self.unstable);
+        for &p in &toppling {
+            self.topple(p);
+        }
+        toppling.len()
+    }
+
+    /// Topple piles until none are too tall. Since the order piles topple
+    /// in doesn't change where things end up, they're toppled one at a time
+    /// (which is much faster than in waves)
+    pub fn stabilize(&mut self) {
+        let topples = self.topples;
+        while let Some(p) = self.unstable.pop() {
+            self.topple(p);
+        }
+        debug!("stabilized after {} topples", self.topples - topples);
+    }
+
+    /// Whether no pile is too tall
+    pub fn is_stable(&self) -> bool {
+        self.unstable.is_empty()
+    }
+
+    /// Empty the grid (the counters carry on)
+    pub fn clear(&mut self) {
+        self.grid.fill(0);
+        self.unstable.clear();
+    }
+
+    /// Grains on the pile at [p]
+    pub fn height(&self, p: Point) -> u32 {
+        self.grid[p]
+    }
+
+    /// Grains dropped so far
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    /// Grains that have fallen off the edges so far
+    pub fn lost(&self) -> u64 {
+        self.lost
+    }
+
+    /// Number of times a pile has toppled (a pile toppling several times
+    /// over at once counts each time)
+    pub fn topples(&self) -> u64 {
+        self.topples
+    }
+
+    /// Grid size as (height, width)
+    pub fn dim(&self) -> (usize, usize) {
+        self.grid.dim()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // add grains to the pile at [p], noting if that makes it unstable
+    // (piles that already were are noted down already)
+    fn add(&mut self, p: Point, n: u32) {
+        let pile = &mut self.grid[p];
+        let was_stable = *pile <= MAX_STABLE;
+        *pile += n;
+        if was_stable && *pile > MAX_STABLE {
+            self.unstable.push(p);
+        }
+    }
+
+    // hand out all the grains a pile can spare to its neighbors, which may
+    // make them unstable in turn
+    fn topple(&mut self, p: Point) {
+        let times = self.grid[p] / (MAX_STABLE + 1);
+        self.grid[p] %= MAX_STABLE + 1;
+        self.topples += times as u64;
+
+        let grains = times * GRAINS_PER_NEIGHBOR;
+        let (gridh, gridw) = self.grid.dim();
+        let Point(i, j) = p;
+        let neighbors = [
+            i.checked_sub(1).map(|i| Point(i, j)),
+            (i + 1 < gridh).then_some(Point(i + 1, j)),
+            j.checked_sub(1).map(|j| Point(i, j)),
+            (j + 1 < gridw).then_some(Point(i, j + 1)),
+        ];
+        for q in neighbors {
+            match q {
+                Some(q) => self.add(q, grains),
+                None => self.lost += grains as u64,
+            }
+        }
+    }
+}
+
+impl Core for Sandpile {
+    /// drop a grain on a pile
+    type Action = Point;
+    type Outcome = ();
+    /// height of a pile
+    type Cell = u32;
+
+    fn size(&self) -> (usize, usize) {
+        self.dim()
+    }
+
+    fn cell(&self, p: Point) -> u32 {
+        self.height(p)
+    }
+
+    fn act(&mut self, p: Point) {
+        self.drop_grains(p, 1);
+    }
+
+    fn tick(&mut self) {
+        self.topple_wave();
+    }
+}
@@ -0,0 +1,135 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+/// Help lines under the grid, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("sandpile.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SandUIAction {
+    Wait,
+    Move(MoveDirection),
+    Drop,
+    MoveSource,
+    Pause,
+    FastForward,
+    SpeedUp,
+    SlowDown,
+    Clear,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, SandUIAction); 14] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        SandUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        SandUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        SandUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        SandUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), SandUIAction::Drop),
+    (KeyBinding::key(KeyCode::Enter), SandUIAction::MoveSource),
+    (KeyBinding::char('p'), SandUIAction::Pause),
+    (KeyBinding::char('f'), SandUIAction::FastForward),
+    (KeyBinding::char('+'), SandUIAction::SpeedUp),
+    (KeyBinding::char('='), SandUIAction::SpeedUp),
+    (KeyBinding::char('-'), SandUIAction::SlowDown),
+    (KeyBinding::char('c'), SandUIAction::Clear),
+    (KeyBinding::char('q'), SandUIAction::Quit),
+    (KeyBinding::ctrl('c'), SandUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, SandUIAction); 12] = [
+    ("up", SandUIAction::Move(MoveDirection::Up)),
+    ("down", SandUIAction::Move(MoveDirection::Down)),
+    ("left", SandUIAction::Move(MoveDirection::Left)),
+    ("right", SandUIAction::Move(MoveDirection::Right)),
+    ("drop", SandUIAction::Drop),
+    ("move_source", SandUIAction::MoveSource),
+    ("pause", SandUIAction::Pause),
+    ("fast_forward", SandUIAction::FastForward),
+    ("speed_up", SandUIAction::SpeedUp),
+    ("slow_down", SandUIAction::SlowDown),
+    ("clear", SandUIAction::Clear),
+    ("quit", SandUIAction::Quit),
+];
+
+pub struct SandUI {
+    gridh: usize,
+    gridw: usize,
+    cursor: Point,
+    pub paused: bool,
+    pub fast: bool, // fast-forward: drop grains by the handful, and skip the avalanches
+    pub bindings: Bindings<SandUIAction>,
+}
+
+impl SandUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(height: usize, width: usize) -> Self {
+        Self {
+            gridh: height,
+            gridw: width,
+            cursor: Point(height / 2, width / 2),
+            paused: false,
+            fast: false,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> SandUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(SandUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> SandUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => SandUIAction::Wait,
+        }
+    }
+
+    // move cursor one cell, stopping at the grid edges
+    pub fn move_cursor(&mut self, dir: MoveDirection) {
+        let Point(i, j) = self.cursor;
+        self.cursor = match dir {
+            MoveDirection::Up => Point(i.saturating_sub(1), j),
+            MoveDirection::Down => Point((i + 1).min(self.gridh - 1), j),
+            MoveDirection::Left => Point(i, j.saturating_sub(1)),
+            MoveDirection::Right => Point(i, (j + 1).min(self.gridw - 1)),
+        };
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+}
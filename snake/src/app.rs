@@ -0,0 +1,278 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::SnakeConfig;
+use crate::snake::{Snake, Square, Step};
+use crate::snakeui::{self, SnakeUI, SnakeUIAction};
+
+// speed when neither the command line nor the config file sets one
+const DEFAULT_TICK: Duration = Duration::from_millis(150);
+
+// each bit of food makes the next steps this much shorter, down to [MIN_TICK]
+const SPEEDUP: f64 = 0.96;
+const MIN_TICK: Duration = Duration::from_millis(50);
+
+// terminal columns each square takes up (so squares come out roughly square)
+const SQUARE_COLS: usize = 2;
+
+// lines of text under the board (status, message, exit prompt)
+const STATUS_ROWS: usize = 3;
+
+/// Snake app (game + UI state)
+pub struct SnakeApp {
+    snake: Snake,
+    ui: SnakeUI,
+    start_tick: Duration, // time between steps before eating anything
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless the snake crashed or filled the board
+    over: bool,
+}
+
+impl SnakeApp {
+    pub fn new(height: usize, width: usize, start_tick: Duration, seed: u64) -> Self {
+        Self {
+            snake: Snake::new(height, width, seed),
+            ui: SnakeUI::new(),
+            start_tick,
+            message: locale::text("snake.start").to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // time between steps, shorter the more the snake has eaten
+    fn step_time(&self) -> Duration {
+        let speedup = SPEEDUP.powi(self.snake.score() as i32);
+        self.start_tick
+            .mul_f64(speedup)
+            .max(MIN_TICK.min(self.start_tick))
+    }
+
+    fn handle_step(&mut self, step: Step) {
+        match step {
+            Step::Crashed => {
+                bell::ring("crash");
+                self.over = true;
+                self.outcome = Outcome::Lost;
+                self.message = locale::text("snake.crash")
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+            }
+            Step::Filled => {
+                bell::ring("win");
+                self.over = true;
+                self.outcome = Outcome::Won;
+                self.message = locale::text("snake.win")
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            Step::Ate => debug!("ate, score {}", self.snake.score()),
+            Step::Moved => {}
+        }
+    }
+
+    // (styled) string for a square: blocks of color, or ASCII without color
+    fn styled_square(&self, sq: Square) -> StyledContent<&'static str> {
+        let (role, plain) = match sq {
+            Square::Empty => return "  ".reset(),
+            Square::Head => (Role::Accent, "@@"),
+            Square::Body => (Role::Success, "[]"),
+            Square::Food => (Role::Danger, "()"),
+        };
+        if style::color_enabled() {
+            "██".with(theme::color(role))
+        } else {
+            plain.bold()
+        }
+    }
+
+    // score, length and speed, plus [paused] while paused
+    fn status(&self) -> String {
+        let mut status = format!(
+            "{} {}   {} {}   {}ms/step",
+            locale::text("snake.score"),
+            self.snake.score(),
+            locale::text("snake.length"),
+            self.snake.length(),
+            self.step_time().as_millis()
+        );
+        if self.ui.paused && !self.over {
+            status += &format!(" [{}]", locale::text("snake.paused"));
+        }
+        status
+    }
+
+    // rows of the board, as (styled) squares
+    fn board_rows(&self) -> impl Iterator<Item = Vec<StyledContent<&'static str>>> + '_ {
+        let (height, width) = self.snake.size();
+        (0..height).map(move |i| {
+            (0..width)
+                .map(|j| self.styled_square(self.snake.cell(Point(i, j))))
+                .collect()
+        })
+    }
+}
+
+// Pretty-print
+impl fmt::Display for SnakeApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, width) = self.snake.size();
+        let edge = "─".repeat(SQUARE_COLS * width);
+        let wall = "│".with(theme::color(Role::Muted));
+        write!(
+            f,
+            "{}\r\n",
+            format!("┌{edge}┐").with(theme::color(Role::Muted))
+        )?;
+        for row in self.board_rows() {
+            write!(f, "{wall}")?;
+            for sq in row {
+                write!(f, "{sq}")?;
+            }
+            write!(f, "{wall}\r\n")?;
+        }
+        write!(
+            f,
+            "{}\r\n",
+            format!("└{edge}┘").with(theme::color(Role::Muted))
+        )?;
+
+        write!(f, "{}\r\n", self.status())?;
+        write!(f, "{}\r\n", self.message)?;
+        if self.over {
+            write!(f, "{}\r\n", locale::text("snake.exit"))?;
+        } else {
+            write!(f, "{}\r\n", snakeui::help_text())?;
+        }
+        Ok(())
+    }
+}
+
+impl Game for SnakeApp {
+    fn name(&self) -> &'static str {
+        "snake"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != SnakeUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            SnakeUIAction::Quit => self.over = true,
+            SnakeUIAction::Pause => self.ui.paused = !self.ui.paused,
+            // turning also gets the game going again
+            SnakeUIAction::Turn(dir) => {
+                self.ui.paused = false;
+                self.snake.act(dir);
+            }
+            SnakeUIAction::Wait => {}
+        }
+        if !self.ui.paused {
+            self.message = "".to_string().reset();
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {
+        if self.ui.paused || self.over {
+            return;
+        }
+        let step = self.snake.step();
+        self.handle_step(step);
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.step_time())
+    }
+
+    // the board with its walls, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.snake.size();
+        let cols = (SQUARE_COLS * width + 2) as u16;
+        let help = snakeui::help_text();
+        (
+            cols.max(text_width(help)),
+            (height + 2 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Snake on the board chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: SnakeConfig = common::cli::setup(&args.common, "snake")?;
+    let tick = args
+        .tick_ms
+        .or(config.tick_ms)
+        .map_or(DEFAULT_TICK, Duration::from_millis);
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("snake seed {seed}");
+    let mut app = SnakeApp::new(args.height as usize, args.width as usize, tick, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &snakeui::ACTIONS)
+        .map_err(|e| format!("bad [snake.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the board up until a key is pressed, unless the player quit
+    if app.outcome != Outcome::Quit {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+
+use super::SnakeApp;
+use crate::snakeui;
+
+impl TuiGame for SnakeApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .board_rows()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let title = format!(" {} {} ", locale::text("snake.score"), self.snake.score());
+        let board = Board::new(rows).title(title);
+
+        let message = tui::span(&self.message);
+        let status = StatusBar::new(snakeui::help_text())
+            .line(self.status())
+            .line(message.clone());
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+
+        if self.over {
+            let mut lines = vec![Line::from(locale::text("snake.exit"))];
+            if !message.content.is_empty() {
+                lines.insert(0, Line::from(message));
+            }
+            let title = format!(" {} ", locale::text("snake.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Snake
+#[derive(Parser)]
+pub struct Args {
+    /// Board height (in squares)
+    #[arg(long, default_value_t = 16, value_parser = clap::value_parser!(u16).range(3..))]
+    pub height: u16,
+
+    /// Board width (in squares)
+    #[arg(long, default_value_t = 24, value_parser = clap::value_parser!(u16).range(5..))]
+    pub width: u16,
+
+    /// Milliseconds between steps at the start (the snake speeds up as it
+    /// eats) [default: 150]
+    #[arg(long)]
+    pub tick_ms: Option<u64>,
+
+    /// Random seed for placing the food (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
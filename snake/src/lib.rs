@@ -0,0 +1,18 @@
+//! Snake: the game core (a snake on a walled-in board, and its food), plus
+//! the crossterm frontend for playing it in a terminal
+
+mod snake;
+pub use snake::{Direction, Snake, Square, Step};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod snakeui;
+#[cfg(feature = "terminal")]
+pub use app::{run, SnakeApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
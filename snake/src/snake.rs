@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// turns remembered ahead of the snake, so quick double turns aren't lost
+const MAX_QUEUED_TURNS: usize = 2;
+
+// squares the snake grows by for each bit of food
+const GROWTH: u32 = 1;
+
+/// Direction the snake's head is moving in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// What's on one square of the board
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Square {
+    Empty,
+    Head,
+    Body,
+    Food,
+}
+
+/// What happened on a step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    Moved,
+    Ate,
+    /// ran into a wall or itself
+    Crashed,
+    /// the snake fills the whole board
+    Filled,
+}
+
+/// A snake on a walled-in board, and the food it's after
+pub struct Snake {
+    height: usize,
+    width: usize,
+    body: VecDeque<Point>, // head first
+    dir: Direction,
+    turns: VecDeque<Direction>, // turns to take on the next steps
+    food: Option<Point>,        // (none once the board is full)
+    growing: u32,               // squares left to grow by
+    score: u32,
+    over: bool,
+    rng: StdRng, // for placing food
+}
+
+impl Snake {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Snake three squares long in the middle of a (height x width) board,
+    /// heading right. The same seed always puts the food in the same places
+    pub fn new(height: usize, width: usize, seed: u64) -> Self {
+        assert!(height >= 3 && width >= 5, "board too small for a snake");
+        let (i, j) = (height / 2, width / 2);
+        let mut snake = Self {
+            height,
+            width,
+            body: (0..3).map(|k| Point(i, j - k)).collect(),
+            dir: Direction::Right,
+            turns: VecDeque::new(),
+            food: None,
+            growing: 0,
+            score: 0,
+            over: false,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        snake.place_food();
+        snake
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Head for [dir] from the next step on (turning straight back is ignored)
+    pub fn turn(&mut self, dir: Direction) {
+        let last = self.turns.back().copied().unwrap_or(self.dir);
+        if dir == last || dir == last.opposite() || self.turns.len() == MAX_QUEUED_TURNS {
+            return;
+        }
+        self.turns.push_back(dir);
+    }
+
+    /// Move the snake forward one square
+    pub fn step(&mut self) -> Step {
+        if self.over {
+            return Step::Crashed;
+        }
+        if let Some(dir) = self.turns.pop_front() {
+            self.dir = dir;
+        }
+
+        let Some(head) = self.next_head() else {
+            debug!("snake hit the wall at score {}", self.score);
+            self.over = true;
+            return Step::Crashed;
+        };
+        // (the tail moves out of the way, unless the snake is growing)
+        let tail_moves = self.growing == 0;
+        let body = if tail_moves {
+            self.body.range(..self.body.len() - 1)
+        } else {
+            self.body.range(..)
+        };
+        if body.clone().any(|&p| p == head) {
+            debug!("snake ran into itself at score {}", self.score);
+            self.over = true;
+            return Step::Crashed;
+        }
+
+        self.body.push_front(head);
+        if tail_moves {
+            self.body.pop_back();
+        } else {
+            self.growing -= 1;
+        }
+
+        if self.food == Some(head) {
+            self.score += 1;
+            self.growing += GROWTH;
+            self.place_food();
+            if self.food.is_none() {
+                self.over = true;
+                return Step::Filled;
+            }
+            return Step::Ate;
+        }
+        Step::Moved
+    }
+
+    /// Bits of food eaten
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Squares the snake takes up
+    pub fn length(&self) -> usize {
+        self.body.len()
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.dir
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // square the head moves into next (None if that's off the board)
+    fn next_head(&self) -> Option<Point> {
+        let Point(i, j) = self.body[0];
+        let (i, j) = match self.dir {
+            Direction::Up => (i.checked_sub(1)?, j),
+            Direction::Down => (i + 1, j),
+            Direction::Left => (i, j.checked_sub(1)?),
+            Direction::Right => (i, j + 1),
+        };
+        (i < self.height && j < self.width).then_some(Point(i, j))
+    }
+
+    // put food on a random empty square (none if there aren't any left)
+    fn place_food(&mut self) {
+        let n_empty = self.height * self.width - self.body.len();
+        if n_empty == 0 {
+            self.food = None;
+            return;
+        }
+        let k = self.rng.gen_range(0..n_empty);
+        self.food = (0..self.height)
+            .flat_map(|i| (0..self.width).map(move |j| Point(i, j)))
+            .filter(|p| !self.body.contains(p))
+            .nth(k);
+    }
+}
+
+impl Core for Snake {
+    type Action = Direction;
+    type Outcome = ();
+    type Cell = Square;
+
+    fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    fn cell(&self, p: Point) -> Square {
+        if self.body[0] == p {
+            Square::Head
+        } else if self.body.contains(&p) {
+            Square::Body
+        } else if self.food == Some(p) {
+            Square::Food
+        } else {
+            Square::Empty
+        }
+    }
+
+    fn act(&mut self, dir: Direction) {
+        self.turn(dir);
+    }
+
+    fn tick(&mut self) {
+        self.step();
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+}
@@ -0,0 +1,104 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::snake::Direction;
+
+/// Help lines under the board, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("snake.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnakeUIAction {
+    Wait,
+    Turn(Direction),
+    Pause,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, SnakeUIAction); 12] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        SnakeUIAction::Turn(Direction::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        SnakeUIAction::Turn(Direction::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        SnakeUIAction::Turn(Direction::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        SnakeUIAction::Turn(Direction::Right),
+    ),
+    (KeyBinding::char('w'), SnakeUIAction::Turn(Direction::Up)),
+    (KeyBinding::char('s'), SnakeUIAction::Turn(Direction::Down)),
+    (KeyBinding::char('a'), SnakeUIAction::Turn(Direction::Left)),
+    (KeyBinding::char('d'), SnakeUIAction::Turn(Direction::Right)),
+    (KeyBinding::char(' '), SnakeUIAction::Pause),
+    (KeyBinding::char('p'), SnakeUIAction::Pause),
+    (KeyBinding::char('q'), SnakeUIAction::Quit),
+    (KeyBinding::ctrl('c'), SnakeUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, SnakeUIAction); 6] = [
+    ("up", SnakeUIAction::Turn(Direction::Up)),
+    ("down", SnakeUIAction::Turn(Direction::Down)),
+    ("left", SnakeUIAction::Turn(Direction::Left)),
+    ("right", SnakeUIAction::Turn(Direction::Right)),
+    ("pause", SnakeUIAction::Pause),
+    ("quit", SnakeUIAction::Quit),
+];
+
+pub struct SnakeUI {
+    pub paused: bool,
+    pub bindings: Bindings<SnakeUIAction>,
+}
+
+impl SnakeUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Starts out paused, until the first turn
+    pub fn new() -> Self {
+        Self {
+            paused: true,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> SnakeUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(SnakeUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> SnakeUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => SnakeUIAction::Wait,
+        }
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<SnakeUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
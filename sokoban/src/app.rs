@@ -0,0 +1,279 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::SokobanConfig;
+use crate::level::{Level, BUNDLED};
+use crate::sokoban::{Move, MoveResult, Sokoban, Tile};
+use crate::sokoui::{self, SokoUI, SokoUIAction};
+
+// terminal columns each square takes up (so squares come out roughly square)
+const SQUARE_COLS: usize = 2;
+
+// lines of text around the map (title, gap, counters, message)
+const STATUS_ROWS: usize = 4;
+
+/// Sokoban app (a pack of levels, the one being played, and UI state)
+pub struct SokobanApp {
+    levels: Vec<Level>,
+    index: usize, // level being played
+    sokoban: Sokoban,
+    ui: SokoUI,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // won once any level has been solved
+    over: bool,
+}
+
+impl SokobanApp {
+    /// Play [levels], starting on the one at [index]. Every level has to
+    /// have been checked with [Sokoban::new] already
+    pub fn new(levels: Vec<Level>, index: usize) -> Self {
+        let sokoban = Sokoban::new(&levels[index]).expect("levels were checked");
+        Self {
+            levels,
+            index,
+            sokoban,
+            ui: SokoUI::new(),
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // start the level at [index], if there is one
+    fn go_to_level(&mut self, index: usize) {
+        let Some(level) = self.levels.get(index) else {
+            bell::ring("no such level");
+            return;
+        };
+        debug!("level {}", index + 1);
+        self.sokoban = Sokoban::new(level).expect("levels were checked");
+        self.index = index;
+        self.message = "".to_string().reset();
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        match res {
+            MoveResult::Solved => {
+                bell::ring("win");
+                self.outcome = Outcome::Won;
+                let next = match self.index + 1 == self.levels.len() {
+                    true => locale::text("sokoban.complete"),
+                    false => locale::text("sokoban.next"),
+                };
+                self.message = format!("{} {next}", locale::text("sokoban.solved"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Blocked | MoveResult::NothingToUndo => {
+                bell::ring("invalid move");
+            }
+            MoveResult::Moved | MoveResult::Pushed | MoveResult::Undone | MoveResult::Restarted => {
+                self.message = "".to_string().reset()
+            }
+        }
+    }
+
+    // level number and title
+    fn title(&self) -> String {
+        let mut title = format!(
+            "{} {}/{}",
+            locale::text("sokoban.level"),
+            self.index + 1,
+            self.levels.len()
+        );
+        if let Some(name) = &self.levels[self.index].title {
+            title += &format!(": {name}");
+        }
+        title
+    }
+
+    fn counters(&self) -> String {
+        format!(
+            "{} {}   {} {}",
+            locale::text("sokoban.moves"),
+            self.sokoban.moves(),
+            locale::text("sokoban.pushes"),
+            self.sokoban.pushes()
+        )
+    }
+
+    // (styled) string for a square: blocks of color, or ASCII without color
+    fn styled_tile(tile: Tile) -> StyledContent<&'static str> {
+        let (role, colored, plain) = match tile {
+            Tile::Outside | Tile::Floor => return "  ".reset(),
+            Tile::Wall => (Role::Muted, "██", "##"),
+            Tile::Goal => (Role::Marker, " .", " ."),
+            Tile::Box => (Role::Marker, "[]", "[]"),
+            Tile::BoxOnGoal => (Role::Success, "[]", "{}"),
+            Tile::Player => (Role::Accent, "@@", "@@"),
+            Tile::PlayerOnGoal => (Role::Accent, "@@", "++"),
+        };
+        match style::color_enabled() {
+            true => colored.with(theme::color(role)).bold(),
+            false => plain.bold(),
+        }
+    }
+
+    // rows of the map, as (styled) squares
+    fn map_rows(&self) -> impl Iterator<Item = Vec<StyledContent<&'static str>>> + '_ {
+        let (height, width) = self.sokoban.size();
+        (0..height).map(move |i| {
+            (0..width)
+                .map(|j| Self::styled_tile(self.sokoban.cell(Point(i, j))))
+                .collect()
+        })
+    }
+}
+
+// Pretty-print
+impl fmt::Display for SokobanApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\r\n", self.title().bold())?;
+        for row in self.map_rows() {
+            for tile in row {
+                write!(f, "{tile}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.counters())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", sokoui::help_text())
+    }
+}
+
+impl Game for SokobanApp {
+    fn name(&self) -> &'static str {
+        "sokoban"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != SokoUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            SokoUIAction::Quit => self.over = true,
+            SokoUIAction::NextLevel => self.go_to_level(self.index + 1),
+            SokoUIAction::PrevLevel => match self.index.checked_sub(1) {
+                Some(index) => self.go_to_level(index),
+                None => bell::ring("no such level"),
+            },
+            // (a solved level stays solved, unless it's undone)
+            SokoUIAction::Move(Move::Walk(_)) if self.sokoban.is_solved() => {}
+            SokoUIAction::Move(mv) => {
+                let res = self.sokoban.make_move(mv);
+                self.handle_res(res);
+            }
+            SokoUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    // the whole map, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.sokoban.size();
+        let help = sokoui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max((SQUARE_COLS * width) as u16),
+            (height + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+// levels from the file at [path], or the bundled ones, checked to be playable
+fn load_levels(args: &Args, config: &SokobanConfig) -> Result<Vec<Level>, String> {
+    let levels = match args.levels.as_ref().or(config.levels.as_ref()) {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+            Level::parse_pack(&path.display().to_string(), &text)?
+        }
+        None => Level::parse_xsb(BUNDLED)?,
+    };
+    for (k, level) in levels.iter().enumerate() {
+        Sokoban::new(level).map_err(|e| format!("level {} can't be played: {e}", k + 1))?;
+    }
+    Ok(levels)
+}
+
+/// Play Sokoban, on the level pack chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: SokobanConfig = common::cli::setup(&args.common, "sokoban")?;
+    let levels = load_levels(&args, &config)?;
+    let index = args.level as usize - 1;
+    if index >= levels.len() {
+        return Err(format!(
+            "there's no level {} (the pack has {})",
+            args.level,
+            levels.len()
+        ));
+    }
+    info!(
+        "sokoban: {} levels, starting on {}",
+        levels.len(),
+        args.level
+    );
+
+    let mut app = SokobanApp::new(levels, index);
+    app.ui
+        .bindings
+        .remap(&config.keys, &sokoui::ACTIONS)
+        .map_err(|e| format!("bad [sokoban.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::SokobanApp;
+use crate::sokoui;
+
+impl TuiGame for SokobanApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .map_rows()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows).title(format!(" {} ", self.title()));
+
+        let status = StatusBar::new(sokoui::help_text())
+            .line(self.counters())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Sokoban
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on blocked moves, solving levels and achievements")
+}))]
+pub struct Args {
+    /// Level pack to play, in XSB (.xsb, .txt) or SLC (.slc) format
+    /// [default: the levels that come with the game]
+    #[arg(long)]
+    pub levels: Option<PathBuf>,
+
+    /// Level in the pack to start on (counting from 1)
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub level: u32,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+}
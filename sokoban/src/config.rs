@@ -0,0 +1,16 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [sokoban] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SokobanConfig {
+    /// level pack to play instead of the bundled one
+    pub levels: Option<PathBuf>,
+    /// keys for each action, e.g. undo = ["u", "backspace"]
+    pub keys: KeyMap,
+}
@@ -0,0 +1,147 @@
+/// Levels bundled with the game
+pub const BUNDLED: &str = include_str!("../levels/bundled.xsb");
+
+// characters a row of a level can be made of (- and _ are floor, like space)
+const LEVEL_CHARS: &str = "#@+$*.-_ ";
+
+/// One level, as the rows of its map in XSB notation:
+/// `#` wall, `@` player, `$` box, `.` goal, `*` box on a goal,
+/// `+` player on a goal, and space (or `-` or `_`) for floor
+#[derive(Debug, Clone, PartialEq)]
+pub struct Level {
+    pub title: Option<String>,
+    pub rows: Vec<String>,
+}
+
+impl Level {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Every level in a pack in XSB format: maps separated by anything that
+    /// isn't part of a map. A `; comment` line before a map, or a `Title:`
+    /// line after it, gives it a title
+    pub fn parse_xsb(text: &str) -> Result<Vec<Level>, String> {
+        let mut levels: Vec<Level> = Vec::new();
+        let mut rows = Vec::new();
+        let mut comment = None; // last comment seen, for the next level's title
+        for line in text.lines() {
+            let line = line.trim_end();
+            if is_level_row(line) {
+                rows.push(normalize_row(line));
+                continue;
+            }
+            if !rows.is_empty() {
+                levels.push(Level {
+                    title: comment.take(),
+                    rows: std::mem::take(&mut rows),
+                });
+            }
+            if let Some(text) = line.strip_prefix(';') {
+                comment = Some(text.trim().to_string()).filter(|text| !text.is_empty());
+            } else if let Some(title) = line.strip_prefix("Title:") {
+                if let Some(level) = levels.last_mut() {
+                    level.title = Some(title.trim().to_string());
+                }
+            }
+        }
+        if !rows.is_empty() {
+            levels.push(Level {
+                title: comment,
+                rows,
+            });
+        }
+        if levels.is_empty() {
+            return Err("no levels found".into());
+        }
+        Ok(levels)
+    }
+
+    /// Every level in a pack in SLC format (the XML one used by most level
+    /// collections online): `<Level Id="...">` elements with a `<L>` per row
+    pub fn parse_slc(text: &str) -> Result<Vec<Level>, String> {
+        let mut levels = Vec::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("<Level ").or_else(|| rest.find("<Level>")) {
+            let body = &rest[start..];
+            let end = body
+                .find("</Level>")
+                .ok_or_else(|| format!("level {} isn't closed", levels.len() + 1))?;
+            let (level, tail) = body.split_at(end);
+            rest = tail;
+
+            let tag = &level[..level.find('>').unwrap_or(level.len())];
+            let title = attribute(tag, "Id").map(unescape);
+            let rows = elements(level, "L")
+                .map(|row| normalize_row(&unescape(row)))
+                .collect::<Vec<_>>();
+            if let Some(row) = rows.iter().find(|row| !is_level_row(row)) {
+                return Err(format!("bad row in level {}: '{row}'", levels.len() + 1));
+            }
+            levels.push(Level { title, rows });
+        }
+        if levels.is_empty() {
+            return Err("no levels found".into());
+        }
+        Ok(levels)
+    }
+
+    /// Levels in [text], in SLC format if [name] ends in .slc and XSB otherwise
+    pub fn parse_pack(name: &str, text: &str) -> Result<Vec<Level>, String> {
+        let is_slc = name.to_ascii_lowercase().ends_with(".slc");
+        let levels = match is_slc {
+            true => Self::parse_slc(text),
+            false => Self::parse_xsb(text),
+        };
+        levels.map_err(|e| format!("bad level file {name}: {e}"))
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Size of the map, as (height, width)
+    pub fn size(&self) -> (usize, usize) {
+        let width = self.rows.iter().map(|row| row.chars().count()).max();
+        (self.rows.len(), width.unwrap_or(0))
+    }
+}
+
+// whether [line] is a row of a map (walls and the like, with at least one wall)
+fn is_level_row(line: &str) -> bool {
+    line.contains('#') && line.chars().all(|c| LEVEL_CHARS.contains(c))
+}
+
+// spaces for every kind of floor
+fn normalize_row(row: &str) -> String {
+    row.replace(['-', '_'], " ").trim_end().to_string()
+}
+
+// value of the attribute [name]="..." in an XML start [tag]
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!(" {name}=\""))? + name.len() + 3;
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+// text of each <[name]>...</[name]> element in [xml]
+fn elements<'a>(xml: &'a str, name: &str) -> impl Iterator<Item = &'a str> {
+    let (open, close) = (format!("<{name}>"), format!("</{name}>"));
+    let mut rest = xml;
+    std::iter::from_fn(move || {
+        let start = rest.find(&open)? + open.len();
+        let len = rest[start..].find(&close)?;
+        let text = &rest[start..start + len];
+        rest = &rest[start + len..];
+        Some(text)
+    })
+}
+
+// undo XML's escaping of special characters
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,21 @@
+//! Sokoban: the game core (a level's map, its boxes and the player's
+//! moves), level packs in XSB and SLC format, plus the crossterm frontend
+//! for playing it in a terminal
+
+mod level;
+mod sokoban;
+pub use level::{Level, BUNDLED};
+pub use sokoban::{Direction, Move, MoveResult, Sokoban, Tile};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod sokoui;
+#[cfg(feature = "terminal")]
+pub use app::{run, SokobanApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,341 @@
+use common::{Core, Point};
+use log::debug;
+
+use crate::level::Level;
+
+/// Direction to walk (or push) in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    // step in this direction, as (rows, cols)
+    fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+}
+
+/// Something the player can do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Move {
+    Walk(Direction),
+    /// take back the last step (and any push that came with it)
+    Undo,
+    /// put everything back where the level started
+    Restart,
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    Moved,
+    Pushed,
+    /// a wall, or a box that can't move, is in the way
+    Blocked,
+    Undone,
+    /// nothing left to undo
+    NothingToUndo,
+    Restarted,
+    /// every box is on a goal
+    Solved,
+}
+
+/// What's on one square of the map
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tile {
+    /// floor the player can't get to (outside the walls)
+    Outside,
+    Wall,
+    Floor,
+    Goal,
+    Box,
+    BoxOnGoal,
+    Player,
+    PlayerOnGoal,
+}
+
+// a step taken, and whether it pushed a box
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    dir: Direction,
+    pushed: bool,
+}
+
+/// A Sokoban level being played: walls, goals, boxes and the player,
+/// plus every step taken so far (for undoing them)
+pub struct Sokoban {
+    height: usize,
+    width: usize,
+    walls: Vec<bool>,
+    goals: Vec<bool>,
+    inside: Vec<bool>, // squares the player can reach (ignoring boxes)
+    start_boxes: Vec<bool>,
+    start_player: Point,
+    boxes: Vec<bool>,
+    player: Point,
+    history: Vec<Step>,
+    pushes: u32,
+}
+
+impl Sokoban {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Set up [level], checking there's one player and a goal for every box
+    pub fn new(level: &Level) -> Result<Self, String> {
+        let (height, width) = level.size();
+        let mut walls = vec![false; height * width];
+        let mut goals = vec![false; height * width];
+        let mut boxes = vec![false; height * width];
+        let mut players = Vec::new();
+        for (i, row) in level.rows.iter().enumerate() {
+            for (j, c) in row.chars().enumerate() {
+                let k = i * width + j;
+                match c {
+                    '#' => walls[k] = true,
+                    '.' => goals[k] = true,
+                    '$' => boxes[k] = true,
+                    '*' => (goals[k], boxes[k]) = (true, true),
+                    '@' => players.push(Point(i, j)),
+                    '+' => {
+                        goals[k] = true;
+                        players.push(Point(i, j));
+                    }
+                    ' ' | '-' | '_' => {}
+                    _ => return Err(format!("unknown square '{c}' at ({i}, {j})")),
+                }
+            }
+        }
+        let &[player] = players.as_slice() else {
+            return Err(format!("level has {} players (needs 1)", players.len()));
+        };
+        let n_boxes = boxes.iter().filter(|&&b| b).count();
+        let n_goals = goals.iter().filter(|&&g| g).count();
+        if n_boxes == 0 || n_boxes != n_goals {
+            return Err(format!(
+                "level has {n_boxes} boxes and {n_goals} goals (needs as many of each)"
+            ));
+        }
+
+        let mut sokoban = Self {
+            height,
+            width,
+            walls,
+            goals,
+            inside: Vec::new(),
+            start_boxes: boxes.clone(),
+            start_player: player,
+            boxes,
+            player,
+            history: Vec::new(),
+            pushes: 0,
+        };
+        sokoban.inside = sokoban.reachable();
+        Ok(sokoban)
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn make_move(&mut self, mv: Move) -> MoveResult {
+        match mv {
+            Move::Walk(dir) => self.walk(dir),
+            Move::Undo => self.undo(),
+            Move::Restart => {
+                self.boxes.clone_from(&self.start_boxes);
+                self.player = self.start_player;
+                self.history.clear();
+                self.pushes = 0;
+                MoveResult::Restarted
+            }
+        }
+    }
+
+    /// Steps taken (not counting ones undone)
+    pub fn moves(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    /// Steps that pushed a box
+    pub fn pushes(&self) -> u32 {
+        self.pushes
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.boxes
+            .iter()
+            .zip(&self.goals)
+            .all(|(&is_box, &goal)| is_box == goal)
+    }
+
+    /// Steps taken so far in LURD notation (lowercase for walking,
+    /// uppercase for pushing), the usual way of writing down solutions
+    pub fn lurd(&self) -> String {
+        let letter = |step: &Step| {
+            let c = match step.dir {
+                Direction::Up => 'u',
+                Direction::Down => 'd',
+                Direction::Left => 'l',
+                Direction::Right => 'r',
+            };
+            match step.pushed {
+                true => c.to_ascii_uppercase(),
+                false => c,
+            }
+        };
+        self.history.iter().map(letter).collect()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // square one step from [p] in [dir] (None if that's off the map)
+    fn neighbor(&self, Point(i, j): Point, dir: Direction) -> Option<Point> {
+        let (di, dj) = dir.delta();
+        let (i, j) = (i.checked_add_signed(di)?, j.checked_add_signed(dj)?);
+        (i < self.height && j < self.width).then_some(Point(i, j))
+    }
+
+    fn index(&self, Point(i, j): Point) -> usize {
+        i * self.width + j
+    }
+
+    // whether [p] is floor with nothing on it
+    fn is_free(&self, p: Point) -> bool {
+        let k = self.index(p);
+        !self.walls[k] && !self.boxes[k]
+    }
+
+    fn walk(&mut self, dir: Direction) -> MoveResult {
+        let Some(next) = self.neighbor(self.player, dir) else {
+            return MoveResult::Blocked;
+        };
+        let k = self.index(next);
+        if self.walls[k] {
+            return MoveResult::Blocked;
+        }
+        let pushed = self.boxes[k];
+        if pushed {
+            let Some(beyond) = self.neighbor(next, dir).filter(|&p| self.is_free(p)) else {
+                return MoveResult::Blocked;
+            };
+            let kb = self.index(beyond);
+            (self.boxes[k], self.boxes[kb]) = (false, true);
+            self.pushes += 1;
+        }
+        self.player = next;
+        self.history.push(Step { dir, pushed });
+
+        if pushed && self.is_solved() {
+            debug!(
+                "solved in {} moves, {} pushes: {}",
+                self.moves(),
+                self.pushes,
+                self.lurd()
+            );
+            return MoveResult::Solved;
+        }
+        match pushed {
+            true => MoveResult::Pushed,
+            false => MoveResult::Moved,
+        }
+    }
+
+    fn undo(&mut self) -> MoveResult {
+        let Some(step) = self.history.pop() else {
+            return MoveResult::NothingToUndo;
+        };
+        let (di, dj) = step.dir.delta();
+        let Point(i, j) = self.player;
+        let back = Point(i.wrapping_add_signed(-di), j.wrapping_add_signed(-dj));
+        if step.pushed {
+            let pushed_to = self
+                .neighbor(self.player, step.dir)
+                .expect("box was pushed here");
+            let (k, kb) = (self.index(self.player), self.index(pushed_to));
+            (self.boxes[k], self.boxes[kb]) = (true, false);
+            self.pushes -= 1;
+        }
+        self.player = back;
+        MoveResult::Undone
+    }
+
+    // squares the player can walk to from the start, going around walls
+    fn reachable(&self) -> Vec<bool> {
+        let mut seen = vec![false; self.height * self.width];
+        let mut todo = vec![self.player];
+        seen[self.index(self.player)] = true;
+        while let Some(p) = todo.pop() {
+            let dirs = [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ];
+            for q in dirs.into_iter().filter_map(|dir| self.neighbor(p, dir)) {
+                let k = self.index(q);
+                if !seen[k] && !self.walls[k] {
+                    seen[k] = true;
+                    todo.push(q);
+                }
+            }
+        }
+        seen
+    }
+}
+
+impl Core for Sokoban {
+    type Action = Move;
+    type Outcome = MoveResult;
+    type Cell = Tile;
+
+    fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    fn cell(&self, p: Point) -> Tile {
+        let k = self.index(p);
+        let goal = self.goals[k];
+        if self.walls[k] {
+            Tile::Wall
+        } else if p == self.player {
+            if goal {
+                Tile::PlayerOnGoal
+            } else {
+                Tile::Player
+            }
+        } else if self.boxes[k] {
+            if goal {
+                Tile::BoxOnGoal
+            } else {
+                Tile::Box
+            }
+        } else if goal {
+            Tile::Goal
+        } else if self.inside[k] {
+            Tile::Floor
+        } else {
+            Tile::Outside
+        }
+    }
+
+    fn act(&mut self, mv: Move) -> MoveResult {
+        self.make_move(mv)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_solved()
+    }
+}
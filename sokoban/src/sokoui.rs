@@ -0,0 +1,113 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::sokoban::{Direction, Move};
+
+/// Help lines under the map, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("sokoban.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SokoUIAction {
+    Wait,
+    Move(Move),
+    NextLevel,
+    PrevLevel,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, SokoUIAction); 16] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        SokoUIAction::Move(Move::Walk(Direction::Up)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        SokoUIAction::Move(Move::Walk(Direction::Down)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        SokoUIAction::Move(Move::Walk(Direction::Left)),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        SokoUIAction::Move(Move::Walk(Direction::Right)),
+    ),
+    (
+        KeyBinding::char('w'),
+        SokoUIAction::Move(Move::Walk(Direction::Up)),
+    ),
+    (
+        KeyBinding::char('s'),
+        SokoUIAction::Move(Move::Walk(Direction::Down)),
+    ),
+    (
+        KeyBinding::char('a'),
+        SokoUIAction::Move(Move::Walk(Direction::Left)),
+    ),
+    (
+        KeyBinding::char('d'),
+        SokoUIAction::Move(Move::Walk(Direction::Right)),
+    ),
+    (KeyBinding::char('u'), SokoUIAction::Move(Move::Undo)),
+    (KeyBinding::char('z'), SokoUIAction::Move(Move::Undo)),
+    (
+        KeyBinding::key(KeyCode::Backspace),
+        SokoUIAction::Move(Move::Undo),
+    ),
+    (KeyBinding::char('r'), SokoUIAction::Move(Move::Restart)),
+    (KeyBinding::char('n'), SokoUIAction::NextLevel),
+    (KeyBinding::char('p'), SokoUIAction::PrevLevel),
+    (KeyBinding::char('q'), SokoUIAction::Quit),
+    (KeyBinding::ctrl('c'), SokoUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, SokoUIAction); 9] = [
+    ("up", SokoUIAction::Move(Move::Walk(Direction::Up))),
+    ("down", SokoUIAction::Move(Move::Walk(Direction::Down))),
+    ("left", SokoUIAction::Move(Move::Walk(Direction::Left))),
+    ("right", SokoUIAction::Move(Move::Walk(Direction::Right))),
+    ("undo", SokoUIAction::Move(Move::Undo)),
+    ("restart", SokoUIAction::Move(Move::Restart)),
+    ("next_level", SokoUIAction::NextLevel),
+    ("prev_level", SokoUIAction::PrevLevel),
+    ("quit", SokoUIAction::Quit),
+];
+
+pub struct SokoUI {
+    pub bindings: Bindings<SokoUIAction>,
+}
+
+impl SokoUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> SokoUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(SokoUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> SokoUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => SokoUIAction::Wait,
+        }
+    }
+}
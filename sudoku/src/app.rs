@@ -0,0 +1,338 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::SudokuConfig;
+use crate::generator::Difficulty;
+use crate::sudoku::{Move, MoveResult, SquareView, Sudoku};
+use crate::sudokuui::{self, SudokuUI, SudokuUIAction};
+
+// lines between boxes, inside the grid's border
+const BOX_EDGE: &str = "───────┼───────┼───────";
+const TOP_EDGE: &str = "┌───────┬───────┬───────┐";
+const BOTTOM_EDGE: &str = "└───────┴───────┴───────┘";
+
+// lines of text under the grid (marks, counters, message)
+const STATUS_ROWS: usize = 3;
+
+// how often the clock is checked (it only shows whole seconds)
+const CLOCK_TICK: Duration = Duration::from_millis(100);
+
+/// Sudoku app (puzzle + UI state)
+pub struct SudokuApp {
+    sudoku: Sudoku,
+    ui: SudokuUI,
+    message: StyledContent<String>,
+    started: Instant,
+    elapsed: Duration, // frozen once the puzzle is solved
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless the puzzle was solved
+    over: bool,
+}
+
+impl SudokuApp {
+    pub fn new(sudoku: Sudoku) -> Self {
+        Self {
+            sudoku,
+            ui: SudokuUI::new(),
+            message: "".to_string().reset(),
+            started: Instant::now(),
+            elapsed: Duration::ZERO,
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        self.started = Instant::now();
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        match res {
+            MoveResult::Solved => {
+                bell::ring("win");
+                self.over = true;
+                self.outcome = Outcome::Won;
+                self.elapsed = self.started.elapsed();
+                self.message = locale::text("sudoku.win")
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Given => {
+                bell::ring("invalid move");
+                self.message = locale::text("sudoku.given")
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            MoveResult::Hint(p) => {
+                // (the cursor goes to the square the hint filled in)
+                debug!("hint at ({}, {})", p.0, p.1);
+                self.ui.set_cursor(p);
+                self.message = "".to_string().reset();
+            }
+            MoveResult::Ok => self.message = "".to_string().reset(),
+        }
+    }
+
+    // (styled) string for a square, highlighted if the cursor is on it,
+    // or if it has the same digit as the square under the cursor
+    fn styled_square(&self, p: Point) -> StyledContent<String> {
+        let cursor = self.ui.get_cursor();
+        let same_digit = self.sudoku.digit(cursor).is_some()
+            && self.sudoku.digit(p) == self.sudoku.digit(cursor);
+        let digit = |d: u8| ((b'0' + d) as char).to_string();
+        let styled = match self.sudoku.cell(p) {
+            SquareView::Given(d) => digit(d).bold().with(theme::color(Role::Text)),
+            SquareView::Filled {
+                digit: d,
+                conflict: true,
+            } => digit(d).bold().with(theme::color(Role::Danger)),
+            SquareView::Filled { digit: d, .. } => digit(d).with(theme::color(Role::Accent)),
+            SquareView::Marked(_) => "·".to_string().with(theme::color(Role::Marker)),
+            SquareView::Empty => ".".to_string().with(theme::color(Role::Muted)),
+        };
+        let styled = match same_digit && p != cursor {
+            true => styled.with(theme::color(Role::Marker)).bold(),
+            false => styled,
+        };
+        if p != cursor {
+            return styled;
+        }
+        // (without color, pencil mode shows as an underline)
+        match self.ui.pencil {
+            true => styled.reverse().underlined(),
+            false => styled.reverse(),
+        }
+    }
+
+    /// Lines of the grid inside its border, each a list of (styled) pieces
+    fn grid_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let muted = |s: &str| s.to_string().with(theme::color(Role::Muted));
+        let mut lines = Vec::new();
+        for i in 0..9 {
+            if i > 0 && i % 3 == 0 {
+                lines.push(vec![muted(BOX_EDGE)]);
+            }
+            let mut line = Vec::new();
+            for j in 0..9 {
+                if j > 0 && j % 3 == 0 {
+                    line.push(muted(" │"));
+                }
+                line.push(" ".to_string().reset());
+                line.push(self.styled_square(Point(i, j)));
+            }
+            line.push(" ".to_string().reset());
+            lines.push(line);
+        }
+        lines
+    }
+
+    // pencil marks at the cursor, and the mode digits go in with
+    fn marks_line(&self) -> String {
+        let mode = match self.ui.pencil {
+            true => locale::text("sudoku.pencil"),
+            false => locale::text("sudoku.pen"),
+        };
+        let marks = self.sudoku.marks(self.ui.get_cursor());
+        if marks.is_empty() {
+            return format!("[{mode}]");
+        }
+        let marks: Vec<_> = marks.iter().map(u8::to_string).collect();
+        format!(
+            "[{mode}]   {} {}",
+            locale::text("sudoku.marks"),
+            marks.join(" ")
+        )
+    }
+
+    fn counters(&self) -> String {
+        format!(
+            "{} {}s   {} {}",
+            locale::text("sudoku.time"),
+            self.elapsed.as_secs(),
+            locale::text("sudoku.hints"),
+            self.sudoku.hints()
+        )
+    }
+}
+
+// Pretty-print
+impl fmt::Display for SudokuApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let muted = |s: &'static str| s.with(theme::color(Role::Muted));
+        write!(f, "{}\r\n", muted(TOP_EDGE))?;
+        for line in self.grid_lines() {
+            let is_edge = line.len() == 1;
+            let (left, right) = if is_edge {
+                ("├", "┤")
+            } else {
+                ("│", "│")
+            };
+            write!(f, "{}", muted(left))?;
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "{}\r\n", muted(right))?;
+        }
+        write!(f, "{}\r\n", muted(BOTTOM_EDGE))?;
+
+        write!(f, "{}\r\n", self.marks_line())?;
+        write!(f, "{}\r\n", self.counters())?;
+        write!(f, "{}\r\n", self.message)?;
+        if self.over {
+            write!(f, "{}", locale::text("sudoku.exit"))
+        } else {
+            write!(f, "{}", sudokuui::help_text())
+        }
+    }
+}
+
+impl Game for SudokuApp {
+    fn name(&self) -> &'static str {
+        "sudoku"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != SudokuUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        let p = self.ui.get_cursor();
+        let mv = match user_action {
+            SudokuUIAction::Quit => {
+                self.over = true;
+                None
+            }
+            SudokuUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the grid");
+                }
+                None
+            }
+            SudokuUIAction::TogglePencil => {
+                self.ui.pencil = !self.ui.pencil;
+                None
+            }
+            SudokuUIAction::Digit(d) if self.ui.pencil => Some(Move::ToggleMark(p, d)),
+            SudokuUIAction::Digit(d) => Some(Move::Set(p, d)),
+            SudokuUIAction::Clear => Some(Move::Clear(p)),
+            SudokuUIAction::Hint => Some(Move::Hint),
+            SudokuUIAction::Wait => None,
+        };
+        if let Some(mv) = mv {
+            let res = self.sudoku.make_move(mv);
+            self.handle_res(res);
+        }
+        self.redraw.set(true);
+    }
+
+    // keep the clock running between moves
+    fn tick(&mut self) {
+        if self.over {
+            return;
+        }
+        let elapsed = self.started.elapsed();
+        if elapsed.as_secs() != self.elapsed.as_secs() {
+            self.redraw.set(true);
+        }
+        self.elapsed = elapsed;
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(CLOCK_TICK)
+    }
+
+    // the grid, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = sudokuui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max(text_width(TOP_EDGE)),
+            (13 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play a Sudoku puzzle, generated or given on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    if let Some(puzzle) = &args.puzzle {
+        let sudoku: Sudoku = puzzle.parse()?;
+        if args.solve {
+            print!("{}", sudoku.solution());
+            return Ok(());
+        }
+    }
+    let config: SudokuConfig = common::cli::setup(&args.common, "sudoku")?;
+
+    let sudoku = match &args.puzzle {
+        Some(puzzle) => puzzle.parse()?,
+        None => {
+            let difficulty = args
+                .difficulty
+                .or(config.difficulty)
+                .unwrap_or(Difficulty::Medium);
+            let seed = args.seed.unwrap_or_else(rand::random);
+            info!("sudoku seed {seed}");
+            Sudoku::generate(difficulty, seed)
+        }
+    };
+    let mut app = SudokuApp::new(sudoku);
+    app.ui
+        .bindings
+        .remap(&config.keys, &sudokuui::ACTIONS)
+        .map_err(|e| format!("bad [sudoku.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the grid up until a key is pressed, unless the player quit
+    if app.outcome != Outcome::Quit {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+
+use super::SudokuApp;
+use crate::sudokuui;
+
+impl TuiGame for SudokuApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .grid_lines()
+            .iter()
+            .map(|line| line.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows);
+
+        let message = tui::span(&self.message);
+        let status = StatusBar::new(sudokuui::help_text())
+            .line(self.marks_line())
+            .line(self.counters())
+            .line(message.clone());
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+
+        if self.over {
+            let mut lines = vec![Line::from(locale::text("sudoku.exit"))];
+            if !message.content.is_empty() {
+                lines.insert(0, Line::from(message));
+            }
+            let title = format!(" {} ", locale::text("sudoku.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
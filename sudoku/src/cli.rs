@@ -0,0 +1,42 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+use crate::generator::Difficulty;
+
+/// Sudoku
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on changing a given square, solving and achievements")
+}))]
+pub struct Args {
+    /// How many squares the puzzle starts with: easy, medium or hard
+    /// [default: medium, unless the config file picks another]
+    #[arg(long)]
+    pub difficulty: Option<Difficulty>,
+
+    /// Play this puzzle instead of a generated one: 81 digits, a row at a
+    /// time, with . or 0 for empty squares
+    #[arg(long)]
+    pub puzzle: Option<String>,
+
+    /// Print the solution to --puzzle instead of playing it
+    #[arg(long, requires = "puzzle")]
+    pub solve: bool,
+
+    /// Random seed for generating the puzzle (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
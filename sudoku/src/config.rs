@@ -0,0 +1,17 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+use crate::generator::Difficulty;
+
+/// Settings from the [sudoku] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SudokuConfig {
+    /// difficulty of generated puzzles
+    #[serde(deserialize_with = "common::config::from_str")]
+    pub difficulty: Option<Difficulty>,
+    /// keys for each action, e.g. hint = ["h", "?"]
+    pub keys: KeyMap,
+}
@@ -0,0 +1,68 @@
+use std::str::FromStr;
+
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::solver::{self, Cells};
+
+/// How many squares a generated puzzle starts with filled in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    // squares to leave filled in (hard puzzles may end up with a few more,
+    // if taking any more out would allow a second solution)
+    fn clues(self) -> usize {
+        match self {
+            Difficulty::Easy => 40,
+            Difficulty::Medium => 32,
+            Difficulty::Hard => 24,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!(
+                "unknown difficulty '{s}' (expected easy, medium or hard)"
+            )),
+        }
+    }
+}
+
+/// A random puzzle with exactly one solution, as (puzzle, solution).
+/// The same seed always gives the same puzzle
+pub fn generate(difficulty: Difficulty, seed: u64) -> (Cells, Cells) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let solution = solver::random_grid(&mut rng);
+
+    // take squares out in a random order, putting back any whose
+    // removal would let the puzzle be solved another way
+    let mut puzzle = solution;
+    let mut order: Vec<usize> = (0..81).collect();
+    order.shuffle(&mut rng);
+    let mut clues = 81;
+    for k in order {
+        if clues == difficulty.clues() {
+            break;
+        }
+        puzzle[k] = 0;
+        if solver::count_solutions(&puzzle, 2) == 1 {
+            clues -= 1;
+        } else {
+            puzzle[k] = solution[k];
+        }
+    }
+    debug!("generated a {difficulty:?} puzzle with {clues} clues");
+    (puzzle, solution)
+}
@@ -0,0 +1,21 @@
+//! Sudoku: the game core (a puzzle, pencil marks and hints), a solver and
+//! a puzzle generator, plus the crossterm frontend for playing it in a terminal
+
+mod generator;
+pub mod solver;
+mod sudoku;
+pub use generator::{generate, Difficulty};
+pub use sudoku::{Move, MoveResult, SquareView, Sudoku};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod sudokuui;
+#[cfg(feature = "terminal")]
+pub use app::{run, SudokuApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,71 @@
+use rand::{rngs::StdRng, seq::SliceRandom};
+
+/// Squares of a grid, a row at a time (0 for empty)
+pub type Cells = [u8; 81];
+
+// bits 1-9, one per digit
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+/// Squares sharing a row, column or box with square [k] (some more than once)
+pub fn peers(k: usize) -> impl Iterator<Item = usize> {
+    let (i, j) = (k / 9, k % 9);
+    let (top, left) = (i / 3 * 3, j / 3 * 3);
+    let row = (0..9).map(move |c| i * 9 + c);
+    let col = (0..9).map(move |r| r * 9 + j);
+    let square = (0..9).map(move |n| (top + n / 3) * 9 + left + n % 3);
+    row.chain(col).chain(square).filter(move |&p| p != k)
+}
+
+/// Digits that could go in square [k] without repeating one in its row,
+/// column or box, as a bitmask (bit d for digit d)
+pub fn candidates(cells: &Cells, k: usize) -> u16 {
+    let used = peers(k).fold(0, |used, p| used | 1 << cells[p]);
+    ALL_DIGITS & !used
+}
+
+/// Some solution of [cells], if there is one
+pub fn solve(cells: &Cells) -> Option<Cells> {
+    let mut solutions = Vec::new();
+    search(&mut cells.clone(), 1, None, &mut solutions);
+    solutions.pop()
+}
+
+/// Number of solutions [cells] has, counting no higher than [limit]
+/// (a proper puzzle has exactly one)
+pub fn count_solutions(cells: &Cells, limit: usize) -> usize {
+    let mut solutions = Vec::new();
+    search(&mut cells.clone(), limit, None, &mut solutions);
+    solutions.len()
+}
+
+/// A random full grid (digits tried in the order [rng] shuffles them into)
+pub fn random_grid(rng: &mut StdRng) -> Cells {
+    let mut solutions = Vec::new();
+    search(&mut [0; 81], 1, Some(rng), &mut solutions);
+    solutions.pop().expect("an empty grid has solutions")
+}
+
+// backtracking search, filling in the square with the fewest candidates
+// first, until [limit] solutions have been found
+fn search(cells: &mut Cells, limit: usize, mut rng: Option<&mut StdRng>, found: &mut Vec<Cells>) {
+    let best = (0..81)
+        .filter(|&k| cells[k] == 0)
+        .map(|k| (k, candidates(cells, k)))
+        .min_by_key(|&(_, digits)| digits.count_ones());
+    let Some((k, digits)) = best else {
+        found.push(*cells);
+        return;
+    };
+    let mut digits: Vec<u8> = (1..=9).filter(|d| digits & 1 << d != 0).collect();
+    if let Some(rng) = rng.as_deref_mut() {
+        digits.shuffle(rng);
+    }
+    for d in digits {
+        cells[k] = d;
+        search(cells, limit, rng.as_deref_mut(), found);
+        if found.len() >= limit {
+            break;
+        }
+    }
+    cells[k] = 0;
+}
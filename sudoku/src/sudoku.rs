@@ -0,0 +1,259 @@
+use std::fmt;
+use std::str::FromStr;
+
+use common::{Core, Point};
+
+use crate::generator::{self, Difficulty};
+use crate::solver::{self, Cells};
+
+/// Something the player can do
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Move {
+    /// write a digit (1-9) in a square
+    Set(Point, u8),
+    /// rub out a square's digit and pencil marks
+    Clear(Point),
+    /// pencil a digit in as a possibility, or rub it out
+    ToggleMark(Point, u8),
+    /// fill in a square from the solution (fixing a wrong digit first, if there is one)
+    Hint,
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    Ok,
+    /// the square was filled in from the start, and can't be changed
+    Given,
+    /// the hint filled in this square
+    Hint(Point),
+    /// every square is filled in, with no digit repeated in a row, column or box
+    Solved,
+}
+
+/// What's in one square, as far as drawing it goes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SquareView {
+    /// filled in from the start
+    Given(u8),
+    /// filled in by the player (conflicting if the digit is repeated in
+    /// its row, column or box)
+    Filled {
+        digit: u8,
+        conflict: bool,
+    },
+    /// empty, with these digits pencilled in (bit d for digit d)
+    Marked(u16),
+    Empty,
+}
+
+/// A Sudoku puzzle being played, along with its solution (for hints)
+#[derive(Clone)]
+pub struct Sudoku {
+    givens: Cells,
+    cells: Cells,
+    marks: [u16; 81],
+    solution: Cells,
+    hints: u32,
+}
+
+impl Sudoku {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Random puzzle with exactly one solution
+    pub fn generate(difficulty: Difficulty, seed: u64) -> Self {
+        let (puzzle, solution) = generator::generate(difficulty, seed);
+        Self::with_solution(puzzle, solution)
+    }
+
+    fn with_solution(puzzle: Cells, solution: Cells) -> Self {
+        Self {
+            givens: puzzle,
+            cells: puzzle,
+            marks: [0; 81],
+            solution,
+            hints: 0,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn make_move(&mut self, mv: Move) -> MoveResult {
+        let k = match mv {
+            Move::Set(p, _) | Move::Clear(p) | Move::ToggleMark(p, _) => index(p),
+            Move::Hint => return self.hint(),
+        };
+        if self.givens[k] != 0 {
+            return MoveResult::Given;
+        }
+        match mv {
+            Move::Set(_, digit) => {
+                self.cells[k] = digit;
+                // (the digit can't go anywhere else nearby now)
+                for p in solver::peers(k) {
+                    self.marks[p] &= !(1 << digit);
+                }
+            }
+            Move::Clear(_) => (self.cells[k], self.marks[k]) = (0, 0),
+            Move::ToggleMark(_, digit) if self.cells[k] == 0 => self.marks[k] ^= 1 << digit,
+            Move::ToggleMark(..) | Move::Hint => {}
+        }
+        self.result()
+    }
+
+    /// Every square filled in, with no repeats
+    pub fn is_solved(&self) -> bool {
+        (0..81).all(|k| self.cells[k] != 0 && !self.conflicts(k))
+    }
+
+    /// Hints taken so far
+    pub fn hints(&self) -> u32 {
+        self.hints
+    }
+
+    /// Digit in square [p], if it's filled in
+    pub fn digit(&self, p: Point) -> Option<u8> {
+        Some(self.cells[index(p)]).filter(|&d| d != 0)
+    }
+
+    /// Digits pencilled in at square [p]
+    pub fn marks(&self, p: Point) -> Vec<u8> {
+        let marks = self.marks[index(p)];
+        (1..=9).filter(|d| marks & 1 << d != 0).collect()
+    }
+
+    /// The puzzle with every square filled in from its solution
+    pub fn solution(&self) -> Self {
+        Self::with_solution(self.solution, self.solution)
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // whether square [k]'s digit is repeated in its row, column or box
+    fn conflicts(&self, k: usize) -> bool {
+        let digit = self.cells[k];
+        digit != 0 && solver::peers(k).any(|p| self.cells[p] == digit)
+    }
+
+    fn result(&self) -> MoveResult {
+        match self.is_solved() {
+            true => MoveResult::Solved,
+            false => MoveResult::Ok,
+        }
+    }
+
+    // fix the first wrong digit, or else fill in the empty square with the
+    // fewest possibilities (the easiest one to have worked out)
+    fn hint(&mut self) -> MoveResult {
+        let wrong = (0..81).find(|&k| self.cells[k] != 0 && self.cells[k] != self.solution[k]);
+        let easiest = || {
+            (0..81)
+                .filter(|&k| self.cells[k] == 0)
+                .min_by_key(|&k| solver::candidates(&self.cells, k).count_ones())
+        };
+        let Some(k) = wrong.or_else(easiest) else {
+            return self.result();
+        };
+        self.cells[k] = self.solution[k];
+        self.marks[k] = 0;
+        self.hints += 1;
+        match self.result() {
+            MoveResult::Solved => MoveResult::Solved,
+            _ => MoveResult::Hint(Point(k / 9, k % 9)),
+        }
+    }
+}
+
+// position of [p] in a grid stored a row at a time
+fn index(Point(i, j): Point) -> usize {
+    i * 9 + j
+}
+
+/// A puzzle as 81 digits, a row at a time, with `.` or `0` for empty
+/// squares (whitespace and `|`, `-`, `+` lines between boxes are skipped)
+impl FromStr for Sudoku {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut puzzle = [0; 81];
+        let mut n = 0;
+        for c in s
+            .chars()
+            .filter(|c| !c.is_whitespace() && !"|-+".contains(*c))
+        {
+            let digit = match c {
+                '.' | '0' => 0,
+                '1'..='9' => c as u8 - b'0',
+                _ => return Err(format!("unexpected '{c}' in puzzle")),
+            };
+            if n == 81 {
+                return Err("puzzle has more than 81 squares".into());
+            }
+            puzzle[n] = digit;
+            n += 1;
+        }
+        if n < 81 {
+            return Err(format!("puzzle has {n} squares (needs 81)"));
+        }
+        let sudoku = Self::with_solution(puzzle, puzzle);
+        if (0..81).any(|k| sudoku.conflicts(k)) {
+            return Err("puzzle repeats a digit in a row, column or box".into());
+        }
+        let solution = solver::solve(&puzzle).ok_or("puzzle has no solution")?;
+        Ok(Self::with_solution(puzzle, solution))
+    }
+}
+
+/// The grid as it is, nine digits to a line (with `.` for empty squares)
+impl fmt::Display for Sudoku {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.cells.chunks(9) {
+            let line: String = row
+                .iter()
+                .map(|&d| match d {
+                    0 => '.',
+                    d => (b'0' + d) as char,
+                })
+                .collect();
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Core for Sudoku {
+    type Action = Move;
+    type Outcome = MoveResult;
+    type Cell = SquareView;
+
+    fn size(&self) -> (usize, usize) {
+        (9, 9)
+    }
+
+    fn cell(&self, p: Point) -> SquareView {
+        let k = index(p);
+        match (self.givens[k], self.cells[k]) {
+            (0, 0) if self.marks[k] != 0 => SquareView::Marked(self.marks[k]),
+            (0, 0) => SquareView::Empty,
+            (0, digit) => SquareView::Filled {
+                digit,
+                conflict: self.conflicts(k),
+            },
+            (digit, _) => SquareView::Given(digit),
+        }
+    }
+
+    fn act(&mut self, mv: Move) -> MoveResult {
+        self.make_move(mv)
+    }
+
+    fn is_over(&self) -> bool {
+        self.is_solved()
+    }
+}
@@ -0,0 +1,150 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+/// Help lines under the grid, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("sudoku.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SudokuUIAction {
+    Wait,
+    Move(MoveDirection),
+    Digit(u8),
+    Clear,
+    TogglePencil,
+    Hint,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, SudokuUIAction); 21] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        SudokuUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        SudokuUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        SudokuUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        SudokuUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char('1'), SudokuUIAction::Digit(1)),
+    (KeyBinding::char('2'), SudokuUIAction::Digit(2)),
+    (KeyBinding::char('3'), SudokuUIAction::Digit(3)),
+    (KeyBinding::char('4'), SudokuUIAction::Digit(4)),
+    (KeyBinding::char('5'), SudokuUIAction::Digit(5)),
+    (KeyBinding::char('6'), SudokuUIAction::Digit(6)),
+    (KeyBinding::char('7'), SudokuUIAction::Digit(7)),
+    (KeyBinding::char('8'), SudokuUIAction::Digit(8)),
+    (KeyBinding::char('9'), SudokuUIAction::Digit(9)),
+    (KeyBinding::char('0'), SudokuUIAction::Clear),
+    (KeyBinding::key(KeyCode::Backspace), SudokuUIAction::Clear),
+    (KeyBinding::key(KeyCode::Delete), SudokuUIAction::Clear),
+    (KeyBinding::char('p'), SudokuUIAction::TogglePencil),
+    (KeyBinding::key(KeyCode::Tab), SudokuUIAction::TogglePencil),
+    (KeyBinding::char('h'), SudokuUIAction::Hint),
+    (KeyBinding::char('q'), SudokuUIAction::Quit),
+    (KeyBinding::ctrl('c'), SudokuUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, SudokuUIAction); 8] = [
+    ("up", SudokuUIAction::Move(MoveDirection::Up)),
+    ("down", SudokuUIAction::Move(MoveDirection::Down)),
+    ("left", SudokuUIAction::Move(MoveDirection::Left)),
+    ("right", SudokuUIAction::Move(MoveDirection::Right)),
+    ("clear", SudokuUIAction::Clear),
+    ("pencil", SudokuUIAction::TogglePencil),
+    ("hint", SudokuUIAction::Hint),
+    ("quit", SudokuUIAction::Quit),
+];
+
+pub struct SudokuUI {
+    cursor: Point,
+    /// whether digits go in as pencil marks
+    pub pencil: bool,
+    pub bindings: Bindings<SudokuUIAction>,
+}
+
+impl SudokuUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            cursor: Point(4, 4),
+            pencil: false,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> SudokuUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(SudokuUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> SudokuUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => SudokuUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a square, unless that's off the grid
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let Point(i, j) = self.cursor;
+        let (i, j) = match dir {
+            MoveDirection::Up => (i.checked_sub(1), Some(j)),
+            MoveDirection::Down => (Some(i + 1).filter(|&i| i < 9), Some(j)),
+            MoveDirection::Left => (Some(i), j.checked_sub(1)),
+            MoveDirection::Right => (Some(i), Some(j + 1).filter(|&j| j < 9)),
+        };
+        let (Some(i), Some(j)) = (i, j) else {
+            return Err("already at the edge".into());
+        };
+        self.cursor = Point(i, j);
+        Ok(())
+    }
+
+    pub fn set_cursor(&mut self, p: Point) {
+        self.cursor = p;
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<SudokuUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::TetrisConfig;
+use crate::tetris::{MoveResult, Piece, Square, Tetris, WIDTH};
+use crate::tetrisui::{self, TetrisUI, TetrisUIAction};
+
+// levels there are (past the last, pieces don't fall any faster)
+const MAX_LEVEL: u32 = 20;
+
+// pieces never fall faster than this
+const MIN_GRAVITY: Duration = Duration::from_millis(10);
+
+// terminal columns each square takes up (so squares come out roughly square)
+const SQUARE_COLS: usize = 2;
+
+// columns between the board and the panel next to it
+const PANEL_GAP: &str = "  ";
+
+/// Tetris app (game + UI state)
+pub struct TetrisApp {
+    tetris: Tetris,
+    ui: TetrisUI,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless the game was lost
+    over: bool,
+}
+
+impl TetrisApp {
+    pub fn new(level: u32, seed: u64) -> Self {
+        Self {
+            tetris: Tetris::new(level, seed),
+            ui: TetrisUI::new(),
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // time for a piece to fall one row at the current level
+    // (the usual (0.8 - (level - 1) * 0.007) ^ (level - 1) seconds)
+    fn gravity(&self) -> Duration {
+        let level = self.tetris.level().min(MAX_LEVEL) as f64 - 1.0;
+        let secs = (0.8 - level * 0.007).powf(level);
+        Duration::from_secs_f64(secs).max(MIN_GRAVITY)
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        match res {
+            MoveResult::ToppedOut => {
+                bell::ring("topped out");
+                self.over = true;
+                self.outcome = Outcome::Lost;
+                self.message = locale::text("tetris.game_over")
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+            }
+            MoveResult::Locked(4) => {
+                self.message = locale::text("tetris.tetris")
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Success));
+            }
+            MoveResult::Locked(_) => self.message = "".to_string().reset(),
+            MoveResult::Moved | MoveResult::Blocked => {}
+        }
+    }
+
+    // (styled) string for a square: blocks in each piece's color, or ASCII
+    // without color
+    fn styled_square(sq: Square) -> StyledContent<&'static str> {
+        let color = |piece: Piece| theme::current().cell(piece as u8 + 2);
+        match (sq, style::color_enabled()) {
+            (Square::Empty, _) => " .".with(theme::color(Role::Muted)),
+            (Square::Block(piece) | Square::Active(piece), true) => "██".with(color(piece)),
+            (Square::Ghost(piece), true) => "░░".with(color(piece)),
+            (Square::Block(_) | Square::Active(_), false) => "[]".bold(),
+            (Square::Ghost(_), false) => "::".reset(),
+        }
+    }
+
+    // rows of the board, as (styled) squares
+    fn board_rows(&self) -> impl Iterator<Item = Vec<StyledContent<&'static str>>> + '_ {
+        let (height, width) = self.tetris.size();
+        (0..height).map(move |i| {
+            (0..width)
+                .map(|j| Self::styled_square(self.tetris.cell(Point(i, j))))
+                .collect()
+        })
+    }
+
+    // the next piece, as two rows of (styled) squares
+    fn preview_rows(&self) -> [Vec<StyledContent<&'static str>>; 2] {
+        let piece = self.tetris.next_piece();
+        let shape = piece.shape();
+        [0, 1].map(|i| {
+            (0..4)
+                .map(|j| match shape.contains(&(i, j)) {
+                    true => Self::styled_square(Square::Block(piece)),
+                    false => "  ".reset(),
+                })
+                .collect()
+        })
+    }
+
+    // score, lines and level, plus [paused] while paused
+    fn status_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("{:<7}{}", locale::text("tetris.score"), self.tetris.score()),
+            format!("{:<7}{}", locale::text("tetris.lines"), self.tetris.lines()),
+            format!("{:<7}{}", locale::text("tetris.level"), self.tetris.level()),
+        ];
+        if self.ui.paused && !self.over {
+            lines.push(format!("[{}]", locale::text("tetris.paused")));
+        }
+        lines
+    }
+
+    // everything that goes next to the board, a line at a time
+    fn panel_lines(&self) -> Vec<String> {
+        let mut lines = vec![String::new(), locale::text("tetris.next").to_string()];
+        for row in self.preview_rows() {
+            lines.push(row.iter().map(ToString::to_string).collect());
+        }
+        lines.push(String::new());
+        lines.extend(self.status_lines());
+        lines.push(String::new());
+        lines.push(self.message.to_string());
+        if self.over {
+            lines.push(locale::text("tetris.exit").to_string());
+        } else {
+            let help = tetrisui::help_text().lines();
+            lines.extend(
+                help.map(|line| line.to_string().with(theme::color(Role::Muted)).to_string()),
+            );
+        }
+        lines
+    }
+}
+
+// Pretty-print
+impl fmt::Display for TetrisApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let edge = "─".repeat(SQUARE_COLS * WIDTH);
+        let muted = |s: String| s.with(theme::color(Role::Muted));
+        let mut board = vec![muted(format!("┌{edge}┐")).to_string()];
+        for row in self.board_rows() {
+            let squares: String = row.iter().map(ToString::to_string).collect();
+            let wall = muted("│".into());
+            board.push(format!("{wall}{squares}{wall}"));
+        }
+        board.push(muted(format!("└{edge}┘")).to_string());
+
+        let mut panel = self.panel_lines().into_iter();
+        for line in board {
+            write!(f, "{line}")?;
+            if let Some(text) = panel.next() {
+                write!(f, "{PANEL_GAP}{text}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl Game for TetrisApp {
+    fn name(&self) -> &'static str {
+        "tetris"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != TetrisUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            TetrisUIAction::Quit => self.over = true,
+            TetrisUIAction::Pause => self.ui.paused = !self.ui.paused,
+            TetrisUIAction::Move(mv) if !self.ui.paused => {
+                let res = self.tetris.make_move(mv);
+                self.handle_res(res);
+            }
+            TetrisUIAction::Move(_) | TetrisUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {
+        if self.ui.paused || self.over {
+            return;
+        }
+        let res = self.tetris.fall();
+        self.handle_res(res);
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.gravity())
+    }
+
+    // the board with its walls, and the help text next to it
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.tetris.size();
+        let board_cols = (SQUARE_COLS * width + 2 + PANEL_GAP.len()) as u16;
+        let panel_cols = tetrisui::help_text().lines().map(text_width).max();
+        (
+            board_cols + panel_cols.unwrap_or_default(),
+            (height + 2) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play Tetris, starting on the level chosen on the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: TetrisConfig = common::cli::setup(&args.common, "tetris")?;
+    let level = args.level.or(config.level).unwrap_or(1);
+    if !(1..=MAX_LEVEL).contains(&level) {
+        return Err(format!(
+            "bad [tetris] config: level {level} isn't between 1 and {MAX_LEVEL}"
+        ));
+    }
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("tetris seed {seed}");
+    let mut app = TetrisApp::new(level, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &tetrisui::ACTIONS)
+        .map_err(|e| format!("bad [tetris.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the board up until a key is pressed, unless the player quit
+    if app.outcome != Outcome::Quit {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
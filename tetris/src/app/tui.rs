@@ -0,0 +1,50 @@
+use ratatui::layout::{Constraint, Layout};
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+
+use super::{TetrisApp, PANEL_GAP};
+use crate::tetrisui;
+
+impl TuiGame for TetrisApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .board_rows()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows);
+        let (board_cols, _) = board.size();
+
+        let mut panel = StatusBar::new(tetrisui::help_text()).line(locale::text("tetris.next"));
+        for row in self.preview_rows() {
+            panel = panel.line(row.iter().map(tui::span).collect::<Line>());
+        }
+        panel = panel.line("");
+        for line in self.status_lines() {
+            panel = panel.line(line);
+        }
+        let message = tui::span(&self.message);
+        panel = panel.line("").line(message.clone()).line("");
+
+        let gap = PANEL_GAP.len() as u16;
+        let [board_area, _, panel_area] = Layout::horizontal([
+            Constraint::Length(board_cols),
+            Constraint::Length(gap),
+            Constraint::Min(0),
+        ])
+        .areas(frame.size());
+        frame.render_widget(board, board_area);
+        frame.render_widget(panel, panel_area);
+
+        if self.over {
+            let mut lines = vec![Line::from(locale::text("tetris.exit"))];
+            if !message.content.is_empty() {
+                lines.insert(0, Line::from(message));
+            }
+            let title = format!(" {} ", locale::text("tetris.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
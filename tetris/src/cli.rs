@@ -0,0 +1,30 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Tetris
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on losing and achievements")
+}))]
+pub struct Args {
+    /// Level to start on (pieces fall faster the higher it is) [default: 1]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=20))]
+    pub level: Option<u32>,
+
+    /// Random seed for dealing the pieces (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
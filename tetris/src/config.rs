@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [tetris] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TetrisConfig {
+    /// level to start on
+    pub level: Option<u32>,
+    /// keys for each action, e.g. hard_drop = ["space", "enter"]
+    pub keys: KeyMap,
+}
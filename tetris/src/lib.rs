@@ -0,0 +1,18 @@
+//! Tetris: the game core (a board, the falling piece and its rules), plus
+//! the crossterm frontend for playing it in a terminal
+
+mod tetris;
+pub use tetris::{Move, MoveResult, Piece, Square, Tetris, HEIGHT, WIDTH};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod tetrisui;
+#[cfg(feature = "terminal")]
+pub use app::{run, TetrisApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
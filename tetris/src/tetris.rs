@@ -0,0 +1,377 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// Standard board size (pieces spawn in the top rows)
+pub const HEIGHT: usize = 20;
+pub const WIDTH: usize = 10;
+
+// points for clearing 0-4 lines at once (times the level)
+const LINE_POINTS: [u32; 5] = [0, 100, 300, 500, 800];
+
+// lines to clear to go up a level
+const LINES_PER_LEVEL: u32 = 10;
+
+// offsets tried in turn when a rotation doesn't fit where it is, as (rows, cols)
+const KICKS: [(i32, i32); 6] = [(0, 0), (0, -1), (0, 1), (0, -2), (0, 2), (-1, 0)];
+
+/// The seven tetrominoes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Piece {
+    I,
+    O,
+    T,
+    S,
+    Z,
+    J,
+    L,
+}
+
+const PIECES: [Piece; 7] = [
+    Piece::I,
+    Piece::O,
+    Piece::T,
+    Piece::S,
+    Piece::Z,
+    Piece::J,
+    Piece::L,
+];
+
+impl Piece {
+    /// Cells the piece covers, unrotated, with its top row at row 0
+    pub fn shape(self) -> [(usize, usize); 4] {
+        self.cells(0).map(|(i, j)| {
+            let top = if self == Piece::I { 1 } else { 0 };
+            ((i - top) as usize, j as usize)
+        })
+    }
+
+    // side of the square box the piece turns in
+    fn box_size(self) -> i32 {
+        match self {
+            Piece::I => 4,
+            Piece::O => 2,
+            _ => 3,
+        }
+    }
+
+    // cells covered within the piece's box, after [rotation] quarter turns clockwise
+    fn cells(self, rotation: u8) -> [(i32, i32); 4] {
+        let cells = match self {
+            Piece::I => [(1, 0), (1, 1), (1, 2), (1, 3)],
+            Piece::O => [(0, 0), (0, 1), (1, 0), (1, 1)],
+            Piece::T => [(0, 1), (1, 0), (1, 1), (1, 2)],
+            Piece::S => [(0, 1), (0, 2), (1, 0), (1, 1)],
+            Piece::Z => [(0, 0), (0, 1), (1, 1), (1, 2)],
+            Piece::J => [(0, 0), (1, 0), (1, 1), (1, 2)],
+            Piece::L => [(0, 2), (1, 0), (1, 1), (1, 2)],
+        };
+        let n = self.box_size();
+        cells.map(|mut cell| {
+            for _ in 0..rotation % 4 {
+                cell = (cell.1, n - 1 - cell.0);
+            }
+            cell
+        })
+    }
+}
+
+/// Something the player can do with the falling piece
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Move {
+    Left,
+    Right,
+    RotateCw,
+    RotateCcw,
+    /// down one row
+    SoftDrop,
+    /// all the way down, landing it
+    HardDrop,
+}
+
+/// What a move (or the piece falling by itself) led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    Moved,
+    /// there's no room to move that way
+    Blocked,
+    /// the piece landed, clearing this many lines
+    Locked(u32),
+    /// the next piece has no room to come in, which ends the game
+    ToppedOut,
+}
+
+/// What's on one square of the board
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Square {
+    Empty,
+    /// part of a piece that's landed
+    Block(Piece),
+    /// part of the falling piece
+    Active(Piece),
+    /// where the falling piece would land
+    Ghost(Piece),
+}
+
+// the falling piece: its box's top left corner (which can be above the board)
+#[derive(Debug, Clone, Copy)]
+struct Active {
+    piece: Piece,
+    rotation: u8,
+    row: i32,
+    col: i32,
+}
+
+impl Active {
+    fn spawn(piece: Piece) -> Self {
+        Self {
+            piece,
+            rotation: 0,
+            row: if piece == Piece::I { -1 } else { 0 },
+            col: (WIDTH as i32 - piece.box_size()) / 2,
+        }
+    }
+
+    // cells covered on the board, as (row, col)
+    fn cells(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let cells = self.piece.cells(self.rotation);
+        cells.into_iter().map(|(i, j)| (self.row + i, self.col + j))
+    }
+
+    fn shifted(self, rows: i32, cols: i32) -> Self {
+        Self {
+            row: self.row + rows,
+            col: self.col + cols,
+            ..self
+        }
+    }
+}
+
+/// A Tetris board, the falling piece and the ones to come
+pub struct Tetris {
+    board: Vec<[Option<Piece>; WIDTH]>, // top row first
+    active: Active,
+    next: Piece,
+    bag: Vec<Piece>, // pieces left in the shuffled set of seven
+    start_level: u32,
+    score: u32,
+    lines: u32,
+    over: bool,
+    rng: StdRng,
+}
+
+impl Tetris {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Empty board, starting at [level]. Pieces come in shuffled sets of all
+    /// seven, and the same seed always deals them in the same order
+    pub fn new(level: u32, seed: u64) -> Self {
+        let mut tetris = Self {
+            board: vec![[None; WIDTH]; HEIGHT],
+            active: Active::spawn(Piece::I),
+            next: Piece::I,
+            bag: Vec::new(),
+            start_level: level.max(1),
+            score: 0,
+            lines: 0,
+            over: false,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        tetris.active = Active::spawn(tetris.deal());
+        tetris.next = tetris.deal();
+        tetris
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn make_move(&mut self, mv: Move) -> MoveResult {
+        if self.over {
+            return MoveResult::ToppedOut;
+        }
+        match mv {
+            Move::Left => self.shift(0, -1),
+            Move::Right => self.shift(0, 1),
+            Move::RotateCw => self.rotate(1),
+            Move::RotateCcw => self.rotate(3),
+            Move::SoftDrop => {
+                let res = self.fall();
+                if res == MoveResult::Moved {
+                    self.score += 1;
+                }
+                res
+            }
+            Move::HardDrop => {
+                let landing = self.landing();
+                self.score += 2 * (landing.row - self.active.row) as u32;
+                self.active = landing;
+                self.lock()
+            }
+        }
+    }
+
+    /// Move the piece down a row, landing it if it can't go any further
+    pub fn fall(&mut self) -> MoveResult {
+        if self.over {
+            return MoveResult::ToppedOut;
+        }
+        match self.shift(1, 0) {
+            MoveResult::Blocked => self.lock(),
+            res => res,
+        }
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Lines cleared so far
+    pub fn lines(&self) -> u32 {
+        self.lines
+    }
+
+    /// Goes up every ten lines
+    pub fn level(&self) -> u32 {
+        self.start_level + self.lines / LINES_PER_LEVEL
+    }
+
+    /// Piece that comes in after the falling one
+    pub fn next_piece(&self) -> Piece {
+        self.next
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // next piece from the bag, refilling it with all seven once it's empty
+    fn deal(&mut self) -> Piece {
+        if self.bag.is_empty() {
+            self.bag = PIECES.to_vec();
+            self.bag.shuffle(&mut self.rng);
+        }
+        self.bag.pop().expect("bag was just filled")
+    }
+
+    // whether [active] is on the board (or above it) without overlapping anything
+    fn fits(&self, active: &Active) -> bool {
+        active.cells().all(|(i, j)| {
+            (0..WIDTH as i32).contains(&j)
+                && i < HEIGHT as i32
+                && (i < 0 || self.board[i as usize][j as usize].is_none())
+        })
+    }
+
+    fn shift(&mut self, rows: i32, cols: i32) -> MoveResult {
+        let moved = self.active.shifted(rows, cols);
+        if !self.fits(&moved) {
+            return MoveResult::Blocked;
+        }
+        self.active = moved;
+        MoveResult::Moved
+    }
+
+    // turn [quarters] quarter turns clockwise, nudging the piece over if
+    // there's no room where it is
+    fn rotate(&mut self, quarters: u8) -> MoveResult {
+        let rotated = Active {
+            rotation: (self.active.rotation + quarters) % 4,
+            ..self.active
+        };
+        let kicked = KICKS
+            .iter()
+            .map(|&(rows, cols)| rotated.shifted(rows, cols))
+            .find(|active| self.fits(active));
+        match kicked {
+            Some(active) => {
+                self.active = active;
+                MoveResult::Moved
+            }
+            None => MoveResult::Blocked,
+        }
+    }
+
+    // where the falling piece would land if dropped
+    fn landing(&self) -> Active {
+        let mut landing = self.active;
+        while self.fits(&landing.shifted(1, 0)) {
+            landing = landing.shifted(1, 0);
+        }
+        landing
+    }
+
+    // land the falling piece, clear any full lines, and bring in the next piece
+    fn lock(&mut self) -> MoveResult {
+        // (landing sticking out the top ends the game)
+        if self.active.cells().any(|(i, _)| i < 0) {
+            debug!("topped out at score {}", self.score);
+            self.over = true;
+            return MoveResult::ToppedOut;
+        }
+        let piece = self.active.piece;
+        for (i, j) in self.active.cells() {
+            self.board[i as usize][j as usize] = Some(piece);
+        }
+
+        let level = self.level();
+        self.board.retain(|row| row.iter().any(Option::is_none));
+        let cleared = HEIGHT - self.board.len();
+        let mut empty = vec![[None; WIDTH]; cleared];
+        empty.append(&mut self.board);
+        self.board = empty;
+        self.lines += cleared as u32;
+        self.score += LINE_POINTS[cleared] * level;
+        if cleared > 0 {
+            debug!("cleared {cleared} lines, score {}", self.score);
+        }
+
+        self.active = Active::spawn(self.next);
+        self.next = self.deal();
+        if !self.fits(&self.active) {
+            debug!("topped out at score {}", self.score);
+            self.over = true;
+            return MoveResult::ToppedOut;
+        }
+        MoveResult::Locked(cleared as u32)
+    }
+}
+
+impl Core for Tetris {
+    type Action = Move;
+    type Outcome = MoveResult;
+    type Cell = Square;
+
+    fn size(&self) -> (usize, usize) {
+        (HEIGHT, WIDTH)
+    }
+
+    fn cell(&self, Point(i, j): Point) -> Square {
+        if let Some(piece) = self.board[i][j] {
+            return Square::Block(piece);
+        }
+        let at = (i as i32, j as i32);
+        let piece = self.active.piece;
+        if self.active.cells().any(|cell| cell == at) {
+            Square::Active(piece)
+        } else if !self.over && self.landing().cells().any(|cell| cell == at) {
+            Square::Ghost(piece)
+        } else {
+            Square::Empty
+        }
+    }
+
+    fn act(&mut self, mv: Move) -> MoveResult {
+        self.make_move(mv)
+    }
+
+    fn tick(&mut self) {
+        self.fall();
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+}
@@ -0,0 +1,104 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::tetris::Move;
+
+/// Help lines next to the board, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("tetris.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TetrisUIAction {
+    Wait,
+    Move(Move),
+    Pause,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, TetrisUIAction); 11] = [
+    (
+        KeyBinding::key(KeyCode::Left),
+        TetrisUIAction::Move(Move::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        TetrisUIAction::Move(Move::Right),
+    ),
+    (
+        KeyBinding::key(KeyCode::Up),
+        TetrisUIAction::Move(Move::RotateCw),
+    ),
+    (KeyBinding::char('x'), TetrisUIAction::Move(Move::RotateCw)),
+    (KeyBinding::char('z'), TetrisUIAction::Move(Move::RotateCcw)),
+    (
+        KeyBinding::key(KeyCode::Down),
+        TetrisUIAction::Move(Move::SoftDrop),
+    ),
+    (KeyBinding::char(' '), TetrisUIAction::Move(Move::HardDrop)),
+    (KeyBinding::char('p'), TetrisUIAction::Pause),
+    (KeyBinding::key(KeyCode::Esc), TetrisUIAction::Pause),
+    (KeyBinding::char('q'), TetrisUIAction::Quit),
+    (KeyBinding::ctrl('c'), TetrisUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, TetrisUIAction); 8] = [
+    ("left", TetrisUIAction::Move(Move::Left)),
+    ("right", TetrisUIAction::Move(Move::Right)),
+    ("rotate", TetrisUIAction::Move(Move::RotateCw)),
+    ("rotate_back", TetrisUIAction::Move(Move::RotateCcw)),
+    ("soft_drop", TetrisUIAction::Move(Move::SoftDrop)),
+    ("hard_drop", TetrisUIAction::Move(Move::HardDrop)),
+    ("pause", TetrisUIAction::Pause),
+    ("quit", TetrisUIAction::Quit),
+];
+
+pub struct TetrisUI {
+    pub paused: bool,
+    pub bindings: Bindings<TetrisUIAction>,
+}
+
+impl TetrisUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            paused: false,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> TetrisUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(TetrisUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> TetrisUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => TetrisUIAction::Wait,
+        }
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<TetrisUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
@@ -0,0 +1,361 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{Core, Game, Player, Point, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::TicTacToeConfig;
+use crate::minimax::Minimax;
+use crate::tictactoe::{Mark, MoveResult, TicTacToe};
+use crate::tictactoeui::{self, TicTacToeUI, TicTacToeUIAction};
+
+// line between rows of squares
+const ROW_EDGE: &str = "───┼───┼───";
+
+// lines of text under the board (gap, turn, score, message)
+const STATUS_ROWS: usize = 4;
+
+// how long the computer takes over its move (it knows straight away, but
+// answering instantly makes its mark hard to spot)
+const COMPUTER_DELAY: Duration = Duration::from_millis(400);
+
+/// Tic-tac-toe app (the game being played, the computer player if there is
+/// one, the score over every game so far, and UI state)
+pub struct TicTacToeApp {
+    game: TicTacToe,
+    ui: TicTacToeUI,
+    computer: Option<Minimax>, // none for two players at one keyboard
+    computer_mark: Mark,       // (swapping every game)
+    wins: (u32, u32),          // you and the computer's, or X and O's
+    draws: u32,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last game decided against the computer
+    over: bool,
+}
+
+impl TicTacToeApp {
+    /// Game against [computer] playing [computer_mark], or between two
+    /// people without one
+    pub fn new(computer: Option<Minimax>, computer_mark: Mark) -> Self {
+        Self {
+            game: TicTacToe::new(),
+            ui: TicTacToeUI::new(),
+            computer,
+            computer_mark,
+            wins: (0, 0),
+            draws: 0,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn is_computers_turn(&self) -> bool {
+        self.computer.is_some() && self.game.turn() == self.computer_mark && !self.game.is_over()
+    }
+
+    fn new_game(&mut self) {
+        self.game = TicTacToe::new();
+        self.computer_mark = self.computer_mark.other();
+        self.ui.set_cursor(Point(1, 1));
+        self.message = "".to_string().reset();
+    }
+
+    fn place(&mut self, p: Point) {
+        let res = self.game.place(p);
+        debug!(
+            "{:?} at ({}, {}): {res:?}",
+            self.game.turn().other(),
+            p.0,
+            p.1
+        );
+        self.handle_res(res);
+    }
+
+    fn handle_res(&mut self, res: MoveResult) {
+        let again = locale::text("tictactoe.again");
+        match res {
+            MoveResult::Won(mark) if self.computer.is_none() => {
+                bell::ring("win");
+                match mark {
+                    Mark::X => self.wins.0 += 1,
+                    Mark::O => self.wins.1 += 1,
+                }
+                let wins = locale::text("tictactoe.wins");
+                self.message = format!("{} {wins} {again}", mark_str(mark))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Won(mark) if mark == self.computer_mark => {
+                bell::ring("lose");
+                self.wins.1 += 1;
+                self.outcome = Outcome::Lost;
+                self.message = format!("{} {again}", locale::text("tictactoe.lose"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+            }
+            MoveResult::Won(_) => {
+                bell::ring("win");
+                self.wins.0 += 1;
+                self.outcome = Outcome::Won;
+                self.message = format!("{} {again}", locale::text("tictactoe.win"))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Success));
+            }
+            MoveResult::Draw => {
+                self.draws += 1;
+                self.message = format!("{} {again}", locale::text("tictactoe.draw")).bold();
+            }
+            MoveResult::Taken => {
+                bell::ring("invalid move");
+                self.message = locale::text("tictactoe.taken")
+                    .to_string()
+                    .with(theme::color(Role::Danger));
+            }
+            MoveResult::GameOver => bell::ring("invalid move"),
+            MoveResult::Placed => self.message = "".to_string().reset(),
+        }
+    }
+
+    // (styled) string for a square: its mark, or its number key if it's
+    // empty. The cursor is reversed, and a winning line highlighted
+    fn styled_square(&self, p: Point) -> StyledContent<String> {
+        let (text, role) = match self.game.cell(p) {
+            Some(Mark::X) => ("X".to_string(), Role::Marker),
+            Some(Mark::O) => ("O".to_string(), Role::Hidden),
+            None => ((3 * p.0 + p.1 + 1).to_string(), Role::Muted),
+        };
+        let styled = format!(" {text} ").with(theme::color(role));
+        let styled = match self.game.cell(p) {
+            Some(_) => styled.bold(),
+            None => styled,
+        };
+        if self
+            .game
+            .winning_line()
+            .is_some_and(|line| line.contains(&p))
+        {
+            return styled
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success));
+        }
+        if p == self.ui.get_cursor() && !self.game.is_over() {
+            return styled.reverse();
+        }
+        styled
+    }
+
+    /// Lines of the board, each a list of (styled) pieces
+    fn board_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let muted = |s: &str| s.to_string().with(theme::color(Role::Muted));
+        let mut lines = Vec::new();
+        for i in 0..3 {
+            if i > 0 {
+                lines.push(vec![muted(ROW_EDGE)]);
+            }
+            let mut line = Vec::new();
+            for j in 0..3 {
+                if j > 0 {
+                    line.push(muted("│"));
+                }
+                line.push(self.styled_square(Point(i, j)));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    // which side the player is on, and whose turn it is
+    fn turn_line(&self) -> String {
+        let mut parts = Vec::new();
+        if self.computer.is_some() {
+            let you = mark_str(self.computer_mark.other());
+            parts.push(format!("{} {you}", locale::text("tictactoe.playing")));
+        }
+        if !self.game.is_over() {
+            let turn = mark_str(self.game.turn());
+            parts.push(format!("{turn} {}", locale::text("tictactoe.to_move")));
+        }
+        parts.join("   ")
+    }
+
+    // games won by each side, and drawn
+    fn score_line(&self) -> String {
+        let (first, second) = match self.computer {
+            Some(_) => (
+                locale::text("tictactoe.you"),
+                locale::text("tictactoe.computer"),
+            ),
+            None => ("X", "O"),
+        };
+        format!(
+            "{first} {}   {second} {}   {} {}",
+            self.wins.0,
+            self.wins.1,
+            locale::text("tictactoe.draws"),
+            self.draws
+        )
+    }
+}
+
+fn mark_str(mark: Mark) -> &'static str {
+    match mark {
+        Mark::X => "X",
+        Mark::O => "O",
+    }
+}
+
+// Pretty-print
+impl fmt::Display for TicTacToeApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.board_lines() {
+            for piece in line {
+                write!(f, "{piece}")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        write!(f, "\r\n{}\r\n", self.turn_line())?;
+        write!(f, "{}\r\n", self.score_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", tictactoeui::help_text())
+    }
+}
+
+impl Game for TicTacToeApp {
+    fn name(&self) -> &'static str {
+        "tictactoe"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != TicTacToeUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            TicTacToeUIAction::Quit => self.over = true,
+            TicTacToeUIAction::NewGame => self.new_game(),
+            TicTacToeUIAction::Move(dir) => {
+                if self.ui.move_cursor(dir).is_err() {
+                    bell::ring("edge of the board");
+                }
+            }
+            // (the computer's turn is its own)
+            TicTacToeUIAction::Select | TicTacToeUIAction::Square(_)
+                if self.is_computers_turn() => {}
+            TicTacToeUIAction::Select => self.place(self.ui.get_cursor()),
+            TicTacToeUIAction::Square(k) => {
+                let p = Point(k / 3, k % 3);
+                self.ui.set_cursor(p);
+                self.place(p);
+            }
+            TicTacToeUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    // let the computer move, when it's its turn
+    fn tick(&mut self) {
+        if !self.is_computers_turn() {
+            return;
+        }
+        let Some(p) = self
+            .computer
+            .as_mut()
+            .and_then(|computer| computer.choose_action(&self.game))
+        else {
+            return;
+        };
+        self.place(p);
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        self.computer.is_some().then_some(COMPUTER_DELAY)
+    }
+
+    // the board, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = tictactoeui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        (
+            cols.max(text_width(ROW_EDGE)),
+            (5 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Play tic-tac-toe against the computer, or two people at one keyboard
+pub fn run(args: Args) -> Result<(), String> {
+    let config: TicTacToeConfig = common::cli::setup(&args.common, "tictactoe")?;
+
+    let computer = match args.two_player || config.two_player {
+        true => None,
+        false => {
+            let seed = args.seed.unwrap_or_else(rand::random);
+            info!("tictactoe seed {seed}");
+            Some(Minimax::new(seed))
+        }
+    };
+    let computer_mark = match args.second {
+        true => Mark::X,
+        false => Mark::O,
+    };
+    let mut app = TicTacToeApp::new(computer, computer_mark);
+    app.ui
+        .bindings
+        .remap(&config.keys, &tictactoeui::ACTIONS)
+        .map_err(|e| format!("bad [tictactoe.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Tic-tac-toe
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on taken squares, wins and achievements")
+}))]
+pub struct Args {
+    /// Two people take turns at the same keyboard, instead of playing the computer
+    #[arg(long)]
+    pub two_player: bool,
+
+    /// Let the computer go first in the first game (sides swap every game)
+    #[arg(long, conflicts_with = "two_player")]
+    pub second: bool,
+
+    /// Random seed for the computer's choice between equally good moves
+    /// (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,21 @@
+//! Tic-tac-toe: the game core (a 3x3 board and whose turn it is), a minimax
+//! computer player that never loses, plus the crossterm frontend for
+//! playing it in a terminal, against the computer or another person
+
+mod minimax;
+mod tictactoe;
+pub use minimax::Minimax;
+pub use tictactoe::{Mark, MoveResult, TicTacToe};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod tictactoeui;
+#[cfg(feature = "terminal")]
+pub use app::{run, TicTacToeApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
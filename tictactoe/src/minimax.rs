@@ -0,0 +1,73 @@
+use common::{Core, Player, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::tictactoe::{Mark, TicTacToe};
+
+/// Computer player that never loses: it looks at every way the game could
+/// go (there are few enough), assuming the other player plays their best
+/// too. It wins as soon as it can, holds off losing as long as it can, and
+/// picks at random between moves that are just as good
+pub struct Minimax {
+    rng: StdRng, // the same seed always picks the same way
+}
+
+impl Minimax {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Player<TicTacToe> for Minimax {
+    fn choose_action(&mut self, game: &TicTacToe) -> Option<Point> {
+        if game.is_over() {
+            return None;
+        }
+        let me = game.turn();
+        let scored: Vec<_> = game
+            .empty_squares()
+            .map(|p| {
+                let mut next = game.clone();
+                next.place(p);
+                (p, score(&next, me, 1))
+            })
+            .collect();
+        let best = scored.iter().map(|&(_, s)| s).max()?;
+        let best_moves: Vec<_> = scored
+            .into_iter()
+            .filter(|&(_, s)| s == best)
+            .map(|(p, _)| p)
+            .collect();
+        let &p = best_moves.choose(&mut self.rng)?;
+        debug!("minimax plays ({}, {}) for {best}", p.0, p.1);
+        Some(p)
+    }
+}
+
+// how good [game] is for [me], with best play from here on: positive if
+// [me] wins (more so the sooner), negative if [me] loses (less so the
+// later), and 0 for a draw. [depth] is how many moves have been looked ahead
+fn score(game: &TicTacToe, me: Mark, depth: i32) -> i32 {
+    match game.winner() {
+        Some(mark) if mark == me => return 10 - depth,
+        Some(_) => return depth - 10,
+        None if game.is_full() => return 0,
+        None => {}
+    }
+    let scores = game.empty_squares().map(|p| {
+        let mut next = game.clone();
+        next.place(p);
+        score(&next, me, depth + 1)
+    });
+    if game.turn() == me {
+        scores.max().unwrap_or_default()
+    } else {
+        scores.min().unwrap_or_default()
+    }
+}
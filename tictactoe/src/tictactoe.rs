@@ -0,0 +1,159 @@
+use common::{Core, Point};
+use log::debug;
+
+// rows, columns and diagonals, as square indices (a row at a time)
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+/// Mark a player puts down (X always goes first)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mark {
+    X,
+    O,
+}
+
+impl Mark {
+    /// The other player's mark
+    pub fn other(self) -> Self {
+        match self {
+            Mark::X => Mark::O,
+            Mark::O => Mark::X,
+        }
+    }
+}
+
+/// What a move led to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveResult {
+    Placed,
+    /// there's a mark on the square already
+    Taken,
+    /// the mark just placed made three in a row
+    Won(Mark),
+    /// the board filled up with nobody winning
+    Draw,
+    /// the game has already ended
+    GameOver,
+}
+
+/// A game of tic-tac-toe: the marks on the board and whose turn it is
+#[derive(Debug, Clone, PartialEq)]
+pub struct TicTacToe {
+    squares: [Option<Mark>; 9],
+    turn: Mark,
+    winner: Option<Mark>,
+}
+
+impl TicTacToe {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Empty board, with X to move
+    pub fn new() -> Self {
+        Self {
+            squares: [None; 9],
+            turn: Mark::X,
+            winner: None,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Put the mark of whoever's turn it is on [p]
+    pub fn place(&mut self, p: Point) -> MoveResult {
+        if self.is_over() {
+            return MoveResult::GameOver;
+        }
+        let k = index(p);
+        if self.squares[k].is_some() {
+            return MoveResult::Taken;
+        }
+        let mark = self.turn;
+        self.squares[k] = Some(mark);
+        self.turn = mark.other();
+        if self.winning_line().is_some() {
+            debug!("{mark:?} wins");
+            self.winner = Some(mark);
+            return MoveResult::Won(mark);
+        }
+        if self.is_full() {
+            return MoveResult::Draw;
+        }
+        MoveResult::Placed
+    }
+
+    /// Whose turn it is (once the game's over, whoever would have been next)
+    pub fn turn(&self) -> Mark {
+        self.turn
+    }
+
+    pub fn winner(&self) -> Option<Mark> {
+        self.winner
+    }
+
+    /// The three squares in a row, once someone has them
+    pub fn winning_line(&self) -> Option<[Point; 3]> {
+        let line = LINES.iter().find(|line| {
+            let [a, b, c] = line.map(|k| self.squares[k]);
+            a.is_some() && a == b && b == c
+        })?;
+        Some(line.map(point))
+    }
+
+    /// Squares nobody has marked yet
+    pub fn empty_squares(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..9).filter(|&k| self.squares[k].is_none()).map(point)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.squares.iter().all(Option::is_some)
+    }
+}
+
+impl Default for TicTacToe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Core for TicTacToe {
+    type Action = Point;
+    type Outcome = MoveResult;
+    type Cell = Option<Mark>;
+
+    fn size(&self) -> (usize, usize) {
+        (3, 3)
+    }
+
+    fn cell(&self, p: Point) -> Option<Mark> {
+        self.squares[index(p)]
+    }
+
+    fn act(&mut self, p: Point) -> MoveResult {
+        self.place(p)
+    }
+
+    fn is_over(&self) -> bool {
+        self.winner.is_some() || self.is_full()
+    }
+}
+
+fn index(Point(i, j): Point) -> usize {
+    assert!(i < 3 && j < 3, "({i}, {j}) is off the board");
+    i * 3 + j
+}
+
+fn point(k: usize) -> Point {
+    Point(k / 3, k % 3)
+}
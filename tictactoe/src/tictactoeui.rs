@@ -0,0 +1,133 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding, Point};
+
+/// Help lines under the board, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("tictactoe.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TicTacToeUIAction {
+    Wait,
+    Move(MoveDirection),
+    Select,
+    /// put a mark straight on square k (counting a row at a time from 0)
+    Square(usize),
+    NewGame,
+    Quit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, TicTacToeUIAction); 18] = [
+    (
+        KeyBinding::key(KeyCode::Up),
+        TicTacToeUIAction::Move(MoveDirection::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        TicTacToeUIAction::Move(MoveDirection::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        TicTacToeUIAction::Move(MoveDirection::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        TicTacToeUIAction::Move(MoveDirection::Right),
+    ),
+    (KeyBinding::char(' '), TicTacToeUIAction::Select),
+    (KeyBinding::key(KeyCode::Enter), TicTacToeUIAction::Select),
+    (KeyBinding::char('1'), TicTacToeUIAction::Square(0)),
+    (KeyBinding::char('2'), TicTacToeUIAction::Square(1)),
+    (KeyBinding::char('3'), TicTacToeUIAction::Square(2)),
+    (KeyBinding::char('4'), TicTacToeUIAction::Square(3)),
+    (KeyBinding::char('5'), TicTacToeUIAction::Square(4)),
+    (KeyBinding::char('6'), TicTacToeUIAction::Square(5)),
+    (KeyBinding::char('7'), TicTacToeUIAction::Square(6)),
+    (KeyBinding::char('8'), TicTacToeUIAction::Square(7)),
+    (KeyBinding::char('9'), TicTacToeUIAction::Square(8)),
+    (KeyBinding::char('n'), TicTacToeUIAction::NewGame),
+    (KeyBinding::char('q'), TicTacToeUIAction::Quit),
+    (KeyBinding::ctrl('c'), TicTacToeUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, TicTacToeUIAction); 7] = [
+    ("up", TicTacToeUIAction::Move(MoveDirection::Up)),
+    ("down", TicTacToeUIAction::Move(MoveDirection::Down)),
+    ("left", TicTacToeUIAction::Move(MoveDirection::Left)),
+    ("right", TicTacToeUIAction::Move(MoveDirection::Right)),
+    ("select", TicTacToeUIAction::Select),
+    ("new_game", TicTacToeUIAction::NewGame),
+    ("quit", TicTacToeUIAction::Quit),
+];
+
+pub struct TicTacToeUI {
+    cursor: Point,
+    pub bindings: Bindings<TicTacToeUIAction>,
+}
+
+impl TicTacToeUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cursor starting on the middle square
+    pub fn new() -> Self {
+        Self {
+            cursor: Point(1, 1),
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> TicTacToeUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(TicTacToeUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> TicTacToeUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => TicTacToeUIAction::Wait,
+        }
+    }
+
+    /// Move the cursor a square, unless that's off the board
+    pub fn move_cursor(&mut self, dir: MoveDirection) -> Result<(), String> {
+        let Point(i, j) = self.cursor;
+        let (i, j) = match dir {
+            MoveDirection::Up => (i.checked_sub(1), Some(j)),
+            MoveDirection::Down => (Some(i + 1).filter(|&i| i < 3), Some(j)),
+            MoveDirection::Left => (Some(i), j.checked_sub(1)),
+            MoveDirection::Right => (Some(i), Some(j + 1).filter(|&j| j < 3)),
+        };
+        let (Some(i), Some(j)) = (i, j) else {
+            return Err("already at the edge".into());
+        };
+        self.cursor = Point(i, j);
+        Ok(())
+    }
+
+    pub fn set_cursor(&mut self, p: Point) {
+        self.cursor = p;
+    }
+
+    pub fn get_cursor(&self) -> Point {
+        self.cursor
+    }
+}
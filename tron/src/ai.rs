@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+
+use common::{Core, Player, Point};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::tron::{Direction, Side, Tron};
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// Computer rider for one side: it turns towards whichever way leaves it
+/// the most room to ride in, keeping straight on when that's as good, and
+/// steers clear of the squares the other rider could take next
+pub struct Ai {
+    side: Side,
+    rng: StdRng, // for picking between equally roomy turns
+}
+
+impl Ai {
+    pub fn new(side: Side, seed: u64) -> Self {
+        Self {
+            side,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    // squares reachable from [start] without crossing a trail or [avoid]
+    // (counting [start] itself)
+    fn room(tron: &Tron, start: Point, avoid: &[Point]) -> usize {
+        let (height, width) = tron.size();
+        let mut seen = vec![false; height * width];
+        let mut queue = VecDeque::from([start]);
+        seen[start.0 * width + start.1] = true;
+        let mut count = 0;
+        while let Some(p) = queue.pop_front() {
+            count += 1;
+            for dir in DIRECTIONS {
+                let Some(q) = tron.ahead(p, dir) else {
+                    continue;
+                };
+                if seen[q.0 * width + q.1] || tron.is_blocked(q) || avoid.contains(&q) {
+                    continue;
+                }
+                seen[q.0 * width + q.1] = true;
+                queue.push_back(q);
+            }
+        }
+        count
+    }
+}
+
+impl Player<Tron> for Ai {
+    /// Turn to make on this step, if any (None to keep straight on)
+    fn choose_action(&mut self, tron: &Tron) -> Option<(Side, Direction)> {
+        if tron.is_round_over() {
+            return None;
+        }
+        let (head, dir) = (tron.head(self.side), tron.direction(self.side));
+        let other = self.side.opposite();
+        let contested: Vec<Point> = DIRECTIONS
+            .iter()
+            .filter_map(|&d| tron.ahead(tron.head(other), d))
+            .collect();
+
+        // (room left after each way it can go, with a square the other
+        // rider might also take counting as hardly any room at all)
+        let options: Vec<(Direction, usize)> = DIRECTIONS
+            .into_iter()
+            .filter(|&d| d != dir.opposite())
+            .filter_map(|d| {
+                let next = tron.ahead(head, d).filter(|&p| !tron.is_blocked(p))?;
+                let room = match contested.contains(&next) {
+                    true => 1,
+                    false => Self::room(tron, next, &contested),
+                };
+                Some((d, room))
+            })
+            .collect();
+        let best = options.iter().map(|&(_, room)| room).max()?;
+        if options.contains(&(dir, best)) {
+            return None;
+        }
+        let best: Vec<Direction> = options
+            .iter()
+            .filter(|&&(_, room)| room == best)
+            .map(|&(d, _)| d)
+            .collect();
+        let turn = best[self.rng.gen_range(0..best.len())];
+        Some((self.side, turn))
+    }
+}
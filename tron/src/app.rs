@@ -0,0 +1,345 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Core, Game, Player, Point, TerminalGuard};
+
+use crate::ai::Ai;
+use crate::cli::Args;
+use crate::config::TronConfig;
+use crate::tron::{Direction, Side, Square, Step, Tron};
+use crate::tronui::{self, TronUI, TronUIAction};
+
+// speed when neither the command line nor the config file sets one
+const DEFAULT_TICK: Duration = Duration::from_millis(100);
+
+// rounds to win when nothing else sets it
+const DEFAULT_ROUNDS: u32 = 3;
+
+// fewest and most rounds a game can go to
+const MIN_ROUNDS: u32 = 1;
+const MAX_ROUNDS: u32 = 99;
+
+// terminal columns each square takes up (so squares come out roughly square)
+const SQUARE_COLS: usize = 2;
+
+// lines of text under the grid (score, message, exit prompt)
+const STATUS_ROWS: usize = 3;
+
+/// Tron app (game + UI state, and the computer rider if there is one)
+pub struct TronApp {
+    tron: Tron,
+    ui: TronUI,
+    computer: Option<Ai>, // none for two players at one keyboard
+    tick: Duration,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // quit, unless a game against the computer was decided
+    over: bool,
+}
+
+impl TronApp {
+    pub fn new(tron: Tron, computer: Option<Ai>, tick: Duration) -> Self {
+        Self {
+            tron,
+            ui: TronUI::new(),
+            computer,
+            tick,
+            message: locale::text("tron.start").to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    // turn a cycle for the person at the keyboard (against the computer,
+    // either set of keys steers theirs)
+    fn turn(&mut self, side: Side, dir: Direction) {
+        let side = match &self.computer {
+            Some(ai) if ai.side() == side => side.opposite(),
+            _ => side,
+        };
+        self.tron.turn(side, dir);
+    }
+
+    fn handle_step(&mut self, step: Step) {
+        match step {
+            Step::Won(side) => {
+                self.over = true;
+                let (won, text) = match &self.computer {
+                    Some(ai) if ai.side() == side => (false, locale::text("tron.lose")),
+                    Some(_) => (true, locale::text("tron.win")),
+                    None => (
+                        true,
+                        self.side_text(side, "tron.left_wins", "tron.right_wins"),
+                    ),
+                };
+                if self.computer.is_some() {
+                    self.outcome = if won { Outcome::Won } else { Outcome::Lost };
+                }
+                bell::ring(if won { "win" } else { "lose" });
+                let role = if won { Role::Success } else { Role::Danger };
+                self.message = text
+                    .to_string()
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(role));
+            }
+            Step::Round(side) => {
+                bell::ring("crash");
+                let text = match &self.computer {
+                    Some(ai) if ai.side() == side => locale::text("tron.computer_round"),
+                    Some(_) => locale::text("tron.your_round"),
+                    None => self.side_text(side, "tron.left_round", "tron.right_round"),
+                };
+                self.end_round(text);
+            }
+            Step::Draw => {
+                bell::ring("crash");
+                self.end_round(locale::text("tron.draw"));
+            }
+            Step::Moved => {}
+        }
+    }
+
+    // say who took the round, and how to start the next one
+    fn end_round(&mut self, text: &str) {
+        debug!(
+            "round over, {} : {}",
+            self.tron.score(Side::Left),
+            self.tron.score(Side::Right)
+        );
+        self.message = format!("{text} {}", locale::text("tron.next_round")).bold();
+    }
+
+    // text for [side]: "you" or "computer" against the computer, or
+    // [left] and [right] (locale keys) between two people
+    fn side_text(&self, side: Side, left: &str, right: &str) -> &'static str {
+        match (&self.computer, side) {
+            (Some(ai), side) if ai.side() == side => locale::text("tron.computer"),
+            (Some(_), _) => locale::text("tron.you"),
+            (None, Side::Left) => locale::text(left),
+            (None, Side::Right) => locale::text(right),
+        }
+    }
+
+    // (styled) string for a square: blocks of color, or ASCII without color
+    // (with a cycle that's crashed standing out)
+    fn styled_square(&self, sq: Square) -> StyledContent<&'static str> {
+        let (role, plain, block) = match sq {
+            Square::Empty => return "  ".reset(),
+            Square::Trail(Side::Left) => (Role::Accent, "==", "▒▒"),
+            Square::Trail(Side::Right) => (Role::Marker, "::", "▒▒"),
+            Square::Cycle(side) if self.tron.has_crashed(side) => (Role::Danger, "XX", "██"),
+            Square::Cycle(Side::Left) => (Role::Accent, "@@", "██"),
+            Square::Cycle(Side::Right) => (Role::Marker, "&&", "██"),
+        };
+        if style::color_enabled() {
+            block.with(theme::color(role))
+        } else {
+            plain.bold()
+        }
+    }
+
+    // rounds won on each side and rounds to win, plus [paused] while paused
+    fn status(&self) -> String {
+        let mut status = format!(
+            "{} {} : {} {}   {} {}",
+            self.side_text(Side::Left, "tron.left", "tron.right"),
+            self.tron.score(Side::Left),
+            self.tron.score(Side::Right),
+            self.side_text(Side::Right, "tron.left", "tron.right"),
+            locale::text("tron.first_to"),
+            self.tron.rounds()
+        );
+        if self.ui.paused && !self.over && !self.tron.is_round_over() {
+            status += &format!(" [{}]", locale::text("tron.paused"));
+        }
+        status
+    }
+
+    // rows of the grid, as (styled) squares
+    fn grid_rows(&self) -> impl Iterator<Item = Vec<StyledContent<&'static str>>> + '_ {
+        let (height, width) = self.tron.size();
+        (0..height).map(move |i| {
+            (0..width)
+                .map(|j| self.styled_square(self.tron.cell(Point(i, j))))
+                .collect()
+        })
+    }
+}
+
+// Pretty-print
+impl fmt::Display for TronApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (_, width) = self.tron.size();
+        let edge = "─".repeat(SQUARE_COLS * width);
+        let wall = "│".with(theme::color(Role::Muted));
+        write!(
+            f,
+            "{}\r\n",
+            format!("┌{edge}┐").with(theme::color(Role::Muted))
+        )?;
+        for row in self.grid_rows() {
+            write!(f, "{wall}")?;
+            for sq in row {
+                write!(f, "{sq}")?;
+            }
+            write!(f, "{wall}\r\n")?;
+        }
+        write!(
+            f,
+            "{}\r\n",
+            format!("└{edge}┘").with(theme::color(Role::Muted))
+        )?;
+
+        write!(f, "{}\r\n", self.status())?;
+        write!(f, "{}\r\n", self.message)?;
+        if self.over {
+            write!(f, "{}\r\n", locale::text("tron.exit"))?;
+        } else {
+            write!(f, "{}\r\n", tronui::help_text(self.computer.is_some()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Game for TronApp {
+    fn name(&self) -> &'static str {
+        "tron"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != TronUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            TronUIAction::Quit => self.over = true,
+            // (between rounds, this starts the next one)
+            TronUIAction::Pause if self.tron.is_round_over() => {
+                self.tron.next_round();
+                self.ui.paused = false;
+            }
+            TronUIAction::Pause => self.ui.paused = !self.ui.paused,
+            // turning also gets the game going again
+            TronUIAction::Turn(side, dir) if !self.tron.is_round_over() => {
+                self.ui.paused = false;
+                self.turn(side, dir);
+            }
+            TronUIAction::Turn(..) | TronUIAction::Wait => {}
+        }
+        if !self.ui.paused && !self.over && !self.tron.is_round_over() {
+            self.message = "".to_string().reset();
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {
+        if self.ui.paused || self.over || self.tron.is_round_over() {
+            return;
+        }
+        if let Some((side, dir)) = self
+            .computer
+            .as_mut()
+            .and_then(|computer| computer.choose_action(&self.tron))
+        {
+            self.tron.turn(side, dir);
+        }
+        let step = self.tron.step();
+        self.handle_step(step);
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(self.tick)
+    }
+
+    // the grid with its walls, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let (height, width) = self.tron.size();
+        let cols = (SQUARE_COLS * width + 2) as u16;
+        let help = tronui::help_text(self.computer.is_some());
+        (
+            cols.max(text_width(help)),
+            (height + 2 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Ride light cycles, two people at one keyboard or one against the computer
+pub fn run(args: Args) -> Result<(), String> {
+    let config: TronConfig = common::cli::setup(&args.common, "tron")?;
+
+    let rounds = args.rounds.or(config.rounds).unwrap_or(DEFAULT_ROUNDS);
+    if !(MIN_ROUNDS..=MAX_ROUNDS).contains(&rounds) {
+        return Err(format!(
+            "bad [tron] config: rounds {rounds} (goes from {MIN_ROUNDS} to {MAX_ROUNDS})"
+        ));
+    }
+    let tick = args
+        .tick_ms
+        .or(config.tick_ms)
+        .map_or(DEFAULT_TICK, Duration::from_millis);
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("tron seed {seed}, to {rounds} rounds");
+    let computer = (args.ai || config.ai).then(|| Ai::new(Side::Right, seed));
+    let tron = Tron::new(args.height as usize, args.width as usize, rounds);
+    let mut app = TronApp::new(tron, computer, tick);
+    app.ui
+        .bindings
+        .remap(&config.keys, &tronui::ACTIONS)
+        .map_err(|e| format!("bad [tron.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    // leave the grid up until a key is pressed, unless the players quit
+    if app.tron.winner().is_some() {
+        app.ui.wait_for_action_block().ok();
+    }
+    Ok(())
+}
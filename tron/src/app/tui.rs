@@ -0,0 +1,38 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::locale;
+use common::tui::{self, Board, Dialog, StatusBar, TuiGame};
+
+use super::TronApp;
+use crate::tron::Side;
+use crate::tronui;
+
+impl TuiGame for TronApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .grid_rows()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let title = format!(
+            " {} : {} ",
+            self.tron.score(Side::Left),
+            self.tron.score(Side::Right)
+        );
+        let board = Board::new(rows).title(title);
+
+        let message = tui::span(&self.message);
+        let status = StatusBar::new(tronui::help_text(self.computer.is_some()))
+            .line(self.status())
+            .line(message.clone());
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+
+        if self.over && self.tron.winner().is_some() {
+            let lines = vec![Line::from(message), Line::from(locale::text("tron.exit"))];
+            let title = format!(" {} ", locale::text("tron.game_over"));
+            frame.render_widget(Dialog::new(&title, lines), board_area);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Tron light cycles
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on crashes, winning and achievements")
+}))]
+pub struct Args {
+    /// Grid height (in squares)
+    #[arg(long, default_value_t = 18, value_parser = clap::value_parser!(u16).range(3..))]
+    pub height: u16,
+
+    /// Grid width (in squares)
+    #[arg(long, default_value_t = 36, value_parser = clap::value_parser!(u16).range(8..))]
+    pub width: u16,
+
+    /// Rounds to win, from 1 to 99 [default: 3, unless the config file
+    /// picks another]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=99))]
+    pub rounds: Option<u32>,
+
+    /// Milliseconds between steps [default: 100]
+    #[arg(long)]
+    pub tick_ms: Option<u64>,
+
+    /// Have the computer ride the right cycle (the arrow keys steer the
+    /// left one too)
+    #[arg(long)]
+    pub ai: bool,
+
+    /// Random seed for the computer's choice between equally good turns
+    /// (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
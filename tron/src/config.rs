@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [tron] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TronConfig {
+    /// rounds to win
+    pub rounds: Option<u32>,
+    /// milliseconds between steps
+    pub tick_ms: Option<u64>,
+    /// the computer riding the right cycle
+    pub ai: bool,
+    /// keys for each action, e.g. left_up = ["w", "k"]
+    pub keys: KeyMap,
+}
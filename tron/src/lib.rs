@@ -0,0 +1,22 @@
+//! Tron: the game core (two light cycles on a walled-in grid, and the
+//! trails they leave behind), a computer rider for either side, plus the
+//! crossterm frontend for two people at one keyboard, or one against the
+//! computer
+
+mod ai;
+mod tron;
+pub use ai::Ai;
+pub use tron::{Direction, Side, Square, Step, Tron};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod tronui;
+#[cfg(feature = "terminal")]
+pub use app::{run, TronApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
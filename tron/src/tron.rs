@@ -0,0 +1,275 @@
+use std::collections::VecDeque;
+
+use common::{Core, Point};
+use log::debug;
+
+// turns remembered ahead of each cycle, so quick double turns aren't lost
+const MAX_QUEUED_TURNS: usize = 2;
+
+/// Direction a cycle is riding in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// One of the two riders, by the side of the grid they start on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn opposite(self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// What's on one square of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Square {
+    Empty,
+    Trail(Side),
+    Cycle(Side),
+}
+
+/// What happened on a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Moved,
+    /// the other side crashed, and this one takes the round
+    Round(Side),
+    /// both crashed on the same step
+    Draw,
+    /// this side took the last round it needed
+    Won(Side),
+}
+
+/// Light cycles: two riders on a walled-in grid, each leaving a wall of
+/// trail behind it, until one runs into a wall or a trail (its own or the
+/// other's). Played over rounds, to a number of rounds won
+pub struct Tron {
+    height: usize,
+    width: usize,
+    rounds: u32,               // to win
+    trails: Vec<Option<Side>>, // row by row, whose trail is on each square
+    heads: [Point; 2],         // (on the end of their trails)
+    dirs: [Direction; 2],
+    turns: [VecDeque<Direction>; 2], // turns to take on the next steps
+    crashed: [bool; 2],
+    scores: [u32; 2], // rounds won
+    winner: Option<Side>,
+}
+
+impl Tron {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Grid [height] rows by [width] columns, played to [rounds] rounds won
+    pub fn new(height: usize, width: usize, rounds: u32) -> Self {
+        assert!(height >= 3 && width >= 8, "grid too small for light cycles");
+        let mut tron = Self {
+            height,
+            width,
+            rounds,
+            trails: vec![None; height * width],
+            heads: [Point(0, 0); 2],
+            dirs: [Direction::Right, Direction::Left],
+            turns: [VecDeque::new(), VecDeque::new()],
+            crashed: [false; 2],
+            scores: [0, 0],
+            winner: None,
+        };
+        tron.next_round();
+        tron
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Clear the grid, and put the cycles back at the start, facing each
+    /// other across the middle row
+    pub fn next_round(&mut self) {
+        if self.winner.is_some() {
+            return;
+        }
+        let (i, j) = (self.height / 2, self.width / 4);
+        self.trails.fill(None);
+        self.heads = [Point(i, j), Point(i, self.width - 1 - j)];
+        self.dirs = [Direction::Right, Direction::Left];
+        self.turns.iter_mut().for_each(VecDeque::clear);
+        self.crashed = [false; 2];
+        for side in [Side::Left, Side::Right] {
+            let k = self.index(self.heads[side.index()]);
+            self.trails[k] = Some(side);
+        }
+    }
+
+    /// Have [side] head for [dir] from its next step on (turning straight
+    /// back is ignored)
+    pub fn turn(&mut self, side: Side, dir: Direction) {
+        let turns = &self.turns[side.index()];
+        let last = turns.back().copied().unwrap_or(self.dirs[side.index()]);
+        if dir == last || dir == last.opposite() || turns.len() == MAX_QUEUED_TURNS {
+            return;
+        }
+        self.turns[side.index()].push_back(dir);
+    }
+
+    /// Move both cycles forward a square, crashing the ones that run into
+    /// a wall, a trail, or each other
+    pub fn step(&mut self) -> Step {
+        if self.winner.is_some() || self.is_round_over() {
+            return Step::Moved;
+        }
+        let mut next = [None; 2];
+        for side in [Side::Left, Side::Right] {
+            let k = side.index();
+            if let Some(dir) = self.turns[k].pop_front() {
+                self.dirs[k] = dir;
+            }
+            next[k] = self.ahead(self.heads[k], self.dirs[k]);
+            self.crashed[k] = match next[k] {
+                Some(p) => self.trails[self.index(p)].is_some(),
+                None => true,
+            };
+        }
+        // (riding into the same square crashes both)
+        if next[0].is_some() && next[0] == next[1] {
+            self.crashed = [true, true];
+        }
+        for side in [Side::Left, Side::Right] {
+            if let (false, Some(p)) = (self.crashed[side.index()], next[side.index()]) {
+                self.heads[side.index()] = p;
+                let k = self.index(p);
+                self.trails[k] = Some(side);
+            }
+        }
+
+        let round = match self.crashed {
+            [false, false] => return Step::Moved,
+            [true, true] => None,
+            [true, false] => Some(Side::Right),
+            [false, true] => Some(Side::Left),
+        };
+        debug!("round over, {round:?} takes it");
+        let Some(side) = round else {
+            return Step::Draw;
+        };
+        self.scores[side.index()] += 1;
+        if self.scores[side.index()] >= self.rounds {
+            self.winner = Some(side);
+            return Step::Won(side);
+        }
+        Step::Round(side)
+    }
+
+    /// Square a cycle at [p] riding towards [dir] moves into next (None if
+    /// that's off the grid)
+    pub fn ahead(&self, Point(i, j): Point, dir: Direction) -> Option<Point> {
+        let (i, j) = match dir {
+            Direction::Up => (i.checked_sub(1)?, j),
+            Direction::Down => (i + 1, j),
+            Direction::Left => (i, j.checked_sub(1)?),
+            Direction::Right => (i, j + 1),
+        };
+        (i < self.height && j < self.width).then_some(Point(i, j))
+    }
+
+    /// Whether there's a trail on [p]
+    pub fn is_blocked(&self, p: Point) -> bool {
+        self.trails[self.index(p)].is_some()
+    }
+
+    pub fn head(&self, side: Side) -> Point {
+        self.heads[side.index()]
+    }
+
+    pub fn direction(&self, side: Side) -> Direction {
+        self.dirs[side.index()]
+    }
+
+    pub fn has_crashed(&self, side: Side) -> bool {
+        self.crashed[side.index()]
+    }
+
+    /// Whether a cycle has crashed, and the grid is waiting on the next round
+    pub fn is_round_over(&self) -> bool {
+        self.crashed.contains(&true)
+    }
+
+    /// Rounds [side] has won
+    pub fn score(&self, side: Side) -> u32 {
+        self.scores[side.index()]
+    }
+
+    /// Rounds won to win
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub fn winner(&self) -> Option<Side> {
+        self.winner
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    fn index(&self, Point(i, j): Point) -> usize {
+        i * self.width + j
+    }
+}
+
+impl Core for Tron {
+    type Action = (Side, Direction);
+    type Outcome = ();
+    type Cell = Square;
+
+    fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    fn cell(&self, p: Point) -> Square {
+        match self.trails[self.index(p)] {
+            Some(side) if self.heads[side.index()] == p => Square::Cycle(side),
+            Some(side) => Square::Trail(side),
+            None => Square::Empty,
+        }
+    }
+
+    fn act(&mut self, (side, dir): (Side, Direction)) {
+        self.turn(side, dir);
+    }
+
+    fn tick(&mut self) {
+        self.step();
+    }
+
+    fn is_over(&self) -> bool {
+        self.winner.is_some()
+    }
+}
@@ -0,0 +1,137 @@
+use std::io;
+
+use crossterm::event::{read, Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::tron::{Direction, Side};
+
+/// Help lines under the grid, in the current language (with the computer
+/// on the right, both sets of keys steer the left cycle)
+pub fn help_text(ai: bool) -> &'static str {
+    match ai {
+        true => locale::text("tron.help_ai"),
+        false => locale::text("tron.help"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TronUIAction {
+    Wait,
+    Turn(Side, Direction),
+    /// pause or carry on, or start the next round
+    Pause,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, TronUIAction); 12] = [
+    (
+        KeyBinding::char('w'),
+        TronUIAction::Turn(Side::Left, Direction::Up),
+    ),
+    (
+        KeyBinding::char('s'),
+        TronUIAction::Turn(Side::Left, Direction::Down),
+    ),
+    (
+        KeyBinding::char('a'),
+        TronUIAction::Turn(Side::Left, Direction::Left),
+    ),
+    (
+        KeyBinding::char('d'),
+        TronUIAction::Turn(Side::Left, Direction::Right),
+    ),
+    (
+        KeyBinding::key(KeyCode::Up),
+        TronUIAction::Turn(Side::Right, Direction::Up),
+    ),
+    (
+        KeyBinding::key(KeyCode::Down),
+        TronUIAction::Turn(Side::Right, Direction::Down),
+    ),
+    (
+        KeyBinding::key(KeyCode::Left),
+        TronUIAction::Turn(Side::Right, Direction::Left),
+    ),
+    (
+        KeyBinding::key(KeyCode::Right),
+        TronUIAction::Turn(Side::Right, Direction::Right),
+    ),
+    (KeyBinding::char(' '), TronUIAction::Pause),
+    (KeyBinding::char('p'), TronUIAction::Pause),
+    (KeyBinding::char('q'), TronUIAction::Quit),
+    (KeyBinding::ctrl('c'), TronUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, TronUIAction); 10] = [
+    ("left_up", TronUIAction::Turn(Side::Left, Direction::Up)),
+    ("left_down", TronUIAction::Turn(Side::Left, Direction::Down)),
+    ("left_left", TronUIAction::Turn(Side::Left, Direction::Left)),
+    (
+        "left_right",
+        TronUIAction::Turn(Side::Left, Direction::Right),
+    ),
+    ("right_up", TronUIAction::Turn(Side::Right, Direction::Up)),
+    (
+        "right_down",
+        TronUIAction::Turn(Side::Right, Direction::Down),
+    ),
+    (
+        "right_left",
+        TronUIAction::Turn(Side::Right, Direction::Left),
+    ),
+    (
+        "right_right",
+        TronUIAction::Turn(Side::Right, Direction::Right),
+    ),
+    ("pause", TronUIAction::Pause),
+    ("quit", TronUIAction::Quit),
+];
+
+pub struct TronUI {
+    pub paused: bool,
+    pub bindings: Bindings<TronUIAction>,
+}
+
+impl TronUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Starts out paused, until the first turn
+    pub fn new() -> Self {
+        Self {
+            paused: true,
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> TronUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(TronUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> TronUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => TronUIAction::Wait,
+        }
+    }
+
+    // block until a key is pressed
+    pub fn wait_for_action_block(&self) -> io::Result<TronUIAction> {
+        loop {
+            if let Event::Key(key_event) = read()? {
+                return Ok(self.match_key_to_action(key_event));
+            }
+        }
+    }
+}
@@ -0,0 +1,406 @@
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crossterm::event::Event;
+use crossterm::style::{StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info, warn};
+
+use common::bell;
+use common::locale;
+use common::replay;
+use common::stats::{Outcome, Stats, TypingTest, RECENT_TESTS};
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{style, Game, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::TypingConfig;
+use crate::texts::{self, SENTENCES, WORDS};
+use crate::typing::{Letter, Typing, TICKS_PER_SECOND};
+use crate::typingui::{self, TypingUI, TypingUIAction};
+
+// time between ticks (one tick of the test's clock)
+const TICK: Duration = Duration::from_millis(1000 / TICKS_PER_SECOND as u64);
+
+// seconds a test runs for, unless picked, and the fewest and most there
+// can be
+const DEFAULT_SECONDS: u32 = 60;
+const MIN_SECONDS: u32 = 15;
+const MAX_SECONDS: u32 = 600;
+
+// lines of the text shown at a time (the one before the cursor's, the
+// cursor's, and the one after)
+const TEXT_ROWS: usize = 3;
+
+// past tests drawn on the progress line
+const SHOWN_TESTS: usize = 20;
+
+// bars for the progress line, lowest to highest (and without UTF-8)
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const ASCII_BARS: [char; 8] = ['_', '.', ',', ':', '-', '=', '+', '#'];
+
+// lines of text under the box (status, progress, message)
+const STATUS_ROWS: usize = 3;
+
+/// Typing trainer app (the test going on, the results of the ones before
+/// it, and UI state)
+pub struct TypingApp {
+    typing: Typing,
+    ui: TypingUI,
+    width: usize,             // of the text, in columns
+    history: Vec<TypingTest>, // oldest first
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // won once a test's been finished
+    over: bool,
+}
+
+impl TypingApp {
+    /// Tests on [typing]'s text, wrapped [width] columns wide, following on
+    /// from the results in [history]
+    pub fn new(typing: Typing, width: usize, history: Vec<TypingTest>) -> Self {
+        Self {
+            typing,
+            ui: TypingUI::new(),
+            width,
+            history,
+            message: locale::text("typing.start").to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_test(&mut self) {
+        debug!("new test");
+        self.typing.restart();
+        self.message = locale::text("typing.start").to_string().reset();
+    }
+
+    // keep the result of the test that's just run out of time (in the
+    // stats too, unless this is a replay of one that's been had already)
+    fn finish(&mut self) {
+        bell::ring("time up");
+        self.outcome = Outcome::Won;
+        let (wpm, accuracy) = (self.typing.wpm(), self.typing.accuracy());
+        let mut message = format!(
+            "{} {wpm:.0} {}, {accuracy:.0}% {}",
+            locale::text("typing.time_up"),
+            locale::text("typing.wpm"),
+            locale::text("typing.accuracy")
+        );
+        if wpm > self.best_wpm().unwrap_or_default() {
+            bell::ring("high score");
+            message += &format!("  {}", locale::text("typing.new_best"));
+        }
+        self.message = message
+            .bold()
+            .with(theme::color(Role::Text))
+            .on(theme::color(Role::Success));
+
+        let seconds = u64::from(self.typing.seconds());
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let test = TypingTest {
+            started: now.saturating_sub(seconds),
+            seconds,
+            wpm,
+            accuracy,
+        };
+        if !replay::is_replaying() {
+            // (like the rest of the stats, it isn't worth failing a test over)
+            if let Err(e) = Stats::add_typing_test(test.clone()) {
+                warn!("failed to save the test: {e}");
+            }
+        }
+        self.history.push(test);
+    }
+
+    fn best_wpm(&self) -> Option<f64> {
+        self.history.iter().map(|test| test.wpm).reduce(f64::max)
+    }
+
+    // (styled) character of the text at [index]: dim until it's typed, red
+    // where it was typed wrong, and the cursor on the next one to type
+    fn styled_letter(&self, index: usize) -> StyledContent<String> {
+        let styled = match self.typing.letter(index) {
+            Letter::Untyped(c) if style::color_enabled() => {
+                c.to_string().with(theme::color(Role::Muted))
+            }
+            Letter::Untyped(c) => c.to_string().dim(),
+            Letter::Right(c) => c.to_string().with(theme::color(Role::Text)),
+            // (a space typed wrong shows as an underscore, to have
+            // something to see)
+            Letter::Wrong(c) => {
+                let c = if c == ' ' { '_' } else { c };
+                if style::color_enabled() {
+                    c.to_string()
+                        .with(theme::color(Role::Text))
+                        .on(theme::color(Role::Danger))
+                } else {
+                    c.to_string().bold().underlined()
+                }
+            }
+        };
+        if index == self.typing.cursor() && !self.typing.is_over() {
+            styled.reverse()
+        } else {
+            styled
+        }
+    }
+
+    // rows of the text around the cursor, as (styled) characters, each
+    // padded out to the width
+    fn text_rows(&self) -> Vec<Vec<StyledContent<String>>> {
+        let lines = wrap(self.typing.text(), self.width);
+        let cursor = self.typing.cursor();
+        let at = lines
+            .iter()
+            .position(|line| line.contains(&cursor))
+            .unwrap_or_default();
+        let first = at.saturating_sub(1);
+        (first..first + TEXT_ROWS)
+            .map(|k| {
+                let line = lines.get(k).cloned().unwrap_or_default();
+                let padding = " ".repeat(self.width - line.len()).reset();
+                line.map(|index| self.styled_letter(index))
+                    .chain([padding])
+                    .collect()
+            })
+            .collect()
+    }
+
+    // time left, words a minute, accuracy and mistakes not fixed yet
+    fn status(&self) -> String {
+        let left = self.typing.seconds_left();
+        format!(
+            "{} {}:{:02}   {} {:.0}   {} {:.0}%   {} {}",
+            locale::text("typing.time"),
+            left / 60,
+            left % 60,
+            locale::text("typing.wpm"),
+            self.typing.wpm(),
+            locale::text("typing.accuracy"),
+            self.typing.accuracy(),
+            locale::text("typing.errors"),
+            self.typing.errors()
+        )
+    }
+
+    // words a minute over the last few tests, as bars, along with the best
+    // and the recent average
+    fn progress(&self) -> String {
+        let Some(best) = self.best_wpm() else {
+            return locale::text("typing.no_tests").to_string();
+        };
+        let shown = &self.history[self.history.len().saturating_sub(SHOWN_TESTS)..];
+        let lowest = shown.iter().map(|test| test.wpm).fold(best, f64::min);
+        let highest = shown.iter().map(|test| test.wpm).fold(0.0, f64::max);
+        let bars = if style::utf8_locale() {
+            BARS
+        } else {
+            ASCII_BARS
+        };
+        let bars: String = shown
+            .iter()
+            .map(|test| {
+                let height = match highest - lowest {
+                    range if range > 0.0 => (test.wpm - lowest) / range,
+                    _ => 1.0,
+                };
+                bars[(height * (bars.len() - 1) as f64).round() as usize]
+            })
+            .collect();
+        let recent = &self.history[self.history.len().saturating_sub(RECENT_TESTS)..];
+        let average = recent.iter().map(|test| test.wpm).sum::<f64>() / recent.len() as f64;
+        format!(
+            "{} {bars}   {} {best:.0}   {} {average:.0}",
+            locale::text("typing.progress"),
+            locale::text("typing.best"),
+            locale::text("typing.recent")
+        )
+    }
+}
+
+// lines of [text] up to [width] columns wide, as ranges of it: broken
+// after the last space that fits (or mid-word, for words too long for a
+// line)
+fn wrap(text: &[char], width: usize) -> Vec<Range<usize>> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + width).min(text.len());
+        if end < text.len() {
+            if let Some(space) = text[start..end].iter().rposition(|&c| c == ' ') {
+                end = start + space + 1;
+            }
+        }
+        lines.push(start..end);
+        start = end;
+    }
+    lines
+}
+
+// Pretty-print
+impl fmt::Display for TypingApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let edge = "─".repeat(self.width);
+        let wall = "│".with(theme::color(Role::Muted));
+        write!(
+            f,
+            "{}\r\n",
+            format!("┌{edge}┐").with(theme::color(Role::Muted))
+        )?;
+        for row in self.text_rows() {
+            write!(f, "{wall}")?;
+            for letter in row {
+                write!(f, "{letter}")?;
+            }
+            write!(f, "{wall}\r\n")?;
+        }
+        write!(
+            f,
+            "{}\r\n",
+            format!("└{edge}┘").with(theme::color(Role::Muted))
+        )?;
+
+        write!(f, "{}\r\n", self.status())?;
+        write!(f, "{}\r\n", self.progress())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", typingui::help_text())
+    }
+}
+
+impl Game for TypingApp {
+    fn name(&self) -> &'static str {
+        "typing"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != TypingUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            TypingUIAction::Quit => self.over = true,
+            TypingUIAction::NewTest => self.new_test(),
+            // (once the time's up, keys still being typed go nowhere)
+            TypingUIAction::Type(action) if !self.typing.is_over() => {
+                self.typing.act(action);
+                if self.typing.has_started() {
+                    self.message = "".to_string().reset();
+                }
+            }
+            TypingUIAction::Type(_) | TypingUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {
+        if self.over || !self.typing.has_started() || self.typing.is_over() {
+            return;
+        }
+        if self.typing.step() {
+            self.finish();
+        }
+        self.redraw.set(true);
+    }
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    fn tick_rate(&self) -> Option<Duration> {
+        Some(TICK)
+    }
+
+    // the text in its box, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let cols = (self.width + 2) as u16;
+        let help = typingui::help_text();
+        (
+            cols.max(text_width(help)),
+            (TEXT_ROWS + 2 + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+// text from the file at [path], or the bundled words or sentences
+fn load_text(args: &Args, config: &TypingConfig) -> Result<Vec<String>, String> {
+    match args.text.as_ref().or(config.text.as_ref()) {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+            texts::parse(&text).map_err(|e| format!("{}: {e}", path.display()))
+        }
+        None if args.sentences || config.sentences => texts::parse(SENTENCES),
+        None => texts::parse(WORDS),
+    }
+}
+
+/// Take typing tests on the bundled words or sentences, or a text given on
+/// the command line
+pub fn run(args: Args) -> Result<(), String> {
+    let config: TypingConfig = common::cli::setup(&args.common, "typing")?;
+
+    let seconds = args.seconds.or(config.seconds).unwrap_or(DEFAULT_SECONDS);
+    if !(MIN_SECONDS..=MAX_SECONDS).contains(&seconds) {
+        return Err(format!(
+            "bad [typing] config: seconds {seconds} (goes from {MIN_SECONDS} to {MAX_SECONDS})"
+        ));
+    }
+    let pieces = load_text(&args, &config)?;
+    // (a stats file that can't be read just means no tests to go by)
+    let history = Stats::typing_tests().unwrap_or_else(|e| {
+        warn!("failed to read the past tests: {e}");
+        Vec::new()
+    });
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("typing seed {seed}, {seconds}s tests");
+    let typing = Typing::new(pieces, seconds, seed);
+    let mut app = TypingApp::new(typing, args.width as usize, history);
+    app.ui
+        .bindings
+        .remap(&config.keys, &typingui::ACTIONS)
+        .map_err(|e| format!("bad [typing.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
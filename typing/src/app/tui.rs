@@ -0,0 +1,26 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::TypingApp;
+use crate::typingui;
+
+impl TuiGame for TypingApp {
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self
+            .text_rows()
+            .iter()
+            .map(|row| row.iter().map(tui::span).collect::<Line>())
+            .collect();
+        let board = Board::new(rows);
+
+        let status = StatusBar::new(typingui::help_text())
+            .line(self.status())
+            .line(self.progress())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        frame.render_widget(board, board_area);
+        frame.render_widget(status, status_area);
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Typing speed trainer
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell when the time's up, on new bests and achievements")
+}))]
+pub struct Args {
+    /// Seconds each test runs for, from 15 to 600 [default: 60, unless the
+    /// config file picks another]
+    #[arg(long, value_parser = clap::value_parser!(u32).range(15..=600))]
+    pub seconds: Option<u32>,
+
+    /// Type whole sentences (with capitals and punctuation) instead of
+    /// single words
+    #[arg(long)]
+    pub sentences: bool,
+
+    /// Text to type instead, one word or sentence to a line, picked at
+    /// random [default: the words or sentences that come with the game]
+    #[arg(long)]
+    pub text: Option<PathBuf>,
+
+    /// Width of the text (in columns)
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u16).range(30..))]
+    pub width: u16,
+
+    /// Random seed for picking the text (picked at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
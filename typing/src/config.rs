@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [typing] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TypingConfig {
+    /// seconds each test runs for
+    pub seconds: Option<u32>,
+    /// type sentences instead of single words
+    pub sentences: bool,
+    /// text to type instead of the bundled words or sentences
+    pub text: Option<PathBuf>,
+    /// keys for each action, e.g. new_test = "enter"
+    pub keys: KeyMap,
+}
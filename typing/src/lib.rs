@@ -0,0 +1,22 @@
+//! Typing speed trainer: the game core (random words or sentences to type
+//! against the clock, scored in words a minute and accuracy), plus the
+//! crossterm frontend for taking tests in a terminal, with every result
+//! kept in the stats to track progress by
+
+mod texts;
+mod typing;
+pub use texts::{parse, SENTENCES, WORDS};
+pub use typing::{Action, Letter, Typing, TICKS_PER_SECOND, WORD_LENGTH};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod typingui;
+#[cfg(feature = "terminal")]
+pub use app::{run, TypingApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,27 @@
+/// Words bundled with the game
+pub const WORDS: &str = include_str!("../texts/words.txt");
+
+/// Sentences bundled with the game
+pub const SENTENCES: &str = include_str!("../texts/sentences.txt");
+
+/// Pieces of text to type (words or sentences), one to a line. Blank lines
+/// and `# comments` are skipped
+pub fn parse(text: &str) -> Result<Vec<String>, String> {
+    let mut pieces = Vec::new();
+    for (k, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // (runs of spaces and tabs typed as one space)
+        let piece = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if piece.chars().any(char::is_control) {
+            return Err(format!("line {}: '{piece}' can't be typed", k + 1));
+        }
+        pieces.push(piece);
+    }
+    if pieces.is_empty() {
+        return Err("no text found".into());
+    }
+    Ok(pieces)
+}
@@ -0,0 +1,244 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+/// Ticks in a second (the clock runs on ticks, so a replay keeps the same
+/// time)
+pub const TICKS_PER_SECOND: u32 = 10;
+
+/// Characters that count as one word, for words per minute
+pub const WORD_LENGTH: f64 = 5.0;
+
+// characters of text kept ahead of the cursor (more is put on the end as
+// it's typed)
+const LOOKAHEAD: usize = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// type a character
+    Type(char),
+    /// take back the last character typed
+    Backspace,
+    /// take back the characters typed since the last space (or the last
+    /// word, right after a space)
+    DeleteWord,
+}
+
+/// A character of the text, and whether it's been typed right
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Letter {
+    Untyped(char),
+    Right(char),
+    /// the character that was wanted, where something else was typed
+    Wrong(char),
+}
+
+/// A typing test: random words or sentences to type, one after another,
+/// against a clock that starts with the first key
+#[derive(Debug, Clone)]
+pub struct Typing {
+    pieces: Vec<String>, // words or sentences, picked from at random
+    text: Vec<char>,
+    typed: Vec<char>,
+    keystrokes: u32,
+    mistakes: u32, // keystrokes that didn't match the text
+    ticks: u32,    // since the first keystroke
+    limit: u32,    // ticks the test runs for
+    rng: StdRng,   // for picking the pieces
+}
+
+impl Typing {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Test of [seconds] on [pieces] picked at random, with spaces between.
+    /// The same seed always picks the same text
+    pub fn new(pieces: Vec<String>, seconds: u32, seed: u64) -> Self {
+        assert!(!pieces.is_empty(), "nothing to type");
+        let mut typing = Self {
+            pieces,
+            text: Vec::new(),
+            typed: Vec::new(),
+            keystrokes: 0,
+            mistakes: 0,
+            ticks: 0,
+            limit: seconds * TICKS_PER_SECOND,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        typing.fill();
+        typing
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Start over on new text, with the clock stopped
+    pub fn restart(&mut self) {
+        self.text.clear();
+        self.typed.clear();
+        self.keystrokes = 0;
+        self.mistakes = 0;
+        self.ticks = 0;
+        self.fill();
+    }
+
+    pub fn act(&mut self, action: Action) {
+        if self.is_over() {
+            return;
+        }
+        match action {
+            Action::Type(c) => {
+                self.keystrokes += 1;
+                if self.text[self.typed.len()] != c {
+                    self.mistakes += 1;
+                }
+                self.typed.push(c);
+                self.fill();
+            }
+            Action::Backspace => {
+                self.typed.pop();
+            }
+            Action::DeleteWord => {
+                while self.typed.last() == Some(&' ') {
+                    self.typed.pop();
+                }
+                while self.typed.last().is_some_and(|&c| c != ' ') {
+                    self.typed.pop();
+                }
+            }
+        }
+    }
+
+    /// Run the clock on a tick, once it's started. Returns whether that
+    /// was the one the time ran out on
+    pub fn step(&mut self) -> bool {
+        if !self.has_started() || self.is_over() {
+            return false;
+        }
+        self.ticks += 1;
+        if self.is_over() {
+            debug!(
+                "test over: {:.0} wpm, {:.0}% accuracy",
+                self.wpm(),
+                self.accuracy()
+            );
+        }
+        self.is_over()
+    }
+
+    /// The character at [index] of the text, and how it was typed
+    pub fn letter(&self, index: usize) -> Letter {
+        let wanted = self.text[index];
+        match self.typed.get(index) {
+            None => Letter::Untyped(wanted),
+            Some(&c) if c == wanted => Letter::Right(wanted),
+            Some(_) => Letter::Wrong(wanted),
+        }
+    }
+
+    pub fn text(&self) -> &[char] {
+        &self.text
+    }
+
+    /// Index of the next character to type
+    pub fn cursor(&self) -> usize {
+        self.typed.len()
+    }
+
+    /// Whether the first key's been pressed (starting the clock)
+    pub fn has_started(&self) -> bool {
+        self.keystrokes > 0
+    }
+
+    /// Whether the time's run out
+    pub fn is_over(&self) -> bool {
+        self.ticks >= self.limit
+    }
+
+    /// Seconds since the first key, so far
+    pub fn elapsed(&self) -> f64 {
+        f64::from(self.ticks) / f64::from(TICKS_PER_SECOND)
+    }
+
+    /// Whole seconds left on the clock (rounded up)
+    pub fn seconds_left(&self) -> u32 {
+        (self.limit - self.ticks).div_ceil(TICKS_PER_SECOND)
+    }
+
+    /// Seconds the test runs for
+    pub fn seconds(&self) -> u32 {
+        self.limit / TICKS_PER_SECOND
+    }
+
+    /// Words a minute so far, counting only the characters that are typed
+    /// right as they stand
+    pub fn wpm(&self) -> f64 {
+        if self.ticks == 0 {
+            return 0.0;
+        }
+        let right = (0..self.typed.len())
+            .filter(|&k| matches!(self.letter(k), Letter::Right(_)))
+            .count();
+        right as f64 / WORD_LENGTH / (self.elapsed() / 60.0)
+    }
+
+    /// Percent of keystrokes that were right (even ones fixed afterwards
+    /// count as wrong)
+    pub fn accuracy(&self) -> f64 {
+        if self.keystrokes == 0 {
+            return 100.0;
+        }
+        f64::from(self.keystrokes - self.mistakes) * 100.0 / f64::from(self.keystrokes)
+    }
+
+    /// Characters typed wrong and not fixed yet
+    pub fn errors(&self) -> usize {
+        (0..self.typed.len())
+            .filter(|&k| matches!(self.letter(k), Letter::Wrong(_)))
+            .count()
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // put pieces on the end until there's enough text ahead of the cursor
+    fn fill(&mut self) {
+        while self.text.len() < self.typed.len() + LOOKAHEAD {
+            let piece = self.pieces.choose(&mut self.rng).expect("there are pieces");
+            if !self.text.is_empty() {
+                self.text.push(' ');
+            }
+            self.text.extend(piece.chars());
+        }
+    }
+}
+
+impl Core for Typing {
+    type Action = Action;
+    type Outcome = ();
+    type Cell = Letter;
+
+    /// The text as one long row (it's up to the frontend to wrap it)
+    fn size(&self) -> (usize, usize) {
+        (1, self.text.len())
+    }
+
+    fn cell(&self, Point(_, j): Point) -> Letter {
+        self.letter(j)
+    }
+
+    fn act(&mut self, action: Action) {
+        Typing::act(self, action)
+    }
+
+    fn tick(&mut self) {
+        self.step();
+    }
+
+    fn is_over(&self) -> bool {
+        Typing::is_over(self)
+    }
+}
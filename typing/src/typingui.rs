@@ -0,0 +1,87 @@
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use common::{locale, Bindings, KeyBinding};
+
+use crate::typing::Action;
+
+/// Help lines under the text, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("typing.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypingUIAction {
+    Wait,
+    /// type a character, or take some back (any key that makes a
+    /// character types it, unless it's bound to something else)
+    Type(Action),
+    /// start over on new text
+    NewTest,
+    Quit,
+}
+
+// default controls (every character is for typing, so these keep off
+// them)
+const BINDINGS: [(KeyBinding, TypingUIAction); 5] = [
+    (
+        KeyBinding::key(KeyCode::Backspace),
+        TypingUIAction::Type(Action::Backspace),
+    ),
+    (
+        KeyBinding::ctrl('w'),
+        TypingUIAction::Type(Action::DeleteWord),
+    ),
+    (KeyBinding::key(KeyCode::Tab), TypingUIAction::NewTest),
+    (KeyBinding::key(KeyCode::Esc), TypingUIAction::Quit),
+    (KeyBinding::ctrl('c'), TypingUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, TypingUIAction); 4] = [
+    ("backspace", TypingUIAction::Type(Action::Backspace)),
+    ("delete_word", TypingUIAction::Type(Action::DeleteWord)),
+    ("new_test", TypingUIAction::NewTest),
+    ("quit", TypingUIAction::Quit),
+];
+
+pub struct TypingUI {
+    pub bindings: Bindings<TypingUIAction>,
+}
+
+impl TypingUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> TypingUIAction {
+        if let Some(action) = self.bindings.action(key_event) {
+            return action;
+        }
+        let mut modifiers = key_event.modifiers;
+        modifiers.remove(KeyModifiers::SHIFT);
+        match key_event.code {
+            KeyCode::Char(c) if modifiers.is_empty() && key_event.kind == KeyEventKind::Press => {
+                TypingUIAction::Type(Action::Type(c))
+            }
+            _ => TypingUIAction::Wait,
+        }
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> TypingUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => TypingUIAction::Wait,
+        }
+    }
+}
@@ -0,0 +1,442 @@
+mod map;
+#[cfg(feature = "ratatui")]
+mod tui;
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
+
+use crossterm::event::Event;
+use crossterm::style::{ContentStyle, StyledContent, Stylize};
+use crossterm::{cursor, terminal, QueueableCommand};
+use log::{debug, info};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use common::bell;
+use common::locale;
+use common::stats::Outcome;
+use common::terminal::text_width;
+use common::theme::{self, Role};
+use common::{Game, TerminalGuard};
+
+use crate::cli::Args;
+use crate::config::WumpusConfig;
+use crate::wumpus::{
+    self, Action, Death, Hazard, State, TurnResult, Wumpus, ARROW_RANGE, ROOMS, TUNNELS,
+};
+use crate::wumpusui::{self, WumpusUI, WumpusUIAction};
+
+// lines of text under the map (room and tunnels, senses, arrows, message)
+const STATUS_ROWS: usize = 4;
+
+/// Hunt the Wumpus app (the hunt, the arrow being aimed, the score over
+/// every hunt so far, and UI state)
+pub struct WumpusApp {
+    game: Wumpus,
+    ui: WumpusUI,
+    show_map: bool,
+    aim: Option<Vec<usize>>, // rooms the arrow's aimed through, while aiming
+    rng: StdRng,             // for new caves
+    wins: u32,
+    losses: u32,
+    message: StyledContent<String>,
+    redraw: Cell<bool>,
+    outcome: Outcome, // the last hunt
+    over: bool,
+}
+
+impl WumpusApp {
+    /// Hunt in caves laid out with [seed], with or without the map
+    pub fn new(show_map: bool, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        Self {
+            game: Wumpus::new(rng.gen()),
+            ui: WumpusUI::new(),
+            show_map,
+            aim: None,
+            rng,
+            wins: 0,
+            losses: 0,
+            message: "".to_string().reset(),
+            redraw: Cell::new(true),
+            outcome: Outcome::Quit,
+            over: false,
+        }
+    }
+
+    pub fn game_loop(&mut self) -> io::Result<()> {
+        #[cfg(not(feature = "ratatui"))]
+        common::play(self)?;
+        #[cfg(feature = "ratatui")]
+        common::tui::play(self)?;
+        Ok(())
+    }
+
+    fn new_game(&mut self) {
+        self.game = Wumpus::new(self.rng.gen());
+        self.aim = None;
+        self.message = "".to_string().reset();
+    }
+
+    // go down tunnel [k], or aim the arrow down it
+    fn tunnel(&mut self, k: usize) {
+        if self.game.state() != State::Hunting {
+            bell::ring("invalid move");
+            return;
+        }
+        let Some(path) = &mut self.aim else {
+            let room = TUNNELS[self.game.player()][k];
+            self.take_turn(Action::Move(room));
+            return;
+        };
+        let from = path.last().copied().unwrap_or(self.game.player());
+        path.push(TUNNELS[from][k]);
+        if !self.game.is_path(path) {
+            path.pop();
+            bell::ring("invalid move");
+            self.message = locale::text("wumpus.too_crooked")
+                .to_string()
+                .with(theme::color(Role::Danger));
+            return;
+        }
+        self.message = "".to_string().reset();
+        if path.len() == ARROW_RANGE {
+            self.shoot();
+        }
+    }
+
+    fn toggle_aim(&mut self) {
+        if self.game.state() != State::Hunting {
+            bell::ring("invalid move");
+            return;
+        }
+        self.aim = match self.aim {
+            Some(_) => None,
+            None => Some(Vec::new()),
+        };
+        self.message = "".to_string().reset();
+    }
+
+    fn shoot(&mut self) {
+        match self.aim.take() {
+            Some(path) if !path.is_empty() => self.take_turn(Action::Shoot(path)),
+            aim => {
+                self.aim = aim;
+                bell::ring("invalid move");
+            }
+        }
+    }
+
+    fn take_turn(&mut self, action: Action) {
+        debug!("{action:?}");
+        let res = self.game.take_turn(action);
+        debug!("{res:?}");
+        self.handle_res(res);
+    }
+
+    fn handle_res(&mut self, res: TurnResult) {
+        let again = locale::text("wumpus.again");
+        match res {
+            TurnResult::Went(events) => {
+                self.message = narrate(&events).with(theme::color(Role::Accent));
+            }
+            TurnResult::Won(events) => {
+                bell::ring("win");
+                self.wins += 1;
+                self.outcome = Outcome::Won;
+                self.message = format!(
+                    "{}{} {again}",
+                    narrate_before(&events),
+                    locale::text("wumpus.win")
+                )
+                .bold()
+                .with(theme::color(Role::Text))
+                .on(theme::color(Role::Success));
+            }
+            TurnResult::Lost(events, death) => {
+                bell::ring("lose");
+                self.losses += 1;
+                self.outcome = Outcome::Lost;
+                let death = match death {
+                    Death::Eaten => locale::text("wumpus.eaten"),
+                    Death::Fell => locale::text("wumpus.fell"),
+                    Death::Shot => locale::text("wumpus.shot"),
+                    Death::OutOfArrows => locale::text("wumpus.out_of_arrows"),
+                };
+                self.message = format!("{}{death} {again}", narrate_before(&events))
+                    .bold()
+                    .with(theme::color(Role::Text))
+                    .on(theme::color(Role::Danger));
+            }
+            TurnResult::Invalid | TurnResult::GameOver => bell::ring("invalid move"),
+        }
+    }
+
+    /// Lines of the map: rooms been in, and the ones their tunnels lead to
+    /// (all of them, and what was in them, once the hunt's over)
+    fn map_lines(&self) -> Vec<Vec<StyledContent<String>>> {
+        let over = self.game.state() != State::Hunting;
+        let path = self.aim.as_deref().unwrap_or_default();
+        let rooms: Vec<Option<ContentStyle>> = (0..ROOMS)
+            .map(|room| {
+                let style = ContentStyle::new();
+                let hazard = self.game.hazard(room).filter(|_| over);
+                if room == self.game.player() {
+                    Some(style.bold().with(theme::color(Role::Accent)).reverse())
+                } else if path.contains(&room) {
+                    Some(style.bold().with(theme::color(Role::Danger)))
+                } else if let Some(hazard) = hazard {
+                    let role = match hazard {
+                        Hazard::Wumpus => Role::Danger,
+                        Hazard::Pit => Role::Hidden,
+                        Hazard::Bats => Role::Marker,
+                    };
+                    Some(style.bold().with(theme::color(role)).reverse())
+                } else if self.game.is_visited(room) {
+                    Some(style.with(theme::color(Role::Text)))
+                } else if over || self.is_next_to_visited(room) {
+                    Some(style.with(theme::color(Role::Muted)))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let tunnels: Vec<(usize, usize)> = (0..ROOMS)
+            .filter(|&room| over || self.game.is_visited(room))
+            .flat_map(|a| TUNNELS[a].iter().map(move |&b| (a, b)))
+            .filter(|&(a, b)| a < b || !(over || self.game.is_visited(b)))
+            .collect();
+        map::lines(&rooms, &tunnels)
+    }
+
+    fn is_next_to_visited(&self, room: usize) -> bool {
+        TUNNELS[room].iter().any(|&next| self.game.is_visited(next))
+    }
+
+    /// Room the player's in, and the keys for the tunnels out of it (or,
+    /// while aiming, the arrow's path so far, and the tunnels out of the
+    /// last room on it)
+    fn room_line(&self) -> Vec<StyledContent<String>> {
+        let player = self.game.player();
+        let in_room = format!("{} {}.", locale::text("wumpus.in_room"), player + 1);
+        if self.game.state() != State::Hunting {
+            return vec![in_room.reset()];
+        }
+        let (mut line, from) = match &self.aim {
+            None => (
+                vec![format!("{in_room} {} ", locale::text("wumpus.tunnels")).reset()],
+                player,
+            ),
+            Some(path) => {
+                let mut line = vec![format!("{} ", locale::text("wumpus.arrow")).reset()];
+                if !path.is_empty() {
+                    let rooms: Vec<String> =
+                        path.iter().map(|room| (room + 1).to_string()).collect();
+                    line.push(
+                        format!("{} ", rooms.join(" → "))
+                            .bold()
+                            .with(theme::color(Role::Danger)),
+                    );
+                }
+                line.push(format!("{} ", locale::text("wumpus.through")).reset());
+                (line, path.last().copied().unwrap_or(player))
+            }
+        };
+        for (k, &room) in TUNNELS[from].iter().enumerate() {
+            let key = format!("<{}>", k + 1);
+            let doubling_back = self.aim.as_ref().is_some_and(|path| {
+                let mut path = path.clone();
+                path.push(room);
+                !self.game.is_path(&path)
+            });
+            let piece = format!("{key} {}  ", room + 1);
+            line.push(match doubling_back {
+                true => piece.with(theme::color(Role::Muted)),
+                false => piece.with(theme::color(Role::Text)),
+            });
+        }
+        line
+    }
+
+    // what can be sensed from next door
+    fn senses_line(&self) -> StyledContent<String> {
+        if self.game.state() != State::Hunting {
+            return self.reveal_line().reset();
+        }
+        let senses: Vec<&str> = self
+            .game
+            .senses()
+            .into_iter()
+            .map(|hazard| match hazard {
+                Hazard::Wumpus => locale::text("wumpus.smell"),
+                Hazard::Pit => locale::text("wumpus.draft"),
+                Hazard::Bats => locale::text("wumpus.bats"),
+            })
+            .collect();
+        senses.join(" ").bold().with(theme::color(Role::Marker))
+    }
+
+    // where everything was, once the hunt's over
+    fn reveal_line(&self) -> String {
+        let rooms = |rooms: &[usize]| {
+            rooms
+                .iter()
+                .map(|room| (room + 1).to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!(
+            "{} {}   {} {}   {} {}",
+            locale::text("wumpus.wumpus_was"),
+            self.game.wumpus() + 1,
+            locale::text("wumpus.pits_were"),
+            rooms(self.game.pits()),
+            locale::text("wumpus.bats_were"),
+            rooms(self.game.bats())
+        )
+    }
+
+    // arrows left, and hunts won and lost
+    fn score_line(&self) -> String {
+        format!(
+            "{} {}   {} {}   {} {}",
+            locale::text("wumpus.arrows"),
+            self.game.arrows(),
+            locale::text("wumpus.won"),
+            self.wins,
+            locale::text("wumpus.lost"),
+            self.losses
+        )
+    }
+}
+
+// what happened during a turn, a sentence each
+fn narrate(events: &[wumpus::Event]) -> String {
+    events
+        .iter()
+        .map(|&event| match event {
+            wumpus::Event::Bumped => locale::text("wumpus.bumped").to_string(),
+            wumpus::Event::Snatched(room) => {
+                format!("{} {}!", locale::text("wumpus.snatched"), room + 1)
+            }
+            wumpus::Event::Strayed(room) => {
+                format!("{} {}.", locale::text("wumpus.strayed"), room + 1)
+            }
+            wumpus::Event::Missed => locale::text("wumpus.missed").to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// what happened during a turn, before the end of the hunt
+fn narrate_before(events: &[wumpus::Event]) -> String {
+    match events.is_empty() {
+        true => "".to_string(),
+        false => format!("{} ", narrate(events)),
+    }
+}
+
+// Pretty-print
+impl fmt::Display for WumpusApp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.show_map {
+            for line in self.map_lines() {
+                for piece in line {
+                    write!(f, "{piece}")?;
+                }
+                write!(f, "\r\n")?;
+            }
+            write!(f, "\r\n")?;
+        }
+        for piece in self.room_line() {
+            write!(f, "{piece}")?;
+        }
+        write!(f, "\r\n{}\r\n", self.senses_line())?;
+        write!(f, "{}\r\n", self.score_line())?;
+        write!(f, "{}\r\n", self.message)?;
+        write!(f, "{}", wumpusui::help_text())
+    }
+}
+
+impl Game for WumpusApp {
+    fn name(&self) -> &'static str {
+        "wumpus"
+    }
+
+    fn handle_input(&mut self, event: Event) {
+        let user_action = self.ui.match_event_to_action(event);
+        if user_action != WumpusUIAction::Wait {
+            debug!("{user_action:?}");
+        }
+        match user_action {
+            WumpusUIAction::Quit => self.over = true,
+            WumpusUIAction::NewGame => self.new_game(),
+            WumpusUIAction::Tunnel(k) => self.tunnel(k),
+            WumpusUIAction::Aim => self.toggle_aim(),
+            WumpusUIAction::Shoot => self.shoot(),
+            WumpusUIAction::Back => {
+                if self.aim.as_mut().and_then(|path| path.pop()).is_none() {
+                    bell::ring("invalid move");
+                }
+            }
+            WumpusUIAction::Wait => {}
+        }
+        self.redraw.set(true);
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&self, out: &mut dyn Write) -> io::Result<()> {
+        if !self.redraw.replace(false) {
+            return Ok(());
+        }
+        out.queue(cursor::MoveTo(0, 0))?
+            .queue(terminal::Clear(terminal::ClearType::All))?;
+        write!(out, "{self}")
+    }
+
+    fn invalidate(&self) {
+        self.redraw.set(true);
+    }
+
+    // the map, and the help text on its lines
+    fn min_size(&self) -> (u16, u16) {
+        let help = wumpusui::help_text();
+        let cols = help.lines().map(text_width).max().unwrap_or_default();
+        let map_rows = match self.show_map {
+            true => map::ROWS + 1, // (and a gap)
+            false => 0,
+        };
+        (
+            cols.max(map::COLS as u16),
+            (map_rows + STATUS_ROWS + help.lines().count()) as u16,
+        )
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn outcome(&self) -> Outcome {
+        self.outcome
+    }
+}
+
+/// Hunt the Wumpus, with a map of the cave as it's explored
+pub fn run(args: Args) -> Result<(), String> {
+    let config: WumpusConfig = common::cli::setup(&args.common, "wumpus")?;
+
+    let show_map = !(args.no_map || config.no_map);
+    let seed = args.seed.unwrap_or_else(rand::random);
+    info!("wumpus seed {seed}");
+
+    let mut app = WumpusApp::new(show_map, seed);
+    app.ui
+        .bindings
+        .remap(&config.keys, &wumpusui::ACTIONS)
+        .map_err(|e| format!("bad [wumpus.keys] config: {e}"))?;
+
+    let _guard = TerminalGuard::new().map_err(|e| format!("failed to set up terminal: {e}"))?;
+    app.game_loop().map_err(|e| e.to_string())?;
+    Ok(())
+}
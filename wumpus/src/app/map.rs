@@ -0,0 +1,88 @@
+use crossterm::style::{ContentStyle, StyledContent, Stylize};
+
+use common::theme::{self, Role};
+
+use crate::wumpus::ROOMS;
+
+/// Lines the map takes up
+pub const ROWS: usize = 14;
+
+/// Columns the map takes up
+pub const COLS: usize = 39;
+
+// where each room's number goes on the map (the line, and the column of
+// its first digit): the dodecahedron flattened out, with rooms 1 to 5 round
+// the outside and 16 to 20 in the middle, the way it's usually drawn
+const POSITIONS: [(usize, usize); ROOMS] = [
+    (0, 19),
+    (5, 37),
+    (13, 30),
+    (13, 8),
+    (5, 1),
+    (6, 7),
+    (4, 13),
+    (2, 19),
+    (4, 25),
+    (6, 31),
+    (8, 29),
+    (11, 26),
+    (11, 19),
+    (11, 12),
+    (8, 9),
+    (8, 14),
+    (5, 16),
+    (5, 22),
+    (8, 24),
+    (9, 19),
+];
+
+/// Lines of the map, each a list of (styled) pieces: the number of each
+/// room with a style (the rest left off), and the [tunnels] between rooms
+/// as dotted lines
+pub fn lines(
+    rooms: &[Option<ContentStyle>],
+    tunnels: &[(usize, usize)],
+) -> Vec<Vec<StyledContent<String>>> {
+    let mut grid = vec![vec![(' ', ContentStyle::new()); COLS]; ROWS];
+
+    let dots = ContentStyle::new().with(theme::color(Role::Muted));
+    for &(a, b) in tunnels {
+        // (from the middle of one number to the middle of the other)
+        let (i0, j0) = (POSITIONS[a].0 as f64, POSITIONS[a].1 as f64 + 0.5);
+        let (i1, j1) = (POSITIONS[b].0 as f64, POSITIONS[b].1 as f64 + 0.5);
+        let steps = (i1 - i0).abs().max((j1 - j0).abs()) as usize;
+        for t in 1..steps {
+            let t = t as f64 / steps as f64;
+            let i = (i0 + (i1 - i0) * t).round() as usize;
+            let j = (j0 + (j1 - j0) * t).floor() as usize;
+            grid[i][j] = ('·', dots);
+        }
+    }
+
+    for (room, style) in rooms.iter().enumerate() {
+        let Some(style) = style else {
+            continue;
+        };
+        let (i, j) = POSITIONS[room];
+        for (k, c) in format!("{:>2}", room + 1).chars().enumerate() {
+            grid[i][j + k] = (c, *style);
+        }
+    }
+
+    // runs of the same style, as one piece each
+    grid.into_iter()
+        .map(|row| {
+            let mut pieces: Vec<(ContentStyle, String)> = Vec::new();
+            for (c, style) in row {
+                match pieces.last_mut() {
+                    Some((last, text)) if *last == style => text.push(c),
+                    _ => pieces.push((style, c.to_string())),
+                }
+            }
+            pieces
+                .into_iter()
+                .map(|(style, text)| StyledContent::new(style, text))
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,28 @@
+use ratatui::text::Line;
+use ratatui::Frame;
+
+use common::tui::{self, Board, StatusBar, TuiGame};
+
+use super::WumpusApp;
+use crate::wumpusui;
+
+impl TuiGame for WumpusApp {
+    fn draw(&self, frame: &mut Frame) {
+        let status = StatusBar::new(wumpusui::help_text())
+            .line(self.room_line().iter().map(tui::span).collect::<Line>())
+            .line(tui::span(&self.senses_line()))
+            .line(self.score_line())
+            .line(tui::span(&self.message));
+        let (board_area, status_area) = tui::split(frame.size(), status.height());
+        if self.show_map {
+            let board = Board::new(
+                self.map_lines()
+                    .iter()
+                    .map(|line| line.iter().map(tui::span).collect::<Line>())
+                    .collect(),
+            );
+            frame.render_widget(board, board_area);
+        }
+        frame.render_widget(status, status_area);
+    }
+}
@@ -0,0 +1,31 @@
+use clap::Parser;
+use common::cli::{CommonArgs, GameArgs};
+
+/// Hunt the Wumpus
+#[derive(Parser)]
+#[command(mut_arg("bell", |arg| {
+    arg.help("Ring the terminal bell on blocked tunnels, winning, losing and achievements")
+}))]
+pub struct Args {
+    /// Hunt without the map, going by the room numbers alone (as in 1973)
+    #[arg(long)]
+    pub no_map: bool,
+
+    /// Random seed for the cave's hazards, the bats and the Wumpus (picked
+    /// at random if not given)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    #[command(flatten)]
+    pub common: CommonArgs,
+}
+
+impl GameArgs for Args {
+    fn common(&mut self) -> &mut CommonArgs {
+        &mut self.common
+    }
+
+    fn seed(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.seed)
+    }
+}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use common::config::KeyMap;
+
+/// Settings from the [wumpus] section of the config file
+/// (flags given on the command line take priority)
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WumpusConfig {
+    /// hunt without the map, going by the room numbers alone
+    pub no_map: bool,
+    /// keys for each action, e.g. aim = "a"
+    pub keys: KeyMap,
+}
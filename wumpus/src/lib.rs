@@ -0,0 +1,22 @@
+//! Hunt the Wumpus: the game core (a dodecahedral cave of 20 rooms, with
+//! the Wumpus, bottomless pits and super bats in some of them, and crooked
+//! arrows to hunt with), plus the crossterm frontend for playing it in a
+//! terminal, with a map of the rooms visited so far
+
+mod wumpus;
+pub use wumpus::{
+    Action, Death, Event, Hazard, State, TurnResult, Wumpus, ARROWS, ARROW_RANGE, ROOMS, TUNNELS,
+};
+
+#[cfg(feature = "terminal")]
+mod app;
+#[cfg(feature = "terminal")]
+mod cli;
+#[cfg(feature = "terminal")]
+mod config;
+#[cfg(feature = "terminal")]
+mod wumpusui;
+#[cfg(feature = "terminal")]
+pub use app::{run, WumpusApp};
+#[cfg(feature = "terminal")]
+pub use cli::Args;
@@ -0,0 +1,348 @@
+use common::{Core, Point};
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// Rooms in the cave
+pub const ROOMS: usize = 20;
+
+/// Tunnels out of each room (numbered from 0 here, from 1 to the player):
+/// the corners of a dodecahedron, numbered the way the 1973 original did
+pub const TUNNELS: [[usize; 3]; ROOMS] = [
+    [1, 4, 7],
+    [0, 2, 9],
+    [1, 3, 11],
+    [2, 4, 13],
+    [0, 3, 5],
+    [4, 6, 14],
+    [5, 7, 16],
+    [0, 6, 8],
+    [7, 9, 17],
+    [1, 8, 10],
+    [9, 11, 18],
+    [2, 10, 12],
+    [11, 13, 19],
+    [3, 12, 14],
+    [5, 13, 15],
+    [14, 16, 19],
+    [6, 15, 17],
+    [8, 16, 18],
+    [10, 17, 19],
+    [12, 15, 18],
+];
+
+/// Arrows to start with
+pub const ARROWS: u8 = 5;
+
+/// Most rooms an arrow can fly through
+pub const ARROW_RANGE: usize = 5;
+
+// bottomless pits and super bats in the cave
+const PITS: usize = 2;
+const BATS: usize = 2;
+
+// chance of the Wumpus moving to a room next to its own when woken
+const WUMPUS_MOVES: f64 = 0.75;
+
+/// Something in a room, sensed from next door
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hazard {
+    /// eats whoever's in its room (smelt)
+    Wumpus,
+    /// bottomless (felt as a draft)
+    Pit,
+    /// super bats, carrying whoever comes in off somewhere else (heard)
+    Bats,
+}
+
+/// A turn: walking down a tunnel, or shooting a crooked arrow through up
+/// to [ARROW_RANGE] rooms
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Move(usize),
+    Shoot(Vec<usize>),
+}
+
+/// How a hunt ended badly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Death {
+    Eaten,
+    Fell,
+    /// shot by your own arrow
+    Shot,
+    OutOfArrows,
+}
+
+/// Something that happened during a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// walked into the Wumpus's room and woke it, and it went off
+    Bumped,
+    /// carried off by super bats, to this room
+    Snatched(usize),
+    /// an arrow went down a tunnel other than the one aimed for (there
+    /// wasn't one), into this room
+    Strayed(usize),
+    /// the arrow flew through every room aimed at without hitting
+    /// anything, and woke the Wumpus
+    Missed,
+}
+
+/// What a turn led to
+#[derive(Debug, Clone, PartialEq)]
+pub enum TurnResult {
+    /// the hunt goes on, after these
+    Went(Vec<Event>),
+    /// the Wumpus was shot, after these
+    Won(Vec<Event>),
+    Lost(Vec<Event>, Death),
+    /// there's no tunnel there, or the arrow's path doubles straight back
+    Invalid,
+    /// the hunt's already over
+    GameOver,
+}
+
+/// How the hunt stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Hunting,
+    Won,
+    Lost(Death),
+}
+
+/// Hunt the Wumpus: a cave of [ROOMS] rooms, three tunnels out of each,
+/// with the Wumpus, pits and bats in some of them, hunted with [ARROWS]
+/// crooked arrows
+#[derive(Debug, Clone)]
+pub struct Wumpus {
+    player: usize,
+    wumpus: usize,
+    pits: Vec<usize>,
+    bats: Vec<usize>,
+    arrows: u8,
+    visited: Vec<bool>,
+    state: State,
+    rng: StdRng, // for bats, stray arrows and the Wumpus waking
+}
+
+impl Wumpus {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    /// Cave with the player, the Wumpus, pits and bats all in different
+    /// rooms, picked at random (the same seed always gives the same hunt)
+    pub fn new(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut rooms: Vec<usize> = (0..ROOMS).collect();
+        rooms.shuffle(&mut rng);
+        let player = rooms[0];
+        let wumpus = rooms[1];
+        let pits = rooms[2..2 + PITS].to_vec();
+        let bats = rooms[2 + PITS..2 + PITS + BATS].to_vec();
+        debug!("player {player}, wumpus {wumpus}, pits {pits:?}, bats {bats:?}");
+        let mut visited = vec![false; ROOMS];
+        visited[player] = true;
+        Self {
+            player,
+            wumpus,
+            pits,
+            bats,
+            arrows: ARROWS,
+            visited,
+            state: State::Hunting,
+            rng,
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    /// Take a turn: move, or shoot
+    pub fn take_turn(&mut self, action: Action) -> TurnResult {
+        if self.state != State::Hunting {
+            return TurnResult::GameOver;
+        }
+        let mut events = Vec::new();
+        match action {
+            Action::Move(room) => {
+                if !TUNNELS[self.player].contains(&room) {
+                    return TurnResult::Invalid;
+                }
+                self.enter(room, &mut events);
+            }
+            Action::Shoot(path) => {
+                if !self.is_path(&path) {
+                    return TurnResult::Invalid;
+                }
+                self.shoot(&path, &mut events);
+            }
+        }
+        match self.state {
+            State::Hunting => TurnResult::Went(events),
+            State::Won => TurnResult::Won(events),
+            State::Lost(death) => TurnResult::Lost(events, death),
+        }
+    }
+
+    /// Whether an arrow could be aimed along [path]: between one and
+    /// [ARROW_RANGE] rooms, never doubling straight back on itself
+    pub fn is_path(&self, path: &[usize]) -> bool {
+        let mut rooms = vec![self.player];
+        rooms.extend(path);
+        !path.is_empty()
+            && path.len() <= ARROW_RANGE
+            && path.iter().all(|&room| room < ROOMS)
+            && rooms.windows(3).all(|w| w[0] != w[2])
+    }
+
+    /// Hazards in the rooms next to the player's, each once
+    pub fn senses(&self) -> Vec<Hazard> {
+        let near = TUNNELS[self.player];
+        let mut senses = Vec::new();
+        if near.contains(&self.wumpus) {
+            senses.push(Hazard::Wumpus);
+        }
+        if self.pits.iter().any(|pit| near.contains(pit)) {
+            senses.push(Hazard::Pit);
+        }
+        if self.bats.iter().any(|bats| near.contains(bats)) {
+            senses.push(Hazard::Bats);
+        }
+        senses
+    }
+
+    /// What's in [room], if anything (the Wumpus first, if it's in with
+    /// something else)
+    pub fn hazard(&self, room: usize) -> Option<Hazard> {
+        if room == self.wumpus {
+            Some(Hazard::Wumpus)
+        } else if self.pits.contains(&room) {
+            Some(Hazard::Pit)
+        } else if self.bats.contains(&room) {
+            Some(Hazard::Bats)
+        } else {
+            None
+        }
+    }
+
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    pub fn wumpus(&self) -> usize {
+        self.wumpus
+    }
+
+    pub fn pits(&self) -> &[usize] {
+        &self.pits
+    }
+
+    pub fn bats(&self) -> &[usize] {
+        &self.bats
+    }
+
+    /// Arrows left
+    pub fn arrows(&self) -> u8 {
+        self.arrows
+    }
+
+    /// Whether the player has been in [room]
+    pub fn is_visited(&self, room: usize) -> bool {
+        self.visited[room]
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    //////////////
+    // Privates //
+    //////////////
+
+    // walk (or be dropped) into [room], and meet whatever's there
+    fn enter(&mut self, room: usize, events: &mut Vec<Event>) {
+        self.player = room;
+        self.visited[room] = true;
+        if room == self.wumpus {
+            self.wake_wumpus();
+            if self.state != State::Hunting {
+                return;
+            }
+            events.push(Event::Bumped);
+        }
+        if self.pits.contains(&room) {
+            self.state = State::Lost(Death::Fell);
+        } else if self.bats.contains(&room) {
+            let to = self.rng.gen_range(0..ROOMS);
+            debug!("bats carry the player from {room} to {to}");
+            events.push(Event::Snatched(to));
+            self.enter(to, events);
+        }
+    }
+
+    // let an arrow fly along [path], turning down another tunnel at random
+    // where there isn't one to the next room
+    fn shoot(&mut self, path: &[usize], events: &mut Vec<Event>) {
+        self.arrows -= 1;
+        let mut at = self.player;
+        for &aim in path {
+            let room = match TUNNELS[at].contains(&aim) {
+                true => aim,
+                false => {
+                    let stray = TUNNELS[at][self.rng.gen_range(0..3)];
+                    events.push(Event::Strayed(stray));
+                    stray
+                }
+            };
+            if room == self.wumpus {
+                self.state = State::Won;
+                return;
+            }
+            if room == self.player {
+                self.state = State::Lost(Death::Shot);
+                return;
+            }
+            at = room;
+        }
+        events.push(Event::Missed);
+        self.wake_wumpus();
+        if self.state == State::Hunting && self.arrows == 0 {
+            self.state = State::Lost(Death::OutOfArrows);
+        }
+    }
+
+    // the Wumpus wakes up, and most of the time moves to a room next to its
+    // own. It eats the player if it ends up in theirs
+    fn wake_wumpus(&mut self) {
+        if self.rng.gen_bool(WUMPUS_MOVES) {
+            self.wumpus = TUNNELS[self.wumpus][self.rng.gen_range(0..3)];
+        }
+        if self.wumpus == self.player {
+            self.state = State::Lost(Death::Eaten);
+        }
+    }
+}
+
+impl Core for Wumpus {
+    type Action = Action;
+    type Outcome = TurnResult;
+    /// What's in the room (rooms are in a line, numbered from 0)
+    type Cell = Option<Hazard>;
+
+    fn size(&self) -> (usize, usize) {
+        (1, ROOMS)
+    }
+
+    fn cell(&self, Point(_, room): Point) -> Option<Hazard> {
+        self.hazard(room)
+    }
+
+    fn act(&mut self, action: Action) -> TurnResult {
+        self.take_turn(action)
+    }
+
+    fn is_over(&self) -> bool {
+        self.state != State::Hunting
+    }
+}
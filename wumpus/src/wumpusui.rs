@@ -0,0 +1,85 @@
+use crossterm::event::{Event, KeyCode, KeyEvent};
+
+use common::{locale, Bindings, KeyBinding};
+
+/// Help lines under the map, in the current language
+pub fn help_text() -> &'static str {
+    locale::text("wumpus.help")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WumpusUIAction {
+    Wait,
+    /// go down tunnel k (counting from 0, in order of the rooms they lead
+    /// to), or aim the arrow down it while aiming
+    Tunnel(usize),
+    /// start or stop aiming an arrow
+    Aim,
+    /// let the arrow go
+    Shoot,
+    /// take the last room off the arrow's path
+    Back,
+    NewGame,
+    Quit,
+}
+
+// default controls
+const BINDINGS: [(KeyBinding, WumpusUIAction); 11] = [
+    (KeyBinding::char('1'), WumpusUIAction::Tunnel(0)),
+    (KeyBinding::char('2'), WumpusUIAction::Tunnel(1)),
+    (KeyBinding::char('3'), WumpusUIAction::Tunnel(2)),
+    (KeyBinding::char('s'), WumpusUIAction::Aim),
+    (KeyBinding::key(KeyCode::Esc), WumpusUIAction::Aim),
+    (KeyBinding::key(KeyCode::Enter), WumpusUIAction::Shoot),
+    (KeyBinding::char(' '), WumpusUIAction::Shoot),
+    (KeyBinding::key(KeyCode::Backspace), WumpusUIAction::Back),
+    (KeyBinding::char('n'), WumpusUIAction::NewGame),
+    (KeyBinding::char('q'), WumpusUIAction::Quit),
+    (KeyBinding::ctrl('c'), WumpusUIAction::Quit),
+];
+
+// names of the actions that can be remapped in the config file
+pub const ACTIONS: [(&str, WumpusUIAction); 8] = [
+    ("tunnel_1", WumpusUIAction::Tunnel(0)),
+    ("tunnel_2", WumpusUIAction::Tunnel(1)),
+    ("tunnel_3", WumpusUIAction::Tunnel(2)),
+    ("aim", WumpusUIAction::Aim),
+    ("shoot", WumpusUIAction::Shoot),
+    ("back", WumpusUIAction::Back),
+    ("new_game", WumpusUIAction::NewGame),
+    ("quit", WumpusUIAction::Quit),
+];
+
+pub struct WumpusUI {
+    pub bindings: Bindings<WumpusUIAction>,
+}
+
+impl WumpusUI {
+    //////////////////
+    // Constructors //
+    //////////////////
+
+    pub fn new() -> Self {
+        Self {
+            bindings: Bindings::new(&BINDINGS),
+        }
+    }
+
+    /////////////
+    // Publics //
+    /////////////
+
+    pub fn match_key_to_action(&self, key_event: KeyEvent) -> WumpusUIAction {
+        self.bindings
+            .action(key_event)
+            .unwrap_or(WumpusUIAction::Wait)
+    }
+
+    // only keypresses do anything
+    pub fn match_event_to_action(&self, event: Event) -> WumpusUIAction {
+        match event {
+            Event::Key(key_event) => self.match_key_to_action(key_event),
+            _ => WumpusUIAction::Wait,
+        }
+    }
+}